@@ -1615,6 +1615,9 @@ export function DeploymentDetail({ projectName, deploymentId }) {
                             : '-'}
                     </strong>
                 </div>
+                {deployment.message && (
+                    <div><span>message</span><strong>{deployment.message}</strong></div>
+                )}
                 {(deployment.job_url || deployment.pull_request_url) && (
                     <div>
                         <span>source</span>
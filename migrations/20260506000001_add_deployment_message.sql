@@ -0,0 +1,3 @@
+-- Optional human-readable reason for a deployment (e.g., "fix login bug" or the CI commit title)
+ALTER TABLE deployments
+  ADD COLUMN message TEXT;
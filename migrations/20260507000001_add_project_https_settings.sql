@@ -0,0 +1,6 @@
+-- Per-project HTTPS settings for the project's ingresses
+-- force_https: NULL = redirect to HTTPS only when TLS is configured
+-- hsts_max_age: NULL = don't send Strict-Transport-Security
+ALTER TABLE projects
+  ADD COLUMN force_https BOOLEAN,
+  ADD COLUMN hsts_max_age INTEGER CHECK (hsts_max_age > 0 AND hsts_max_age <= 63072000);
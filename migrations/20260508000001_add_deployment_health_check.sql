@@ -0,0 +1,3 @@
+-- Health probe overrides from rise.toml [deploy.health_check], resolved at deployment creation time
+ALTER TABLE deployments
+  ADD COLUMN health_check JSONB;
@@ -0,0 +1,4 @@
+-- Optional CPU/memory limits; NULL means the limit equals the request (cpu/memory)
+ALTER TABLE deployments
+  ADD COLUMN cpu_limit TEXT,
+  ADD COLUMN memory_limit TEXT;
@@ -0,0 +1,3 @@
+-- Set by `rise deployment restart`; changing it rolls the deployment's pods
+ALTER TABLE deployments
+  ADD COLUMN restarted_at TIMESTAMPTZ;
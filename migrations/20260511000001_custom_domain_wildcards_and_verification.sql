@@ -0,0 +1,13 @@
+-- Allow wildcard custom domains (e.g. *.example.com) and track DNS verification
+ALTER TABLE project_custom_domains
+    DROP CONSTRAINT project_custom_domains_domain_check;
+
+ALTER TABLE project_custom_domains
+    ADD CONSTRAINT project_custom_domains_domain_check CHECK (
+        domain ~ '^(\*\.)?[a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?(\.[a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?)*$'
+        AND length(domain) <= 253
+    );
+
+-- Whether the domain resolved to the project's ingress at the last check
+ALTER TABLE project_custom_domains
+    ADD COLUMN verified BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,3 @@
+-- Init container from rise.toml [deploy.init], resolved at deployment creation time
+ALTER TABLE deployments
+  ADD COLUMN init_container JSONB;
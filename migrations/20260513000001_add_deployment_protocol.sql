@@ -0,0 +1,4 @@
+-- Protocol the app speaks, from rise.toml [deploy] protocol. TCP deployments get no Ingress.
+ALTER TABLE deployments
+  ADD COLUMN protocol TEXT NOT NULL DEFAULT 'http'
+  CHECK (protocol IN ('http', 'tcp'));
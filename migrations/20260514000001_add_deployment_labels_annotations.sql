@@ -0,0 +1,4 @@
+-- Custom workload labels/annotations, from rise.toml [deploy.labels] and [deploy.annotations]
+ALTER TABLE deployments
+  ADD COLUMN labels JSONB,
+  ADD COLUMN annotations JSONB;
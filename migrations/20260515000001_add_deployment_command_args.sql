@@ -0,0 +1,4 @@
+-- Container command/args overrides, from rise.toml [deploy] command/args or --command/--args
+ALTER TABLE deployments
+  ADD COLUMN command TEXT[],
+  ADD COLUMN args TEXT[];
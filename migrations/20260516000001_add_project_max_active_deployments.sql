@@ -0,0 +1,4 @@
+-- Per-project quota of concurrently active (Deploying/Healthy/Unhealthy) deployments
+-- NULL = use the server-wide default (server.max_active_deployments_per_project)
+ALTER TABLE projects
+  ADD COLUMN max_active_deployments INTEGER CHECK (max_active_deployments > 0);
@@ -0,0 +1,3 @@
+-- Per-project ingress annotations, from rise.toml [deploy.ingress.annotations]
+ALTER TABLE deployments
+  ADD COLUMN ingress_annotations JSONB;
@@ -0,0 +1,6 @@
+-- Deployment strategy, from rise.toml [deploy] strategy. Blue-green deployments keep the
+-- previous deployment running for drain_period_seconds after the traffic switch.
+ALTER TABLE deployments
+  ADD COLUMN strategy TEXT NOT NULL DEFAULT 'rolling'
+  CHECK (strategy IN ('rolling', 'blue-green')),
+  ADD COLUMN drain_period_seconds INTEGER CHECK (drain_period_seconds > 0);
@@ -0,0 +1,13 @@
+-- Lifecycle timeline of deployments (audit trail): creation and every status change,
+-- with the user or service account that triggered it, if any
+CREATE TABLE deployment_events (
+    id BIGSERIAL PRIMARY KEY,
+    deployment_id UUID NOT NULL REFERENCES deployments(id) ON DELETE CASCADE,
+    event_type TEXT NOT NULL CHECK (event_type IN ('created', 'status_changed')),
+    status TEXT NOT NULL,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    message TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_deployment_events_deployment ON deployment_events(deployment_id, id);
@@ -0,0 +1,3 @@
+-- Persistent volume from rise.toml [deploy.volume] (serialized VolumeConfig). The
+-- PersistentVolumeClaim belongs to the deployment group and outlives the deployment.
+ALTER TABLE deployments ADD COLUMN volume JSONB;
@@ -0,0 +1,2 @@
+-- Replica spreading from rise.toml [deploy.scheduling] (serialized SchedulingConfig)
+ALTER TABLE deployments ADD COLUMN scheduling JSONB;
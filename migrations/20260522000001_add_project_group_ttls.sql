@@ -0,0 +1,4 @@
+-- Project-level deployment group TTL policies (serialized Vec<GroupTtlPolicy>)
+-- Ordered list of {pattern, expire}; the first pattern matching the group wins
+-- NULL = no policies (deployments only expire with an explicit --expire)
+ALTER TABLE projects ADD COLUMN group_ttls JSONB;
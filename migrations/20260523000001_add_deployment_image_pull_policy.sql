@@ -0,0 +1,7 @@
+-- Image pull policy from rise.toml [deploy] image_pull_policy ("always" or "if-not-present")
+-- NULL = derived from the image (if-not-present when digest-pinned, always otherwise)
+ALTER TABLE deployments ADD COLUMN image_pull_policy TEXT;
+
+-- Existing digest-pinned deployments were pulled "always"; keep that so upgrading
+-- doesn't change their pod template and roll them
+UPDATE deployments SET image_pull_policy = 'always' WHERE image_digest IS NOT NULL;
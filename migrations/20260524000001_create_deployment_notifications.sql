@@ -0,0 +1,42 @@
+-- Project-level webhook that is notified when deployments become Healthy,
+-- Unhealthy or Failed. The signing secret is encrypted with the configured
+-- encryption provider.
+CREATE TABLE project_webhooks (
+    project_id UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    secret TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Outbox of pending webhook deliveries. Rows are removed once delivered (or
+-- given up on) by the deployment notifier.
+CREATE TABLE deployment_notifications (
+    id BIGSERIAL PRIMARY KEY,
+    deployment_id UUID NOT NULL REFERENCES deployments(id) ON DELETE CASCADE,
+    status TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_deployment_notifications_next_attempt ON deployment_notifications(next_attempt_at);
+
+-- Enqueue a notification for every status transition of a project with a webhook
+CREATE OR REPLACE FUNCTION enqueue_deployment_notification()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM project_webhooks WHERE project_id = NEW.project_id) THEN
+        INSERT INTO deployment_notifications (deployment_id, status)
+        VALUES (NEW.id, NEW.status);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_deployment_notification
+    AFTER UPDATE OF status ON deployments
+    FOR EACH ROW
+    WHEN (OLD.status IS DISTINCT FROM NEW.status AND NEW.status IN ('Healthy', 'Unhealthy', 'Failed'))
+    EXECUTE FUNCTION enqueue_deployment_notification();
@@ -0,0 +1,7 @@
+-- Kubernetes namespace of the project, resolved from `namespace_format` when the
+-- project is created. Stored so that changing the format later does not orphan
+-- the namespaces of existing projects.
+-- NULL = not resolved yet (backfilled on startup)
+ALTER TABLE projects ADD COLUMN namespace TEXT;
+
+CREATE UNIQUE INDEX idx_projects_namespace ON projects(namespace);
@@ -0,0 +1,2 @@
+-- Ingress rate limits from rise.toml [deploy.ratelimit] (serialized RateLimitConfig)
+ALTER TABLE deployments ADD COLUMN rate_limit JSONB;
@@ -0,0 +1,2 @@
+-- Additional named container ports from rise.toml [deploy.ports] (serialized PortConfig map)
+ALTER TABLE deployments ADD COLUMN extra_ports JSONB;
@@ -0,0 +1,2 @@
+-- Kubernetes ServiceAccount of the pods from rise.toml [deploy.service_account]
+ALTER TABLE deployments ADD COLUMN service_account_name TEXT;
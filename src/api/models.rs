@@ -106,10 +106,16 @@ mod client_models {
         pub cpu: String,
         #[serde(default = "default_memory")]
         pub memory: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub cpu_limit: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub memory_limit: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub job_url: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub pull_request_url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub message: Option<String>,
         #[serde(default)]
         pub created: String,
         #[serde(default)]
@@ -52,6 +52,15 @@ pub struct Project {
     pub app_teams: Vec<TeamInfo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_url: Option<String>,
+    #[serde(default)]
+    pub force_https: Option<bool>,
+    #[serde(default)]
+    pub hsts_max_age: Option<i32>,
+    #[serde(default)]
+    pub max_active_deployments: Option<i32>,
+    /// Deployment group TTL policies, first match wins
+    #[serde(default)]
+    pub group_ttls: Vec<GroupTtlPolicy>,
     /// Effective deployment defaults (from platform settings)
     #[serde(default)]
     pub deployment_defaults: Option<DeploymentDefaultsInfo>,
@@ -60,6 +69,29 @@ pub struct Project {
     pub platform_constraints: Option<PlatformConstraintsInfo>,
 }
 
+/// Expiration applied to new deployments of the groups matching `pattern`
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GroupTtlPolicy {
+    pub pattern: String,
+    pub expire: String,
+}
+
+/// Request for configuring the deployment status webhook of a project
+#[derive(Debug, Serialize)]
+pub struct SetProjectWebhookRequest {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+/// Deployment status webhook of a project
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProjectWebhook {
+    pub url: String,
+    pub has_secret: bool,
+    pub updated_at: String,
+}
+
 /// Effective deployment defaults
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DeploymentDefaultsInfo {
@@ -120,6 +152,24 @@ pub struct UpdateProjectResponse {
     pub project: Project,
 }
 
+/// Active deployment carried over by a project transfer
+#[derive(Debug, Deserialize)]
+pub struct TransferredDeployment {
+    pub deployment_id: String,
+    pub deployment_group: String,
+    pub status: String,
+}
+
+/// Response from project transfer endpoint
+#[derive(Debug, Deserialize)]
+pub struct TransferProjectResponse {
+    pub project: Project,
+    pub previous_owner: Option<OwnerInfo>,
+    pub active_deployments: Vec<TransferredDeployment>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
 /// Request for updating a project
 #[derive(Debug, Serialize)]
 pub struct UpdateProjectRequest {
@@ -136,13 +186,16 @@ pub struct UpdateProjectRequest {
     /// URL to where the project code lives. Use Some(None) to clear, Some(Some(url)) to set.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_url: Option<Option<String>>,
+    /// Replaces the group TTL policies (empty list = remove all policies)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_ttls: Option<Vec<GroupTtlPolicy>>,
 }
 
 /// Current user information
 #[derive(Debug, Deserialize)]
 pub struct MeResponse {
-    #[allow(dead_code)]
     pub id: String,
-    #[allow(dead_code)]
     pub email: String,
+    #[serde(default)]
+    pub is_admin: bool,
 }
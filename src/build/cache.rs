@@ -0,0 +1,136 @@
+// Persistent per-project build cache directories
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use super::BuildMethod;
+
+/// Whether a build method can persist its layer cache to a local directory
+pub(crate) fn supports_cache_dir(method: &BuildMethod) -> bool {
+    matches!(
+        method,
+        BuildMethod::Pack | BuildMethod::Railpack { .. } | BuildMethod::Buildctl
+    )
+}
+
+/// Turn a project name into a single, filesystem-safe path component
+fn cache_key(project: &str) -> String {
+    let key: String = project
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    match key.trim_matches('.') {
+        "" => "default".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+/// Default cache directory for a project: `<user cache dir>/rise/builds/<project>`.
+///
+/// Keying on the project name keeps concurrent builds of different projects from
+/// writing into the same cache. Returns None if the platform has no cache directory.
+pub(crate) fn default_cache_dir(project: &str) -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("rise").join("builds").join(cache_key(project)))
+}
+
+/// Fallback cache key when no project name is known: the app directory name
+pub(crate) fn project_from_app_path(app_path: &str) -> String {
+    let path = Path::new(app_path);
+    path.canonicalize()
+        .ok()
+        .as_deref()
+        .unwrap_or(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Create the cache directory if needed and log whether it already holds a cache.
+///
+/// Returns true on a cache hit, i.e. the directory was populated by an earlier build.
+pub(crate) fn prepare_cache_dir(cache_dir: &Path) -> Result<bool> {
+    std::fs::create_dir_all(cache_dir)
+        .with_context(|| format!("Failed to create cache directory: {}", cache_dir.display()))?;
+    let hit = std::fs::read_dir(cache_dir)
+        .with_context(|| format!("Failed to read cache directory: {}", cache_dir.display()))?
+        .next()
+        .is_some();
+    if hit {
+        info!("Reusing build cache from {}", cache_dir.display());
+    } else {
+        info!(
+            "Build cache at {} is empty, it will be populated by this build",
+            cache_dir.display()
+        );
+    }
+    Ok(hit)
+}
+
+/// Whether a BuildKit local cache export exists in the directory.
+///
+/// Importing from a directory without an `index.json` makes BuildKit fail, so the
+/// import is only added once a previous build exported into it.
+pub(crate) fn has_buildkit_cache(cache_dir: &Path) -> bool {
+    cache_dir.join("index.json").is_file()
+}
+
+/// `--import-cache` / `--export-cache` values for a local BuildKit cache
+pub(crate) fn buildkit_cache_args(cache_dir: &Path) -> (String, String) {
+    (
+        format!("type=local,src={}", cache_dir.display()),
+        format!("type=local,dest={},mode=max", cache_dir.display()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key_is_a_single_safe_component() {
+        assert_eq!(cache_key("my-app"), "my-app");
+        assert_eq!(cache_key("team/app:v1"), "team-app-v1");
+        assert_eq!(cache_key(".."), "default");
+        assert_eq!(cache_key(""), "default");
+    }
+
+    #[test]
+    fn test_default_cache_dir_is_keyed_on_project() {
+        let (Some(a), Some(b)) = (default_cache_dir("a"), default_cache_dir("b")) else {
+            return;
+        };
+        assert_ne!(a, b);
+        assert!(a.ends_with("rise/builds/a"));
+    }
+
+    #[test]
+    fn test_prepare_cache_dir_reports_hits() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("proj");
+        assert!(!prepare_cache_dir(&cache_dir).unwrap());
+        assert!(!has_buildkit_cache(&cache_dir));
+        std::fs::write(cache_dir.join("index.json"), "{}").unwrap();
+        assert!(prepare_cache_dir(&cache_dir).unwrap());
+        assert!(has_buildkit_cache(&cache_dir));
+    }
+
+    #[test]
+    fn test_only_cacheable_methods_use_cache_dir() {
+        assert!(supports_cache_dir(&BuildMethod::Pack));
+        assert!(supports_cache_dir(&BuildMethod::Buildctl));
+        assert!(supports_cache_dir(&BuildMethod::Railpack {
+            use_buildctl: false
+        }));
+        assert!(!supports_cache_dir(&BuildMethod::Nixpacks));
+        assert!(!supports_cache_dir(&BuildMethod::Docker {
+            use_buildx: true
+        }));
+    }
+}
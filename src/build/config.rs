@@ -78,6 +78,24 @@ pub fn load_full_project_config(app_path: &str) -> Result<Option<ProjectBuildCon
             );
         }
 
+        // Validate expiration durations up front, like the backend does for --expire
+        let deploy_configs = config
+            .deploy
+            .iter()
+            .map(|deploy| ("[deploy]".to_string(), deploy))
+            .chain(config.environments.iter().filter_map(|(name, env)| {
+                env.deploy
+                    .as_ref()
+                    .map(|deploy| (format!("[environments.{}.deploy]", name), deploy))
+            }));
+        for (section, deploy) in deploy_configs {
+            if let Some(ref expire) = deploy.expire {
+                crate::rise_toml::parse_expiration_duration(expire).map_err(|e| {
+                    anyhow::anyhow!("Invalid expire '{}' in {}: {}", expire, section, e)
+                })?;
+            }
+        }
+
         Ok(Some(config))
     } else {
         Ok(None)
@@ -351,4 +369,203 @@ env.NODE_ENV = "production"
         let production = config.environments.get("production").unwrap();
         assert!(!production.default);
     }
+
+    #[test]
+    fn test_load_config_with_health_check() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("rise.toml"),
+            r#"
+[deploy.health_check]
+readiness_path = "/ready"
+liveness_path = "/live"
+period_seconds = 5
+
+[environments.production.deploy.health_check]
+readiness_path = "/ready?full=1"
+initial_delay_seconds = 30
+"#,
+        )
+        .unwrap();
+
+        let config = load_full_project_config(temp_dir.path().to_str().unwrap())
+            .unwrap()
+            .unwrap();
+        let global = config.deploy.unwrap().health_check.unwrap();
+        let production = config.environments["production"]
+            .deploy
+            .clone()
+            .unwrap()
+            .health_check
+            .unwrap();
+
+        let merged = production.or(&global);
+        assert_eq!(merged.readiness_path.as_deref(), Some("/ready?full=1"));
+        assert_eq!(merged.liveness_path.as_deref(), Some("/live"));
+        assert_eq!(merged.initial_delay_seconds, Some(30));
+        assert_eq!(merged.period_seconds, Some(5));
+    }
+
+    #[test]
+    fn test_load_config_with_http_healthcheck() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("rise.toml"),
+            r#"
+[deploy.healthcheck]
+path = "/health"
+expected_status = [200, 204]
+timeout = 2
+"#,
+        )
+        .unwrap();
+
+        let config = load_full_project_config(temp_dir.path().to_str().unwrap())
+            .unwrap()
+            .unwrap();
+        let health_check = config.deploy.unwrap().health_check.unwrap();
+        assert_eq!(health_check.path.as_deref(), Some("/health"));
+        assert_eq!(health_check.timeout, Some(2));
+        assert_eq!(health_check.expected_status, Some(vec![200, 204]));
+
+        #[cfg(feature = "backend")]
+        {
+            assert!(health_check.is_expected_status(204));
+            assert!(!health_check.is_expected_status(500));
+
+            // Without expected_status, any 2xx is healthy
+            let default = crate::rise_toml::HealthCheckConfig::default();
+            assert!(default.is_expected_status(200));
+            assert!(!default.is_expected_status(302));
+        }
+    }
+
+    #[test]
+    fn test_load_config_with_labels_and_annotations() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("rise.toml"),
+            r#"
+[deploy.labels]
+team = "payments"
+cost-center = "1234"
+
+[deploy.annotations]
+"example.com/owner" = "payments@example.com"
+
+[environments.production.deploy.labels]
+cost-center = "5678"
+"#,
+        )
+        .unwrap();
+
+        let config = load_full_project_config(temp_dir.path().to_str().unwrap())
+            .unwrap()
+            .unwrap();
+        let global = config.deploy.as_ref().unwrap();
+        let production = config.environments["production"].deploy.as_ref().unwrap();
+
+        let labels = crate::rise_toml::merge_metadata_maps(
+            global.labels.as_ref(),
+            production.labels.as_ref(),
+        )
+        .unwrap();
+        assert_eq!(labels["team"], "payments");
+        assert_eq!(labels["cost-center"], "5678");
+
+        let annotations = crate::rise_toml::merge_metadata_maps(
+            global.annotations.as_ref(),
+            production.annotations.as_ref(),
+        )
+        .unwrap();
+        assert_eq!(annotations["example.com/owner"], "payments@example.com");
+        assert_eq!(crate::rise_toml::merge_metadata_maps(None, None), None);
+    }
+
+    #[test]
+    fn test_load_config_with_deploy_defaults() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("rise.toml"),
+            r#"
+[deploy]
+image = "nginx:latest"
+http_port = 80
+group = "preview"
+expire = "7d"
+
+[environments.staging.deploy]
+expire = "2h"
+"#,
+        )
+        .unwrap();
+
+        let config = load_full_project_config(temp_dir.path().to_str().unwrap())
+            .unwrap()
+            .unwrap();
+        let deploy = config.deploy.as_ref().unwrap();
+        assert_eq!(deploy.image.as_deref(), Some("nginx:latest"));
+        assert_eq!(deploy.http_port, Some(80));
+        assert_eq!(deploy.group.as_deref(), Some("preview"));
+        assert_eq!(deploy.expire.as_deref(), Some("7d"));
+        let staging = config.environments["staging"].deploy.as_ref().unwrap();
+        assert_eq!(staging.expire.as_deref(), Some("2h"));
+    }
+
+    #[test]
+    fn test_load_config_rejects_invalid_expire() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("rise.toml"),
+            r#"
+[environments.staging.deploy]
+expire = "7 days"
+"#,
+        )
+        .unwrap();
+
+        let err = load_full_project_config(temp_dir.path().to_str().unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Invalid expire '7 days' in [environments.staging.deploy]"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_load_config_with_volume() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp_dir.path().join("rise.toml"),
+            r#"
+[deploy.volume]
+size = "5Gi"
+mount_path = "/data"
+
+[environments.production.deploy.volume]
+size = "50Gi"
+storage_class = "ssd"
+mount_path = "/data"
+"#,
+        )
+        .unwrap();
+
+        let config = load_full_project_config(temp_dir.path().to_str().unwrap())
+            .unwrap()
+            .unwrap();
+        let volume = config.deploy.unwrap().volume.unwrap();
+        assert_eq!(volume.size, "5Gi");
+        assert_eq!(volume.mount_path, "/data");
+        assert_eq!(volume.storage_class, None);
+        let production = config.environments["production"]
+            .deploy
+            .as_ref()
+            .unwrap()
+            .volume
+            .as_ref()
+            .unwrap();
+        assert_eq!(production.storage_class.as_deref(), Some("ssd"));
+    }
 }
@@ -8,7 +8,7 @@ use tracing::{debug, info, warn};
 use super::dockerfile_ssl::{
     preprocess_dockerfile_for_ssl, SslCertContext, SSL_CERT_BUILD_CONTEXT,
 };
-use super::registry::docker_push;
+use super::registry::{docker_push, run_build_with_push_retries};
 
 /// Configure buildx output flags (`--push` / `--load`) on a command.
 ///
@@ -47,12 +47,15 @@ pub(crate) struct DockerBuildOptions<'a> {
     pub buildx_supports_push: bool,
     pub use_buildx: bool,
     pub push: bool,
+    pub push_retries: u32,
     pub buildkit_host: Option<&'a str>,
     pub env: &'a [String],
     pub build_context: Option<&'a str>,
     pub build_contexts: &'a std::collections::HashMap<String, String>,
     pub no_cache: bool,
     pub platform: &'a str,
+    pub ssh_key: Option<&'a str>,
+    pub git_token: Option<&'a str>,
 }
 
 /// Build image using Docker or Podman with a Dockerfile
@@ -197,6 +200,29 @@ pub(crate) fn build_image_with_dockerfile(options: DockerBuildOptions) -> Result
         cmd.arg("--build-arg").arg(build_arg);
     }
 
+    // Forward private git dependency credentials as BuildKit ssh/secret mounts,
+    // which are never persisted in image layers
+    if options.use_buildx {
+        if let Some(ssh_key) = options.ssh_key {
+            info!("Forwarding SSH key to the build");
+            cmd.arg("--ssh")
+                .arg(super::git_auth::ssh_forward_arg(ssh_key));
+        }
+        if let Some(git_token) = options.git_token {
+            info!(
+                "Passing git token as '{}' secret",
+                super::git_auth::GIT_TOKEN_SECRET
+            );
+            super::proxy::add_secrets_to_command(
+                &mut cmd,
+                &std::collections::HashMap::from([(
+                    super::git_auth::GIT_TOKEN_SECRET.to_string(),
+                    git_token.to_string(),
+                )]),
+            );
+        }
+    }
+
     // Add build contexts (additional named contexts for multi-stage builds)
     if !options.build_contexts.is_empty() {
         info!("Using {} build context(s)", options.build_contexts.len());
@@ -223,6 +249,15 @@ pub(crate) fn build_image_with_dockerfile(options: DockerBuildOptions) -> Result
         options.push
     };
 
+    // buildx pushes as part of the build
+    if options.push && !needs_fallback_push {
+        return run_build_with_push_retries(
+            &mut cmd,
+            &format!("{} buildx build", options.container_cli),
+            options.push_retries,
+        );
+    }
+
     debug!("Executing command: {:?}", cmd);
 
     let status = cmd
@@ -240,7 +275,11 @@ pub(crate) fn build_image_with_dockerfile(options: DockerBuildOptions) -> Result
     }
 
     if needs_fallback_push {
-        docker_push(options.container_cli, options.image_tag)?;
+        docker_push(
+            options.container_cli,
+            options.image_tag,
+            options.push_retries,
+        )?;
     }
 
     Ok(())
@@ -0,0 +1,88 @@
+// Private git dependency access (SSH key forwarding and git tokens) for builds
+
+use std::collections::HashMap;
+
+use super::BuildMethod;
+
+/// BuildKit secret id under which the git token is exposed to the build
+pub(crate) const GIT_TOKEN_SECRET: &str = "GIT_TOKEN";
+
+/// Git credential helper answering every HTTPS credential request with the token
+/// from the `GIT_TOKEN` environment variable, so the token itself never ends up
+/// in git config or the image.
+const GIT_CREDENTIAL_HELPER: &str =
+    "!f() { echo username=x-access-token; echo \"password=${GIT_TOKEN}\"; }; f";
+
+/// Whether a build method can forward an SSH key into the build (BuildKit `--ssh`)
+pub(crate) fn supports_ssh_forwarding(method: &BuildMethod) -> bool {
+    matches!(
+        method,
+        BuildMethod::Docker { use_buildx: true } | BuildMethod::Buildctl
+    )
+}
+
+/// Whether a build method can pass a git token as a BuildKit secret
+pub(crate) fn supports_git_token(method: &BuildMethod) -> bool {
+    matches!(
+        method,
+        BuildMethod::Docker { use_buildx: true }
+            | BuildMethod::Buildctl
+            | BuildMethod::Railpack { .. }
+    )
+}
+
+/// `--ssh` value forwarding the given private key as the default SSH agent
+pub(crate) fn ssh_forward_arg(ssh_key: &str) -> String {
+    format!("default={}", ssh_key)
+}
+
+/// Build secrets that make `git` authenticate HTTPS remotes with the token.
+///
+/// Railpack exposes secrets as environment variables in every build step, so the
+/// `GIT_CONFIG_*` variables install the credential helper without touching any
+/// file. Dockerfile builds only use `GIT_TOKEN` and mount it explicitly.
+pub(crate) fn git_credential_secrets(git_token: &str) -> HashMap<String, String> {
+    HashMap::from([
+        (GIT_TOKEN_SECRET.to_string(), git_token.to_string()),
+        ("GIT_CONFIG_COUNT".to_string(), "1".to_string()),
+        (
+            "GIT_CONFIG_KEY_0".to_string(),
+            "credential.helper".to_string(),
+        ),
+        (
+            "GIT_CONFIG_VALUE_0".to_string(),
+            GIT_CREDENTIAL_HELPER.to_string(),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_buildkit_dockerfile_builds_forward_ssh() {
+        assert!(supports_ssh_forwarding(&BuildMethod::Docker {
+            use_buildx: true
+        }));
+        assert!(supports_ssh_forwarding(&BuildMethod::Buildctl));
+        assert!(!supports_ssh_forwarding(&BuildMethod::Docker {
+            use_buildx: false
+        }));
+        assert!(!supports_ssh_forwarding(&BuildMethod::Pack));
+        assert!(!supports_ssh_forwarding(&BuildMethod::Nixpacks));
+        assert!(!supports_ssh_forwarding(&BuildMethod::Railpack {
+            use_buildctl: true
+        }));
+    }
+
+    #[test]
+    fn test_git_credential_secrets_reference_token_by_env() {
+        let secrets = git_credential_secrets("s3cret");
+        assert_eq!(secrets[GIT_TOKEN_SECRET], "s3cret");
+        assert_eq!(secrets["GIT_CONFIG_KEY_0"], "credential.helper");
+        // The helper reads the token at runtime instead of embedding it
+        assert!(!secrets["GIT_CONFIG_VALUE_0"].contains("s3cret"));
+        assert!(secrets["GIT_CONFIG_VALUE_0"].contains("${GIT_TOKEN}"));
+    }
+}
@@ -2,7 +2,7 @@
 
 use anyhow::{bail, Result};
 use clap::Args;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 use crate::config::{Config, ContainerCli};
@@ -14,6 +14,7 @@ pub(crate) enum BuildMethod {
         use_buildx: bool,
     },
     Pack,
+    Nixpacks,
     Railpack {
         use_buildctl: bool,
     },
@@ -24,7 +25,7 @@ pub(crate) enum BuildMethod {
 /// Build-related CLI arguments that can be flattened into command structs
 #[derive(Debug, Clone, Args)]
 pub struct BuildArgs {
-    /// Build backend (docker[:build|:buildx|:buildctl], pack, railpack[:buildx|:buildctl])
+    /// Build backend (docker[:build|:buildx|:buildctl], pack, nixpacks, railpack[:buildx|:buildctl])
     #[arg(long)]
     pub backend: Option<String>,
 
@@ -75,14 +76,39 @@ pub struct BuildArgs {
     #[arg(long = "build-context")]
     pub build_contexts: Vec<String>,
 
-    /// Disable build cache (equivalent to docker build --no-cache, pack build --clear-cache)
+    /// Disable build cache (docker build --no-cache, buildctl --opt no-cache=, pack build --clear-cache)
     #[arg(long)]
     pub no_cache: bool,
 
     /// Target platform for the container image build (e.g., linux/amd64, linux/arm64).
     /// Defaults to linux/amd64 for Rise server compatibility.
-    #[arg(long)]
-    pub platform: Option<String>,
+    /// Repeat (or comma-separate) to build a multi-platform image, which requires
+    /// --push and the docker:buildx, buildctl or railpack backend.
+    #[arg(long, value_delimiter = ',')]
+    pub platform: Vec<String>,
+
+    /// SSH private key forwarded to the build for private git dependencies
+    /// (docker:buildx and buildctl only, via `RUN --mount=type=ssh`).
+    /// Can also be set via RISE_SSH_KEY.
+    #[arg(long, value_name = "PATH")]
+    pub ssh_key: Option<String>,
+
+    /// Token for cloning private git dependencies over HTTPS, passed as the
+    /// `GIT_TOKEN` BuildKit secret (docker:buildx, buildctl, railpack).
+    /// Prefer setting RISE_GIT_TOKEN to keep it out of the shell history.
+    #[arg(long, value_name = "TOKEN")]
+    pub git_token: Option<String>,
+
+    /// Directory for the persistent build cache (pack, railpack, buildctl).
+    /// Defaults to a per-project directory under the user cache directory.
+    /// Can also be set via RISE_BUILD_CACHE_DIR.
+    #[arg(long, value_name = "PATH")]
+    pub cache_dir: Option<String>,
+
+    /// Number of times a failed registry push is retried, with exponential backoff
+    /// (2s, 4s, 8s, ...). Authentication errors are not retried. Set to 0 to disable.
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub push_retries: u32,
 }
 
 /// Options for building container images
@@ -114,8 +140,18 @@ pub(crate) struct BuildOptions {
     pub build_contexts: std::collections::HashMap<String, String>,
     /// Disable build cache
     pub no_cache: bool,
-    /// Target platform (e.g., "linux/amd64")
+    /// Target platform(s), comma-separated (e.g., "linux/amd64" or "linux/amd64,linux/arm64")
     pub platform: String,
+    /// SSH private key forwarded to BuildKit builds
+    pub ssh_key: Option<String>,
+    /// Git token exposed to the build as a BuildKit secret
+    pub git_token: Option<String>,
+    /// Persistent build cache directory (None if the platform has no cache directory)
+    pub cache_dir: Option<PathBuf>,
+    /// Whether the cache directory was set explicitly (--cache-dir or RISE_BUILD_CACHE_DIR)
+    pub explicit_cache_dir: bool,
+    /// Number of retries for a failed registry push
+    pub push_retries: u32,
 }
 
 impl BuildOptions {
@@ -140,11 +176,11 @@ impl BuildOptions {
         use tracing::warn;
 
         // Use preloaded config if available, otherwise load from disk
-        let project_config = if let Some(cfg) = preloaded_config {
-            cfg.build
+        let full_config = if let Some(cfg) = preloaded_config {
+            Some(cfg)
         } else {
             match crate::build::config::load_full_project_config(&app_path) {
-                Ok(cfg) => cfg.and_then(|c| c.build),
+                Ok(cfg) => cfg,
                 Err(e) => {
                     warn!(
                         "Failed to load project config: {:#}. Continuing without it.",
@@ -154,6 +190,25 @@ impl BuildOptions {
                 }
             }
         };
+        let project_name = full_config
+            .as_ref()
+            .and_then(|c| c.project.as_ref())
+            .map(|p| p.name.clone());
+        let project_config = full_config.and_then(|c| c.build);
+
+        // Cache directory: CLI flag > env var > per-project default.
+        // Like credentials, it is machine-specific and never read from rise.toml.
+        let explicit_cache_dir = build_args
+            .cache_dir
+            .clone()
+            .or_else(|| crate::build::env_var_non_empty("RISE_BUILD_CACHE_DIR"))
+            .map(PathBuf::from);
+        let explicit_cache_dir_set = explicit_cache_dir.is_some();
+        let cache_dir = explicit_cache_dir.or_else(|| {
+            let project = project_name
+                .unwrap_or_else(|| crate::build::cache::project_from_app_path(&app_path));
+            crate::build::cache::default_cache_dir(&project)
+        });
 
         // Merge: CLI > Project > Environment (via Config) > Global (via Config) > Defaults
         Self {
@@ -252,14 +307,27 @@ impl BuildOptions {
                     .and_then(|c| c.no_cache)
                     .unwrap_or(false),
 
-            platform: build_args
-                .platform
-                .clone()
+            platform: Some(build_args.platform.join(","))
+                .filter(|platform| !platform.is_empty())
                 .or_else(|| crate::build::env_var_non_empty("RISE_PLATFORM"))
                 .or_else(|| project_config.as_ref().and_then(|c| c.platform.clone()))
                 .unwrap_or_else(|| crate::build::DEFAULT_PLATFORM.to_string()),
 
+            // Credentials are never read from rise.toml
+            ssh_key: build_args
+                .ssh_key
+                .clone()
+                .or_else(|| crate::build::env_var_non_empty("RISE_SSH_KEY")),
+            git_token: build_args
+                .git_token
+                .clone()
+                .or_else(|| crate::build::env_var_non_empty("RISE_GIT_TOKEN")),
+
+            cache_dir,
+            explicit_cache_dir: explicit_cache_dir_set,
+
             push: false,
+            push_retries: build_args.push_retries,
         }
     }
 
@@ -268,6 +336,15 @@ impl BuildOptions {
         self.push = push;
         self
     }
+
+    /// Builder method to key the default cache directory on the given project.
+    /// An explicitly configured cache directory is kept as is.
+    pub(crate) fn with_cache_project(mut self, project: &str) -> Self {
+        if !self.explicit_cache_dir {
+            self.cache_dir = crate::build::cache::default_cache_dir(project);
+        }
+        self
+    }
 }
 
 impl BuildMethod {
@@ -278,12 +355,13 @@ impl BuildMethod {
             "docker:buildx" => Ok(BuildMethod::Docker { use_buildx: true }),
             "buildctl" | "docker:buildctl" => Ok(BuildMethod::Buildctl),
             "pack" => Ok(BuildMethod::Pack),
+            "nixpacks" => Ok(BuildMethod::Nixpacks),
             "railpack" | "railpack:buildx" => Ok(BuildMethod::Railpack {
                 use_buildctl: false,
             }),
             "railpack:buildctl" => Ok(BuildMethod::Railpack { use_buildctl: true }),
             _ => bail!(
-                "Invalid build backend '{}'. Supported: docker, docker:build, docker:buildx, buildctl, docker:buildctl, pack, railpack, railpack:buildctl",
+                "Invalid build backend '{}'. Supported: docker, docker:build, docker:buildx, buildctl, docker:buildctl, pack, nixpacks, railpack, railpack:buildctl",
                 backend
             ),
         }
@@ -291,6 +369,9 @@ impl BuildMethod {
 }
 
 /// Select build method based on explicit backend or auto-detection
+///
+/// Without an explicit backend, an explicit `dockerfile` always selects the docker
+/// backend (and must exist), regardless of other build files in the app path.
 /// Returns (BuildMethod, Option<dockerfile_path>)
 pub(crate) fn select_build_method(
     app_path: &str,
@@ -322,6 +403,14 @@ pub(crate) fn select_build_method(
         let method = BuildMethod::from_backend_str(backend_str)?;
         Ok((method, dockerfile_relative))
     } else {
+        if dockerfile.is_some() && !dockerfile_path.is_file() {
+            bail!(
+                "Dockerfile '{}' does not exist (paths are relative to '{}')",
+                dockerfile_path.display(),
+                app_path
+            );
+        }
+
         // Auto-detect based on dockerfile presence
         if dockerfile_path.exists() && dockerfile_path.is_file() {
             // Check if buildx is available
@@ -359,3 +448,139 @@ pub(crate) fn requires_buildkit(method: &BuildMethod) -> bool {
             | BuildMethod::Buildctl
     )
 }
+
+/// Whether a build method can produce a multi-platform image (manifest list)
+pub(crate) fn supports_multi_platform(method: &BuildMethod) -> bool {
+    requires_buildkit(method)
+}
+
+/// Whether a platform setting names more than one platform
+pub(crate) fn is_multi_platform(platform: &str) -> bool {
+    platform.split(',').filter(|p| !p.trim().is_empty()).count() > 1
+}
+
+/// Reject multi-platform builds that can't produce a manifest list.
+///
+/// Only BuildKit backends build several platforms at once, and the result must be
+/// pushed: the local image store can't load a manifest list.
+pub(crate) fn validate_platforms(
+    method: &BuildMethod,
+    platform: &str,
+    push: bool,
+    buildx_supports_push: bool,
+) -> Result<()> {
+    if !is_multi_platform(platform) {
+        return Ok(());
+    }
+    if !supports_multi_platform(method) {
+        bail!(
+            "Multi-platform builds ({}) are not supported by the docker:build, pack and \
+             nixpacks backends. Use the docker:buildx, buildctl or railpack backend.",
+            platform
+        );
+    }
+    if !push {
+        bail!(
+            "Multi-platform images ({}) can't be loaded into the local image store. \
+             Pass --push to push the image to a registry instead.",
+            platform
+        );
+    }
+    let uses_buildx = matches!(
+        method,
+        BuildMethod::Docker { use_buildx: true }
+            | BuildMethod::Railpack {
+                use_buildctl: false
+            }
+    );
+    if uses_buildx && !buildx_supports_push {
+        bail!(
+            "Multi-platform builds ({}) need `buildx build --push`, which this container CLI \
+             doesn't support. Use the buildctl or railpack:buildctl backend.",
+            platform
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_dockerfile_forces_docker_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("docker")).unwrap();
+        std::fs::write(dir.path().join("docker/Dockerfile"), "FROM scratch\n").unwrap();
+        let app_path = dir.path().to_str().unwrap();
+
+        let (method, dockerfile) =
+            select_build_method(app_path, None, Some("docker/Dockerfile"), "false").unwrap();
+        assert_eq!(method, BuildMethod::Docker { use_buildx: false });
+        assert_eq!(dockerfile.as_deref(), Some("docker/Dockerfile"));
+
+        // Without a Dockerfile at the root, auto-detection falls back to railpack
+        let (method, _) = select_build_method(app_path, None, None, "false").unwrap();
+        assert_eq!(
+            method,
+            BuildMethod::Railpack {
+                use_buildctl: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_explicit_dockerfile_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let app_path = dir.path().to_str().unwrap();
+
+        let err =
+            select_build_method(app_path, None, Some("docker/Dockerfile"), "false").unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_explicit_nixpacks_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Dockerfile"), "FROM scratch\n").unwrap();
+        let app_path = dir.path().to_str().unwrap();
+
+        let (method, _) = select_build_method(app_path, Some("nixpacks"), None, "false").unwrap();
+        assert_eq!(method, BuildMethod::Nixpacks);
+        assert!(!requires_buildkit(&method));
+    }
+
+    #[test]
+    fn test_validate_platforms() {
+        let buildx = BuildMethod::Docker { use_buildx: true };
+        let multi = "linux/amd64,linux/arm64";
+
+        // Single platform builds are never restricted
+        assert!(validate_platforms(&BuildMethod::Pack, "linux/arm64", false, false).is_ok());
+
+        assert!(validate_platforms(&buildx, multi, true, true).is_ok());
+        assert!(validate_platforms(&BuildMethod::Buildctl, multi, true, false).is_ok());
+        assert!(validate_platforms(
+            &BuildMethod::Railpack { use_buildctl: true },
+            multi,
+            true,
+            false
+        )
+        .is_ok());
+
+        for method in [
+            BuildMethod::Docker { use_buildx: false },
+            BuildMethod::Pack,
+            BuildMethod::Nixpacks,
+        ] {
+            let err = validate_platforms(&method, multi, true, true).unwrap_err();
+            assert!(err.to_string().contains("not supported"), "{}", err);
+        }
+
+        let err = validate_platforms(&buildx, multi, false, true).unwrap_err();
+        assert!(err.to_string().contains("--push"), "{}", err);
+
+        // buildx frontends without --push support (Podman) would need --load
+        assert!(validate_platforms(&buildx, multi, true, false).is_err());
+    }
+}
@@ -1,14 +1,17 @@
 // Build module - Container image building orchestration
 //
 // This module provides a clean API for building container images using various
-// backends (Docker, Pack, Railpack) and handles related concerns like BuildKit
+// backends (Docker, Pack, Railpack, Nixpacks) and handles related concerns like BuildKit
 // daemon management, SSL certificate handling, and registry operations.
 
 mod buildkit;
+mod cache;
 pub mod config;
 mod docker;
 mod dockerfile_ssl;
+mod git_auth;
 mod method;
+mod nixpacks;
 mod pack;
 mod proxy;
 mod railpack;
@@ -17,7 +20,8 @@ mod ssl;
 
 /// Default target platform for container image builds.
 /// Rise server nodes run linux/amd64, so this is the default for compatibility.
-/// Users can override with `--platform`, `RISE_PLATFORM`, or `[build] platform` in rise.toml.
+/// Users can override with `--platform`, `RISE_PLATFORM`, or `[build] platform` in rise.toml,
+/// and list several platforms (comma-separated) for a multi-platform image.
 pub const DEFAULT_PLATFORM: &str = "linux/amd64";
 
 pub use method::BuildArgs;
@@ -34,7 +38,8 @@ use tracing::{debug, info, warn};
 
 use buildkit::{check_ssl_cert_and_warn, ensure_managed_buildkit_daemon};
 use docker::{build_image_with_dockerfile, DockerBuildOptions};
-use method::{requires_buildkit, select_build_method};
+use method::{is_multi_platform, requires_buildkit, select_build_method, validate_platforms};
+use nixpacks::build_image_with_nixpacks;
 use pack::build_image_with_buildpacks;
 use railpack::build_image_with_railpacks;
 
@@ -116,6 +121,15 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
         options.dockerfile.as_deref(),
         container_cli.command(),
     )?;
+    validate_platforms(
+        &build_method,
+        &options.platform,
+        options.push,
+        container_cli.buildx_supports_push(),
+    )?;
+    if is_multi_platform(&options.platform) {
+        info!("Building multi-platform image for {}", options.platform);
+    }
 
     // Determine if we should use managed buildkit
     let managed_buildkit = match options.managed_buildkit {
@@ -155,10 +169,16 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
     };
 
     // Resolve build_context relative to app_path
-    let resolved_build_context = options.build_context.as_ref().map(|ctx| {
-        let resolved = app_path.join(ctx);
-        resolved.to_string_lossy().to_string()
-    });
+    let resolved_build_context = match options.build_context.as_ref() {
+        Some(ctx) => {
+            let resolved = app_path.join(ctx);
+            if !resolved.is_dir() {
+                bail!("Build context '{}' is not a directory", resolved.display());
+            }
+            Some(resolved.to_string_lossy().to_string())
+        }
+        None => None,
+    };
 
     // Resolve build_contexts paths relative to app_path
     let resolved_build_contexts: std::collections::HashMap<String, String> = options
@@ -170,6 +190,43 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
         })
         .collect();
 
+    // Private git dependency credentials are only forwarded through BuildKit
+    if options.ssh_key.is_some() && !git_auth::supports_ssh_forwarding(&build_method) {
+        warn!(
+            "--ssh-key is ignored: this build method cannot forward SSH keys. \
+             Use the docker:buildx or buildctl backend with `RUN --mount=type=ssh`."
+        );
+    }
+    if options.git_token.is_some() && !git_auth::supports_git_token(&build_method) {
+        warn!(
+            "--git-token is ignored: this build method cannot pass it as a BuildKit secret. \
+             Use the docker:buildx, buildctl or railpack backend."
+        );
+    }
+    if let Some(ref ssh_key) = options.ssh_key {
+        if !Path::new(ssh_key).is_file() {
+            bail!("SSH key '{}' does not exist", ssh_key);
+        }
+    }
+
+    // Persistent build cache, for the backends that can export their cache locally
+    let cache_dir = match options.cache_dir.as_deref() {
+        Some(dir) if cache::supports_cache_dir(&build_method) => {
+            cache::prepare_cache_dir(dir)?;
+            Some(dir)
+        }
+        Some(_) => {
+            if options.explicit_cache_dir {
+                warn!(
+                    "--cache-dir is ignored: this build method keeps its cache in the container \
+                     runtime. Use the pack, railpack or buildctl backend."
+                );
+            }
+            None
+        }
+        None => None,
+    };
+
     // Execute build based on selected method
     match build_method {
         BuildMethod::Docker { use_buildx } => {
@@ -187,12 +244,15 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
                 buildx_supports_push: container_cli.buildx_supports_push(),
                 use_buildx,
                 push: options.push,
+                push_retries: options.push_retries,
                 buildkit_host: buildkit_host.as_deref(),
                 env: &options.env,
                 build_context: resolved_build_context.as_deref(),
                 build_contexts: &resolved_build_contexts,
                 no_cache: options.no_cache,
                 platform: &options.platform,
+                ssh_key: options.ssh_key.as_deref(),
+                git_token: options.git_token.as_deref(),
             })?;
         }
         BuildMethod::Pack => {
@@ -210,11 +270,46 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
                 &options.env,
                 options.no_cache,
                 &options.platform,
+                cache_dir,
             )?;
 
             // Pack doesn't support push during build, so push separately if requested
             if options.push {
-                registry::docker_push(container_cli.command(), &options.image_tag)?;
+                registry::docker_push(
+                    container_cli.command(),
+                    &options.image_tag,
+                    options.push_retries,
+                )?;
+            }
+        }
+        BuildMethod::Nixpacks => {
+            if options.builder.is_some() {
+                warn!("--builder flag is ignored when using nixpacks build method");
+            }
+            if !options.buildpacks.is_empty() {
+                warn!("--buildpack flags are ignored when using nixpacks build method");
+            }
+            if options.explicit_container_cli {
+                warn!("--container-cli flag is ignored when using nixpacks build method");
+            }
+            if options.managed_buildkit.is_some() {
+                warn!("--managed-buildkit flag is ignored when using nixpacks build method");
+            }
+            build_image_with_nixpacks(
+                &options.app_path,
+                &options.image_tag,
+                &options.env,
+                options.no_cache,
+                &options.platform,
+            )?;
+
+            // Nixpacks only builds into the local Docker daemon, so push separately if requested
+            if options.push {
+                registry::docker_push(
+                    container_cli.command(),
+                    &options.image_tag,
+                    options.push_retries,
+                )?;
             }
         }
         BuildMethod::Railpack { use_buildctl } => {
@@ -235,10 +330,13 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
                 buildx_supports_push: container_cli.buildx_supports_push(),
                 use_buildctl,
                 push: options.push,
+                push_retries: options.push_retries,
                 buildkit_host: buildkit_host.as_deref(),
                 env: &options.env,
                 no_cache: options.no_cache,
                 platform: &options.platform,
+                git_token: options.git_token.as_deref(),
+                cache_dir,
             })?;
         }
         BuildMethod::Buildctl => {
@@ -274,9 +372,14 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
                 (None, original_dockerfile_path)
             };
 
-            // Parse env vars into HashMap for secrets
+            // Build args are passed as Dockerfile ARGs and, for `RUN --mount=type=secret`
+            // steps, as secrets
+            let build_args = proxy::parse_env_vars(&options.env)?;
             let mut secrets = proxy::read_and_transform_proxy_vars();
-            secrets.extend(proxy::parse_env_vars(&options.env)?);
+            secrets.extend(build_args.clone());
+            if let Some(ref git_token) = options.git_token {
+                secrets.insert(git_auth::GIT_TOKEN_SECRET.to_string(), git_token.clone());
+            }
 
             // Add SSL cert using named build context (bind mount)
             // RAII cleanup via SslCertContext drop
@@ -306,11 +409,15 @@ pub(crate) fn build_image(options: BuildOptions) -> Result<()> {
                 options.push,
                 buildkit_host.as_deref(),
                 &secrets,
+                &build_args,
                 &local_contexts,
                 BuildctlFrontend::Dockerfile,
                 options.no_cache,
                 container_cli.command(),
                 &options.platform,
+                options.ssh_key.as_deref(),
+                cache_dir,
+                options.push_retries,
             )?;
 
             // Note: SslCertContext cleanup is automatic via RAII when it goes out of scope
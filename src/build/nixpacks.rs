@@ -0,0 +1,74 @@
+// Nixpacks implementation (nixpacks CLI)
+
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+use tracing::{debug, info};
+
+/// Build image using Nixpacks (nixpacks CLI)
+pub(crate) fn build_image_with_nixpacks(
+    app_path: &str,
+    image_tag: &str,
+    env: &[String],
+    no_cache: bool,
+    platform: &str,
+) -> Result<()> {
+    // Check if nixpacks CLI is available
+    let nixpacks_check = Command::new("nixpacks").arg("--version").output();
+
+    if nixpacks_check.is_err() {
+        bail!(
+            "nixpacks CLI not found. Please install it from https://nixpacks.com/docs/install\n\
+             On macOS: brew install nixpacks\n\
+             On Linux: curl -sSL https://nixpacks.com/install.sh | bash"
+        );
+    }
+
+    let mut cmd = Command::new("nixpacks");
+    cmd.arg("build")
+        .arg(app_path)
+        .arg("--name")
+        .arg(image_tag)
+        .arg("--platform")
+        .arg(platform);
+
+    // Add no-cache flag if requested
+    if no_cache {
+        cmd.arg("--no-cache");
+    }
+
+    // Add environment variables if specified. Nixpacks only accepts KEY=VALUE,
+    // so KEY-only entries are resolved from the current environment here.
+    if !env.is_empty() {
+        let mut env_vars: Vec<_> = super::proxy::parse_env_vars(env)?.into_iter().collect();
+        env_vars.sort();
+        info!(
+            "Using environment variables: {:?}",
+            env_vars.iter().map(|(key, _)| key).collect::<Vec<_>>()
+        );
+        for (key, value) in env_vars {
+            cmd.arg("--env").arg(format!("{}={}", key, value));
+        }
+    }
+
+    // Add proxy environment variables
+    let proxy_vars = super::proxy::read_and_transform_proxy_vars();
+    if !proxy_vars.is_empty() {
+        info!("Injecting proxy variables for nixpacks build");
+        for arg in super::proxy::format_for_pack(&proxy_vars) {
+            cmd.arg("--env").arg(arg);
+        }
+    }
+
+    // Never push from nixpacks - always build locally and push separately,
+    // like the pack backend
+
+    debug!("Executing command: {:?}", cmd);
+
+    let status = cmd.status().context("Failed to execute nixpacks build")?;
+
+    if !status.success() {
+        bail!("nixpacks build failed with status: {}", status);
+    }
+
+    Ok(())
+}
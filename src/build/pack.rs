@@ -8,6 +8,7 @@ use tracing::{debug, info};
 use super::ssl::{SSL_CERT_PATHS, SSL_ENV_VARS};
 
 /// Build image using Cloud Native Buildpacks (pack CLI)
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn build_image_with_buildpacks(
     app_path: &str,
     image_tag: &str,
@@ -16,6 +17,7 @@ pub(crate) fn build_image_with_buildpacks(
     env: &[String],
     no_cache: bool,
     platform: &str,
+    cache_dir: Option<&Path>,
 ) -> Result<()> {
     // Check if pack CLI is available
     let pack_check = Command::new("pack").arg("version").output();
@@ -50,6 +52,15 @@ pub(crate) fn build_image_with_buildpacks(
         cmd.arg("--clear-cache");
     }
 
+    // Keep the build cache in a bind-mounted host directory instead of a Docker
+    // volume, so it survives `docker volume prune` and is scoped per project
+    if let Some(cache_dir) = cache_dir {
+        cmd.arg("--cache").arg(format!(
+            "type=build;format=bind;source={}",
+            cache_dir.display()
+        ));
+    }
+
     // Add buildpacks if specified
     if !buildpacks.is_empty() {
         info!("Using buildpacks: {:?}", buildpacks);
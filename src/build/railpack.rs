@@ -9,7 +9,7 @@ use tracing::{debug, info, warn};
 
 use super::buildkit::ensure_buildx_builder;
 use super::proxy;
-use super::registry::docker_push;
+use super::registry::{docker_push, run_build_with_push_retries};
 use super::ssl::embed_ssl_cert_in_plan;
 
 /// BuildKit frontend type for buildctl
@@ -29,10 +29,13 @@ pub(crate) struct RailpackBuildOptions<'a> {
     pub buildx_supports_push: bool,
     pub use_buildctl: bool,
     pub push: bool,
+    pub push_retries: u32,
     pub buildkit_host: Option<&'a str>,
     pub env: &'a [String],
     pub no_cache: bool,
     pub platform: &'a str,
+    pub git_token: Option<&'a str>,
+    pub cache_dir: Option<&'a Path>,
 }
 
 /// RAII guard for cleaning up temp files and directories
@@ -109,6 +112,10 @@ pub(crate) fn build_image_with_railpacks(options: RailpackBuildOptions) -> Resul
     let mut all_secrets = proxy::read_and_transform_proxy_vars();
     let user_env_vars = proxy::parse_env_vars(options.env)?;
     all_secrets.extend(user_env_vars);
+    if let Some(git_token) = options.git_token {
+        info!("Configuring git to authenticate with the provided git token");
+        all_secrets.extend(super::git_auth::git_credential_secrets(git_token));
+    }
 
     // Add SSL env vars before railpack prepare so they are declared in the plan
     // and exposed as secrets during build-time RUN steps.
@@ -198,11 +205,15 @@ pub(crate) fn build_image_with_railpacks(options: RailpackBuildOptions) -> Resul
             options.push,
             options.buildkit_host,
             &all_secrets,
+            &HashMap::new(), // Railpack reads build args from secrets
             &HashMap::new(), // No local contexts for Railpack
             BuildctlFrontend::Railpack,
             options.no_cache,
             options.container_cli,
             options.platform,
+            None, // Railpack plans have no ssh mounts
+            options.cache_dir,
+            options.push_retries,
         )?;
     } else {
         build_with_buildx(
@@ -212,16 +223,37 @@ pub(crate) fn build_image_with_railpacks(options: RailpackBuildOptions) -> Resul
             options.container_cli,
             options.buildx_supports_push,
             options.push,
+            options.push_retries,
             options.buildkit_host,
             &all_secrets,
             options.no_cache,
             options.platform,
+            options.cache_dir,
         )?;
     }
 
     Ok(())
 }
 
+/// Add import/export flags for a local BuildKit cache directory.
+///
+/// The import is skipped until a previous build exported a cache, and when the
+/// build cache is disabled; the export always runs so the cache stays fresh.
+fn add_local_cache_args(
+    cmd: &mut Command,
+    import_flag: &str,
+    export_flag: &str,
+    cache_dir: &Path,
+    no_cache: bool,
+) {
+    let (import, export) = super::cache::buildkit_cache_args(cache_dir);
+    if !no_cache && super::cache::has_buildkit_cache(cache_dir) {
+        debug!("Importing BuildKit cache from {}", cache_dir.display());
+        cmd.arg(import_flag).arg(import);
+    }
+    cmd.arg(export_flag).arg(export);
+}
+
 /// Build with docker buildx
 #[allow(clippy::too_many_arguments)]
 fn build_with_buildx(
@@ -231,10 +263,12 @@ fn build_with_buildx(
     container_cli: &str,
     buildx_supports_push: bool,
     push: bool,
+    push_retries: u32,
     buildkit_host: Option<&str>,
     secrets: &HashMap<String, String>,
     no_cache: bool,
     platform: &str,
+    cache_dir: Option<&Path>,
 ) -> Result<()> {
     // Check buildx availability
     if !super::docker::is_buildx_available(container_cli) {
@@ -278,6 +312,18 @@ fn build_with_buildx(
         cmd.arg("--no-cache");
     }
 
+    // The default docker driver cannot export a local cache, so the cache
+    // directory is only used with a BuildKit builder
+    match (cache_dir, builder_name.is_some()) {
+        (Some(cache_dir), true) => {
+            add_local_cache_args(&mut cmd, "--cache-from", "--cache-to", cache_dir, no_cache)
+        }
+        (Some(_), false) => info!(
+            "Build cache directory not used: the default buildx driver cannot export a local cache"
+        ),
+        (None, _) => {}
+    }
+
     let needs_fallback_push =
         super::docker::configure_buildx_output(&mut cmd, push, buildx_supports_push);
 
@@ -304,6 +350,15 @@ fn build_with_buildx(
 
     cmd.arg(app_path);
 
+    // buildx pushes as part of the build
+    if push && !needs_fallback_push {
+        return run_build_with_push_retries(
+            &mut cmd,
+            &format!("{} buildx build", container_cli),
+            push_retries,
+        );
+    }
+
     debug!("Executing command: {:?}", cmd);
 
     let status = cmd
@@ -319,7 +374,7 @@ fn build_with_buildx(
     }
 
     if needs_fallback_push {
-        docker_push(container_cli, image_tag)?;
+        docker_push(container_cli, image_tag, push_retries)?;
     }
 
     Ok(())
@@ -335,6 +390,9 @@ fn build_with_buildx(
 /// - key: environment variable name
 /// - value: the actual secret value (passed to the build via prefixed env vars)
 ///
+/// The `build_args` HashMap is passed to the Dockerfile frontend as `ARG` values
+/// (ignored by the Railpack frontend).
+///
 /// The `local_contexts` HashMap contains named build contexts:
 /// - key: context name (e.g., "rise-internal-ssl-cert")
 /// - value: local path to the context directory
@@ -346,11 +404,15 @@ pub(crate) fn build_with_buildctl(
     push: bool,
     buildkit_host: Option<&str>,
     secrets: &HashMap<String, String>,
+    build_args: &HashMap<String, String>,
     local_contexts: &HashMap<String, String>,
     frontend: BuildctlFrontend,
     no_cache: bool,
     container_cli: &str,
     platform: &str,
+    ssh_key: Option<&str>,
+    cache_dir: Option<&Path>,
+    push_retries: u32,
 ) -> Result<()> {
     // Check buildctl availability
     let buildctl_check = Command::new("buildctl").arg("--version").output();
@@ -384,6 +446,12 @@ pub(crate) fn build_with_buildctl(
                     cmd.arg("--opt").arg(format!("filename={}", filename_str));
                 }
             }
+            let mut keys: Vec<&String> = build_args.keys().collect();
+            keys.sort();
+            for key in keys {
+                cmd.arg("--opt")
+                    .arg(format!("build-arg:{}={}", key, build_args[key]));
+            }
         }
         BuildctlFrontend::Railpack => {
             cmd.arg("--frontend=gateway.v0")
@@ -410,24 +478,48 @@ pub(crate) fn build_with_buildctl(
     // proxy vars while build containers get the transformed values.
     proxy::add_secrets_to_command(&mut cmd, secrets);
 
+    // Forward the SSH key for `RUN --mount=type=ssh`
+    if let Some(ssh_key) = ssh_key {
+        info!("Forwarding SSH key to the build");
+        cmd.arg("--ssh")
+            .arg(super::git_auth::ssh_forward_arg(ssh_key));
+    }
+
     // Disable build cache via frontend option (buildctl has no --no-cache flag)
     if no_cache {
         cmd.arg("--opt").arg("no-cache=");
     }
 
-    // --output must be last: its value is the next positional arg
-    if push {
-        cmd.arg("--output").arg(format!(
-            "type=image,name={},push=true,platform={}",
-            image_tag, platform
-        ));
+    if let Some(cache_dir) = cache_dir {
+        add_local_cache_args(
+            &mut cmd,
+            "--import-cache",
+            "--export-cache",
+            cache_dir,
+            no_cache,
+        );
+    }
 
-        debug!("Executing command: {:?}", cmd);
+    // Several platforms are built by the frontend; the exporter then pushes a
+    // manifest list. (A comma-separated list can't go into the CSV --output value.)
+    let multi_platform = super::method::is_multi_platform(platform);
+    if multi_platform {
+        cmd.arg("--opt").arg(format!("platform={}", platform));
+    }
 
-        let status = cmd.status().context("Failed to execute buildctl build")?;
-        if !status.success() {
-            bail!("buildctl build failed with status: {}", status);
-        }
+    // --output must be last: its value is the next positional arg
+    if push {
+        let output = if multi_platform {
+            format!("type=image,name={},push=true", image_tag)
+        } else {
+            format!(
+                "type=image,name={},push=true,platform={}",
+                image_tag, platform
+            )
+        };
+        cmd.arg("--output").arg(output);
+
+        run_build_with_push_retries(&mut cmd, "buildctl build", push_retries)?;
     } else {
         // Output as docker tar stream and pipe into `docker load` so the
         // image is available in the local Docker daemon.
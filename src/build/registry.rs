@@ -1,28 +1,206 @@
 // Container registry operations (push and login)
 
 use anyhow::{bail, Context, Result};
+use std::collections::VecDeque;
+use std::io::BufRead;
 use std::path::PathBuf;
-use std::process::Command;
-use tracing::{debug, info};
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Base delay before the first push retry; doubled for every further attempt
+const PUSH_RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Upper bound for the delay between push attempts
+const PUSH_RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Lines of a pushing build's stderr kept to tell a failed push from a failed build
+const BUILD_STDERR_TAIL_LINES: usize = 50;
+
+/// Delay before retry number `retry` (1-based): 2s, 4s, 8s, ... capped at 30s
+fn push_retry_delay(retry: u32) -> Duration {
+    let factor = 1u32 << retry.saturating_sub(1).min(16);
+    (PUSH_RETRY_BASE_DELAY * factor).min(PUSH_RETRY_MAX_DELAY)
+}
+
+/// Whether a push error is an authentication/authorization failure, which
+/// retrying cannot fix
+fn is_auth_failure(registry_response: &str) -> bool {
+    let response = registry_response.to_lowercase();
+    [
+        "unauthorized",
+        "authentication required",
+        "denied",
+        "forbidden",
+    ]
+    .iter()
+    .any(|needle| response.contains(needle))
+}
 
 /// Push image to container registry
-pub(crate) fn docker_push(container_cli: &str, image_tag: &str) -> Result<()> {
+///
+/// Transient failures (network errors, registry 5xx) are retried up to `retries`
+/// times with exponential backoff. Authentication failures fail immediately. The
+/// returned error includes the registry's last response.
+pub(crate) fn docker_push(container_cli: &str, image_tag: &str, retries: u32) -> Result<()> {
     info!("Pushing image to registry: {}", image_tag);
 
-    let mut cmd = Command::new(container_cli);
-    cmd.arg("push").arg(image_tag);
+    let max_attempts = retries.saturating_add(1);
+    let mut attempt = 1;
+    loop {
+        let mut cmd = Command::new(container_cli);
+        cmd.arg("push")
+            .arg(image_tag)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::piped());
 
-    debug!("Executing command: {:?}", cmd);
+        debug!(
+            "Executing command (attempt {}/{}): {:?}",
+            attempt, max_attempts, cmd
+        );
 
-    let status = cmd
-        .status()
-        .with_context(|| format!("Failed to execute {} push", container_cli))?;
+        let output = cmd
+            .output()
+            .with_context(|| format!("Failed to execute {} push", container_cli))?;
 
-    if !status.success() {
-        bail!("{} push failed with status: {}", container_cli, status);
+        // Stderr is captured to report the registry response, so forward it as well
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprint!("{}", stderr);
+
+        if output.status.success() {
+            if attempt > 1 {
+                info!("Push succeeded on attempt {}/{}", attempt, max_attempts);
+            }
+            return Ok(());
+        }
+
+        let registry_response = stderr.trim();
+        if is_auth_failure(registry_response) {
+            bail!(
+                "{} push failed with status: {} (authentication error, not retried)\nRegistry response: {}",
+                container_cli,
+                output.status,
+                registry_response
+            );
+        }
+
+        if attempt >= max_attempts {
+            bail!(
+                "{} push failed with status: {} after {} attempt(s)\nRegistry response: {}",
+                container_cli,
+                output.status,
+                attempt,
+                registry_response
+            );
+        }
+
+        let delay = push_retry_delay(attempt);
+        warn!(
+            "Push attempt {}/{} failed with status: {}; retrying in {}s",
+            attempt,
+            max_attempts,
+            output.status,
+            delay.as_secs()
+        );
+        std::thread::sleep(delay);
+        attempt += 1;
     }
+}
 
-    Ok(())
+/// Run a build that pushes the image itself (`buildx build --push`, `buildctl build`
+/// with `push=true`)
+///
+/// When BuildKit reports that the push failed, the build is run again up to `retries`
+/// times with the same backoff as [`docker_push`]. BuildKit reuses the layers it already
+/// built, so a repeated run only redoes the export and push. Build errors and
+/// authentication failures fail immediately.
+pub(crate) fn run_build_with_push_retries(
+    cmd: &mut Command,
+    description: &str,
+    retries: u32,
+) -> Result<()> {
+    let max_attempts = retries.saturating_add(1);
+    let mut attempt = 1;
+    loop {
+        debug!(
+            "Executing command (attempt {}/{}): {:?}",
+            attempt, max_attempts, cmd
+        );
+
+        let (status, stderr_tail) = run_forwarding_stderr(cmd)
+            .with_context(|| format!("Failed to execute {}", description))?;
+
+        if status.success() {
+            if attempt > 1 {
+                info!("Push succeeded on attempt {}/{}", attempt, max_attempts);
+            }
+            return Ok(());
+        }
+
+        let Some(push_error) = push_failure(&stderr_tail) else {
+            bail!("{} failed with status: {}", description, status);
+        };
+        if is_auth_failure(push_error) {
+            bail!(
+                "{} failed to push with status: {} (authentication error, not retried)\nRegistry response: {}",
+                description,
+                status,
+                push_error
+            );
+        }
+
+        if attempt >= max_attempts {
+            bail!(
+                "{} failed to push with status: {} after {} attempt(s)\nRegistry response: {}",
+                description,
+                status,
+                attempt,
+                push_error
+            );
+        }
+
+        let delay = push_retry_delay(attempt);
+        warn!(
+            "Push attempt {}/{} failed with status: {}; retrying in {}s",
+            attempt,
+            max_attempts,
+            status,
+            delay.as_secs()
+        );
+        std::thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+/// The line of a BuildKit error output reporting a failed push, if the push failed
+fn push_failure(stderr: &str) -> Option<&str> {
+    stderr
+        .lines()
+        .rev()
+        .find(|line| line.to_lowercase().contains("failed to push"))
+        .map(str::trim)
+}
+
+/// Run a command, forwarding its stderr as it is written
+///
+/// Returns the exit status and the last lines of stderr.
+fn run_forwarding_stderr(cmd: &mut Command) -> std::io::Result<(ExitStatus, String)> {
+    let mut child = cmd.stderr(Stdio::piped()).spawn()?;
+
+    let mut tail = VecDeque::with_capacity(BUILD_STDERR_TAIL_LINES);
+    if let Some(stderr) = child.stderr.take() {
+        for line in std::io::BufReader::new(stderr).split(b'\n') {
+            let line = String::from_utf8_lossy(&line?).into_owned();
+            eprintln!("{}", line);
+            if tail.len() == BUILD_STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+    }
+
+    let status = child.wait()?;
+    Ok((status, Vec::from(tail).join("\n")))
 }
 
 /// Pull image from a registry
@@ -155,7 +333,7 @@ pub(crate) fn docker_login(
         .arg("--username")
         .arg(username)
         .arg("--password-stdin")
-        .stdin(std::process::Stdio::piped())
+        .stdin(Stdio::piped())
         .spawn()
         .and_then(|mut child| {
             use std::io::Write;
@@ -172,3 +350,44 @@ pub(crate) fn docker_login(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_retry_delay_doubles_up_to_cap() {
+        assert_eq!(push_retry_delay(1), Duration::from_secs(2));
+        assert_eq!(push_retry_delay(2), Duration::from_secs(4));
+        assert_eq!(push_retry_delay(3), Duration::from_secs(8));
+        assert_eq!(push_retry_delay(5), Duration::from_secs(30));
+        assert_eq!(push_retry_delay(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_push_failure_finds_buildkit_push_error() {
+        let stderr = "#12 exporting to image\n\
+            #12 pushing layers 1.2s done\n\
+            ERROR: failed to solve: failed to push registry.example.com/app:1: 502 Bad Gateway\n";
+        assert_eq!(
+            push_failure(stderr),
+            Some("ERROR: failed to solve: failed to push registry.example.com/app:1: 502 Bad Gateway")
+        );
+
+        let build_error = "#8 [build 3/4] RUN cargo build\n\
+            ERROR: failed to solve: process \"/bin/sh -c cargo build\" did not complete successfully: exit code: 101\n";
+        assert_eq!(push_failure(build_error), None);
+    }
+
+    #[test]
+    fn test_is_auth_failure() {
+        assert!(is_auth_failure("unauthorized: authentication required"));
+        assert!(is_auth_failure(
+            "denied: requested access to the resource is denied"
+        ));
+        assert!(!is_auth_failure(
+            "received unexpected HTTP status: 503 Service Unavailable"
+        ));
+        assert!(!is_auth_failure("dial tcp: i/o timeout"));
+    }
+}
@@ -298,6 +298,197 @@ impl Config {
     }
 }
 
+/// Settings of the CLI config file that `rise config` can read and change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ConfigKey {
+    /// Rise backend URL
+    #[value(alias = "backend_url")]
+    BackendUrl,
+    /// Authentication token (set by `rise login`)
+    Token,
+    /// Container CLI used for builds (docker or podman)
+    #[value(alias = "container_cli")]
+    ContainerCli,
+    /// Whether builds use a Rise-managed BuildKit daemon
+    #[value(alias = "managed_buildkit")]
+    ManagedBuildkit,
+}
+
+impl ConfigKey {
+    pub const ALL: [ConfigKey; 4] = [
+        ConfigKey::BackendUrl,
+        ConfigKey::Token,
+        ConfigKey::ContainerCli,
+        ConfigKey::ManagedBuildkit,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigKey::BackendUrl => "backend-url",
+            ConfigKey::Token => "token",
+            ConfigKey::ContainerCli => "container-cli",
+            ConfigKey::ManagedBuildkit => "managed-buildkit",
+        }
+    }
+
+    /// Environment variable that takes precedence over the config file
+    pub fn env_var(&self) -> &'static str {
+        match self {
+            ConfigKey::BackendUrl => "RISE_URL",
+            ConfigKey::Token => "RISE_TOKEN",
+            ConfigKey::ContainerCli => "RISE_CONTAINER_CLI",
+            ConfigKey::ManagedBuildkit => "RISE_MANAGED_BUILDKIT",
+        }
+    }
+}
+
+impl Config {
+    /// Value stored in the config file for `key`, if any
+    pub fn get_value(&self, key: ConfigKey) -> Option<String> {
+        match key {
+            ConfigKey::BackendUrl => self.backend_url.clone(),
+            ConfigKey::Token => self.token.clone(),
+            ConfigKey::ContainerCli => self.container_cli.clone(),
+            ConfigKey::ManagedBuildkit => self.managed_buildkit.map(|v| v.to_string()),
+        }
+    }
+
+    /// Validate `value` and store it for `key` (not yet saved to disk)
+    pub fn set_value(&mut self, key: ConfigKey, value: &str) -> Result<()> {
+        let value = value.trim();
+        if value.is_empty() {
+            anyhow::bail!(
+                "Value for '{}' cannot be empty (use 'rise config unset {}' to clear it)",
+                key.as_str(),
+                key.as_str()
+            );
+        }
+        match key {
+            ConfigKey::BackendUrl => {
+                if !value.starts_with("http://") && !value.starts_with("https://") {
+                    anyhow::bail!(
+                        "Invalid backend-url '{}': must start with http:// or https://",
+                        value
+                    );
+                }
+                self.backend_url = Some(normalize_backend_url(value));
+            }
+            ConfigKey::Token => self.token = Some(value.to_string()),
+            ConfigKey::ContainerCli => self.container_cli = Some(value.to_string()),
+            ConfigKey::ManagedBuildkit => {
+                let enabled = match value.to_lowercase().as_str() {
+                    "true" | "1" | "yes" | "on" => true,
+                    "false" | "0" | "no" | "off" => false,
+                    _ => anyhow::bail!(
+                        "Invalid managed-buildkit '{}': expected true or false",
+                        value
+                    ),
+                };
+                self.managed_buildkit = Some(enabled);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the stored value for `key` (not yet saved to disk)
+    pub fn unset_value(&mut self, key: ConfigKey) {
+        match key {
+            ConfigKey::BackendUrl => self.backend_url = None,
+            ConfigKey::Token => self.token = None,
+            ConfigKey::ContainerCli => self.container_cli = None,
+            ConfigKey::ManagedBuildkit => self.managed_buildkit = None,
+        }
+    }
+
+    /// Value the CLI uses for `key` and where it comes from: the environment
+    /// variable, the config file, or the built-in default
+    fn effective_value(&self, key: ConfigKey) -> (Option<String>, String) {
+        if let Ok(value) = std::env::var(key.env_var()) {
+            return (
+                Some(value),
+                format!("{} environment variable", key.env_var()),
+            );
+        }
+        if let Some(value) = self.get_value(key) {
+            return (Some(value), "config file".to_string());
+        }
+        let default = match key {
+            ConfigKey::BackendUrl => Some(self.get_backend_url()),
+            ConfigKey::ManagedBuildkit => Some("false".to_string()),
+            ConfigKey::Token | ConfigKey::ContainerCli => None,
+        };
+        (default, "default".to_string())
+    }
+}
+
+/// Display form of a config value; tokens are redacted unless `reveal` is set
+fn display_value(key: ConfigKey, value: &str, reveal: bool) -> String {
+    if key == ConfigKey::Token && !reveal {
+        "<redacted>".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Print all CLI settings with their effective values and sources
+pub fn show_config(config: &Config, reveal: bool) -> Result<()> {
+    println!("Config file: {}", Config::config_path()?.display());
+    println!();
+    for key in ConfigKey::ALL {
+        let (value, source) = config.effective_value(key);
+        let value = match (key, value) {
+            (_, Some(value)) => display_value(key, &value, reveal),
+            (ConfigKey::ContainerCli, None) => "(auto-detect)".to_string(),
+            (_, None) => "(not set)".to_string(),
+        };
+        println!("  {:<17} {}  [{}]", key.as_str(), value, source);
+    }
+    Ok(())
+}
+
+/// Print the effective value of a single CLI setting
+pub fn get_config(config: &Config, key: ConfigKey, reveal: bool) -> Result<()> {
+    match config.effective_value(key) {
+        (Some(value), _) => {
+            println!("{}", display_value(key, &value, reveal));
+            Ok(())
+        }
+        (None, _) => anyhow::bail!("'{}' is not set", key.as_str()),
+    }
+}
+
+/// Store a CLI setting in the config file
+pub fn set_config(config: &mut Config, key: ConfigKey, value: &str) -> Result<()> {
+    config.set_value(key, value)?;
+    config.save()?;
+    let value = config.get_value(key).unwrap_or_default();
+    println!(
+        "Set {} to {}",
+        key.as_str(),
+        display_value(key, &value, false)
+    );
+    warn_if_overridden(key);
+    Ok(())
+}
+
+/// Remove a CLI setting from the config file
+pub fn unset_config(config: &mut Config, key: ConfigKey) -> Result<()> {
+    config.unset_value(key);
+    config.save()?;
+    println!("Unset {}", key.as_str());
+    warn_if_overridden(key);
+    Ok(())
+}
+
+fn warn_if_overridden(key: ConfigKey) {
+    if std::env::var(key.env_var()).is_ok() {
+        eprintln!(
+            "Note: {} is set and takes precedence over the config file",
+            key.env_var()
+        );
+    }
+}
+
 /// Auto-detect which container CLI is available.
 ///
 /// Checks `docker` first, then `podman`. Also detects the case where
@@ -390,6 +581,48 @@ mod tests {
         assert!(!c.get_managed_buildkit());
     }
 
+    #[test]
+    fn test_set_value_validates_and_normalizes() {
+        let mut c = Config::default();
+
+        c.set_value(ConfigKey::BackendUrl, "https://rise.example.com/")
+            .unwrap();
+        assert_eq!(
+            c.get_value(ConfigKey::BackendUrl).as_deref(),
+            Some("https://rise.example.com")
+        );
+        assert!(c
+            .set_value(ConfigKey::BackendUrl, "rise.example.com")
+            .is_err());
+
+        c.set_value(ConfigKey::ManagedBuildkit, "yes").unwrap();
+        assert_eq!(c.managed_buildkit, Some(true));
+        assert!(c.set_value(ConfigKey::ManagedBuildkit, "maybe").is_err());
+
+        assert!(c.set_value(ConfigKey::ContainerCli, " ").is_err());
+        c.set_value(ConfigKey::ContainerCli, "podman").unwrap();
+        assert_eq!(c.container_cli.as_deref(), Some("podman"));
+
+        c.unset_value(ConfigKey::ContainerCli);
+        assert_eq!(c.get_value(ConfigKey::ContainerCli), None);
+    }
+
+    #[test]
+    fn test_display_value_redacts_token() {
+        assert_eq!(
+            display_value(ConfigKey::Token, "secret-token", false),
+            "<redacted>"
+        );
+        assert_eq!(
+            display_value(ConfigKey::Token, "secret-token", true),
+            "secret-token"
+        );
+        assert_eq!(
+            display_value(ConfigKey::ContainerCli, "docker", false),
+            "docker"
+        );
+    }
+
     #[test]
     fn test_runtime_from_version_output_docker_sample() {
         // Sample Docker output:
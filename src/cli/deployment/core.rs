@@ -4,11 +4,19 @@ use comfy_table::{
 };
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
 use crate::build::{self, BuildOptions};
+use crate::cli::output::{print_json, OutputFormat};
 use crate::config::Config;
+use crate::rise_toml::{
+    DeploymentProtocol, DeploymentStrategy, HealthCheckConfig, ImagePullPolicy,
+    InitContainerConfig, PortConfig, RateLimitConfig, SchedulingConfig, ServiceAccountConfig,
+    VolumeConfig,
+};
 
 // Re-export models from API module (always available)
 pub use crate::api::models::{Deployment, DeploymentStatus};
@@ -78,6 +86,64 @@ pub async fn fetch_deployment(
     Ok(deployment)
 }
 
+/// Fetch the most recent deployments in a deployment group
+pub async fn fetch_group_deployments(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    group: &str,
+    limit: usize,
+) -> Result<Vec<Deployment>> {
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments?group={}&limit={}",
+        backend_url,
+        project,
+        urlencoding::encode(group),
+        limit
+    );
+    fetch_deployment_list(http_client, &url, token).await
+}
+
+/// Fetch the most recent deployments of a project across all groups
+pub(super) async fn fetch_project_deployments(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    limit: usize,
+) -> Result<Vec<Deployment>> {
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments?limit={}",
+        backend_url, project, limit
+    );
+    fetch_deployment_list(http_client, &url, token).await
+}
+
+async fn fetch_deployment_list(
+    http_client: &Client,
+    url: &str,
+    token: &str,
+) -> Result<Vec<Deployment>> {
+    let response = http_client
+        .get(url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .context("Failed to list deployments")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!("Failed to list deployments ({}): {}", status, error_text);
+    }
+
+    response.json().await.context("Failed to parse deployments")
+}
+
 /// List deployments for a project
 pub async fn list_deployments(
     http_client: &Client,
@@ -86,6 +152,7 @@ pub async fn list_deployments(
     project: &str,
     group: Option<&str>,
     limit: usize,
+    output: OutputFormat,
 ) -> Result<()> {
     let token = config
         .get_token()
@@ -131,6 +198,10 @@ pub async fn list_deployments(
     // Limit results
     deployments.truncate(limit);
 
+    if output == OutputFormat::Json {
+        return print_json(&deployments);
+    }
+
     if deployments.is_empty() {
         println!("No deployments found for project '{}'", project);
         return Ok(());
@@ -160,6 +231,7 @@ pub async fn list_deployments(
             Cell::new("STATUS").add_attribute(Attribute::Bold),
             Cell::new("CREATED BY").add_attribute(Attribute::Bold),
             Cell::new("IMAGE").add_attribute(Attribute::Bold),
+            Cell::new("MESSAGE").add_attribute(Attribute::Bold),
             Cell::new("GROUP").add_attribute(Attribute::Bold),
             Cell::new("EXPIRY").add_attribute(Attribute::Bold),
             Cell::new("CREATED").add_attribute(Attribute::Bold),
@@ -205,11 +277,21 @@ pub async fn list_deployments(
         // Format image (show the image tag or "-" if not set)
         let image_display = deployment.image.as_deref().unwrap_or("-");
 
+        // Format message (truncated, or "-" if not set)
+        let message_display = match deployment.message {
+            Some(ref message) if message.chars().count() > 40 => {
+                format!("{}...", message.chars().take(37).collect::<String>())
+            }
+            Some(ref message) => message.clone(),
+            None => "-".to_string(),
+        };
+
         // Create cells with appropriate styling
         let mut deployment_cell = Cell::new(&deployment_display);
         let mut status_cell = Cell::new(deployment.status.to_string());
         let mut created_by_cell = Cell::new(&deployment.created_by_email);
         let mut image_cell = Cell::new(image_display);
+        let mut message_cell = Cell::new(&message_display);
         let mut group_cell = Cell::new(&deployment.deployment_group);
         let mut expiry_cell = Cell::new(&expiry);
         let mut created_cell = Cell::new(&created);
@@ -221,6 +303,7 @@ pub async fn list_deployments(
             status_cell = status_cell.add_attribute(Attribute::Bold);
             created_by_cell = created_by_cell.add_attribute(Attribute::Bold);
             image_cell = image_cell.add_attribute(Attribute::Bold);
+            message_cell = message_cell.add_attribute(Attribute::Bold);
             group_cell = group_cell.add_attribute(Attribute::Bold);
             expiry_cell = expiry_cell.add_attribute(Attribute::Bold);
             created_cell = created_cell.add_attribute(Attribute::Bold);
@@ -250,6 +333,7 @@ pub async fn list_deployments(
             status_cell,
             created_by_cell,
             image_cell,
+            message_cell,
             group_cell,
             expiry_cell,
             created_cell,
@@ -263,104 +347,849 @@ pub async fn list_deployments(
     Ok(())
 }
 
-/// Show deployment details and optionally follow until terminal state
-pub async fn show_deployment(
+/// Show deployment details and optionally follow until terminal state
+///
+/// With `json`, the deployment is printed as JSON instead; when following, one
+/// line of JSON is printed each time the deployment changes.
+#[allow(clippy::too_many_arguments)]
+pub async fn show_deployment(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: &str,
+    follow: bool,
+    timeout_str: &str,
+    json: bool,
+) -> Result<()> {
+    let deployment = if follow && json {
+        super::follow_ui::follow_deployment_json(
+            http_client,
+            backend_url,
+            config,
+            project,
+            deployment_id,
+            timeout_str,
+        )
+        .await?
+    } else if follow {
+        // Use new enhanced UI for follow mode
+        super::follow_ui::follow_deployment_with_ui(
+            http_client,
+            backend_url,
+            config,
+            project,
+            deployment_id,
+            timeout_str,
+        )
+        .await?
+    } else {
+        // One-shot display (no follow)
+        let token = config
+            .token
+            .as_ref()
+            .context("Not logged in. Please run 'rise login' first.")?;
+
+        let deployment =
+            fetch_deployment(http_client, backend_url, token, project, deployment_id).await?;
+
+        if json {
+            print_json(&deployment)?;
+        } else {
+            // Use the same UI as follow mode
+            super::follow_ui::print_deployment_snapshot(&deployment);
+        }
+        deployment
+    };
+
+    // Exit with error if deployment failed
+    if deployment.status == DeploymentStatus::Failed {
+        if let Some(error) = deployment.error_message {
+            bail!("Deployment failed: {}", error);
+        } else {
+            bail!("Deployment failed");
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    reason: String,
+    message: String,
+    object: String,
+    count: i32,
+    last_seen: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentEventsResponse {
+    events: Vec<DeploymentEvent>,
+}
+
+/// Show deployment details followed by the runtime events of its workload
+///
+/// Events (e.g. Kubernetes FailedScheduling or image pull errors) explain why a
+/// deployment is stuck when its containers report no error themselves.
+pub async fn describe_deployment(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: &str,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let deployment =
+        fetch_deployment(http_client, backend_url, &token, project, deployment_id).await?;
+    super::follow_ui::print_deployment_snapshot(&deployment);
+
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/{}/events",
+        backend_url, project, deployment_id
+    );
+
+    let response = http_client
+        .get(&url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .context("Failed to fetch deployment events")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!(
+            "Failed to fetch deployment events ({}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let events: DeploymentEventsResponse = response
+        .json()
+        .await
+        .context("Failed to parse deployment events")?;
+
+    println!();
+    if events.events.is_empty() {
+        println!("Events: <none>");
+        return Ok(());
+    }
+
+    println!("Events:");
+    let now = chrono::Utc::now();
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            Cell::new("TYPE").add_attribute(Attribute::Bold),
+            Cell::new("REASON").add_attribute(Attribute::Bold),
+            Cell::new("AGE").add_attribute(Attribute::Bold),
+            Cell::new("OBJECT").add_attribute(Attribute::Bold),
+            Cell::new("MESSAGE").add_attribute(Attribute::Bold),
+        ]);
+
+    for event in events.events {
+        let mut age = event
+            .last_seen
+            .map(|last_seen| crate::cli::project::format_age(now - last_seen))
+            .unwrap_or_else(|| "-".to_string());
+        if event.count > 1 {
+            age = format!("{} (x{})", age, event.count);
+        }
+
+        let mut type_cell = Cell::new(&event.event_type);
+        if event.event_type == "Warning" {
+            type_cell = type_cell.fg(Color::Yellow);
+        }
+        table.add_row(vec![
+            type_cell,
+            Cell::new(&event.reason),
+            Cell::new(age),
+            Cell::new(&event.object),
+            Cell::new(&event.message),
+        ]);
+    }
+
+    println!("{}", table);
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentAuditEvent {
+    event_type: String,
+    status: String,
+    message: Option<String>,
+    actor_email: Option<String>,
+    actor_is_service_account: bool,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentHistoryResponse {
+    events: Vec<DeploymentAuditEvent>,
+}
+
+/// Show the recorded lifecycle events of a deployment, oldest first
+pub async fn show_deployment_history(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: &str,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/{}/history",
+        backend_url, project, deployment_id
+    );
+
+    let response = http_client
+        .get(&url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .context("Failed to fetch deployment history")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!(
+            "Failed to fetch deployment history ({}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let history: DeploymentHistoryResponse = response
+        .json()
+        .await
+        .context("Failed to parse deployment history")?;
+
+    if history.events.is_empty() {
+        println!("No history recorded for deployment '{}'", deployment_id);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            Cell::new("TIME").add_attribute(Attribute::Bold),
+            Cell::new("EVENT").add_attribute(Attribute::Bold),
+            Cell::new("STATUS").add_attribute(Attribute::Bold),
+            Cell::new("ACTOR").add_attribute(Attribute::Bold),
+            Cell::new("MESSAGE").add_attribute(Attribute::Bold),
+        ]);
+
+    for event in history.events {
+        let actor = match event.actor_email {
+            Some(email) if event.actor_is_service_account => format!("{} (service account)", email),
+            Some(email) => email,
+            None => "controller".to_string(),
+        };
+
+        table.add_row(vec![
+            Cell::new(event.created_at.format("%Y-%m-%d %H:%M:%S").to_string()),
+            Cell::new(&event.event_type),
+            Cell::new(&event.status),
+            Cell::new(actor),
+            Cell::new(event.message.as_deref().unwrap_or("")),
+        ]);
+    }
+
+    println!("{}", table);
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct EnvKeyDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RollbackPreview {
+    deployment_group: String,
+    target_deployment_id: String,
+    target_status: DeploymentStatus,
+    image: String,
+    current_deployment_id: Option<String>,
+    url: Option<String>,
+    env_diff: EnvKeyDiff,
+}
+
+/// Rollback to a previous deployment
+///
+/// Creates a new deployment with the same image as the reference deployment. The
+/// target is `deployment_id`, or the deployment preceding the group's current one
+/// when `None`. With `dry_run`, only the preview is printed.
+pub async fn rollback_deployment(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: Option<&str>,
+    group: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let mut url = format!(
+        "{}/api/v1/projects/{}/deployments/rollback-preview",
+        backend_url, project
+    );
+    let mut params = Vec::new();
+    if let Some(deployment_id) = deployment_id {
+        params.push(format!(
+            "deployment_id={}",
+            urlencoding::encode(deployment_id)
+        ));
+    }
+    if let Some(group) = group {
+        params.push(format!("group={}", urlencoding::encode(group)));
+    }
+    if !params.is_empty() {
+        url = format!("{}?{}", url, params.join("&"));
+    }
+
+    let response = http_client
+        .get(&url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .context("Failed to preview rollback")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!("Failed to preview rollback ({}): {}", status, error_text);
+    }
+
+    let preview: RollbackPreview = response
+        .json()
+        .await
+        .context("Failed to parse rollback preview")?;
+
+    println!(
+        "Rollback target: {} ({}) in group '{}'",
+        preview.target_deployment_id, preview.target_status, preview.deployment_group
+    );
+    println!(
+        "  Current: {}",
+        preview.current_deployment_id.as_deref().unwrap_or("(none)")
+    );
+    println!("  Image:   {}", preview.image);
+    if let Some(ref url) = preview.url {
+        println!("  URL:     {}", url);
+    }
+    if preview.env_diff.added.is_empty() && preview.env_diff.removed.is_empty() {
+        println!("  Env:     no key changes");
+    } else {
+        println!("  Env:");
+        for key in &preview.env_diff.added {
+            println!("    + {}", key);
+        }
+        for key in &preview.env_diff.removed {
+            println!("    - {}", key);
+        }
+    }
+
+    if dry_run {
+        println!("\nDry run: no deployment created");
+        return Ok(());
+    }
+
+    let message = format!("Rollback to {}", preview.target_deployment_id);
+    let deployment = call_create_deployment_api(
+        http_client,
+        backend_url,
+        &token,
+        project,
+        None,
+        Some(&preview.deployment_group),
+        None,
+        None,
+        None,
+        Some(&preview.target_deployment_id),
+        true,
+        false,
+        &[],
+        None,
+        None,
+        Some(&message),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    println!(
+        "\n✓ Created deployment {}:{} rolling back to {}",
+        project, deployment.deployment_id, preview.target_deployment_id
+    );
+
+    Ok(())
+}
+
+/// Promote a deployment to another group
+///
+/// Creates a new deployment in `to_group` that reuses the image and env var
+/// snapshot of `deployment_id`, without rebuilding.
+#[allow(clippy::too_many_arguments)]
+pub async fn promote_deployment(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: &str,
+    to_group: &str,
+    environment: Option<&str>,
+    message: Option<&str>,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/{}/promote",
+        backend_url, project, deployment_id
+    );
+
+    let response = http_client
+        .post(&url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "to": to_group,
+            "environment": environment,
+            "message": message,
+        }))
+        .send()
+        .await
+        .context("Failed to promote deployment")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!("Failed to promote deployment ({}): {}", status, error_text);
+    }
+
+    let deployment: CreateDeploymentResponse = response
+        .json()
+        .await
+        .context("Failed to parse promote response")?;
+
+    println!(
+        "✓ Created deployment {}:{} in group '{}' from {}",
+        project, deployment.deployment_id, to_group, deployment_id
+    );
+    println!("  Image: {}", deployment.image_tag);
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentDiffSide {
+    deployment_id: String,
+    status: DeploymentStatus,
+    image: Option<String>,
+    image_digest: Option<String>,
+    http_port: u16,
+    replicas: u32,
+    cpu: String,
+    memory: String,
+    cpu_limit: Option<String>,
+    memory_limit: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum EnvVarChange {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnvVarDiffEntry {
+    key: String,
+    change: EnvVarChange,
+    is_secret: bool,
+    from_value: Option<String>,
+    to_value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentDiff {
+    from: DeploymentDiffSide,
+    to: DeploymentDiffSide,
+    env: Vec<EnvVarDiffEntry>,
+}
+
+/// The compared fields of one side of a deployment diff, in display order
+fn diff_fields(d: &DeploymentDiffSide) -> [(&'static str, String); 8] {
+    let optional = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    [
+        ("image", optional(&d.image)),
+        ("image_digest", optional(&d.image_digest)),
+        ("http_port", d.http_port.to_string()),
+        ("replicas", d.replicas.to_string()),
+        ("cpu", d.cpu.clone()),
+        ("memory", d.memory.clone()),
+        ("cpu_limit", optional(&d.cpu_limit)),
+        ("memory_limit", optional(&d.memory_limit)),
+    ]
+}
+
+/// Show what changed between two deployments of a project
+///
+/// Prints a unified-diff style comparison of image, port, replicas, resources and env
+/// vars. Secret values are never shown, only whether they changed.
+pub async fn diff_deployments(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    from: &str,
+    to: &str,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/diff?from={}&to={}",
+        backend_url,
+        project,
+        urlencoding::encode(from),
+        urlencoding::encode(to)
+    );
+
+    let response = http_client
+        .get(&url)
+        .bearer_auth(&token)
+        .send()
+        .await
+        .context("Failed to diff deployments")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!("Failed to diff deployments ({}): {}", status, error_text);
+    }
+
+    let diff: DeploymentDiff = response
+        .json()
+        .await
+        .context("Failed to parse deployment diff")?;
+
+    println!("--- {} ({})", diff.from.deployment_id, diff.from.status);
+    println!("+++ {} ({})", diff.to.deployment_id, diff.to.status);
+
+    for ((name, old), (_, new)) in diff_fields(&diff.from)
+        .into_iter()
+        .zip(diff_fields(&diff.to))
+    {
+        if old == new {
+            println!("  {}: {}", name, old);
+        } else {
+            println!("- {}: {}", name, old);
+            println!("+ {}: {}", name, new);
+        }
+    }
+
+    println!("  env:");
+    for var in &diff.env {
+        let line = |value: &Option<String>| match value {
+            Some(value) => format!("{}={}", var.key, value),
+            None => format!("{} (secret)", var.key),
+        };
+        match var.change {
+            EnvVarChange::Unchanged => println!("    {}", line(&var.from_value)),
+            EnvVarChange::Added => println!("+   {}", line(&var.to_value)),
+            EnvVarChange::Removed => println!("-   {}", line(&var.from_value)),
+            EnvVarChange::Changed
+                if var.is_secret && var.from_value.is_none() && var.to_value.is_none() =>
+            {
+                println!("~   {} (secret, value changed)", var.key)
+            }
+            EnvVarChange::Changed => {
+                println!("-   {}", line(&var.from_value));
+                println!("+   {}", line(&var.to_value));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct StopDeploymentsResponse {
+    stopped_count: usize,
+    deployment_ids: Vec<String>,
+    #[serde(default)]
+    groups: Vec<String>,
+}
+
+/// Stop all deployments in a group for a project, or in every group if `group` is `None`
+pub async fn stop_deployments_by_group(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    group: Option<&str>,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let (scope, query) = match group {
+        Some(group) => (
+            format!("group '{}'", group),
+            format!("group={}", urlencoding::encode(group)),
+        ),
+        None => ("any group".to_string(), "all=true".to_string()),
+    };
+
+    info!(
+        "Stopping deployments in {} for project '{}'",
+        scope, project
+    );
+
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/stop?{}",
+        backend_url, project, query
+    );
+
+    let response = http_client
+        .post(&url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .context("Failed to stop deployments")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!("Failed to stop deployments ({}): {}", status, error_text);
+    }
+
+    let stop_response: StopDeploymentsResponse = response
+        .json()
+        .await
+        .context("Failed to parse stop response")?;
+
+    if stop_response.stopped_count == 0 {
+        println!("No running deployments found in {}", scope);
+    } else if group.is_some() {
+        println!(
+            "✓ Stopped {} deployment(s) in {}:",
+            stop_response.stopped_count, scope
+        );
+        for deployment_id in &stop_response.deployment_ids {
+            println!("  - {}", deployment_id);
+        }
+    } else {
+        println!(
+            "✓ Stopped {} deployment(s) across {} group(s): {}",
+            stop_response.stopped_count,
+            stop_response.groups.len(),
+            stop_response.groups.join(", ")
+        );
+        for deployment_id in &stop_response.deployment_ids {
+            println!("  - {}", deployment_id);
+        }
+    }
+
+    Ok(())
+}
+
+/// Cancel a single in-progress deployment without touching the group's active deployment
+pub async fn cancel_deployment(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: &str,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let deployment =
+        request_cancellation(http_client, backend_url, &token, project, deployment_id).await?;
+
+    println!(
+        "✓ Deployment {} is now {}",
+        deployment.deployment_id, deployment.status
+    );
+
+    Ok(())
+}
+
+/// Change the number of replicas of a running deployment
+pub async fn scale_deployment(
     http_client: &Client,
     backend_url: &str,
     config: &Config,
     project: &str,
     deployment_id: &str,
-    follow: bool,
-    timeout_str: &str,
+    replicas: u32,
 ) -> Result<()> {
-    if follow {
-        // Use new enhanced UI for follow mode
-        let deployment = super::follow_ui::follow_deployment_with_ui(
-            http_client,
-            backend_url,
-            config,
-            project,
-            deployment_id,
-            timeout_str,
-        )
-        .await?;
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
 
-        // Exit with error if deployment failed
-        if deployment.status == DeploymentStatus::Failed {
-            if let Some(error) = deployment.error_message {
-                bail!("Deployment failed: {}", error);
-            } else {
-                bail!("Deployment failed");
-            }
-        }
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/{}/scale",
+        backend_url, project, deployment_id
+    );
 
-        Ok(())
-    } else {
-        // One-shot display (no follow)
-        let token = config
-            .token
-            .as_ref()
-            .context("Not logged in. Please run 'rise login' first.")?;
+    let response = http_client
+        .post(&url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "replicas": replicas }))
+        .send()
+        .await
+        .context("Failed to scale deployment")?;
 
-        let deployment =
-            fetch_deployment(http_client, backend_url, token, project, deployment_id).await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!("Failed to scale deployment ({}): {}", status, error_text);
+    }
 
-        // Use the same UI as follow mode
-        super::follow_ui::print_deployment_snapshot(&deployment);
+    let deployment: Deployment = response
+        .json()
+        .await
+        .context("Failed to parse scale response")?;
 
-        // Exit with error if deployment failed
-        if deployment.status == DeploymentStatus::Failed {
-            if let Some(error) = deployment.error_message {
-                bail!("Deployment failed: {}", error);
-            } else {
-                bail!("Deployment failed");
-            }
-        }
+    println!(
+        "✓ Scaling deployment {} to {} replica(s)",
+        deployment.deployment_id, deployment.replicas
+    );
 
-        Ok(())
-    }
+    Ok(())
 }
 
-/// Rollback to a previous deployment
-///
-/// Creates a new deployment with the same image as the reference deployment
+/// Restart the pods of a running deployment without rebuilding its image
+pub async fn restart_deployment(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: &str,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
 
-#[derive(Debug, Deserialize)]
-struct StopDeploymentsResponse {
-    stopped_count: usize,
-    deployment_ids: Vec<String>,
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/{}/restart",
+        backend_url, project, deployment_id
+    );
+
+    let response = http_client
+        .post(&url)
+        .bearer_auth(token)
+        .send()
+        .await
+        .context("Failed to restart deployment")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!("Failed to restart deployment ({}): {}", status, error_text);
+    }
+
+    println!("✓ Restarting deployment {}", deployment_id);
+
+    Ok(())
 }
 
-/// Stop all deployments in a group for a project
-pub async fn stop_deployments_by_group(
+/// Reset the expiration of a deployment to `expires_in` from now
+pub async fn renew_deployment(
     http_client: &Client,
     backend_url: &str,
     config: &Config,
     project: &str,
-    group: &str,
+    deployment_id: &str,
+    expires_in: &str,
 ) -> Result<()> {
     let token = config
         .get_token()
         .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
 
-    info!(
-        "Stopping deployments in group '{}' for project '{}'",
-        group, project
-    );
-
     let url = format!(
-        "{}/api/v1/projects/{}/deployments/stop?group={}",
-        backend_url,
-        project,
-        urlencoding::encode(group)
+        "{}/api/v1/projects/{}/deployments/{}/renew",
+        backend_url, project, deployment_id
     );
 
     let response = http_client
         .post(&url)
         .bearer_auth(token)
+        .json(&serde_json::json!({ "expires_in": expires_in }))
         .send()
         .await
-        .context("Failed to stop deployments")?;
+        .context("Failed to renew deployment")?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -368,25 +1197,24 @@ pub async fn stop_deployments_by_group(
             .text()
             .await
             .unwrap_or_else(|_| "Unknown error".to_string());
-        bail!("Failed to stop deployments ({}): {}", status, error_text);
+        bail!("Failed to renew deployment ({}): {}", status, error_text);
     }
 
-    let stop_response: StopDeploymentsResponse = response
+    let deployment: Deployment = response
         .json()
         .await
-        .context("Failed to parse stop response")?;
+        .context("Failed to parse deployment response")?;
 
-    if stop_response.stopped_count == 0 {
-        println!("No running deployments found in group '{}'", group);
-    } else {
-        println!(
-            "✓ Stopped {} deployment(s) in group '{}':",
-            stop_response.stopped_count, group
-        );
-        for deployment_id in &stop_response.deployment_ids {
-            println!("  - {}", deployment_id);
-        }
-    }
+    let expires_at = match deployment.expires_at {
+        Some(expires_at) => chrono::DateTime::parse_from_rfc3339(&expires_at)
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or(expires_at),
+        None => "-".to_string(),
+    };
+    println!(
+        "✓ Deployment {} now expires at {}",
+        deployment_id, expires_at
+    );
 
     Ok(())
 }
@@ -424,6 +1252,12 @@ struct CreateDeploymentResponse {
     credentials: RegistryCredentials,
 }
 
+#[derive(Debug, Deserialize)]
+struct DryRunDeploymentResponse {
+    deployment_id: String,
+    manifests: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct GetRegistryCredsResponse {
     credentials: RegistryCredentials,
@@ -509,6 +1343,9 @@ pub struct DeploymentOptions<'a> {
     /// URL to the pull request/merge request associated with this deployment.
     /// If None, auto-detection from CI environment variables is attempted.
     pub pull_request_url: Option<String>,
+    /// Human-readable reason for the deployment.
+    /// If None, auto-detection of the commit title from CI environment variables is attempted.
+    pub message: Option<String>,
     /// Pre-loaded project config from rise.toml to avoid re-loading during build.
     pub toml_config: Option<build::config::ProjectBuildConfig>,
     /// Number of replicas (resolved from CLI flag > rise.toml > server default)
@@ -517,6 +1354,44 @@ pub struct DeploymentOptions<'a> {
     pub cpu: Option<String>,
     /// Memory allocation (resolved from CLI flag > rise.toml > server default)
     pub memory: Option<String>,
+    /// CPU limit (resolved from CLI flag > rise.toml > server default)
+    pub cpu_limit: Option<String>,
+    /// Memory limit (resolved from CLI flag > rise.toml > server default)
+    pub memory_limit: Option<String>,
+    /// Health probe overrides (resolved from rise.toml environment > rise.toml global)
+    pub health_check: Option<HealthCheckConfig>,
+    /// Init container (resolved from rise.toml environment > rise.toml global)
+    pub init_container: Option<InitContainerConfig>,
+    /// App protocol (resolved from rise.toml environment > rise.toml global)
+    pub protocol: Option<DeploymentProtocol>,
+    /// Workload labels (rise.toml global merged with rise.toml environment)
+    pub labels: Option<BTreeMap<String, String>>,
+    /// Workload annotations (rise.toml global merged with rise.toml environment)
+    pub annotations: Option<BTreeMap<String, String>>,
+    /// Container command override (resolved from CLI flag > rise.toml environment > rise.toml global)
+    pub command: Option<Vec<String>>,
+    /// Container args override (resolved from CLI flag > rise.toml environment > rise.toml global)
+    pub args: Option<Vec<String>>,
+    /// Ingress annotations (rise.toml global merged with rise.toml environment)
+    pub ingress_annotations: Option<BTreeMap<String, String>>,
+    /// Deployment strategy (resolved from rise.toml environment > rise.toml global)
+    pub strategy: Option<DeploymentStrategy>,
+    /// Blue-green drain period (resolved from rise.toml environment > rise.toml global)
+    pub drain_period: Option<String>,
+    /// Persistent volume (resolved from rise.toml environment > rise.toml global)
+    pub volume: Option<VolumeConfig>,
+    /// Replica spreading (resolved from rise.toml environment > rise.toml global)
+    pub scheduling: Option<SchedulingConfig>,
+    /// Image pull policy (resolved from rise.toml environment > rise.toml global)
+    pub image_pull_policy: Option<ImagePullPolicy>,
+    /// Ingress rate limits (resolved from rise.toml environment > rise.toml global)
+    pub ratelimit: Option<RateLimitConfig>,
+    /// Additional named container ports (resolved from rise.toml environment > rise.toml global)
+    pub ports: Option<BTreeMap<String, PortConfig>>,
+    /// Kubernetes ServiceAccount (resolved from rise.toml environment > rise.toml global)
+    pub service_account: Option<ServiceAccountConfig>,
+    /// Only print the Kubernetes manifests the deployment would produce
+    pub dry_run: bool,
 }
 
 pub async fn create_deployment(
@@ -544,6 +1419,17 @@ pub async fn create_deployment(
         );
     }
 
+    // Re-pushing a pre-built image pulls a single platform variant
+    if deploy_opts.image.is_some()
+        && deploy_opts.push_image
+        && deploy_opts.build_args.platform.len() > 1
+    {
+        bail!(
+            "--push-image pulls and re-pushes a single platform; pass at most one --platform. \
+             Deploy a multi-platform image by reference (without --push-image) instead."
+        );
+    }
+
     // Get authentication token
     let token = config
         .get_token()
@@ -555,6 +1441,7 @@ pub async fn create_deployment(
         .pull_request_url
         .clone()
         .or_else(detect_ci_pull_request_url);
+    let resolved_message = deploy_opts.message.clone().or_else(detect_ci_commit_title);
 
     if let Some(ref url) = resolved_job_url {
         info!("Deployment job URL: {}", url);
@@ -563,6 +1450,46 @@ pub async fn create_deployment(
         info!("Deployment pull request URL: {}", url);
     }
 
+    if deploy_opts.dry_run {
+        let payload = create_deployment_payload(
+            deploy_opts.project_name,
+            deploy_opts.image,
+            deploy_opts.group,
+            deploy_opts.environment,
+            deploy_opts.expires_in,
+            deploy_opts.http_port,
+            deploy_opts.from_deployment,
+            deploy_opts.use_source_env_vars,
+            deploy_opts.push_image,
+            &deploy_opts.env_overrides,
+            resolved_job_url.as_deref(),
+            resolved_pull_request_url.as_deref(),
+            resolved_message.as_deref(),
+            deploy_opts.replicas,
+            deploy_opts.cpu.as_deref(),
+            deploy_opts.memory.as_deref(),
+            deploy_opts.cpu_limit.as_deref(),
+            deploy_opts.memory_limit.as_deref(),
+            deploy_opts.health_check.as_ref(),
+            deploy_opts.init_container.as_ref(),
+            deploy_opts.protocol,
+            deploy_opts.labels.as_ref(),
+            deploy_opts.annotations.as_ref(),
+            deploy_opts.command.as_deref(),
+            deploy_opts.args.as_deref(),
+            deploy_opts.ingress_annotations.as_ref(),
+            deploy_opts.strategy,
+            deploy_opts.drain_period.as_deref(),
+            deploy_opts.volume.as_ref(),
+            deploy_opts.scheduling.as_ref(),
+            deploy_opts.image_pull_policy,
+            deploy_opts.ratelimit.as_ref(),
+            deploy_opts.ports.as_ref(),
+            deploy_opts.service_account.as_ref(),
+        );
+        return print_dry_run_manifests(http_client, backend_url, &token, &payload).await;
+    }
+
     // Step 1: Create deployment and get deployment ID + credentials
     info!(
         "Creating deployment for project '{}'",
@@ -584,9 +1511,28 @@ pub async fn create_deployment(
         &deploy_opts.env_overrides,
         resolved_job_url.as_deref(),
         resolved_pull_request_url.as_deref(),
+        resolved_message.as_deref(),
         deploy_opts.replicas,
         deploy_opts.cpu.as_deref(),
         deploy_opts.memory.as_deref(),
+        deploy_opts.cpu_limit.as_deref(),
+        deploy_opts.memory_limit.as_deref(),
+        deploy_opts.health_check.as_ref(),
+        deploy_opts.init_container.as_ref(),
+        deploy_opts.protocol,
+        deploy_opts.labels.as_ref(),
+        deploy_opts.annotations.as_ref(),
+        deploy_opts.command.as_deref(),
+        deploy_opts.args.as_deref(),
+        deploy_opts.ingress_annotations.as_ref(),
+        deploy_opts.strategy,
+        deploy_opts.drain_period.as_deref(),
+        deploy_opts.volume.as_ref(),
+        deploy_opts.scheduling.as_ref(),
+        deploy_opts.image_pull_policy,
+        deploy_opts.ratelimit.as_ref(),
+        deploy_opts.ports.as_ref(),
+        deploy_opts.service_account.as_ref(),
     )
     .await?;
 
@@ -605,7 +1551,7 @@ pub async fn create_deployment(
             eprintln!("\n⚠️  Caught Ctrl+C, cancelling deployment...");
 
             // Cancel the deployment
-            if let Err(e) = cancel_deployment(
+            if let Err(e) = request_cancellation(
                 &http_client_clone,
                 &backend_url_clone,
                 &token_clone,
@@ -662,7 +1608,8 @@ pub async fn create_deployment(
             let platform = deploy_opts
                 .build_args
                 .platform
-                .as_deref()
+                .first()
+                .map(String::as_str)
                 .unwrap_or(build::DEFAULT_PLATFORM);
             if let Err(e) = build::docker_pull(&container_cli, source_image, platform) {
                 update_deployment_status(
@@ -708,7 +1655,11 @@ pub async fn create_deployment(
             .await?;
 
             // Push to Rise registry
-            if let Err(e) = build::docker_push(&container_cli, &deployment_info.image_tag) {
+            if let Err(e) = build::docker_push(
+                &container_cli,
+                &deployment_info.image_tag,
+                deploy_opts.build_args.push_retries,
+            ) {
                 update_deployment_status(
                     http_client,
                     backend_url,
@@ -761,7 +1712,8 @@ pub async fn create_deployment(
             deploy_opts.path.to_string(),
             deploy_opts.build_args,
             deploy_opts.toml_config,
-        );
+        )
+        .with_cache_project(deploy_opts.project_name);
 
         // Step 2: Fetch deployment-scoped registry credentials and login
         let credentials = fetch_deployment_registry_credentials(
@@ -842,6 +1794,7 @@ pub async fn create_deployment(
         &deployment_info.deployment_id,
         true,  // follow
         "10m", // timeout
+        false, // json
     )
     .await?;
 
@@ -967,11 +1920,43 @@ fn detect_ci_pull_request_url() -> Option<String> {
     None
 }
 
+/// Auto-detect the commit title (first line of the commit message) from CI environment variables.
+///
+/// Supported CI environments:
+/// - GitLab CI: `CI_COMMIT_TITLE`
+/// - GitHub Actions: `head_commit.message` from the event payload at `GITHUB_EVENT_PATH`
+///   (only present for push events)
+///
+/// Returns the detected title, or None if not available.
+fn detect_ci_commit_title() -> Option<String> {
+    // GitLab CI
+    if let Ok(title) = std::env::var("CI_COMMIT_TITLE") {
+        if !title.trim().is_empty() {
+            return Some(title.trim().to_string());
+        }
+    }
+
+    // GitHub Actions
+    if let Ok(event_path) = std::env::var("GITHUB_EVENT_PATH") {
+        let event: serde_json::Value = std::fs::read_to_string(&event_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())?;
+        if let Some(title) = event["head_commit"]["message"]
+            .as_str()
+            .and_then(|message| message.lines().next())
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+        {
+            return Some(title.to_string());
+        }
+    }
+
+    None
+}
+
+/// Build the request body shared by deployment creation and dry-run rendering
 #[allow(clippy::too_many_arguments)]
-async fn call_create_deployment_api(
-    http_client: &Client,
-    backend_url: &str,
-    token: &str,
+fn create_deployment_payload(
     project_name: &str,
     image: Option<&str>,
     group: Option<&str>,
@@ -984,11 +1969,29 @@ async fn call_create_deployment_api(
     env_overrides: &[EnvOverride],
     job_url: Option<&str>,
     pull_request_url: Option<&str>,
+    message: Option<&str>,
     replicas: Option<u32>,
     cpu: Option<&str>,
     memory: Option<&str>,
-) -> Result<CreateDeploymentResponse> {
-    let url = format!("{}/api/v1/deployments", backend_url);
+    cpu_limit: Option<&str>,
+    memory_limit: Option<&str>,
+    health_check: Option<&HealthCheckConfig>,
+    init_container: Option<&InitContainerConfig>,
+    protocol: Option<DeploymentProtocol>,
+    labels: Option<&BTreeMap<String, String>>,
+    annotations: Option<&BTreeMap<String, String>>,
+    command: Option<&[String]>,
+    args: Option<&[String]>,
+    ingress_annotations: Option<&BTreeMap<String, String>>,
+    strategy: Option<DeploymentStrategy>,
+    drain_period: Option<&str>,
+    volume: Option<&VolumeConfig>,
+    scheduling: Option<&SchedulingConfig>,
+    image_pull_policy: Option<ImagePullPolicy>,
+    ratelimit: Option<&RateLimitConfig>,
+    ports: Option<&BTreeMap<String, PortConfig>>,
+    service_account: Option<&ServiceAccountConfig>,
+) -> serde_json::Value {
     let mut payload = serde_json::json!({
         "project": project_name,
     });
@@ -1040,6 +2043,11 @@ async fn call_create_deployment_api(
         payload["pull_request_url"] = serde_json::json!(url);
     }
 
+    // Add message if provided
+    if let Some(m) = message {
+        payload["message"] = serde_json::json!(m);
+    }
+
     // Add resource fields if provided
     if let Some(r) = replicas {
         payload["replicas"] = serde_json::json!(r);
@@ -1050,6 +2058,61 @@ async fn call_create_deployment_api(
     if let Some(m) = memory {
         payload["memory"] = serde_json::json!(m);
     }
+    if let Some(c) = cpu_limit {
+        payload["cpu_limit"] = serde_json::json!(c);
+    }
+    if let Some(m) = memory_limit {
+        payload["memory_limit"] = serde_json::json!(m);
+    }
+    if let Some(hc) = health_check {
+        payload["health_check"] = serde_json::json!(hc);
+    }
+    if let Some(init) = init_container {
+        payload["init_container"] = serde_json::json!(init);
+    }
+    if let Some(protocol) = protocol {
+        payload["protocol"] = serde_json::json!(protocol);
+    }
+    if let Some(labels) = labels {
+        payload["labels"] = serde_json::json!(labels);
+    }
+    if let Some(annotations) = annotations {
+        payload["annotations"] = serde_json::json!(annotations);
+    }
+    // An empty override would wipe the image's entrypoint/cmd, so it is omitted
+    if let Some(command) = command.filter(|c| !c.is_empty()) {
+        payload["command"] = serde_json::json!(command);
+    }
+    if let Some(args) = args.filter(|a| !a.is_empty()) {
+        payload["args"] = serde_json::json!(args);
+    }
+    if let Some(ingress_annotations) = ingress_annotations {
+        payload["ingress_annotations"] = serde_json::json!(ingress_annotations);
+    }
+    if let Some(strategy) = strategy {
+        payload["strategy"] = serde_json::json!(strategy);
+    }
+    if let Some(drain_period) = drain_period {
+        payload["drain_period"] = serde_json::json!(drain_period);
+    }
+    if let Some(volume) = volume {
+        payload["volume"] = serde_json::json!(volume);
+    }
+    if let Some(scheduling) = scheduling {
+        payload["scheduling"] = serde_json::json!(scheduling);
+    }
+    if let Some(image_pull_policy) = image_pull_policy {
+        payload["image_pull_policy"] = serde_json::json!(image_pull_policy);
+    }
+    if let Some(ratelimit) = ratelimit {
+        payload["ratelimit"] = serde_json::json!(ratelimit);
+    }
+    if let Some(ports) = ports {
+        payload["ports"] = serde_json::json!(ports);
+    }
+    if let Some(service_account) = service_account {
+        payload["service_account"] = serde_json::json!(service_account);
+    }
 
     // Add env_overrides if any
     if !env_overrides.is_empty() {
@@ -1074,6 +2137,87 @@ async fn call_create_deployment_api(
         payload["env_overrides"] = serde_json::json!(overrides);
     }
 
+    payload
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn call_create_deployment_api(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project_name: &str,
+    image: Option<&str>,
+    group: Option<&str>,
+    environment: Option<&str>,
+    expires_in: Option<&str>,
+    http_port: Option<u16>,
+    from_deployment: Option<&str>,
+    use_source_env_vars: bool,
+    push_image: bool,
+    env_overrides: &[EnvOverride],
+    job_url: Option<&str>,
+    pull_request_url: Option<&str>,
+    message: Option<&str>,
+    replicas: Option<u32>,
+    cpu: Option<&str>,
+    memory: Option<&str>,
+    cpu_limit: Option<&str>,
+    memory_limit: Option<&str>,
+    health_check: Option<&HealthCheckConfig>,
+    init_container: Option<&InitContainerConfig>,
+    protocol: Option<DeploymentProtocol>,
+    labels: Option<&BTreeMap<String, String>>,
+    annotations: Option<&BTreeMap<String, String>>,
+    command: Option<&[String]>,
+    args: Option<&[String]>,
+    ingress_annotations: Option<&BTreeMap<String, String>>,
+    strategy: Option<DeploymentStrategy>,
+    drain_period: Option<&str>,
+    volume: Option<&VolumeConfig>,
+    scheduling: Option<&SchedulingConfig>,
+    image_pull_policy: Option<ImagePullPolicy>,
+    ratelimit: Option<&RateLimitConfig>,
+    ports: Option<&BTreeMap<String, PortConfig>>,
+    service_account: Option<&ServiceAccountConfig>,
+) -> Result<CreateDeploymentResponse> {
+    let url = format!("{}/api/v1/deployments", backend_url);
+    let payload = create_deployment_payload(
+        project_name,
+        image,
+        group,
+        environment,
+        expires_in,
+        http_port,
+        from_deployment,
+        use_source_env_vars,
+        push_image,
+        env_overrides,
+        job_url,
+        pull_request_url,
+        message,
+        replicas,
+        cpu,
+        memory,
+        cpu_limit,
+        memory_limit,
+        health_check,
+        init_container,
+        protocol,
+        labels,
+        annotations,
+        command,
+        args,
+        ingress_annotations,
+        strategy,
+        drain_period,
+        volume,
+        scheduling,
+        image_pull_policy,
+        ratelimit,
+        ports,
+        service_account,
+    );
+
     let response = http_client
         .post(&url)
         .header("Authorization", format!("Bearer {}", token))
@@ -1082,6 +2226,15 @@ async fn call_create_deployment_api(
         .await
         .context("Failed to create deployment")?;
 
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        // The project is at its deployment quota; the backend explains how to free up room
+        let error: crate::api::project::ProjectErrorResponse = response
+            .json()
+            .await
+            .context("Failed to parse error response")?;
+        bail!("{}", error.error);
+    }
+
     if !response.status().is_success() {
         let status = response.status();
         let error_text = response
@@ -1099,27 +2252,67 @@ async fn call_create_deployment_api(
     Ok(deployment_info)
 }
 
-/// Cancel a deployment by marking it as Cancelling
-async fn cancel_deployment(
+/// Render the manifests for a deployment request without creating it, and print them
+async fn print_dry_run_manifests(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    payload: &serde_json::Value,
+) -> Result<()> {
+    let url = format!("{}/api/v1/deployments/dry-run", backend_url);
+
+    let response = http_client
+        .post(&url)
+        .bearer_auth(token)
+        .json(payload)
+        .send()
+        .await
+        .context("Failed to render deployment manifests")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!(
+            "Failed to render deployment manifests ({}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let rendered: DryRunDeploymentResponse = response
+        .json()
+        .await
+        .context("Failed to parse dry-run response")?;
+
+    // Manifests go to stdout so they can be piped; everything else to stderr
+    print!("{}", rendered.manifests);
+    eprintln!(
+        "Dry run: no deployment was created. Resource names use the placeholder deployment ID '{}'; secret values are redacted.",
+        rendered.deployment_id
+    );
+
+    Ok(())
+}
+
+/// Cancel a deployment through the cancel endpoint, which refuses the group's active deployment
+async fn request_cancellation(
     http_client: &Client,
     backend_url: &str,
     token: &str,
     project_name: &str,
     deployment_id: &str,
-) -> Result<()> {
+) -> Result<Deployment> {
     let url = format!(
-        "{}/api/v1/projects/{}/deployments/{}/status",
+        "{}/api/v1/projects/{}/deployments/{}/cancel",
         backend_url, project_name, deployment_id
     );
 
-    let payload = serde_json::json!({
-        "status": "Cancelling"
-    });
-
     let response = http_client
-        .patch(&url)
+        .post(&url)
         .bearer_auth(token)
-        .json(&payload)
         .send()
         .await
         .context("Failed to cancel deployment")?;
@@ -1133,7 +2326,10 @@ async fn cancel_deployment(
         bail!("Failed to cancel deployment ({}): {}", status, error_text);
     }
 
-    Ok(())
+    response
+        .json()
+        .await
+        .context("Failed to parse cancel response")
 }
 
 async fn update_deployment_status(
@@ -1190,9 +2386,18 @@ pub struct GetLogsParams<'a> {
     pub tail: Option<usize>,
     pub timestamps: bool,
     pub since: Option<&'a str>,
+    pub all_pods: bool,
+    /// Write the logs to this file instead of stdout
+    pub download: Option<&'a std::path::Path>,
+    /// Only keep lines matching this regex
+    pub grep: Option<&'a str>,
+    /// Drop the lines matching `grep` instead
+    pub invert_match: bool,
 }
 
 /// Get logs from a deployment
+///
+/// Logs are printed to stdout, or written to `params.download` when set.
 pub async fn get_logs(
     http_client: &reqwest::Client,
     backend_url: &str,
@@ -1201,6 +2406,15 @@ pub async fn get_logs(
 ) -> anyhow::Result<()> {
     use futures::StreamExt;
 
+    // Compile the filter before anything is streamed
+    let filter = params
+        .grep
+        .map(|pattern| {
+            regex::Regex::new(pattern)
+                .with_context(|| format!("Invalid --grep pattern '{}'", pattern))
+        })
+        .transpose()?;
+
     // Build URL with query parameters
     let mut url = format!(
         "{}/api/v1/projects/{}/deployments/{}/logs",
@@ -1227,6 +2441,9 @@ pub async fn get_logs(
         since_param = format!("since={}", seconds);
         query_params.push(&since_param);
     }
+    if params.all_pods {
+        query_params.push("all_pods=true");
+    }
 
     if !query_params.is_empty() {
         url.push('?');
@@ -1256,6 +2473,31 @@ pub async fn get_logs(
         ));
     }
 
+    let mut out: Box<dyn std::io::Write> = match params.download {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent).with_context(|| {
+                    format!("Failed to create directory '{}'", parent.display())
+                })?;
+            }
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("Failed to create '{}'", path.display()))?;
+            Box::new(std::io::BufWriter::new(file))
+        }
+        None => Box::new(std::io::stdout()),
+    };
+    let mut bytes_written = 0usize;
+    let mut write_line = |line: &str| -> anyhow::Result<()> {
+        if let Some(filter) = &filter {
+            if filter.is_match(line) == params.invert_match {
+                return Ok(());
+            }
+        }
+        writeln!(out, "{}", line).context("Failed to write logs")?;
+        bytes_written += line.len() + 1;
+        Ok(())
+    };
+
     // Setup Ctrl+C handler for graceful shutdown
     let ctrl_c = tokio::signal::ctrl_c();
     tokio::pin!(ctrl_c);
@@ -1287,12 +2529,12 @@ pub async fn get_logs(
                             if let Some(data) = line.strip_prefix("data: ") {
                                 // Only print non-empty data lines
                                 if !data.is_empty() {
-                                    println!("{}", data);
+                                    write_line(data)?;
                                 }
                             } else if !line.is_empty() && !line.starts_with(':') {
                                 // SSE comments start with ':', skip them
                                 // Print other non-empty lines (in case format changes)
-                                println!("{}", line);
+                                write_line(line)?;
                             }
                         }
                     }
@@ -1315,13 +2557,22 @@ pub async fn get_logs(
         if let Some(data) = line.strip_prefix("data: ") {
             // Only print non-empty data
             if !data.is_empty() {
-                println!("{}", data);
+                write_line(data)?;
             }
         } else if !line.is_empty() && !line.starts_with(':') {
-            println!("{}", line);
+            write_line(line)?;
         }
     }
 
+    out.flush().context("Failed to write logs")?;
+    if let Some(path) = params.download {
+        println!(
+            "✓ Saved {} bytes of logs to {}",
+            bytes_written,
+            path.display()
+        );
+    }
+
     Ok(())
 }
 
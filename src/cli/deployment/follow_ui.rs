@@ -1,6 +1,10 @@
 use anyhow::{bail, Result};
+use comfy_table::{
+    modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Attribute, Cell, Color, Table,
+};
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::io::{self, IsTerminal, Write as _};
 use std::time::{Duration, Instant};
 use tracing::{debug, info};
@@ -8,7 +12,10 @@ use tracing::{debug, info};
 use crate::api::models::{Deployment, DeploymentStatus};
 use crate::config::Config;
 
-use super::core::{fetch_deployment, open_log_stream, parse_duration, LogStreamError};
+use super::core::{
+    fetch_deployment, fetch_group_deployments, fetch_project_deployments, open_log_stream,
+    parse_duration, LogStreamError,
+};
 
 // Project info for fetching project URL
 #[derive(Deserialize)]
@@ -56,6 +63,15 @@ mod ansi {
 // Spinner animation frames
 const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
+// Number of recent group deployments inspected while following a group transition
+const GROUP_FOLLOW_LIMIT: usize = 20;
+
+// Number of recent project deployments fetched per poll by `rise deployment watch`
+const WATCH_LIMIT: usize = 50;
+
+// How long `rise deployment watch` highlights a status transition
+const WATCH_HIGHLIGHT: Duration = Duration::from_secs(10);
+
 /// State tracking between polls
 struct FollowState {
     last_status: DeploymentStatus,
@@ -100,7 +116,37 @@ impl FollowState {
     }
 }
 
-/// Live status section that gets replaced on each poll
+/// Result of observing a deployment's status in a poll
+#[derive(Debug, PartialEq)]
+enum StatusChange {
+    /// First time this deployment was seen
+    New,
+    Unchanged,
+    /// Status changed since the previous poll (holds the previous status)
+    Changed(DeploymentStatus),
+}
+
+/// Tracks the status of several deployments across polls to detect transitions
+#[derive(Default)]
+struct StatusTracker {
+    last_statuses: HashMap<String, DeploymentStatus>,
+}
+
+impl StatusTracker {
+    /// Record the current status of a deployment and report how it changed
+    fn observe(&mut self, deployment: &Deployment) -> StatusChange {
+        match self
+            .last_statuses
+            .insert(deployment.deployment_id.clone(), deployment.status.clone())
+        {
+            None => StatusChange::New,
+            Some(previous) if previous == deployment.status => StatusChange::Unchanged,
+            Some(previous) => StatusChange::Changed(previous),
+        }
+    }
+}
+
+/// Live output section that gets replaced on each poll
 struct LiveStatusSection {
     pub last_line_count: usize,
 }
@@ -126,70 +172,69 @@ impl LiveStatusSection {
         }
     }
 
-    fn render(
-        &mut self,
-        deployment: &Deployment,
-        state: &FollowState,
-        controller_phase: &Option<ReconcilePhase>,
-    ) -> String {
-        // Clear previous output
+    /// Replace the previously drawn output with `output`
+    fn redraw(&mut self, output: &str) {
         self.clear_previous();
+        print!("{}", output);
+        io::stdout().flush().unwrap();
+        self.last_line_count = output.lines().count();
+    }
+}
 
-        let mut output = String::new();
-        let mut line_count = 0;
-
-        // Status line with icon and color
-        let icon = status_icon(&deployment.status);
-        let color = status_color(&deployment.status);
-        let spinner = if is_in_progress(&deployment.status) {
-            format!("{} ", spinner_frame(state.spinner_frame))
-        } else {
-            String::new()
-        };
+/// Render the live status of a followed deployment
+fn render_live_status(
+    deployment: &Deployment,
+    state: &FollowState,
+    controller_phase: &Option<ReconcilePhase>,
+) -> String {
+    let mut output = String::new();
 
-        // Show deployment status + controller phase if available
-        let status_text = if let Some(phase) = controller_phase {
-            format!("{} ({})", deployment.status, format_controller_phase(phase))
-        } else {
-            format!("{}", deployment.status)
-        };
+    // Status line with icon and color
+    let icon = status_icon(&deployment.status);
+    let color = status_color(&deployment.status);
+    let spinner = if is_in_progress(&deployment.status) {
+        format!("{} ", spinner_frame(state.spinner_frame))
+    } else {
+        String::new()
+    };
 
-        output.push_str(&format!(
-            "{}{} Status:    {}{}{}\n",
-            spinner,
-            icon,
-            color,
-            status_text,
-            ansi::RESET
-        ));
-        line_count += 1;
+    // Show deployment status + controller phase if available
+    let status_text = if let Some(phase) = controller_phase {
+        format!("{} ({})", deployment.status, format_controller_phase(phase))
+    } else {
+        format!("{}", deployment.status)
+    };
 
-        // URL if available
-        if let Some(ref url) = deployment.primary_url {
-            output.push_str(&format!("   URL:       {}\n", url));
-            line_count += 1;
-        }
+    output.push_str(&format!(
+        "{}{} Status:    {}{}{}\n",
+        spinner,
+        icon,
+        color,
+        status_text,
+        ansi::RESET
+    ));
 
-        // Error message if present
-        if let Some(ref error) = deployment.error_message {
-            output.push_str(&format!(
-                "   {}Error:{} {}\n",
-                "\x1B[31m",
-                ansi::RESET,
-                error
-            ));
-            line_count += 1;
-        }
+    // URL if available
+    if let Some(ref url) = deployment.primary_url {
+        output.push_str(&format!("   URL:       {}\n", url));
+    }
 
-        // Controller metadata summary (container ID if available)
-        if let Some(container_id) = extract_container_id(&deployment.controller_metadata) {
-            output.push_str(&format!("   Container: {}\n", container_id));
-            line_count += 1;
-        }
+    // Error message if present
+    if let Some(ref error) = deployment.error_message {
+        output.push_str(&format!(
+            "   {}Error:{} {}\n",
+            "\x1B[31m",
+            ansi::RESET,
+            error
+        ));
+    }
 
-        self.last_line_count = line_count;
-        output
+    // Controller metadata summary (container ID if available)
+    if let Some(container_id) = extract_container_id(&deployment.controller_metadata) {
+        output.push_str(&format!("   Container: {}\n", container_id));
     }
+
+    output
 }
 
 /// Get status color ANSI code
@@ -259,6 +304,40 @@ fn is_terminal_state(status: &DeploymentStatus) -> bool {
     )
 }
 
+/// Check if the deployment is being torn down (stopped, superseded or cancelled)
+fn is_teardown_state(status: &DeploymentStatus) -> bool {
+    matches!(
+        status,
+        DeploymentStatus::Terminating | DeploymentStatus::Cancelling
+    )
+}
+
+/// Check if the status is part of a controller-driven group transition,
+/// i.e. a deployment coming up or an old deployment being torn down
+fn is_group_transition_state(status: &DeploymentStatus) -> bool {
+    matches!(
+        status,
+        DeploymentStatus::Pushed
+            | DeploymentStatus::Deploying
+            | DeploymentStatus::Terminating
+            | DeploymentStatus::Cancelling
+    )
+}
+
+/// Check if a superseded deployment is kept running after a blue-green switch.
+///
+/// It no longer receives traffic and its drain period can be long, so it does not
+/// hold up following the group.
+fn is_draining(deployment: &Deployment) -> bool {
+    deployment.status == DeploymentStatus::Terminating
+        && deployment
+            .controller_metadata
+            .get("drain_until")
+            .and_then(|v| v.as_str())
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            .is_some_and(|until| until > chrono::Utc::now())
+}
+
 /// Parse controller metadata to extract deployment phase info (handles legacy Docker deployments)
 fn parse_controller_metadata(metadata: &serde_json::Value) -> Option<DockerMetadata> {
     if metadata.is_null() || metadata == &serde_json::json!({}) {
@@ -348,6 +427,11 @@ pub fn print_deployment_snapshot(deployment: &Deployment) {
     // Created by
     println!("   Created by:     {}", deployment.created_by_email);
 
+    // Message (if set)
+    if let Some(ref message) = deployment.message {
+        println!("   Message:        {}", message);
+    }
+
     // Created/Updated timestamps
     println!("   Created:        {}", deployment.created);
     if deployment.updated != deployment.created {
@@ -368,9 +452,15 @@ pub fn print_deployment_snapshot(deployment: &Deployment) {
     }
 
     // Resources
+    let with_limit = |request: &str, limit: &Option<String>| match limit {
+        Some(limit) if limit != request => format!("{} (limit {})", request, limit),
+        _ => request.to_string(),
+    };
     println!(
         "   Resources:      {} replicas, cpu={}, memory={}",
-        deployment.replicas, deployment.cpu, deployment.memory
+        deployment.replicas,
+        with_limit(&deployment.cpu, &deployment.cpu_limit),
+        with_limit(&deployment.memory, &deployment.memory_limit)
     );
 
     // URL if available
@@ -579,6 +669,91 @@ async fn status_only_polling(
     }
 }
 
+/// Follow the deployment group of `deployment` until it has settled.
+///
+/// Rollbacks, supersedes and stops involve more than one deployment: the new
+/// deployment comes up while the previous one is torn down. This logs status
+/// changes of every deployment taking part in the transition and returns the
+/// refreshed followed deployment once no deployment in the group is coming up
+/// or being torn down anymore. Returns immediately if the group is already stable.
+async fn follow_group_transition(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    deployment: Deployment,
+    timeout: Duration,
+    start_time: Instant,
+) -> Result<Deployment> {
+    // Failed/cancelled deployments never took over the group - nothing to follow
+    if deployment.status != DeploymentStatus::Healthy && !is_teardown_state(&deployment.status) {
+        return Ok(deployment);
+    }
+
+    let group = deployment.deployment_group.clone();
+    let mut tracker = StatusTracker::default();
+    tracker.observe(&deployment);
+    let mut followed = deployment;
+    let mut saw_transition = false;
+
+    loop {
+        let deployments = fetch_group_deployments(
+            http_client,
+            backend_url,
+            token,
+            project,
+            &group,
+            GROUP_FOLLOW_LIMIT,
+        )
+        .await?;
+
+        for d in &deployments {
+            let log = match tracker.observe(d) {
+                StatusChange::Changed(_) => true,
+                StatusChange::New => is_group_transition_state(&d.status),
+                StatusChange::Unchanged => false,
+            };
+            if log {
+                log_state_change(project, &d.deployment_id, &d.status, &None);
+            }
+
+            if d.deployment_id == followed.deployment_id {
+                followed = d.clone();
+            }
+        }
+
+        let in_transition = deployments
+            .iter()
+            .any(|d| is_group_transition_state(&d.status) && !is_draining(d));
+        if !in_transition {
+            if saw_transition {
+                match deployments
+                    .iter()
+                    .find(|d| d.status == DeploymentStatus::Healthy)
+                {
+                    Some(active) => info!(
+                        "Group '{}' settled: {}:{} is active",
+                        group, project, active.deployment_id
+                    ),
+                    None => info!("Group '{}' settled: no active deployment", group),
+                }
+            }
+            return Ok(followed);
+        }
+        saw_transition = true;
+
+        if start_time.elapsed() >= timeout {
+            bail!(
+                "Timeout waiting for deployment group '{}' to settle after {:?}",
+                group,
+                timeout
+            );
+        }
+
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
 /// Main follow function with enhanced UX
 pub async fn follow_deployment_with_ui(
     http_client: &Client,
@@ -635,9 +810,7 @@ pub async fn follow_deployment_with_ui(
                 );
                 live_section.last_line_count = 0;
             } else {
-                let output = live_section.render(&deployment, &state, &controller_phase);
-                print!("{}", output);
-                io::stdout().flush().unwrap();
+                live_section.redraw(&render_live_status(&deployment, &state, &controller_phase));
             }
 
             state.update(&deployment, controller_phase);
@@ -653,6 +826,11 @@ pub async fn follow_deployment_with_ui(
                 return Ok(deployment);
             }
 
+            // Being torn down - follow the group transition instead
+            if is_teardown_state(&deployment.status) {
+                return Ok(deployment);
+            }
+
             if start_time.elapsed() >= timeout {
                 bail!(
                     "Timeout waiting for deployment to complete after {:?}",
@@ -674,8 +852,8 @@ pub async fn follow_deployment_with_ui(
         }
     };
 
-    // Phase 2: Log streaming + status monitoring (only if not already terminal)
-    let final_deployment = if !is_terminal_state(&deployment.status) {
+    // Phase 2: Log streaming + status monitoring (only if logs are available)
+    let final_deployment = if should_stream_logs(&deployment.status) {
         // Clear spinner UI and restore cursor
         live_section.clear_previous();
         print!("{}", ansi::SHOW_CURSOR);
@@ -699,7 +877,19 @@ pub async fn follow_deployment_with_ui(
         deployment
     };
 
-    // Phase 3: Print project URL if deployment became active (Healthy in default group)
+    // Phase 3: Follow the group until superseded/terminating deployments have settled
+    let final_deployment = follow_group_transition(
+        http_client,
+        backend_url,
+        &token,
+        project,
+        final_deployment,
+        timeout,
+        start_time,
+    )
+    .await?;
+
+    // Phase 4: Print project URL if deployment became active (Healthy in default group)
     if final_deployment.status == DeploymentStatus::Healthy
         && final_deployment.deployment_group == "default"
     {
@@ -751,7 +941,7 @@ async fn follow_deployment_simple(
             break deployment;
         }
 
-        if should_stream_logs(&deployment.status) {
+        if should_stream_logs(&deployment.status) || is_teardown_state(&deployment.status) {
             break deployment;
         }
 
@@ -762,8 +952,8 @@ async fn follow_deployment_simple(
         tokio::time::sleep(Duration::from_secs(1)).await;
     };
 
-    // Phase 2: Log streaming + status monitoring (only if not terminal)
-    let final_deployment = if !is_terminal_state(&deployment.status) {
+    // Phase 2: Log streaming + status monitoring (only if logs are available)
+    let final_deployment = if should_stream_logs(&deployment.status) {
         println!("--- Logs ---");
 
         stream_logs_with_status_polling(
@@ -780,7 +970,19 @@ async fn follow_deployment_simple(
         deployment
     };
 
-    // Phase 3: Print project URL if deployment became active (Healthy in default group)
+    // Phase 3: Follow the group until superseded/terminating deployments have settled
+    let final_deployment = follow_group_transition(
+        http_client,
+        backend_url,
+        token,
+        project,
+        final_deployment,
+        timeout,
+        start_time,
+    )
+    .await?;
+
+    // Phase 4: Print project URL if deployment became active (Healthy in default group)
     if final_deployment.status == DeploymentStatus::Healthy
         && final_deployment.deployment_group == "default"
     {
@@ -795,3 +997,264 @@ async fn follow_deployment_simple(
 
     Ok(final_deployment)
 }
+
+/// Follow a deployment printing newline-delimited JSON for scripts.
+///
+/// Prints the deployment on the first poll and whenever it changes, until it
+/// reaches a terminal state. The last line is the final deployment. Logs are not
+/// streamed so stdout stays machine-readable.
+pub async fn follow_deployment_json(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    deployment_id: &str,
+    timeout_str: &str,
+) -> Result<Deployment> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not authenticated"))?;
+
+    let timeout = parse_duration(timeout_str)?;
+    let start_time = Instant::now();
+    let mut last_line: Option<String> = None;
+
+    loop {
+        let deployment =
+            fetch_deployment(http_client, backend_url, &token, project, deployment_id).await?;
+
+        let line = serde_json::to_string(&deployment)?;
+        if last_line.as_deref() != Some(line.as_str()) {
+            println!("{}", line);
+            io::stdout().flush()?;
+            last_line = Some(line);
+        }
+
+        if is_terminal_state(&deployment.status) {
+            return Ok(deployment);
+        }
+
+        if start_time.elapsed() >= timeout {
+            bail!(
+                "Timeout waiting for deployment to complete after {:?}",
+                timeout
+            );
+        }
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Deployments shown by `rise deployment watch`: per group, the newest deployment and,
+/// if that is not the one serving traffic, the group's healthy deployment.
+///
+/// Expects `deployments` newest first. The default group is listed first, the others by name.
+fn watch_rows(deployments: &[Deployment]) -> Vec<&Deployment> {
+    let mut groups: Vec<&str> = Vec::new();
+    for d in deployments {
+        if !groups.contains(&d.deployment_group.as_str()) {
+            groups.push(&d.deployment_group);
+        }
+    }
+    groups.sort_by_key(|group| (*group != "default", *group));
+
+    let mut rows = Vec::new();
+    for group in groups {
+        let mut in_group = deployments.iter().filter(|d| d.deployment_group == group);
+        let Some(newest) = in_group.next() else {
+            continue;
+        };
+        rows.push(newest);
+        if newest.status != DeploymentStatus::Healthy {
+            if let Some(active) = in_group.find(|d| d.status == DeploymentStatus::Healthy) {
+                rows.push(active);
+            }
+        }
+    }
+    rows
+}
+
+/// Table color of a deployment status (mirrors `status_color`)
+fn status_cell_color(status: &DeploymentStatus) -> Color {
+    match status {
+        DeploymentStatus::Healthy => Color::Green,
+        DeploymentStatus::Failed | DeploymentStatus::Unhealthy => Color::Red,
+        DeploymentStatus::Deploying => Color::Yellow,
+        DeploymentStatus::Building | DeploymentStatus::Pushing => Color::Cyan,
+        DeploymentStatus::Cancelled | DeploymentStatus::Stopped => Color::DarkGrey,
+        _ => Color::White,
+    }
+}
+
+/// Render the `rise deployment watch` table
+///
+/// Deployments whose status changed within `WATCH_HIGHLIGHT` show the transition
+/// (e.g. "Deploying → Healthy") in bold.
+fn render_watch_table(
+    rows: &[&Deployment],
+    transitions: &HashMap<String, (DeploymentStatus, Instant)>,
+    spinner_frame_num: usize,
+) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            Cell::new("GROUP").add_attribute(Attribute::Bold),
+            Cell::new("DEPLOYMENT").add_attribute(Attribute::Bold),
+            Cell::new("STATUS").add_attribute(Attribute::Bold),
+            Cell::new("URL").add_attribute(Attribute::Bold),
+        ]);
+
+    for deployment in rows {
+        let spinner = if is_in_progress(&deployment.status) {
+            format!("{} ", spinner_frame(spinner_frame_num))
+        } else {
+            String::new()
+        };
+        let recent_transition = transitions
+            .get(&deployment.deployment_id)
+            .filter(|(_, at)| at.elapsed() < WATCH_HIGHLIGHT);
+        let mut status_cell = match recent_transition {
+            Some((previous, _)) => {
+                Cell::new(format!("{}{} → {}", spinner, previous, deployment.status))
+                    .add_attribute(Attribute::Bold)
+            }
+            None => Cell::new(format!("{}{}", spinner, deployment.status)),
+        };
+        status_cell = status_cell.fg(status_cell_color(&deployment.status));
+
+        // Only Healthy deployments can be connected to
+        let url = match deployment.status {
+            DeploymentStatus::Healthy => deployment.primary_url.as_deref().unwrap_or("-"),
+            _ => "-",
+        };
+
+        table.add_row(vec![
+            Cell::new(&deployment.deployment_group),
+            Cell::new(&deployment.deployment_id),
+            status_cell,
+            Cell::new(url),
+        ]);
+    }
+
+    table
+}
+
+/// Live view of the deployments of every group in a project, until Ctrl+C
+pub async fn watch_deployments(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project: &str,
+    interval_str: &str,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+    let interval = parse_duration(interval_str)?;
+    let tty = is_tty();
+
+    let mut tracker = StatusTracker::default();
+    let mut transitions: HashMap<String, (DeploymentStatus, Instant)> = HashMap::new();
+    let mut live_section = LiveStatusSection::new();
+    let mut spinner_frame_num = 0;
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    if tty {
+        print!("{}", ansi::HIDE_CURSOR);
+        io::stdout().flush().unwrap();
+    }
+
+    let result: Result<()> = async {
+        loop {
+            let deployments = tokio::select! {
+                _ = &mut ctrl_c => return Ok(()),
+                deployments = fetch_project_deployments(
+                    http_client, backend_url, &token, project, WATCH_LIMIT,
+                ) => deployments?,
+            };
+            let rows = watch_rows(&deployments);
+
+            for d in &rows {
+                match tracker.observe(d) {
+                    StatusChange::Changed(previous) => {
+                        if !tty {
+                            log_state_change(project, &d.deployment_id, &d.status, &None);
+                        }
+                        transitions.insert(d.deployment_id.clone(), (previous, Instant::now()));
+                    }
+                    StatusChange::New if !tty => {
+                        log_state_change(project, &d.deployment_id, &d.status, &None);
+                    }
+                    _ => {}
+                }
+            }
+
+            if tty {
+                let table = render_watch_table(&rows, &transitions, spinner_frame_num);
+                live_section.redraw(&format!(
+                    "Watching deployments of '{}' every {} (Ctrl+C to exit)\n{}\n",
+                    project, interval_str, table
+                ));
+                spinner_frame_num = (spinner_frame_num + 1) % SPINNER_FRAMES.len();
+            }
+
+            tokio::select! {
+                _ = &mut ctrl_c => return Ok(()),
+                _ = tokio::time::sleep(interval) => {}
+            }
+        }
+    }
+    .await;
+
+    if tty {
+        print!("{}", ansi::SHOW_CURSOR);
+        io::stdout().flush().unwrap();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(id: &str, group: &str, status: DeploymentStatus) -> Deployment {
+        Deployment {
+            deployment_id: id.to_string(),
+            deployment_group: group.to_string(),
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_watch_rows_shows_newest_and_active_per_group() {
+        let deployments = vec![
+            deployment("3", "staging", DeploymentStatus::Healthy),
+            deployment("2", "default", DeploymentStatus::Deploying),
+            deployment("1", "default", DeploymentStatus::Healthy),
+            deployment("0", "default", DeploymentStatus::Superseded),
+        ];
+        let ids: Vec<&str> = watch_rows(&deployments)
+            .iter()
+            .map(|d| d.deployment_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["2", "1", "3"]);
+    }
+
+    #[test]
+    fn test_status_tracker_reports_transitions() {
+        let mut tracker = StatusTracker::default();
+        let mut d = deployment("1", "default", DeploymentStatus::Deploying);
+        assert_eq!(tracker.observe(&d), StatusChange::New);
+        assert_eq!(tracker.observe(&d), StatusChange::Unchanged);
+        d.status = DeploymentStatus::Healthy;
+        assert_eq!(
+            tracker.observe(&d),
+            StatusChange::Changed(DeploymentStatus::Deploying)
+        );
+    }
+}
@@ -2,6 +2,10 @@ mod core;
 mod follow_ui;
 
 pub use core::{
-    create_deployment, get_logs, list_deployments, show_deployment, stop_deployments_by_group,
-    DeploymentOptions, EnvOverride, GetLogsParams,
+    cancel_deployment, create_deployment, describe_deployment, diff_deployments,
+    fetch_group_deployments, get_logs, list_deployments, promote_deployment, renew_deployment,
+    restart_deployment, rollback_deployment, scale_deployment, show_deployment,
+    show_deployment_history, stop_deployments_by_group, DeploymentOptions, EnvOverride,
+    GetLogsParams,
 };
+pub use follow_ui::watch_deployments;
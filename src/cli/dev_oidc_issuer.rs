@@ -0,0 +1,268 @@
+//! Local OIDC issuer for testing service accounts (development only)
+//!
+//! Serves an OIDC discovery document and a JWKS, and mints RS256 tokens with
+//! arbitrary claims, so workload identity matching can be tried without a real CI
+//! provider. The signing key is generated on every start and only lives in memory.
+//! This runs inside the CLI process; the backend never serves these endpoints.
+
+use anyhow::{Context, Result};
+use axum::{extract::State, routing::get, Json, Router};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64URL;
+use base64::Engine;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey};
+use rsa::traits::PublicKeyParts;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Path of the JWKS, relative to the issuer URL
+const JWKS_PATH: &str = "/jwks";
+
+/// Claims of minted tokens unless overridden with `--claim` or per request
+const DEFAULT_CLAIMS: &[(&str, &str)] = &[("aud", "rise"), ("sub", "dev")];
+
+struct DevIssuer {
+    issuer_url: String,
+    key_id: String,
+    encoding_key: EncodingKey,
+    jwks: serde_json::Value,
+    default_claims: serde_json::Map<String, serde_json::Value>,
+    expires_in: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: &'static str,
+    expires_in: u64,
+}
+
+impl DevIssuer {
+    fn new(issuer_url: String, claims: &[(String, String)], expires_in: u64) -> Result<Self> {
+        let private_key = RsaPrivateKey::new(&mut rsa::rand_core::OsRng, 2048)
+            .context("Failed to generate RSA key")?;
+        let public_key = RsaPublicKey::from(&private_key);
+
+        let private_key_pem = private_key
+            .to_pkcs8_pem(rsa::pkcs8::LineEnding::LF)
+            .context("Failed to encode private key")?;
+        let public_key_pem = public_key
+            .to_public_key_pem(rsa::pkcs8::LineEnding::LF)
+            .context("Failed to encode public key")?;
+        let encoding_key = EncodingKey::from_rsa_pem(private_key_pem.as_bytes())
+            .context("Failed to load signing key")?;
+        let key_id = hex_prefix(&Sha256::digest(public_key_pem.as_bytes()), 8);
+
+        let jwks = serde_json::json!({
+            "keys": [{
+                "kty": "RSA",
+                "use": "sig",
+                "alg": "RS256",
+                "kid": key_id,
+                "n": BASE64URL.encode(public_key.n().to_bytes_be()),
+                "e": BASE64URL.encode(public_key.e().to_bytes_be()),
+            }]
+        });
+
+        Ok(Self {
+            issuer_url,
+            key_id,
+            encoding_key,
+            jwks,
+            default_claims: default_claims(claims),
+            expires_in,
+        })
+    }
+
+    fn discovery_document(&self) -> serde_json::Value {
+        serde_json::json!({
+            "issuer": self.issuer_url,
+            "jwks_uri": format!("{}{}", self.issuer_url, JWKS_PATH),
+            "response_types_supported": ["id_token"],
+            "subject_types_supported": ["public"],
+            "id_token_signing_alg_values_supported": ["RS256"],
+        })
+    }
+
+    /// Sign a token with the default claims, overridden by `overrides`
+    fn mint(&self, overrides: serde_json::Map<String, serde_json::Value>) -> Result<String> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let claims = token_claims(
+            &self.issuer_url,
+            &self.default_claims,
+            overrides,
+            now,
+            self.expires_in,
+        );
+
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(self.key_id.clone());
+        encode(&header, &claims, &self.encoding_key).context("Failed to sign token")
+    }
+}
+
+/// Default claims merged with the `--claim` values
+fn default_claims(claims: &[(String, String)]) -> serde_json::Map<String, serde_json::Value> {
+    let mut merged: serde_json::Map<String, serde_json::Value> = DEFAULT_CLAIMS
+        .iter()
+        .map(|(key, value)| (key.to_string(), serde_json::json!(value)))
+        .collect();
+    for (key, value) in claims {
+        merged.insert(key.clone(), serde_json::json!(value));
+    }
+    merged
+}
+
+/// Build the claims of a token. `iss`, `iat` and `exp` are always set by the issuer.
+fn token_claims(
+    issuer_url: &str,
+    defaults: &serde_json::Map<String, serde_json::Value>,
+    overrides: serde_json::Map<String, serde_json::Value>,
+    now: u64,
+    expires_in: u64,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut claims = defaults.clone();
+    claims.extend(overrides);
+    claims.insert("iss".to_string(), serde_json::json!(issuer_url));
+    claims.insert("iat".to_string(), serde_json::json!(now));
+    claims.insert("exp".to_string(), serde_json::json!(now + expires_in));
+    claims
+}
+
+fn hex_prefix(bytes: &[u8], len: usize) -> String {
+    bytes[..len].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+async fn discovery(State(issuer): State<Arc<DevIssuer>>) -> Json<serde_json::Value> {
+    Json(issuer.discovery_document())
+}
+
+async fn jwks(State(issuer): State<Arc<DevIssuer>>) -> Json<serde_json::Value> {
+    Json(issuer.jwks.clone())
+}
+
+/// POST /token - mint a token; an optional JSON object body overrides claims
+async fn token(
+    State(issuer): State<Arc<DevIssuer>>,
+    body: Option<Json<serde_json::Map<String, serde_json::Value>>>,
+) -> Result<Json<TokenResponse>, (axum::http::StatusCode, String)> {
+    let overrides = body.map(|Json(claims)| claims).unwrap_or_default();
+    let access_token = issuer.mint(overrides).map_err(|e| {
+        (
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
+            format!("{:#}", e),
+        )
+    })?;
+    Ok(Json(TokenResponse {
+        access_token,
+        token_type: "Bearer",
+        expires_in: issuer.expires_in,
+    }))
+}
+
+/// Run the dev OIDC issuer until interrupted
+pub async fn run_dev_oidc_issuer(
+    bind: &str,
+    port: u16,
+    issuer_url: Option<String>,
+    claims: &[(String, String)],
+    expires_in: u64,
+) -> Result<()> {
+    let issuer_url = issuer_url
+        .unwrap_or_else(|| format!("http://localhost:{}", port))
+        .trim_end_matches('/')
+        .to_string();
+    let issuer = Arc::new(DevIssuer::new(issuer_url.clone(), claims, expires_in)?);
+
+    let app = Router::new()
+        .route("/.well-known/openid-configuration", get(discovery))
+        .route(JWKS_PATH, get(jwks))
+        .route("/token", get(token).post(token))
+        .with_state(issuer.clone());
+
+    let addr = format!("{}:{}", bind, port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind to {}", addr))?;
+
+    let sa_claims: Vec<String> = issuer
+        .default_claims
+        .iter()
+        .map(|(key, value)| match value.as_str() {
+            Some(s) => format!("--claim {}={}", key, s),
+            None => format!("--claim {}={}", key, value),
+        })
+        .collect();
+
+    println!(
+        "Dev OIDC issuer listening on {} (for local testing only; keys change on every start)",
+        addr
+    );
+    println!("\nIssuer URL: {}", issuer_url);
+    println!("\nRegister it with a service account:");
+    println!(
+        "  rise sa create -p <project> --issuer {} {}",
+        issuer_url,
+        sa_claims.join(" ")
+    );
+    println!(
+        "\nToken with the default claims (expires in {}s):",
+        expires_in
+    );
+    println!("  {}", issuer.mint(Default::default())?);
+    println!("\nMint tokens with other claims:");
+    println!(
+        "  export RISE_TOKEN=$(curl -s {}/token -d '{{\"sub\": \"other\"}}' -H 'Content-Type: application/json' | jq -r .access_token)",
+        issuer_url
+    );
+    println!(
+        "\nThe backend must be able to reach the issuer URL (see server.ssrf.allow_http and allow_private_networks)."
+    );
+    println!("Press Ctrl+C to stop.");
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .context("Dev OIDC issuer failed")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_claims_merge_defaults_overrides_and_registered_claims() {
+        let defaults = default_claims(&[
+            ("aud".to_string(), "rise-project-demo".to_string()),
+            ("project_path".to_string(), "org/demo".to_string()),
+        ]);
+        assert_eq!(defaults["aud"], "rise-project-demo");
+        assert_eq!(defaults["sub"], "dev");
+
+        let mut overrides = serde_json::Map::new();
+        overrides.insert("sub".to_string(), serde_json::json!("ci"));
+        overrides.insert("iss".to_string(), serde_json::json!("https://evil.example"));
+        overrides.insert("ref_protected".to_string(), serde_json::json!(true));
+
+        let claims = token_claims("http://localhost:9400", &defaults, overrides, 1000, 60);
+        assert_eq!(
+            serde_json::Value::Object(claims),
+            serde_json::json!({
+                "aud": "rise-project-demo",
+                "sub": "ci",
+                "project_path": "org/demo",
+                "ref_protected": true,
+                "iss": "http://localhost:9400",
+                "iat": 1000,
+                "exp": 1060,
+            })
+        );
+    }
+}
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 struct CustomDomainResponse {
     id: String,
     domain: String,
+    #[serde(default)]
+    verified: bool,
     created_at: String,
     updated_at: String,
 }
@@ -17,6 +19,20 @@ struct CustomDomainsResponse {
     domains: Vec<CustomDomainResponse>,
 }
 
+#[derive(Debug, Deserialize)]
+struct DomainCheckItem {
+    name: String,
+    status: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DomainCheckResponse {
+    domain: String,
+    ingress_host: Option<String>,
+    checks: Vec<DomainCheckItem>,
+}
+
 #[derive(Debug, Serialize)]
 struct AddCustomDomainRequest {
     domain: String,
@@ -107,11 +123,16 @@ pub async fn list_domains(
     table
         .load_preset(UTF8_FULL)
         .apply_modifier(UTF8_ROUND_CORNERS)
-        .set_header(vec![Cell::new("DOMAIN"), Cell::new("CREATED AT")]);
+        .set_header(vec![
+            Cell::new("DOMAIN"),
+            Cell::new("VERIFIED"),
+            Cell::new("CREATED AT"),
+        ]);
 
     for domain in &domains_response.domains {
         table.add_row(vec![
             Cell::new(&domain.domain),
+            Cell::new(if domain.verified { "yes" } else { "no" }),
             Cell::new(&domain.created_at),
         ]);
     }
@@ -160,3 +181,71 @@ pub async fn remove_domain(
 
     Ok(())
 }
+
+/// Diagnose DNS, TLS and HTTP routing of a custom domain
+pub async fn check_domain(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    domain: &str,
+) -> Result<()> {
+    let url = format!(
+        "{}/api/v1/projects/{}/domains/{}/check",
+        backend_url, project, domain
+    );
+
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to check custom domain (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let check_response: DomainCheckResponse = response
+        .json()
+        .await
+        .context("Failed to parse domain check response")?;
+
+    println!("Checking custom domain '{}'", check_response.domain);
+    if let Some(ref ingress_host) = check_response.ingress_host {
+        println!("Expected target: {}", ingress_host);
+    }
+    println!();
+
+    let mut failed = 0;
+    for check in &check_response.checks {
+        let symbol = match check.status.as_str() {
+            "pass" => "✓",
+            "fail" => {
+                failed += 1;
+                "✗"
+            }
+            _ => "-",
+        };
+        println!("{} {}: {}", symbol, check.name, check.message);
+    }
+
+    if failed > 0 {
+        println!();
+        anyhow::bail!(
+            "{} check(s) failed for custom domain '{}'",
+            failed,
+            check_response.domain
+        );
+    }
+
+    Ok(())
+}
@@ -40,14 +40,22 @@ fn env_url(backend_url: &str, project: &str, suffix: &str, environment: Option<&
 }
 
 /// Fetch environment variables from a project (internal helper)
+///
+/// With `include_unprotected_values`, the backend returns decrypted values for
+/// unprotected secrets.
 async fn fetch_env_vars_response(
     http_client: &Client,
     backend_url: &str,
     token: &str,
     project: &str,
     environment: Option<&str>,
+    include_unprotected_values: bool,
 ) -> Result<EnvVarsResponse> {
-    let url = env_url(backend_url, project, "", environment);
+    let mut url = env_url(backend_url, project, "", environment);
+    if include_unprotected_values {
+        url.push(if environment.is_some() { '&' } else { '?' });
+        url.push_str("include_unprotected_values=true");
+    }
 
     let response = http_client
         .get(&url)
@@ -203,6 +211,98 @@ pub async fn set_env(
     Ok(())
 }
 
+#[derive(Debug, Serialize)]
+struct SetEnvVarsItem<'a> {
+    key: &'a str,
+    value: &'a str,
+    is_secret: bool,
+    is_protected: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct SetEnvVarsRequest<'a> {
+    env_vars: Vec<SetEnvVarsItem<'a>>,
+}
+
+/// Set several environment variables for a project in a single request
+///
+/// The backend applies all variables atomically: either every variable is
+/// stored or none are.
+#[allow(clippy::too_many_arguments)]
+pub async fn set_env_many(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    vars: &[(String, String)],
+    is_secret: bool,
+    is_protected: bool,
+    environment: Option<&str>,
+) -> Result<()> {
+    let url = env_url(backend_url, project, "", environment);
+
+    let payload = SetEnvVarsRequest {
+        env_vars: vars
+            .iter()
+            .map(|(key, value)| SetEnvVarsItem {
+                key,
+                value,
+                is_secret,
+                is_protected,
+            })
+            .collect(),
+    };
+
+    let response = http_client
+        .put(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&payload)
+        .send()
+        .await
+        .context("Failed to set environment variables")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to set environment variables (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let env_vars_response: EnvVarsResponse =
+        response.json().await.context("Failed to parse response")?;
+
+    for var in &env_vars_response.env_vars {
+        let var_type = if var.is_secret {
+            if var.is_protected {
+                "protected secret"
+            } else {
+                "unprotected secret"
+            }
+        } else {
+            "plain text"
+        };
+        if let Some(env_name) = environment {
+            println!(
+                "✓ Set {} variable '{}' for project '{}' (environment: {})",
+                var_type, var.key, project, env_name
+            );
+        } else {
+            println!(
+                "✓ Set {} variable '{}' for project '{}'",
+                var_type, var.key, project
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// List environment variables for a project
 pub async fn list_env(
     http_client: &Client,
@@ -212,7 +312,8 @@ pub async fn list_env(
     environment: Option<&str>,
 ) -> Result<()> {
     let env_vars_response =
-        fetch_env_vars_response(http_client, backend_url, token, project, environment).await?;
+        fetch_env_vars_response(http_client, backend_url, token, project, environment, false)
+            .await?;
 
     if env_vars_response.env_vars.is_empty() {
         println!(
@@ -292,7 +393,8 @@ pub async fn get_env(
 ) -> Result<()> {
     // First, fetch the variable to check if it exists and get its metadata
     let env_vars_response =
-        fetch_env_vars_response(http_client, backend_url, token, project, environment).await?;
+        fetch_env_vars_response(http_client, backend_url, token, project, environment, false)
+            .await?;
 
     let env_var = env_vars_response
         .env_vars
@@ -413,17 +515,7 @@ fn iter_env_file_lines(contents: &str) -> impl Iterator<Item = (usize, &str)> {
     })
 }
 
-/// Parse a single KEY=VALUE or KEY=secret:VALUE string
-pub fn parse_env_string(s: &str) -> anyhow::Result<ParsedEnvVar> {
-    let parts: Vec<&str> = s.splitn(2, '=').collect();
-    if parts.len() != 2 {
-        anyhow::bail!("Invalid format (expected KEY=value): {}", s);
-    }
-
-    let key = parts[0].trim();
-    let value_part = parts[1];
-
-    // Validate key name
+fn validate_key(key: &str) -> anyhow::Result<()> {
     if key.is_empty() {
         anyhow::bail!("Invalid key name '' (must be alphanumeric with underscores)");
     }
@@ -435,6 +527,48 @@ pub fn parse_env_string(s: &str) -> anyhow::Result<ParsedEnvVar> {
         );
     }
 
+    Ok(())
+}
+
+/// Parse the positional arguments of `rise env set`
+///
+/// Accepts either the legacy `KEY VALUE` form or any number of `KEY=VALUE`
+/// pairs. Values are taken literally; use `--secret` to mark them as secrets.
+pub fn parse_env_assignments(args: &[String]) -> anyhow::Result<Vec<(String, String)>> {
+    if let [key, value] = args {
+        if !key.contains('=') {
+            validate_key(key)?;
+            return Ok(vec![(key.clone(), value.clone())]);
+        }
+    }
+
+    let mut pairs: Vec<(String, String)> = Vec::with_capacity(args.len());
+    for arg in args {
+        let Some((key, value)) = arg.split_once('=') else {
+            anyhow::bail!("Invalid format (expected KEY=VALUE): {}", arg);
+        };
+        validate_key(key)?;
+        if pairs.iter().any(|(existing, _)| existing == key) {
+            anyhow::bail!("Variable '{}' is given more than once", key);
+        }
+        pairs.push((key.to_string(), value.to_string()));
+    }
+
+    Ok(pairs)
+}
+
+/// Parse a single KEY=VALUE or KEY=secret:VALUE string
+pub fn parse_env_string(s: &str) -> anyhow::Result<ParsedEnvVar> {
+    let parts: Vec<&str> = s.splitn(2, '=').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid format (expected KEY=value): {}", s);
+    }
+
+    let key = parts[0].trim();
+    let value_part = parts[1];
+
+    validate_key(key)?;
+
     let (value, is_secret) = if let Some(stripped) = value_part.strip_prefix("secret:") {
         (stripped, true)
     } else {
@@ -471,6 +605,7 @@ pub fn parse_env_file(contents: &str) -> anyhow::Result<Vec<ParsedEnvVar>> {
 /// - Lines starting with # are comments
 /// - Empty lines are ignored
 /// - Format: KEY=value (plain text) or KEY=secret:value (secret)
+/// - Masked secrets (`KEY=secret:***`, as written by `rise env export`) are skipped
 /// - Example:
 ///   ```
 ///   # Database configuration
@@ -489,6 +624,7 @@ pub async fn import_env(
         .with_context(|| format!("Failed to read file: {}", file_path.display()))?;
 
     let mut success_count = 0;
+    let mut skipped_count = 0;
     let mut error_count = 0;
 
     for (line_num, line) in iter_env_file_lines(&contents) {
@@ -501,6 +637,16 @@ pub async fn import_env(
             }
         };
 
+        // Masked secrets from `rise env export` carry no value; keep the existing one
+        if parsed.is_secret && parsed.value == MASKED_SECRET_VALUE {
+            eprintln!(
+                "Warning: Line {}: Skipping masked secret '{}' (export with --reveal to include it)",
+                line_num, parsed.key
+            );
+            skipped_count += 1;
+            continue;
+        }
+
         // Set the variable
         // Protected defaults to true for secrets, false for non-secrets
         let is_protected = parsed.is_secret;
@@ -529,8 +675,8 @@ pub async fn import_env(
     }
 
     println!(
-        "\n✓ Import complete: {} variables set, {} errors",
-        success_count, error_count
+        "\n✓ Import complete: {} variables set, {} skipped, {} errors",
+        success_count, skipped_count, error_count
     );
 
     if error_count > 0 {
@@ -617,17 +763,181 @@ pub async fn list_deployment_env(
     Ok(())
 }
 
-/// Export environment variables in dotfile format (KEY=value per line).
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum EnvVarChange {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnvVarDiffEntry {
+    key: String,
+    change: EnvVarChange,
+    is_secret: bool,
+    from_value: Option<String>,
+    to_value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeploymentEnvDiffResponse {
+    deployment_id: String,
+    has_changes: bool,
+    env: Vec<EnvVarDiffEntry>,
+}
+
+/// Compare a deployment's environment variables with the current project variables
 ///
-/// Output goes to stdout for clean piping (`rise env export > .env` or
-/// `eval $(rise env export)`). Warnings about protected secrets go to stderr.
+/// Lists project variables that were added, removed or changed since the deployment
+/// was created. Secret values are never shown, only whether they changed.
+pub async fn diff_deployment_env(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    deployment_id: &str,
+) -> Result<()> {
+    let url = format!(
+        "{}/api/v1/projects/{}/deployments/{}/env-diff",
+        backend_url, project, deployment_id
+    );
+
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .context("Failed to diff deployment environment variables")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to diff deployment environment variables (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let diff: DeploymentEnvDiffResponse = response
+        .json()
+        .await
+        .context("Failed to parse environment diff response")?;
+
+    if !diff.has_changes {
+        println!(
+            "Deployment '{}' is up to date with the environment variables of project '{}'",
+            diff.deployment_id, project
+        );
+        return Ok(());
+    }
+
+    println!("--- {} (deployment)", diff.deployment_id);
+    println!("+++ {} (project)", project);
+    for var in &diff.env {
+        let line = |value: &Option<String>| match value {
+            Some(value) => format!("{}={}", var.key, value),
+            None => format!("{} (secret)", var.key),
+        };
+        match var.change {
+            EnvVarChange::Unchanged => {}
+            EnvVarChange::Added => println!("+ {}", line(&var.to_value)),
+            EnvVarChange::Removed => println!("- {}", line(&var.from_value)),
+            EnvVarChange::Changed
+                if var.is_secret && var.from_value.is_none() && var.to_value.is_none() =>
+            {
+                println!("~ {} (secret, value changed)", var.key)
+            }
+            EnvVarChange::Changed => {
+                println!("- {}", line(&var.from_value));
+                println!("+ {}", line(&var.to_value));
+            }
+        }
+    }
+    println!("\nRedeploy to pick up the current project environment variables");
+
+    Ok(())
+}
+
+/// Value written for secrets whose value is not exported
+const MASKED_SECRET_VALUE: &str = "***";
+
+/// Output formats of `rise env export`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Resolved `KEY=value` lines as a deployment sees them, for `eval` or `source`
+    #[default]
+    Dotenv,
+    /// The project's stored variables in the `rise env import` file format
+    Import,
+}
+
+/// Export environment variables to stdout or `file`
+///
+/// With [`ExportFormat::Dotenv`], the variables a deployment to `environment`
+/// would get are written as `KEY=value`; protected secrets are left out with a
+/// warning on stderr. With [`ExportFormat::Import`], see [`format_import_file`].
+#[allow(clippy::too_many_arguments)]
 pub async fn export_env(
     http_client: &Client,
     backend_url: &str,
     token: &str,
     project: &str,
     environment: Option<&str>,
+    format: ExportFormat,
+    file: Option<&PathBuf>,
+    reveal: bool,
 ) -> Result<()> {
+    let (contents, count) = match format {
+        ExportFormat::Dotenv => {
+            if reveal {
+                anyhow::bail!("--reveal only applies to --format import");
+            }
+            format_dotenv_file(http_client, backend_url, token, project, environment).await?
+        }
+        ExportFormat::Import => {
+            format_import_file(
+                http_client,
+                backend_url,
+                token,
+                project,
+                environment,
+                reveal,
+            )
+            .await?
+        }
+    };
+
+    match file {
+        Some(path) => {
+            std::fs::write(path, &contents)
+                .with_context(|| format!("Failed to write file: {}", path.display()))?;
+            eprintln!(
+                "✓ Exported {} variable{} to {}",
+                count,
+                if count == 1 { "" } else { "s" },
+                path.display()
+            );
+        }
+        None => print!("{}", contents),
+    }
+
+    Ok(())
+}
+
+/// Render the resolved variables of a deployment to `environment` as `KEY=value` lines
+async fn format_dotenv_file(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    environment: Option<&str>,
+) -> Result<(String, usize)> {
     let (loadable_vars, protected_keys) = fetch_preview_env_vars(
         http_client,
         backend_url,
@@ -649,16 +959,84 @@ pub async fn export_env(
         }
     }
 
-    for (key, value) in &loadable_vars {
-        println!("{}={}", key, value);
+    let contents = loadable_vars
+        .iter()
+        .map(|(key, value)| format!("{}={}\n", key, value))
+        .collect();
+    Ok((contents, loadable_vars.len()))
+}
+
+/// Render the project's stored variables in the `rise env import` file format
+///
+/// Without `environment`, only global variables are exported; with it, only the
+/// variables scoped to that environment. Secrets are written as `KEY=secret:***`
+/// unless `reveal` is set, in which case unprotected secrets are decrypted by the
+/// backend. Protected secrets are always masked.
+async fn format_import_file(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    environment: Option<&str>,
+    reveal: bool,
+) -> Result<(String, usize)> {
+    let env_vars_response = fetch_env_vars_response(
+        http_client,
+        backend_url,
+        token,
+        project,
+        environment,
+        reveal,
+    )
+    .await?;
+
+    let vars: Vec<EnvVarResponse> = env_vars_response
+        .env_vars
+        .into_iter()
+        .filter(|var| var.environment.as_deref() == environment)
+        .collect();
+
+    if reveal {
+        let protected_keys: Vec<&str> = vars
+            .iter()
+            .filter(|var| var.is_protected)
+            .map(|var| var.key.as_str())
+            .collect();
+        if !protected_keys.is_empty() {
+            eprintln!(
+                "warning: {} protected secret{} masked (cannot be revealed):",
+                protected_keys.len(),
+                if protected_keys.len() == 1 { "" } else { "s" }
+            );
+            for key in &protected_keys {
+                eprintln!("  - {}", key);
+            }
+        }
     }
 
-    Ok(())
+    Ok((format_env_file(&vars, reveal), vars.len()))
+}
+
+/// Render variables as `KEY=value` / `KEY=secret:value` lines
+fn format_env_file(vars: &[EnvVarResponse], reveal: bool) -> String {
+    vars.iter()
+        .map(|var| {
+            if !var.is_secret {
+                format!("{}={}\n", var.key, var.value)
+            } else if reveal && !var.is_protected {
+                format!("{}=secret:{}\n", var.key, var.value)
+            } else {
+                format!("{}=secret:{}\n", var.key, MASKED_SECRET_VALUE)
+            }
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{parse_env_file, parse_env_string};
+    use super::{
+        format_env_file, parse_env_assignments, parse_env_file, parse_env_string, EnvVarResponse,
+    };
 
     #[test]
     fn parse_env_string_rejects_empty_keys() {
@@ -686,4 +1064,54 @@ mod tests {
         assert_eq!(parsed.value, "value");
         assert!(parsed.is_secret);
     }
+
+    #[test]
+    fn parse_env_assignments_accepts_legacy_and_pair_forms() {
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        let legacy = parse_env_assignments(&args(&["PORT", "8080"])).unwrap();
+        assert_eq!(legacy, vec![("PORT".to_string(), "8080".to_string())]);
+
+        let pairs = parse_env_assignments(&args(&["A=1", "B=x=y"])).unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("A".to_string(), "1".to_string()),
+                ("B".to_string(), "x=y".to_string())
+            ]
+        );
+
+        let err = parse_env_assignments(&args(&["A=1", "B"])).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid format (expected KEY=VALUE): B");
+
+        let err = parse_env_assignments(&args(&["A=1", "A=2"])).unwrap_err();
+        assert_eq!(err.to_string(), "Variable 'A' is given more than once");
+    }
+
+    #[test]
+    fn format_env_file_round_trips_through_parse_env_file() {
+        let var = |key: &str, value: &str, is_secret: bool, is_protected: bool| EnvVarResponse {
+            key: key.to_string(),
+            value: value.to_string(),
+            is_secret,
+            is_protected,
+            environment: None,
+            source: None,
+        };
+        let vars = vec![
+            var("DB_HOST", "localhost", false, false),
+            var("API_KEY", "a=b:c", true, false),
+            var("SIGNING_KEY", "••••••••", true, true),
+        ];
+
+        let revealed = parse_env_file(&format_env_file(&vars, true)).unwrap();
+        assert_eq!(revealed[0].value, "localhost");
+        assert!(!revealed[0].is_secret);
+        assert_eq!(revealed[1].value, "a=b:c");
+        assert!(revealed[1].is_secret);
+        assert_eq!(revealed[2].value, "***");
+
+        let masked = format_env_file(&vars, false);
+        assert!(masked.contains("API_KEY=secret:***\n"));
+    }
 }
@@ -42,6 +42,42 @@ struct ListExtensionsResponse {
     extensions: Vec<Extension>,
 }
 
+#[derive(Debug, Serialize)]
+struct ValidateExtensionSpecRequest {
+    spec: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidateExtensionSpecResponse {
+    valid: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionTypeMetadata {
+    extension_type: String,
+    display_name: String,
+    documentation: String,
+    spec_schema: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListExtensionTypesResponse {
+    extension_types: Vec<ExtensionTypeMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionEvent {
+    timestamp: String,
+    level: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtensionEventsResponse {
+    events: Vec<ExtensionEvent>,
+}
+
 /// Create or update extension for a project
 pub async fn create_extension(
     project: &str,
@@ -327,6 +363,64 @@ pub async fn show_extension(project: &str, extension: &str) -> Result<()> {
     Ok(())
 }
 
+/// Show the recent reconcile events of an extension
+pub async fn extension_logs(project: &str, extension: &str, tail: Option<usize>) -> Result<()> {
+    let config = Config::load()?;
+    let backend_url = config.get_backend_url();
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not authenticated. Please run 'rise login' first"))?;
+
+    let http_client = Client::new();
+    let url = format!(
+        "{}/api/v1/projects/{}/extensions/{}/events",
+        backend_url, project, extension
+    );
+
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .context("Failed to get extension events")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to get extension events (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let events_response: ExtensionEventsResponse = response
+        .json()
+        .await
+        .context("Failed to parse extension events response")?;
+
+    let events = &events_response.events;
+    if events.is_empty() {
+        println!("No events recorded for extension '{}'", extension);
+        return Ok(());
+    }
+
+    let skip = tail.map_or(0, |n| events.len().saturating_sub(n));
+    for event in &events[skip..] {
+        println!(
+            "{} {:<7} {}",
+            event.timestamp,
+            event.level.to_uppercase(),
+            event.message
+        );
+    }
+
+    Ok(())
+}
+
 /// Delete extension from a project
 pub async fn delete_extension(project: &str, extension: &str) -> Result<()> {
     let config = Config::load()?;
@@ -369,3 +463,105 @@ pub async fn delete_extension(project: &str, extension: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Validate a spec against an extension type and/or show the type's spec schema,
+/// without creating or changing any extension
+pub async fn validate_extension(
+    extension_type: &str,
+    spec: Option<Value>,
+    show_schema: bool,
+) -> Result<()> {
+    let config = Config::load()?;
+    let backend_url = config.get_backend_url();
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not authenticated. Please run 'rise login' first"))?;
+
+    let http_client = Client::new();
+
+    if show_schema {
+        let url = format!("{}/api/v1/extensions/types", backend_url);
+        let response = http_client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await
+            .context("Failed to list extension types")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            anyhow::bail!(
+                "Failed to list extension types (status {}): {}",
+                status,
+                error_text
+            );
+        }
+
+        let types_response: ListExtensionTypesResponse = response
+            .json()
+            .await
+            .context("Failed to parse extension types response")?;
+        let metadata = types_response
+            .extension_types
+            .into_iter()
+            .find(|t| t.extension_type == extension_type)
+            .ok_or_else(|| anyhow::anyhow!("Unknown extension type: {}", extension_type))?;
+
+        println!("{} ({})", metadata.display_name, metadata.extension_type);
+        println!("\n{}", metadata.documentation.trim());
+        println!("\nSpec schema:");
+        println!("{}", serde_json::to_string_pretty(&metadata.spec_schema)?);
+    }
+
+    let Some(spec) = spec else {
+        return Ok(());
+    };
+
+    let url = format!(
+        "{}/api/v1/extensions/types/{}/validate",
+        backend_url, extension_type
+    );
+    let response = http_client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&ValidateExtensionSpecRequest { spec })
+        .send()
+        .await
+        .context("Failed to validate extension spec")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to validate extension spec (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let validation: ValidateExtensionSpecResponse = response
+        .json()
+        .await
+        .context("Failed to parse validate extension spec response")?;
+
+    if show_schema {
+        println!();
+    }
+    if !validation.valid {
+        anyhow::bail!(
+            "Spec is not valid for extension type '{}': {}",
+            extension_type,
+            validation.error.unwrap_or_default()
+        );
+    }
+    println!("✓ Spec is valid for extension type '{}'", extension_type);
+
+    Ok(())
+}
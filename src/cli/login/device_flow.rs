@@ -1,3 +1,4 @@
+use super::issuers::ensure_issuer_exists;
 use crate::config::{normalize_backend_url, Config};
 use crate::login::token_utils::{format_token_expiration, log_token_debug};
 use anyhow::{Context, Result};
@@ -8,6 +9,8 @@ use std::time::Duration;
 #[derive(Debug, Serialize)]
 struct AuthorizeRequest {
     flow: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issuer: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,9 +47,14 @@ pub async fn handle_device_flow(
     backend_url: &str,
     config: &mut Config,
     backend_url_to_save: Option<&str>,
+    issuer: Option<&str>,
 ) -> Result<()> {
     let backend_url = normalize_backend_url(backend_url);
 
+    if let Some(issuer) = issuer {
+        ensure_issuer_exists(http_client, &backend_url, issuer).await?;
+    }
+
     eprintln!("⚠️  Warning: Device flow may not be supported by all identity providers.");
     eprintln!("   For best results, use the browser flow: rise login");
     eprintln!();
@@ -57,6 +65,7 @@ pub async fn handle_device_flow(
     let authorize_url = format!("{}/api/v1/auth/authorize", backend_url);
     let authorize_request = AuthorizeRequest {
         flow: "device".to_string(),
+        issuer: issuer.map(str::to_string),
     };
 
     let response = http_client
@@ -116,6 +125,8 @@ pub async fn handle_device_flow(
     #[derive(Serialize)]
     struct DeviceExchangeRequest {
         device_code: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        issuer: Option<String>,
     }
 
     #[derive(Deserialize)]
@@ -141,6 +152,7 @@ pub async fn handle_device_flow(
 
         let exchange_request = DeviceExchangeRequest {
             device_code: device_code.clone(),
+            issuer: issuer.map(str::to_string),
         };
 
         let response = http_client
@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct IssuersResponse {
+    issuers: Vec<LoginIssuer>,
+}
+
+/// An OIDC issuer the backend offers for login
+#[derive(Debug, Deserialize)]
+struct LoginIssuer {
+    name: String,
+    #[serde(default)]
+    display_name: Option<String>,
+    issuer: String,
+}
+
+/// Check that the backend offers the issuer `name` for login
+///
+/// Fails with the list of available issuers if it doesn't.
+pub(crate) async fn ensure_issuer_exists(
+    http_client: &Client,
+    backend_url: &str,
+    name: &str,
+) -> Result<()> {
+    let url = format!("{}/api/v1/auth/issuers", backend_url);
+
+    let response = http_client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch login issuers from backend")?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!("The backend does not support selecting a login issuer");
+    }
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to fetch login issuers (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let issuers: IssuersResponse = response
+        .json()
+        .await
+        .context("Failed to parse login issuers response")?;
+
+    match issuers.issuers.iter().find(|issuer| issuer.name == name) {
+        Some(issuer) => {
+            println!(
+                "Logging in with issuer '{}' ({})",
+                issuer.name,
+                issuer.display_name.as_deref().unwrap_or(&issuer.issuer)
+            );
+            Ok(())
+        }
+        None => {
+            let available: Vec<&str> = issuers
+                .issuers
+                .iter()
+                .map(|issuer| issuer.name.as_str())
+                .collect();
+            anyhow::bail!(
+                "Unknown issuer '{}'. Available issuers: {}",
+                name,
+                available.join(", ")
+            )
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod device_flow;
+mod issuers;
 pub mod oauth_code;
 pub(crate) mod token_utils;
 
@@ -1,3 +1,4 @@
+use super::issuers::ensure_issuer_exists;
 use crate::config::{normalize_backend_url, Config};
 use crate::login::token_utils::{format_token_expiration, log_token_debug};
 use anyhow::{Context, Result};
@@ -130,6 +131,8 @@ struct AuthorizeRequest {
     code_challenge: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     code_challenge_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issuer: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -142,6 +145,8 @@ struct CodeExchangeRequest {
     code: String,
     code_verifier: String,
     redirect_uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issuer: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -193,9 +198,14 @@ pub async fn handle_authorization_code_flow(
     backend_url: &str,
     config: &mut Config,
     backend_url_to_save: Option<&str>,
+    issuer: Option<&str>,
 ) -> Result<()> {
     let backend_url = normalize_backend_url(backend_url);
 
+    if let Some(issuer) = issuer {
+        ensure_issuer_exists(http_client, &backend_url, issuer).await?;
+    }
+
     // Step 1: Discover OpenID endpoints
     tracing::debug!("Discovering authentication endpoints...");
     let discovery = discover_endpoints(http_client, &backend_url)
@@ -218,6 +228,7 @@ pub async fn handle_authorization_code_flow(
         redirect_uri: Some(redirect_uri.clone()),
         code_challenge: Some(code_challenge.clone()),
         code_challenge_method: Some("S256".to_string()),
+        issuer: issuer.map(str::to_string),
     };
 
     let response = http_client
@@ -277,6 +288,7 @@ pub async fn handle_authorization_code_flow(
         code,
         code_verifier,
         redirect_uri,
+        issuer: issuer.map(str::to_string),
     };
 
     let response = http_client
@@ -2,14 +2,17 @@
 pub mod backend;
 pub mod config;
 pub mod deployment;
+pub mod dev_oidc_issuer;
 pub mod domain;
 pub mod encrypt;
 pub mod env;
 pub mod environment;
 pub mod extension;
 pub mod login;
+pub mod output;
 pub mod project;
 pub mod run;
 pub mod service_account;
 pub mod team;
 pub mod version;
+pub mod whoami;
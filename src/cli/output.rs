@@ -0,0 +1,27 @@
+//! Output formats for listing commands.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::IsTerminal;
+
+/// How list commands render their results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable table
+    #[default]
+    Table,
+    /// JSON array of the API objects, for scripting
+    Json,
+}
+
+/// Print `value` as JSON to stdout, pretty-printed only when stdout is a terminal
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    let json = if std::io::stdout().is_terminal() {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+    .context("Failed to serialize output as JSON")?;
+    println!("{}", json);
+    Ok(())
+}
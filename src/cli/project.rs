@@ -1,7 +1,9 @@
+use crate::api::models::{Deployment, DeploymentStatus};
 use crate::api::project::{
-    CreateProjectResponse, MeResponse, OwnerInfo, Project, ProjectErrorResponse, ProjectStatus,
-    UpdateProjectResponse,
+    CreateProjectResponse, GroupTtlPolicy, MeResponse, OwnerInfo, Project, ProjectErrorResponse,
+    ProjectStatus, TransferProjectResponse, UpdateProjectResponse,
 };
+use crate::cli::output::{print_json, OutputFormat};
 use crate::config::Config;
 use anyhow::{Context, Result};
 use comfy_table::{modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Attribute, Cell, Table};
@@ -48,14 +50,14 @@ async fn get_current_user(
 fn parse_owner(owner: &str) -> Result<(String, String)> {
     let parts: Vec<&str> = owner.splitn(2, ':').collect();
     if parts.len() != 2 {
-        anyhow::bail!("Invalid owner format. Use 'user:email' or 'team:name'");
+        anyhow::bail!("Invalid owner format. Use 'user:email', 'team:name' or 'group:name'");
     }
 
     let owner_type = parts[0].to_lowercase();
     let owner_value = parts[1].to_string();
 
-    if owner_type != "user" && owner_type != "team" {
-        anyhow::bail!("Owner type must be 'user' or 'team'");
+    if !matches!(owner_type.as_str(), "user" | "team" | "group") {
+        anyhow::bail!("Owner type must be 'user', 'team' or 'group'");
     }
 
     Ok((owner_type, owner_value))
@@ -129,12 +131,13 @@ pub async fn create_project(
     enum OwnerType {
         User(String),
         Team(String),
+        Group(String),
     }
 
-    let owner_payload = if owner_type == "user" {
-        OwnerType::User(owner_id)
-    } else {
-        OwnerType::Team(owner_id)
+    let owner_payload = match owner_type.as_str() {
+        "user" => OwnerType::User(owner_id),
+        "team" => OwnerType::Team(owner_id),
+        _ => OwnerType::Group(owner_id),
     };
 
     #[derive(Serialize)]
@@ -208,7 +211,12 @@ pub async fn create_project(
 }
 
 // List all projects
-pub async fn list_projects(http_client: &Client, backend_url: &str, config: &Config) -> Result<()> {
+pub async fn list_projects(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    output: OutputFormat,
+) -> Result<()> {
     let token = config
         .get_token()
         .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
@@ -227,7 +235,9 @@ pub async fn list_projects(http_client: &Client, backend_url: &str, config: &Con
             .await
             .context("Failed to parse list projects response")?;
 
-        if projects.is_empty() {
+        if output == OutputFormat::Json {
+            print_json(&projects)?;
+        } else if projects.is_empty() {
             println!("No projects found.");
         } else {
             let mut table = Table::new();
@@ -288,12 +298,13 @@ pub async fn list_projects(http_client: &Client, backend_url: &str, config: &Con
     Ok(())
 }
 
-// Show project details
+// Show project details, optionally with the most recent deployments of each group
 pub async fn show_project(
     http_client: &Client,
     backend_url: &str,
     config: &Config,
     project_identifier: &str,
+    deployments_per_group: Option<usize>,
 ) -> Result<()> {
     let token = config
         .get_token()
@@ -325,6 +336,24 @@ pub async fn show_project(
         if let Some(ref url) = project.source_url {
             println!("Source URL: {}", url);
         }
+        match project.force_https {
+            Some(true) => println!("Force HTTPS: on"),
+            Some(false) => println!("Force HTTPS: off"),
+            None => println!("Force HTTPS: auto (when TLS is configured)"),
+        }
+        if let Some(max_age) = project.hsts_max_age {
+            println!("HSTS: max-age={}", max_age);
+        }
+        match project.max_active_deployments {
+            Some(quota) => println!("Deployment Quota: {} active", quota),
+            None => println!("Deployment Quota: server default"),
+        }
+        if !project.group_ttls.is_empty() {
+            println!("Group TTLs:");
+            for policy in &project.group_ttls {
+                println!("  - {} → {}", policy.pattern, policy.expire);
+            }
+        }
         if !project.custom_domain_urls.is_empty() {
             println!("Custom Domains:");
             for domain_url in &project.custom_domain_urls {
@@ -376,6 +405,34 @@ pub async fn show_project(
                 defaults.replicas, defaults.cpu, defaults.memory
             );
         }
+
+        if let Some(limit) = deployments_per_group {
+            let groups = project.deployment_groups.unwrap_or_default();
+            let mut deployments = Vec::new();
+            for group in &groups {
+                deployments.extend(
+                    crate::cli::deployment::fetch_group_deployments(
+                        http_client,
+                        backend_url,
+                        &token,
+                        &project.name,
+                        group,
+                        limit,
+                    )
+                    .await?,
+                );
+            }
+
+            println!("\nRecent Deployments:");
+            if deployments.is_empty() {
+                println!("  (none)");
+            } else {
+                println!(
+                    "{}",
+                    recent_deployments_table(&deployments, chrono::Utc::now())
+                );
+            }
+        }
     } else if response.status() == reqwest::StatusCode::NOT_FOUND {
         // Handle 404 with potential fuzzy match suggestions
         let error: ProjectErrorResponse = response
@@ -403,6 +460,78 @@ pub async fn show_project(
     Ok(())
 }
 
+/// Render deployments as a compact GROUP / DEPLOYMENT / STATUS / URL / AGE table
+fn recent_deployments_table(
+    deployments: &[Deployment],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Table {
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_header(vec![
+            Cell::new("GROUP").add_attribute(Attribute::Bold),
+            Cell::new("DEPLOYMENT").add_attribute(Attribute::Bold),
+            Cell::new("STATUS").add_attribute(Attribute::Bold),
+            Cell::new("URL").add_attribute(Attribute::Bold),
+            Cell::new("AGE").add_attribute(Attribute::Bold),
+        ]);
+
+    for deployment in deployments {
+        // Only Healthy deployments can be connected to
+        let url = match deployment.status {
+            DeploymentStatus::Healthy => deployment.primary_url.as_deref().unwrap_or("-"),
+            _ => "-",
+        };
+        let age = chrono::DateTime::parse_from_rfc3339(&deployment.created)
+            .map(|created| format_age(now - created.with_timezone(&chrono::Utc)))
+            .unwrap_or_else(|_| "-".to_string());
+
+        table.add_row(vec![
+            Cell::new(&deployment.deployment_group),
+            Cell::new(&deployment.deployment_id),
+            Cell::new(deployment.status.to_string()),
+            Cell::new(url),
+            Cell::new(age),
+        ]);
+    }
+
+    table
+}
+
+/// Format a duration as a short age in its largest unit (e.g. "45s", "3h", "2d")
+pub(crate) fn format_age(age: chrono::Duration) -> String {
+    let secs = age.num_seconds().max(0);
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
+/// Values of `rise project update --force-https`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ForceHttps {
+    /// Always redirect HTTP to HTTPS
+    True,
+    /// Never redirect
+    False,
+    /// Redirect only when TLS is configured (the default)
+    Auto,
+}
+
+impl ForceHttps {
+    /// The project's `force_https` setting; `None` is automatic
+    pub fn setting(self) -> Option<bool> {
+        match self {
+            ForceHttps::True => Some(true),
+            ForceHttps::False => Some(false),
+            ForceHttps::Auto => None,
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn update_project(
     http_client: &Client,
@@ -413,6 +542,9 @@ pub async fn update_project(
     access_class: Option<String>,
     owner: Option<String>,
     source_url: Option<Option<String>>,
+    force_https: Option<ForceHttps>,
+    hsts_max_age: Option<u32>,
+    max_active_deployments: Option<u32>,
 ) -> Result<()> {
     let token = config
         .get_token()
@@ -423,14 +555,15 @@ pub async fn update_project(
     enum OwnerType {
         User(String),
         Team(String),
+        Group(String),
     }
 
     let owner_payload = if let Some(owner_str) = owner {
         let (owner_type, owner_id) = parse_owner(&owner_str)?;
-        Some(if owner_type == "user" {
-            OwnerType::User(owner_id)
-        } else {
-            OwnerType::Team(owner_id)
+        Some(match owner_type.as_str() {
+            "user" => OwnerType::User(owner_id),
+            "team" => OwnerType::Team(owner_id),
+            _ => OwnerType::Group(owner_id),
         })
     } else {
         None
@@ -446,6 +579,13 @@ pub async fn update_project(
         owner: Option<OwnerType>,
         #[serde(skip_serializing_if = "Option::is_none")]
         source_url: Option<Option<String>>,
+        /// Some(None) resets to automatic
+        #[serde(skip_serializing_if = "Option::is_none")]
+        force_https: Option<Option<bool>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        hsts_max_age: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_active_deployments: Option<u32>,
     }
 
     let request = UpdateRequest {
@@ -453,6 +593,9 @@ pub async fn update_project(
         access_class: access_class.clone(),
         owner: owner_payload,
         source_url: source_url.clone(),
+        force_https: force_https.map(ForceHttps::setting),
+        hsts_max_age,
+        max_active_deployments,
     };
 
     let url = format!("{}/api/v1/projects/{}", backend_url, project_identifier);
@@ -505,6 +648,154 @@ pub async fn update_project(
     Ok(())
 }
 
+// Format a project owner as "user:email" / "team:name"
+fn format_owner(owner: Option<&OwnerInfo>) -> String {
+    match owner {
+        Some(OwnerInfo::User(u)) => format!("user:{}", u.email),
+        Some(OwnerInfo::Team(t)) => format!("team:{}", t.name),
+        None => "(none)".to_string(),
+    }
+}
+
+// Transfer a project to a new owner after confirmation
+pub async fn transfer_project(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project_identifier: &str,
+    new_owner: &str,
+    yes: bool,
+) -> Result<()> {
+    use std::io::{IsTerminal, Write};
+
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let (owner_type, owner_id) = parse_owner(new_owner)?;
+
+    let url = format!("{}/api/v1/projects/{}", backend_url, project_identifier);
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .context("Failed to send get project request")?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        let error: ProjectErrorResponse = response
+            .json()
+            .await
+            .context("Failed to parse error response")?;
+
+        eprintln!("{}", error.error);
+        if let Some(suggestions) = error.suggestions {
+            eprintln!("\nDid you mean one of these?");
+            for suggestion in suggestions {
+                eprintln!("  - {}", suggestion);
+            }
+        }
+        std::process::exit(1);
+    } else if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!("Failed to get project (status {}): {}", status, error_text);
+    }
+
+    let project: Project = response
+        .json()
+        .await
+        .context("Failed to parse get project response")?;
+
+    println!("Project:       {}", project.name);
+    println!("Current owner: {}", format_owner(project.owner.as_ref()));
+    println!("New owner:     {}:{}", owner_type, owner_id);
+
+    if !yes {
+        if !std::io::stdin().is_terminal() {
+            anyhow::bail!("Refusing to transfer without confirmation; pass --yes to confirm");
+        }
+
+        print!("\nTransfer project '{}'? [y/N] ", project.name);
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin()
+            .read_line(&mut answer)
+            .context("Failed to read confirmation")?;
+        if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+            println!("Aborted");
+            return Ok(());
+        }
+    }
+
+    let owner = match owner_type.as_str() {
+        "user" => serde_json::json!({ "user": owner_id }),
+        "team" => serde_json::json!({ "team": owner_id }),
+        _ => serde_json::json!({ "group": owner_id }),
+    };
+
+    let url = format!(
+        "{}/api/v1/projects/{}/transfer",
+        backend_url, project_identifier
+    );
+    let response = http_client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&serde_json::json!({ "owner": owner }))
+        .send()
+        .await
+        .context("Failed to send transfer project request")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to transfer project (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let transfer: TransferProjectResponse = response
+        .json()
+        .await
+        .context("Failed to parse transfer project response")?;
+
+    println!(
+        "\n✓ Transferred project '{}' from {} to {}",
+        transfer.project.name,
+        format_owner(transfer.previous_owner.as_ref()),
+        format_owner(transfer.project.owner.as_ref())
+    );
+
+    if transfer.active_deployments.is_empty() {
+        println!("  No active deployments");
+    } else {
+        println!(
+            "  Active deployments (keep running under the new owner): {}",
+            transfer.active_deployments.len()
+        );
+        for deployment in &transfer.active_deployments {
+            println!(
+                "    {}: {} ({})",
+                deployment.deployment_group, deployment.deployment_id, deployment.status
+            );
+        }
+    }
+
+    for warning in &transfer.warnings {
+        println!("  ⚠ {}", warning);
+    }
+
+    Ok(())
+}
+
 // Delete a project
 pub async fn delete_project(
     http_client: &Client,
@@ -652,6 +943,7 @@ pub async fn add_app_user(
         app_users: Some(updated_users),
         app_teams: Some(updated_teams),
         source_url: None,
+        group_ttls: None,
     };
 
     let url = format!("{}/api/v1/projects/{}", backend_url, project);
@@ -684,6 +976,223 @@ pub async fn add_app_user(
     Ok(())
 }
 
+/// Fetch a project and replace its group TTL policies with the result of `update`
+async fn update_group_ttls(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    update: impl FnOnce(&mut Vec<GroupTtlPolicy>) -> Result<()>,
+) -> Result<()> {
+    use crate::api::project::UpdateProjectRequest;
+
+    let url = format!("{}/api/v1/projects/{}", backend_url, project);
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .context("Failed to fetch project")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to fetch project (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let current_project: Project = response
+        .json()
+        .await
+        .context("Failed to parse project response")?;
+
+    let mut policies = current_project.group_ttls;
+    update(&mut policies)?;
+
+    let request = UpdateProjectRequest {
+        name: None,
+        access_class: None,
+        owner: None,
+        app_users: None,
+        app_teams: None,
+        source_url: None,
+        group_ttls: Some(policies),
+    };
+
+    let response = http_client
+        .put(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&request)
+        .send()
+        .await
+        .context("Failed to update project")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to update group TTLs (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    Ok(())
+}
+
+/// Set the TTL of deployment groups matching `pattern` (replaces an existing policy
+/// for the same pattern, otherwise appends it)
+pub async fn set_group_ttl(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    pattern: &str,
+    duration: &str,
+) -> Result<()> {
+    crate::rise_toml::parse_expiration_duration(duration)
+        .map_err(|e| anyhow::anyhow!("Invalid duration '{}': {}", duration, e))?;
+
+    let policy = GroupTtlPolicy {
+        pattern: pattern.to_string(),
+        expire: duration.to_string(),
+    };
+    update_group_ttls(http_client, backend_url, token, project, |policies| {
+        match policies.iter_mut().find(|p| p.pattern == policy.pattern) {
+            Some(existing) => existing.expire = policy.expire,
+            None => policies.push(policy),
+        }
+        Ok(())
+    })
+    .await?;
+
+    println!(
+        "✓ Deployments in groups matching '{}' of project '{}' now expire after {}",
+        pattern, project, duration
+    );
+    Ok(())
+}
+
+/// Remove the TTL policy for `pattern`
+pub async fn unset_group_ttl(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    pattern: &str,
+) -> Result<()> {
+    update_group_ttls(http_client, backend_url, token, project, |policies| {
+        let before = policies.len();
+        policies.retain(|p| p.pattern != pattern);
+        if policies.len() == before {
+            anyhow::bail!(
+                "Project '{}' has no group TTL for pattern '{}'",
+                project,
+                pattern
+            );
+        }
+        Ok(())
+    })
+    .await?;
+
+    println!(
+        "✓ Removed group TTL for '{}' from project '{}'",
+        pattern, project
+    );
+    Ok(())
+}
+
+/// Configure the webhook that is notified about deployment status changes
+pub async fn set_webhook(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+    webhook_url: &str,
+    secret: Option<&str>,
+) -> Result<()> {
+    use crate::api::project::{ProjectWebhook, SetProjectWebhookRequest};
+
+    let url = format!("{}/api/v1/projects/{}/webhook", backend_url, project);
+    let request = SetProjectWebhookRequest {
+        url: webhook_url.to_string(),
+        secret: secret.map(str::to_string),
+    };
+
+    let response = http_client
+        .put(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&request)
+        .send()
+        .await
+        .context("Failed to set webhook")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!("Failed to set webhook (status {}): {}", status, error_text);
+    }
+
+    let webhook: ProjectWebhook = response
+        .json()
+        .await
+        .context("Failed to parse webhook response")?;
+
+    println!(
+        "✓ Deployment status changes of project '{}' are now posted to {}",
+        project, webhook.url
+    );
+    if webhook.has_secret {
+        println!("  Requests are signed with HMAC-SHA256 in the X-Rise-Signature header");
+    }
+    Ok(())
+}
+
+/// Remove the deployment status webhook of a project
+pub async fn unset_webhook(
+    http_client: &Client,
+    backend_url: &str,
+    token: &str,
+    project: &str,
+) -> Result<()> {
+    let url = format!("{}/api/v1/projects/{}/webhook", backend_url, project);
+
+    let response = http_client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .context("Failed to remove webhook")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to remove webhook (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    println!("✓ Removed webhook from project '{}'", project);
+    Ok(())
+}
+
 /// Remove a user or team from app users
 pub async fn remove_app_user(
     http_client: &Client,
@@ -763,6 +1272,7 @@ pub async fn remove_app_user(
         app_users: Some(updated_users),
         app_teams: Some(updated_teams),
         source_url: None,
+        group_ttls: None,
     };
 
     let url = format!("{}/api/v1/projects/{}", backend_url, project);
@@ -862,3 +1372,71 @@ pub async fn list_app_users(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(id: &str, status: DeploymentStatus, created: &str) -> Deployment {
+        Deployment {
+            deployment_id: id.to_string(),
+            deployment_group: "default".to_string(),
+            status,
+            primary_url: Some("https://demo.example.com".to_string()),
+            created: created.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_age_uses_largest_unit() {
+        assert_eq!(format_age(chrono::Duration::seconds(45)), "45s");
+        assert_eq!(format_age(chrono::Duration::minutes(90)), "1h");
+        assert_eq!(format_age(chrono::Duration::days(3)), "3d");
+        assert_eq!(format_age(chrono::Duration::seconds(-5)), "0s");
+    }
+
+    #[test]
+    fn test_recent_deployments_table_only_links_healthy_deployments() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let deployments = vec![
+            deployment(
+                "20260101-1130",
+                DeploymentStatus::Healthy,
+                "2026-01-01T11:30:00Z",
+            ),
+            deployment(
+                "20260101-0900",
+                DeploymentStatus::Failed,
+                "2026-01-01T09:00:00Z",
+            ),
+            deployment(
+                "20251231-1200",
+                DeploymentStatus::Superseded,
+                "not a timestamp",
+            ),
+        ];
+
+        let table = recent_deployments_table(&deployments, now);
+        let rows: Vec<Vec<String>> = table
+            .row_iter()
+            .map(|row| row.cell_iter().map(|cell| cell.content()).collect())
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    "default",
+                    "20260101-1130",
+                    "Healthy",
+                    "https://demo.example.com",
+                    "30m"
+                ],
+                vec!["default", "20260101-0900", "Failed", "-", "3h"],
+                vec!["default", "20251231-1200", "Superseded", "-", "-"],
+            ]
+        );
+    }
+}
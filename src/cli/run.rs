@@ -1,14 +1,25 @@
 // Local development runner - builds and runs container images locally
 
 use anyhow::{bail, Context, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{EventKind, RecursiveMode, Watcher};
 use reqwest::Client;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::time::Duration;
+use tokio::sync::mpsc;
 use tracing::{info, warn};
 
 use crate::build::{self, BuildOptions};
 use crate::cli::env;
 use crate::config::Config;
 
+/// How long `--watch` waits for file changes to settle before rebuilding
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Directories whose changes never trigger a `--watch` rebuild
+const WATCH_IGNORED_DIRS: &[&str] = &[".git", "target", "node_modules"];
+
 /// Options for running a container locally
 pub struct RunOptions<'a> {
     pub project_name: Option<&'a str>,
@@ -19,6 +30,8 @@ pub struct RunOptions<'a> {
     pub expose: u16,
     pub run_env: &'a [(String, String)],
     pub build_args: &'a build::BuildArgs,
+    /// Rebuild and restart the container when files in `path` change
+    pub watch: bool,
 }
 
 /// Build and run a container image locally for development
@@ -49,8 +62,12 @@ pub async fn run_locally(
         None,
     )
     .with_push(false); // Never push local dev images
+    let build_options = match options.project_name {
+        Some(project) => build_options.with_cache_project(project),
+        None => build_options,
+    };
 
-    build::build_image(build_options)?;
+    build::build_image(build_options.clone())?;
 
     // Resolve container CLI
     let container_cli = options
@@ -61,17 +78,15 @@ pub async fn run_locally(
 
     info!("Starting container with {}...", container_cli);
 
-    // Prepare docker run command
-    let mut cmd = Command::new(container_cli);
-    cmd.arg("run")
-        .arg("--rm") // Remove container when it exits
-        .arg("-it") // Interactive with TTY
-        .arg("-p")
-        .arg(format!("{}:{}", options.expose, options.http_port)); // Port mapping
+    // Prepare docker run arguments (shared by every container started in --watch mode)
+    let mut run_args = vec![
+        "-p".to_string(),
+        format!("{}:{}", options.expose, options.http_port), // Port mapping
+    ];
 
     // PORT is set below after loading project env vars (CLI flag takes precedence)
 
-    cmd.arg("--add-host=host.docker.internal:host-gateway");
+    run_args.push("--add-host=host.docker.internal:host-gateway".to_string());
 
     // Always try to resolve project name from rise.toml or explicit argument
     let project_name = if let Some(name) = options.project_name {
@@ -126,7 +141,7 @@ pub async fn run_locally(
                                     port_from_preview = true;
                                     continue;
                                 }
-                                cmd.arg("-e").arg(format!("{}={}", key, value));
+                                run_args.extend(["-e".to_string(), format!("{}={}", key, value)]);
                             }
                         }
 
@@ -160,7 +175,7 @@ pub async fn run_locally(
     }
 
     // Set PORT — CLI flag always takes precedence over preview value
-    cmd.arg("-e").arg(format!("PORT={}", options.http_port));
+    run_args.extend(["-e".to_string(), format!("PORT={}", options.http_port)]);
     let _ = port_from_preview; // suppress unused warning when env loading is skipped
 
     // Add user-specified runtime environment variables (these take precedence)
@@ -171,18 +186,10 @@ pub async fn run_locally(
             if options.run_env.len() == 1 { "" } else { "s" }
         );
         for (key, value) in options.run_env {
-            cmd.arg("-e").arg(format!("{}={}", key, value));
+            run_args.extend(["-e".to_string(), format!("{}={}", key, value)]);
         }
     }
 
-    // Add the image tag
-    cmd.arg(&image_tag);
-
-    // Set up stdio to inherit from parent (allows interactive usage)
-    cmd.stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit());
-
     info!(
         "Running container: {} (port {}:{}, PORT={})",
         image_tag, options.expose, options.http_port, options.http_port
@@ -194,10 +201,33 @@ pub async fn run_locally(
         "Application will be available at http://localhost:{}",
         options.expose
     );
+
+    if options.watch {
+        return watch_and_rerun(
+            container_cli,
+            &run_args,
+            &image_tag,
+            build_options,
+            options.path,
+        )
+        .await;
+    }
+
     info!("Press Ctrl+C to stop the container");
 
     // Execute the command and wait for completion
-    let status = cmd.status().context("Failed to run container")?;
+    let status = Command::new(container_cli)
+        .arg("run")
+        .arg("--rm") // Remove container when it exits
+        .arg("-it") // Interactive with TTY
+        .args(&run_args)
+        .arg(&image_tag)
+        // Inherit stdio from parent (allows interactive usage)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .context("Failed to run container")?;
 
     if !status.success() {
         if let Some(code) = status.code() {
@@ -209,3 +239,243 @@ pub async fn run_locally(
 
     Ok(())
 }
+
+/// A container started by `--watch`, stopped by name before it is replaced
+struct WatchContainer {
+    name: String,
+    child: tokio::process::Child,
+}
+
+/// Rebuild the image and restart the container whenever files in the app directory change
+///
+/// The previous container keeps running until the new image has been built, so a failed
+/// build leaves the last working version reachable.
+async fn watch_and_rerun(
+    container_cli: &str,
+    run_args: &[String],
+    image_tag: &str,
+    build_options: BuildOptions,
+    path: &str,
+) -> Result<()> {
+    let root = Path::new(path)
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve path '{}'", path))?;
+    let ignored = watch_ignore_matcher(&root);
+
+    let (tx, mut changes) = mpsc::unbounded_channel::<PathBuf>();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        match event {
+            // Builds read the whole app directory; only modifications matter
+            Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("File watcher error: {}", e),
+        }
+    })
+    .context("Failed to create file watcher")?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch '{}'", root.display()))?;
+
+    let mut generation = 0;
+    let mut container = Some(start_watch_container(
+        container_cli,
+        run_args,
+        image_tag,
+        generation,
+    )?);
+    info!("Watching '{}' for changes (Ctrl+C to stop)", root.display());
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            status = async {
+                match container.as_mut() {
+                    Some(running) => running.child.wait().await,
+                    None => std::future::pending().await,
+                }
+            } => {
+                container = None;
+                match status {
+                    Ok(status) => warn!("Container exited ({}), waiting for changes to rebuild", status),
+                    Err(e) => warn!("Failed to wait for container: {}", e),
+                }
+            }
+            Some(path) = changes.recv() => {
+                if is_watch_ignored(&ignored, &root, &path) {
+                    continue;
+                }
+
+                // Debounce: collect changes until the directory has been quiet for a moment
+                let mut changed = vec![path];
+                while let Ok(Some(path)) = tokio::time::timeout(WATCH_DEBOUNCE, changes.recv()).await {
+                    if !is_watch_ignored(&ignored, &root, &path) && !changed.contains(&path) {
+                        changed.push(path);
+                    }
+                }
+
+                let first = changed[0].strip_prefix(&root).unwrap_or(&changed[0]);
+                let more = match changed.len() {
+                    1 => String::new(),
+                    n => format!(" and {} more", n - 1),
+                };
+                println!();
+                println!("━━━ {} changed{}, rebuilding {} ━━━", first.display(), more, image_tag);
+
+                let build = tokio::task::spawn_blocking({
+                    let build_options = build_options.clone();
+                    move || build::build_image(build_options)
+                });
+                let result = tokio::select! {
+                    _ = &mut ctrl_c => break,
+                    result = build => result.context("Build task panicked")?,
+                };
+
+                match result {
+                    Ok(()) => {
+                        stop_watch_container(container_cli, container.take()).await;
+                        generation += 1;
+                        container = Some(start_watch_container(
+                            container_cli,
+                            run_args,
+                            image_tag,
+                            generation,
+                        )?);
+                        println!("✓ Rebuilt and restarted {}", image_tag);
+                    }
+                    Err(e) => {
+                        warn!("Build failed, keeping the previous container running: {:#}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    stop_watch_container(container_cli, container.take()).await;
+    Ok(())
+}
+
+/// Start a detached-from-TTY container for `--watch`, named so it can be stopped later
+fn start_watch_container(
+    container_cli: &str,
+    run_args: &[String],
+    image_tag: &str,
+    generation: u32,
+) -> Result<WatchContainer> {
+    let name = format!("{}-{}-{}", image_tag, std::process::id(), generation);
+    let child = tokio::process::Command::new(container_cli)
+        .arg("run")
+        .arg("--rm")
+        .arg("--name")
+        .arg(&name)
+        .args(run_args)
+        .arg(image_tag)
+        .stdin(Stdio::null())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to run container")?;
+
+    Ok(WatchContainer { name, child })
+}
+
+/// Stop a `--watch` container and wait for its `run` process to exit
+async fn stop_watch_container(container_cli: &str, container: Option<WatchContainer>) {
+    let Some(mut container) = container else {
+        return;
+    };
+
+    let stopped = tokio::process::Command::new(container_cli)
+        .arg("stop")
+        .arg(&container.name)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await;
+    if !matches!(stopped, Ok(status) if status.success()) {
+        // Already gone (e.g. stopped by Ctrl+C), or the CLI failed; make sure `run` exits
+        let _ = container.child.kill().await;
+    }
+    let _ = container.child.wait().await;
+}
+
+/// Matcher for paths whose changes `--watch` ignores
+///
+/// Covers [`WATCH_IGNORED_DIRS`] plus the patterns in the app's `.dockerignore`.
+/// `.dockerignore` patterns are relative to the build context root, so they are
+/// anchored to `root`.
+fn watch_ignore_matcher(root: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    for dir in WATCH_IGNORED_DIRS {
+        let _ = builder.add_line(None, &format!("{}/", dir));
+    }
+
+    if let Ok(contents) = std::fs::read_to_string(root.join(".dockerignore")) {
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negation, pattern) = match line.strip_prefix('!') {
+                Some(pattern) => ("!", pattern),
+                None => ("", line),
+            };
+            let pattern = pattern.trim_start_matches("./").trim_start_matches('/');
+            if let Err(e) = builder.add_line(None, &format!("{}/{}", negation, pattern)) {
+                warn!("Ignoring invalid .dockerignore pattern '{}': {}", line, e);
+            }
+        }
+    }
+
+    builder.build().unwrap_or_else(|e| {
+        warn!("Failed to load .dockerignore patterns: {}", e);
+        Gitignore::empty()
+    })
+}
+
+/// Whether a change to `path` should be ignored by `--watch`
+fn is_watch_ignored(matcher: &Gitignore, root: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return true;
+    };
+    matcher
+        .matched_path_or_any_parents(relative, path.is_dir())
+        .is_ignore()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_ignore_matcher() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(
+            root.join(".dockerignore"),
+            "# build output\ndist\n*.log\n!keep.log\n./tmp\n",
+        )
+        .unwrap();
+        let matcher = watch_ignore_matcher(&root);
+        let ignored = |path: &str| is_watch_ignored(&matcher, &root, &root.join(path));
+
+        assert!(ignored(".git/index"));
+        assert!(ignored("target/debug/app"));
+        assert!(ignored("web/node_modules/react/index.js"));
+        assert!(ignored("dist/bundle.js"));
+        assert!(ignored("server.log"));
+        assert!(ignored("tmp/cache"));
+        assert!(!ignored("keep.log"));
+        // .dockerignore patterns only match from the build context root
+        assert!(!ignored("logs/server.log"));
+        assert!(!ignored("src/main.rs"));
+        assert!(!ignored(".dockerignore"));
+        assert!(ignored("/elsewhere/main.rs"));
+    }
+}
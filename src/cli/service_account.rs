@@ -268,3 +268,143 @@ pub async fn delete_service_account(
 
     Ok(())
 }
+
+#[derive(Debug, Serialize)]
+struct TestTokenRequest {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServiceAccountMatch {
+    id: String,
+    email: String,
+    matched: bool,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TestTokenResponse {
+    issuer: String,
+    claims: serde_json::Map<String, serde_json::Value>,
+    token_error: Option<String>,
+    service_accounts: Vec<ServiceAccountMatch>,
+}
+
+/// Check which service account an OIDC token would authenticate as
+pub async fn test_token(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    project_name: &str,
+    oidc_token: Option<String>,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    // Read from stdin if no token provided
+    let oidc_token = match oidc_token {
+        Some(t) => t,
+        None => {
+            use std::io::{IsTerminal, Read};
+
+            if std::io::stdin().is_terminal() {
+                eprintln!("Paste the OIDC token to test (press Ctrl+D when done):");
+            }
+
+            let mut buffer = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buffer)
+                .context("Failed to read token from stdin")?;
+            buffer
+        }
+    };
+    let oidc_token = oidc_token.trim().to_string();
+    if oidc_token.is_empty() {
+        anyhow::bail!("Token cannot be empty");
+    }
+
+    let url = format!(
+        "{}/api/v1/projects/{}/workload-identities/test",
+        backend_url, project_name
+    );
+
+    let response = http_client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .json(&TestTokenRequest { token: oidc_token })
+        .send()
+        .await
+        .context("Failed to send test token request")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!("Failed to test token (status {}): {}", status, error_text);
+    }
+
+    let result: TestTokenResponse = response
+        .json()
+        .await
+        .context("Failed to parse test token response")?;
+
+    println!("Issuer: {}", result.issuer);
+    println!("Claims:");
+    for (key, value) in &result.claims {
+        match value.as_str() {
+            Some(s) => println!("  {}: {}", key, s),
+            None => println!("  {}: {}", key, value),
+        }
+    }
+
+    if result.service_accounts.is_empty() {
+        println!(
+            "\nNo service accounts of project '{}' use issuer '{}'.",
+            project_name, result.issuer
+        );
+        return Ok(());
+    }
+
+    match result.token_error {
+        Some(ref e) => println!("\n✗ Token verification failed: {}", e),
+        None => println!("\n✓ Token signature and expiry are valid"),
+    }
+
+    println!("\nService accounts:");
+    for sa in &result.service_accounts {
+        if sa.matched {
+            println!("  ✓ {} ({})", sa.email, sa.id);
+        } else {
+            println!(
+                "  ✗ {} ({}): {}",
+                sa.email,
+                sa.id,
+                sa.reason.as_deref().unwrap_or("no match")
+            );
+        }
+    }
+
+    let matched: Vec<&ServiceAccountMatch> = result
+        .service_accounts
+        .iter()
+        .filter(|sa| sa.matched)
+        .collect();
+    println!();
+    match (matched.as_slice(), &result.token_error) {
+        ([], _) => println!("The token's claims match no service account."),
+        ([sa], None) => println!("The token authenticates as {}.", sa.email),
+        ([sa], Some(_)) => println!(
+            "The token's claims match {}, but the token itself is not valid.",
+            sa.email
+        ),
+        (_, _) => println!(
+            "The token's claims match {} service accounts; authentication is rejected as ambiguous.",
+            matched.len()
+        ),
+    }
+
+    Ok(())
+}
@@ -120,6 +120,13 @@ struct TeamErrorResponse {
     suggestions: Option<Vec<String>>,
 }
 
+#[derive(Debug, Deserialize)]
+struct TeamProject {
+    name: String,
+    status: String,
+    access_class: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct CreateTeamResponse {
     team: Team,
@@ -585,3 +592,88 @@ pub async fn delete_team(
 
     Ok(())
 }
+
+// List projects owned by a team
+pub async fn list_team_projects(
+    http_client: &Client,
+    backend_url: &str,
+    config: &Config,
+    team_identifier: &str,
+) -> Result<()> {
+    let token = config
+        .get_token()
+        .ok_or_else(|| anyhow::anyhow!("Not logged in. Please run 'rise login' first."))?;
+
+    let url = format!("{}/api/v1/teams/{}/projects", backend_url, team_identifier);
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .context("Failed to send list team projects request")?;
+
+    if response.status().is_success() {
+        let projects: Vec<TeamProject> = response
+            .json()
+            .await
+            .context("Failed to parse list team projects response")?;
+
+        if projects.is_empty() {
+            println!("Team '{}' does not own any projects.", team_identifier);
+        } else {
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .apply_modifier(UTF8_ROUND_CORNERS)
+                .set_header(vec![
+                    Cell::new("NAME").add_attribute(Attribute::Bold),
+                    Cell::new("VISIBILITY").add_attribute(Attribute::Bold),
+                    Cell::new("STATUS").add_attribute(Attribute::Bold),
+                ]);
+
+            for project in projects {
+                table.add_row(vec![
+                    Cell::new(&project.name),
+                    Cell::new(&project.access_class),
+                    Cell::new(&project.status),
+                ]);
+            }
+
+            println!("{}", table);
+        }
+    } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+        let error: TeamErrorResponse = response
+            .json()
+            .await
+            .context("Failed to parse error response")?;
+
+        eprintln!("{}", error.error);
+        if let Some(suggestions) = error.suggestions {
+            eprintln!("\nDid you mean one of these?");
+            for suggestion in suggestions {
+                eprintln!("  - {}", suggestion);
+            }
+        }
+        std::process::exit(1);
+    } else if response.status() == reqwest::StatusCode::FORBIDDEN {
+        let error: TeamErrorResponse = response
+            .json()
+            .await
+            .context("Failed to parse error response")?;
+        eprintln!("Error: {}", error.error);
+        std::process::exit(1);
+    } else {
+        let status = response.status();
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        anyhow::bail!(
+            "Failed to list team projects (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::api::project::MeResponse;
+use crate::config::Config;
+use crate::login::token_utils::{decode_token_identity, format_token_expiration};
+use anyhow::{bail, Context, Result};
+use reqwest::{Client, StatusCode};
+
+/// Show the identity of the stored token and confirm it with the backend
+pub async fn whoami(http_client: &Client, backend_url: &str, config: &Config) -> Result<()> {
+    let Some(token) = config.get_token() else {
+        bail!(
+            "Not logged in to {}. Please run 'rise login' to authenticate",
+            backend_url
+        );
+    };
+
+    let identity = decode_token_identity(&token)
+        .context("Stored token is not a valid JWT. Please run 'rise login' again")?;
+
+    if identity.is_expired() {
+        let expired_at = identity
+            .expires_at()
+            .map(|exp| exp.format("%B %d, %Y at %H:%M UTC").to_string())
+            .unwrap_or_default();
+        let account = identity
+            .email
+            .as_deref()
+            .map(|email| format!(" ({})", email))
+            .unwrap_or_default();
+        bail!(
+            "Token for {}{} expired on {}. Please run 'rise login' to re-authenticate",
+            backend_url,
+            account,
+            expired_at
+        );
+    }
+
+    let url = format!("{}/api/v1/users/me", backend_url);
+    let response = http_client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await
+        .context("Failed to get current user")?;
+
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        bail!(
+            "Token was rejected by {}. Please run 'rise login' to re-authenticate",
+            backend_url
+        );
+    }
+    if !status.is_success() {
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        bail!(
+            "Failed to get current user (status {}): {}",
+            status,
+            error_text
+        );
+    }
+
+    let me: MeResponse = response
+        .json()
+        .await
+        .context("Failed to parse me response")?;
+
+    let token_source = if std::env::var("RISE_TOKEN").is_ok() {
+        "RISE_TOKEN environment variable"
+    } else {
+        "~/.config/rise/config.json"
+    };
+    let expires = format_token_expiration(&token).unwrap_or_else(|_| "never".to_string());
+
+    println!("Logged in to {}", backend_url);
+    println!("  Email:      {}", me.email);
+    println!("  User ID:    {}", me.id);
+    println!("  Subject:    {}", identity.sub.as_deref().unwrap_or("-"));
+    println!("  Issuer:     {}", identity.iss.as_deref().unwrap_or("-"));
+    println!("  Expires:    {}", expires);
+    println!("  Admin:      {}", if me.is_admin { "yes" } else { "no" });
+    println!("  Token from: {}", token_source);
+
+    Ok(())
+}
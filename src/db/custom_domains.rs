@@ -13,7 +13,7 @@ pub async fn list_project_custom_domains(
     let domains = sqlx::query_as!(
         CustomDomain,
         r#"
-        SELECT id, project_id, domain, is_primary, created_at, updated_at
+        SELECT id, project_id, domain, is_primary, verified, created_at, updated_at
         FROM project_custom_domains
         WHERE project_id = $1
         ORDER BY domain ASC
@@ -36,7 +36,7 @@ pub async fn get_custom_domain(
     let domain = sqlx::query_as!(
         CustomDomain,
         r#"
-        SELECT id, project_id, domain, is_primary, created_at, updated_at
+        SELECT id, project_id, domain, is_primary, verified, created_at, updated_at
         FROM project_custom_domains
         WHERE project_id = $1 AND domain = $2
         "#,
@@ -61,7 +61,7 @@ pub async fn add_custom_domain(
         r#"
         INSERT INTO project_custom_domains (project_id, domain)
         VALUES ($1, $2)
-        RETURNING id, project_id, domain, is_primary, created_at, updated_at
+        RETURNING id, project_id, domain, is_primary, verified, created_at, updated_at
         "#,
         project_id,
         domain
@@ -73,6 +73,24 @@ pub async fn add_custom_domain(
     Ok(domain)
 }
 
+/// Record the outcome of a DNS verification check
+pub async fn set_verified(pool: &PgPool, id: Uuid, verified: bool) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE project_custom_domains
+        SET verified = $2
+        WHERE id = $1
+        "#,
+        id,
+        verified
+    )
+    .execute(pool)
+    .await
+    .context("Failed to update custom domain verification")?;
+
+    Ok(())
+}
+
 /// Delete a custom domain from a project
 pub async fn delete_custom_domain(pool: &PgPool, project_id: Uuid, domain: &str) -> Result<bool> {
     let result = sqlx::query!(
@@ -100,7 +118,7 @@ pub async fn get_custom_domains_batch(
     let domains = sqlx::query_as!(
         CustomDomain,
         r#"
-        SELECT id, project_id, domain, is_primary, created_at, updated_at
+        SELECT id, project_id, domain, is_primary, verified, created_at, updated_at
         FROM project_custom_domains
         WHERE project_id = ANY($1)
         ORDER BY project_id, domain ASC
@@ -148,7 +166,7 @@ pub async fn set_primary_domain(
         UPDATE project_custom_domains
         SET is_primary = true
         WHERE project_id = $1 AND domain = $2
-        RETURNING id, project_id, domain, is_primary, created_at, updated_at
+        RETURNING id, project_id, domain, is_primary, verified, created_at, updated_at
         "#,
         project_id,
         domain
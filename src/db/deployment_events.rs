@@ -0,0 +1,79 @@
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::{Deployment, DeploymentAuditEvent};
+
+/// Event type of the first event of every deployment
+pub const EVENT_CREATED: &str = "created";
+/// Event type of every status transition after creation
+pub const EVENT_STATUS_CHANGED: &str = "status_changed";
+
+/// Record a lifecycle event for `deployment` with its current status.
+///
+/// `actor_id` is the user or service account that triggered the event (None for
+/// transitions made by the controller). Failures are logged and never fail the
+/// transition that was already committed.
+pub async fn record(
+    pool: &PgPool,
+    deployment: &Deployment,
+    event_type: &str,
+    actor_id: Option<Uuid>,
+    message: Option<&str>,
+) {
+    let status = deployment.status.to_string();
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO deployment_events (deployment_id, event_type, status, actor_id, message)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        deployment.id,
+        event_type,
+        status,
+        actor_id,
+        message
+    )
+    .execute(pool)
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!(
+            deployment_id = %deployment.deployment_id,
+            "Failed to record '{}' event ({}): {:?}",
+            event_type,
+            status,
+            e
+        );
+    }
+}
+
+/// List the lifecycle events of a deployment, oldest first
+pub async fn list_for_deployment(
+    pool: &PgPool,
+    deployment_id: Uuid,
+) -> Result<Vec<DeploymentAuditEvent>> {
+    let events = sqlx::query_as!(
+        DeploymentAuditEvent,
+        r#"
+        SELECT
+            e.event_type,
+            e.status,
+            e.message,
+            u.email as "actor_email?",
+            EXISTS (
+                SELECT 1 FROM service_accounts sa WHERE sa.user_id = e.actor_id
+            ) as "actor_is_service_account!",
+            e.created_at
+        FROM deployment_events e
+        LEFT JOIN users u ON u.id = e.actor_id
+        WHERE e.deployment_id = $1
+        ORDER BY e.id
+        "#,
+        deployment_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to list deployment events")?;
+
+    Ok(events)
+}
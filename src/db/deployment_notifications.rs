@@ -0,0 +1,64 @@
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+use super::models::DeploymentNotification;
+
+/// List pending notifications that are due for delivery, oldest first.
+///
+/// Notifications are enqueued by a database trigger whenever a deployment of a
+/// project with a webhook becomes Healthy, Unhealthy or Failed.
+pub async fn list_due(pool: &PgPool, limit: i64) -> Result<Vec<DeploymentNotification>> {
+    let notifications = sqlx::query_as!(
+        DeploymentNotification,
+        r#"
+        SELECT id, deployment_id, status, attempts, created_at
+        FROM deployment_notifications
+        WHERE next_attempt_at <= NOW()
+        ORDER BY id
+        LIMIT $1
+        "#,
+        limit
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to list due deployment notifications")?;
+
+    Ok(notifications)
+}
+
+/// Remove a notification after it was delivered or given up on
+pub async fn delete(pool: &PgPool, id: i64) -> Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM deployment_notifications
+        WHERE id = $1
+        "#,
+        id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete deployment notification")?;
+
+    Ok(())
+}
+
+/// Record a failed delivery attempt and schedule the next one `retry_in_secs` from now
+pub async fn reschedule(pool: &PgPool, id: i64, retry_in_secs: f64, error: &str) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE deployment_notifications
+        SET attempts = attempts + 1,
+            next_attempt_at = NOW() + ($2 * INTERVAL '1 second'),
+            last_error = $3
+        WHERE id = $1
+        "#,
+        id,
+        retry_in_secs,
+        error
+    )
+    .execute(pool)
+    .await
+    .context("Failed to reschedule deployment notification")?;
+
+    Ok(())
+}
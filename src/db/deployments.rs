@@ -3,6 +3,7 @@ use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::db::deployment_events;
 use crate::db::models::{Deployment, DeploymentStatus, TerminationReason};
 use crate::server::deployment::state_machine;
 
@@ -24,12 +25,50 @@ pub struct CreateDeploymentParams<'a> {
     pub job_url: Option<&'a str>,
     /// URL to the pull request/merge request associated with this deployment
     pub pull_request_url: Option<&'a str>,
+    /// Optional human-readable reason for the deployment
+    pub message: Option<&'a str>,
     /// Number of replicas
     pub replicas: i32,
     /// CPU allocation (e.g., "500m", "1")
     pub cpu: &'a str,
     /// Memory allocation (e.g., "256Mi", "1Gi")
     pub memory: &'a str,
+    /// CPU limit (None = same as `cpu`)
+    pub cpu_limit: Option<&'a str>,
+    /// Memory limit (None = same as `memory`)
+    pub memory_limit: Option<&'a str>,
+    /// Health probe overrides (serialized `HealthCheckConfig`)
+    pub health_check: Option<&'a serde_json::Value>,
+    /// Init container run before the app starts (serialized `InitContainerConfig`)
+    pub init_container: Option<&'a serde_json::Value>,
+    /// Protocol the app speaks (`http` or `tcp`)
+    pub protocol: &'a str,
+    /// Custom labels for the app's workload (JSON object of strings)
+    pub labels: Option<&'a serde_json::Value>,
+    /// Custom annotations for the app's workload (JSON object of strings)
+    pub annotations: Option<&'a serde_json::Value>,
+    /// Override for the app container's command (image entrypoint)
+    pub command: Option<&'a [String]>,
+    /// Override for the app container's args (image cmd)
+    pub args: Option<&'a [String]>,
+    /// Extra annotations for the app's ingresses (JSON object of strings)
+    pub ingress_annotations: Option<&'a serde_json::Value>,
+    /// How the deployment replaces the active one (`rolling` or `blue-green`)
+    pub strategy: &'a str,
+    /// Blue-green drain period in seconds (None = controller default)
+    pub drain_period_seconds: Option<i32>,
+    /// Persistent volume of the deployment group (serialized `VolumeConfig`)
+    pub volume: Option<&'a serde_json::Value>,
+    /// Replica spreading across nodes or zones (serialized `SchedulingConfig`)
+    pub scheduling: Option<&'a serde_json::Value>,
+    /// Image pull policy of the app's containers (`None` = derived from the image)
+    pub image_pull_policy: Option<&'a str>,
+    /// Ingress rate limits (serialized `RateLimitConfig`)
+    pub rate_limit: Option<&'a serde_json::Value>,
+    /// Additional named container ports (serialized `PortConfig` map)
+    pub extra_ports: Option<&'a serde_json::Value>,
+    /// Kubernetes ServiceAccount the pods run as
+    pub service_account_name: Option<&'a str>,
 }
 
 /// List deployments for a project
@@ -46,8 +85,8 @@ pub async fn list_for_project(pool: &PgPool, project_id: Uuid) -> Result<Vec<Dep
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -85,8 +124,8 @@ pub async fn list_non_terminal_for_project(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -121,8 +160,8 @@ pub async fn get_deployments_batch(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -156,8 +195,8 @@ pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Deployment>> {
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -189,8 +228,8 @@ pub async fn find_by_deployment_id(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -227,8 +266,8 @@ pub async fn find_by_deployment_id_unscoped(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -248,13 +287,75 @@ pub async fn find_by_deployment_id_unscoped(
 
 /// Create a new deployment
 pub async fn create(pool: &PgPool, params: CreateDeploymentParams<'_>) -> Result<Deployment> {
+    let deployment = insert(pool, &params).await?;
+
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_CREATED,
+        Some(params.created_by_id),
+        params.message,
+    )
+    .await;
+
+    Ok(deployment)
+}
+
+/// Create a new deployment unless its project already has `quota` active deployments
+/// outside the deployment's group
+///
+/// The project row is locked while counting and inserting, so concurrent creations for
+/// the same project are serialized and can't exceed the quota together. Returns `None`
+/// if the quota is reached.
+pub async fn create_within_quota(
+    pool: &PgPool,
+    params: CreateDeploymentParams<'_>,
+    quota: i64,
+) -> Result<Option<Deployment>> {
+    let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+    sqlx::query!(
+        r#"
+        SELECT id FROM projects WHERE id = $1 FOR UPDATE
+        "#,
+        params.project_id
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .context("Failed to lock project")?;
+
+    let active =
+        count_active_outside_group(&mut *tx, params.project_id, params.deployment_group).await?;
+    if active >= quota {
+        return Ok(None);
+    }
+
+    let deployment = insert(&mut *tx, &params).await?;
+    tx.commit().await.context("Failed to commit transaction")?;
+
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_CREATED,
+        Some(params.created_by_id),
+        params.message,
+    )
+    .await;
+
+    Ok(Some(deployment))
+}
+
+async fn insert<'a, E>(executor: E, params: &CreateDeploymentParams<'_>) -> Result<Deployment>
+where
+    E: sqlx::Executor<'a, Database = sqlx::Postgres>,
+{
     let status_str = params.status.to_string();
 
     let deployment = sqlx::query_as!(
         Deployment,
         r#"
-        INSERT INTO deployments (deployment_id, project_id, created_by_id, status, image, image_digest, rolled_back_from_deployment_id, deployment_group, environment_id, expires_at, http_port, is_active, job_url, pull_request_url, replicas, cpu, memory)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+        INSERT INTO deployments (deployment_id, project_id, created_by_id, status, image, image_digest, rolled_back_from_deployment_id, deployment_group, environment_id, expires_at, http_port, is_active, job_url, pull_request_url, message, replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34, $35, $36)
         RETURNING
             id, deployment_id, project_id, created_by_id,
             status as "status: DeploymentStatus",
@@ -265,8 +366,8 @@ pub async fn create(pool: &PgPool, params: CreateDeploymentParams<'_>) -> Result
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         params.deployment_id,
@@ -283,11 +384,30 @@ pub async fn create(pool: &PgPool, params: CreateDeploymentParams<'_>) -> Result
         params.is_active,
         params.job_url,
         params.pull_request_url,
+        params.message,
         params.replicas,
         params.cpu,
-        params.memory
+        params.memory,
+        params.cpu_limit,
+        params.memory_limit,
+        params.health_check,
+        params.init_container,
+        params.protocol,
+        params.labels,
+        params.annotations,
+        params.command,
+        params.args,
+        params.ingress_annotations,
+        params.strategy,
+        params.drain_period_seconds,
+        params.volume,
+        params.scheduling,
+        params.image_pull_policy,
+        params.rate_limit,
+        params.extra_ports,
+        params.service_account_name
     )
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await
     .context("Failed to create deployment")?;
 
@@ -316,8 +436,8 @@ pub async fn update_status(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -360,8 +480,8 @@ pub async fn update_status(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         "#,
@@ -373,7 +493,19 @@ pub async fn update_status(
     .context("Failed to execute deployment status update")?;
 
     match deployment {
-        Some(d) => Ok(d),
+        Some(d) => {
+            if d.status != current.status {
+                deployment_events::record(
+                    pool,
+                    &d,
+                    deployment_events::EVENT_STATUS_CHANGED,
+                    None,
+                    None,
+                )
+                .await;
+            }
+            Ok(d)
+        }
         None => {
             tracing::warn!(
                 "UPDATE returned 0 rows for deployment {} (transition {} -> {}), but validation passed",
@@ -403,8 +535,8 @@ pub async fn mark_failed(pool: &PgPool, id: Uuid, error_message: &str) -> Result
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         "#,
@@ -415,6 +547,15 @@ pub async fn mark_failed(pool: &PgPool, id: Uuid, error_message: &str) -> Result
     .await
     .context("Failed to mark deployment as failed")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        None,
+        deployment.error_message.as_deref(),
+    )
+    .await;
+
     Ok(deployment)
 }
 
@@ -441,8 +582,8 @@ pub async fn update_controller_metadata(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         "#,
@@ -489,8 +630,8 @@ pub async fn mark_cancelled(pool: &PgPool, id: Uuid) -> Result<Deployment> {
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id
@@ -499,6 +640,15 @@ pub async fn mark_cancelled(pool: &PgPool, id: Uuid) -> Result<Deployment> {
     .await
     .context("Failed to mark deployment as cancelled")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        None,
+        None,
+    )
+    .await;
+
     Ok(deployment)
 }
 
@@ -525,8 +675,8 @@ pub async fn mark_stopped(pool: &PgPool, id: Uuid) -> Result<Deployment> {
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id
@@ -535,6 +685,15 @@ pub async fn mark_stopped(pool: &PgPool, id: Uuid) -> Result<Deployment> {
     .await
     .context("Failed to mark deployment as stopped")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        None,
+        None,
+    )
+    .await;
+
     Ok(deployment)
 }
 
@@ -561,8 +720,8 @@ pub async fn mark_superseded(pool: &PgPool, id: Uuid) -> Result<Deployment> {
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id
@@ -571,6 +730,15 @@ pub async fn mark_superseded(pool: &PgPool, id: Uuid) -> Result<Deployment> {
     .await
     .context("Failed to mark deployment as superseded")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        None,
+        None,
+    )
+    .await;
+
     Ok(deployment)
 }
 
@@ -597,8 +765,8 @@ pub async fn mark_expired(pool: &PgPool, id: Uuid) -> Result<Deployment> {
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id
@@ -607,6 +775,15 @@ pub async fn mark_expired(pool: &PgPool, id: Uuid) -> Result<Deployment> {
     .await
     .context("Failed to mark deployment as expired")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        None,
+        None,
+    )
+    .await;
+
     Ok(deployment)
 }
 
@@ -633,8 +810,8 @@ pub async fn mark_healthy(pool: &PgPool, id: Uuid) -> Result<Deployment> {
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id
@@ -643,6 +820,15 @@ pub async fn mark_healthy(pool: &PgPool, id: Uuid) -> Result<Deployment> {
     .await
     .context("Failed to mark deployment as healthy")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        None,
+        None,
+    )
+    .await;
+
     Ok(deployment)
 }
 
@@ -668,8 +854,8 @@ pub async fn mark_unhealthy(pool: &PgPool, id: Uuid, reason: String) -> Result<D
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id,
@@ -679,6 +865,15 @@ pub async fn mark_unhealthy(pool: &PgPool, id: Uuid, reason: String) -> Result<D
     .await
     .context("Failed to mark deployment as unhealthy")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        None,
+        deployment.error_message.as_deref(),
+    )
+    .await;
+
     Ok(deployment)
 }
 
@@ -687,7 +882,9 @@ pub async fn mark_terminating(
     pool: &PgPool,
     id: Uuid,
     reason: TerminationReason,
+    actor_id: Option<Uuid>,
 ) -> Result<Deployment> {
+    let reason_str = reason.to_string();
     let deployment = sqlx::query_as!(
         Deployment,
         r#"
@@ -707,8 +904,8 @@ pub async fn mark_terminating(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id,
@@ -718,11 +915,24 @@ pub async fn mark_terminating(
     .await
     .context("Failed to mark deployment as terminating")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        actor_id,
+        Some(&reason_str),
+    )
+    .await;
+
     Ok(deployment)
 }
 
 /// Mark deployment as cancelling
-pub async fn mark_cancelling(pool: &PgPool, id: Uuid) -> Result<Deployment> {
+pub async fn mark_cancelling(
+    pool: &PgPool,
+    id: Uuid,
+    actor_id: Option<Uuid>,
+) -> Result<Deployment> {
     let deployment = sqlx::query_as!(
         Deployment,
         r#"
@@ -742,8 +952,8 @@ pub async fn mark_cancelling(pool: &PgPool, id: Uuid) -> Result<Deployment> {
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         "#,
         id
@@ -752,6 +962,114 @@ pub async fn mark_cancelling(pool: &PgPool, id: Uuid) -> Result<Deployment> {
     .await
     .context("Failed to mark deployment as cancelling")?;
 
+    deployment_events::record(
+        pool,
+        &deployment,
+        deployment_events::EVENT_STATUS_CHANGED,
+        actor_id,
+        None,
+    )
+    .await;
+
+    Ok(deployment)
+}
+
+/// Set the replica count of a deployment and flag it for reconciliation
+pub async fn update_replicas(pool: &PgPool, id: Uuid, replicas: i32) -> Result<Deployment> {
+    let deployment = sqlx::query_as!(
+        Deployment,
+        r#"
+        UPDATE deployments
+        SET replicas = $2, needs_reconcile = TRUE, updated_at = NOW()
+        WHERE id = $1
+        RETURNING
+            id, deployment_id, project_id, created_by_id,
+            status as "status: DeploymentStatus",
+            deployment_group, environment_id, expires_at,
+            completed_at, error_message, build_logs,
+            controller_metadata as "controller_metadata: serde_json::Value",
+            image, image_digest, rolled_back_from_deployment_id,
+            http_port, needs_reconcile, is_active,
+            deploying_started_at,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
+            termination_reason as "termination_reason: _",
+            created_at, updated_at
+        "#,
+        id,
+        replicas
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to update deployment replicas")?;
+
+    Ok(deployment)
+}
+
+/// Set a new expiration time for a deployment
+pub async fn update_expires_at(
+    pool: &PgPool,
+    id: Uuid,
+    expires_at: DateTime<Utc>,
+) -> Result<Deployment> {
+    let deployment = sqlx::query_as!(
+        Deployment,
+        r#"
+        UPDATE deployments
+        SET expires_at = $2, updated_at = NOW()
+        WHERE id = $1
+        RETURNING
+            id, deployment_id, project_id, created_by_id,
+            status as "status: DeploymentStatus",
+            deployment_group, environment_id, expires_at,
+            completed_at, error_message, build_logs,
+            controller_metadata as "controller_metadata: serde_json::Value",
+            image, image_digest, rolled_back_from_deployment_id,
+            http_port, needs_reconcile, is_active,
+            deploying_started_at,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
+            termination_reason as "termination_reason: _",
+            created_at, updated_at
+        "#,
+        id,
+        expires_at
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to update deployment expiration")?;
+
+    Ok(deployment)
+}
+
+/// Request a restart of a deployment's pods and flag it for reconciliation
+pub async fn mark_restarted(pool: &PgPool, id: Uuid) -> Result<Deployment> {
+    let deployment = sqlx::query_as!(
+        Deployment,
+        r#"
+        UPDATE deployments
+        SET restarted_at = NOW(), needs_reconcile = TRUE, updated_at = NOW()
+        WHERE id = $1
+        RETURNING
+            id, deployment_id, project_id, created_by_id,
+            status as "status: DeploymentStatus",
+            deployment_group, environment_id, expires_at,
+            completed_at, error_message, build_logs,
+            controller_metadata as "controller_metadata: serde_json::Value",
+            image, image_digest, rolled_back_from_deployment_id,
+            http_port, needs_reconcile, is_active,
+            deploying_started_at,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
+            termination_reason as "termination_reason: _",
+            created_at, updated_at
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to mark deployment as restarted")?;
+
     Ok(deployment)
 }
 
@@ -807,8 +1125,8 @@ pub async fn find_active_for_project_and_group(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         FROM deployments
         WHERE project_id = $1
@@ -846,8 +1164,8 @@ pub async fn find_non_terminal_for_project_and_group(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         FROM deployments
         WHERE project_id = $1
@@ -885,8 +1203,8 @@ pub async fn find_active_deployment_for_group(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         FROM deployments
         WHERE project_id = $1
@@ -924,8 +1242,8 @@ pub async fn find_last_for_project_and_group(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             created_at, updated_at
         FROM deployments
         WHERE project_id = $1
@@ -968,8 +1286,8 @@ pub async fn list_for_project_and_group(
                 image, image_digest, rolled_back_from_deployment_id,
                 http_port, needs_reconcile, is_active,
                 deploying_started_at,
-                first_healthy_at, job_url, pull_request_url,
-                replicas, cpu, memory,
+                first_healthy_at, job_url, pull_request_url, message,
+                replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
                 created_at, updated_at
             FROM deployments
             WHERE project_id = $1 AND deployment_group = $2
@@ -998,8 +1316,8 @@ pub async fn list_for_project_and_group(
                 image, image_digest, rolled_back_from_deployment_id,
                 http_port, needs_reconcile, is_active,
                 deploying_started_at,
-                first_healthy_at, job_url, pull_request_url,
-                replicas, cpu, memory,
+                first_healthy_at, job_url, pull_request_url, message,
+                replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
                 created_at, updated_at
             FROM deployments
             WHERE project_id = $1
@@ -1017,6 +1335,70 @@ pub async fn list_for_project_and_group(
     Ok(deployments)
 }
 
+/// Count deployments in Deploying, across all projects or for a single project
+pub async fn count_deploying(pool: &PgPool, project_id: Option<Uuid>) -> Result<i64> {
+    let count = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM deployments
+        WHERE status = 'Deploying'
+          AND ($1::uuid IS NULL OR project_id = $1)
+        "#,
+        project_id
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to count deploying deployments")?;
+
+    Ok(count)
+}
+
+/// Count a project's in-progress or running deployments outside `group`
+///
+/// Counts everything from `Pending` through `Unhealthy`, so deployments that were just
+/// created count right away. Deployments in `group` are excluded because a new
+/// deployment to that group supersedes them rather than adding to them.
+async fn count_active_outside_group<'a, E>(
+    executor: E,
+    project_id: Uuid,
+    group: &str,
+) -> Result<i64>
+where
+    E: sqlx::Executor<'a, Database = sqlx::Postgres>,
+{
+    let count = sqlx::query_scalar!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM deployments
+        WHERE project_id = $1
+          AND deployment_group != $2
+          AND status IN ('Pending', 'Building', 'Pushing', 'Pushed', 'Deploying', 'Healthy', 'Unhealthy')
+        "#,
+        project_id,
+        group
+    )
+    .fetch_one(executor)
+    .await
+    .context("Failed to count active deployments")?;
+
+    Ok(count)
+}
+
+/// Count deployments per status across all projects
+pub async fn count_by_status(pool: &PgPool) -> Result<Vec<(String, i64)>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT status, COUNT(*) as "count!"
+        FROM deployments
+        GROUP BY status
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|r| (r.status, r.count)).collect())
+}
+
 /// Get all active deployment groups for a project
 /// Returns deployment groups based on the following rules:
 /// - "default" group: always included if it has any deployments (regardless of status)
@@ -1138,8 +1520,8 @@ pub async fn get_active_deployments_for_project(
             image, image_digest, rolled_back_from_deployment_id,
             http_port, needs_reconcile, is_active,
             deploying_started_at,
-            first_healthy_at, job_url, pull_request_url,
-            replicas, cpu, memory,
+            first_healthy_at, job_url, pull_request_url, message,
+            replicas, cpu, memory, cpu_limit, memory_limit, health_check, init_container, protocol, labels, annotations, command, args, ingress_annotations, strategy, drain_period_seconds, volume, scheduling, image_pull_policy, rate_limit, extra_ports, service_account_name, restarted_at,
             termination_reason as "termination_reason: _",
             created_at, updated_at
         FROM deployments
@@ -1400,9 +1782,28 @@ mod tests {
                 is_active: false,
                 job_url: None,
                 pull_request_url: None,
+                message: None,
                 replicas: 1,
                 cpu: "500m",
                 memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
             },
         )
         .await
@@ -1486,9 +1887,28 @@ mod tests {
                 is_active: false,
                 job_url: None,
                 pull_request_url: None,
+                message: None,
                 replicas: 1,
                 cpu: "500m",
                 memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
             },
         )
         .await
@@ -1509,4 +1929,135 @@ mod tests {
         let deployment = mark_healthy(&pool, deployment.id).await.unwrap();
         assert_eq!(deployment.first_healthy_at, Some(first_healthy_at));
     }
+
+    fn quota_test_params(
+        deployment_id: &'static str,
+        project_id: Uuid,
+        user_id: Uuid,
+        group: &'static str,
+        status: DeploymentStatus,
+    ) -> CreateDeploymentParams<'static> {
+        CreateDeploymentParams {
+            deployment_id,
+            project_id,
+            created_by_id: user_id,
+            status,
+            image: Some("test:v1"),
+            image_digest: None,
+            rolled_back_from_deployment_id: None,
+            deployment_group: group,
+            environment_id: None,
+            expires_at: None,
+            http_port: 8080,
+            is_active: false,
+            job_url: None,
+            pull_request_url: None,
+            message: None,
+            replicas: 1,
+            cpu: "500m",
+            memory: "256Mi",
+            cpu_limit: None,
+            memory_limit: None,
+            health_check: None,
+            init_container: None,
+            protocol: "http",
+            labels: None,
+            annotations: None,
+            command: None,
+            args: None,
+            ingress_annotations: None,
+            strategy: "rolling",
+            drain_period_seconds: None,
+            volume: None,
+            scheduling: None,
+            image_pull_policy: None,
+            rate_limit: None,
+            extra_ports: None,
+            service_account_name: None,
+        }
+    }
+
+    /// Test that the quota counts in-progress deployments but not the target group
+    #[sqlx::test]
+    async fn test_create_within_quota(pool: PgPool) {
+        let user = crate::db::users::create(&pool, "test@example.com")
+            .await
+            .unwrap();
+        let project = crate::db::projects::create(
+            &pool,
+            "quota-test",
+            crate::db::models::ProjectStatus::Running,
+            "public".to_string(),
+            Some(user.id),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        for (deployment_id, group, status) in [
+            ("20260101-100000", "default", DeploymentStatus::Healthy),
+            ("20260101-110000", "mr/1", DeploymentStatus::Stopped),
+        ] {
+            create(
+                &pool,
+                quota_test_params(deployment_id, project.id, user.id, group, status),
+            )
+            .await
+            .unwrap();
+        }
+
+        // One active deployment; a freshly created (Pending) one counts right away
+        let created = create_within_quota(
+            &pool,
+            quota_test_params(
+                "20260101-120000",
+                project.id,
+                user.id,
+                "mr/2",
+                DeploymentStatus::Pending,
+            ),
+            2,
+        )
+        .await
+        .unwrap();
+        assert!(created.is_some());
+
+        let rejected = create_within_quota(
+            &pool,
+            quota_test_params(
+                "20260101-130000",
+                project.id,
+                user.id,
+                "mr/3",
+                DeploymentStatus::Pending,
+            ),
+            2,
+        )
+        .await
+        .unwrap();
+        assert!(rejected.is_none());
+        assert!(
+            find_by_project_and_deployment_id(&pool, project.id, "20260101-130000")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // Redeploying an active group replaces a deployment instead of adding one
+        let redeployed = create_within_quota(
+            &pool,
+            quota_test_params(
+                "20260101-140000",
+                project.id,
+                user.id,
+                "mr/2",
+                DeploymentStatus::Pending,
+            ),
+            2,
+        )
+        .await
+        .unwrap();
+        assert!(redeployed.is_some());
+    }
 }
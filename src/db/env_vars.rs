@@ -48,12 +48,15 @@ pub async fn list_project_env_vars(
 }
 
 /// Get a specific project environment variable by key and optional environment.
-pub async fn get_project_env_var(
-    pool: &PgPool,
+pub async fn get_project_env_var<'a, E>(
+    executor: E,
     project_id: Uuid,
     key: &str,
     environment_id: Option<Uuid>,
-) -> Result<Option<ProjectEnvVar>> {
+) -> Result<Option<ProjectEnvVar>>
+where
+    E: sqlx::Executor<'a, Database = sqlx::Postgres>,
+{
     let env_var = if let Some(env_id) = environment_id {
         sqlx::query_as!(
             ProjectEnvVar,
@@ -66,7 +69,7 @@ pub async fn get_project_env_var(
             key,
             env_id
         )
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await
         .context("Failed to get project environment variable")?
     } else {
@@ -80,7 +83,7 @@ pub async fn get_project_env_var(
             project_id,
             key
         )
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await
         .context("Failed to get project environment variable")?
     };
@@ -99,11 +102,78 @@ pub async fn upsert_project_env_var(
     is_secret: bool,
     is_protected: bool,
     environment_id: Option<Uuid>,
+) -> Result<ProjectEnvVar> {
+    let mut conn = pool
+        .acquire()
+        .await
+        .context("Failed to acquire database connection")?;
+    upsert_project_env_var_on(
+        &mut conn,
+        project_id,
+        key,
+        value,
+        is_secret,
+        is_protected,
+        environment_id,
+    )
+    .await
+}
+
+/// A single variable written by `upsert_project_env_vars`.
+#[derive(Debug, Clone)]
+pub struct ProjectEnvVarUpsert<'a> {
+    pub key: &'a str,
+    pub value: &'a str,
+    pub is_secret: bool,
+    pub is_protected: bool,
+}
+
+/// Create or update several project environment variables at once.
+///
+/// All variables are written in a single transaction, so either every
+/// variable is stored or none are.
+pub async fn upsert_project_env_vars(
+    pool: &PgPool,
+    project_id: Uuid,
+    env_vars: &[ProjectEnvVarUpsert<'_>],
+    environment_id: Option<Uuid>,
+) -> Result<Vec<ProjectEnvVar>> {
+    let mut tx = pool.begin().await.context("Failed to begin transaction")?;
+
+    let mut stored = Vec::with_capacity(env_vars.len());
+    for env_var in env_vars {
+        stored.push(
+            upsert_project_env_var_on(
+                &mut tx,
+                project_id,
+                env_var.key,
+                env_var.value,
+                env_var.is_secret,
+                env_var.is_protected,
+                environment_id,
+            )
+            .await?,
+        );
+    }
+
+    tx.commit().await.context("Failed to commit transaction")?;
+
+    Ok(stored)
+}
+
+async fn upsert_project_env_var_on(
+    conn: &mut sqlx::PgConnection,
+    project_id: Uuid,
+    key: &str,
+    value: &str,
+    is_secret: bool,
+    is_protected: bool,
+    environment_id: Option<Uuid>,
 ) -> Result<ProjectEnvVar> {
     // We use the COALESCE-based unique index for conflict detection.
     // sqlx doesn't support ON CONFLICT on expressions directly, so we use a
     // two-step approach: try to find existing, then insert or update.
-    let existing = get_project_env_var(pool, project_id, key, environment_id).await?;
+    let existing = get_project_env_var(&mut *conn, project_id, key, environment_id).await?;
 
     let env_var = if let Some(existing) = existing {
         sqlx::query_as!(
@@ -119,7 +189,7 @@ pub async fn upsert_project_env_var(
             is_secret,
             is_protected
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await
         .context("Failed to update project environment variable")?
     } else {
@@ -137,7 +207,7 @@ pub async fn upsert_project_env_var(
             is_protected,
             environment_id
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await
         .context("Failed to insert project environment variable")?
     };
@@ -428,9 +498,28 @@ mod tests {
                 is_active: false,
                 job_url: None,
                 pull_request_url: None,
+                message: None,
                 replicas: 1,
                 cpu: "500m",
                 memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
             },
         )
         .await
@@ -530,9 +619,28 @@ mod tests {
                 is_active: false,
                 job_url: None,
                 pull_request_url: None,
+                message: None,
                 replicas: 1,
                 cpu: "500m",
                 memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
             },
         )
         .await
@@ -556,4 +664,59 @@ mod tests {
         assert_eq!(dep_vars[0].value, "staging-db");
         assert_eq!(dep_vars[0].source, "env:staging");
     }
+
+    #[sqlx::test]
+    async fn upsert_project_env_vars_writes_all_variables(pool: PgPool) {
+        let user = crate::db::users::create(&pool, "env-bulk-test@example.com")
+            .await
+            .unwrap();
+
+        let project = crate::db::projects::create(
+            &pool,
+            "env-bulk-test-project",
+            ProjectStatus::Stopped,
+            "default".to_string(),
+            Some(user.id),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        upsert_project_env_var(&pool, project.id, "PORT", "8080", false, false, None)
+            .await
+            .unwrap();
+
+        let stored = upsert_project_env_vars(
+            &pool,
+            project.id,
+            &[
+                ProjectEnvVarUpsert {
+                    key: "PORT",
+                    value: "9090",
+                    is_secret: false,
+                    is_protected: false,
+                },
+                ProjectEnvVarUpsert {
+                    key: "API_KEY",
+                    value: "encrypted",
+                    is_secret: true,
+                    is_protected: true,
+                },
+            ],
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stored.len(), 2);
+
+        let vars = list_project_env_vars(&pool, project.id, None)
+            .await
+            .unwrap();
+        assert_eq!(vars.len(), 2);
+        assert_eq!(vars[0].key, "API_KEY");
+        assert!(vars[0].is_secret);
+        assert_eq!(vars[1].key, "PORT");
+        assert_eq!(vars[1].value, "9090");
+    }
 }
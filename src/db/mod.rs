@@ -1,4 +1,6 @@
 pub mod custom_domains;
+pub mod deployment_events;
+pub mod deployment_notifications;
 pub mod deployments;
 pub mod env_vars;
 pub mod environments;
@@ -8,6 +10,7 @@ pub mod models;
 pub mod oauth_transient_state;
 pub mod postgres_admin;
 pub mod project_app_users;
+pub mod project_webhooks;
 pub mod projects;
 pub mod service_accounts;
 pub mod teams;
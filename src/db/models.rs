@@ -27,6 +27,17 @@ pub struct Project {
     pub finalizers: Vec<String>,
     /// URL to where the project code lives (e.g. a GitHub/GitLab repository)
     pub source_url: Option<String>,
+    /// Redirect HTTP to HTTPS on the project's ingresses.
+    /// `None` enables the redirect only when TLS is configured.
+    pub force_https: Option<bool>,
+    /// `max-age` (seconds) of the HSTS header; `None` disables HSTS
+    pub hsts_max_age: Option<i32>,
+    /// Quota of concurrently active deployments; `None` uses the server-wide default
+    pub max_active_deployments: Option<i32>,
+    /// Deployment group TTL policies (serialized `Vec<GroupTtlPolicy>`); `None` = no policies
+    pub group_ttls: Option<serde_json::Value>,
+    /// Kubernetes namespace resolved from `namespace_format` at creation; `None` until backfilled
+    pub namespace: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -160,12 +171,53 @@ pub struct Deployment {
     pub job_url: Option<String>,
     /// URL to the pull request/merge request associated with this deployment
     pub pull_request_url: Option<String>,
+    /// Optional human-readable reason for the deployment (e.g., "fix login bug")
+    pub message: Option<String>,
     /// Number of replicas for this deployment
     pub replicas: i32,
     /// CPU allocation (e.g., "500m", "1")
     pub cpu: String,
     /// Memory allocation (e.g., "256Mi", "1Gi")
     pub memory: String,
+    /// CPU limit, if different from the `cpu` request
+    pub cpu_limit: Option<String>,
+    /// Memory limit, if different from the `memory` request
+    pub memory_limit: Option<String>,
+    /// Health probe overrides (serialized `HealthCheckConfig`), if any
+    pub health_check: Option<serde_json::Value>,
+    /// Init container run before the app starts (serialized `InitContainerConfig`), if any
+    pub init_container: Option<serde_json::Value>,
+    /// Protocol the app speaks (`http` or `tcp`, see `DeploymentProtocol`)
+    pub protocol: String,
+    /// Custom labels for the app's workload (JSON object of strings), if any
+    pub labels: Option<serde_json::Value>,
+    /// Custom annotations for the app's workload (JSON object of strings), if any
+    pub annotations: Option<serde_json::Value>,
+    /// Override for the app container's command (image entrypoint), if any
+    pub command: Option<Vec<String>>,
+    /// Override for the app container's args (image cmd), if any
+    pub args: Option<Vec<String>>,
+    /// Extra annotations for the app's ingresses (JSON object of strings), if any
+    pub ingress_annotations: Option<serde_json::Value>,
+    /// How the deployment replaces the active one (`rolling` or `blue-green`, see `DeploymentStrategy`)
+    pub strategy: String,
+    /// Blue-green drain period in seconds, if set
+    pub drain_period_seconds: Option<i32>,
+    /// Persistent volume of the deployment group (serialized `VolumeConfig`), if any
+    pub volume: Option<serde_json::Value>,
+    /// Replica spreading across nodes or zones (serialized `SchedulingConfig`), if any
+    pub scheduling: Option<serde_json::Value>,
+    /// Image pull policy ("always" or "if-not-present"); `None` pulls digest-pinned
+    /// images only if missing and everything else always
+    pub image_pull_policy: Option<String>,
+    /// Ingress rate limits (serialized `RateLimitConfig`), if any
+    pub rate_limit: Option<serde_json::Value>,
+    /// Additional named container ports (serialized `PortConfig` map), if any
+    pub extra_ports: Option<serde_json::Value>,
+    /// Kubernetes ServiceAccount the pods run as, if not the environment's
+    pub service_account_name: Option<String>,
+    /// When the pods were last restarted via `rise deployment restart`
+    pub restarted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -243,6 +295,44 @@ impl std::fmt::Display for TerminationReason {
     }
 }
 
+/// Lifecycle event of a deployment (see `db::deployment_events`)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeploymentAuditEvent {
+    /// `created` or `status_changed`
+    pub event_type: String,
+    /// Status of the deployment after the event
+    pub status: String,
+    /// Error message or termination reason, if any
+    pub message: Option<String>,
+    /// Email of the user or service account that triggered the event, if any
+    pub actor_email: Option<String>,
+    pub actor_is_service_account: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Deployment status webhook of a project (see `db::project_webhooks`)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProjectWebhook {
+    pub project_id: Uuid,
+    pub url: String,
+    /// HMAC signing secret, encrypted with the configured encryption provider
+    pub secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Pending webhook delivery for a deployment status transition
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DeploymentNotification {
+    pub id: i64,
+    pub deployment_id: Uuid,
+    /// Status the deployment transitioned to
+    pub status: String,
+    /// Number of failed delivery attempts so far
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Project environment variable
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ProjectEnvVar {
@@ -342,6 +432,8 @@ pub struct CustomDomain {
     pub project_id: Uuid,
     pub domain: String,
     pub is_primary: bool,
+    /// Whether the domain resolved to the project's ingress at the last check
+    pub verified: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
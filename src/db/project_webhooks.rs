@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::ProjectWebhook;
+
+/// Get the deployment status webhook of a project
+pub async fn get(pool: &PgPool, project_id: Uuid) -> Result<Option<ProjectWebhook>> {
+    let webhook = sqlx::query_as!(
+        ProjectWebhook,
+        r#"
+        SELECT project_id, url, secret, created_at, updated_at
+        FROM project_webhooks
+        WHERE project_id = $1
+        "#,
+        project_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("Failed to get project webhook")?;
+
+    Ok(webhook)
+}
+
+/// Create or replace the deployment status webhook of a project.
+///
+/// `secret` must already be encrypted.
+pub async fn upsert(
+    pool: &PgPool,
+    project_id: Uuid,
+    url: &str,
+    secret: Option<&str>,
+) -> Result<ProjectWebhook> {
+    let webhook = sqlx::query_as!(
+        ProjectWebhook,
+        r#"
+        INSERT INTO project_webhooks (project_id, url, secret)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (project_id) DO UPDATE
+        SET url = EXCLUDED.url, secret = EXCLUDED.secret, updated_at = NOW()
+        RETURNING project_id, url, secret, created_at, updated_at
+        "#,
+        project_id,
+        url,
+        secret
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to store project webhook")?;
+
+    Ok(webhook)
+}
+
+/// Remove the deployment status webhook of a project.
+///
+/// Returns false if the project had no webhook.
+pub async fn delete(pool: &PgPool, project_id: Uuid) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM project_webhooks
+        WHERE project_id = $1
+        "#,
+        project_id
+    )
+    .execute(pool)
+    .await
+    .context("Failed to delete project webhook")?;
+
+    Ok(result.rows_affected() > 0)
+}
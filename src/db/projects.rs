@@ -17,7 +17,7 @@ pub async fn list(pool: &PgPool, owner_user_id: Option<Uuid>) -> Result<Vec<Proj
                 status as "status: ProjectStatus",
                 access_class,
                 owner_user_id, owner_team_id,
-                finalizers, source_url,
+                finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
                 created_at, updated_at
             FROM projects
             WHERE owner_user_id = $1
@@ -36,7 +36,7 @@ pub async fn list(pool: &PgPool, owner_user_id: Option<Uuid>) -> Result<Vec<Proj
                 status as "status: ProjectStatus",
                 access_class,
                 owner_user_id, owner_team_id,
-                finalizers, source_url,
+                finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
                 created_at, updated_at
             FROM projects
             ORDER BY created_at DESC
@@ -59,7 +59,7 @@ pub async fn list_accessible_by_user(pool: &PgPool, user_id: Uuid) -> Result<Vec
             p.status as "status: ProjectStatus",
             p.access_class,
             p.owner_user_id, p.owner_team_id,
-            p.finalizers, p.source_url,
+            p.finalizers, p.source_url, p.force_https, p.hsts_max_age, p.max_active_deployments, p.group_ttls, p.namespace,
             p.created_at, p.updated_at
         FROM projects p
         WHERE
@@ -96,7 +96,7 @@ pub async fn find_by_name(pool: &PgPool, name: &str) -> Result<Option<Project>>
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         FROM projects
         WHERE name = $1
@@ -120,7 +120,7 @@ pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<Project>> {
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         FROM projects
         WHERE id = $1
@@ -145,7 +145,7 @@ pub async fn find_by_ids(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<Project>> {
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         FROM projects
         WHERE id = ANY($1)
@@ -184,7 +184,7 @@ where
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         "#,
         name,
@@ -201,6 +201,27 @@ where
     Ok(project)
 }
 
+/// Store the Kubernetes namespace of a project
+pub async fn set_namespace<'a, E>(executor: E, id: Uuid, namespace: &str) -> Result<()>
+where
+    E: sqlx::Executor<'a, Database = sqlx::Postgres>,
+{
+    sqlx::query!(
+        r#"
+        UPDATE projects
+        SET namespace = $2
+        WHERE id = $1
+        "#,
+        id,
+        namespace
+    )
+    .execute(executor)
+    .await
+    .context("Failed to set project namespace")?;
+
+    Ok(())
+}
+
 /// Update project status
 pub async fn update_status(pool: &PgPool, id: Uuid, status: ProjectStatus) -> Result<Project> {
     let status_str = status.to_string();
@@ -216,7 +237,7 @@ pub async fn update_status(pool: &PgPool, id: Uuid, status: ProjectStatus) -> Re
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         "#,
         id,
@@ -242,7 +263,7 @@ pub async fn update_access_class(pool: &PgPool, id: Uuid, access_class: String)
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         "#,
         id,
@@ -273,7 +294,7 @@ pub async fn update_owner(
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         "#,
         id,
@@ -304,7 +325,7 @@ pub async fn update_source_url(
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         "#,
         id,
@@ -317,6 +338,98 @@ pub async fn update_source_url(
     Ok(project)
 }
 
+/// Update project HTTPS settings (HTTP→HTTPS redirect and HSTS)
+pub async fn update_https_settings(
+    pool: &PgPool,
+    id: Uuid,
+    force_https: Option<bool>,
+    hsts_max_age: Option<i32>,
+) -> Result<Project> {
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        UPDATE projects
+        SET force_https = $2, hsts_max_age = $3
+        WHERE id = $1
+        RETURNING
+            id, name,
+            status as "status: ProjectStatus",
+            access_class,
+            owner_user_id, owner_team_id,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
+            created_at, updated_at
+        "#,
+        id,
+        force_https,
+        hsts_max_age
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to update project HTTPS settings")?;
+
+    Ok(project)
+}
+
+/// Update the project's quota of concurrently active deployments (`None` = server default)
+pub async fn update_max_active_deployments(
+    pool: &PgPool,
+    id: Uuid,
+    max_active_deployments: Option<i32>,
+) -> Result<Project> {
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        UPDATE projects
+        SET max_active_deployments = $2
+        WHERE id = $1
+        RETURNING
+            id, name,
+            status as "status: ProjectStatus",
+            access_class,
+            owner_user_id, owner_team_id,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
+            created_at, updated_at
+        "#,
+        id,
+        max_active_deployments
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to update project deployment quota")?;
+
+    Ok(project)
+}
+
+/// Update the project's deployment group TTL policies (`None` = no policies)
+pub async fn update_group_ttls(
+    pool: &PgPool,
+    id: Uuid,
+    group_ttls: Option<serde_json::Value>,
+) -> Result<Project> {
+    let project = sqlx::query_as!(
+        Project,
+        r#"
+        UPDATE projects
+        SET group_ttls = $2
+        WHERE id = $1
+        RETURNING
+            id, name,
+            status as "status: ProjectStatus",
+            access_class,
+            owner_user_id, owner_team_id,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
+            created_at, updated_at
+        "#,
+        id,
+        group_ttls
+    )
+    .fetch_one(pool)
+    .await
+    .context("Failed to update project group TTL policies")?;
+
+    Ok(project)
+}
+
 /// Delete project by ID
 pub async fn delete(pool: &PgPool, id: Uuid) -> Result<()> {
     sqlx::query!("DELETE FROM projects WHERE id = $1", id)
@@ -340,6 +453,31 @@ pub async fn count_owned_by_team(pool: &PgPool, team_id: Uuid) -> Result<i64> {
     Ok(result.count)
 }
 
+/// List projects owned by a team
+pub async fn list_owned_by_team(pool: &PgPool, team_id: Uuid) -> Result<Vec<Project>> {
+    let projects = sqlx::query_as!(
+        Project,
+        r#"
+        SELECT
+            id, name,
+            status as "status: ProjectStatus",
+            access_class,
+            owner_user_id, owner_team_id,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
+            created_at, updated_at
+        FROM projects
+        WHERE owner_team_id = $1
+        ORDER BY name ASC
+        "#,
+        team_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to list projects owned by team")?;
+
+    Ok(projects)
+}
+
 /// Check if user can access project (directly or via team)
 pub async fn user_can_access(pool: &PgPool, project_id: Uuid, user_id: Uuid) -> Result<bool> {
     let result = sqlx::query!(
@@ -517,7 +655,7 @@ pub async fn mark_deleting(pool: &PgPool, id: Uuid) -> Result<Project> {
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         "#,
         id
@@ -539,7 +677,7 @@ pub async fn find_deleting(pool: &PgPool, limit: i64) -> Result<Vec<Project>> {
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         FROM projects
         WHERE status = 'Deleting'
@@ -613,7 +751,7 @@ pub async fn find_deleting_with_finalizer(
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         FROM projects
         WHERE status = 'Deleting' AND $1 = ANY(finalizers)
@@ -658,7 +796,7 @@ pub async fn list_active(pool: &PgPool) -> Result<Vec<Project>> {
             status as "status: ProjectStatus",
             access_class,
             owner_user_id, owner_team_id,
-            finalizers, source_url,
+            finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
             created_at, updated_at
         FROM projects
         WHERE status NOT IN ('Deleting', 'Terminated')
@@ -718,9 +856,28 @@ mod tests {
                 is_active: false, // Initially not active
                 job_url: None,
                 pull_request_url: None,
+                message: None,
                 replicas: 1,
                 cpu: "500m",
                 memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
             },
         )
         .await
@@ -766,9 +923,28 @@ mod tests {
                 is_active: false, // This is NOT active
                 job_url: None,
                 pull_request_url: None,
+                message: None,
                 replicas: 1,
                 cpu: "500m",
                 memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
             },
         )
         .await
@@ -835,6 +1011,116 @@ mod tests {
         assert!(project.source_url.is_none(), "source_url should be cleared");
     }
 
+    /// Test setting and clearing HTTPS settings on a project
+    #[sqlx::test]
+    async fn test_update_https_settings(pool: PgPool) {
+        let user = crate::db::users::create(&pool, "test@example.com")
+            .await
+            .expect("Failed to create test user");
+
+        let project = create(
+            &pool,
+            "https-test",
+            ProjectStatus::Stopped,
+            "default".to_string(),
+            Some(user.id),
+            None,
+            None,
+        )
+        .await
+        .expect("Failed to create test project");
+
+        assert!(project.force_https.is_none());
+        assert!(project.hsts_max_age.is_none());
+
+        let project = update_https_settings(&pool, project.id, Some(true), Some(31536000))
+            .await
+            .expect("Failed to set HTTPS settings");
+        assert_eq!(project.force_https, Some(true));
+        assert_eq!(project.hsts_max_age, Some(31536000));
+
+        // Out-of-range max-age is rejected by the check constraint
+        assert!(
+            update_https_settings(&pool, project.id, Some(true), Some(0))
+                .await
+                .is_err()
+        );
+
+        let project = update_https_settings(&pool, project.id, None, None)
+            .await
+            .expect("Failed to clear HTTPS settings");
+        assert!(project.force_https.is_none());
+        assert!(project.hsts_max_age.is_none());
+    }
+
+    /// Test setting and clearing the deployment quota of a project
+    #[sqlx::test]
+    async fn test_update_max_active_deployments(pool: PgPool) {
+        let user = crate::db::users::create(&pool, "test@example.com")
+            .await
+            .expect("Failed to create test user");
+
+        let project = create(
+            &pool,
+            "quota-test",
+            ProjectStatus::Stopped,
+            "default".to_string(),
+            Some(user.id),
+            None,
+            None,
+        )
+        .await
+        .expect("Failed to create test project");
+        assert!(project.max_active_deployments.is_none());
+
+        let project = update_max_active_deployments(&pool, project.id, Some(3))
+            .await
+            .expect("Failed to set deployment quota");
+        assert_eq!(project.max_active_deployments, Some(3));
+
+        // A quota of zero is rejected by the check constraint
+        assert!(update_max_active_deployments(&pool, project.id, Some(0))
+            .await
+            .is_err());
+
+        let project = update_max_active_deployments(&pool, project.id, None)
+            .await
+            .expect("Failed to clear deployment quota");
+        assert!(project.max_active_deployments.is_none());
+    }
+
+    /// Test setting and clearing the group TTL policies of a project
+    #[sqlx::test]
+    async fn test_update_group_ttls(pool: PgPool) {
+        let user = crate::db::users::create(&pool, "test@example.com")
+            .await
+            .expect("Failed to create test user");
+
+        let project = create(
+            &pool,
+            "ttl-test",
+            ProjectStatus::Stopped,
+            "default".to_string(),
+            Some(user.id),
+            None,
+            None,
+        )
+        .await
+        .expect("Failed to create test project");
+        assert!(project.group_ttls.is_none());
+
+        let policies = serde_json::json!([{"pattern": "mr/*", "expire": "7d"}]);
+        let project = update_group_ttls(&pool, project.id, Some(policies.clone()))
+            .await
+            .expect("Failed to set group TTL policies");
+        assert_eq!(project.group_ttls, Some(policies));
+
+        let project = update_group_ttls(&pool, project.id, None)
+            .await
+            .expect("Failed to clear group TTL policies");
+        assert!(project.group_ttls.is_none());
+    }
+
     /// Test that project status is Stopped when no active deployment but has failed deployment
     #[sqlx::test]
     async fn test_project_status_with_only_failed_deployment(pool: PgPool) {
@@ -874,9 +1160,28 @@ mod tests {
                 is_active: false,
                 job_url: None,
                 pull_request_url: None,
+                message: None,
                 replicas: 1,
                 cpu: "500m",
                 memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
             },
         )
         .await
@@ -19,7 +19,7 @@ pub async fn create(
         Project,
         r#"
         SELECT id, name, status as "status: _", access_class,
-               owner_user_id, owner_team_id, finalizers, source_url,
+               owner_user_id, owner_team_id, finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
                created_at, updated_at
         FROM projects
         WHERE id = $1
@@ -305,7 +305,7 @@ pub async fn create_with_raw_claims(
         Project,
         r#"
         SELECT id, name, status as "status: _", access_class,
-               owner_user_id, owner_team_id, finalizers, source_url,
+               owner_user_id, owner_team_id, finalizers, source_url, force_https, hsts_max_age, max_active_deployments, group_ttls, namespace,
                created_at, updated_at
         FROM projects
         WHERE id = $1
@@ -0,0 +1,187 @@
+//! Tracing subscriber setup.
+//!
+//! `RISE_LOG_FORMAT` selects between human-readable `text` logs (the default) and
+//! `json` logs with one object per line. In JSON mode, fields of the enclosing spans
+//! and of the event itself (e.g. `project`, `deployment_id`) are emitted as top-level
+//! keys so log aggregators can index them directly.
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::{self, FormatEvent, FormatFields, JsonFields};
+use tracing_subscriber::fmt::{FmtContext, FormattedFields, MakeWriter};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+/// Log output format, selected via `RISE_LOG_FORMAT`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    fn from_env() -> Result<Self> {
+        match std::env::var("RISE_LOG_FORMAT") {
+            Err(_) => Ok(Self::Text),
+            Ok(value) => match value.to_lowercase().as_str() {
+                "" | "text" => Ok(Self::Text),
+                "json" => Ok(Self::Json),
+                _ => bail!(
+                    "Invalid RISE_LOG_FORMAT '{}': expected 'text' or 'json'",
+                    value
+                ),
+            },
+        }
+    }
+}
+
+/// Initialize the global tracing subscriber, logging to stderr
+pub fn init() -> Result<()> {
+    let filter = EnvFilter::new(std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()));
+    let registry = tracing_subscriber::registry().with(filter);
+
+    match LogFormat::from_env()? {
+        LogFormat::Text => registry
+            .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+            .init(),
+        LogFormat::Json => registry.with(json_layer(std::io::stderr)).init(),
+    }
+
+    Ok(())
+}
+
+fn json_layer<S, W>(writer: W) -> tracing_subscriber::fmt::Layer<S, JsonFields, FlatJson, W>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .fmt_fields(JsonFields::new())
+        .event_format(FlatJson)
+        .with_writer(writer)
+}
+
+/// JSON event formatter that merges span and event fields into one flat object
+///
+/// Event fields win over span fields, and inner spans over outer ones.
+struct FlatJson;
+
+impl<S, N> FormatEvent<S, N> for FlatJson
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut fields = Map::new();
+        fields.insert(
+            "timestamp".into(),
+            Utc::now()
+                .to_rfc3339_opts(SecondsFormat::Micros, true)
+                .into(),
+        );
+        fields.insert("level".into(), metadata.level().to_string().into());
+        fields.insert("target".into(), metadata.target().into());
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                // Span fields are stored pre-rendered as a JSON object by `JsonFields`
+                if let Some(Ok(Value::Object(span_fields))) = extensions
+                    .get::<FormattedFields<N>>()
+                    .map(|f| serde_json::from_str::<Value>(f))
+                {
+                    fields.extend(span_fields);
+                }
+            }
+        }
+
+        event.record(&mut JsonVisitor(&mut fields));
+
+        let line = serde_json::to_string(&fields).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{}", line)
+    }
+}
+
+/// Records event fields into a JSON object, keeping numbers and booleans typed
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{:?}", value).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_json_layer_flattens_span_and_event_fields() {
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = tracing_subscriber::registry().with(json_layer(move || writer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("sync", project = "my-app", attempt = 1);
+            let _guard = span.enter();
+            tracing::warn!(deployment_id = %"20260101-120000", ready = false, "Deployment not ready");
+        });
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let line: Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["message"], "Deployment not ready");
+        assert_eq!(line["project"], "my-app");
+        assert_eq!(line["attempt"], 1);
+        assert_eq!(line["deployment_id"], "20260101-120000");
+        assert_eq!(line["ready"], false);
+        assert!(line["timestamp"].is_string());
+    }
+}
@@ -1,7 +1,6 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use reqwest::Client;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 // Module declarations with feature gates
 #[cfg(feature = "cli")]
@@ -11,6 +10,7 @@ mod build;
 #[cfg(feature = "cli")]
 mod cli;
 
+mod logging;
 #[cfg(any(feature = "cli", feature = "backend"))]
 mod rise_toml;
 
@@ -21,6 +21,8 @@ mod server;
 
 // Re-export for convenience (CLI modules)
 #[cfg(feature = "cli")]
+use cli::output::OutputFormat;
+#[cfg(feature = "cli")]
 use cli::*;
 
 /// Resolve environment from explicit flag or the `default = true` environment in rise.toml.
@@ -95,6 +97,7 @@ struct DeployArgs {
     #[arg(default_value = ".")]
     path: String,
     /// Pre-built image to deploy (e.g., nginx:latest). Skips build if provided.
+    /// Falls back to `image` under [deploy] in rise.toml.
     #[arg(long, short)]
     image: Option<String>,
     /// Create deployment from an existing deployment (e.g., '20240101-120000'). Skips build and reuses the image.
@@ -103,17 +106,20 @@ struct DeployArgs {
     /// When used with --from, copy environment variables from source deployment instead of using current project vars
     #[arg(long)]
     use_source_env_vars: bool,
-    /// Deployment group (e.g., 'default', 'mr/27'). Defaults to 'default' if not specified.
+    /// Deployment group (e.g., 'default', 'mr/27'). Falls back to `group` under [deploy]
+    /// in rise.toml, then 'default'.
     #[arg(long, short)]
     group: Option<String>,
     /// Target environment (e.g., 'production', 'staging'). Resolved from group if not specified.
     #[arg(long, short = 'E')]
     environment: Option<String>,
     /// Expiration duration (e.g., '7d', '2h', '30m'). Deployment will be automatically cleaned up after this period.
+    /// Falls back to `expire` under [deploy] in rise.toml.
     #[arg(long)]
     expire: Option<String>,
     /// HTTP port the application listens on (e.g., 3000, 8080, 5000).
-    /// Required when using --image. Defaults to 8080 for buildpack builds.
+    /// Required when using --image. Falls back to `http_port` under [deploy] in rise.toml,
+    /// then 8080 for buildpack builds.
     #[arg(long)]
     http_port: Option<u16>,
     /// Push the --image to the Rise registry instead of deploying it directly.
@@ -144,15 +150,37 @@ struct DeployArgs {
     /// Auto-detected from: CI_MERGE_REQUEST_URL (GitLab), GITHUB_SERVER_URL + GITHUB_REPOSITORY + GITHUB_REF + GITHUB_EVENT_NAME (GitHub Actions).
     #[arg(long)]
     pull_request_url: Option<String>,
+    /// Short message describing why this deployment happened (e.g., "fix login bug").
+    /// Auto-detected from the commit title in CI: CI_COMMIT_TITLE (GitLab), GITHUB_EVENT_PATH (GitHub Actions push events).
+    #[arg(long, short = 'm')]
+    message: Option<String>,
     /// Number of replicas for this deployment (overrides rise.toml)
     #[arg(long)]
     replicas: Option<u32>,
-    /// CPU allocation (e.g., "500m", "1") — sets both K8s request and limit (overrides rise.toml)
+    /// CPU allocation (e.g., "500m", "1") — the K8s request, and the limit unless --cpu-limit is set (overrides rise.toml)
     #[arg(long)]
     cpu: Option<String>,
-    /// Memory allocation (e.g., "256Mi", "1Gi") — sets both K8s request and limit (overrides rise.toml)
+    /// Memory allocation (e.g., "256Mi", "1Gi") — the K8s request, and the limit unless --memory-limit is set (overrides rise.toml)
     #[arg(long)]
     memory: Option<String>,
+    /// CPU limit (e.g., "1"), at least --cpu (overrides rise.toml)
+    #[arg(long)]
+    cpu_limit: Option<String>,
+    /// Memory limit (e.g., "512Mi"), at least --memory (overrides rise.toml)
+    #[arg(long)]
+    memory_limit: Option<String>,
+    /// Override the container command, i.e. the image's entrypoint (repeat for each
+    /// element, e.g. --command docker-entrypoint.sh; overrides rise.toml)
+    #[arg(long, value_name = "ARG", allow_hyphen_values = true)]
+    command: Vec<String>,
+    /// Override the container arguments, i.e. the image's cmd (repeat for each
+    /// element, e.g. --args postgres --args -c --args max_connections=200; overrides rise.toml)
+    #[arg(long = "args", value_name = "ARG", allow_hyphen_values = true)]
+    container_args: Vec<String>,
+    /// Print the Kubernetes manifests the deployment would create (secrets redacted)
+    /// without building, pushing or creating anything
+    #[arg(long)]
+    dry_run: bool,
     #[command(flatten)]
     build_args: build::BuildArgs,
 }
@@ -176,6 +204,9 @@ enum Commands {
         #[command(flatten)]
         build_args: build::BuildArgs,
     },
+    /// View and change CLI configuration (backend URL, token, container CLI, ...)
+    #[command(subcommand)]
+    Config(ConfigCommands),
     /// Deploy an application (shortcut for 'deployment create')
     Deploy {
         #[command(flatten)]
@@ -185,6 +216,24 @@ enum Commands {
     #[command(subcommand)]
     #[command(visible_alias = "d")]
     Deployment(DeploymentCommands),
+    /// Run a local OIDC issuer for testing service accounts (development only)
+    DevOidc {
+        /// Port to listen on
+        #[arg(long, default_value = "9400")]
+        port: u16,
+        /// Address to bind to
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+        /// Issuer URL as seen by the backend (defaults to http://localhost:<port>)
+        #[arg(long)]
+        issuer_url: Option<String>,
+        /// Claims of minted tokens (format: KEY=VALUE, can be specified multiple times; defaults to aud=rise and sub=dev)
+        #[arg(long = "claim", value_parser = parse_key_val::<String, String>)]
+        claims: Vec<(String, String)>,
+        /// Lifetime of minted tokens in seconds
+        #[arg(long, default_value = "3600")]
+        expires_in: u64,
+    },
     /// Custom domain management commands
     #[command(subcommand)]
     #[command(visible_alias = "dom")]
@@ -217,6 +266,9 @@ enum Commands {
         /// Use device authorization flow
         #[arg(long, conflicts_with = "browser")]
         device: bool,
+        /// Name of the identity provider to log in with, if the backend offers several
+        #[arg(long)]
+        issuer: Option<String>,
     },
     /// Project management commands
     #[command(subcommand)]
@@ -245,6 +297,9 @@ enum Commands {
         /// Runtime environment variables (format: KEY=VALUE, can be specified multiple times)
         #[arg(long = "env", short = 'e', value_parser = parse_key_val::<String, String>)]
         run_env: Vec<(String, String)>,
+        /// Rebuild the image and restart the container when files change
+        #[arg(long, short)]
+        watch: bool,
         #[command(flatten)]
         build_args: build::BuildArgs,
     },
@@ -256,6 +311,8 @@ enum Commands {
     #[command(subcommand)]
     #[command(visible_alias = "t")]
     Team(TeamCommands),
+    /// Show the currently authenticated identity and token expiry
+    Whoami,
 }
 
 #[derive(Subcommand, Debug)]
@@ -269,7 +326,7 @@ enum ProjectCommands {
         /// Access class (e.g., public, private)
         #[arg(long, default_value = "public")]
         access_class: String,
-        /// Owner (format: "user:email" or "team:name", defaults to current user)
+        /// Owner (format: "user:email", "team:name" or "group:name", defaults to current user)
         #[arg(long)]
         owner: Option<String>,
         /// URL to where the project code lives (e.g. a GitHub/GitLab repository)
@@ -285,12 +342,19 @@ enum ProjectCommands {
     /// List all projects
     #[command(visible_alias = "ls")]
     #[command(visible_alias = "l")]
-    List {},
+    List {
+        /// Output format
+        #[arg(long, short, value_enum, default_value_t)]
+        output: OutputFormat,
+    },
     /// Show project details
     #[command(visible_alias = "s")]
     Show {
         /// Project name
         project: String,
+        /// Also show the N most recent deployments of each deployment group
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "3")]
+        deployments: Option<usize>,
     },
     /// Update project
     #[command(visible_alias = "u")]
@@ -304,12 +368,37 @@ enum ProjectCommands {
         /// New access class (e.g., public, private)
         #[arg(long)]
         access_class: Option<String>,
-        /// Transfer ownership (format: "user:email" or "team:name")
+        /// Transfer ownership (format: "user:email", "team:name" or "group:name" for an IdP group)
         #[arg(long)]
         owner: Option<String>,
         /// URL to where the project code lives (e.g. a GitHub/GitLab repository). Use empty string to clear.
         #[arg(long)]
         source_url: Option<String>,
+        /// Redirect HTTP to HTTPS (`auto`, the default, redirects when TLS is configured)
+        #[arg(long, value_enum)]
+        force_https: Option<project::ForceHttps>,
+        /// Send HSTS with this max-age in seconds (0 disables HSTS)
+        #[arg(long)]
+        hsts_max_age: Option<u32>,
+        /// Maximum number of concurrently active deployments, admins only
+        /// (0 reverts to the server default)
+        #[arg(long)]
+        max_active_deployments: Option<u32>,
+    },
+    /// Transfer a project to another user, team or IdP group
+    ///
+    /// Shows the current and new owner and asks for confirmation. Active deployments
+    /// keep running; who can manage and (for private projects) reach them follows the
+    /// new owner.
+    Transfer {
+        /// Project name
+        project: String,
+        /// New owner (format: "user:email", "team:name" or "group:name" for an IdP group)
+        #[arg(long)]
+        to: String,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
     },
     /// Delete a project
     #[command(visible_alias = "del")]
@@ -318,6 +407,56 @@ enum ProjectCommands {
         /// Project name
         project: String,
     },
+    /// Expire new deployments in matching groups after a duration
+    ///
+    /// Applies when a deployment doesn't set its own expiration (--expire or
+    /// [deploy] expire). Policies are checked in order and the first match wins.
+    SetTtl {
+        /// Group name pattern (`*` matches any characters, `?` a single character)
+        pattern: String,
+        /// Time until expiration (e.g., 7d, 12h, 30m)
+        duration: String,
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+    },
+    /// Remove the group TTL for a pattern
+    UnsetTtl {
+        /// Group name pattern, as passed to set-ttl
+        pattern: String,
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+    },
+    /// Post deployment status changes (Healthy, Unhealthy, Failed) to a URL
+    SetWebhook {
+        /// Webhook URL (must be HTTPS)
+        url: String,
+        /// Secret used to sign requests (X-Rise-Signature: sha256=<HMAC-SHA256 of the body>)
+        #[arg(long)]
+        secret: Option<String>,
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+    },
+    /// Stop posting deployment status changes
+    UnsetWebhook {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+    },
     /// Manage app users/teams (view-only access to deployed apps)
     #[command(subcommand)]
     AppUser(AppUserCommands),
@@ -360,6 +499,41 @@ enum AppUserCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    /// Show all settings with their effective values and where they come from
+    #[command(visible_alias = "s")]
+    Show {
+        /// Print the token instead of redacting it
+        #[arg(long)]
+        reveal: bool,
+    },
+    /// Print the effective value of a setting
+    #[command(visible_alias = "g")]
+    Get {
+        /// Setting to print
+        #[arg(value_enum)]
+        key: config::ConfigKey,
+        /// Print the token instead of redacting it
+        #[arg(long)]
+        reveal: bool,
+    },
+    /// Store a setting in the config file
+    Set {
+        /// Setting to change
+        #[arg(value_enum)]
+        key: config::ConfigKey,
+        /// New value
+        value: String,
+    },
+    /// Remove a setting from the config file
+    Unset {
+        /// Setting to remove
+        #[arg(value_enum)]
+        key: config::ConfigKey,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum TeamCommands {
     /// Create a new team
@@ -385,6 +559,12 @@ enum TeamCommands {
         /// Team name
         team: String,
     },
+    /// List projects owned by a team
+    #[command(visible_alias = "projects")]
+    ListProjects {
+        /// Team name
+        team: String,
+    },
     /// Update team
     #[command(visible_alias = "u")]
     #[command(visible_alias = "edit")]
@@ -442,6 +622,9 @@ enum DeploymentCommands {
         /// Limit number of deployments to show
         #[arg(long, short, default_value = "10")]
         limit: usize,
+        /// Output format
+        #[arg(long, short, value_enum, default_value_t)]
+        output: OutputFormat,
     },
     /// Show deployment details
     #[command(visible_alias = "s")]
@@ -460,8 +643,72 @@ enum DeploymentCommands {
         /// Timeout for following deployment
         #[arg(long, default_value = "5m")]
         timeout: String,
+        /// Print the deployment as JSON (one line per change with --follow)
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show deployment details and the Kubernetes events of its pods
+    ///
+    /// Events such as FailedScheduling or quota errors explain why a deployment is
+    /// stuck in Deploying when its containers report no error.
+    Describe {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID (YYYYMMDD-HHMMSS format)
+        deployment_id: String,
+    },
+    /// Show the lifecycle history of a deployment
+    ///
+    /// Lists every status change with the time it happened and who triggered it
+    /// (a user, a service account, or the controller).
+    #[command(visible_alias = "events")]
+    History {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID (YYYYMMDD-HHMMSS format)
+        deployment_id: String,
+    },
+    /// Live view of the status of every deployment group
+    ///
+    /// Redraws a table of groups, statuses and URLs on each poll and highlights
+    /// status transitions. Exit with Ctrl+C.
+    #[command(visible_alias = "w")]
+    Watch {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Polling interval
+        #[arg(long, default_value = "3s")]
+        interval: String,
+    },
+    /// Show what changed between two deployments
+    ///
+    /// Compares image, port, replicas, resources and environment variables. Secret
+    /// values are not shown, only whether they changed.
+    Diff {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID to compare from (YYYYMMDD-HHMMSS format)
+        from: String,
+        /// Deployment ID to compare to (YYYYMMDD-HHMMSS format)
+        to: String,
     },
-    /// Stop all deployments in a group
+    /// Stop all deployments in a group (or in every group with --all)
     Stop {
         /// Project name (optional if rise.toml contains [project] section)
         #[arg(long, short = 'p')]
@@ -470,8 +717,108 @@ enum DeploymentCommands {
         #[arg(long, default_value = ".")]
         path: String,
         /// Deployment group to stop
+        #[arg(long, short, conflicts_with = "all", required_unless_present = "all")]
+        group: Option<String>,
+        /// Stop the deployments of every group in the project
+        #[arg(long)]
+        all: bool,
+    },
+    /// Cancel a single in-progress deployment
+    ///
+    /// The active deployment of the group keeps serving traffic.
+    Cancel {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID (YYYYMMDD-HHMMSS format)
+        deployment_id: String,
+    },
+    /// Roll back to a previous deployment
+    ///
+    /// Creates a new deployment with the same image as the target deployment
+    Rollback {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID to roll back to
+        #[arg(required_unless_present = "previous", conflicts_with = "previous")]
+        deployment_id: Option<String>,
+        /// Roll back to the deployment that preceded the group's current deployment
+        #[arg(long)]
+        previous: bool,
+        /// Deployment group (defaults to the target's group, or 'default' with --previous)
         #[arg(long, short)]
-        group: String,
+        group: Option<String>,
+        /// Show the rollback target and what changes without deploying
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Deploy the image and env vars of a deployment to another group, without rebuilding
+    Promote {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID to promote (YYYYMMDD-HHMMSS format)
+        deployment_id: String,
+        /// Target deployment group (e.g., 'default')
+        #[arg(long)]
+        to: String,
+        /// Target environment (defaults to the environment resolved from the group)
+        #[arg(long, short = 'E')]
+        environment: Option<String>,
+        /// Reason for the promotion, shown in the deployment list
+        #[arg(long, short = 'm')]
+        message: Option<String>,
+    },
+    /// Change the number of replicas of a running deployment
+    Scale {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID (YYYYMMDD-HHMMSS format)
+        deployment_id: String,
+        /// Desired number of replicas
+        #[arg(long)]
+        replicas: u32,
+    },
+    /// Restart the pods of a running deployment without rebuilding
+    Restart {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID (YYYYMMDD-HHMMSS format)
+        deployment_id: String,
+    },
+    /// Extend the lifetime of an expiring deployment
+    ///
+    /// The new expiration is computed from now and may be capped by the platform.
+    Renew {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID (YYYYMMDD-HHMMSS format)
+        deployment_id: String,
+        /// New expiration duration from now (e.g., 7d, 2h, 30m)
+        #[arg(long)]
+        expire: String,
     },
     /// Show logs from a deployment
     Logs {
@@ -495,6 +842,18 @@ enum DeploymentCommands {
         /// Show logs since duration (e.g., "5m", "1h")
         #[arg(long)]
         since: Option<String>,
+        /// Merge logs of all pods of the deployment (prefixed with the pod name)
+        #[arg(long)]
+        all_pods: bool,
+        /// Save the logs to this file instead of printing them
+        #[arg(long, value_name = "PATH", conflicts_with = "follow")]
+        download: Option<std::path::PathBuf>,
+        /// Only show lines matching this regular expression
+        #[arg(long, value_name = "PATTERN")]
+        grep: Option<String>,
+        /// Show the lines that do not match --grep instead
+        #[arg(long, requires = "grep")]
+        invert_match: bool,
     },
 }
 
@@ -554,6 +913,22 @@ enum ServiceAccountCommands {
         /// Service account ID
         id: String,
     },
+    /// Check which service account an OIDC token would authenticate as
+    ///
+    /// Verifies the token and matches its claims against the project's service
+    /// accounts, reporting why each one does or doesn't match.
+    #[command(visible_alias = "token")]
+    TestToken {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// OIDC token to test (read from stdin if not provided)
+        #[arg(long)]
+        token: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -567,11 +942,10 @@ enum EnvCommands {
         /// Path to rise.toml (defaults to current directory)
         #[arg(long, default_value = ".")]
         path: String,
-        /// Variable name (e.g., DATABASE_URL)
-        key: String,
-        /// Variable value
-        value: String,
-        /// Mark as secret (encrypted at rest)
+        /// Variables as KEY=VALUE pairs, or a single variable as KEY VALUE
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+        /// Mark as secret (encrypted at rest). Applies to all given variables.
         #[arg(long)]
         secret: bool,
         /// Mark secret as protected (cannot be decrypted via API). Only applies to secrets. Defaults to true for secrets, must be false for non-secrets.
@@ -654,8 +1028,20 @@ enum EnvCommands {
         #[arg(long, default_value = ".")]
         path: String,
         /// Target environment (e.g., 'staging'). Resolved from rise.toml if not specified.
+        /// With `--format import`, exports only the variables scoped to it instead of the
+        /// global ones.
         #[arg(long, short = 'E')]
         environment: Option<String>,
+        /// Output format: resolved `KEY=value` lines, or the `rise env import` file format
+        #[arg(long, value_enum, default_value_t)]
+        format: env::ExportFormat,
+        /// Write to this file instead of stdout
+        #[arg(long, short = 'f')]
+        file: Option<std::path::PathBuf>,
+        /// With `--format import`, include decrypted values of unprotected secrets
+        /// (otherwise written as secret:***)
+        #[arg(long)]
+        reveal: bool,
     },
     /// Show environment variables for a deployment (read-only)
     ShowDeployment {
@@ -668,6 +1054,17 @@ enum EnvCommands {
         /// Deployment ID
         deployment_id: String,
     },
+    /// Compare a deployment's environment variables with the current project variables
+    Diff {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Deployment ID
+        deployment_id: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -795,6 +1192,18 @@ enum DomainCommands {
         /// Domain name
         domain: String,
     },
+    /// Diagnose DNS, TLS and HTTP routing of a custom domain
+    #[command(visible_alias = "c")]
+    Check {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Domain name
+        domain: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -871,6 +1280,20 @@ enum ExtensionCommands {
         /// Extension name
         extension: String,
     },
+    /// Show recent reconcile events of an extension (e.g. why provisioning is stuck)
+    Logs {
+        /// Project name (optional if rise.toml contains [project] section)
+        #[arg(long, short = 'p')]
+        project: Option<String>,
+        /// Path to rise.toml (defaults to current directory)
+        #[arg(long, default_value = ".")]
+        path: String,
+        /// Extension name
+        extension: String,
+        /// Only show the last N events
+        #[arg(long)]
+        tail: Option<usize>,
+    },
     /// Delete an extension from a project
     #[command(visible_alias = "rm")]
     #[command(visible_alias = "del")]
@@ -884,6 +1307,24 @@ enum ExtensionCommands {
         /// Extension name
         extension: String,
     },
+    /// Check a spec against an extension type without creating anything
+    ///
+    /// The backend runs the same validation as `create`. With `--schema`, the type's
+    /// documentation and spec schema are printed as well.
+    Validate {
+        /// Extension type (handler identifier, e.g., "aws-rds-provisioner", "oauth")
+        #[arg(long)]
+        r#type: String,
+        /// Extension spec as JSON string
+        #[arg(long, conflicts_with = "spec_file")]
+        spec: Option<String>,
+        /// Read the extension spec from a JSON file
+        #[arg(long)]
+        spec_file: Option<std::path::PathBuf>,
+        /// Print the extension type's documentation and spec schema
+        #[arg(long)]
+        schema: bool,
+    },
 }
 
 /// Parse a single key-value pair
@@ -905,12 +1346,7 @@ where
 #[tokio::main]
 async fn main() -> Result<()> {
     // Initialize tracing for all commands
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
-        ))
-        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
-        .init();
+    logging::init()?;
 
     let cli = Cli::parse();
 
@@ -932,9 +1368,13 @@ async fn main() -> Result<()> {
     let mut config = config::Config::load()?;
     let backend_url = config.get_backend_url();
 
-    // Check version compatibility for all commands except Login
-    // (Backend commands are handled above and don't use the HTTP API; Login might use a custom URL)
-    if !matches!(&cli_command, Commands::Login { .. }) {
+    // Check version compatibility for all commands except Login, Config and DevOidc
+    // (Backend commands are handled above and don't use the HTTP API; Login might use a custom URL;
+    // Config only touches the local config file; DevOidc doesn't talk to the backend)
+    if !matches!(
+        &cli_command,
+        Commands::Login { .. } | Commands::Config(_) | Commands::DevOidc { .. }
+    ) {
         // Non-fatal version check - just warns user
         let _ = version::check_version_compatibility(&http_client, &backend_url).await;
     }
@@ -944,6 +1384,7 @@ async fn main() -> Result<()> {
             url,
             browser: _,
             device,
+            issuer,
         } => {
             // Use provided URL or fall back to config default
             let login_url = url
@@ -953,8 +1394,14 @@ async fn main() -> Result<()> {
 
             if *device {
                 // Device flow (explicit)
-                login::handle_device_flow(&http_client, &login_url, &mut config, url.as_deref())
-                    .await?;
+                login::handle_device_flow(
+                    &http_client,
+                    &login_url,
+                    &mut config,
+                    url.as_deref(),
+                    issuer.as_deref(),
+                )
+                .await?;
             } else {
                 // Authorization code flow with PKCE (default)
                 login::handle_authorization_code_flow(
@@ -962,6 +1409,7 @@ async fn main() -> Result<()> {
                     &login_url,
                     &mut config,
                     url.as_deref(),
+                    issuer.as_deref(),
                 )
                 .await?;
             }
@@ -971,6 +1419,12 @@ async fn main() -> Result<()> {
             // Already handled above before config loading
             unreachable!("Backend commands should have been handled earlier")
         }
+        Commands::Config(config_cmd) => match config_cmd {
+            ConfigCommands::Show { reveal } => config::show_config(&config, *reveal)?,
+            ConfigCommands::Get { key, reveal } => config::get_config(&config, *key, *reveal)?,
+            ConfigCommands::Set { key, value } => config::set_config(&mut config, *key, value)?,
+            ConfigCommands::Unset { key } => config::unset_config(&mut config, *key)?,
+        },
         Commands::Encrypt { plaintext } => {
             cli::encrypt::encrypt_command(&config, plaintext.clone()).await?;
         }
@@ -996,11 +1450,15 @@ async fn main() -> Result<()> {
                 )
                 .await?;
             }
-            ProjectCommands::List {} => {
-                project::list_projects(&http_client, &backend_url, &config).await?;
+            ProjectCommands::List { output } => {
+                project::list_projects(&http_client, &backend_url, &config, *output).await?;
             }
-            ProjectCommands::Show { project } => {
-                project::show_project(&http_client, &backend_url, &config, project).await?;
+            ProjectCommands::Show {
+                project,
+                deployments,
+            } => {
+                project::show_project(&http_client, &backend_url, &config, project, *deployments)
+                    .await?;
             }
             ProjectCommands::Update {
                 project,
@@ -1008,6 +1466,9 @@ async fn main() -> Result<()> {
                 access_class,
                 owner,
                 source_url,
+                force_https,
+                hsts_max_age,
+                max_active_deployments,
             } => {
                 // Convert "--source-url ''" (empty string) to Some(None) to clear
                 let source_url_opt: Option<Option<String>> =
@@ -1023,12 +1484,85 @@ async fn main() -> Result<()> {
                     access_class.clone(),
                     owner.clone(),
                     source_url_opt,
+                    *force_https,
+                    *hsts_max_age,
+                    *max_active_deployments,
                 )
                 .await?;
             }
+            ProjectCommands::Transfer { project, to, yes } => {
+                project::transfer_project(&http_client, &backend_url, &config, project, to, *yes)
+                    .await?;
+            }
             ProjectCommands::Delete { project } => {
                 project::delete_project(&http_client, &backend_url, &config, project).await?;
             }
+            ProjectCommands::SetTtl {
+                pattern,
+                duration,
+                project,
+                path,
+            } => {
+                let token = config.get_token().ok_or_else(|| {
+                    anyhow::anyhow!("Not authenticated. Please run 'rise login' first")
+                })?;
+                let project_name = resolve_project_name(project.clone(), path)?;
+                cli::project::set_group_ttl(
+                    &http_client,
+                    &backend_url,
+                    &token,
+                    &project_name,
+                    pattern,
+                    duration,
+                )
+                .await?;
+            }
+            ProjectCommands::UnsetTtl {
+                pattern,
+                project,
+                path,
+            } => {
+                let token = config.get_token().ok_or_else(|| {
+                    anyhow::anyhow!("Not authenticated. Please run 'rise login' first")
+                })?;
+                let project_name = resolve_project_name(project.clone(), path)?;
+                cli::project::unset_group_ttl(
+                    &http_client,
+                    &backend_url,
+                    &token,
+                    &project_name,
+                    pattern,
+                )
+                .await?;
+            }
+            ProjectCommands::SetWebhook {
+                url,
+                secret,
+                project,
+                path,
+            } => {
+                let token = config.get_token().ok_or_else(|| {
+                    anyhow::anyhow!("Not authenticated. Please run 'rise login' first")
+                })?;
+                let project_name = resolve_project_name(project.clone(), path)?;
+                cli::project::set_webhook(
+                    &http_client,
+                    &backend_url,
+                    &token,
+                    &project_name,
+                    url,
+                    secret.as_deref(),
+                )
+                .await?;
+            }
+            ProjectCommands::UnsetWebhook { project, path } => {
+                let token = config.get_token().ok_or_else(|| {
+                    anyhow::anyhow!("Not authenticated. Please run 'rise login' first")
+                })?;
+                let project_name = resolve_project_name(project.clone(), path)?;
+                cli::project::unset_webhook(&http_client, &backend_url, &token, &project_name)
+                    .await?;
+            }
             ProjectCommands::AppUser(app_user_cmd) => {
                 let token = config.get_token().ok_or_else(|| {
                     anyhow::anyhow!("Not authenticated. Please run 'rise login' first")
@@ -1111,6 +1645,9 @@ async fn main() -> Result<()> {
             TeamCommands::Show { team } => {
                 team::show_team(&http_client, &backend_url, &config, team).await?;
             }
+            TeamCommands::ListProjects { team } => {
+                team::list_team_projects(&http_client, &backend_url, &config, team).await?;
+            }
             TeamCommands::Update {
                 team,
                 name,
@@ -1186,6 +1723,44 @@ async fn main() -> Result<()> {
                     toml_config.as_ref(),
                 )?;
 
+                // Resolve environment: explicit --environment flag takes precedence,
+                // otherwise fall back to the `default = true` environment from rise.toml.
+                let resolved_environment =
+                    resolve_environment(args.environment.clone(), toml_config.as_ref());
+
+                // Deployment settings resolve from CLI flags > rise.toml environment > rise.toml global
+                let toml_env_deploy = resolved_environment.as_deref().and_then(|env_name| {
+                    toml_config
+                        .as_ref()
+                        .and_then(|c| c.environments.get(env_name))
+                        .and_then(|e| e.deploy.as_ref())
+                });
+                let toml_global_deploy = toml_config.as_ref().and_then(|c| c.deploy.as_ref());
+
+                // --from reuses the source deployment's image, so a rise.toml image doesn't apply
+                let image = args.image.clone().or_else(|| {
+                    if args.from.is_some() {
+                        return None;
+                    }
+                    toml_env_deploy
+                        .and_then(|d| d.image.clone())
+                        .or_else(|| toml_global_deploy.and_then(|d| d.image.clone()))
+                });
+                let http_port = args
+                    .http_port
+                    .or_else(|| toml_env_deploy.and_then(|d| d.http_port))
+                    .or_else(|| toml_global_deploy.and_then(|d| d.http_port));
+                let group = args
+                    .group
+                    .clone()
+                    .or_else(|| toml_env_deploy.and_then(|d| d.group.clone()))
+                    .or_else(|| toml_global_deploy.and_then(|d| d.group.clone()));
+                let expire = args
+                    .expire
+                    .clone()
+                    .or_else(|| toml_env_deploy.and_then(|d| d.expire.clone()))
+                    .or_else(|| toml_global_deploy.and_then(|d| d.expire.clone()));
+
                 // Both --image and --from cannot be specified together
                 if args.image.is_some() && args.from.is_some() {
                     eprintln!("Error: Cannot specify both --image and --from");
@@ -1193,7 +1768,7 @@ async fn main() -> Result<()> {
                 }
 
                 // --push-image requires --image
-                if args.push_image && args.image.is_none() {
+                if args.push_image && image.is_none() {
                     eprintln!("Error: --push-image requires --image");
                     std::process::exit(1);
                 }
@@ -1205,22 +1780,18 @@ async fn main() -> Result<()> {
                 }
 
                 // For pre-built images, --http-port is required since we can't infer it
-                if let Some(image) = &args.image {
-                    if args.http_port.is_none() {
+                if let Some(image) = &image {
+                    if http_port.is_none() {
                         eprintln!("Error: --http-port is required when using --image");
                         eprintln!(
                             "Example: rise deployment create {} --image {} --http-port 80",
                             project_name, image
                         );
+                        eprintln!("Or set http_port under [deploy] in rise.toml");
                         std::process::exit(1);
                     }
                 }
 
-                // Resolve environment: explicit --environment flag takes precedence,
-                // otherwise fall back to the `default = true` environment from rise.toml.
-                let resolved_environment =
-                    resolve_environment(args.environment.clone(), toml_config.as_ref());
-
                 // Collect runtime env overrides with source tracking.
                 // All toml vars are sent tagged with for_environment; the server
                 // filters them after resolving the deployment's target environment.
@@ -1321,19 +1892,12 @@ async fn main() -> Result<()> {
                 }
 
                 // Pass through the http_port option - server will resolve from:
-                // 1. Explicit http_port (if provided)
+                // 1. Explicit http_port (--http-port or rise.toml, if provided)
                 // 2. Source deployment's http_port (if --from is used)
                 // 3. Project's PORT env var (if set)
                 // 4. Default 8080
-                // Resolve deployment resources from CLI flags > rise.toml environment > rise.toml global
-                let toml_env_deploy = resolved_environment.as_deref().and_then(|env_name| {
-                    toml_config
-                        .as_ref()
-                        .and_then(|c| c.environments.get(env_name))
-                        .and_then(|e| e.deploy.as_ref())
-                });
-                let toml_global_deploy = toml_config.as_ref().and_then(|c| c.deploy.as_ref());
-
+                //
+                // Deployment resources resolve the same way as the settings above.
                 let replicas = args
                     .replicas
                     .or_else(|| toml_env_deploy.and_then(|d| d.replicas))
@@ -1348,6 +1912,77 @@ async fn main() -> Result<()> {
                     .clone()
                     .or_else(|| toml_env_deploy.and_then(|d| d.memory.clone()))
                     .or_else(|| toml_global_deploy.and_then(|d| d.memory.clone()));
+                let cpu_limit = args
+                    .cpu_limit
+                    .clone()
+                    .or_else(|| toml_env_deploy.and_then(|d| d.cpu_limit.clone()))
+                    .or_else(|| toml_global_deploy.and_then(|d| d.cpu_limit.clone()));
+                let memory_limit = args
+                    .memory_limit
+                    .clone()
+                    .or_else(|| toml_env_deploy.and_then(|d| d.memory_limit.clone()))
+                    .or_else(|| toml_global_deploy.and_then(|d| d.memory_limit.clone()));
+                let health_check = match (
+                    toml_env_deploy.and_then(|d| d.health_check.clone()),
+                    toml_global_deploy.and_then(|d| d.health_check.as_ref()),
+                ) {
+                    (Some(env), Some(global)) => Some(env.or(global)),
+                    (env, global) => env.or_else(|| global.cloned()),
+                };
+                let init_container = toml_env_deploy
+                    .and_then(|d| d.init.clone())
+                    .or_else(|| toml_global_deploy.and_then(|d| d.init.clone()));
+                let protocol = toml_env_deploy
+                    .and_then(|d| d.protocol)
+                    .or_else(|| toml_global_deploy.and_then(|d| d.protocol));
+                let labels = rise_toml::merge_metadata_maps(
+                    toml_global_deploy.and_then(|d| d.labels.as_ref()),
+                    toml_env_deploy.and_then(|d| d.labels.as_ref()),
+                );
+                let annotations = rise_toml::merge_metadata_maps(
+                    toml_global_deploy.and_then(|d| d.annotations.as_ref()),
+                    toml_env_deploy.and_then(|d| d.annotations.as_ref()),
+                );
+                let command = Some(args.command.clone())
+                    .filter(|c| !c.is_empty())
+                    .or_else(|| toml_env_deploy.and_then(|d| d.command.clone()))
+                    .or_else(|| toml_global_deploy.and_then(|d| d.command.clone()));
+                let container_args = Some(args.container_args.clone())
+                    .filter(|a| !a.is_empty())
+                    .or_else(|| toml_env_deploy.and_then(|d| d.args.clone()))
+                    .or_else(|| toml_global_deploy.and_then(|d| d.args.clone()));
+                let ingress_annotations = rise_toml::merge_metadata_maps(
+                    toml_global_deploy
+                        .and_then(|d| d.ingress.as_ref())
+                        .and_then(|i| i.annotations.as_ref()),
+                    toml_env_deploy
+                        .and_then(|d| d.ingress.as_ref())
+                        .and_then(|i| i.annotations.as_ref()),
+                );
+                let strategy = toml_env_deploy
+                    .and_then(|d| d.strategy)
+                    .or_else(|| toml_global_deploy.and_then(|d| d.strategy));
+                let drain_period = toml_env_deploy
+                    .and_then(|d| d.drain_period.clone())
+                    .or_else(|| toml_global_deploy.and_then(|d| d.drain_period.clone()));
+                let volume = toml_env_deploy
+                    .and_then(|d| d.volume.clone())
+                    .or_else(|| toml_global_deploy.and_then(|d| d.volume.clone()));
+                let scheduling = toml_env_deploy
+                    .and_then(|d| d.scheduling.clone())
+                    .or_else(|| toml_global_deploy.and_then(|d| d.scheduling.clone()));
+                let image_pull_policy = toml_env_deploy
+                    .and_then(|d| d.image_pull_policy)
+                    .or_else(|| toml_global_deploy.and_then(|d| d.image_pull_policy));
+                let ratelimit = toml_env_deploy
+                    .and_then(|d| d.ratelimit.clone())
+                    .or_else(|| toml_global_deploy.and_then(|d| d.ratelimit.clone()));
+                let ports = toml_env_deploy
+                    .and_then(|d| d.ports.clone())
+                    .or_else(|| toml_global_deploy.and_then(|d| d.ports.clone()));
+                let service_account = toml_env_deploy
+                    .and_then(|d| d.service_account.clone())
+                    .or_else(|| toml_global_deploy.and_then(|d| d.service_account.clone()));
 
                 deployment::create_deployment(
                     &http_client,
@@ -1356,11 +1991,11 @@ async fn main() -> Result<()> {
                     deployment::DeploymentOptions {
                         project_name: &project_name,
                         path: &args.path,
-                        image: args.image.as_deref(),
-                        group: args.group.as_deref(),
+                        image: image.as_deref(),
+                        group: group.as_deref(),
                         environment: resolved_environment.as_deref(),
-                        expires_in: args.expire.as_deref(),
-                        http_port: args.http_port,
+                        expires_in: expire.as_deref(),
+                        http_port,
                         build_args: &args.build_args,
                         from_deployment: args.from.as_deref(),
                         use_source_env_vars: args.use_source_env_vars,
@@ -1368,10 +2003,30 @@ async fn main() -> Result<()> {
                         env_overrides,
                         job_url: args.job_url.clone(),
                         pull_request_url: args.pull_request_url.clone(),
+                        message: args.message.clone(),
                         toml_config,
                         replicas,
                         cpu,
                         memory,
+                        cpu_limit,
+                        memory_limit,
+                        health_check,
+                        init_container,
+                        protocol,
+                        labels,
+                        annotations,
+                        command,
+                        args: container_args,
+                        ingress_annotations,
+                        strategy,
+                        drain_period,
+                        volume,
+                        scheduling,
+                        image_pull_policy,
+                        ratelimit,
+                        ports,
+                        service_account,
+                        dry_run: args.dry_run,
                     },
                 )
                 .await?;
@@ -1381,6 +2036,7 @@ async fn main() -> Result<()> {
                 path,
                 group,
                 limit,
+                output,
             } => {
                 let project_name = resolve_project_name(project.clone(), path)?;
                 deployment::list_deployments(
@@ -1390,6 +2046,22 @@ async fn main() -> Result<()> {
                     &project_name,
                     group.as_deref(),
                     *limit,
+                    *output,
+                )
+                .await?;
+            }
+            DeploymentCommands::Watch {
+                project,
+                path,
+                interval,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::watch_deployments(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    interval,
                 )
                 .await?;
             }
@@ -1399,6 +2071,7 @@ async fn main() -> Result<()> {
                 deployment_id,
                 follow,
                 timeout,
+                json,
             } => {
                 let project_name = resolve_project_name(project.clone(), path)?;
                 deployment::show_deployment(
@@ -1409,6 +2082,7 @@ async fn main() -> Result<()> {
                     deployment_id,
                     *follow,
                     timeout,
+                    *json,
                 )
                 .await?;
             }
@@ -1416,6 +2090,7 @@ async fn main() -> Result<()> {
                 project,
                 path,
                 group,
+                all: _,
             } => {
                 let project_name = resolve_project_name(project.clone(), path)?;
                 deployment::stop_deployments_by_group(
@@ -1423,7 +2098,159 @@ async fn main() -> Result<()> {
                     &backend_url,
                     &config,
                     &project_name,
-                    group,
+                    group.as_deref(),
+                )
+                .await?;
+            }
+            DeploymentCommands::Diff {
+                project,
+                path,
+                from,
+                to,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::diff_deployments(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    from,
+                    to,
+                )
+                .await?;
+            }
+            DeploymentCommands::Rollback {
+                project,
+                path,
+                deployment_id,
+                previous: _,
+                group,
+                dry_run,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::rollback_deployment(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id.as_deref(),
+                    group.as_deref(),
+                    *dry_run,
+                )
+                .await?;
+            }
+            DeploymentCommands::Promote {
+                project,
+                path,
+                deployment_id,
+                to,
+                environment,
+                message,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::promote_deployment(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id,
+                    to,
+                    environment.as_deref(),
+                    message.as_deref(),
+                )
+                .await?;
+            }
+            DeploymentCommands::Scale {
+                project,
+                path,
+                deployment_id,
+                replicas,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::scale_deployment(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id,
+                    *replicas,
+                )
+                .await?;
+            }
+            DeploymentCommands::Describe {
+                project,
+                path,
+                deployment_id,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::describe_deployment(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id,
+                )
+                .await?;
+            }
+            DeploymentCommands::History {
+                project,
+                path,
+                deployment_id,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::show_deployment_history(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id,
+                )
+                .await?;
+            }
+            DeploymentCommands::Cancel {
+                project,
+                path,
+                deployment_id,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::cancel_deployment(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id,
+                )
+                .await?;
+            }
+            DeploymentCommands::Restart {
+                project,
+                path,
+                deployment_id,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::restart_deployment(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id,
+                )
+                .await?;
+            }
+            DeploymentCommands::Renew {
+                project,
+                path,
+                deployment_id,
+                expire,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                deployment::renew_deployment(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    deployment_id,
+                    expire,
                 )
                 .await?;
             }
@@ -1435,6 +2262,10 @@ async fn main() -> Result<()> {
                 tail,
                 timestamps,
                 since,
+                all_pods,
+                download,
+                grep,
+                invert_match,
             } => {
                 let project_name = resolve_project_name(project.clone(), path)?;
                 let token = config.get_token().ok_or_else(|| {
@@ -1451,6 +2282,10 @@ async fn main() -> Result<()> {
                         tail: *tail,
                         timestamps: *timestamps,
                         since: since.as_deref(),
+                        all_pods: *all_pods,
+                        download: download.as_deref(),
+                        grep: grep.as_deref(),
+                        invert_match: *invert_match,
                     },
                 )
                 .await?;
@@ -1526,6 +2361,21 @@ async fn main() -> Result<()> {
                 )
                 .await?;
             }
+            ServiceAccountCommands::TestToken {
+                project,
+                path,
+                token,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                service_account::test_token(
+                    &http_client,
+                    &backend_url,
+                    &config,
+                    &project_name,
+                    token.clone(),
+                )
+                .await?;
+            }
         },
         Commands::Environment(env_cmd) => {
             environment::handle_environment_command(&http_client, &backend_url, &config, env_cmd)
@@ -1539,28 +2389,42 @@ async fn main() -> Result<()> {
                 EnvCommands::Set {
                     project,
                     path,
-                    key,
-                    value,
+                    vars,
                     secret,
                     protected,
                     environment,
                 } => {
+                    let assignments = env::parse_env_assignments(vars)?;
                     let project_name = resolve_project_name(project.clone(), path)?;
                     // Protected defaults to true for secrets, false for non-secrets
                     // Can be explicitly overridden with --protected flag
                     let is_protected = protected.unwrap_or(*secret);
-                    env::set_env(
-                        &http_client,
-                        &backend_url,
-                        &token,
-                        &project_name,
-                        key,
-                        value,
-                        *secret,
-                        is_protected,
-                        environment.as_deref(),
-                    )
-                    .await?;
+                    if let [(key, value)] = assignments.as_slice() {
+                        env::set_env(
+                            &http_client,
+                            &backend_url,
+                            &token,
+                            &project_name,
+                            key,
+                            value,
+                            *secret,
+                            is_protected,
+                            environment.as_deref(),
+                        )
+                        .await?;
+                    } else {
+                        env::set_env_many(
+                            &http_client,
+                            &backend_url,
+                            &token,
+                            &project_name,
+                            &assignments,
+                            *secret,
+                            is_protected,
+                            environment.as_deref(),
+                        )
+                        .await?;
+                    }
                 }
                 EnvCommands::List {
                     project,
@@ -1632,6 +2496,9 @@ async fn main() -> Result<()> {
                     project,
                     path,
                     environment,
+                    format,
+                    file,
+                    reveal,
                 } => {
                     let toml_config = build::config::load_full_project_config(path)?;
                     let project_name = resolve_project_name_with_config(
@@ -1639,14 +2506,23 @@ async fn main() -> Result<()> {
                         path,
                         toml_config.as_ref(),
                     )?;
-                    let resolved_env =
-                        resolve_environment(environment.clone(), toml_config.as_ref());
+                    // The import format exports one scope as stored, so only an explicit
+                    // environment selects it
+                    let resolved_env = match format {
+                        env::ExportFormat::Dotenv => {
+                            resolve_environment(environment.clone(), toml_config.as_ref())
+                        }
+                        env::ExportFormat::Import => environment.clone(),
+                    };
                     env::export_env(
                         &http_client,
                         &backend_url,
                         &token,
                         &project_name,
                         resolved_env.as_deref(),
+                        *format,
+                        file.as_ref(),
+                        *reveal,
                     )
                     .await?;
                 }
@@ -1665,6 +2541,21 @@ async fn main() -> Result<()> {
                     )
                     .await?;
                 }
+                EnvCommands::Diff {
+                    project,
+                    path,
+                    deployment_id,
+                } => {
+                    let project_name = resolve_project_name(project.clone(), path)?;
+                    env::diff_deployment_env(
+                        &http_client,
+                        &backend_url,
+                        &token,
+                        &project_name,
+                        deployment_id,
+                    )
+                    .await?;
+                }
             }
         }
         Commands::Domain(domain_cmd) => {
@@ -1700,6 +2591,15 @@ async fn main() -> Result<()> {
                     )
                     .await?;
                 }
+                DomainCommands::Check {
+                    project,
+                    path,
+                    domain,
+                } => {
+                    let project_name = resolve_project_name(project.clone(), path)?;
+                    domain::check_domain(&http_client, &backend_url, &token, &project_name, domain)
+                        .await?;
+                }
             }
         }
         Commands::Extension(extension_cmd) => match extension_cmd {
@@ -1749,6 +2649,15 @@ async fn main() -> Result<()> {
                 let project_name = resolve_project_name(project.clone(), path)?;
                 extension::show_extension(&project_name, extension).await?;
             }
+            ExtensionCommands::Logs {
+                project,
+                path,
+                extension,
+                tail,
+            } => {
+                let project_name = resolve_project_name(project.clone(), path)?;
+                extension::extension_logs(&project_name, extension, *tail).await?;
+            }
             ExtensionCommands::Delete {
                 project,
                 path,
@@ -1757,6 +2666,29 @@ async fn main() -> Result<()> {
                 let project_name = resolve_project_name(project.clone(), path)?;
                 extension::delete_extension(&project_name, extension).await?;
             }
+            ExtensionCommands::Validate {
+                r#type,
+                spec,
+                spec_file,
+                schema,
+            } => {
+                let spec = match (spec, spec_file) {
+                    (Some(spec), _) => Some(spec.clone()),
+                    (None, Some(spec_file)) => {
+                        Some(std::fs::read_to_string(spec_file).with_context(|| {
+                            format!("Failed to read spec file {}", spec_file.display())
+                        })?)
+                    }
+                    (None, None) => None,
+                };
+                if spec.is_none() && !schema {
+                    anyhow::bail!("Pass --spec or --spec-file to validate, or --schema");
+                }
+                let spec: Option<serde_json::Value> = spec
+                    .map(|spec| serde_json::from_str(&spec).context("Failed to parse spec as JSON"))
+                    .transpose()?;
+                extension::validate_extension(r#type, spec, *schema).await?;
+            }
         },
         Commands::Build {
             tag,
@@ -1783,6 +2715,7 @@ async fn main() -> Result<()> {
             http_port,
             expose,
             run_env,
+            watch,
             build_args,
         } => {
             let expose_port = expose.unwrap_or(*http_port);
@@ -1803,10 +2736,30 @@ async fn main() -> Result<()> {
                     expose: expose_port,
                     run_env,
                     build_args,
+                    watch: *watch,
                 },
             )
             .await?;
         }
+        Commands::DevOidc {
+            port,
+            bind,
+            issuer_url,
+            claims,
+            expires_in,
+        } => {
+            dev_oidc_issuer::run_dev_oidc_issuer(
+                bind,
+                *port,
+                issuer_url.clone(),
+                claims,
+                *expires_in,
+            )
+            .await?;
+        }
+        Commands::Whoami => {
+            whoami::whoami(&http_client, &backend_url, &config).await?;
+        }
         Commands::Deploy { .. } => {
             // Already transformed to Deployment(Create) above
             unreachable!("Deploy command should have been transformed to Deployment(Create)")
@@ -51,14 +51,440 @@ pub struct EnvironmentConfig {
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
 #[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
 pub struct DeployConfig {
+    /// Pre-built image to deploy instead of building (e.g., "nginx:latest"); requires `http_port`
+    pub image: Option<String>,
+
+    /// HTTP port the app listens on (e.g., 3000)
+    pub http_port: Option<u16>,
+
+    /// Deployment group (e.g., "default", "mr/27")
+    pub group: Option<String>,
+
+    /// Expiration duration after which the deployment is cleaned up (e.g., "7d", "2h", "30m")
+    pub expire: Option<String>,
+
     /// Number of replicas
     pub replicas: Option<u32>,
 
-    /// CPU allocation (e.g., "500m", "1") — sets both K8s request and limit
+    /// CPU allocation (e.g., "500m", "1") — the K8s request, and the limit unless `cpu_limit` is set
     pub cpu: Option<String>,
 
-    /// Memory allocation (e.g., "256Mi", "1Gi") — sets both K8s request and limit
+    /// Memory allocation (e.g., "256Mi", "1Gi") — the K8s request, and the limit unless `memory_limit` is set
     pub memory: Option<String>,
+
+    /// CPU limit (e.g., "1"); must be at least `cpu`
+    pub cpu_limit: Option<String>,
+
+    /// Memory limit (e.g., "512Mi"); must be at least `memory`
+    pub memory_limit: Option<String>,
+
+    /// HTTP health probe overrides (defaults come from the backend configuration)
+    #[serde(alias = "healthcheck")]
+    pub health_check: Option<HealthCheckConfig>,
+
+    /// Container that must run to completion before the app starts (e.g., database migrations)
+    pub init: Option<InitContainerConfig>,
+
+    /// Protocol the app speaks on its port (default: http). TCP apps get no Ingress.
+    pub protocol: Option<DeploymentProtocol>,
+
+    /// Extra labels for the app's pods (e.g., cost allocation). `rise.dev/*` keys are reserved.
+    pub labels: Option<BTreeMap<String, String>>,
+
+    /// Extra annotations for the app's pods. `rise.dev/*` keys are reserved.
+    pub annotations: Option<BTreeMap<String, String>>,
+
+    /// Override for the app container's command, i.e. the image's entrypoint
+    /// (e.g., ["docker-entrypoint.sh"])
+    pub command: Option<Vec<String>>,
+
+    /// Override for the app container's arguments, i.e. the image's cmd
+    /// (e.g., ["postgres", "-c", "max_connections=200"])
+    pub args: Option<Vec<String>>,
+
+    /// Ingress settings for the app (HTTP apps only)
+    pub ingress: Option<IngressConfig>,
+
+    /// How traffic moves from the previous deployment to this one (default: rolling)
+    pub strategy: Option<DeploymentStrategy>,
+
+    /// How long the previous deployment keeps running after a blue-green switch
+    /// (e.g., "10m", "1h"; default: 5m). Only valid with `strategy = "blue-green"`.
+    pub drain_period: Option<String>,
+
+    /// Persistent volume mounted into the app container. Requires `replicas = 1`.
+    pub volume: Option<VolumeConfig>,
+
+    /// How replicas are spread across nodes or zones. Only applies with `replicas > 1`.
+    pub scheduling: Option<SchedulingConfig>,
+
+    /// When nodes pull the image (default: if-not-present for digest-pinned images,
+    /// always otherwise)
+    pub image_pull_policy: Option<ImagePullPolicy>,
+
+    /// Per-client request rate limits enforced by the ingress (HTTP apps only)
+    #[serde(alias = "rate_limit")]
+    pub ratelimit: Option<RateLimitConfig>,
+
+    /// Additional named ports the app listens on besides `http_port` (e.g., for metrics),
+    /// keyed by port name
+    pub ports: Option<BTreeMap<String, PortConfig>>,
+
+    /// Kubernetes ServiceAccount the app's pods run as (e.g., for IRSA or GKE
+    /// Workload Identity). Defaults to the environment's ServiceAccount.
+    pub service_account: Option<ServiceAccountConfig>,
+}
+
+/// Ingress settings for a deployment
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct IngressConfig {
+    /// Extra annotations for the app's ingresses (e.g., nginx `proxy-body-size`).
+    /// Only keys allowed by the backend's `allowed_ingress_annotations` are accepted.
+    pub annotations: Option<BTreeMap<String, String>>,
+}
+
+/// Request rate limits enforced by ingress-nginx, per client IP
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct RateLimitConfig {
+    /// Requests per second a client may make (1-100000)
+    pub rps: Option<u32>,
+
+    /// Burst size as a multiple of `rps` (1-100; ingress-nginx default: 5). Requires `rps`.
+    pub burst: Option<u32>,
+
+    /// Concurrent connections a client may open (1-100000)
+    pub connections: Option<u32>,
+}
+
+/// An additional container port declared in `[deploy.ports]`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct PortConfig {
+    /// Port number the app listens on (1-65535); must differ from `http_port`
+    pub port: u16,
+
+    /// Also expose the port on the deployment group's Service (default: false)
+    #[serde(default)]
+    pub service: bool,
+}
+
+/// The Kubernetes ServiceAccount declared in `[deploy.service_account]`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct ServiceAccountConfig {
+    /// Name of the ServiceAccount in the project's namespace (a DNS subdomain name)
+    pub name: String,
+}
+
+/// Merge `[deploy.labels]` / `[deploy.annotations]` maps, with environment entries
+/// overriding global ones key by key. Returns None if neither level sets the map.
+pub fn merge_metadata_maps(
+    global: Option<&BTreeMap<String, String>>,
+    env: Option<&BTreeMap<String, String>>,
+) -> Option<BTreeMap<String, String>> {
+    match (global, env) {
+        (None, None) => None,
+        (global, env) => {
+            let mut merged = global.cloned().unwrap_or_default();
+            merged.extend(
+                env.into_iter()
+                    .flatten()
+                    .map(|(k, v)| (k.clone(), v.clone())),
+            );
+            Some(merged)
+        }
+    }
+}
+
+/// Parse an expiration duration string (e.g., "7d", "2h", "30m")
+///
+/// Used by the backend for `expires_in` and by the CLI to validate `[deploy] expire`.
+pub fn parse_expiration_duration(expires_in: &str) -> Result<chrono::Duration, String> {
+    let s = expires_in.trim();
+    let (num_str, unit) = if let Some(num_str) = s.strip_suffix('d') {
+        (num_str, "d")
+    } else if let Some(num_str) = s.strip_suffix('h') {
+        (num_str, "h")
+    } else if let Some(num_str) = s.strip_suffix('m') {
+        (num_str, "m")
+    } else {
+        return Err("Duration must end with d, h, or m".to_string());
+    };
+
+    let num: i64 = num_str
+        .parse()
+        .map_err(|_| "Invalid number in duration".to_string())?;
+
+    if num <= 0 {
+        return Err("Duration must be positive".to_string());
+    }
+
+    let duration = match unit {
+        "d" => chrono::Duration::days(num),
+        "h" => chrono::Duration::hours(num),
+        "m" => chrono::Duration::minutes(num),
+        _ => return Err("Invalid duration unit".to_string()),
+    };
+
+    Ok(duration)
+}
+
+/// Protocol a deployment's app speaks on its port
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DeploymentProtocol {
+    /// HTTP app, exposed through an Ingress
+    #[default]
+    Http,
+    /// Plain TCP app (databases, gRPC without HTTP routing), only reachable via its Service
+    Tcp,
+}
+
+#[cfg(feature = "backend")]
+impl DeploymentProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeploymentProtocol::Http => "http",
+            DeploymentProtocol::Tcp => "tcp",
+        }
+    }
+
+    /// Parse the stored protocol, treating unknown values as HTTP
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "tcp" => DeploymentProtocol::Tcp,
+            _ => DeploymentProtocol::Http,
+        }
+    }
+}
+
+/// How a deployment replaces the active deployment of its group
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum DeploymentStrategy {
+    /// Switch traffic once the new deployment is healthy and stop the previous one right away
+    #[default]
+    Rolling,
+    /// Switch traffic once the new deployment is healthy and keep the previous one
+    /// running, without traffic, for a drain period
+    BlueGreen,
+}
+
+#[cfg(feature = "backend")]
+impl DeploymentStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeploymentStrategy::Rolling => "rolling",
+            DeploymentStrategy::BlueGreen => "blue-green",
+        }
+    }
+
+    /// Parse the stored strategy, treating unknown values as rolling
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "blue-green" => DeploymentStrategy::BlueGreen,
+            _ => DeploymentStrategy::Rolling,
+        }
+    }
+}
+
+/// Init container run before the app container starts
+///
+/// It receives the same environment variables as the app. The deployment fails if it
+/// keeps failing.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct InitContainerConfig {
+    /// Command and arguments to run (e.g., ["./manage.py", "migrate"])
+    pub command: Vec<String>,
+
+    /// Image to run (defaults to the deployment's image)
+    pub image: Option<String>,
+}
+
+/// Persistent volume for a deployment group
+///
+/// Backed by a `ReadWriteOnce` PersistentVolumeClaim that is kept across redeploys
+/// of the group and only deleted together with the project.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct VolumeConfig {
+    /// Requested storage size (e.g., "1Gi")
+    pub size: String,
+
+    /// Storage class of the claim (defaults to the cluster's default storage class)
+    pub storage_class: Option<String>,
+
+    /// Absolute path the volume is mounted at in the container (e.g., "/data")
+    pub mount_path: String,
+}
+
+/// Spreading of a deployment's replicas across failure domains
+///
+/// Without `anti_affinity`, replicas are balanced with a topology spread constraint
+/// that still schedules pods when the cluster cannot satisfy it.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct SchedulingConfig {
+    /// Failure domain to spread replicas across (default: node)
+    pub spread_across: Option<SpreadAcross>,
+
+    /// Keep replicas apart with pod anti-affinity instead: `preferred` avoids sharing
+    /// a node/zone where possible, `required` leaves replicas pending rather than share one
+    pub anti_affinity: Option<AntiAffinity>,
+}
+
+/// Failure domain replicas are spread across
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum SpreadAcross {
+    /// Spread across nodes (`kubernetes.io/hostname`)
+    #[default]
+    Node,
+    /// Spread across zones (`topology.kubernetes.io/zone`)
+    Zone,
+}
+
+/// How strictly replicas avoid sharing a node or zone
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum AntiAffinity {
+    /// Avoid sharing where possible, but still schedule replicas if it can't be avoided
+    Preferred,
+    /// Never schedule two replicas on the same node or zone
+    Required,
+}
+
+/// When a node pulls a deployment's image
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum ImagePullPolicy {
+    /// Pull on every container start, picking up a tag that was pushed again
+    Always,
+    /// Reuse an image already present on the node
+    IfNotPresent,
+}
+
+#[cfg(feature = "backend")]
+impl ImagePullPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImagePullPolicy::Always => "always",
+            ImagePullPolicy::IfNotPresent => "if-not-present",
+        }
+    }
+
+    /// Parse a stored policy; unknown values yield None (derived default)
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(ImagePullPolicy::Always),
+            "if-not-present" => Some(ImagePullPolicy::IfNotPresent),
+            _ => None,
+        }
+    }
+
+    /// Kubernetes `imagePullPolicy` value
+    pub fn kubernetes_value(&self) -> &'static str {
+        match self {
+            ImagePullPolicy::Always => "Always",
+            ImagePullPolicy::IfNotPresent => "IfNotPresent",
+        }
+    }
+}
+
+/// HTTP health probe configuration for a deployment
+///
+/// Unset fields fall back to the deployment controller's configured defaults.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
+pub struct HealthCheckConfig {
+    /// Path that must respond successfully before the deployment receives traffic
+    pub readiness_path: Option<String>,
+
+    /// Path that must keep responding successfully, or the container is restarted
+    pub liveness_path: Option<String>,
+
+    /// Seconds to wait after the container starts before probing
+    pub initial_delay_seconds: Option<i32>,
+
+    /// Seconds between probes
+    pub period_seconds: Option<i32>,
+
+    /// Path of the startup probe (default: the liveness path). Setting any `startup_*`
+    /// field adds a startup probe; liveness and readiness probes only begin once it
+    /// succeeds. The app gets `startup_failure_threshold * startup_period_seconds`
+    /// seconds to start (default: 30 * 10s = 5 minutes) before it is restarted.
+    pub startup_path: Option<String>,
+
+    /// Failed startup probes tolerated before the container is restarted (default: 30)
+    pub startup_failure_threshold: Option<i32>,
+
+    /// Seconds between startup probes (default: 10)
+    pub startup_period_seconds: Option<i32>,
+
+    /// Path the controller requests from every ready pod, on top of the Kubernetes
+    /// probes. Unset keeps the default: health is pod readiness only.
+    pub path: Option<String>,
+
+    /// Status codes `path` must return (default: any 2xx)
+    pub expected_status: Option<Vec<u16>>,
+
+    /// Seconds to wait for a response from `path` (default and maximum: 5)
+    pub timeout: Option<u64>,
+}
+
+impl HealthCheckConfig {
+    /// Fill fields unset in `self` from `fallback`
+    pub fn or(self, fallback: &HealthCheckConfig) -> HealthCheckConfig {
+        HealthCheckConfig {
+            readiness_path: self
+                .readiness_path
+                .or_else(|| fallback.readiness_path.clone()),
+            liveness_path: self
+                .liveness_path
+                .or_else(|| fallback.liveness_path.clone()),
+            initial_delay_seconds: self
+                .initial_delay_seconds
+                .or(fallback.initial_delay_seconds),
+            period_seconds: self.period_seconds.or(fallback.period_seconds),
+            startup_path: self.startup_path.or_else(|| fallback.startup_path.clone()),
+            startup_failure_threshold: self
+                .startup_failure_threshold
+                .or(fallback.startup_failure_threshold),
+            startup_period_seconds: self
+                .startup_period_seconds
+                .or(fallback.startup_period_seconds),
+            path: self.path.or_else(|| fallback.path.clone()),
+            expected_status: self
+                .expected_status
+                .or_else(|| fallback.expected_status.clone()),
+            timeout: self.timeout.or(fallback.timeout),
+        }
+    }
+
+    /// Whether any `startup_*` field is set, which enables the startup probe
+    #[cfg(feature = "backend")]
+    pub fn has_startup_probe(&self) -> bool {
+        self.startup_path.is_some()
+            || self.startup_failure_threshold.is_some()
+            || self.startup_period_seconds.is_some()
+    }
+
+    /// Whether `status` counts as healthy for the controller's HTTP check
+    #[cfg(feature = "backend")]
+    pub fn is_expected_status(&self, status: u16) -> bool {
+        match self.expected_status {
+            Some(ref expected) => expected.contains(&status),
+            None => (200..300).contains(&status),
+        }
+    }
 }
 
 /// Project metadata configuration
@@ -77,7 +503,7 @@ pub struct ProjectConfig {
 #[derive(Debug, Deserialize, Serialize, Default)]
 #[cfg_attr(feature = "backend", derive(schemars::JsonSchema))]
 pub struct BuildConfig {
-    /// Build backend (docker, docker:build, docker:buildx, buildctl, pack, railpack[:buildx], railpack:buildctl)
+    /// Build backend (docker, docker:build, docker:buildx, buildctl, pack, nixpacks, railpack[:buildx], railpack:buildctl)
     pub backend: Option<String>,
 
     /// Buildpack builder to use (only for pack backend)
@@ -1,4 +1,4 @@
-use crate::db::models::User;
+use crate::db::models::{ServiceAccount, User};
 use crate::db::service_accounts;
 use crate::server::auth::jwt::JwtValidator;
 use crate::server::error::{ServerError, ServerErrorExt};
@@ -77,23 +77,8 @@ impl AuthContext {
                 // Try each SA's expected claims against the token
                 let mut matching_sas = Vec::new();
                 let mut last_error = None;
-                for sa in &service_accounts {
-                    let expected_claims: HashMap<String, String> =
-                        match serde_json::from_value(sa.claims.clone()) {
-                            Ok(claims) => claims,
-                            Err(e) => {
-                                tracing::error!(
-                                    "Failed to deserialize claims for service account {}: {}",
-                                    sa.id,
-                                    e
-                                );
-                                return Err(ServerError::internal(
-                                    "Invalid service account claims configuration",
-                                ));
-                            }
-                        };
-
-                    match JwtValidator::validate_custom_claims(&token.claims, &expected_claims) {
+                for (sa, result) in match_service_accounts(&token.claims, &service_accounts)? {
+                    match result {
                         Ok(()) => {
                             matching_sas.push(sa);
                         }
@@ -158,6 +143,34 @@ impl AuthContext {
     }
 }
 
+/// Match a token's claims against the expected claims of each service account.
+///
+/// Returns the outcome for every service account, in order. Fails closed if a
+/// service account's stored claims are malformed.
+pub fn match_service_accounts<'a>(
+    token_claims: &serde_json::Value,
+    service_accounts: &'a [ServiceAccount],
+) -> Result<Vec<(&'a ServiceAccount, anyhow::Result<()>)>, ServerError> {
+    service_accounts
+        .iter()
+        .map(|sa| {
+            let expected_claims: HashMap<String, String> =
+                serde_json::from_value(sa.claims.clone()).map_err(|e| {
+                    tracing::error!(
+                        "Failed to deserialize claims for service account {}: {}",
+                        sa.id,
+                        e
+                    );
+                    ServerError::internal("Invalid service account claims configuration")
+                })?;
+            Ok((
+                sa,
+                JwtValidator::validate_custom_claims(token_claims, &expected_claims),
+            ))
+        })
+        .collect()
+}
+
 impl FromRequestParts<AppState> for AuthContext {
     type Rejection = ServerError;
 
@@ -295,6 +308,36 @@ mod tests {
         assert!(err.message.contains("Invalid service account claims"));
     }
 
+    #[test]
+    fn test_match_service_accounts_reports_each_outcome() {
+        let sa = |claims: serde_json::Value| ServiceAccount {
+            id: uuid::Uuid::new_v4(),
+            project_id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            issuer_url: "https://gitlab.com".to_string(),
+            claims,
+            sequence: 1,
+            allowed_environment_ids: None,
+            deleted_at: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let sas = vec![
+            sa(serde_json::json!({"project_path": "myorg/*"})),
+            sa(serde_json::json!({"project_path": "otherorg/app"})),
+        ];
+        let token_claims = serde_json::json!({"project_path": "myorg/app"});
+
+        let results = match_service_accounts(&token_claims, &sas).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.is_ok());
+        let err = results[1].1.as_ref().unwrap_err().to_string();
+        assert!(
+            err.contains("expected 'otherorg/app', got 'myorg/app'"),
+            "{err}"
+        );
+    }
+
     #[sqlx::test]
     async fn test_resolve_rise_jwt_returns_user(pool: PgPool) {
         let user = users::create(&pool, "regular@example.com").await.unwrap();
@@ -1,6 +1,7 @@
 use crate::db::{projects, users};
 use crate::server::auth::{
     cookie_helpers::{self, CookieSettings},
+    issuers::{LoginIssuer, LoginIssuerInfo},
     token_storage::{
         generate_code_challenge, generate_code_verifier, generate_state_token,
         CompletedAuthSession, OAuth2State,
@@ -185,27 +186,42 @@ fn validate_redirect_url(redirect_url: &str, public_url: &str) -> String {
     SAFE_FALLBACK.to_string()
 }
 
+/// Look up the issuer a CLI login request selected (`None` selects the default issuer)
+fn login_issuer<'a>(
+    state: &'a AppState,
+    name: Option<&str>,
+) -> Result<&'a LoginIssuer, (StatusCode, String)> {
+    state.login_issuers.get(name).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Unknown issuer '{}'", name.unwrap_or_default()),
+        )
+    })
+}
+
 /// Helper function to sync IdP groups after login
 ///
-/// This validates the token and syncs the user's team memberships from IdP groups.
+/// This validates the token against the issuer that minted it and syncs the user's
+/// team memberships from IdP groups.
 /// Should be called during login flows (code exchange, device exchange, OAuth callback).
 async fn sync_groups_after_login(
     state: &AppState,
+    issuer: &LoginIssuer,
     id_token: &str,
 ) -> Result<(), (StatusCode, String)> {
-    // Only sync if enabled
-    if !state.auth_settings.idp_group_sync_enabled {
+    // Only sync if enabled, and only from issuers trusted with group claims
+    if !state.auth_settings.idp_group_sync_enabled || !issuer.sync_groups {
         return Ok(());
     }
 
     // Build expected claims for validation
     let mut expected_claims = HashMap::new();
-    expected_claims.insert("aud".to_string(), state.auth_settings.client_id.clone());
+    expected_claims.insert("aud".to_string(), issuer.client_id.clone());
 
     // Validate token to get claims
     let claims_value = state
         .jwt_validator
-        .validate(id_token, &state.auth_settings.issuer, &expected_claims)
+        .validate(id_token, &issuer.issuer, &expected_claims)
         .await
         .map_err(|e| {
             tracing::warn!("Failed to validate token for group sync: {:#}", e);
@@ -222,6 +238,13 @@ async fn sync_groups_after_login(
             )
         })?;
 
+    if !issuer.allows_email(&claims.email) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!("Issuer '{}' may not log in {}", issuer.name, claims.email),
+        ));
+    }
+
     // Get or create user
     let user = users::find_or_create(&state.db_pool, &claims.email)
         .await
@@ -261,11 +284,17 @@ pub struct CodeExchangeRequest {
     pub code: String,
     pub code_verifier: String,
     pub redirect_uri: String,
+    /// Name of the issuer the login was started with (default issuer if not set)
+    #[serde(default)]
+    pub issuer: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct DeviceExchangeRequest {
     pub device_code: String,
+    /// Name of the issuer the login was started with (default issuer if not set)
+    #[serde(default)]
+    pub issuer: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -296,6 +325,9 @@ pub struct AuthorizeRequest {
     pub code_challenge_method: Option<String>,
     /// Flow type: "code" for authorization code flow, "device" for device flow
     pub flow: String,
+    /// Name of the issuer to log in with (default issuer if not set)
+    #[serde(default)]
+    pub issuer: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -329,6 +361,8 @@ pub async fn authorize(
     State(state): State<AppState>,
     Json(payload): Json<AuthorizeRequest>,
 ) -> Result<Json<AuthorizeResponse>, (StatusCode, String)> {
+    let issuer = login_issuer(&state, payload.issuer.as_deref())?;
+
     match payload.flow.as_str() {
         "code" => {
             // Authorization code flow with PKCE
@@ -353,7 +387,7 @@ pub async fn authorize(
 
             // Build authorization URL with typed parameters
             let params = crate::server::auth::oauth::AuthorizeParams {
-                client_id: &state.auth_settings.client_id,
+                client_id: &issuer.client_id,
                 redirect_uri: &redirect_uri,
                 response_type: "code",
                 scope: "openid email profile offline_access",
@@ -362,7 +396,7 @@ pub async fn authorize(
                 state: None,
             };
 
-            let authorization_url = state.oauth_client.build_authorize_url(&params);
+            let authorization_url = issuer.oauth_client.build_authorize_url(&params);
 
             Ok(Json(AuthorizeResponse {
                 authorization_url: Some(authorization_url),
@@ -376,7 +410,7 @@ pub async fn authorize(
         }
         "device" => {
             // Device authorization flow
-            let device_response = state.oauth_client.device_flow_start().await.map_err(|e| {
+            let device_response = issuer.oauth_client.device_flow_start().await.map_err(|e| {
                 tracing::error!("Failed to start device flow: {:#}", e);
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -412,8 +446,10 @@ pub async fn code_exchange(
         payload.redirect_uri
     );
 
+    let issuer = login_issuer(&state, payload.issuer.as_deref())?;
+
     // Exchange authorization code for tokens using PKCE
-    let token_info = state
+    let token_info = issuer
         .oauth_client
         .exchange_code_pkce(&payload.code, &payload.code_verifier, &payload.redirect_uri)
         .await
@@ -447,22 +483,18 @@ pub async fn code_exchange(
     }
 
     // Sync IdP groups after successful login
-    if let Err(e) = sync_groups_after_login(&state, &token_info.id_token).await {
+    if let Err(e) = sync_groups_after_login(&state, issuer, &token_info.id_token).await {
         tracing::warn!("Group sync failed during code exchange: {:?}", e);
         // Don't fail the login if group sync fails
     }
 
-    // Validate the IdP JWT to extract claims
+    // Validate the IdP JWT against the issuer that minted it to extract claims
     let mut expected_claims = HashMap::new();
-    expected_claims.insert("aud".to_string(), state.auth_settings.client_id.clone());
+    expected_claims.insert("aud".to_string(), issuer.client_id.clone());
 
     let claims = state
         .jwt_validator
-        .validate(
-            &token_info.id_token,
-            &state.auth_settings.issuer,
-            &expected_claims,
-        )
+        .validate(&token_info.id_token, &issuer.issuer, &expected_claims)
         .await
         .map_err(|e| {
             tracing::error!("Failed to validate ID token: {:#}", e);
@@ -475,6 +507,19 @@ pub async fn code_exchange(
         .and_then(|v| v.as_str())
         .ok_or_else(|| (StatusCode::BAD_REQUEST, "Email claim missing".to_string()))?;
 
+    // Secondary issuers may only log in users of their own email domains
+    if !issuer.allows_email(email) {
+        tracing::warn!(
+            "Issuer '{}' rejected for {}: email domain not allowed",
+            issuer.name,
+            email
+        );
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!("Issuer '{}' may not log in {}", issuer.name, email),
+        ));
+    }
+
     // Find or create user
     let user = users::find_or_create(&state.db_pool, email)
         .await
@@ -500,8 +545,9 @@ pub async fn code_exchange(
         })?;
 
     tracing::info!(
-        "CLI login successful for user {} - issued Rise JWT",
-        user.email
+        "CLI login successful for user {} via issuer '{}' - issued Rise JWT",
+        user.email,
+        issuer.name
     );
 
     Ok(Json(LoginResponse { token: rise_jwt }))
@@ -518,8 +564,19 @@ pub async fn device_exchange(
         &payload.device_code[..8.min(payload.device_code.len())]
     );
 
+    let Ok(issuer) = login_issuer(&state, payload.issuer.as_deref()) else {
+        return Json(DeviceExchangeResponse {
+            token: None,
+            error: Some("invalid_request".to_string()),
+            error_description: Some(format!(
+                "Unknown issuer '{}'",
+                payload.issuer.unwrap_or_default()
+            )),
+        });
+    };
+
     // Poll the identity provider's token endpoint with the device code
-    match state
+    match issuer
         .oauth_client
         .device_flow_poll(&payload.device_code)
         .await
@@ -528,22 +585,18 @@ pub async fn device_exchange(
             tracing::info!("Device authorization successful");
 
             // Sync IdP groups after successful login
-            if let Err(e) = sync_groups_after_login(&state, &token_info.id_token).await {
+            if let Err(e) = sync_groups_after_login(&state, issuer, &token_info.id_token).await {
                 tracing::warn!("Group sync failed during device exchange: {:?}", e);
                 // Don't fail the login if group sync fails
             }
 
-            // Validate the IdP JWT to extract claims
+            // Validate the IdP JWT against the issuer that minted it to extract claims
             let mut expected_claims = HashMap::new();
-            expected_claims.insert("aud".to_string(), state.auth_settings.client_id.clone());
+            expected_claims.insert("aud".to_string(), issuer.client_id.clone());
 
             let claims = match state
                 .jwt_validator
-                .validate(
-                    &token_info.id_token,
-                    &state.auth_settings.issuer,
-                    &expected_claims,
-                )
+                .validate(&token_info.id_token, &issuer.issuer, &expected_claims)
                 .await
             {
                 Ok(claims) => claims,
@@ -570,6 +623,23 @@ pub async fn device_exchange(
                 }
             };
 
+            // Secondary issuers may only log in users of their own email domains
+            if !issuer.allows_email(email) {
+                tracing::warn!(
+                    "Issuer '{}' rejected for {}: email domain not allowed",
+                    issuer.name,
+                    email
+                );
+                return Json(DeviceExchangeResponse {
+                    token: None,
+                    error: Some("access_denied".to_string()),
+                    error_description: Some(format!(
+                        "Issuer '{}' may not log in {}",
+                        issuer.name, email
+                    )),
+                });
+            }
+
             // Find or create user
             let user = match users::find_or_create(&state.db_pool, email).await {
                 Ok(user) => user,
@@ -601,8 +671,9 @@ pub async fn device_exchange(
             };
 
             tracing::info!(
-                "CLI device login successful for user {} - issued Rise JWT",
-                user.email
+                "CLI device login successful for user {} via issuer '{}' - issued Rise JWT",
+                user.email,
+                issuer.name
             );
 
             Json(DeviceExchangeResponse {
@@ -1238,7 +1309,13 @@ pub async fn oauth_callback(
     tracing::info!("Successfully exchanged code for tokens");
 
     // Sync IdP groups after successful login
-    if let Err(e) = sync_groups_after_login(&state, &token_info.id_token).await {
+    if let Err(e) = sync_groups_after_login(
+        &state,
+        state.login_issuers.default_issuer(),
+        &token_info.id_token,
+    )
+    .await
+    {
         tracing::warn!("Group sync failed during OAuth callback: {:?}", e);
         // Don't fail the login if group sync fails
     }
@@ -1445,7 +1522,12 @@ pub async fn oauth_callback(
         })?;
 
     // Sync groups after login
-    sync_groups_after_login(&state, &token_info.id_token).await?;
+    sync_groups_after_login(
+        &state,
+        state.login_issuers.default_issuer(),
+        &token_info.id_token,
+    )
+    .await?;
 
     // Issue Rise HS256 JWT for user authentication (consumed by the UI)
     let rise_jwt = state
@@ -2041,6 +2123,21 @@ pub async fn jwks(
     Ok(Json(jwks))
 }
 
+#[derive(Debug, Serialize)]
+pub struct IssuersResponse {
+    pub issuers: Vec<LoginIssuerInfo>,
+}
+
+/// Issuer discovery endpoint
+///
+/// Lists the OIDC issuers users can log in with (`rise login --issuer <name>`).
+#[instrument(skip(state))]
+pub async fn list_issuers(State(state): State<AppState>) -> Json<IssuersResponse> {
+    Json(IssuersResponse {
+        issuers: state.login_issuers.infos(),
+    })
+}
+
 /// OpenID Connect Discovery endpoint
 ///
 /// Returns OpenID Provider metadata as per OpenID Connect Discovery 1.0.
@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::Arc;
+
+use super::oauth::OAuthClient;
+use crate::server::settings::AuthSettings;
+
+/// Name under which the top-level `auth` issuer is offered for login
+pub const DEFAULT_ISSUER_NAME: &str = "default";
+
+/// An OIDC provider users can log in with
+pub struct LoginIssuer {
+    /// Name used to select the issuer (`rise login --issuer <name>`)
+    pub name: String,
+    /// Human-readable name shown when listing issuers
+    pub display_name: Option<String>,
+    /// Issuer URL, expected as the `iss` claim of its ID tokens
+    pub issuer: String,
+    /// OAuth client ID, expected as the `aud` claim of its ID tokens
+    pub client_id: String,
+    pub oauth_client: Arc<OAuthClient>,
+    /// Email domains the issuer may log users in with; `None` allows any (default issuer)
+    pub allowed_email_domains: Option<Vec<String>>,
+    /// Whether IdP group sync trusts this issuer's `groups` claim
+    pub sync_groups: bool,
+}
+
+impl LoginIssuer {
+    /// Whether this issuer may log in the user with `email`
+    pub fn allows_email(&self, email: &str) -> bool {
+        email_domain_allowed(self.allowed_email_domains.as_deref(), email)
+    }
+}
+
+/// Whether `email` belongs to one of `domains` (case-insensitive); `None` allows any email
+fn email_domain_allowed(domains: Option<&[String]>, email: &str) -> bool {
+    let Some(domains) = domains else {
+        return true;
+    };
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !local.contains('@')
+        && domains
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(domain))
+}
+
+/// Issuer metadata returned by the issuer discovery endpoint
+#[derive(Debug, Serialize)]
+pub struct LoginIssuerInfo {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub issuer: String,
+    pub default: bool,
+}
+
+/// The issuers available for login: the default issuer from `auth` followed by the
+/// named issuers from `auth.issuers`, sorted by name
+pub struct LoginIssuers {
+    issuers: Vec<LoginIssuer>,
+}
+
+impl LoginIssuers {
+    /// Build the login issuers, reusing `default_client` for the default issuer
+    pub async fn new(auth: &AuthSettings, default_client: Arc<OAuthClient>) -> Result<Self> {
+        let mut issuers = vec![LoginIssuer {
+            name: DEFAULT_ISSUER_NAME.to_string(),
+            display_name: None,
+            issuer: auth.issuer.clone(),
+            client_id: auth.client_id.clone(),
+            oauth_client: default_client,
+            allowed_email_domains: None,
+            sync_groups: true,
+        }];
+
+        let mut names: Vec<&String> = auth.issuers.keys().collect();
+        names.sort();
+        for name in names {
+            let settings = &auth.issuers[name];
+            let oauth_client = OAuthClient::new(
+                settings.issuer.clone(),
+                settings.client_id.clone(),
+                settings.client_secret.clone(),
+                settings.authorize_url.clone(),
+                settings.token_url.clone(),
+            )
+            .await
+            .with_context(|| format!("Failed to initialize OAuth client for issuer '{}'", name))?;
+
+            tracing::info!("Initialized login issuer '{}' ({})", name, settings.issuer);
+            issuers.push(LoginIssuer {
+                name: name.clone(),
+                display_name: settings.display_name.clone(),
+                issuer: settings.issuer.clone(),
+                client_id: settings.client_id.clone(),
+                oauth_client: Arc::new(oauth_client),
+                allowed_email_domains: Some(settings.allowed_email_domains.clone()),
+                sync_groups: settings.sync_groups,
+            });
+        }
+
+        Ok(Self { issuers })
+    }
+
+    /// The issuer configured at the top level of `auth`
+    pub fn default_issuer(&self) -> &LoginIssuer {
+        &self.issuers[0]
+    }
+
+    /// Look up an issuer by name; `None` selects the default issuer
+    pub fn get(&self, name: Option<&str>) -> Option<&LoginIssuer> {
+        match name {
+            None => Some(self.default_issuer()),
+            Some(name) => self.issuers.iter().find(|issuer| issuer.name == name),
+        }
+    }
+
+    /// Metadata of all issuers, default first
+    pub fn infos(&self) -> Vec<LoginIssuerInfo> {
+        self.issuers
+            .iter()
+            .enumerate()
+            .map(|(i, issuer)| LoginIssuerInfo {
+                name: issuer.name.clone(),
+                display_name: issuer.display_name.clone(),
+                issuer: issuer.issuer.clone(),
+                default: i == 0,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secondary_issuer_cannot_claim_emails_outside_its_domains() {
+        let contractors = vec!["contractors.example.com".to_string()];
+        let allowed = |email: &str| email_domain_allowed(Some(&contractors), email);
+
+        assert!(allowed("jane@contractors.example.com"));
+        assert!(allowed("jane@Contractors.Example.com"));
+        // Accounts of the primary issuer (e.g. an admin) can't be taken over
+        assert!(!allowed("admin@example.com"));
+        assert!(!allowed("admin@corp.example.com"));
+        assert!(!allowed("admin@example.com@contractors.example.com"));
+        assert!(!allowed("jane@evil-contractors.example.com"));
+        assert!(!allowed("@contractors.example.com"));
+        assert!(!allowed("contractors.example.com"));
+
+        // The default issuer is not restricted
+        assert!(email_domain_allowed(None, "admin@example.com"));
+    }
+}
@@ -4,6 +4,7 @@ pub mod cookie_helpers;
 pub mod entra_sync;
 pub mod group_sync;
 pub mod handlers;
+pub mod issuers;
 pub mod jwt;
 pub mod jwt_signer;
 pub mod middleware;
@@ -18,6 +18,7 @@ pub fn public_routes() -> Router<AppState> {
         .route("/auth/logout", get(handlers::oauth_logout))
         .route("/auth/cli-success", get(handlers::cli_auth_success))
         .route("/auth/jwks", get(handlers::jwks))
+        .route("/auth/issuers", get(handlers::list_issuers))
 }
 
 /// Root-level well-known routes (must be at root per OIDC spec)
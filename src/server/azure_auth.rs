@@ -0,0 +1,157 @@
+//! Microsoft Entra ID (Azure AD) access tokens for server-side Azure integrations
+//!
+//! Shared by the Azure Container Registry provider and the Key Vault encryption provider.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Microsoft Entra ID endpoint for client credential token requests
+const LOGIN_URL: &str = "https://login.microsoftonline.com";
+
+/// Azure Instance Metadata Service token endpoint (managed identity)
+const IMDS_TOKEN_URL: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+
+/// Refresh cached access tokens this long before they expire
+const TOKEN_EXPIRY_BUFFER: Duration = Duration::from_secs(5 * 60);
+
+/// How access tokens are obtained
+pub enum AzureCredential {
+    /// Service principal using the client credentials flow
+    ServicePrincipal {
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+    },
+    /// Managed identity via the instance metadata service; `client_id` selects a
+    /// user-assigned identity
+    ManagedIdentity { client_id: Option<String> },
+}
+
+/// Mints and caches Entra ID access tokens for a single resource
+pub struct AzureTokenSource {
+    http_client: reqwest::Client,
+    credential: AzureCredential,
+    /// Resource the tokens are issued for (e.g., "https://vault.azure.net")
+    resource: &'static str,
+    /// Cached access token and the instant it expires
+    cached_token: RwLock<Option<(String, Instant)>>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    /// AAD returns a number, the metadata service a string
+    expires_in: ExpiresIn,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExpiresIn {
+    Number(u64),
+    String(String),
+}
+
+impl ExpiresIn {
+    fn seconds(&self) -> u64 {
+        match self {
+            ExpiresIn::Number(n) => *n,
+            ExpiresIn::String(s) => s.parse().unwrap_or(0),
+        }
+    }
+}
+
+impl AzureTokenSource {
+    pub fn new(credential: AzureCredential, resource: &'static str) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            credential,
+            resource,
+            cached_token: RwLock::new(None),
+        }
+    }
+
+    /// Get an access token, reusing the cached one until shortly before it expires
+    pub async fn access_token(&self) -> Result<String> {
+        {
+            let cache = self.cached_token.read().unwrap();
+            if let Some((token, expires_at)) = cache.as_ref() {
+                if expires_at.saturating_duration_since(Instant::now()) > TOKEN_EXPIRY_BUFFER {
+                    return Ok(token.clone());
+                }
+            }
+        }
+
+        let response = self.fetch_access_token().await?;
+        let expires_at = Instant::now() + Duration::from_secs(response.expires_in.seconds());
+        {
+            let mut cache = self.cached_token.write().unwrap();
+            *cache = Some((response.access_token.clone(), expires_at));
+        }
+
+        Ok(response.access_token)
+    }
+
+    async fn fetch_access_token(&self) -> Result<TokenResponse> {
+        tracing::debug!(resource = self.resource, "Fetching Azure AD access token");
+
+        let scope = format!("{}/.default", self.resource);
+        let request = match &self.credential {
+            AzureCredential::ServicePrincipal {
+                tenant_id,
+                client_id,
+                client_secret,
+            } => self
+                .http_client
+                .post(format!("{}/{}/oauth2/v2.0/token", LOGIN_URL, tenant_id))
+                .form(&[
+                    ("grant_type", "client_credentials"),
+                    ("client_id", client_id.as_str()),
+                    ("client_secret", client_secret.as_str()),
+                    ("scope", scope.as_str()),
+                ]),
+            AzureCredential::ManagedIdentity { client_id } => {
+                let mut query = vec![("api-version", "2018-02-01"), ("resource", self.resource)];
+                if let Some(client_id) = client_id {
+                    query.push(("client_id", client_id.as_str()));
+                }
+                let url = url::Url::parse_with_params(IMDS_TOKEN_URL, &query)
+                    .context("Failed to build managed identity token URL")?;
+                self.http_client.get(url).header("Metadata", "true")
+            }
+        };
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to reach Azure AD for an access token")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            bail!("Azure AD token request returned {}: {}", status, body);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse Azure AD token response")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_response_accepts_string_and_number_expiry() {
+        let aad: TokenResponse =
+            serde_json::from_str(r#"{"access_token":"a","expires_in":3599}"#).unwrap();
+        assert_eq!(aad.expires_in.seconds(), 3599);
+
+        let imds: TokenResponse =
+            serde_json::from_str(r#"{"access_token":"a","expires_in":"86399"}"#).unwrap();
+        assert_eq!(imds.expires_in.seconds(), 86399);
+    }
+}
@@ -0,0 +1,395 @@
+//! Diagnostics for custom domains.
+//!
+//! Runs DNS, TLS and HTTP checks for a custom domain from the backend's network
+//! perspective, so users can tell whether a broken domain is caused by DNS, a
+//! missing certificate, or routing.
+
+use std::collections::BTreeSet;
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use openssl::asn1::Asn1Time;
+use openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
+
+use super::models::DomainCheckItem;
+use super::validation;
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Certificates expiring sooner than this are reported as failing
+const MIN_CERTIFICATE_DAYS_REMAINING: i32 = 14;
+
+/// Certificate details relevant for diagnostics
+#[derive(Debug, Clone)]
+struct CertificateInfo {
+    dns_names: Vec<String>,
+    not_after: String,
+    days_remaining: i32,
+}
+
+/// Run all checks for `domain`.
+///
+/// `ingress_host` is the host the domain is expected to point at (the project's
+/// default ingress host). TLS and HTTP checks only run when the domain resolves to
+/// the same addresses as the ingress, so the backend never connects to arbitrary
+/// hosts on behalf of users. They connect to the matching address instead of
+/// resolving the domain again, so a DNS change between the checks cannot redirect
+/// them. Wildcard domains are checked via a host they cover.
+///
+/// Returns the checks and whether the domain points at the ingress.
+pub async fn check_domain(
+    domain: &str,
+    ingress_host: Option<&str>,
+) -> (Vec<DomainCheckItem>, bool) {
+    let host = validation::probe_host(domain);
+    let mut checks = Vec::new();
+
+    let Some(ingress_ip) = check_dns(&host, ingress_host, &mut checks).await else {
+        let reason = "Skipped because the domain does not point at the ingress";
+        checks.push(DomainCheckItem::skip("TLS certificate", reason));
+        checks.push(DomainCheckItem::skip("HTTP", reason));
+        return (checks, false);
+    };
+    let addr = SocketAddr::new(ingress_ip, 443);
+
+    let owned_host = host.clone();
+    let certificate = tokio::task::spawn_blocking(move || fetch_certificate(&owned_host, addr))
+        .await
+        .context("TLS check panicked")
+        .and_then(|result| result);
+    checks.push(match certificate {
+        Ok(info) => evaluate_certificate(&host, &info),
+        Err(e) => DomainCheckItem::fail("TLS certificate", format!("{:#}", e)),
+    });
+
+    checks.push(check_http(&host, addr).await);
+    (checks, true)
+}
+
+/// Verify that `domain` points at the ingress, via a CNAME or A/AAAA records
+pub async fn verify_dns(domain: &str, ingress_host: Option<&str>) -> bool {
+    check_dns(
+        &validation::probe_host(domain),
+        ingress_host,
+        &mut Vec::new(),
+    )
+    .await
+    .is_some()
+}
+
+/// Resolve `host` and compare it against the ingress addresses
+///
+/// Returns an address shared by the domain and the ingress if the domain points
+/// at the ingress.
+async fn check_dns(
+    host: &str,
+    ingress_host: Option<&str>,
+    checks: &mut Vec<DomainCheckItem>,
+) -> Option<IpAddr> {
+    let domain_ips = match resolve(host).await {
+        Ok(ips) => {
+            checks.push(DomainCheckItem::pass(
+                "DNS resolution",
+                format!("{} resolves to {}", host, format_ips(&ips)),
+            ));
+            Some(ips)
+        }
+        Err(e) => {
+            checks.push(DomainCheckItem::fail(
+                "DNS resolution",
+                format!("{} does not resolve: {}", host, e),
+            ));
+            None
+        }
+    };
+
+    match (&domain_ips, ingress_host) {
+        (None, _) => {
+            checks.push(DomainCheckItem::skip(
+                "Points at ingress",
+                "Skipped because the domain does not resolve",
+            ));
+            None
+        }
+        (Some(_), None) => {
+            checks.push(DomainCheckItem::skip(
+                "Points at ingress",
+                "No ingress host is configured on this Rise instance",
+            ));
+            None
+        }
+        (Some(domain_ips), Some(ingress_host)) => match resolve(ingress_host).await {
+            Ok(ingress_ips) if !domain_ips.is_disjoint(&ingress_ips) => {
+                checks.push(DomainCheckItem::pass(
+                    "Points at ingress",
+                    format!("Matches ingress host {}", ingress_host),
+                ));
+                domain_ips.intersection(&ingress_ips).next().copied()
+            }
+            Ok(ingress_ips) => {
+                checks.push(DomainCheckItem::fail(
+                    "Points at ingress",
+                    format!(
+                        "Expected a CNAME to {} (or A records for {}), but {} resolves to {}",
+                        ingress_host,
+                        format_ips(&ingress_ips),
+                        host,
+                        format_ips(domain_ips)
+                    ),
+                ));
+                None
+            }
+            Err(e) => {
+                checks.push(DomainCheckItem::fail(
+                    "Points at ingress",
+                    format!("Ingress host {} does not resolve: {}", ingress_host, e),
+                ));
+                None
+            }
+        },
+    }
+}
+
+async fn resolve(host: &str) -> Result<BTreeSet<IpAddr>> {
+    let addrs = tokio::time::timeout(CHECK_TIMEOUT, tokio::net::lookup_host((host, 443)))
+        .await
+        .context("DNS lookup timed out")??;
+    let ips: BTreeSet<IpAddr> = addrs.map(|addr| addr.ip()).collect();
+    if ips.is_empty() {
+        anyhow::bail!("no addresses found");
+    }
+    Ok(ips)
+}
+
+fn format_ips(ips: &BTreeSet<IpAddr>) -> String {
+    ips.iter()
+        .map(IpAddr::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Fetch the certificate served for `domain` by the already validated `addr`.
+///
+/// Chain verification is disabled so expired or mismatched certificates can still
+/// be inspected; trust is covered by the HTTP check.
+fn fetch_certificate(domain: &str, addr: SocketAddr) -> Result<CertificateInfo> {
+    let mut builder = SslConnector::builder(SslMethod::tls())?;
+    builder.set_verify(SslVerifyMode::NONE);
+    let connector = builder.build();
+
+    let stream = TcpStream::connect_timeout(&addr, CHECK_TIMEOUT)
+        .with_context(|| format!("Failed to connect to {} ({})", domain, addr))?;
+    stream.set_read_timeout(Some(CHECK_TIMEOUT))?;
+    stream.set_write_timeout(Some(CHECK_TIMEOUT))?;
+
+    let mut config = connector.configure()?;
+    config.set_verify_hostname(false);
+    let tls = config
+        .connect(domain, stream)
+        .map_err(|e| anyhow::anyhow!("TLS handshake failed: {}", e))?;
+    let cert = tls
+        .ssl()
+        .peer_certificate()
+        .context("Server did not present a certificate")?;
+
+    let dns_names = cert
+        .subject_alt_names()
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.dnsname().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let days_remaining = Asn1Time::days_from_now(0)?.diff(cert.not_after())?.days;
+
+    Ok(CertificateInfo {
+        dns_names,
+        not_after: cert.not_after().to_string(),
+        days_remaining,
+    })
+}
+
+fn evaluate_certificate(domain: &str, info: &CertificateInfo) -> DomainCheckItem {
+    if !info
+        .dns_names
+        .iter()
+        .any(|name| certificate_name_matches(name, domain))
+    {
+        let covered = if info.dns_names.is_empty() {
+            "no DNS names".to_string()
+        } else {
+            info.dns_names.join(", ")
+        };
+        return DomainCheckItem::fail(
+            "TLS certificate",
+            format!(
+                "Certificate does not cover {} (covers {}); it may not be issued yet",
+                domain, covered
+            ),
+        );
+    }
+    if info.days_remaining < 0 {
+        return DomainCheckItem::fail(
+            "TLS certificate",
+            format!("Certificate expired on {}", info.not_after),
+        );
+    }
+    if info.days_remaining < MIN_CERTIFICATE_DAYS_REMAINING {
+        return DomainCheckItem::fail(
+            "TLS certificate",
+            format!(
+                "Certificate expires in {} days ({}); renewal may be failing",
+                info.days_remaining, info.not_after
+            ),
+        );
+    }
+    DomainCheckItem::pass(
+        "TLS certificate",
+        format!(
+            "Valid until {} ({} days)",
+            info.not_after, info.days_remaining
+        ),
+    )
+}
+
+/// Match a certificate DNS name (possibly a single-label wildcard) against a domain
+fn certificate_name_matches(name: &str, domain: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    let domain = domain.to_ascii_lowercase();
+    match name.strip_prefix("*.") {
+        Some(suffix) => domain
+            .split_once('.')
+            .is_some_and(|(label, rest)| !label.is_empty() && rest == suffix),
+        None => name == domain,
+    }
+}
+
+/// Request `https://{domain}/` from the already validated `addr`
+async fn check_http(domain: &str, addr: SocketAddr) -> DomainCheckItem {
+    let client = match reqwest::Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve(domain, addr)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return DomainCheckItem::fail("HTTP", format!("{}", e)),
+    };
+
+    let url = format!("https://{}/", domain);
+    match client.get(&url).send().await {
+        Ok(response) => evaluate_http_status(&url, response.status()),
+        Err(e) => DomainCheckItem::fail("HTTP", format!("GET {} failed: {:#}", url, e)),
+    }
+}
+
+fn evaluate_http_status(url: &str, status: reqwest::StatusCode) -> DomainCheckItem {
+    if status.is_server_error() {
+        DomainCheckItem::fail("HTTP", format!("GET {} returned {}", url, status))
+    } else if status == reqwest::StatusCode::NOT_FOUND {
+        DomainCheckItem::fail(
+            "HTTP",
+            format!(
+                "GET {} returned {}; the ingress may not route this domain yet",
+                url, status
+            ),
+        )
+    } else {
+        DomainCheckItem::pass("HTTP", format!("GET {} returned {}", url, status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::custom_domains::models::DomainCheckStatus;
+
+    fn cert(names: &[&str], days_remaining: i32) -> CertificateInfo {
+        CertificateInfo {
+            dns_names: names.iter().map(|n| n.to_string()).collect(),
+            not_after: "Jan  1 00:00:00 2027 GMT".to_string(),
+            days_remaining,
+        }
+    }
+
+    #[test]
+    fn test_certificate_name_matches() {
+        assert!(certificate_name_matches(
+            "app.example.com",
+            "app.example.com"
+        ));
+        assert!(certificate_name_matches(
+            "App.Example.com",
+            "app.example.COM"
+        ));
+        assert!(certificate_name_matches("*.example.com", "app.example.com"));
+        assert!(!certificate_name_matches("*.example.com", "example.com"));
+        assert!(!certificate_name_matches(
+            "*.example.com",
+            "a.b.example.com"
+        ));
+        assert!(!certificate_name_matches(
+            "other.example.com",
+            "app.example.com"
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_certificate() {
+        let domain = "app.example.com";
+        assert_eq!(
+            evaluate_certificate(domain, &cert(&["app.example.com"], 60)).status,
+            DomainCheckStatus::Pass
+        );
+        assert_eq!(
+            evaluate_certificate(domain, &cert(&["*.example.com"], 60)).status,
+            DomainCheckStatus::Pass
+        );
+        assert_eq!(
+            evaluate_certificate(domain, &cert(&["ingress.local"], 60)).status,
+            DomainCheckStatus::Fail
+        );
+        assert_eq!(
+            evaluate_certificate(domain, &cert(&["app.example.com"], 3)).status,
+            DomainCheckStatus::Fail
+        );
+        assert_eq!(
+            evaluate_certificate(domain, &cert(&["app.example.com"], -1)).status,
+            DomainCheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_evaluate_http_status() {
+        let url = "https://app.example.com/";
+        for (status, expected) in [
+            (200, DomainCheckStatus::Pass),
+            (302, DomainCheckStatus::Pass),
+            (401, DomainCheckStatus::Pass),
+            (404, DomainCheckStatus::Fail),
+            (503, DomainCheckStatus::Fail),
+        ] {
+            let status = reqwest::StatusCode::from_u16(status).unwrap();
+            assert_eq!(evaluate_http_status(url, status).status, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_domain_skips_remaining_checks() {
+        let (checks, points_at_ingress) =
+            check_domain("does-not-exist.invalid", Some("ingress.invalid")).await;
+        assert!(!points_at_ingress);
+        let statuses: Vec<_> = checks.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                DomainCheckStatus::Fail,
+                DomainCheckStatus::Skip,
+                DomainCheckStatus::Skip,
+                DomainCheckStatus::Skip,
+            ]
+        );
+    }
+}
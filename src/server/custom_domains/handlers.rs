@@ -1,5 +1,7 @@
-use super::models::{AddCustomDomainRequest, CustomDomainResponse, CustomDomainsResponse};
-use super::validation;
+use super::models::{
+    AddCustomDomainRequest, CustomDomainResponse, CustomDomainsResponse, DomainCheckResponse,
+};
+use super::{check, validation};
 use crate::db::{custom_domains as db_custom_domains, deployments as db_deployments, projects};
 use crate::server::auth::context::AuthContext;
 use crate::server::deployment::models::DEFAULT_DEPLOYMENT_GROUP;
@@ -11,7 +13,15 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use tracing::info;
+use tracing::{info, warn};
+
+/// Host custom domains of `project` are expected to point at
+fn project_ingress_host(state: &AppState, project: &crate::db::models::Project) -> Option<String> {
+    state
+        .production_ingress_url_template
+        .as_deref()
+        .and_then(|template| validation::project_ingress_host(template, &project.name))
+}
 
 /// Add a custom domain to a project
 pub async fn add_custom_domain(
@@ -35,10 +45,13 @@ pub async fn add_custom_domain(
     let user = auth.user()?;
     ensure_project_access_or_admin(&state, user, &project).await?;
 
+    let domain_name = validation::normalize_domain(&payload.domain);
+    validation::validate_domain_name(&domain_name).map_err(ServerError::bad_request)?;
+
     // Validate that the custom domain doesn't overlap with project default domain patterns
     if let Some(ref production_template) = state.production_ingress_url_template {
         if let Err(reason) = validation::validate_custom_domain(
-            &payload.domain,
+            &domain_name,
             production_template,
             state.staging_ingress_url_template.as_deref(),
             Some(&state.public_url),
@@ -48,7 +61,7 @@ pub async fn add_custom_domain(
     }
 
     // Add the custom domain
-    let domain = db_custom_domains::add_custom_domain(&state.db_pool, project.id, &payload.domain)
+    let mut domain = db_custom_domains::add_custom_domain(&state.db_pool, project.id, &domain_name)
         .await
         .map_err(|e| {
             // Check if it's a duplicate key error or validation error
@@ -56,14 +69,26 @@ pub async fn add_custom_domain(
             if error_message.contains("duplicate key")
                 || error_message.contains("unique constraint")
             {
-                ServerError::conflict(format!("Domain '{}' is already in use", payload.domain))
+                ServerError::conflict(format!("Domain '{}' is already in use", domain_name))
             } else if error_message.contains("check constraint") {
-                ServerError::bad_request(format!("Invalid domain format: {}", payload.domain))
+                ServerError::bad_request(format!("Invalid domain format: {}", domain_name))
             } else {
                 ServerError::internal_anyhow(e, "Failed to add custom domain")
             }
         })?;
 
+    // Check DNS right away so an already configured domain shows up as verified
+    let ingress_host = project_ingress_host(&state, &project);
+    if check::verify_dns(&domain.domain, ingress_host.as_deref()).await {
+        match db_custom_domains::set_verified(&state.db_pool, domain.id, true).await {
+            Ok(()) => domain.verified = true,
+            Err(e) => warn!(
+                "Failed to mark custom domain '{}' as verified: {:?}",
+                domain.domain, e
+            ),
+        }
+    }
+
     // Trigger reconciliation of the active deployment in the default group
     // Custom domains are only applied to the default deployment group
     match db_deployments::find_active_for_project_and_group(
@@ -90,7 +115,7 @@ pub async fn add_custom_domain(
             } else {
                 info!(
                     "Successfully marked deployment {} for reconciliation after adding custom domain '{}'",
-                    active_deployment.deployment_id, payload.domain
+                    active_deployment.deployment_id, domain.domain
                 );
             }
         }
@@ -194,6 +219,60 @@ pub async fn get_custom_domain(
     Ok(Json(CustomDomainResponse::from_db_model(&domain)))
 }
 
+/// Diagnose DNS, TLS and HTTP routing of a custom domain
+pub async fn check_custom_domain(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((project_id_or_name, domain)): Path<(String, String)>,
+) -> Result<Json<DomainCheckResponse>, ServerError> {
+    // Find project by ID or name
+    let project = if let Ok(uuid) = project_id_or_name.parse() {
+        projects::find_by_id(&state.db_pool, uuid)
+            .await
+            .internal_err("Failed to get project")?
+    } else {
+        projects::find_by_name(&state.db_pool, &project_id_or_name)
+            .await
+            .internal_err("Failed to get project")?
+    }
+    .ok_or_else(|| ServerError::not_found("Project not found"))?;
+
+    let user = auth.user()?;
+    ensure_project_access_or_admin(&state, user, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found("Project not found")
+            } else {
+                e
+            }
+        })?;
+
+    // Only domains configured on the project can be checked
+    let domain = db_custom_domains::get_custom_domain(&state.db_pool, project.id, &domain)
+        .await
+        .internal_err("Failed to get custom domain")?
+        .ok_or_else(|| ServerError::not_found("Custom domain not found"))?;
+
+    let ingress_host = project_ingress_host(&state, &project);
+
+    let (checks, verified) = check::check_domain(&domain.domain, ingress_host.as_deref()).await;
+    if verified != domain.verified {
+        if let Err(e) = db_custom_domains::set_verified(&state.db_pool, domain.id, verified).await {
+            warn!(
+                "Failed to update verification of custom domain '{}': {:?}",
+                domain.domain, e
+            );
+        }
+    }
+
+    Ok(Json(DomainCheckResponse {
+        domain: domain.domain,
+        ingress_host,
+        checks,
+    }))
+}
+
 /// Delete a custom domain
 pub async fn delete_custom_domain(
     State(state): State<AppState>,
@@ -292,6 +371,13 @@ pub async fn set_primary_domain(
     let user = auth.user()?;
     ensure_project_access_or_admin(&state, user, &project).await?;
 
+    // A wildcard has no single URL to serve as the project's primary URL
+    if validation::is_wildcard(&domain) {
+        return Err(ServerError::bad_request(
+            "Wildcard domains cannot be set as primary",
+        ));
+    }
+
     // Set the domain as primary
     let updated_domain = db_custom_domains::set_primary_domain(&state.db_pool, project.id, &domain)
         .await
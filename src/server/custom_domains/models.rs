@@ -12,6 +12,8 @@ pub struct CustomDomainResponse {
     pub id: String,
     pub domain: String,
     pub is_primary: bool,
+    /// Whether the domain resolved to the project's ingress at the last check
+    pub verified: bool,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -23,6 +25,7 @@ impl CustomDomainResponse {
             id: domain.id.to_string(),
             domain: domain.domain.clone(),
             is_primary: domain.is_primary,
+            verified: domain.verified,
             created_at: domain.created_at.to_rfc3339(),
             updated_at: domain.updated_at.to_rfc3339(),
         }
@@ -34,3 +37,51 @@ impl CustomDomainResponse {
 pub struct CustomDomainsResponse {
     pub domains: Vec<CustomDomainResponse>,
 }
+
+/// Outcome of a single custom domain check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DomainCheckStatus {
+    Pass,
+    Fail,
+    Skip,
+}
+
+/// A single item of a custom domain diagnosis
+#[derive(Debug, Serialize)]
+pub struct DomainCheckItem {
+    pub name: String,
+    pub status: DomainCheckStatus,
+    pub message: String,
+}
+
+impl DomainCheckItem {
+    pub fn pass(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, DomainCheckStatus::Pass, message)
+    }
+
+    pub fn fail(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, DomainCheckStatus::Fail, message)
+    }
+
+    pub fn skip(name: &str, message: impl Into<String>) -> Self {
+        Self::new(name, DomainCheckStatus::Skip, message)
+    }
+
+    fn new(name: &str, status: DomainCheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Response of a custom domain diagnosis
+#[derive(Debug, Serialize)]
+pub struct DomainCheckResponse {
+    pub domain: String,
+    /// Host the domain is expected to point at, if known
+    pub ingress_host: Option<String>,
+    pub checks: Vec<DomainCheckItem>,
+}
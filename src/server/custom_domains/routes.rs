@@ -19,4 +19,8 @@ pub fn routes() -> Router<AppState> {
             "/projects/{project_id_or_name}/domains/{domain}/primary",
             put(handlers::set_primary_domain).delete(handlers::unset_primary_domain),
         )
+        .route(
+            "/projects/{project_id_or_name}/domains/{domain}/check",
+            get(handlers::check_custom_domain),
+        )
 }
@@ -112,6 +112,101 @@ fn extract_hostname_from_url(url: &str) -> Option<String> {
     }
 }
 
+/// Resolve the default ingress hostname of a project from the production ingress URL template.
+///
+/// Custom domains are expected to point (via CNAME) at this host.
+pub fn project_ingress_host(production_template: &str, project_name: &str) -> Option<String> {
+    extract_hostname_from_url(&production_template.replace("{project_name}", project_name))
+}
+
+/// Label substituted for `*` when a wildcard domain must be treated as a concrete host
+/// (DNS lookups, TLS/HTTP checks, conflict checks).
+const WILDCARD_PROBE_LABEL: &str = "rise-wildcard-check";
+
+/// Whether `domain` is a wildcard domain such as `*.example.com`
+pub fn is_wildcard(domain: &str) -> bool {
+    domain.starts_with("*.")
+}
+
+/// A concrete host covered by `domain`: the domain itself, or for a wildcard a
+/// host one label below its base (`*.example.com` → `rise-wildcard-check.example.com`).
+pub fn probe_host(domain: &str) -> String {
+    match domain.strip_prefix("*.") {
+        Some(base) => format!("{}.{}", WILDCARD_PROBE_LABEL, base),
+        None => domain.to_string(),
+    }
+}
+
+/// Normalize user input: trim whitespace and a trailing dot, lowercase
+pub fn normalize_domain(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Validate that `domain` is a valid hostname.
+///
+/// Accepts apex domains (`example.com`), subdomains, and wildcards where `*` is the
+/// entire leftmost label (`*.example.com`). IP addresses and single-label names are
+/// rejected.
+pub fn validate_domain_name(domain: &str) -> Result<(), String> {
+    if domain.is_empty() || domain.len() > 253 {
+        return Err(format!(
+            "Invalid domain '{}': must be 1 to 253 characters long",
+            domain
+        ));
+    }
+
+    let name = domain.strip_prefix("*.").unwrap_or(domain);
+    let labels: Vec<&str> = name.split('.').collect();
+    if labels.len() < 2 {
+        return Err(format!(
+            "Invalid domain '{}': must have at least two labels below the wildcard (e.g. example.com)",
+            domain
+        ));
+    }
+
+    for label in &labels {
+        if label.contains('*') {
+            return Err(format!(
+                "Invalid domain '{}': '*' is only allowed as the entire leftmost label (e.g. *.example.com)",
+                domain
+            ));
+        }
+        if label.is_empty() || label.len() > 63 {
+            return Err(format!(
+                "Invalid domain '{}': labels must be 1 to 63 characters long",
+                domain
+            ));
+        }
+        if !label
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(format!(
+                "Invalid domain '{}': labels may only contain lowercase letters, digits and hyphens",
+                domain
+            ));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(format!(
+                "Invalid domain '{}': labels must not start or end with a hyphen",
+                domain
+            ));
+        }
+    }
+
+    if labels
+        .last()
+        .is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit()))
+    {
+        return Err(format!(
+            "Invalid domain '{}': IP addresses are not supported",
+            domain
+        ));
+    }
+
+    Ok(())
+}
+
 /// Check if a custom domain would conflict with project default domain patterns or Rise's public URL.
 ///
 /// # Arguments
@@ -130,10 +225,20 @@ pub fn validate_custom_domain(
     staging_template: Option<&str>,
     rise_public_url: Option<&str>,
 ) -> Result<(), String> {
+    // Wildcards conflict if any host they cover does; checking one host one label
+    // below the wildcard base covers all of them, since templates match whole labels
+    let host = probe_host(domain);
+
     // Check against Rise's own public URL
     if let Some(public_url) = rise_public_url {
         if let Some(rise_hostname) = extract_hostname_from_url(public_url) {
-            if domain == rise_hostname {
+            let covers_rise = match domain.strip_prefix("*.") {
+                Some(base) => rise_hostname
+                    .split_once('.')
+                    .is_some_and(|(_, parent)| parent == base),
+                None => domain == rise_hostname,
+            };
+            if covers_rise {
                 return Err(format!(
                     "Custom domain '{}' conflicts with Rise's public URL hostname",
                     domain
@@ -144,7 +249,7 @@ pub fn validate_custom_domain(
 
     // Check against production template
     if let Some(regex) = template_to_regex(production_template) {
-        if regex.is_match(domain) {
+        if regex.is_match(&host) {
             return Err(format!(
                 "Custom domain '{}' conflicts with the project default domain pattern (production template)",
                 domain
@@ -155,7 +260,7 @@ pub fn validate_custom_domain(
     // Check against staging template if provided
     if let Some(staging_template) = staging_template {
         if let Some(regex) = template_to_regex(staging_template) {
-            if regex.is_match(domain) {
+            if regex.is_match(&host) {
                 return Err(format!(
                     "Custom domain '{}' conflicts with the staging deployment domain pattern",
                     domain
@@ -171,6 +276,18 @@ pub fn validate_custom_domain(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_project_ingress_host() {
+        assert_eq!(
+            project_ingress_host("{project_name}.apps.example.com", "my-app"),
+            Some("my-app.apps.example.com".to_string())
+        );
+        assert_eq!(
+            project_ingress_host("apps.example.com:8443/{project_name}", "my-app"),
+            Some("apps.example.com".to_string())
+        );
+    }
+
     #[test]
     fn test_template_to_regex_subdomain() {
         let regex = template_to_regex("{project_name}.apps.example.com").unwrap();
@@ -340,4 +457,77 @@ mod tests {
             .unwrap_err()
             .contains("conflicts with the project default domain pattern"));
     }
+
+    #[test]
+    fn test_validate_domain_name() {
+        for valid in [
+            "example.com",
+            "app.example.com",
+            "*.example.com",
+            "*.apps.example.co.uk",
+            "my-app1.example.com",
+        ] {
+            assert_eq!(validate_domain_name(valid), Ok(()), "{}", valid);
+        }
+
+        for invalid in [
+            "",
+            "localhost",
+            "*.com",
+            "*example.com",
+            "app.*.example.com",
+            "**.example.com",
+            "-app.example.com",
+            "app-.example.com",
+            "app..example.com",
+            "App.example.com",
+            "app_1.example.com",
+            "10.0.0.1",
+            &format!("{}.com", "a".repeat(64)),
+        ] {
+            assert!(validate_domain_name(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_normalize_domain() {
+        assert_eq!(normalize_domain(" App.Example.COM. "), "app.example.com");
+    }
+
+    #[test]
+    fn test_validate_custom_domain_wildcard() {
+        let template = "{project_name}.apps.example.com";
+        // Would capture every project's default host
+        assert!(validate_custom_domain("*.apps.example.com", template, None, None).is_err());
+        assert!(validate_custom_domain("*.example.com", template, None, None).is_ok());
+
+        // Would capture Rise itself
+        let result = validate_custom_domain(
+            "*.example.com",
+            template,
+            None,
+            Some("https://rise.example.com"),
+        );
+        assert!(result
+            .unwrap_err()
+            .contains("conflicts with Rise's public URL"));
+        assert!(validate_custom_domain(
+            "*.example.com",
+            template,
+            None,
+            Some("https://rise.internal.example.com"),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_probe_host() {
+        assert_eq!(probe_host("example.com"), "example.com");
+        assert_eq!(
+            probe_host("*.example.com"),
+            "rise-wildcard-check.example.com"
+        );
+        assert!(is_wildcard("*.example.com"));
+        assert!(!is_wildcard("example.com"));
+    }
 }
@@ -0,0 +1,1922 @@
+//! AWS ECS (Fargate) deployment backend.
+//!
+//! Every infrastructure-bearing deployment runs as its own ECS service with a
+//! dedicated ALB target group. Each deployment group gets one listener rule matching
+//! the group's ingress URL; the rule forwards to all target groups of the group,
+//! weighted so that only the active deployment receives traffic. New deployments
+//! join the rule with weight 0 because ECS only accepts target groups that are
+//! attached to a load balancer.
+//!
+//! ECS has no Metacontroller equivalent, so [`EcsController`] polls the database,
+//! drives status transitions with the same helpers as the sync webhook, and
+//! garbage-collects services, rules and target groups no deployment needs anymore.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_sdk_ecs::types::{
+    AssignPublicIp, AwsVpcConfiguration, Compatibility, ContainerDefinition,
+    DeploymentCircuitBreaker, DeploymentConfiguration, DeploymentRolloutState, KeyValuePair,
+    LaunchType, LoadBalancer, LogConfiguration, LogDriver, NetworkConfiguration, NetworkMode,
+    PortMapping, PropagateTags, Service, ServiceField, TransportProtocol,
+};
+use aws_sdk_elasticloadbalancingv2::types::{
+    Action, ActionTypeEnum, ForwardActionConfig, HostHeaderConditionConfig, Matcher,
+    PathPatternConditionConfig, ProtocolEnum, Rule, RuleCondition, TargetGroupTuple,
+    TargetHealthStateEnum, TargetTypeEnum,
+};
+use chrono::Utc;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use super::metadata::{ControllerMetadata, EcsServiceStatus};
+use super::{DeploymentBackend, DeploymentUrls};
+use crate::db::leader_leases::LeaderElection;
+use crate::db::models::{Deployment, DeploymentStatus, Project};
+use crate::db::{deployments as db_deployments, projects as db_projects};
+use crate::server::deployment::quantity;
+use crate::server::deployment::resource_builder::{
+    format_group_ingress_url, LABEL_DEPLOYMENT_GROUP, LABEL_DEPLOYMENT_ID, LABEL_MANAGED_BY,
+    LABEL_PROJECT,
+};
+use crate::server::deployment::webhook::{self, ResolvedDeploymentEnvVars};
+use crate::server::metrics;
+use crate::server::settings::DeploymentControllerSettings;
+use crate::server::state::AppState;
+
+/// Name of the application container in every task definition
+const CONTAINER_NAME: &str = "app";
+/// Prefix of target groups created by Rise (names are limited to 32 characters)
+const TARGET_GROUP_PREFIX: &str = "rise-";
+/// ALB forward actions accept at most five target groups
+const MAX_FORWARD_TARGET_GROUPS: usize = 5;
+/// Interval between CloudWatch Logs polls when following logs
+const LOG_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Fargate CPU units, smallest first
+const FARGATE_CPU_UNITS: [u32; 7] = [256, 512, 1024, 2048, 4096, 8192, 16384];
+
+/// ECS controller configuration, extracted from the `ecs` deployment controller settings
+#[derive(Debug, Clone)]
+pub struct EcsConfig {
+    pub region: String,
+    pub cluster: String,
+    pub subnets: Vec<String>,
+    pub security_groups: Vec<String>,
+    pub assign_public_ip: bool,
+    pub vpc_id: String,
+    pub listener_arn: String,
+    pub execution_role_arn: String,
+    pub task_role_arn: Option<String>,
+    pub log_group: String,
+    pub production_ingress_url_template: String,
+    pub staging_ingress_url_template: Option<String>,
+    pub ingress_schema: String,
+    pub health_check_path: String,
+    pub reconcile_interval: Duration,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<String>,
+}
+
+impl EcsConfig {
+    /// Extract the ECS configuration, returning `None` for other controller types
+    pub fn from_settings(settings: &DeploymentControllerSettings) -> Option<Self> {
+        match settings {
+            DeploymentControllerSettings::Ecs {
+                region,
+                cluster,
+                subnets,
+                security_groups,
+                assign_public_ip,
+                vpc_id,
+                listener_arn,
+                execution_role_arn,
+                task_role_arn,
+                log_group,
+                production_ingress_url_template,
+                staging_ingress_url_template,
+                ingress_schema,
+                health_check_path,
+                reconcile_interval_secs,
+                access_key_id,
+                secret_access_key,
+                ..
+            } => Some(Self {
+                region: region.clone(),
+                cluster: cluster.clone(),
+                subnets: subnets.clone(),
+                security_groups: security_groups.clone(),
+                assign_public_ip: *assign_public_ip,
+                vpc_id: vpc_id.clone(),
+                listener_arn: listener_arn.clone(),
+                execution_role_arn: execution_role_arn.clone(),
+                task_role_arn: task_role_arn.clone(),
+                log_group: log_group.clone(),
+                production_ingress_url_template: production_ingress_url_template.clone(),
+                staging_ingress_url_template: staging_ingress_url_template.clone(),
+                ingress_schema: ingress_schema.clone(),
+                health_check_path: health_check_path.clone(),
+                reconcile_interval: Duration::from_secs((*reconcile_interval_secs).max(1)),
+                access_key_id: access_key_id.clone(),
+                secret_access_key: secret_access_key.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// ECS service name of a deployment (also the CloudWatch log stream prefix)
+fn service_name(project: &Project, deployment: &Deployment) -> String {
+    format!("{}-{}", project.name, deployment.deployment_id)
+}
+
+/// Target group name of a deployment, derived from its UUID to fit into 32 characters
+fn target_group_name(deployment: &Deployment) -> String {
+    let id = deployment.id.simple().to_string();
+    format!(
+        "{}{}",
+        TARGET_GROUP_PREFIX,
+        &id[..32 - TARGET_GROUP_PREFIX.len()]
+    )
+}
+
+/// Task definition family of a project
+fn task_family(project: &Project) -> String {
+    format!("rise-{}", project.name)
+}
+
+fn resource_tags(project: &Project, deployment: &Deployment) -> Vec<(&'static str, String)> {
+    vec![
+        (LABEL_MANAGED_BY, "rise".to_string()),
+        (LABEL_PROJECT, project.name.clone()),
+        (LABEL_DEPLOYMENT_ID, deployment.deployment_id.clone()),
+        (LABEL_DEPLOYMENT_GROUP, deployment.deployment_group.clone()),
+    ]
+}
+
+/// Pick the smallest valid Fargate task size fitting the requested CPU and memory.
+///
+/// Returns `(cpu_units, memory_mib)`; requests beyond the largest size are capped.
+fn fargate_task_size(cpu_millicores: u64, memory_bytes: u64) -> (u32, u32) {
+    let cpu_units = (cpu_millicores * 1024).div_ceil(1000);
+    let memory_mib = memory_bytes.div_ceil(1024 * 1024);
+
+    for cpu in FARGATE_CPU_UNITS {
+        if u64::from(cpu) < cpu_units {
+            continue;
+        }
+        if let Some(memory) = fargate_memory_options(cpu)
+            .into_iter()
+            .find(|m| u64::from(*m) >= memory_mib)
+        {
+            return (cpu, memory);
+        }
+    }
+    (16384, 122880)
+}
+
+/// Valid memory sizes (MiB) for a Fargate CPU size
+fn fargate_memory_options(cpu: u32) -> Vec<u32> {
+    let gib = |range: std::ops::RangeInclusive<u32>, step: usize| {
+        range.step_by(step).map(|g| g * 1024).collect::<Vec<_>>()
+    };
+    match cpu {
+        256 => vec![512, 1024, 2048],
+        512 => gib(1..=4, 1),
+        1024 => gib(2..=8, 1),
+        2048 => gib(4..=16, 1),
+        4096 => gib(8..=30, 1),
+        8192 => gib(16..=60, 4),
+        _ => gib(32..=120, 8),
+    }
+}
+
+/// Secrets Manager secret holding the secret env vars of an ECS service
+fn secret_name(service_name: &str) -> String {
+    format!("rise/{}", service_name)
+}
+
+/// Split resolved deployment env vars into task definition environment entries and
+/// secret values.
+///
+/// Task definitions are readable by anyone allowed to describe them and every
+/// revision is kept, so secret values never go into `environment`; they are stored
+/// in Secrets Manager and referenced through [`container_secrets`].
+fn container_environment(
+    env_vars: ResolvedDeploymentEnvVars,
+) -> (Vec<KeyValuePair>, BTreeMap<String, String>) {
+    let environment = env_vars
+        .plain_env_vars
+        .into_iter()
+        .map(|var| {
+            KeyValuePair::builder()
+                .name(var.name)
+                .value(var.value.unwrap_or_default())
+                .build()
+        })
+        .collect();
+    let secrets = env_vars
+        .secret_env_vars
+        .into_iter()
+        .map(|(key, value)| (key, String::from_utf8_lossy(&value.0).into_owned()))
+        .collect();
+    (environment, secrets)
+}
+
+/// Container secrets reading each variable from the JSON secret at `secret_arn`
+fn container_secrets<'a>(
+    secret_arn: &str,
+    names: impl IntoIterator<Item = &'a String>,
+) -> Result<Vec<aws_sdk_ecs::types::Secret>> {
+    names
+        .into_iter()
+        .map(|name| {
+            aws_sdk_ecs::types::Secret::builder()
+                .name(name)
+                .value_from(format!("{}:{}::", secret_arn, name))
+                .build()
+                .context("Failed to build container secret")
+        })
+        .collect()
+}
+
+/// Host and path conditions of a deployment group's listener rule
+#[derive(Debug, Clone, PartialEq)]
+struct RuleMatch {
+    host: String,
+    path_patterns: Vec<String>,
+}
+
+impl RuleMatch {
+    /// Build the match for an ingress URL such as "app.example.com" or "example.com/app"
+    fn from_url(url: &str) -> Self {
+        match url.find('/') {
+            Some(slash_pos) => {
+                let path = url[slash_pos..].trim_end_matches('/');
+                Self {
+                    host: url[..slash_pos].to_string(),
+                    path_patterns: vec![path.to_string(), format!("{}/*", path)],
+                }
+            }
+            None => Self {
+                host: url.to_string(),
+                path_patterns: Vec::new(),
+            },
+        }
+    }
+
+    fn conditions(&self) -> Vec<RuleCondition> {
+        let mut conditions = vec![RuleCondition::builder()
+            .field("host-header")
+            .host_header_config(
+                HostHeaderConditionConfig::builder()
+                    .values(self.host.clone())
+                    .build(),
+            )
+            .build()];
+        if !self.path_patterns.is_empty() {
+            conditions.push(
+                RuleCondition::builder()
+                    .field("path-pattern")
+                    .path_pattern_config(
+                        PathPatternConditionConfig::builder()
+                            .set_values(Some(self.path_patterns.clone()))
+                            .build(),
+                    )
+                    .build(),
+            );
+        }
+        conditions
+    }
+
+    /// Whether an existing listener rule has exactly these conditions
+    fn matches(&self, rule: &Rule) -> bool {
+        let mut host = None;
+        let mut paths: Vec<String> = Vec::new();
+        for condition in rule.conditions() {
+            match condition.field() {
+                Some("host-header") => {
+                    let values = condition
+                        .host_header_config()
+                        .map(|c| c.values())
+                        .unwrap_or_default();
+                    if values.len() != 1 {
+                        return false;
+                    }
+                    host = Some(values[0].to_ascii_lowercase());
+                }
+                Some("path-pattern") => {
+                    paths = condition
+                        .path_pattern_config()
+                        .map(|c| c.values().to_vec())
+                        .unwrap_or_default();
+                }
+                _ => return false,
+            }
+        }
+        host.as_deref() == Some(self.host.to_ascii_lowercase().as_str())
+            && paths == self.path_patterns
+    }
+}
+
+/// Forward weights for the deployments of a group.
+///
+/// The active deployment gets all traffic; if the group has no active deployment yet,
+/// the newest one does. Other deployments are attached with weight 0.
+fn forward_weights<'a>(group_deployments: &[&'a Deployment]) -> Vec<(&'a Deployment, i32)> {
+    let mut deployments: Vec<&Deployment> = group_deployments.to_vec();
+    // Active first, then newest first
+    deployments.sort_by(|a, b| {
+        b.is_active
+            .cmp(&a.is_active)
+            .then(b.created_at.cmp(&a.created_at))
+    });
+    deployments.truncate(MAX_FORWARD_TARGET_GROUPS);
+    deployments
+        .into_iter()
+        .enumerate()
+        .map(|(i, d)| (d, if i == 0 { 1 } else { 0 }))
+        .collect()
+}
+
+/// Target group ARNs and weights a listener rule forwards to
+fn rule_forward_targets(rule: &Rule) -> Vec<(String, i32)> {
+    rule.actions()
+        .iter()
+        .filter(|action| action.r#type() == Some(&ActionTypeEnum::Forward))
+        .flat_map(|action| {
+            let tuples: Vec<(String, i32)> = action
+                .forward_config()
+                .map(|config| {
+                    config
+                        .target_groups()
+                        .iter()
+                        .filter_map(|tg| {
+                            tg.target_group_arn()
+                                .map(|arn| (arn.to_string(), tg.weight().unwrap_or(1)))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            if tuples.is_empty() {
+                action
+                    .target_group_arn()
+                    .map(|arn| vec![(arn.to_string(), 1)])
+                    .unwrap_or_default()
+            } else {
+                tuples
+            }
+        })
+        .collect()
+}
+
+fn forward_action(targets: &[(String, i32)]) -> Action {
+    let config = targets
+        .iter()
+        .fold(ForwardActionConfig::builder(), |config, (arn, weight)| {
+            config.target_groups(
+                TargetGroupTuple::builder()
+                    .target_group_arn(arn)
+                    .weight(*weight)
+                    .build(),
+            )
+        })
+        .build();
+    Action::builder()
+        .r#type(ActionTypeEnum::Forward)
+        .forward_config(config)
+        .build()
+}
+
+/// Lowest listener rule priority not used by any rule
+fn lowest_free_priority(rules: &[Rule]) -> i32 {
+    let used: HashSet<i32> = rules
+        .iter()
+        .filter_map(|rule| rule.priority().and_then(|p| p.parse().ok()))
+        .collect();
+    (1..).find(|p| !used.contains(p)).unwrap_or(1)
+}
+
+/// Observed runtime state of a deployment's ECS service
+#[derive(Debug, Clone, Default)]
+struct ServiceObservation {
+    running_count: i32,
+    desired_count: i32,
+    healthy_targets: i32,
+    rollout_failure: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum EcsHealth {
+    Ready,
+    NotReady(String),
+    Failed(String),
+}
+
+fn evaluate_service_health(observation: &ServiceObservation) -> EcsHealth {
+    if let Some(ref reason) = observation.rollout_failure {
+        return EcsHealth::Failed(format!("ECS deployment failed: {}", reason));
+    }
+    let ready = observation.desired_count > 0
+        && observation.running_count >= observation.desired_count
+        && observation.healthy_targets >= observation.desired_count;
+    if ready {
+        EcsHealth::Ready
+    } else {
+        EcsHealth::NotReady(format!(
+            "Deployment unhealthy: {}/{} tasks running, {} healthy targets",
+            observation.running_count, observation.desired_count, observation.healthy_targets
+        ))
+    }
+}
+
+/// A target group managed by Rise
+#[derive(Debug, Clone)]
+struct ManagedTargetGroup {
+    arn: String,
+    project: Option<String>,
+}
+
+/// Listener rules and Rise target groups, loaded once per reconcile pass
+#[derive(Debug, Default)]
+struct EcsSnapshot {
+    rules: Vec<Rule>,
+    /// Keyed by target group name
+    target_groups: HashMap<String, ManagedTargetGroup>,
+}
+
+/// Resources that must survive the garbage-collection sweep
+#[derive(Debug, Default)]
+struct DesiredResources {
+    services: HashSet<String>,
+    target_groups: HashSet<String>,
+    rules: HashSet<String>,
+    /// Projects whose reconcile failed; their resources are left untouched
+    protected_projects: HashSet<String>,
+}
+
+/// AWS ECS deployment backend
+pub struct EcsBackend {
+    ecs: aws_sdk_ecs::Client,
+    elb: aws_sdk_elasticloadbalancingv2::Client,
+    logs: aws_sdk_cloudwatchlogs::Client,
+    secrets: aws_sdk_secretsmanager::Client,
+    config: EcsConfig,
+}
+
+/// Factory for the `ecs` deployment controller type
+pub(super) struct EcsBackendFactory;
+
+#[async_trait]
+impl super::DeploymentBackendFactory for EcsBackendFactory {
+    fn backend_type(&self) -> &'static str {
+        "ecs"
+    }
+
+    async fn create(&self, ctx: &super::BackendContext<'_>) -> Result<Arc<dyn DeploymentBackend>> {
+        let config = EcsConfig::from_settings(ctx.settings).ok_or_else(|| {
+            anyhow::anyhow!("ECS backend requires ecs deployment_controller settings")
+        })?;
+        let backend = EcsBackend::new(config).await;
+
+        backend.test_connection().await?;
+        tracing::info!("ECS deployment backend initialized and cluster verified");
+
+        Ok(Arc::new(backend))
+    }
+}
+
+impl EcsBackend {
+    pub async fn new(config: EcsConfig) -> Self {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest())
+            .region(aws_config::Region::new(config.region.clone()));
+        if let (Some(key_id), Some(secret_key)) = (&config.access_key_id, &config.secret_access_key)
+        {
+            loader = loader.credentials_provider(aws_sdk_ecs::config::Credentials::new(
+                key_id,
+                secret_key,
+                None,
+                None,
+                "static-credentials",
+            ));
+        }
+        let aws_config = loader.load().await;
+
+        Self {
+            ecs: aws_sdk_ecs::Client::new(&aws_config),
+            elb: aws_sdk_elasticloadbalancingv2::Client::new(&aws_config),
+            logs: aws_sdk_cloudwatchlogs::Client::new(&aws_config),
+            secrets: aws_sdk_secretsmanager::Client::new(&aws_config),
+            config,
+        }
+    }
+
+    /// Verify the configured cluster exists and is active
+    async fn test_connection(&self) -> Result<()> {
+        let output = self
+            .ecs
+            .describe_clusters()
+            .clusters(&self.config.cluster)
+            .send()
+            .await
+            .context("Failed to describe ECS cluster")?;
+        let active = output
+            .clusters()
+            .iter()
+            .any(|cluster| cluster.status() == Some("ACTIVE"));
+        if !active {
+            anyhow::bail!(
+                "ECS cluster '{}' not found or not active",
+                self.config.cluster
+            );
+        }
+        Ok(())
+    }
+
+    fn group_url(&self, project: &Project, deployment_group: &str) -> String {
+        format_group_ingress_url(
+            &self.config.production_ingress_url_template,
+            self.config.staging_ingress_url_template.as_deref(),
+            &project.name,
+            deployment_group,
+        )
+    }
+
+    fn urls_for_group(&self, project: &Project, deployment_group: &str) -> DeploymentUrls {
+        let default_url = format!(
+            "{}://{}",
+            self.config.ingress_schema,
+            self.group_url(project, deployment_group)
+        );
+        DeploymentUrls {
+            primary_url: default_url.clone(),
+            default_url,
+            custom_domain_urls: Vec::new(),
+        }
+    }
+
+    // ── Snapshot ───────────────────────────────────────────────────────
+
+    async fn load_snapshot(&self) -> Result<EcsSnapshot> {
+        let mut rules = Vec::new();
+        let mut marker = None;
+        loop {
+            let output = self
+                .elb
+                .describe_rules()
+                .listener_arn(&self.config.listener_arn)
+                .set_marker(marker)
+                .send()
+                .await
+                .context("Failed to describe listener rules")?;
+            rules.extend(output.rules().iter().cloned());
+            marker = output.next_marker().map(str::to_string);
+            if marker.is_none() {
+                break;
+            }
+        }
+
+        let mut candidates = Vec::new();
+        let mut marker = None;
+        loop {
+            let output = self
+                .elb
+                .describe_target_groups()
+                .set_marker(marker)
+                .send()
+                .await
+                .context("Failed to describe target groups")?;
+            candidates.extend(
+                output
+                    .target_groups()
+                    .iter()
+                    .filter(|tg| {
+                        tg.vpc_id() == Some(self.config.vpc_id.as_str())
+                            && tg
+                                .target_group_name()
+                                .is_some_and(|name| name.starts_with(TARGET_GROUP_PREFIX))
+                    })
+                    .filter_map(|tg| {
+                        Some((
+                            tg.target_group_name()?.to_string(),
+                            tg.target_group_arn()?.to_string(),
+                        ))
+                    }),
+            );
+            marker = output.next_marker().map(str::to_string);
+            if marker.is_none() {
+                break;
+            }
+        }
+
+        // Only target groups tagged as managed by Rise are considered ours
+        let mut target_groups = HashMap::new();
+        for chunk in candidates.chunks(20) {
+            let output = self
+                .elb
+                .describe_tags()
+                .set_resource_arns(Some(chunk.iter().map(|(_, arn)| arn.clone()).collect()))
+                .send()
+                .await
+                .context("Failed to describe target group tags")?;
+            for description in output.tag_descriptions() {
+                let tag = |key: &str| {
+                    description
+                        .tags()
+                        .iter()
+                        .find(|t| t.key() == Some(key))
+                        .and_then(|t| t.value())
+                        .map(str::to_string)
+                };
+                if tag(LABEL_MANAGED_BY).as_deref() != Some("rise") {
+                    continue;
+                }
+                if let Some((name, arn)) = chunk
+                    .iter()
+                    .find(|(_, arn)| Some(arn.as_str()) == description.resource_arn())
+                {
+                    target_groups.insert(
+                        name.clone(),
+                        ManagedTargetGroup {
+                            arn: arn.clone(),
+                            project: tag(LABEL_PROJECT),
+                        },
+                    );
+                }
+            }
+        }
+
+        Ok(EcsSnapshot {
+            rules,
+            target_groups,
+        })
+    }
+
+    // ── Provisioning ───────────────────────────────────────────────────
+
+    async fn describe_service(&self, name: &str) -> Result<Option<Service>> {
+        let output = self
+            .ecs
+            .describe_services()
+            .cluster(&self.config.cluster)
+            .services(name)
+            .send()
+            .await
+            .context("Failed to describe ECS service")?;
+        Ok(output
+            .services()
+            .iter()
+            .find(|service| service.status() != Some("INACTIVE"))
+            .cloned())
+    }
+
+    async fn ensure_target_group(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        snapshot: &mut EcsSnapshot,
+    ) -> Result<String> {
+        let name = target_group_name(deployment);
+        if let Some(tg) = snapshot.target_groups.get(&name) {
+            return Ok(tg.arn.clone());
+        }
+
+        let tags = resource_tags(project, deployment)
+            .into_iter()
+            .map(|(key, value)| {
+                aws_sdk_elasticloadbalancingv2::types::Tag::builder()
+                    .key(key)
+                    .value(value)
+                    .build()
+            })
+            .collect();
+        let output = self
+            .elb
+            .create_target_group()
+            .name(&name)
+            .protocol(ProtocolEnum::Http)
+            .port(deployment.http_port)
+            .vpc_id(&self.config.vpc_id)
+            .target_type(TargetTypeEnum::Ip)
+            .health_check_path(&self.config.health_check_path)
+            .matcher(Matcher::builder().http_code("200-499").build())
+            .set_tags(Some(tags))
+            .send()
+            .await
+            .context("Failed to create target group")?;
+        let arn = output
+            .target_groups()
+            .first()
+            .and_then(|tg| tg.target_group_arn())
+            .ok_or_else(|| anyhow::anyhow!("CreateTargetGroup returned no target group"))?
+            .to_string();
+
+        info!(
+            deployment_id = %deployment.deployment_id,
+            "Created target group {}", name
+        );
+        snapshot.target_groups.insert(
+            name,
+            ManagedTargetGroup {
+                arn: arn.clone(),
+                project: Some(project.name.clone()),
+            },
+        );
+        Ok(arn)
+    }
+
+    /// Create or update the listener rule of a deployment group.
+    ///
+    /// Returns the rule ARN, or `None` if no deployment of the group has a target group.
+    async fn sync_group_rule(
+        &self,
+        project: &Project,
+        deployment_group: &str,
+        group_deployments: &[&Deployment],
+        snapshot: &mut EcsSnapshot,
+    ) -> Result<Option<String>> {
+        let targets: Vec<(String, i32)> = forward_weights(group_deployments)
+            .into_iter()
+            .filter_map(|(deployment, weight)| {
+                snapshot
+                    .target_groups
+                    .get(&target_group_name(deployment))
+                    .map(|tg| (tg.arn.clone(), weight))
+            })
+            .collect();
+        if targets.is_empty() {
+            return Ok(None);
+        }
+
+        let rule_match = RuleMatch::from_url(&self.group_url(project, deployment_group));
+        let existing = snapshot
+            .rules
+            .iter()
+            .position(|rule| rule_match.matches(rule));
+
+        let rule = match existing {
+            Some(index) => {
+                let rule = &snapshot.rules[index];
+                if rule_forward_targets(rule) == targets {
+                    return Ok(rule.rule_arn().map(str::to_string));
+                }
+                let output = self
+                    .elb
+                    .modify_rule()
+                    .set_rule_arn(rule.rule_arn().map(str::to_string))
+                    .actions(forward_action(&targets))
+                    .send()
+                    .await
+                    .context("Failed to update listener rule")?;
+                let updated = output
+                    .rules()
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("ModifyRule returned no rule"))?;
+                snapshot.rules[index] = updated.clone();
+                updated
+            }
+            None => {
+                let output = self
+                    .elb
+                    .create_rule()
+                    .listener_arn(&self.config.listener_arn)
+                    .priority(lowest_free_priority(&snapshot.rules))
+                    .set_conditions(Some(rule_match.conditions()))
+                    .actions(forward_action(&targets))
+                    .send()
+                    .await
+                    .context("Failed to create listener rule")?;
+                let created = output
+                    .rules()
+                    .first()
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("CreateRule returned no rule"))?;
+                info!(
+                    project = %project.name,
+                    deployment_group = %deployment_group,
+                    "Created listener rule for {}", rule_match.host
+                );
+                snapshot.rules.push(created.clone());
+                created
+            }
+        };
+        Ok(rule.rule_arn().map(str::to_string))
+    }
+
+    async fn register_task_definition(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        image: String,
+        environment: Vec<KeyValuePair>,
+        secrets: Vec<aws_sdk_ecs::types::Secret>,
+    ) -> Result<String> {
+        let cpu_millicores = quantity::parse_cpu_millicores(&deployment.cpu)?;
+        let memory_bytes = quantity::parse_memory_bytes(&deployment.memory)?;
+        let (cpu, memory) = fargate_task_size(cpu_millicores, memory_bytes);
+
+        let log_configuration = LogConfiguration::builder()
+            .log_driver(LogDriver::Awslogs)
+            .options("awslogs-group", &self.config.log_group)
+            .options("awslogs-region", &self.config.region)
+            .options("awslogs-stream-prefix", service_name(project, deployment))
+            .build()?;
+        let container = ContainerDefinition::builder()
+            .name(CONTAINER_NAME)
+            .image(image)
+            // Kubernetes command/args map to the Docker ENTRYPOINT/CMD, which ECS
+            // calls entryPoint/command
+            .set_entry_point(deployment.command.clone())
+            .set_command(deployment.args.clone())
+            .essential(true)
+            .port_mappings(
+                PortMapping::builder()
+                    .container_port(deployment.http_port)
+                    .protocol(TransportProtocol::Tcp)
+                    .build(),
+            )
+            .set_environment(Some(environment))
+            .set_secrets((!secrets.is_empty()).then_some(secrets))
+            .log_configuration(log_configuration)
+            .build();
+
+        let output = self
+            .ecs
+            .register_task_definition()
+            .family(task_family(project))
+            .network_mode(NetworkMode::Awsvpc)
+            .requires_compatibilities(Compatibility::Fargate)
+            .cpu(cpu.to_string())
+            .memory(memory.to_string())
+            .execution_role_arn(&self.config.execution_role_arn)
+            .set_task_role_arn(self.config.task_role_arn.clone())
+            .container_definitions(container)
+            .set_tags(Some(ecs_tags(project, deployment)))
+            .send()
+            .await
+            .context("Failed to register task definition")?;
+        output
+            .task_definition()
+            .and_then(|td| td.task_definition_arn())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("RegisterTaskDefinition returned no ARN"))
+    }
+
+    /// Store the secret env vars of a deployment as a JSON object in Secrets Manager
+    /// and return the secret's ARN. The execution role reads it when tasks start.
+    async fn store_deployment_secrets(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        values: &BTreeMap<String, String>,
+    ) -> Result<String> {
+        let name = secret_name(&service_name(project, deployment));
+        let secret_string =
+            serde_json::to_string(values).context("Failed to serialize deployment secrets")?;
+        let tags = resource_tags(project, deployment)
+            .into_iter()
+            .map(|(key, value)| {
+                aws_sdk_secretsmanager::types::Tag::builder()
+                    .key(key)
+                    .value(value)
+                    .build()
+            })
+            .collect();
+
+        let created = self
+            .secrets
+            .create_secret()
+            .name(&name)
+            .secret_string(&secret_string)
+            .set_tags(Some(tags))
+            .send()
+            .await;
+        let arn = match created {
+            Ok(output) => output.arn().map(str::to_string),
+            // Left over from an earlier attempt whose service creation failed
+            Err(e)
+                if e.as_service_error()
+                    .is_some_and(|e| e.is_resource_exists_exception()) =>
+            {
+                self.secrets
+                    .put_secret_value()
+                    .secret_id(&name)
+                    .secret_string(&secret_string)
+                    .send()
+                    .await
+                    .context("Failed to update deployment secret")?
+                    .arn()
+                    .map(str::to_string)
+            }
+            Err(e) => return Err(e).context("Failed to create deployment secret"),
+        };
+        arn.ok_or_else(|| anyhow::anyhow!("Secrets Manager returned no secret ARN"))
+    }
+
+    async fn create_service(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        task_definition_arn: &str,
+        target_group_arn: &str,
+    ) -> Result<()> {
+        let network = NetworkConfiguration::builder()
+            .awsvpc_configuration(
+                AwsVpcConfiguration::builder()
+                    .set_subnets(Some(self.config.subnets.clone()))
+                    .set_security_groups(Some(self.config.security_groups.clone()))
+                    .assign_public_ip(if self.config.assign_public_ip {
+                        AssignPublicIp::Enabled
+                    } else {
+                        AssignPublicIp::Disabled
+                    })
+                    .build()?,
+            )
+            .build();
+
+        self.ecs
+            .create_service()
+            .cluster(&self.config.cluster)
+            .service_name(service_name(project, deployment))
+            .task_definition(task_definition_arn)
+            .desired_count(deployment.replicas)
+            .launch_type(LaunchType::Fargate)
+            .network_configuration(network)
+            .load_balancers(
+                LoadBalancer::builder()
+                    .target_group_arn(target_group_arn)
+                    .container_name(CONTAINER_NAME)
+                    .container_port(deployment.http_port)
+                    .build(),
+            )
+            .deployment_configuration(
+                DeploymentConfiguration::builder()
+                    .deployment_circuit_breaker(
+                        DeploymentCircuitBreaker::builder()
+                            .enable(true)
+                            .rollback(false)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .propagate_tags(PropagateTags::Service)
+            .set_tags(Some(ecs_tags(project, deployment)))
+            .send()
+            .await
+            .context("Failed to create ECS service")?;
+        Ok(())
+    }
+
+    /// Create the target group, listener rule attachment, task definition and
+    /// service of a deployment. If the service already exists, it is only synced
+    /// via [`Self::sync_service`].
+    async fn ensure_deployment_resources(
+        &self,
+        state: &AppState,
+        project: &Project,
+        deployment: &Deployment,
+        group_deployments: &[&Deployment],
+        snapshot: &mut EcsSnapshot,
+    ) -> Result<()> {
+        let name = service_name(project, deployment);
+        if let Some(service) = self.describe_service(&name).await? {
+            return self.sync_service(deployment, &service).await;
+        }
+
+        let target_group_arn = self
+            .ensure_target_group(project, deployment, snapshot)
+            .await?;
+        self.sync_group_rule(
+            project,
+            &deployment.deployment_group,
+            group_deployments,
+            snapshot,
+        )
+        .await?;
+
+        let env_vars = webhook::load_env_vars(state, project, deployment).await?;
+        let image =
+            crate::server::deployment::utils::get_deployment_image_tag(state, deployment, project)
+                .await;
+        let (environment, secret_values) = container_environment(env_vars);
+        let secrets = if secret_values.is_empty() {
+            Vec::new()
+        } else {
+            let secret_arn = self
+                .store_deployment_secrets(project, deployment, &secret_values)
+                .await?;
+            container_secrets(&secret_arn, secret_values.keys())?
+        };
+        let task_definition_arn = self
+            .register_task_definition(project, deployment, image, environment, secrets)
+            .await?;
+        self.create_service(project, deployment, &task_definition_arn, &target_group_arn)
+            .await?;
+
+        info!(
+            deployment_id = %deployment.deployment_id,
+            "Created ECS service {}", name
+        );
+        Ok(())
+    }
+
+    /// Apply replica changes and restarts (`rise deployment scale`/`restart`) to an
+    /// existing service
+    async fn sync_service(&self, deployment: &Deployment, service: &Service) -> Result<()> {
+        let name = service.service_name().unwrap_or_default();
+        let scale = service.desired_count() != deployment.replicas;
+
+        // Restart if requested after the service's current deployment started
+        let primary_started_at = service
+            .deployments()
+            .iter()
+            .find(|d| d.status() == Some("PRIMARY"))
+            .and_then(|d| d.created_at())
+            .and_then(|t| chrono::DateTime::from_timestamp(t.secs(), t.subsec_nanos()));
+        let restart = match (deployment.restarted_at, primary_started_at) {
+            (Some(restarted_at), Some(started_at)) => restarted_at > started_at,
+            _ => false,
+        };
+
+        if !scale && !restart {
+            return Ok(());
+        }
+
+        self.ecs
+            .update_service()
+            .cluster(&self.config.cluster)
+            .service(name)
+            .desired_count(deployment.replicas)
+            .force_new_deployment(restart)
+            .send()
+            .await
+            .context("Failed to update ECS service")?;
+        if scale {
+            info!(
+                deployment_id = %deployment.deployment_id,
+                "Scaled ECS service {} to {} tasks", name, deployment.replicas
+            );
+        }
+        if restart {
+            info!(
+                deployment_id = %deployment.deployment_id,
+                "Restarting tasks of ECS service {}", name
+            );
+        }
+        Ok(())
+    }
+
+    // ── Observation ────────────────────────────────────────────────────
+
+    async fn observe(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        snapshot: &EcsSnapshot,
+    ) -> Result<Option<ServiceObservation>> {
+        let Some(service) = self
+            .describe_service(&service_name(project, deployment))
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let rollout_failure = service
+            .deployments()
+            .iter()
+            .find(|d| d.status() == Some("PRIMARY"))
+            .filter(|d| d.rollout_state() == Some(&DeploymentRolloutState::Failed))
+            .map(|d| {
+                d.rollout_state_reason()
+                    .unwrap_or("rollout failed")
+                    .to_string()
+            });
+
+        let healthy_targets = match snapshot.target_groups.get(&target_group_name(deployment)) {
+            Some(tg) => self
+                .elb
+                .describe_target_health()
+                .target_group_arn(&tg.arn)
+                .send()
+                .await
+                .context("Failed to describe target health")?
+                .target_health_descriptions()
+                .iter()
+                .filter(|d| {
+                    d.target_health().and_then(|h| h.state())
+                        == Some(&TargetHealthStateEnum::Healthy)
+                })
+                .count() as i32,
+            None => 0,
+        };
+
+        Ok(Some(ServiceObservation {
+            running_count: service.running_count(),
+            desired_count: service.desired_count(),
+            healthy_targets,
+            rollout_failure,
+        }))
+    }
+
+    // ── Garbage collection ─────────────────────────────────────────────
+
+    /// Delete listener rules, services and target groups that are not desired.
+    async fn sweep(&self, snapshot: &EcsSnapshot, desired: &DesiredResources) -> Result<()> {
+        let protected = |project: Option<&String>| {
+            project.is_some_and(|p| desired.protected_projects.contains(p))
+        };
+        let managed_arns: HashMap<&str, &ManagedTargetGroup> = snapshot
+            .target_groups
+            .values()
+            .map(|tg| (tg.arn.as_str(), tg))
+            .collect();
+
+        // 1. Listener rules forwarding only to Rise target groups
+        for rule in &snapshot.rules {
+            let Some(rule_arn) = rule.rule_arn() else {
+                continue;
+            };
+            let targets = rule_forward_targets(rule);
+            if rule.is_default() == Some(true)
+                || targets.is_empty()
+                || desired.rules.contains(rule_arn)
+            {
+                continue;
+            }
+            let owners: Option<Vec<&ManagedTargetGroup>> = targets
+                .iter()
+                .map(|(arn, _)| managed_arns.get(arn.as_str()).copied())
+                .collect();
+            let Some(owners) = owners else {
+                continue;
+            };
+            if owners.iter().any(|tg| protected(tg.project.as_ref())) {
+                continue;
+            }
+            info!("Deleting unused listener rule {}", rule_arn);
+            if let Err(e) = self.elb.delete_rule().rule_arn(rule_arn).send().await {
+                warn!("Failed to delete listener rule {}: {:?}", rule_arn, e);
+            }
+        }
+
+        // 2. ECS services
+        let mut service_arns = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .ecs
+                .list_services()
+                .cluster(&self.config.cluster)
+                .set_next_token(next_token)
+                .send()
+                .await
+                .context("Failed to list ECS services")?;
+            service_arns.extend(output.service_arns().iter().cloned());
+            next_token = output.next_token().map(str::to_string);
+            if next_token.is_none() {
+                break;
+            }
+        }
+        for chunk in service_arns.chunks(10) {
+            let output = self
+                .ecs
+                .describe_services()
+                .cluster(&self.config.cluster)
+                .set_services(Some(chunk.to_vec()))
+                .include(ServiceField::Tags)
+                .send()
+                .await
+                .context("Failed to describe ECS services")?;
+            for service in output.services() {
+                let tag = |key: &str| {
+                    service
+                        .tags()
+                        .iter()
+                        .find(|t| t.key() == Some(key))
+                        .and_then(|t| t.value())
+                        .map(str::to_string)
+                };
+                let Some(name) = service.service_name() else {
+                    continue;
+                };
+                if service.status() != Some("ACTIVE")
+                    || tag(LABEL_MANAGED_BY).as_deref() != Some("rise")
+                    || desired.services.contains(name)
+                    || protected(tag(LABEL_PROJECT).as_ref())
+                {
+                    continue;
+                }
+                info!("Deleting unused ECS service {}", name);
+                if let Err(e) = self
+                    .ecs
+                    .delete_service()
+                    .cluster(&self.config.cluster)
+                    .service(name)
+                    .force(true)
+                    .send()
+                    .await
+                {
+                    warn!("Failed to delete ECS service {}: {:?}", name, e);
+                    continue;
+                }
+                self.delete_deployment_secret(name).await;
+            }
+        }
+
+        // 3. Target groups (deletion fails while a deleted service is still draining;
+        //    it is retried on the next pass)
+        for (name, tg) in &snapshot.target_groups {
+            if desired.target_groups.contains(name) || protected(tg.project.as_ref()) {
+                continue;
+            }
+            match self
+                .elb
+                .delete_target_group()
+                .target_group_arn(&tg.arn)
+                .send()
+                .await
+            {
+                Ok(_) => info!("Deleted unused target group {}", name),
+                Err(e) => debug!("Target group {} not deleted yet: {:?}", name, e),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl EcsBackend {
+    /// Delete the secret env vars stored for a deleted ECS service, if any
+    async fn delete_deployment_secret(&self, service_name: &str) {
+        let name = secret_name(service_name);
+        match self
+            .secrets
+            .delete_secret()
+            .secret_id(&name)
+            .force_delete_without_recovery(true)
+            .send()
+            .await
+        {
+            Ok(_) => info!("Deleted deployment secret {}", name),
+            Err(e)
+                if e.as_service_error()
+                    .is_some_and(|e| e.is_resource_not_found_exception()) => {}
+            Err(e) => warn!("Failed to delete deployment secret {}: {:?}", name, e),
+        }
+    }
+}
+
+fn ecs_tags(project: &Project, deployment: &Deployment) -> Vec<aws_sdk_ecs::types::Tag> {
+    resource_tags(project, deployment)
+        .into_iter()
+        .map(|(key, value)| {
+            aws_sdk_ecs::types::Tag::builder()
+                .key(key)
+                .value(value)
+                .build()
+        })
+        .collect()
+}
+
+/// A CloudWatch log event of a deployment
+struct LogEvent {
+    id: String,
+    timestamp: i64,
+    message: String,
+}
+
+async fn fetch_log_events(
+    client: &aws_sdk_cloudwatchlogs::Client,
+    log_group: &str,
+    stream_prefix: &str,
+    start_time: Option<i64>,
+) -> Result<Vec<LogEvent>> {
+    let mut events = Vec::new();
+    let mut next_token = None;
+    loop {
+        let output = client
+            .filter_log_events()
+            .log_group_name(log_group)
+            .log_stream_name_prefix(stream_prefix)
+            .set_start_time(start_time)
+            .set_next_token(next_token)
+            .send()
+            .await
+            .context("Failed to fetch CloudWatch log events")?;
+        events.extend(output.events().iter().filter_map(|event| {
+            Some(LogEvent {
+                id: event.event_id()?.to_string(),
+                timestamp: event.timestamp()?,
+                message: event.message().unwrap_or_default().to_string(),
+            })
+        }));
+        next_token = output.next_token().map(str::to_string);
+        if next_token.is_none() {
+            break;
+        }
+    }
+    events.sort_by_key(|event| event.timestamp);
+    Ok(events)
+}
+
+fn format_log_event(event: &LogEvent, timestamps: bool) -> String {
+    let mut line = if timestamps {
+        let time = chrono::DateTime::from_timestamp_millis(event.timestamp)
+            .unwrap_or_default()
+            .to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        format!("{} {}", time, event.message)
+    } else {
+        event.message.clone()
+    };
+    if !line.ends_with('\n') {
+        line.push('\n');
+    }
+    line
+}
+
+#[async_trait]
+impl DeploymentBackend for EcsBackend {
+    async fn get_deployment_urls(
+        &self,
+        deployment: &Deployment,
+        project: &Project,
+    ) -> Result<DeploymentUrls> {
+        Ok(self.urls_for_group(project, &deployment.deployment_group))
+    }
+
+    async fn get_project_urls(
+        &self,
+        project: &Project,
+        deployment_group: &str,
+    ) -> Result<DeploymentUrls> {
+        Ok(self.urls_for_group(project, deployment_group))
+    }
+
+    async fn stream_logs(
+        &self,
+        deployment: &Deployment,
+        project: &Project,
+        follow: bool,
+        tail_lines: Option<i64>,
+        timestamps: bool,
+        since_seconds: Option<i64>,
+        _all_pods: bool,
+    ) -> Result<futures::stream::BoxStream<'static, Result<bytes::Bytes, anyhow::Error>>> {
+        use futures::StreamExt;
+
+        // Log streams of all tasks are always merged
+        let client = self.logs.clone();
+        let log_group = self.config.log_group.clone();
+        // awslogs streams are named "<prefix>/<container>/<task-id>"
+        let stream_prefix = format!("{}/", service_name(project, deployment));
+        let mut start_time =
+            since_seconds.map(|seconds| Utc::now().timestamp_millis() - seconds * 1000);
+
+        let stream = async_stream::stream! {
+            // Event IDs already emitted at the latest timestamp, since polling
+            // from that timestamp returns them again
+            let mut seen_at_latest: HashSet<String> = HashSet::new();
+            let mut first = true;
+            loop {
+                let events = match fetch_log_events(&client, &log_group, &stream_prefix, start_time).await {
+                    Ok(events) => events,
+                    Err(e) => {
+                        yield Err(e);
+                        break;
+                    }
+                };
+                let mut events: VecDeque<LogEvent> = events.into();
+                if first {
+                    if let Some(tail) = tail_lines {
+                        while events.len() as i64 > tail.max(0) {
+                            events.pop_front();
+                        }
+                    }
+                    first = false;
+                }
+                for event in events {
+                    if start_time == Some(event.timestamp) && seen_at_latest.contains(&event.id) {
+                        continue;
+                    }
+                    if start_time != Some(event.timestamp) {
+                        start_time = Some(event.timestamp);
+                        seen_at_latest.clear();
+                    }
+                    seen_at_latest.insert(event.id.clone());
+                    yield Ok(bytes::Bytes::from(format_log_event(&event, timestamps)));
+                }
+                if !follow {
+                    break;
+                }
+                tokio::time::sleep(LOG_POLL_INTERVAL).await;
+            }
+        };
+
+        Ok(stream.boxed())
+    }
+}
+
+// ── Reconciliation ─────────────────────────────────────────────────────
+
+/// Drives deployment status for the ECS backend
+///
+/// Runs on the leader only and, on every pass:
+/// 1. Advances each non-terminal deployment (Pushed → Deploying → Healthy/Failed, ...)
+/// 2. Creates ECS services for Deploying deployments and updates listener rules
+/// 3. Deletes ECS resources that no deployment needs anymore
+pub struct EcsController {
+    state: AppState,
+    backend: Arc<EcsBackend>,
+    election: LeaderElection,
+}
+
+impl EcsController {
+    pub fn new(state: AppState, backend: Arc<EcsBackend>) -> Self {
+        let election = LeaderElection::spawn(
+            state.db_pool.clone(),
+            "rise-ecs-controller",
+            Uuid::new_v4(),
+            Duration::from_secs(60),
+        );
+        Self {
+            state,
+            backend,
+            election,
+        }
+    }
+
+    /// Start the reconcile loop
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            self.reconcile_loop().await;
+        });
+    }
+
+    async fn reconcile_loop(&self) {
+        info!("ECS reconcile loop started");
+        let mut ticker = interval(self.backend.config.reconcile_interval);
+
+        loop {
+            ticker.tick().await;
+
+            if !self.election.is_leader() {
+                continue;
+            }
+
+            let started = std::time::Instant::now();
+            let result = self.reconcile().await;
+            metrics::record_reconcile("ecs", started.elapsed(), result.is_err());
+            if let Err(e) = result {
+                error!("Error in ECS reconcile loop: {:#}", e);
+            }
+        }
+    }
+
+    async fn reconcile(&self) -> Result<()> {
+        let mut snapshot = self.backend.load_snapshot().await?;
+        let mut desired = DesiredResources::default();
+
+        for project in db_projects::list(&self.state.db_pool, None).await? {
+            if let Err(e) = self
+                .reconcile_project(&project, &mut snapshot, &mut desired)
+                .await
+            {
+                warn!(
+                    project = %project.name,
+                    "Failed to reconcile ECS resources: {:#}", e
+                );
+                // Without a complete view of the project, sweeping could delete live services
+                desired.protected_projects.insert(project.name.clone());
+            }
+        }
+
+        self.backend.sweep(&snapshot, &desired).await
+    }
+
+    async fn reconcile_project(
+        &self,
+        project: &Project,
+        snapshot: &mut EcsSnapshot,
+        desired: &mut DesiredResources,
+    ) -> Result<()> {
+        let pool = &self.state.db_pool;
+
+        let deployments = db_deployments::list_non_terminal_for_project(pool, project.id).await?;
+        for deployment in &deployments {
+            self.transition(project, deployment, &deployments, snapshot)
+                .await?;
+        }
+        let non_terminal: Vec<&Deployment> = deployments.iter().collect();
+        webhook::check_expirations(&self.state, &non_terminal, project).await?;
+
+        // Desired resources follow the statuses after this pass's transitions
+        let deployments = db_deployments::list_non_terminal_for_project(pool, project.id).await?;
+        let mut groups: HashMap<&str, Vec<&Deployment>> = HashMap::new();
+        for deployment in deployments.iter().filter(|d| has_infrastructure(d)) {
+            desired.services.insert(service_name(project, deployment));
+            desired.target_groups.insert(target_group_name(deployment));
+            groups
+                .entry(deployment.deployment_group.as_str())
+                .or_default()
+                .push(deployment);
+        }
+        for (group, group_deployments) in groups {
+            if let Some(rule_arn) = self
+                .backend
+                .sync_group_rule(project, group, &group_deployments, snapshot)
+                .await?
+            {
+                desired.rules.insert(rule_arn);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn transition(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        non_terminal: &[Deployment],
+        snapshot: &mut EcsSnapshot,
+    ) -> Result<()> {
+        let state = &self.state;
+        match deployment.status {
+            // The CLI drives pre-infrastructure transitions
+            DeploymentStatus::Pending | DeploymentStatus::Building | DeploymentStatus::Pushing => {
+                webhook::check_pre_pushed_timeout(state, deployment).await?;
+            }
+
+            // Resources of cancelled/terminated deployments are removed by the sweep
+            DeploymentStatus::Cancelling => {
+                info!(
+                    deployment_id = %deployment.deployment_id,
+                    "Cancelling deployment — marking as Cancelled"
+                );
+                db_deployments::mark_cancelled(&state.db_pool, deployment.id).await?;
+                db_projects::update_calculated_status(&state.db_pool, project.id).await?;
+            }
+            DeploymentStatus::Terminating => {
+                webhook::complete_termination(state, deployment, project).await?;
+            }
+
+            DeploymentStatus::Pushed => {
+                // Stay in Pushed while the concurrency limits are reached
+                if !webhook::may_start_deploying(state, deployment).await? {
+                    return Ok(());
+                }
+                info!(
+                    deployment_id = %deployment.deployment_id,
+                    "Deployment image pushed, transitioning to Deploying"
+                );
+                db_deployments::update_status(
+                    &state.db_pool,
+                    deployment.id,
+                    DeploymentStatus::Deploying,
+                )
+                .await?;
+                db_projects::update_calculated_status(&state.db_pool, project.id).await?;
+            }
+
+            DeploymentStatus::Deploying => {
+                // Never promote a deployment that just timed out
+                if webhook::check_deploying_timeout(state, deployment, project).await? {
+                    return Ok(());
+                }
+                let group_deployments: Vec<&Deployment> = non_terminal
+                    .iter()
+                    .filter(|d| {
+                        d.deployment_group == deployment.deployment_group
+                            && (d.id == deployment.id || has_infrastructure(d))
+                    })
+                    .collect();
+                if let Err(e) = self
+                    .backend
+                    .ensure_deployment_resources(
+                        state,
+                        project,
+                        deployment,
+                        &group_deployments,
+                        snapshot,
+                    )
+                    .await
+                {
+                    // Retried on the next pass until the Deploying timeout fails the deployment
+                    warn!(
+                        deployment_id = %deployment.deployment_id,
+                        "Failed to create ECS resources: {:#}", e
+                    );
+                    return Ok(());
+                }
+                self.check_health(project, deployment, snapshot).await?;
+            }
+
+            DeploymentStatus::Healthy | DeploymentStatus::Unhealthy => {
+                if deployment.needs_reconcile {
+                    let name = service_name(project, deployment);
+                    if let Some(service) = self.backend.describe_service(&name).await? {
+                        if let Err(e) = self.backend.sync_service(deployment, &service).await {
+                            warn!(
+                                deployment_id = %deployment.deployment_id,
+                                "Failed to sync ECS service: {:#}", e
+                            );
+                            return Ok(());
+                        }
+                    }
+                    db_deployments::clear_needs_reconcile(&state.db_pool, deployment.id).await?;
+                }
+                self.check_health(project, deployment, snapshot).await?;
+            }
+
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles Deploying → Healthy/Failed, Healthy → Unhealthy and Unhealthy → Healthy
+    async fn check_health(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        snapshot: &EcsSnapshot,
+    ) -> Result<()> {
+        let pool = &self.state.db_pool;
+        let Some(observation) = self.backend.observe(project, deployment, snapshot).await? else {
+            debug!(
+                deployment_id = %deployment.deployment_id,
+                "No ECS service yet"
+            );
+            return Ok(());
+        };
+        let health = evaluate_service_health(&observation);
+
+        let mut metadata = ControllerMetadata::from_value(&deployment.controller_metadata);
+        metadata.ecs = Some(EcsServiceStatus {
+            service: service_name(project, deployment),
+            running_count: observation.running_count,
+            desired_count: observation.desired_count,
+            healthy_targets: observation.healthy_targets,
+        });
+        metadata.set_health(health == EcsHealth::Ready);
+        if let Err(e) =
+            db_deployments::update_controller_metadata(pool, deployment.id, &metadata.to_value())
+                .await
+        {
+            warn!(
+                deployment_id = %deployment.deployment_id,
+                "Failed to update controller metadata: {:?}", e
+            );
+        }
+
+        match (&deployment.status, health) {
+            (DeploymentStatus::Deploying, EcsHealth::Failed(msg)) => {
+                warn!(deployment_id = %deployment.deployment_id, "{}", msg);
+                metrics::record_health_check_failure("ecs");
+                db_deployments::mark_failed(pool, deployment.id, &msg).await?;
+                db_projects::update_calculated_status(pool, project.id).await?;
+            }
+            (DeploymentStatus::Deploying, EcsHealth::Ready) => {
+                info!(
+                    deployment_id = %deployment.deployment_id,
+                    "ECS service is ready, marking as Healthy"
+                );
+                webhook::handle_deployment_became_healthy(&self.state, deployment, project).await?;
+            }
+            (DeploymentStatus::Healthy, EcsHealth::Failed(msg) | EcsHealth::NotReady(msg)) => {
+                warn!(
+                    deployment_id = %deployment.deployment_id,
+                    "Healthy deployment is now unhealthy: {}", msg
+                );
+                metrics::record_health_check_failure("ecs");
+                db_deployments::mark_unhealthy(pool, deployment.id, msg).await?;
+                db_projects::update_calculated_status(pool, project.id).await?;
+            }
+            (DeploymentStatus::Unhealthy, EcsHealth::Ready) => {
+                info!(
+                    deployment_id = %deployment.deployment_id,
+                    "Unhealthy deployment has recovered, marking as Healthy"
+                );
+                db_deployments::mark_healthy(pool, deployment.id).await?;
+                db_projects::update_calculated_status(pool, project.id).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Whether a deployment should currently have ECS resources
+fn has_infrastructure(deployment: &Deployment) -> bool {
+    matches!(
+        deployment.status,
+        DeploymentStatus::Deploying | DeploymentStatus::Healthy | DeploymentStatus::Unhealthy
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::EnvVar;
+    use k8s_openapi::ByteString;
+
+    fn test_deployment(deployment_id: &str, is_active: bool, age_minutes: i64) -> Deployment {
+        let created_at = Utc::now() - chrono::Duration::minutes(age_minutes);
+        Deployment {
+            id: Uuid::new_v4(),
+            deployment_id: deployment_id.to_string(),
+            project_id: Uuid::new_v4(),
+            created_by_id: Uuid::new_v4(),
+            status: DeploymentStatus::Healthy,
+            deployment_group: "default".to_string(),
+            environment_id: None,
+            expires_at: None,
+            termination_reason: None,
+            completed_at: None,
+            error_message: None,
+            build_logs: None,
+            controller_metadata: serde_json::json!({}),
+            image: None,
+            image_digest: None,
+            rolled_back_from_deployment_id: None,
+            http_port: 8080,
+            needs_reconcile: false,
+            is_active,
+            deploying_started_at: None,
+            first_healthy_at: None,
+            job_url: None,
+            pull_request_url: None,
+            message: None,
+            replicas: 1,
+            cpu: "500m".to_string(),
+            memory: "256Mi".to_string(),
+            cpu_limit: None,
+            memory_limit: None,
+            health_check: None,
+            init_container: None,
+            protocol: "http".to_string(),
+            labels: None,
+            annotations: None,
+            command: None,
+            args: None,
+            ingress_annotations: None,
+            strategy: "rolling".to_string(),
+            drain_period_seconds: None,
+            volume: None,
+            scheduling: None,
+            image_pull_policy: None,
+            rate_limit: None,
+            extra_ports: None,
+            service_account_name: None,
+            restarted_at: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_fargate_task_size() {
+        const MIB: u64 = 1024 * 1024;
+        assert_eq!(fargate_task_size(250, 256 * MIB), (256, 512));
+        assert_eq!(fargate_task_size(500, 256 * MIB), (512, 1024));
+        assert_eq!(fargate_task_size(1000, 3 * 1024 * MIB), (1024, 3072));
+        // Memory beyond the CPU size's range bumps the CPU size
+        assert_eq!(fargate_task_size(250, 3 * 1024 * MIB), (512, 3072));
+        assert_eq!(fargate_task_size(2000, 17 * 1024 * MIB), (4096, 17408));
+        assert_eq!(fargate_task_size(64000, 512 * 1024 * MIB), (16384, 122880));
+    }
+
+    #[test]
+    fn test_target_group_name_fits_limit() {
+        let deployment = test_deployment("20250101-120000", false, 0);
+        let name = target_group_name(&deployment);
+        assert_eq!(name.len(), 32);
+        assert!(name.starts_with(TARGET_GROUP_PREFIX));
+    }
+
+    #[test]
+    fn test_rule_match_from_url() {
+        assert_eq!(
+            RuleMatch::from_url("myapp.apps.example.com"),
+            RuleMatch {
+                host: "myapp.apps.example.com".to_string(),
+                path_patterns: vec![],
+            }
+        );
+        assert_eq!(
+            RuleMatch::from_url("example.com/myapp/"),
+            RuleMatch {
+                host: "example.com".to_string(),
+                path_patterns: vec!["/myapp".to_string(), "/myapp/*".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_rule_match_matches_own_conditions_only() {
+        let sub_path = RuleMatch::from_url("example.com/myapp");
+        let rule = Rule::builder()
+            .set_conditions(Some(sub_path.conditions()))
+            .build();
+        assert!(sub_path.matches(&rule));
+        assert!(!RuleMatch::from_url("example.com").matches(&rule));
+        assert!(!RuleMatch::from_url("example.com/other").matches(&rule));
+    }
+
+    #[test]
+    fn test_forward_weights_prefer_active_then_newest() {
+        let old_active = test_deployment("old", true, 30);
+        let newer = test_deployment("newer", false, 10);
+        let newest = test_deployment("newest", false, 1);
+
+        let weights = forward_weights(&[&newer, &old_active, &newest]);
+        let ids: Vec<(&str, i32)> = weights
+            .iter()
+            .map(|(d, w)| (d.deployment_id.as_str(), *w))
+            .collect();
+        assert_eq!(ids, vec![("old", 1), ("newest", 0), ("newer", 0)]);
+
+        // Without an active deployment, the newest one receives traffic
+        let weights = forward_weights(&[&newer, &newest]);
+        assert_eq!(weights[0].0.deployment_id, "newest");
+        assert_eq!(weights[0].1, 1);
+    }
+
+    #[test]
+    fn test_forward_action_round_trip() {
+        let targets = vec![("arn:tg/a".to_string(), 1), ("arn:tg/b".to_string(), 0)];
+        let rule = Rule::builder().actions(forward_action(&targets)).build();
+        assert_eq!(rule_forward_targets(&rule), targets);
+    }
+
+    #[test]
+    fn test_lowest_free_priority() {
+        let rule = |priority: &str| Rule::builder().priority(priority).build();
+        assert_eq!(lowest_free_priority(&[]), 1);
+        assert_eq!(
+            lowest_free_priority(&[rule("1"), rule("2"), rule("4"), rule("default")]),
+            3
+        );
+    }
+
+    #[test]
+    fn test_container_environment_keeps_secrets_out_of_environment() {
+        let mut resolved = ResolvedDeploymentEnvVars::default();
+        resolved.plain_env_vars.push(EnvVar {
+            name: "PORT".to_string(),
+            value: Some("8080".to_string()),
+            ..Default::default()
+        });
+        resolved
+            .secret_env_vars
+            .insert("API_KEY".to_string(), ByteString(b"s3cret".to_vec()));
+
+        let (env, secrets) = container_environment(resolved);
+        let env: Vec<(&str, &str)> = env
+            .iter()
+            .map(|kv| (kv.name().unwrap(), kv.value().unwrap()))
+            .collect();
+        assert_eq!(env, vec![("PORT", "8080")]);
+        assert_eq!(
+            secrets,
+            BTreeMap::from([("API_KEY".to_string(), "s3cret".to_string())])
+        );
+
+        let arn = "arn:aws:secretsmanager:eu-west-1:123456789012:secret:rise/demo-1-AbCdEf";
+        let refs = container_secrets(arn, secrets.keys()).unwrap();
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].name(), "API_KEY");
+        assert_eq!(refs[0].value_from(), format!("{}:API_KEY::", arn));
+    }
+
+    #[test]
+    fn test_evaluate_service_health() {
+        let observation = |running, healthy_targets| ServiceObservation {
+            running_count: running,
+            desired_count: 2,
+            healthy_targets,
+            rollout_failure: None,
+        };
+        assert_eq!(
+            evaluate_service_health(&observation(2, 2)),
+            EcsHealth::Ready
+        );
+        assert!(matches!(
+            evaluate_service_health(&observation(2, 1)),
+            EcsHealth::NotReady(_)
+        ));
+        let failed = ServiceObservation {
+            rollout_failure: Some("tasks failed to start".to_string()),
+            ..observation(0, 0)
+        };
+        assert!(matches!(
+            evaluate_service_health(&failed),
+            EcsHealth::Failed(msg) if msg.contains("tasks failed to start")
+        ));
+    }
+}
@@ -2,16 +2,22 @@
 //!
 //! All reconciliation, health checks, and infrastructure management are handled
 //! by the Metacontroller sync webhook. This module provides only the remaining
-//! operations needed by HTTP handlers: log streaming, URL computation, and
-//! environment cleanup.
+//! operations needed by HTTP handlers: log streaming, events, URL computation,
+//! and environment cleanup.
 
-use super::{DeploymentBackend, DeploymentUrls};
+use super::{DeploymentBackend, DeploymentEvent, DeploymentUrls};
 use crate::db::models::{Deployment, Project};
 use crate::server::deployment::resource_builder::ResourceBuilder;
 use anyhow::Result;
 use async_trait::async_trait;
+use k8s_openapi::api::core::v1::{Event, ObjectReference, Pod};
+use kube::api::{Api, ListParams, LogParams};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::{AbortHandle, JoinSet};
 
 /// Slim Kubernetes backend wrapping ResourceBuilder and kube client.
 ///
@@ -39,8 +45,6 @@ impl KubernetesBackend {
 
     /// Test Kubernetes API connectivity by listing pods (ClusterRole grants pod read access)
     pub async fn test_connection(&self) -> Result<()> {
-        use k8s_openapi::api::core::v1::Pod;
-        use kube::api::Api;
         let pod_api: Api<Pod> = Api::all(self.kube_client.clone());
         pod_api
             .list(&kube::api::ListParams::default().limit(1))
@@ -50,6 +54,42 @@ impl KubernetesBackend {
     }
 }
 
+/// Factory registering the Kubernetes backend under `type: kubernetes`
+pub struct KubernetesBackendFactory;
+
+#[async_trait]
+impl super::DeploymentBackendFactory for KubernetesBackendFactory {
+    fn backend_type(&self) -> &'static str {
+        "kubernetes"
+    }
+
+    async fn create(&self, ctx: &super::BackendContext<'_>) -> Result<Arc<dyn DeploymentBackend>> {
+        if !matches!(
+            ctx.settings,
+            crate::server::settings::DeploymentControllerSettings::Kubernetes { .. }
+        ) {
+            anyhow::bail!("Kubernetes backend requires kubernetes deployment_controller settings");
+        }
+
+        let resource_builder = ctx
+            .resource_builder
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Kubernetes resource builder not initialized"))?;
+        let kube_client = ctx
+            .kube_client
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Kubernetes client not initialized"))?;
+
+        let backend = KubernetesBackend::new(kube_client, resource_builder, ctx.db_pool.clone());
+
+        // Test Kubernetes API connection
+        backend.test_connection().await?;
+        tracing::info!("Kubernetes deployment backend initialized and connection tested");
+
+        Ok(Arc::new(backend))
+    }
+}
+
 #[async_trait]
 impl DeploymentBackend for KubernetesBackend {
     async fn get_deployment_urls(
@@ -116,33 +156,21 @@ impl DeploymentBackend for KubernetesBackend {
         tail_lines: Option<i64>,
         timestamps: bool,
         since_seconds: Option<i64>,
+        all_pods: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<bytes::Bytes, anyhow::Error>>> {
         use futures::StreamExt;
-        use k8s_openapi::api::core::v1::Pod;
-        use kube::api::{Api, ListParams, LogParams};
 
         // Derive namespace from project name
         let namespace = self.resource_builder.namespace_name(project);
 
-        // Find pod using label selector
+        // Find pods using label selector
         let pod_api: Api<Pod> = Api::namespaced(self.kube_client.clone(), &namespace);
-        let pods = pod_api
-            .list(&ListParams::default().labels(&format!(
-                "rise.dev/deployment-id={}",
-                deployment.deployment_id
-            )))
-            .await?;
-
-        let pod = pods
-            .items
-            .first()
-            .ok_or_else(|| anyhow::anyhow!("Pod not found - deployment may not be ready yet"))?;
+        let selector = format!("rise.dev/deployment-id={}", deployment.deployment_id);
+        let pod_names = list_pod_names(&pod_api, &selector).await?;
 
-        let pod_name = pod
-            .metadata
-            .name
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Pod name not found"))?
+        let pod_name = pod_names
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("Pod not found - deployment may not be ready yet"))?
             .clone();
 
         // Build LogParams
@@ -158,6 +186,12 @@ impl DeploymentBackend for KubernetesBackend {
             log_params.since_seconds = Some(since);
         }
 
+        if all_pods {
+            return Ok(stream_all_pod_logs(
+                pod_api, selector, log_params, pod_names,
+            ));
+        }
+
         // Stream logs from pod
         let mut log_stream = pod_api.log_stream(&pod_name, &log_params).await?;
 
@@ -181,4 +215,313 @@ impl DeploymentBackend for KubernetesBackend {
 
         Ok(stream.boxed())
     }
+
+    async fn list_events(
+        &self,
+        deployment: &Deployment,
+        project: &Project,
+    ) -> Result<Vec<DeploymentEvent>> {
+        let namespace = self.resource_builder.namespace_name(project);
+        let event_api: Api<Event> = Api::namespaced(self.kube_client.clone(), &namespace);
+        let workload_name = ResourceBuilder::deployment_name(project, deployment);
+
+        let mut events: Vec<DeploymentEvent> = event_api
+            .list(&ListParams::default())
+            .await?
+            .items
+            .into_iter()
+            .filter(|event| involves_workload(&event.involved_object, &workload_name))
+            .map(to_deployment_event)
+            .collect();
+        events.sort_by_key(|event| event.last_seen);
+        Ok(events)
+    }
+}
+
+/// Whether an event is about the deployment's Kubernetes Deployment, or one of its
+/// ReplicaSets or pods, whose names are derived from the Deployment's name
+fn involves_workload(object: &ObjectReference, workload_name: &str) -> bool {
+    let Some(name) = object.name.as_deref() else {
+        return false;
+    };
+    match object.kind.as_deref() {
+        Some("Deployment") => name == workload_name,
+        Some("ReplicaSet" | "Pod") => name
+            .strip_prefix(workload_name)
+            .is_some_and(|suffix| suffix.starts_with('-')),
+        _ => false,
+    }
+}
+
+fn to_deployment_event(event: Event) -> DeploymentEvent {
+    // Newer events only set event_time (or series.last_observed_time for repeats)
+    let last_seen = event
+        .series
+        .as_ref()
+        .and_then(|series| series.last_observed_time.as_ref())
+        .and_then(k8s_time_to_utc)
+        .or_else(|| event.last_timestamp.as_ref().and_then(k8s_time_to_utc))
+        .or_else(|| event.event_time.as_ref().and_then(k8s_time_to_utc))
+        .or_else(|| event.first_timestamp.as_ref().and_then(k8s_time_to_utc));
+    let count = event
+        .series
+        .as_ref()
+        .and_then(|series| series.count)
+        .or(event.count)
+        .unwrap_or(1);
+
+    DeploymentEvent {
+        event_type: event.type_.unwrap_or_else(|| "Normal".to_string()),
+        reason: event.reason.unwrap_or_default(),
+        message: event.message.unwrap_or_default().trim().to_string(),
+        object: format!(
+            "{}/{}",
+            event.involved_object.kind.unwrap_or_default(),
+            event.involved_object.name.unwrap_or_default()
+        ),
+        count,
+        last_seen,
+    }
+}
+
+/// Convert a Kubernetes `Time`/`MicroTime` through its RFC 3339 serialization, which
+/// doesn't depend on the datetime library backing k8s-openapi
+pub(super) fn k8s_time_to_utc<T: serde::Serialize>(
+    time: &T,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    serde_json::to_value(time).ok()?.as_str()?.parse().ok()
+}
+
+/// How often a followed multi-pod log stream looks for new and deleted pods
+const POD_LOG_RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+
+enum PodLogEvent {
+    Line {
+        pod: String,
+        line: String,
+    },
+    /// The pod's log stream ended; `opened` is false if it could not be opened at all
+    Ended {
+        pod: String,
+        opened: bool,
+    },
+}
+
+async fn list_pod_names(pod_api: &Api<Pod>, selector: &str) -> Result<Vec<String>> {
+    let pods = pod_api
+        .list(&ListParams::default().labels(selector))
+        .await?;
+    Ok(pods
+        .items
+        .into_iter()
+        .filter_map(|pod| pod.metadata.name)
+        .collect())
+}
+
+/// Merge the log streams of all pods matching `selector`, line by line
+///
+/// Lines are prefixed with the pod name while more than one pod is streamed. When
+/// following, pods are re-listed periodically: new pods (e.g. from a rolling update)
+/// are picked up, and streams of deleted pods are dropped without ending the stream.
+fn stream_all_pod_logs(
+    pod_api: Api<Pod>,
+    selector: String,
+    log_params: LogParams,
+    initial_pods: Vec<String>,
+) -> futures::stream::BoxStream<'static, Result<bytes::Bytes, anyhow::Error>> {
+    use futures::StreamExt;
+
+    let stream = async_stream::stream! {
+        let follow = log_params.follow;
+        let (tx, mut rx) = mpsc::channel(1024);
+        // Dropping the JoinSet (client disconnected) aborts all pod streams
+        let mut tasks = JoinSet::new();
+        let mut active: HashMap<String, AbortHandle> = HashMap::new();
+        // Pods whose stream ended while the pod still existed (e.g. container restart)
+        let mut ended_at: HashMap<String, Instant> = HashMap::new();
+
+        for pod in initial_pods {
+            let handle = tasks.spawn(forward_pod_log(
+                pod_api.clone(),
+                pod.clone(),
+                log_params.clone(),
+                tx.clone(),
+            ));
+            active.insert(pod, handle);
+        }
+
+        let mut rescan = tokio::time::interval(POD_LOG_RESCAN_INTERVAL);
+        rescan.tick().await;
+
+        loop {
+            tokio::select! {
+                event = rx.recv() => match event {
+                    Some(PodLogEvent::Line { pod, line }) => {
+                        let line = if active.len() > 1 {
+                            format!("[{}] {}\n", pod, line)
+                        } else {
+                            format!("{}\n", line)
+                        };
+                        yield Ok(bytes::Bytes::from(line));
+                    }
+                    Some(PodLogEvent::Ended { pod, opened }) => {
+                        active.remove(&pod);
+                        if opened {
+                            ended_at.insert(pod, Instant::now());
+                        }
+                        if !follow && active.is_empty() {
+                            break;
+                        }
+                    }
+                    None => break,
+                },
+                _ = rescan.tick(), if follow => {
+                    let pods = match list_pod_names(&pod_api, &selector).await {
+                        Ok(pods) => pods,
+                        Err(e) => {
+                            tracing::warn!("Failed to list pods for log stream: {:?}", e);
+                            continue;
+                        }
+                    };
+
+                    active.retain(|pod, handle| {
+                        let exists = pods.contains(pod);
+                        if !exists {
+                            handle.abort();
+                        }
+                        exists
+                    });
+                    ended_at.retain(|pod, _| pods.contains(pod));
+
+                    for pod in pods {
+                        if active.contains_key(&pod) {
+                            continue;
+                        }
+                        // New pods are streamed from the start; reconnected pods
+                        // from where their previous stream ended
+                        let mut params = log_params.clone();
+                        params.tail_lines = None;
+                        params.since_seconds = ended_at
+                            .get(&pod)
+                            .map(|ended| ended.elapsed().as_secs().max(1) as i64);
+                        let handle = tasks.spawn(forward_pod_log(
+                            pod_api.clone(),
+                            pod.clone(),
+                            params,
+                            tx.clone(),
+                        ));
+                        active.insert(pod, handle);
+                    }
+                }
+            }
+        }
+    };
+
+    stream.boxed()
+}
+
+/// Forward the log lines of a single pod until its stream ends
+async fn forward_pod_log(
+    pod_api: Api<Pod>,
+    pod: String,
+    log_params: LogParams,
+    tx: mpsc::Sender<PodLogEvent>,
+) {
+    use futures::{AsyncBufReadExt, StreamExt};
+
+    let opened = match pod_api.log_stream(&pod, &log_params).await {
+        Ok(log_stream) => {
+            let mut lines = Box::pin(log_stream.lines());
+            while let Some(line) = lines.next().await {
+                match line {
+                    Ok(line) => {
+                        let event = PodLogEvent::Line {
+                            pod: pod.clone(),
+                            line,
+                        };
+                        if tx.send(event).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::debug!(pod = %pod, "Pod log stream error: {}", e);
+                        break;
+                    }
+                }
+            }
+            true
+        }
+        Err(e) => {
+            tracing::debug!(pod = %pod, "Failed to stream pod logs: {}", e);
+            false
+        }
+    };
+
+    let _ = tx.send(PodLogEvent::Ended { pod, opened }).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(kind: &str, name: &str) -> ObjectReference {
+        ObjectReference {
+            kind: Some(kind.to_string()),
+            name: Some(name.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn events_are_matched_to_the_deployments_workload() {
+        let workload = "demo-20260502-000000";
+
+        assert!(involves_workload(&object("Deployment", workload), workload));
+        assert!(involves_workload(
+            &object("ReplicaSet", "demo-20260502-000000-7d9f8"),
+            workload
+        ));
+        assert!(involves_workload(
+            &object("Pod", "demo-20260502-000000-7d9f8-abcde"),
+            workload
+        ));
+
+        assert!(!involves_workload(
+            &object("Pod", "demo-20260502-000001-7d9f8-abcde"),
+            workload
+        ));
+        assert!(!involves_workload(
+            &object("Deployment", "demo-20260502-000000-canary"),
+            workload
+        ));
+        assert!(!involves_workload(&object("Ingress", "default"), workload));
+    }
+
+    #[test]
+    fn events_prefer_the_latest_timestamp() {
+        let event: Event = serde_json::from_value(serde_json::json!({
+            "metadata": {"name": "demo.1"},
+            "involvedObject": {"kind": "Pod", "name": "demo-20260502-000000-7d9f8-abcde"},
+            "type": "Warning",
+            "reason": "FailedScheduling",
+            "message": "0/3 nodes are available: 3 Insufficient cpu.\n",
+            "count": 4,
+            "firstTimestamp": "2026-05-02T00:00:00Z",
+            "lastTimestamp": "2026-05-02T00:05:00Z",
+        }))
+        .unwrap();
+
+        let event = to_deployment_event(event);
+        assert_eq!(event.event_type, "Warning");
+        assert_eq!(event.object, "Pod/demo-20260502-000000-7d9f8-abcde");
+        assert_eq!(
+            event.message,
+            "0/3 nodes are available: 3 Insufficient cpu."
+        );
+        assert_eq!(event.count, 4);
+        assert_eq!(
+            event.last_seen,
+            Some("2026-05-02T00:05:00Z".parse().unwrap())
+        );
+    }
 }
@@ -0,0 +1,333 @@
+//! Typed view of `deployments.controller_metadata`.
+//!
+//! Controllers record observed runtime state (health, pod status, ECS service
+//! counts) in the deployment's `controller_metadata` JSON column, which the API
+//! returns as-is to the frontend. All reads and writes go through
+//! [`ControllerMetadata`] so the schema lives in one place. Every field is optional
+//! and unknown fields are preserved, so previously stored JSON keeps round-tripping.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Runtime state recorded by the deployment controller
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ControllerMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<HealthCheck>,
+    /// Pod status observed by the Kubernetes controller
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_status: Option<PodStatus>,
+    /// Service status observed by the ECS controller
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ecs: Option<EcsServiceStatus>,
+    /// Reconcile phase reported by the legacy Docker controller
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconcile_phase: Option<String>,
+    /// Blue-green: a superseded deployment keeps running, without traffic, until then
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drain_until: Option<DateTime<Utc>>,
+    /// Fields not known to this version, kept so updates don't drop them
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Result of the controller's last health evaluation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthCheck {
+    pub last_check: DateTime<Utc>,
+    pub healthy: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodStatus {
+    pub desired_replicas: i32,
+    pub ready_replicas: i32,
+    pub current_replicas: i32,
+    #[serde(default)]
+    pub pods: Vec<PodInfo>,
+    pub last_checked: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodInfo {
+    pub name: String,
+    pub phase: String,
+    /// Pod is being deleted
+    #[serde(default)]
+    pub terminating: bool,
+    /// Pod no longer exists; carried forward from a previous snapshot
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub terminated: bool,
+    #[serde(default)]
+    pub conditions: Vec<PodCondition>,
+    #[serde(default)]
+    pub containers: Vec<ContainerInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PodCondition {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub status: String,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainerInfo {
+    pub name: String,
+    pub ready: bool,
+    pub restart_count: i32,
+    pub state: Option<ContainerState>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContainerState {
+    /// One of `waiting`, `running` or `terminated`
+    pub state_type: String,
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EcsServiceStatus {
+    pub service: String,
+    pub running_count: i32,
+    pub desired_count: i32,
+    pub healthy_targets: i32,
+}
+
+impl ControllerMetadata {
+    /// Parse stored metadata
+    ///
+    /// Malformed fields are logged and dropped; the fields that parse are kept.
+    pub fn from_value(value: &serde_json::Value) -> Self {
+        if value.is_null() {
+            return Self::default();
+        }
+        let error = match serde_json::from_value(value.clone()) {
+            Ok(metadata) => return metadata,
+            Err(e) => e,
+        };
+        let Some(object) = value.as_object() else {
+            warn!("Ignoring malformed controller metadata: {}", error);
+            return Self::default();
+        };
+
+        let mut object = object.clone();
+        object.retain(|key, field| {
+            let valid = Self::field_is_valid(key, field);
+            if !valid {
+                warn!(field = %key, "Dropping malformed controller metadata field: {}", error);
+            }
+            valid
+        });
+        serde_json::from_value(serde_json::Value::Object(object)).unwrap_or_else(|e| {
+            warn!("Ignoring malformed controller metadata: {}", e);
+            Self::default()
+        })
+    }
+
+    /// Whether `field` parses as the known field `key`; unknown fields are always valid
+    fn field_is_valid(key: &str, field: &serde_json::Value) -> bool {
+        fn parses<T: serde::de::DeserializeOwned>(field: &serde_json::Value) -> bool {
+            serde_json::from_value::<T>(field.clone()).is_ok()
+        }
+
+        match key {
+            "health" => parses::<Option<HealthCheck>>(field),
+            "pod_status" => parses::<Option<PodStatus>>(field),
+            "ecs" => parses::<Option<EcsServiceStatus>>(field),
+            "reconcile_phase" => parses::<Option<String>>(field),
+            "drain_until" => parses::<Option<DateTime<Utc>>>(field),
+            _ => true,
+        }
+    }
+
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_else(|_| serde_json::json!({}))
+    }
+
+    /// Record the outcome of a health evaluation
+    pub fn set_health(&mut self, healthy: bool) {
+        self.health = Some(HealthCheck {
+            last_check: Utc::now(),
+            healthy,
+        });
+    }
+}
+
+impl PodStatus {
+    /// Append pods from `previous` that were shutting down and have since disappeared.
+    ///
+    /// They are kept as `terminated` so their last-known state stays visible. At most
+    /// `max_inactive` inactive (terminating or terminated) pods are kept in total.
+    pub fn carry_forward_terminated(&mut self, previous: &PodStatus, max_inactive: usize) {
+        let live_pod_names: std::collections::HashSet<&str> =
+            self.pods.iter().map(|p| p.name.as_str()).collect();
+        let mut carried = Vec::new();
+        let mut inactive_count = self.pods.iter().filter(|p| p.terminating).count();
+
+        for prev_pod in &previous.pods {
+            if inactive_count >= max_inactive {
+                break;
+            }
+            if (prev_pod.terminating || prev_pod.terminated)
+                && !prev_pod.name.is_empty()
+                && !live_pod_names.contains(prev_pod.name.as_str())
+            {
+                carried.push(PodInfo {
+                    terminating: false,
+                    terminated: true,
+                    ..prev_pod.clone()
+                });
+                inactive_count += 1;
+            }
+        }
+
+        self.pods.extend(carried);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod(name: &str, terminating: bool, terminated: bool) -> PodInfo {
+        PodInfo {
+            name: name.to_string(),
+            phase: "Running".to_string(),
+            terminating,
+            terminated,
+            conditions: vec![],
+            containers: vec![],
+        }
+    }
+
+    fn pod_status(pods: Vec<PodInfo>) -> PodStatus {
+        PodStatus {
+            desired_replicas: 1,
+            ready_replicas: 1,
+            current_replicas: 1,
+            pods,
+            last_checked: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_round_trips_stored_json() {
+        let stored = serde_json::json!({
+            "health": {"last_check": "2026-01-01T00:00:00+00:00", "healthy": true},
+            "pod_status": {
+                "desired_replicas": 1,
+                "ready_replicas": 1,
+                "current_replicas": 1,
+                "last_checked": "2026-01-01T00:00:00+00:00",
+                "pods": [{
+                    "name": "app-1",
+                    "phase": "Running",
+                    "terminating": false,
+                    "conditions": [{"type": "Ready", "status": "True", "reason": null, "message": null}],
+                    "containers": [{
+                        "name": "app",
+                        "ready": true,
+                        "restart_count": 0,
+                        "state": {"state_type": "running", "reason": "2026-01-01 00:00:00 UTC"}
+                    }]
+                }]
+            },
+            "custom": {"kept": true}
+        });
+
+        let metadata = ControllerMetadata::from_value(&stored);
+        assert_eq!(metadata.health.as_ref().map(|h| h.healthy), Some(true));
+        assert_eq!(metadata.pod_status.as_ref().unwrap().pods[0].name, "app-1");
+
+        let value = metadata.to_value();
+        assert_eq!(value["custom"], serde_json::json!({"kept": true}));
+        assert_eq!(ControllerMetadata::from_value(&value), metadata);
+    }
+
+    #[test]
+    fn test_empty_and_malformed_metadata_default() {
+        assert_eq!(
+            ControllerMetadata::from_value(&serde_json::Value::Null),
+            ControllerMetadata::default()
+        );
+        assert_eq!(
+            ControllerMetadata::from_value(&serde_json::json!({})),
+            ControllerMetadata::default()
+        );
+        assert_eq!(
+            ControllerMetadata::from_value(&serde_json::json!({"health": "yes"})),
+            ControllerMetadata::default()
+        );
+        assert_eq!(
+            ControllerMetadata::default().to_value(),
+            serde_json::json!({})
+        );
+    }
+
+    #[test]
+    fn test_malformed_field_keeps_the_others() {
+        let metadata = ControllerMetadata::from_value(&serde_json::json!({
+            "health": {"last_check": "2026-01-01T00:00:00+00:00", "healthy": true},
+            "pod_status": {"desired_replicas": "one"},
+            "reconcile_phase": "Completed",
+            "custom": {"kept": true}
+        }));
+
+        assert_eq!(metadata.health.as_ref().map(|h| h.healthy), Some(true));
+        assert!(metadata.pod_status.is_none());
+        assert_eq!(metadata.reconcile_phase.as_deref(), Some("Completed"));
+        assert_eq!(metadata.other["custom"], serde_json::json!({"kept": true}));
+    }
+
+    #[test]
+    fn test_set_health_keeps_other_fields() {
+        let mut metadata = ControllerMetadata::from_value(&serde_json::json!({
+            "reconcile_phase": "Completed",
+        }));
+        metadata.set_health(false);
+
+        let value = metadata.to_value();
+        assert_eq!(value["reconcile_phase"], "Completed");
+        assert_eq!(value["health"]["healthy"], false);
+    }
+
+    #[test]
+    fn test_carry_forward_terminated_pods() {
+        let previous = pod_status(vec![
+            pod("live", false, false),
+            pod("gone-terminating", true, false),
+            pod("gone-terminated", false, true),
+            pod("gone-running", false, false),
+            pod("still-terminating", true, false),
+        ]);
+        let mut current = pod_status(vec![
+            pod("live", false, false),
+            pod("still-terminating", true, false),
+        ]);
+
+        current.carry_forward_terminated(&previous, 10);
+        let names: Vec<_> = current.pods.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "live",
+                "still-terminating",
+                "gone-terminating",
+                "gone-terminated"
+            ]
+        );
+        assert!(current.pods[2].terminated && !current.pods[2].terminating);
+
+        let mut limited = pod_status(vec![pod("still-terminating", true, false)]);
+        limited.carry_forward_terminated(&previous, 2);
+        assert_eq!(limited.pods.len(), 2);
+    }
+}
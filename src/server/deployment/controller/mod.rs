@@ -1,8 +1,16 @@
 #[cfg(feature = "backend")]
+pub mod ecs;
+#[cfg(feature = "backend")]
 mod kubernetes;
+#[cfg(feature = "backend")]
+pub mod metadata;
+#[cfg(feature = "backend")]
+pub mod orphan_gc;
+#[cfg(feature = "backend")]
+pub mod registry;
 
 #[cfg(feature = "backend")]
-pub use kubernetes::KubernetesBackend;
+pub use registry::{BackendContext, DeploymentBackendFactory, DeploymentBackendRegistry};
 
 use async_trait::async_trait;
 
@@ -19,11 +27,30 @@ pub struct DeploymentUrls {
     pub custom_domain_urls: Vec<String>,
 }
 
+/// A runtime event of a deployment's workload (e.g., a Kubernetes `Event`)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DeploymentEvent {
+    /// Event type (`Normal` or `Warning`)
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Short reason (e.g., "FailedScheduling", "BackOff")
+    pub reason: String,
+    /// Human-readable description
+    pub message: String,
+    /// Object the event is about (e.g., "Pod/myapp-20260102-120000-7d9f8-abcde")
+    pub object: String,
+    /// How many times the event occurred
+    pub count: i32,
+    /// When the event last occurred
+    pub last_seen: Option<chrono::DateTime<chrono::Utc>>,
+}
+
 /// Trait that all deployment backends must implement
 ///
 /// With Metacontroller, reconciliation/health checks/termination are handled
 /// by the sync webhook. This trait provides the remaining backend operations
-/// needed by HTTP handlers: log streaming, URL computation, and environment cleanup.
+/// needed by HTTP handlers: log streaming, events, URL computation, and environment
+/// cleanup.
 #[async_trait]
 pub trait DeploymentBackend: Send + Sync {
     /// Calculate URLs where this deployment can be accessed
@@ -60,9 +87,24 @@ pub trait DeploymentBackend: Send + Sync {
         Ok(())
     }
 
+    /// List runtime events of a deployment's workload, oldest first
+    ///
+    /// Surfaces scheduling, quota and image pull problems that don't show up as
+    /// container errors. Backends without such events return an empty list.
+    async fn list_events(
+        &self,
+        deployment: &Deployment,
+        project: &Project,
+    ) -> anyhow::Result<Vec<DeploymentEvent>> {
+        let _ = (deployment, project);
+        Ok(Vec::new())
+    }
+
     /// Stream logs from a deployment
     ///
     /// Returns a stream of log bytes from the deployment's runtime (pod/container).
+    /// With `all_pods`, logs of all of the deployment's pods are merged.
+    #[allow(clippy::too_many_arguments)]
     async fn stream_logs(
         &self,
         deployment: &Deployment,
@@ -71,5 +113,6 @@ pub trait DeploymentBackend: Send + Sync {
         tail_lines: Option<i64>,
         timestamps: bool,
         since_seconds: Option<i64>,
+        all_pods: bool,
     ) -> anyhow::Result<futures::stream::BoxStream<'static, Result<bytes::Bytes, anyhow::Error>>>;
 }
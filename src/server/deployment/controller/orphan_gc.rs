@@ -0,0 +1,339 @@
+//! Garbage collection of orphaned Kubernetes resources
+//!
+//! Metacontroller deletes the children a sync no longer returns, but resources
+//! can still outlive their deployment: ReplicaSets orphaned by a cascading delete
+//! that never finished, children of a sync that crashed halfway, or deployments
+//! whose database row was removed out-of-band. This loop periodically lists every
+//! Rise-managed resource carrying a deployment ID and deletes those whose
+//! deployment is missing or terminal.
+//!
+//! Services and Ingresses are shared by a deployment group and carry no deployment
+//! ID; they stay with Metacontroller. Resources of projects unknown to the database
+//! or outside the project's namespace are never touched, so several Rise
+//! installations can share a cluster.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::apps::v1::{Deployment as K8sDeployment, ReplicaSet};
+use k8s_openapi::api::core::v1::Secret;
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::NamespaceResourceScope;
+use kube::api::{Api, DeleteParams, ListParams};
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use super::kubernetes::k8s_time_to_utc;
+use crate::db::leader_leases::LeaderElection;
+use crate::db::{deployments as db_deployments, projects as db_projects};
+use crate::server::deployment::resource_builder::{
+    LABEL_DEPLOYMENT_ID, LABEL_MANAGED_BY, LABEL_PROJECT,
+};
+use crate::server::deployment::state_machine;
+use crate::server::state::AppState;
+
+/// Resources younger than this are never collected, so a deployment that is
+/// being created right now can't lose its children to a stale database read
+const MIN_ORPHAN_AGE: chrono::Duration = chrono::Duration::minutes(10);
+
+/// A Rise-managed resource labelled with a deployment ID
+#[derive(Debug, Clone, PartialEq)]
+struct ManagedResource {
+    kind: &'static str,
+    namespace: String,
+    name: String,
+    project: String,
+    deployment_id: String,
+    created_at: Option<DateTime<Utc>>,
+}
+
+/// What the collector knows about a project from the database
+struct ProjectDeployments {
+    namespace: String,
+    /// Deployment IDs of non-terminal deployments (including draining ones)
+    active: HashSet<String>,
+}
+
+/// Periodically deletes Kubernetes resources of deployments that no longer exist
+pub struct OrphanCollector {
+    state: AppState,
+    kube_client: kube::Client,
+    interval: Duration,
+    dry_run: bool,
+    election: LeaderElection,
+}
+
+impl OrphanCollector {
+    pub fn new(state: AppState, interval: Duration, dry_run: bool) -> Result<Self> {
+        let kube_client = state
+            .kube_client
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Kubernetes client not initialized"))?;
+        let election = LeaderElection::spawn(
+            state.db_pool.clone(),
+            "rise-orphan-gc",
+            Uuid::new_v4(),
+            Duration::from_secs(60),
+        );
+        Ok(Self {
+            state,
+            kube_client,
+            interval,
+            dry_run,
+            election,
+        })
+    }
+
+    /// Start the collection loop
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            self.collection_loop().await;
+        });
+    }
+
+    async fn collection_loop(&self) {
+        info!(
+            dry_run = self.dry_run,
+            "Orphaned resource collector started"
+        );
+        let mut ticker = interval(self.interval);
+
+        loop {
+            ticker.tick().await;
+
+            if !self.election.is_leader() {
+                continue;
+            }
+
+            if let Err(e) = self.collect().await {
+                error!("Error in orphaned resource collector: {:#}", e);
+            }
+        }
+    }
+
+    /// Run one collection pass
+    async fn collect(&self) -> Result<()> {
+        // List resources before reading deployments, so a deployment created in
+        // between is seen as active rather than its resources as orphaned
+        let mut resources = Vec::new();
+        resources.extend(self.list::<K8sDeployment>("Deployment").await?);
+        resources.extend(self.list::<ReplicaSet>("ReplicaSet").await?);
+        resources.extend(
+            self.list::<PodDisruptionBudget>("PodDisruptionBudget")
+                .await?,
+        );
+        resources.extend(self.list::<Secret>("Secret").await?);
+        if resources.is_empty() {
+            return Ok(());
+        }
+
+        let projects = self.load_projects(&resources).await?;
+        let orphans = orphaned_resources(&resources, &projects, Utc::now());
+        debug!(
+            "Orphaned resource collector checked {} resources, {} orphaned",
+            resources.len(),
+            orphans.len()
+        );
+
+        for resource in orphans {
+            if self.dry_run {
+                info!(
+                    project = %resource.project,
+                    deployment_id = %resource.deployment_id,
+                    "Dry run: would delete orphaned {} {}/{}",
+                    resource.kind,
+                    resource.namespace,
+                    resource.name
+                );
+                continue;
+            }
+            self.delete(resource).await;
+        }
+        Ok(())
+    }
+
+    /// List the metadata of all Rise-managed resources of a kind that carry a deployment ID
+    async fn list<K>(&self, kind: &'static str) -> Result<Vec<ManagedResource>>
+    where
+        K: kube::Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+            + Clone
+            + serde::de::DeserializeOwned
+            + std::fmt::Debug,
+    {
+        let api: Api<K> = Api::all(self.kube_client.clone());
+        let selector = format!("{}=rise,{}", LABEL_MANAGED_BY, LABEL_DEPLOYMENT_ID);
+        let list = api
+            .list_metadata(&ListParams::default().labels(&selector))
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to list {}s: {}", kind, e))?;
+
+        Ok(list
+            .items
+            .into_iter()
+            .filter(|item| item.metadata.deletion_timestamp.is_none())
+            .filter_map(|item| {
+                let meta = item.metadata;
+                let labels = meta.labels.as_ref()?;
+                Some(ManagedResource {
+                    kind,
+                    namespace: meta.namespace.clone()?,
+                    name: meta.name.clone()?,
+                    project: labels.get(LABEL_PROJECT)?.clone(),
+                    deployment_id: labels.get(LABEL_DEPLOYMENT_ID)?.clone(),
+                    created_at: meta.creation_timestamp.as_ref().and_then(k8s_time_to_utc),
+                })
+            })
+            .collect())
+    }
+
+    /// Load the namespace and active deployments of every project referenced by `resources`
+    async fn load_projects(
+        &self,
+        resources: &[ManagedResource],
+    ) -> Result<HashMap<String, ProjectDeployments>> {
+        let Some(ref resource_builder) = self.state.resource_builder else {
+            anyhow::bail!("Kubernetes resource builder not initialized");
+        };
+        let pool = &self.state.db_pool;
+
+        let names: HashSet<&str> = resources.iter().map(|r| r.project.as_str()).collect();
+        let mut projects = HashMap::new();
+        for name in names {
+            let Some(project) = db_projects::find_by_name(pool, name).await? else {
+                debug!(project = name, "Skipping resources of unknown project");
+                continue;
+            };
+            let active = db_deployments::list_for_project(pool, project.id)
+                .await?
+                .into_iter()
+                .filter(|d| !state_machine::is_terminal(&d.status))
+                .map(|d| d.deployment_id)
+                .collect();
+            projects.insert(
+                name.to_string(),
+                ProjectDeployments {
+                    namespace: resource_builder.namespace_name(&project),
+                    active,
+                },
+            );
+        }
+        Ok(projects)
+    }
+
+    async fn delete(&self, resource: &ManagedResource) {
+        let client = self.kube_client.clone();
+        let namespace = resource.namespace.as_str();
+        let params = DeleteParams::background();
+        let result = match resource.kind {
+            "Deployment" => Api::<K8sDeployment>::namespaced(client, namespace)
+                .delete(&resource.name, &params)
+                .await
+                .map(|_| ()),
+            "ReplicaSet" => Api::<ReplicaSet>::namespaced(client, namespace)
+                .delete(&resource.name, &params)
+                .await
+                .map(|_| ()),
+            "PodDisruptionBudget" => Api::<PodDisruptionBudget>::namespaced(client, namespace)
+                .delete(&resource.name, &params)
+                .await
+                .map(|_| ()),
+            "Secret" => Api::<Secret>::namespaced(client, namespace)
+                .delete(&resource.name, &params)
+                .await
+                .map(|_| ()),
+            other => unreachable!("unexpected resource kind {}", other),
+        };
+
+        match result {
+            Ok(()) => info!(
+                project = %resource.project,
+                deployment_id = %resource.deployment_id,
+                "Deleted orphaned {} {}/{}",
+                resource.kind,
+                resource.namespace,
+                resource.name
+            ),
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => warn!(
+                project = %resource.project,
+                deployment_id = %resource.deployment_id,
+                "Failed to delete orphaned {} {}/{}: {:?}",
+                resource.kind,
+                resource.namespace,
+                resource.name,
+                e
+            ),
+        }
+    }
+}
+
+/// Resources whose deployment is missing or terminal.
+///
+/// Resources of unknown projects, outside the project's namespace, or younger than
+/// [`MIN_ORPHAN_AGE`] (or without a creation timestamp) are kept.
+fn orphaned_resources<'a>(
+    resources: &'a [ManagedResource],
+    projects: &HashMap<String, ProjectDeployments>,
+    now: DateTime<Utc>,
+) -> Vec<&'a ManagedResource> {
+    resources
+        .iter()
+        .filter(|r| r.created_at.is_some_and(|t| now - t >= MIN_ORPHAN_AGE))
+        .filter(|r| {
+            projects
+                .get(&r.project)
+                .is_some_and(|p| p.namespace == r.namespace && !p.active.contains(&r.deployment_id))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(
+        name: &str,
+        namespace: &str,
+        project: &str,
+        deployment_id: &str,
+    ) -> ManagedResource {
+        ManagedResource {
+            kind: "ReplicaSet",
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            project: project.to_string(),
+            deployment_id: deployment_id.to_string(),
+            created_at: Some(Utc::now() - chrono::Duration::hours(1)),
+        }
+    }
+
+    #[test]
+    fn orphaned_resources_keeps_active_young_and_foreign_resources() {
+        let mut young = resource("young", "rise-demo", "demo", "20260101-000000");
+        young.created_at = Some(Utc::now() - chrono::Duration::minutes(1));
+        let resources = vec![
+            resource("active", "rise-demo", "demo", "20260102-000000"),
+            resource("orphan", "rise-demo", "demo", "20260101-000000"),
+            young,
+            resource("other-namespace", "elsewhere", "demo", "20260101-000000"),
+            resource("unknown-project", "rise-ghost", "ghost", "20260101-000000"),
+        ];
+        let projects = HashMap::from([(
+            "demo".to_string(),
+            ProjectDeployments {
+                namespace: "rise-demo".to_string(),
+                active: HashSet::from(["20260102-000000".to_string()]),
+            },
+        )]);
+
+        let names: Vec<&str> = orphaned_resources(&resources, &projects, Utc::now())
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["orphan"]);
+    }
+}
@@ -0,0 +1,216 @@
+//! Registry of deployment backend factories.
+//!
+//! Backends register a factory under their `deployment_controller.type` string.
+//! At startup, the backend is created by looking up the configured type, so adding
+//! an orchestrator is a self-contained module, a settings variant, and one
+//! registration line in [`DeploymentBackendRegistry::with_builtin_backends`].
+//!
+//! Backends not shipped with Rise are registered on a registry passed to
+//! `run_server_with_backends`. Their `deployment_controller` section is handed to
+//! the factory as [`DeploymentControllerSettings::Custom`], with all fields other
+//! than `type` in `config`.
+
+use super::DeploymentBackend;
+use crate::server::deployment::resource_builder::ResourceBuilder;
+use crate::server::settings::DeploymentControllerSettings;
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Shared resources handed to backend factories
+pub struct BackendContext<'a> {
+    /// The configured deployment controller settings
+    pub settings: &'a DeploymentControllerSettings,
+    pub db_pool: PgPool,
+    pub resource_builder: Option<Arc<ResourceBuilder>>,
+    pub kube_client: Option<kube::Client>,
+}
+
+/// Factory creating a deployment backend from settings
+#[async_trait]
+pub trait DeploymentBackendFactory: Send + Sync {
+    /// The `deployment_controller.type` this factory handles (e.g., "kubernetes")
+    fn backend_type(&self) -> &'static str;
+
+    /// Create the backend, validating connectivity where applicable
+    async fn create(&self, ctx: &BackendContext<'_>) -> Result<Arc<dyn DeploymentBackend>>;
+}
+
+/// Registry for deployment backend factories
+pub struct DeploymentBackendRegistry {
+    factories: HashMap<String, Arc<dyn DeploymentBackendFactory>>,
+}
+
+impl DeploymentBackendRegistry {
+    pub fn new() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Create a registry with all backends shipped with Rise
+    pub fn with_builtin_backends() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(super::kubernetes::KubernetesBackendFactory));
+        registry.register(Arc::new(super::ecs::EcsBackendFactory));
+        registry
+    }
+
+    /// Register a backend factory, replacing any factory for the same type
+    pub fn register(&mut self, factory: Arc<dyn DeploymentBackendFactory>) {
+        self.factories
+            .insert(factory.backend_type().to_string(), factory);
+    }
+
+    /// List all registered backend types (sorted)
+    pub fn list(&self) -> Vec<String> {
+        let mut types: Vec<String> = self.factories.keys().cloned().collect();
+        types.sort();
+        types
+    }
+
+    /// Create the backend registered for `backend_type`
+    pub async fn create(
+        &self,
+        backend_type: &str,
+        ctx: &BackendContext<'_>,
+    ) -> Result<Arc<dyn DeploymentBackend>> {
+        let factory = self.factories.get(backend_type).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown deployment controller type '{}'. Available: {}",
+                backend_type,
+                self.list().join(", ")
+            )
+        })?;
+        tracing::info!("Initializing '{}' deployment backend", backend_type);
+        factory.create(ctx).await
+    }
+}
+
+impl Default for DeploymentBackendRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{Deployment, Project};
+    use crate::server::deployment::controller::DeploymentUrls;
+
+    struct NoopBackend;
+
+    #[async_trait]
+    impl DeploymentBackend for NoopBackend {
+        async fn get_deployment_urls(
+            &self,
+            _deployment: &Deployment,
+            _project: &Project,
+        ) -> Result<DeploymentUrls> {
+            unreachable!("not used in these tests")
+        }
+
+        async fn get_project_urls(
+            &self,
+            _project: &Project,
+            _deployment_group: &str,
+        ) -> Result<DeploymentUrls> {
+            unreachable!("not used in these tests")
+        }
+
+        async fn stream_logs(
+            &self,
+            _deployment: &Deployment,
+            _project: &Project,
+            _follow: bool,
+            _tail_lines: Option<i64>,
+            _timestamps: bool,
+            _since_seconds: Option<i64>,
+            _all_pods: bool,
+        ) -> Result<futures::stream::BoxStream<'static, Result<bytes::Bytes, anyhow::Error>>>
+        {
+            unreachable!("not used in these tests")
+        }
+    }
+
+    struct NoopFactory;
+
+    #[async_trait]
+    impl DeploymentBackendFactory for NoopFactory {
+        fn backend_type(&self) -> &'static str {
+            "noop"
+        }
+
+        async fn create(&self, _ctx: &BackendContext<'_>) -> Result<Arc<dyn DeploymentBackend>> {
+            Ok(Arc::new(NoopBackend))
+        }
+    }
+
+    fn test_settings() -> DeploymentControllerSettings {
+        serde_yaml::from_str(
+            r#"
+type: kubernetes
+production_ingress_url_template: "{project_name}.apps.example.com"
+namespace_format: "rise-{project_name}"
+auth_backend_url: "http://rise-backend.default.svc.cluster.local:3000"
+auth_signin_url: "https://rise.example.com"
+access_classes: {}
+network_policy:
+  ingress: []
+"#,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_registered_backend() {
+        let mut registry = DeploymentBackendRegistry::with_builtin_backends();
+        registry.register(Arc::new(NoopFactory));
+        assert_eq!(registry.list(), vec!["ecs", "kubernetes", "noop"]);
+
+        let settings = test_settings();
+        let ctx = BackendContext {
+            settings: &settings,
+            db_pool: PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+            resource_builder: None,
+            kube_client: None,
+        };
+        assert!(registry.create("noop", &ctx).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_custom_backend_from_settings() {
+        let mut registry = DeploymentBackendRegistry::with_builtin_backends();
+        registry.register(Arc::new(NoopFactory));
+
+        let settings = DeploymentControllerSettings::Custom {
+            backend_type: "noop".to_string(),
+            config: serde_json::Map::new(),
+        };
+        let ctx = BackendContext {
+            settings: &settings,
+            db_pool: PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+            resource_builder: None,
+            kube_client: None,
+        };
+        assert!(registry.create(settings.backend_type(), &ctx).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_backend_type_lists_available() {
+        let registry = DeploymentBackendRegistry::with_builtin_backends();
+        let settings = test_settings();
+        let ctx = BackendContext {
+            settings: &settings,
+            db_pool: PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+            resource_builder: None,
+            kube_client: None,
+        };
+
+        let err = registry.create("nomad", &ctx).await.err().unwrap();
+        assert!(err.to_string().contains("Available: ecs, kubernetes"));
+    }
+}
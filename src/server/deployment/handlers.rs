@@ -15,6 +15,7 @@ use super::state_machine;
 use super::utils::{create_deployment_with_hooks, generate_deployment_id};
 use crate::db::models::DeploymentStatus as DbDeploymentStatus;
 use crate::db::{deployments as db_deployments, projects, service_accounts, users};
+use crate::rise_toml::{parse_expiration_duration, DeploymentStrategy};
 use crate::server::auth::context::AuthContext;
 use crate::server::error::{ServerError, ServerErrorExt};
 use crate::server::registry::ImageTagType;
@@ -56,35 +57,56 @@ fn is_valid_group_name(name: &str) -> bool {
     true
 }
 
-/// Parse expiration duration string (e.g., "7d", "2h", "30m") to DateTime
-fn parse_expiration(expires_in: &str) -> Result<DateTime<Utc>, String> {
-    let s = expires_in.trim();
-    let (num_str, unit) = if let Some(num_str) = s.strip_suffix('d') {
-        (num_str, "d")
-    } else if let Some(num_str) = s.strip_suffix('h') {
-        (num_str, "h")
-    } else if let Some(num_str) = s.strip_suffix('m') {
-        (num_str, "m")
-    } else {
-        return Err("Duration must end with d, h, or m".to_string());
-    };
+/// Compute the expiration of a created or renewed deployment: `expires_in` from
+/// `now`, clamped to the platform's `max_expiration` if one is configured
+fn capped_expiration(
+    now: DateTime<Utc>,
+    expires_in: &str,
+    max_expiration: Option<chrono::Duration>,
+) -> Result<DateTime<Utc>, ServerError> {
+    let requested = parse_expiration_duration(expires_in).map_err(|e| {
+        ServerError::bad_request(format!(
+            "Invalid expiration duration '{}': {}",
+            expires_in, e
+        ))
+    })?;
 
-    let num: i64 = num_str
-        .parse()
-        .map_err(|_| "Invalid number in duration".to_string())?;
+    Ok(now + max_expiration.map_or(requested, |max| requested.min(max)))
+}
 
-    if num <= 0 {
-        return Err("Duration must be positive".to_string());
-    }
+/// The platform's `max_expiration`, if one is configured
+fn max_expiration(state: &AppState) -> Result<Option<chrono::Duration>, ServerError> {
+    state
+        .deployment_constraints
+        .as_ref()
+        .map(|c| c.max_expiration())
+        .transpose()
+        .map(Option::flatten)
+        .map_err(|e| {
+            ServerError::internal(format!(
+                "Invalid deployment_constraints.max_expiration: {}",
+                e
+            ))
+        })
+}
 
-    let duration = match unit {
-        "d" => chrono::Duration::days(num),
-        "h" => chrono::Duration::hours(num),
-        "m" => chrono::Duration::minutes(num),
-        _ => return Err("Invalid duration unit".to_string()),
+/// Compute the expiration of a new deployment in `group` from the first of the
+/// project's group TTL policies whose pattern matches it
+fn group_ttl_expiration(
+    now: DateTime<Utc>,
+    policies: &[crate::server::project::models::GroupTtlPolicy],
+    group: &str,
+) -> Result<Option<DateTime<Utc>>, ServerError> {
+    let Some(policy) = policies.iter().find(|policy| policy.matches(group)) else {
+        return Ok(None);
     };
-
-    Ok(Utc::now() + duration)
+    let duration = parse_expiration_duration(&policy.expire).map_err(|e| {
+        ServerError::internal(format!(
+            "Invalid group TTL '{}' for pattern '{}': {}",
+            policy.expire, policy.pattern, e
+        ))
+    })?;
+    Ok(Some(now + duration))
 }
 
 /// Normalize image reference by adding registry hostname and namespace if missing
@@ -248,6 +270,36 @@ async fn insert_rise_env_vars(
     Ok(())
 }
 
+/// Reject a new deployment whose env vars reference each other in a loop
+///
+/// Runs once the snapshot is complete (project vars, overrides and system vars).
+/// The deployment is marked as failed so the controller never picks it up.
+/// Other errors, such as a failing decrypt, are left to the controller to report.
+async fn reject_circular_env_references(
+    state: &AppState,
+    deployment: &crate::db::models::Deployment,
+    project: &crate::db::models::Project,
+) -> Result<(), ServerError> {
+    let err = match crate::server::deployment::webhook::load_env_vars(state, project, deployment)
+        .await
+    {
+        Err(e) if e.is::<crate::server::deployment::env_interpolation::CircularReference>() => e,
+        _ => return Ok(()),
+    };
+
+    let error_msg = err.to_string();
+    if let Err(mark_err) =
+        db_deployments::mark_failed(&state.db_pool, deployment.id, &error_msg).await
+    {
+        error!(
+            "Failed to mark deployment as failed after a circular env var reference: {:?}",
+            mark_err
+        );
+    }
+    Err(ServerError::bad_request(error_msg)
+        .with_context("deployment_id", &deployment.deployment_id))
+}
+
 /// Filter env overrides by target environment.
 ///
 /// - `for_environment: None` → always included (global override)
@@ -325,11 +377,10 @@ async fn apply_env_overrides(
 
         // Encrypt if secret
         let value_to_store = if env_override.is_secret {
-            let provider = state.encryption_provider.as_ref().ok_or_else(|| {
-                ServerError::bad_request(
-                    "Cannot store secret variables: no encryption provider configured",
-                )
-            })?;
+            let provider = state
+                .encryption_provider
+                .as_ref()
+                .ok_or_else(crate::server::env_vars::handlers::no_encryption_provider)?;
             provider
                 .encrypt(&env_override.value)
                 .await
@@ -396,410 +447,2421 @@ fn validate_env_overrides(overrides: &[models::EnvOverride]) -> Result<(), Serve
     Ok(())
 }
 
-/// Convert DB DeploymentStatus to API DeploymentStatus
-fn convert_status_from_db(status: DbDeploymentStatus) -> DeploymentStatus {
-    match status {
-        DbDeploymentStatus::Pending => DeploymentStatus::Pending,
-        DbDeploymentStatus::Building => DeploymentStatus::Building,
-        DbDeploymentStatus::Pushing => DeploymentStatus::Pushing,
-        DbDeploymentStatus::Pushed => DeploymentStatus::Pushed,
-        DbDeploymentStatus::Deploying => DeploymentStatus::Deploying,
-        DbDeploymentStatus::Healthy => DeploymentStatus::Healthy,
-        DbDeploymentStatus::Unhealthy => DeploymentStatus::Unhealthy,
-        DbDeploymentStatus::Cancelling => DeploymentStatus::Cancelling,
-        DbDeploymentStatus::Cancelled => DeploymentStatus::Cancelled,
-        DbDeploymentStatus::Terminating => DeploymentStatus::Terminating,
-        DbDeploymentStatus::Stopped => DeploymentStatus::Stopped,
-        DbDeploymentStatus::Superseded => DeploymentStatus::Superseded,
-        DbDeploymentStatus::Failed => DeploymentStatus::Failed,
-        DbDeploymentStatus::Expired => DeploymentStatus::Expired,
+/// Maximum length (in characters) of a deployment message
+const MAX_DEPLOYMENT_MESSAGE_LEN: usize = 500;
+
+/// Trim a deployment message, treating blank messages as absent.
+fn normalize_deployment_message(message: Option<&str>) -> Result<Option<String>, ServerError> {
+    let Some(message) = message.map(str::trim).filter(|m| !m.is_empty()) else {
+        return Ok(None);
+    };
+
+    if message.chars().count() > MAX_DEPLOYMENT_MESSAGE_LEN {
+        return Err(ServerError::bad_request(format!(
+            "Deployment message must be at most {} characters",
+            MAX_DEPLOYMENT_MESSAGE_LEN
+        )));
     }
+
+    Ok(Some(message.to_string()))
 }
 
-/// Fetch the creator email for a deployment
-async fn get_creator_email(pool: &sqlx::PgPool, created_by_id: uuid::Uuid) -> String {
-    match users::find_by_id(pool, created_by_id).await {
-        Ok(Some(user)) => user.email,
-        _ => "unknown".to_string(),
+/// Validate health probe overrides and serialize them for storage.
+fn normalize_health_check(
+    health_check: Option<&crate::rise_toml::HealthCheckConfig>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(health_check) = health_check else {
+        return Ok(None);
+    };
+
+    for (field, path) in [
+        ("readiness_path", &health_check.readiness_path),
+        ("liveness_path", &health_check.liveness_path),
+        ("startup_path", &health_check.startup_path),
+        ("path", &health_check.path),
+    ] {
+        if let Some(path) = path {
+            if !path.starts_with('/') {
+                return Err(ServerError::bad_request(format!(
+                    "health_check.{} must start with '/' (got '{}')",
+                    field, path
+                )));
+            }
+        }
+    }
+    if health_check.initial_delay_seconds.is_some_and(|d| d < 0) {
+        return Err(ServerError::bad_request(
+            "health_check.initial_delay_seconds must not be negative",
+        ));
+    }
+    if health_check.period_seconds.is_some_and(|p| p < 1) {
+        return Err(ServerError::bad_request(
+            "health_check.period_seconds must be at least 1",
+        ));
+    }
+    if health_check
+        .startup_failure_threshold
+        .is_some_and(|t| t < 1)
+    {
+        return Err(ServerError::bad_request(
+            "health_check.startup_failure_threshold must be at least 1",
+        ));
+    }
+    if health_check.startup_period_seconds.is_some_and(|p| p < 1) {
+        return Err(ServerError::bad_request(
+            "health_check.startup_period_seconds must be at least 1",
+        ));
+    }
+    if let Some(ref expected) = health_check.expected_status {
+        if expected.is_empty() || expected.iter().any(|s| !(100..=599).contains(s)) {
+            return Err(ServerError::bad_request(
+                "health_check.expected_status must list HTTP status codes (100-599)",
+            ));
+        }
+    }
+    if health_check.timeout.is_some_and(|t| t < 1) {
+        return Err(ServerError::bad_request(
+            "health_check.timeout must be at least 1",
+        ));
     }
+
+    serde_json::to_value(health_check)
+        .map(Some)
+        .internal_err("Failed to serialize health check configuration")
 }
 
-/// Convert DB Deployment to API Deployment
+/// Validate an init container and serialize it for storage.
+fn normalize_init_container(
+    init_container: Option<&crate::rise_toml::InitContainerConfig>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(init_container) = init_container else {
+        return Ok(None);
+    };
+
+    if init_container.command.is_empty() || init_container.command[0].trim().is_empty() {
+        return Err(ServerError::bad_request(
+            "init_container.command must not be empty",
+        ));
+    }
+    if init_container
+        .image
+        .as_deref()
+        .is_some_and(|image| image.trim().is_empty())
+    {
+        return Err(ServerError::bad_request(
+            "init_container.image must not be empty when set",
+        ));
+    }
+
+    serde_json::to_value(init_container)
+        .map(Some)
+        .internal_err("Failed to serialize init container configuration")
+}
+
+/// Validate custom workload labels or annotations and serialize them for storage.
 ///
-/// Dynamically calculates the image tag when not stored in the database,
-/// using the registry provider's configuration to construct the full image reference.
-async fn convert_deployment(
-    state: &AppState,
-    deployment: crate::db::models::Deployment,
-    project: &crate::db::models::Project,
-    created_by_email: String,
-    primary_url: Option<String>,
-    custom_domain_urls: Vec<String>,
-) -> Deployment {
-    // Backfill image field for locally-built deployments
-    // For pre-built images, deployment.image is already set
-    // For build-from-source, calculate the internal registry tag
-    let image = if deployment.image.is_some() {
-        deployment.image.clone()
-    } else {
-        Some(super::utils::get_deployment_image_tag(state, &deployment, project).await)
+/// `field` is `labels` or `annotations`; only label values are restricted.
+fn normalize_workload_metadata(
+    field: &str,
+    entries: Option<&std::collections::BTreeMap<String, String>>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(entries) = entries.filter(|entries| !entries.is_empty()) else {
+        return Ok(None);
     };
-    let can_rollback = state_machine::can_create_from(&deployment);
 
-    // Resolve environment name and color
-    let (environment, environment_color) = if let Some(env_id) = deployment.environment_id {
-        let env = crate::db::environments::find_by_id(&state.db_pool, env_id)
-            .await
-            .ok()
-            .flatten();
-        (env.as_ref().map(|e| e.name.clone()), env.map(|e| e.color))
-    } else {
-        (None, None)
+    for (key, value) in entries {
+        if key.starts_with(models::RESERVED_METADATA_PREFIX) {
+            return Err(ServerError::bad_request(format!(
+                "{}: '{}' uses the '{}' prefix reserved for Rise",
+                field,
+                key,
+                models::RESERVED_METADATA_PREFIX
+            )));
+        }
+        if !models::is_valid_metadata_key(key) {
+            return Err(ServerError::bad_request(format!(
+                "{}: '{}' is not a valid Kubernetes key",
+                field, key
+            )));
+        }
+        if field == "labels" && !models::is_valid_label_value(value) {
+            return Err(ServerError::bad_request(format!(
+                "labels: value '{}' of '{}' is not a valid Kubernetes label value",
+                value, key
+            )));
+        }
+    }
+
+    serde_json::to_value(entries)
+        .map(Some)
+        .internal_err("Failed to serialize workload metadata")
+}
+
+/// Validate custom ingress annotations (`[deploy.ingress.annotations]`) against the
+/// controller's allowlist and serialize them for storage.
+///
+/// Only the Kubernetes controller renders ingresses, so other controllers reject them.
+fn normalize_ingress_annotations(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    entries: Option<&std::collections::BTreeMap<String, String>>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(entries) = entries.filter(|entries| !entries.is_empty()) else {
+        return Ok(None);
+    };
+    let Some(resource_builder) = resource_builder else {
+        return Err(ServerError::bad_request(
+            "ingress_annotations are only supported with the Kubernetes deployment controller",
+        ));
     };
 
-    Deployment {
-        id: deployment.id.to_string(),
-        deployment_id: deployment.deployment_id,
-        project: deployment.project_id.to_string(),
-        created_by: deployment.created_by_id.to_string(),
-        created_by_email,
-        status: convert_status_from_db(deployment.status),
-        deployment_group: deployment.deployment_group,
-        environment,
-        environment_color,
-        expires_at: deployment.expires_at.map(|dt| dt.to_rfc3339()),
-        error_message: deployment.error_message,
-        completed_at: deployment.completed_at.map(|dt| dt.to_rfc3339()),
-        build_logs: deployment.build_logs,
-        controller_metadata: deployment.controller_metadata,
-        primary_url,
-        custom_domain_urls,
-        image,
-        image_digest: deployment.image_digest,
-        http_port: deployment.http_port as u16,
-        is_active: deployment.is_active,
-        can_rollback,
-        replicas: deployment.replicas as u32,
-        cpu: deployment.cpu,
-        memory: deployment.memory,
-        job_url: deployment.job_url,
-        pull_request_url: deployment.pull_request_url,
-        created: deployment.created_at.to_rfc3339(),
-        updated: deployment.updated_at.to_rfc3339(),
+    for key in entries.keys() {
+        if !models::is_valid_metadata_key(key) {
+            return Err(ServerError::bad_request(format!(
+                "ingress_annotations: '{}' is not a valid Kubernetes key",
+                key
+            )));
+        }
+        if super::resource_builder::RESERVED_INGRESS_ANNOTATIONS.contains(&key.as_str()) {
+            return Err(ServerError::bad_request(format!(
+                "ingress_annotations: '{}' is managed by Rise and cannot be overridden",
+                key
+            )));
+        }
+        if !resource_builder.is_ingress_annotation_allowed(key) {
+            return Err(ServerError::bad_request(format!(
+                "ingress_annotations: '{}' is not allowed on this server (allowed: {})",
+                key,
+                resource_builder.allowed_ingress_annotations.join(", ")
+            )));
+        }
     }
+
+    serde_json::to_value(entries)
+        .map(Some)
+        .internal_err("Failed to serialize ingress annotations")
 }
 
-/// Resolve the deployment target (group + environment) from request parameters.
+/// Longest drain period a blue-green deployment may request
+const MAX_DRAIN_PERIOD_SECONDS: i64 = 24 * 60 * 60;
+
+/// Validate the deployment strategy and blue-green drain period.
 ///
-/// Rules:
-/// - If both specified: use both, but validate that the group is not the primary
-///   deployment group of a *different* environment.
-/// - If environment specified, no group: use environment's primary deployment group.
-/// - If group specified, no environment: look up environment whose primary group matches;
-///   then auto-resolve (0 envs → none, 1 env → use it, 2+ → error).
-/// - If neither specified: if the project has exactly one environment, use it.
-///   If zero, use the default group with no environment. If more than one, error.
-async fn resolve_deployment_target(
-    pool: &sqlx::PgPool,
-    project_id: uuid::Uuid,
-    requested_environment: Option<&str>,
-    requested_group: Option<&str>,
-) -> Result<(String, Option<crate::db::models::Environment>), ServerError> {
-    use crate::db::environments;
+/// Returns the stored strategy name and the drain period in seconds. Only the
+/// Kubernetes controller implements blue-green, and a drain period requires it.
+fn normalize_strategy(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    strategy: Option<DeploymentStrategy>,
+    drain_period: Option<&str>,
+) -> Result<(String, Option<i32>), ServerError> {
+    let strategy = strategy.unwrap_or_default();
+    if strategy == DeploymentStrategy::BlueGreen && resource_builder.is_none() {
+        return Err(ServerError::bad_request(
+            "strategy 'blue-green' is only supported with the Kubernetes deployment controller",
+        ));
+    }
 
-    match (requested_environment, requested_group) {
-        // Both specified
-        (Some(env_name), Some(group)) => {
-            let env = environments::find_by_name(pool, project_id, env_name)
-                .await
-                .internal_err("Failed to look up environment")?
-                .ok_or_else(|| {
-                    ServerError::not_found(format!("Environment '{}' not found", env_name))
-                })?;
-            // Validate: if the group is the primary group of another environment, reject
-            if let Some(primary_env) = environments::find_by_primary_group(pool, project_id, group)
-                .await
-                .internal_err("Failed to look up environment by group")?
-            {
-                if primary_env.name != env_name {
-                    return Err(ServerError::bad_request(format!(
-                        "Group '{}' is the primary deployment group of environment '{}', and cannot be used with environment '{}'.",
-                        group, primary_env.name, env_name
-                    )));
-                }
+    let drain_period_seconds = match drain_period {
+        None => None,
+        Some(_) if strategy != DeploymentStrategy::BlueGreen => {
+            return Err(ServerError::bad_request(
+                "drain_period requires strategy 'blue-green'",
+            ));
+        }
+        Some(drain_period) => {
+            let seconds = parse_expiration_duration(drain_period)
+                .map_err(|e| {
+                    ServerError::bad_request(format!(
+                        "Invalid drain_period '{}': {}",
+                        drain_period, e
+                    ))
+                })?
+                .num_seconds();
+            if seconds > MAX_DRAIN_PERIOD_SECONDS {
+                return Err(ServerError::bad_request(format!(
+                    "drain_period '{}' exceeds the maximum of 24h",
+                    drain_period
+                )));
             }
-            Ok((group.to_string(), Some(env)))
+            Some(seconds as i32)
         }
-        // Environment only, no group
-        (Some(env_name), None) => {
-            let env = environments::find_by_name(pool, project_id, env_name)
-                .await
-                .internal_err("Failed to look up environment")?
-                .ok_or_else(|| {
-                    ServerError::not_found(format!("Environment '{}' not found", env_name))
-                })?;
-            let group = env.primary_deployment_group.clone().ok_or_else(|| {
-                ServerError::bad_request(format!(
-                    "Environment '{}' has no primary deployment group. Specify a deployment group explicitly.",
-                    env_name
-                ))
-            })?;
-            Ok((group, Some(env)))
-        }
-        // Group only, no environment
-        (None, Some(group)) => {
-            // Check if this group is the primary group of an environment
-            let env = environments::find_by_primary_group(pool, project_id, group)
-                .await
-                .internal_err("Failed to look up environment by group")?;
-            if env.is_some() {
-                Ok((group.to_string(), env))
-            } else {
-                // No primary group match. Auto-resolve from available environments.
-                let all_envs = environments::list_for_project(pool, project_id)
-                    .await
-                    .internal_err("Failed to list environments")?;
-                match all_envs.len() {
-                    0 => Ok((group.to_string(), None)),
-                    1 => Ok((
-                        group.to_string(),
-                        Some(all_envs.into_iter().next().unwrap()),
-                    )),
-                    _ => Err(ServerError::bad_request(
-                        "Multiple environments configured. Specify an environment to select one.",
-                    )),
-                }
-            }
-        }
-        // Neither specified: auto-resolve from available environments
-        (None, None) => {
-            let all_envs = environments::list_for_project(pool, project_id)
-                .await
-                .internal_err("Failed to list environments")?;
-            match all_envs.len() {
-                0 => Ok((models::DEFAULT_DEPLOYMENT_GROUP.to_string(), None)),
-                1 => {
-                    let env = all_envs.into_iter().next().unwrap();
-                    let group = env
-                        .primary_deployment_group
-                        .clone()
-                        .unwrap_or_else(|| models::DEFAULT_DEPLOYMENT_GROUP.to_string());
-                    Ok((group, Some(env)))
-                }
-                _ => Err(ServerError::bad_request(
-                    "Multiple environments configured. Specify an environment to select one.",
-                )),
-            }
+    };
+
+    Ok((strategy.as_str().to_string(), drain_period_seconds))
+}
+
+/// Validate a persistent volume and serialize it for storage.
+///
+/// Only the Kubernetes controller creates PersistentVolumeClaims.
+fn normalize_volume(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    volume: Option<&crate::rise_toml::VolumeConfig>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(volume) = volume else {
+        return Ok(None);
+    };
+
+    if resource_builder.is_none() {
+        return Err(ServerError::bad_request(
+            "volume is only supported with the Kubernetes deployment controller",
+        ));
+    }
+    match super::quantity::parse_memory_bytes(&volume.size) {
+        Ok(bytes) if bytes > 0 => {}
+        _ => {
+            return Err(ServerError::bad_request(format!(
+                "Invalid volume.size '{}': expected a storage quantity such as '1Gi'",
+                volume.size
+            )));
         }
     }
+    if !volume.mount_path.starts_with('/') || volume.mount_path == "/" {
+        return Err(ServerError::bad_request(format!(
+            "volume.mount_path '{}' must be an absolute path other than '/'",
+            volume.mount_path
+        )));
+    }
+    if volume
+        .storage_class
+        .as_deref()
+        .is_some_and(|class| class.trim().is_empty())
+    {
+        return Err(ServerError::bad_request(
+            "volume.storage_class must not be empty when set",
+        ));
+    }
+
+    serde_json::to_value(volume)
+        .map(Some)
+        .internal_err("Failed to serialize volume configuration")
 }
 
-/// Resolve the effective HTTP port for a deployment.
+/// Validate replica spreading and serialize it for storage.
 ///
-/// Priority:
-/// 1. Explicit http_port from request (if provided)
-/// 2. PORT env var from project (if set and valid)
-/// 3. Default: 8080
-async fn resolve_effective_http_port(
-    state: &AppState,
-    project_id: uuid::Uuid,
-    explicit_port: Option<u16>,
-) -> Result<u16, ServerError> {
-    // 1. Explicit port takes precedence
-    if let Some(port) = explicit_port {
-        return Ok(port);
-    }
-
-    // 2. Check project's PORT env var
-    let project_env_vars =
-        crate::db::env_vars::list_project_env_vars(&state.db_pool, project_id, None)
-            .await
-            .internal_err("Failed to list project environment variables")?;
+/// Only the Kubernetes controller places pods by topology.
+fn normalize_scheduling(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    scheduling: Option<&crate::rise_toml::SchedulingConfig>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(scheduling) = scheduling else {
+        return Ok(None);
+    };
 
-    if let Some(port_var) = project_env_vars.iter().find(|v| v.key == "PORT") {
-        if let Ok(port) = port_var.value.parse::<u16>() {
-            if port > 0 {
-                debug!("Using PORT {} from project environment variable", port);
-                return Ok(port);
-            }
-        }
-        // Invalid PORT value - warn but fall through to default
-        debug!(
-            "Project PORT env var '{}' is not a valid port number, using default",
-            port_var.value
-        );
+    if resource_builder.is_none() {
+        return Err(ServerError::bad_request(
+            "scheduling is only supported with the Kubernetes deployment controller",
+        ));
     }
 
-    // 3. Default to 8080
-    debug!("No explicit port or PORT env var, defaulting to 8080");
-    Ok(8080)
+    serde_json::to_value(scheduling)
+        .map(Some)
+        .internal_err("Failed to serialize scheduling configuration")
 }
 
-/// Validate deployment resources against effective constraints.
+/// Validate the image pull policy and return its stored name.
 ///
-/// Constraints are resolved from: environment-specific overrides > platform defaults.
-/// When `is_redeploy` is true, error messages include a hint about using CLI flags to override
-/// inherited resource values.
-#[cfg(feature = "backend")]
-fn validate_resource_constraints(
-    state: &AppState,
-    resolved_environment: &Option<crate::db::models::Environment>,
-    replicas: u32,
-    cpu: &str,
-    memory: &str,
-    is_redeploy: bool,
-) -> Result<(), ServerError> {
-    let platform_constraints = state
-        .deployment_constraints
-        .as_ref()
-        .cloned()
-        .unwrap_or_default();
+/// ECS tasks pull according to the agent's configuration, not per deployment.
+fn normalize_image_pull_policy(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    image_pull_policy: Option<crate::rise_toml::ImagePullPolicy>,
+) -> Result<Option<String>, ServerError> {
+    let Some(image_pull_policy) = image_pull_policy else {
+        return Ok(None);
+    };
 
-    let eff_min_replicas = resolved_environment
-        .as_ref()
-        .and_then(|e| e.min_replicas)
-        .map(|v| v as u32)
-        .unwrap_or(platform_constraints.min_replicas);
-    let eff_max_replicas = resolved_environment
-        .as_ref()
-        .and_then(|e| e.max_replicas)
-        .map(|v| v as u32)
-        .unwrap_or(platform_constraints.max_replicas);
-    let eff_min_cpu = resolved_environment
-        .as_ref()
-        .and_then(|e| e.min_cpu.as_deref())
-        .unwrap_or(&platform_constraints.min_cpu);
-    let eff_max_cpu = resolved_environment
-        .as_ref()
-        .and_then(|e| e.max_cpu.as_deref())
-        .unwrap_or(&platform_constraints.max_cpu);
-    let eff_min_memory = resolved_environment
-        .as_ref()
-        .and_then(|e| e.min_memory.as_deref())
-        .unwrap_or(&platform_constraints.min_memory);
-    let eff_max_memory = resolved_environment
-        .as_ref()
-        .and_then(|e| e.max_memory.as_deref())
-        .unwrap_or(&platform_constraints.max_memory);
+    if resource_builder.is_none() {
+        return Err(ServerError::bad_request(
+            "image_pull_policy is only supported with the Kubernetes deployment controller",
+        ));
+    }
 
-    let redeploy_hint = if is_redeploy {
-        ". Use --replicas, --cpu, or --memory flags with `rise deploy` to override inherited values"
-    } else {
-        ""
+    Ok(Some(image_pull_policy.as_str().to_string()))
+}
+
+/// Validate ingress rate limits and serialize them for storage.
+///
+/// Rate limits are enforced by ingress-nginx, so they need the Kubernetes controller.
+fn normalize_rate_limit(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    rate_limit: Option<&crate::rise_toml::RateLimitConfig>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(rate_limit) = rate_limit else {
+        return Ok(None);
     };
 
-    if replicas < eff_min_replicas || replicas > eff_max_replicas {
-        return Err(ServerError::bad_request(format!(
-            "Requested {} replicas but allowed range is [{}, {}]{}",
-            replicas, eff_min_replicas, eff_max_replicas, redeploy_hint
-        )));
+    if rate_limit.rps.is_none() && rate_limit.connections.is_none() {
+        return Err(ServerError::bad_request(
+            "ratelimit: set at least one of 'rps' or 'connections'",
+        ));
+    }
+    for (name, value, max) in [
+        ("rps", rate_limit.rps, 100_000),
+        ("burst", rate_limit.burst, 100),
+        ("connections", rate_limit.connections, 100_000),
+    ] {
+        if let Some(value) = value {
+            if !(1..=max).contains(&value) {
+                return Err(ServerError::bad_request(format!(
+                    "ratelimit: '{}' must be between 1 and {}",
+                    name, max
+                )));
+            }
+        }
+    }
+    if rate_limit.burst.is_some() && rate_limit.rps.is_none() {
+        return Err(ServerError::bad_request(
+            "ratelimit: 'burst' is a multiple of 'rps' and requires it",
+        ));
+    }
+    if resource_builder.is_none() {
+        return Err(ServerError::bad_request(
+            "ratelimit is only supported with the Kubernetes deployment controller",
+        ));
     }
 
-    super::quantity::validate_cpu_range(cpu, eff_min_cpu, eff_max_cpu).map_err(|e| {
-        ServerError::bad_request(format!("CPU constraint violation: {}{}", e, redeploy_hint))
-    })?;
+    serde_json::to_value(rate_limit)
+        .map(Some)
+        .internal_err("Failed to serialize rate limit configuration")
+}
 
-    super::quantity::validate_memory_range(memory, eff_min_memory, eff_max_memory).map_err(
-        |e| {
-            ServerError::bad_request(format!(
-                "Memory constraint violation: {}{}",
-                e, redeploy_hint
-            ))
-        },
-    )?;
+/// Maximum number of additional ports a deployment may declare
+const MAX_EXTRA_PORTS: usize = 10;
 
-    Ok(())
-}
+/// Port names the controller uses for the primary Service port
+const RESERVED_PORT_NAMES: &[&str] = &["http", "tcp"];
 
-/// POST /deployments - Create a new deployment
-pub async fn create_deployment(
-    State(state): State<AppState>,
-    auth: AuthContext,
-    Json(payload): Json<CreateDeploymentRequest>,
-) -> Result<Json<CreateDeploymentResponse>, ServerError> {
-    info!("Creating deployment for project '{}'", payload.project);
+/// Validate additional container ports and serialize them for storage.
+///
+/// Names must be valid Kubernetes port names (IANA service names: at most 15
+/// lowercase alphanumerics or '-', with at least one letter). Conflicts with the
+/// primary port are checked by [`validate_extra_ports_http_port`] once it is known.
+fn normalize_extra_ports(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    ports: Option<&std::collections::BTreeMap<String, crate::rise_toml::PortConfig>>,
+) -> Result<Option<serde_json::Value>, ServerError> {
+    let Some(ports) = ports.filter(|ports| !ports.is_empty()) else {
+        return Ok(None);
+    };
 
-    // Validate deployment group name if explicitly provided
-    if let Some(ref group) = payload.group {
-        if !is_valid_group_name(group) {
+    if ports.len() > MAX_EXTRA_PORTS {
+        return Err(ServerError::bad_request(format!(
+            "ports: at most {} additional ports are allowed",
+            MAX_EXTRA_PORTS
+        )));
+    }
+    let mut seen = std::collections::HashSet::new();
+    for (name, config) in ports {
+        let valid_name = !name.is_empty()
+            && name.len() <= 15
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            && name.chars().any(|c| c.is_ascii_lowercase())
+            && !name.starts_with('-')
+            && !name.ends_with('-')
+            && !name.contains("--");
+        if !valid_name {
             return Err(ServerError::bad_request(format!(
-                "Invalid group name '{}'. Must be 'default' or match pattern [a-z0-9][a-z0-9/-]*[a-z0-9] (no consecutive hyphens, normalized length max 63 chars)",
-                group
+                "ports: invalid port name '{}' (at most 15 lowercase alphanumerics or '-', \
+                 with at least one letter)",
+                name
             )));
         }
-    }
-
-    // Validate http_port if provided (should be 1-65535)
-    if let Some(port) = payload.http_port {
-        if port == 0 {
-            return Err(ServerError::bad_request(
-                "HTTP port must be between 1 and 65535",
-            ));
+        if RESERVED_PORT_NAMES.contains(&name.as_str()) {
+            return Err(ServerError::bad_request(format!(
+                "ports: the name '{}' is reserved for the primary port",
+                name
+            )));
+        }
+        if config.port == 0 {
+            return Err(ServerError::bad_request(format!(
+                "ports: '{}' must be between 1 and 65535",
+                name
+            )));
+        }
+        if !seen.insert(config.port) {
+            return Err(ServerError::bad_request(format!(
+                "ports: port {} is declared more than once",
+                config.port
+            )));
         }
     }
+    if resource_builder.is_none() {
+        return Err(ServerError::bad_request(
+            "ports is only supported with the Kubernetes deployment controller",
+        ));
+    }
 
-    // Validate and normalize URL fields if provided
-    let job_url = match payload.job_url {
-        Some(ref url) => Some(
-            crate::server::project::handlers::validate_http_url(url)
-                .map_err(|e| ServerError::bad_request(format!("job_url: {e}")))?,
-        ),
-        None => None,
-    };
-    let pull_request_url = match payload.pull_request_url {
-        Some(ref url) => Some(
-            crate::server::project::handlers::validate_http_url(url)
-                .map_err(|e| ServerError::bad_request(format!("pull_request_url: {e}")))?,
-        ),
-        None => None,
-    };
-
-    validate_env_overrides(&payload.env_overrides)?;
+    serde_json::to_value(ports)
+        .map(Some)
+        .internal_err("Failed to serialize port configuration")
+}
 
-    // Parse expiration duration if provided
-    let expires_at = if let Some(ref expires_in) = payload.expires_in {
-        Some(parse_expiration(expires_in).map_err(|e| {
-            ServerError::bad_request(format!(
-                "Invalid expiration duration '{}': {}",
-                expires_in, e
-            ))
-        })?)
-    } else {
-        None
+/// Reject additional ports that reuse the primary port, which stays the one used
+/// for the Ingress and health probes.
+fn validate_extra_ports_http_port(
+    extra_ports: Option<&serde_json::Value>,
+    http_port: u16,
+) -> Result<(), ServerError> {
+    let ports: std::collections::BTreeMap<String, crate::rise_toml::PortConfig> = match extra_ports
+    {
+        Some(value) => serde_json::from_value(value.clone())
+            .internal_err("Failed to parse stored port configuration")?,
+        None => return Ok(()),
     };
+    if let Some((name, _)) = ports.iter().find(|(_, config)| config.port == http_port) {
+        return Err(ServerError::bad_request(format!(
+            "ports: '{}' uses port {}, which is already the deployment's http_port",
+            name, http_port
+        )));
+    }
+    Ok(())
+}
 
-    // Query project by name
-    let project = projects::find_by_name(&state.db_pool, &payload.project)
-        .await
-        .internal_err("Failed to query project")?
-        .ok_or_else(|| {
-            ServerError::not_found(format!("Project '{}' not found", payload.project))
-        })?;
-
-    // Prevent deployments on projects in deletion lifecycle
-    // Projects in Deleting or Terminated status should not accept new deployments
-    if matches!(
-        project.status,
-        crate::db::models::ProjectStatus::Deleting | crate::db::models::ProjectStatus::Terminated
-    ) {
-        return Err(ServerError::conflict(format!(
-            "Cannot create deployment for project in {:?} state",
-            project.status
+/// Validate the protocol the app speaks and return its stored name.
+///
+/// Only the Kubernetes controller exposes plain TCP apps (through their Service);
+/// the ECS controller serves every app through its HTTP load balancer.
+fn normalize_protocol(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    protocol: Option<crate::rise_toml::DeploymentProtocol>,
+) -> Result<String, ServerError> {
+    let protocol = protocol.unwrap_or_default();
+    if protocol != crate::rise_toml::DeploymentProtocol::Http && resource_builder.is_none() {
+        return Err(ServerError::bad_request(format!(
+            "protocol '{}' is only supported with the Kubernetes deployment controller",
+            protocol.as_str()
         )));
     }
 
-    // Resolve deployment target (group + environment) from request parameters
-    let (resolved_group, resolved_environment) = resolve_deployment_target(
-        &state.db_pool,
-        project.id,
+    Ok(protocol.as_str().to_string())
+}
+
+/// Validate the pods' Kubernetes ServiceAccount and return its name.
+///
+/// ServiceAccount names are DNS subdomain names: at most 253 lowercase
+/// alphanumerics, '-' or '.', starting and ending with an alphanumeric.
+/// `default` and the per-environment `env-*` accounts are reserved, so a
+/// deployment can't borrow another environment's IAM binding.
+fn normalize_service_account(
+    resource_builder: Option<&super::resource_builder::ResourceBuilder>,
+    service_account: Option<&crate::rise_toml::ServiceAccountConfig>,
+) -> Result<Option<String>, ServerError> {
+    let Some(service_account) = service_account else {
+        return Ok(None);
+    };
+
+    let name = service_account.name.as_str();
+    let valid = !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                && !part.starts_with('-')
+                && !part.ends_with('-')
+        });
+    if !valid {
+        return Err(ServerError::bad_request(format!(
+            "service_account: invalid name '{}' (lowercase alphanumerics, '-' or '.', \
+             starting and ending with an alphanumeric, at most 253 characters)",
+            name
+        )));
+    }
+    if name == "default" || name.starts_with("env-") {
+        return Err(ServerError::bad_request(format!(
+            "service_account: '{}' is reserved for the namespace or environment \
+             ServiceAccounts; use a dedicated name",
+            name
+        )));
+    }
+    if resource_builder.is_none() {
+        return Err(ServerError::bad_request(
+            "service_account is only supported with the Kubernetes deployment controller",
+        ));
+    }
+
+    Ok(Some(name.to_string()))
+}
+
+/// Reject multiple replicas for deployments with a volume: the claim is
+/// `ReadWriteOnce`, so replicas on different nodes could never mount it.
+fn validate_volume_replicas(
+    volume: Option<&serde_json::Value>,
+    replicas: u32,
+) -> Result<(), ServerError> {
+    if volume.is_some() && replicas != 1 {
+        return Err(ServerError::bad_request(format!(
+            "Deployments with a volume must run exactly 1 replica (requested {})",
+            replicas
+        )));
+    }
+    Ok(())
+}
+
+/// Reject blue-green for deployments with a volume: the previous deployment must
+/// release the group's `ReadWriteOnce` claim before the new one starts, so it can't
+/// keep running through a drain period.
+fn validate_volume_strategy(
+    volume: Option<&serde_json::Value>,
+    strategy: &str,
+) -> Result<(), ServerError> {
+    if volume.is_some() && DeploymentStrategy::from_db(strategy) == DeploymentStrategy::BlueGreen {
+        return Err(ServerError::bad_request(
+            "strategy 'blue-green' is not supported for deployments with a volume",
+        ));
+    }
+    Ok(())
+}
+
+/// Validate a container command/args override.
+///
+/// `field` is `command` or `args`. An empty list is treated as no override, so the
+/// image's entrypoint/cmd is kept.
+fn normalize_container_override(
+    field: &str,
+    values: Option<&[String]>,
+) -> Result<Option<Vec<String>>, ServerError> {
+    let Some(values) = values.filter(|values| !values.is_empty()) else {
+        return Ok(None);
+    };
+
+    if field == "command" && values[0].trim().is_empty() {
+        return Err(ServerError::bad_request(
+            "command must start with a non-empty executable",
+        ));
+    }
+
+    Ok(Some(values.to_vec()))
+}
+
+/// Convert DB DeploymentStatus to API DeploymentStatus
+fn convert_status_from_db(status: DbDeploymentStatus) -> DeploymentStatus {
+    match status {
+        DbDeploymentStatus::Pending => DeploymentStatus::Pending,
+        DbDeploymentStatus::Building => DeploymentStatus::Building,
+        DbDeploymentStatus::Pushing => DeploymentStatus::Pushing,
+        DbDeploymentStatus::Pushed => DeploymentStatus::Pushed,
+        DbDeploymentStatus::Deploying => DeploymentStatus::Deploying,
+        DbDeploymentStatus::Healthy => DeploymentStatus::Healthy,
+        DbDeploymentStatus::Unhealthy => DeploymentStatus::Unhealthy,
+        DbDeploymentStatus::Cancelling => DeploymentStatus::Cancelling,
+        DbDeploymentStatus::Cancelled => DeploymentStatus::Cancelled,
+        DbDeploymentStatus::Terminating => DeploymentStatus::Terminating,
+        DbDeploymentStatus::Stopped => DeploymentStatus::Stopped,
+        DbDeploymentStatus::Superseded => DeploymentStatus::Superseded,
+        DbDeploymentStatus::Failed => DeploymentStatus::Failed,
+        DbDeploymentStatus::Expired => DeploymentStatus::Expired,
+    }
+}
+
+/// Fetch the creator email for a deployment
+async fn get_creator_email(pool: &sqlx::PgPool, created_by_id: uuid::Uuid) -> String {
+    match users::find_by_id(pool, created_by_id).await {
+        Ok(Some(user)) => user.email,
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Convert DB Deployment to API Deployment
+///
+/// Dynamically calculates the image tag when not stored in the database,
+/// using the registry provider's configuration to construct the full image reference.
+async fn convert_deployment(
+    state: &AppState,
+    deployment: crate::db::models::Deployment,
+    project: &crate::db::models::Project,
+    created_by_email: String,
+    primary_url: Option<String>,
+    custom_domain_urls: Vec<String>,
+) -> Deployment {
+    // Backfill image field for locally-built deployments
+    // For pre-built images, deployment.image is already set
+    // For build-from-source, calculate the internal registry tag
+    let image = if deployment.image.is_some() {
+        deployment.image.clone()
+    } else {
+        Some(super::utils::get_deployment_image_tag(state, &deployment, project).await)
+    };
+    let can_rollback = state_machine::can_create_from(&deployment);
+
+    // Resolve environment name and color
+    let (environment, environment_color) = if let Some(env_id) = deployment.environment_id {
+        let env = crate::db::environments::find_by_id(&state.db_pool, env_id)
+            .await
+            .ok()
+            .flatten();
+        (env.as_ref().map(|e| e.name.clone()), env.map(|e| e.color))
+    } else {
+        (None, None)
+    };
+
+    Deployment {
+        id: deployment.id.to_string(),
+        deployment_id: deployment.deployment_id,
+        project: deployment.project_id.to_string(),
+        created_by: deployment.created_by_id.to_string(),
+        created_by_email,
+        status: convert_status_from_db(deployment.status),
+        deployment_group: deployment.deployment_group,
+        environment,
+        environment_color,
+        expires_at: deployment.expires_at.map(|dt| dt.to_rfc3339()),
+        error_message: deployment.error_message,
+        completed_at: deployment.completed_at.map(|dt| dt.to_rfc3339()),
+        build_logs: deployment.build_logs,
+        controller_metadata: deployment.controller_metadata,
+        primary_url,
+        custom_domain_urls,
+        image,
+        image_digest: deployment.image_digest,
+        http_port: deployment.http_port as u16,
+        is_active: deployment.is_active,
+        can_rollback,
+        replicas: deployment.replicas as u32,
+        cpu: deployment.cpu,
+        memory: deployment.memory,
+        cpu_limit: deployment.cpu_limit,
+        memory_limit: deployment.memory_limit,
+        job_url: deployment.job_url,
+        pull_request_url: deployment.pull_request_url,
+        message: deployment.message,
+        created: deployment.created_at.to_rfc3339(),
+        updated: deployment.updated_at.to_rfc3339(),
+    }
+}
+
+/// Resolve the deployment target (group + environment) from request parameters.
+///
+/// Rules:
+/// - If both specified: use both, but validate that the group is not the primary
+///   deployment group of a *different* environment.
+/// - If environment specified, no group: use environment's primary deployment group.
+/// - If group specified, no environment: look up environment whose primary group matches;
+///   then auto-resolve (0 envs → none, 1 env → use it, 2+ → error).
+/// - If neither specified: if the project has exactly one environment, use it.
+///   If zero, use the default group with no environment. If more than one, error.
+async fn resolve_deployment_target(
+    pool: &sqlx::PgPool,
+    project_id: uuid::Uuid,
+    requested_environment: Option<&str>,
+    requested_group: Option<&str>,
+) -> Result<(String, Option<crate::db::models::Environment>), ServerError> {
+    use crate::db::environments;
+
+    match (requested_environment, requested_group) {
+        // Both specified
+        (Some(env_name), Some(group)) => {
+            let env = environments::find_by_name(pool, project_id, env_name)
+                .await
+                .internal_err("Failed to look up environment")?
+                .ok_or_else(|| {
+                    ServerError::not_found(format!("Environment '{}' not found", env_name))
+                })?;
+            // Validate: if the group is the primary group of another environment, reject
+            if let Some(primary_env) = environments::find_by_primary_group(pool, project_id, group)
+                .await
+                .internal_err("Failed to look up environment by group")?
+            {
+                if primary_env.name != env_name {
+                    return Err(ServerError::bad_request(format!(
+                        "Group '{}' is the primary deployment group of environment '{}', and cannot be used with environment '{}'.",
+                        group, primary_env.name, env_name
+                    )));
+                }
+            }
+            Ok((group.to_string(), Some(env)))
+        }
+        // Environment only, no group
+        (Some(env_name), None) => {
+            let env = environments::find_by_name(pool, project_id, env_name)
+                .await
+                .internal_err("Failed to look up environment")?
+                .ok_or_else(|| {
+                    ServerError::not_found(format!("Environment '{}' not found", env_name))
+                })?;
+            let group = env.primary_deployment_group.clone().ok_or_else(|| {
+                ServerError::bad_request(format!(
+                    "Environment '{}' has no primary deployment group. Specify a deployment group explicitly.",
+                    env_name
+                ))
+            })?;
+            Ok((group, Some(env)))
+        }
+        // Group only, no environment
+        (None, Some(group)) => {
+            // Check if this group is the primary group of an environment
+            let env = environments::find_by_primary_group(pool, project_id, group)
+                .await
+                .internal_err("Failed to look up environment by group")?;
+            if env.is_some() {
+                Ok((group.to_string(), env))
+            } else {
+                // No primary group match. Auto-resolve from available environments.
+                let all_envs = environments::list_for_project(pool, project_id)
+                    .await
+                    .internal_err("Failed to list environments")?;
+                match all_envs.len() {
+                    0 => Ok((group.to_string(), None)),
+                    1 => Ok((
+                        group.to_string(),
+                        Some(all_envs.into_iter().next().unwrap()),
+                    )),
+                    _ => Err(ServerError::bad_request(
+                        "Multiple environments configured. Specify an environment to select one.",
+                    )),
+                }
+            }
+        }
+        // Neither specified: auto-resolve from available environments
+        (None, None) => {
+            let all_envs = environments::list_for_project(pool, project_id)
+                .await
+                .internal_err("Failed to list environments")?;
+            match all_envs.len() {
+                0 => Ok((models::DEFAULT_DEPLOYMENT_GROUP.to_string(), None)),
+                1 => {
+                    let env = all_envs.into_iter().next().unwrap();
+                    let group = env
+                        .primary_deployment_group
+                        .clone()
+                        .unwrap_or_else(|| models::DEFAULT_DEPLOYMENT_GROUP.to_string());
+                    Ok((group, Some(env)))
+                }
+                _ => Err(ServerError::bad_request(
+                    "Multiple environments configured. Specify an environment to select one.",
+                )),
+            }
+        }
+    }
+}
+
+/// Resolve the effective HTTP port for a deployment.
+///
+/// Priority:
+/// 1. Explicit http_port from request (if provided)
+/// 2. PORT env var from project (if set and valid)
+/// 3. Default: 8080
+async fn resolve_effective_http_port(
+    state: &AppState,
+    project_id: uuid::Uuid,
+    explicit_port: Option<u16>,
+) -> Result<u16, ServerError> {
+    // 1. Explicit port takes precedence
+    if let Some(port) = explicit_port {
+        return Ok(port);
+    }
+
+    // 2. Check project's PORT env var
+    let project_env_vars =
+        crate::db::env_vars::list_project_env_vars(&state.db_pool, project_id, None)
+            .await
+            .internal_err("Failed to list project environment variables")?;
+
+    if let Some(port_var) = project_env_vars.iter().find(|v| v.key == "PORT") {
+        if let Ok(port) = port_var.value.parse::<u16>() {
+            if port > 0 {
+                debug!("Using PORT {} from project environment variable", port);
+                return Ok(port);
+            }
+        }
+        // Invalid PORT value - warn but fall through to default
+        debug!(
+            "Project PORT env var '{}' is not a valid port number, using default",
+            port_var.value
+        );
+    }
+
+    // 3. Default to 8080
+    debug!("No explicit port or PORT env var, defaulting to 8080");
+    Ok(8080)
+}
+
+/// Validate deployment resources against effective constraints.
+///
+/// Constraints are resolved from: environment-specific overrides > platform defaults.
+/// When `is_redeploy` is true, error messages include a hint about using CLI flags to override
+/// inherited resource values.
+///
+/// Limits must lie between the request and the maximum allowed value.
+#[cfg(feature = "backend")]
+#[allow(clippy::too_many_arguments)]
+fn validate_resource_constraints(
+    state: &AppState,
+    resolved_environment: &Option<crate::db::models::Environment>,
+    replicas: u32,
+    cpu: &str,
+    memory: &str,
+    cpu_limit: Option<&str>,
+    memory_limit: Option<&str>,
+    is_redeploy: bool,
+) -> Result<(), ServerError> {
+    let platform_constraints = state
+        .deployment_constraints
+        .as_ref()
+        .cloned()
+        .unwrap_or_default();
+
+    let eff_min_replicas = resolved_environment
+        .as_ref()
+        .and_then(|e| e.min_replicas)
+        .map(|v| v as u32)
+        .unwrap_or(platform_constraints.min_replicas);
+    let eff_max_replicas = resolved_environment
+        .as_ref()
+        .and_then(|e| e.max_replicas)
+        .map(|v| v as u32)
+        .unwrap_or(platform_constraints.max_replicas);
+    let eff_min_cpu = resolved_environment
+        .as_ref()
+        .and_then(|e| e.min_cpu.as_deref())
+        .unwrap_or(&platform_constraints.min_cpu);
+    let eff_max_cpu = resolved_environment
+        .as_ref()
+        .and_then(|e| e.max_cpu.as_deref())
+        .unwrap_or(&platform_constraints.max_cpu);
+    let eff_min_memory = resolved_environment
+        .as_ref()
+        .and_then(|e| e.min_memory.as_deref())
+        .unwrap_or(&platform_constraints.min_memory);
+    let eff_max_memory = resolved_environment
+        .as_ref()
+        .and_then(|e| e.max_memory.as_deref())
+        .unwrap_or(&platform_constraints.max_memory);
+
+    let redeploy_hint = if is_redeploy {
+        ". Use --replicas, --cpu, or --memory flags with `rise deploy` to override inherited values"
+    } else {
+        ""
+    };
+
+    if replicas < eff_min_replicas || replicas > eff_max_replicas {
+        return Err(ServerError::bad_request(format!(
+            "Requested {} replicas but allowed range is [{}, {}]{}",
+            replicas, eff_min_replicas, eff_max_replicas, redeploy_hint
+        )));
+    }
+
+    super::quantity::validate_cpu_range(cpu, eff_min_cpu, eff_max_cpu).map_err(|e| {
+        ServerError::bad_request(format!("CPU constraint violation: {}{}", e, redeploy_hint))
+    })?;
+
+    super::quantity::validate_memory_range(memory, eff_min_memory, eff_max_memory).map_err(
+        |e| {
+            ServerError::bad_request(format!(
+                "Memory constraint violation: {}{}",
+                e, redeploy_hint
+            ))
+        },
+    )?;
+
+    if let Some(cpu_limit) = cpu_limit {
+        super::quantity::validate_cpu_range(cpu_limit, cpu, eff_max_cpu).map_err(|e| {
+            ServerError::bad_request(format!(
+                "CPU limit constraint violation: {}{}",
+                e, redeploy_hint
+            ))
+        })?;
+    }
+
+    if let Some(memory_limit) = memory_limit {
+        super::quantity::validate_memory_range(memory_limit, memory, eff_max_memory).map_err(
+            |e| {
+                ServerError::bad_request(format!(
+                    "Memory limit constraint violation: {}{}",
+                    e, redeploy_hint
+                ))
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Resolve the requested deployment resources (replicas, cpu, memory, limits).
+///
+/// Priority: request payload > platform defaults. Validation against constraints
+/// happens separately, after redeploys inherited the source deployment's values.
+fn resolve_requested_resources(
+    state: &AppState,
+    payload: &CreateDeploymentRequest,
+) -> (u32, String, String, Option<String>, Option<String>) {
+    #[cfg(feature = "backend")]
+    {
+        let defaults = state
+            .deployment_defaults
+            .as_ref()
+            .cloned()
+            .unwrap_or_default();
+
+        // Resolve: request > platform defaults
+        let replicas = payload.replicas.unwrap_or(defaults.replicas);
+        let cpu = payload.cpu.clone().unwrap_or_else(|| defaults.cpu.clone());
+        let memory = payload
+            .memory
+            .clone()
+            .unwrap_or_else(|| defaults.memory.clone());
+        // Default limits only apply together with the default request
+        let cpu_limit = payload.cpu_limit.clone().or_else(|| {
+            payload
+                .cpu
+                .is_none()
+                .then(|| defaults.cpu_limit.clone())
+                .flatten()
+        });
+        let memory_limit = payload.memory_limit.clone().or_else(|| {
+            payload
+                .memory
+                .is_none()
+                .then(|| defaults.memory_limit.clone())
+                .flatten()
+        });
+
+        (replicas, cpu, memory, cpu_limit, memory_limit)
+    }
+
+    #[cfg(not(feature = "backend"))]
+    {
+        let replicas = payload.replicas.unwrap_or(1);
+        let cpu = payload.cpu.clone().unwrap_or_else(|| "500m".to_string());
+        let memory = payload
+            .memory
+            .clone()
+            .unwrap_or_else(|| "256Mi".to_string());
+        (
+            replicas,
+            cpu,
+            memory,
+            payload.cpu_limit.clone(),
+            payload.memory_limit.clone(),
+        )
+    }
+}
+
+/// Validate a deployment group name given in a create request
+fn validate_requested_group(group: Option<&str>) -> Result<(), ServerError> {
+    match group {
+        Some(group) if !is_valid_group_name(group) => Err(ServerError::bad_request(format!(
+            "Invalid group name '{}'. Must be 'default' or match pattern [a-z0-9][a-z0-9/-]*[a-z0-9] (no consecutive hyphens, normalized length max 63 chars)",
+            group
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Reject new deployments for projects that are being deleted
+fn ensure_project_accepts_deployments(
+    project: &crate::db::models::Project,
+) -> Result<(), ServerError> {
+    // Projects in Deleting or Terminated status should not accept new deployments
+    if matches!(
+        project.status,
+        crate::db::models::ProjectStatus::Deleting | crate::db::models::ProjectStatus::Terminated
+    ) {
+        return Err(ServerError::conflict(format!(
+            "Cannot create deployment for project in {:?} state",
+            project.status
+        )));
+    }
+    Ok(())
+}
+
+/// Resolve who is deploying to the project and check they may deploy to the target environment
+async fn authorize_deployment(
+    state: &AppState,
+    auth: AuthContext,
+    project: &crate::db::models::Project,
+    resolved_environment: Option<&crate::db::models::Environment>,
+) -> Result<crate::db::models::User, ServerError> {
+    // Resolve auth for project scope (validates SA claims if external token)
+    // Only mask auth failures (401/403) as 404 to prevent project existence leakage;
+    // preserve 409 (SA collision) and 5xx (misconfiguration) for diagnosability.
+    let (user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
+
+    // Check deployment permissions (SA access already validated above)
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(state, &user, project)
+            .await
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project.name)))?;
+        return Ok(user);
+    }
+
+    // Enforce service account environment restrictions
+    let sa = service_accounts::find_active_by_user_id(&state.db_pool, user.id)
+        .await
+        .internal_err("Failed to look up service account")?;
+    if let Some(ref allowed_env_ids) = sa.and_then(|sa| sa.allowed_environment_ids) {
+        match resolved_environment.map(|e| e.id) {
+            Some(env_id) if !allowed_env_ids.contains(&env_id) => {
+                return Err(ServerError::forbidden(
+                    "This service account is not allowed to deploy to the requested environment",
+                ));
+            }
+            None => {
+                // SA has environment restrictions but no environment was specified;
+                // block the deployment since we can't verify the target is allowed.
+                return Err(ServerError::forbidden(
+                    "This service account requires an explicit environment target",
+                ));
+            }
+            _ => {} // target environment is in the allowed list
+        }
+    }
+
+    Ok(user)
+}
+
+/// Deployment settings resolved from a create request
+///
+/// Produced by [`resolve_deployment_spec`] for both `create_deployment` and
+/// `dry_run_deployment`, so a dry run renders exactly what a deploy would store.
+struct DeploymentSpec {
+    http_port: u16,
+    replicas: u32,
+    cpu: String,
+    memory: String,
+    cpu_limit: Option<String>,
+    memory_limit: Option<String>,
+    health_check: Option<serde_json::Value>,
+    init_container: Option<serde_json::Value>,
+    protocol: String,
+    labels: Option<serde_json::Value>,
+    annotations: Option<serde_json::Value>,
+    command: Option<Vec<String>>,
+    args: Option<Vec<String>>,
+    ingress_annotations: Option<serde_json::Value>,
+    strategy: String,
+    drain_period_seconds: Option<i32>,
+    volume: Option<serde_json::Value>,
+    scheduling: Option<serde_json::Value>,
+    image_pull_policy: Option<String>,
+    rate_limit: Option<serde_json::Value>,
+    extra_ports: Option<serde_json::Value>,
+    service_account_name: Option<String>,
+    /// Deployment a redeploy is created from (`from_deployment`)
+    source: Option<crate::db::models::Deployment>,
+}
+
+impl DeploymentSpec {
+    /// Deployment that built the image of a redeploy
+    ///
+    /// For chained redeployments this follows the chain to the original source, so the
+    /// image tag of the deployment that built the image is used.
+    fn original_source_id(&self) -> Option<uuid::Uuid> {
+        self.source
+            .as_ref()
+            .map(|source| source.rolled_back_from_deployment_id.unwrap_or(source.id))
+    }
+}
+
+/// Normalize and validate the settings of a create request
+///
+/// Values not given in the request fall back to the platform defaults, or for
+/// redeploys (`from_deployment`) to the source deployment's values. Validation runs
+/// on the effective values, after that inheritance.
+async fn resolve_deployment_spec(
+    state: &AppState,
+    payload: &CreateDeploymentRequest,
+    project: &crate::db::models::Project,
+    resolved_environment: &Option<crate::db::models::Environment>,
+) -> Result<DeploymentSpec, ServerError> {
+    // Validate http_port if provided (should be 1-65535)
+    if payload.http_port == Some(0) {
+        return Err(ServerError::bad_request(
+            "HTTP port must be between 1 and 65535",
+        ));
+    }
+
+    let resource_builder = state.resource_builder.as_deref();
+
+    // Resolve effective http_port:
+    // 1. Explicit http_port from request (if provided)
+    // 2. Source deployment's http_port (if --from is used, handled below)
+    // 3. PORT env var from project (if set and valid)
+    // 4. Default: 8080
+    let http_port = resolve_effective_http_port(state, project.id, payload.http_port).await?;
+
+    // Resolve effective deployment resources (replicas, cpu, memory)
+    // Priority: request payload > platform defaults
+    // Validation against constraints happens after rollback inheritance (below)
+    let (replicas, cpu, memory, cpu_limit, memory_limit) =
+        resolve_requested_resources(state, payload);
+    let (strategy, drain_period_seconds) = normalize_strategy(
+        resource_builder,
+        payload.strategy,
+        payload.drain_period.as_deref(),
+    )?;
+
+    let mut spec = DeploymentSpec {
+        http_port,
+        replicas,
+        cpu,
+        memory,
+        cpu_limit,
+        memory_limit,
+        health_check: normalize_health_check(payload.health_check.as_ref())?,
+        init_container: normalize_init_container(payload.init_container.as_ref())?,
+        protocol: normalize_protocol(resource_builder, payload.protocol)?,
+        labels: normalize_workload_metadata("labels", payload.labels.as_ref())?,
+        annotations: normalize_workload_metadata("annotations", payload.annotations.as_ref())?,
+        command: normalize_container_override("command", payload.command.as_deref())?,
+        args: normalize_container_override("args", payload.args.as_deref())?,
+        ingress_annotations: normalize_ingress_annotations(
+            resource_builder,
+            payload.ingress_annotations.as_ref(),
+        )?,
+        strategy,
+        drain_period_seconds,
+        volume: normalize_volume(resource_builder, payload.volume.as_ref())?,
+        scheduling: normalize_scheduling(resource_builder, payload.scheduling.as_ref())?,
+        image_pull_policy: normalize_image_pull_policy(
+            resource_builder,
+            payload.image_pull_policy,
+        )?,
+        rate_limit: normalize_rate_limit(resource_builder, payload.ratelimit.as_ref())?,
+        extra_ports: normalize_extra_ports(resource_builder, payload.ports.as_ref())?,
+        service_account_name: normalize_service_account(
+            resource_builder,
+            payload.service_account.as_ref(),
+        )?,
+        source: None,
+    };
+
+    // Handle deployment creation from an existing deployment (redeploy/rollback)
+    if let Some(ref from_deployment_id) = payload.from_deployment {
+        let source = db_deployments::find_by_project_and_deployment_id(
+            &state.db_pool,
+            project.id,
+            from_deployment_id,
+        )
+        .await
+        .internal_err("Failed to find source deployment")?
+        .ok_or_else(|| {
+            ServerError::not_found(format!(
+                "Source deployment '{}' not found for project '{}'",
+                from_deployment_id, project.name
+            ))
+        })?;
+
+        // Verify the source deployment already has a reusable image.
+        if !state_machine::can_create_from(&source) {
+            return Err(ServerError::bad_request(format!(
+                "Cannot create deployment from '{}' because its image is not available yet (status '{}').",
+                from_deployment_id, source.status
+            )));
+        }
+
+        // Inherit settings from source deployment unless explicitly overridden
+        if payload.http_port.is_none() {
+            spec.http_port = source.http_port as u16;
+        }
+        if payload.replicas.is_none() {
+            spec.replicas = source.replicas as u32;
+        }
+        if payload.cpu.is_none() {
+            spec.cpu = source.cpu.clone();
+            if payload.cpu_limit.is_none() {
+                spec.cpu_limit = source.cpu_limit.clone();
+            }
+        }
+        if payload.memory.is_none() {
+            spec.memory = source.memory.clone();
+            if payload.memory_limit.is_none() {
+                spec.memory_limit = source.memory_limit.clone();
+            }
+        }
+        if payload.health_check.is_none() {
+            spec.health_check = source.health_check.clone();
+        }
+        if payload.init_container.is_none() {
+            spec.init_container = source.init_container.clone();
+        }
+        if payload.protocol.is_none() {
+            spec.protocol = source.protocol.clone();
+        }
+        if payload.labels.is_none() {
+            spec.labels = source.labels.clone();
+        }
+        if payload.annotations.is_none() {
+            spec.annotations = source.annotations.clone();
+        }
+        if payload.command.is_none() {
+            spec.command = source.command.clone();
+        }
+        if payload.args.is_none() {
+            spec.args = source.args.clone();
+        }
+        if payload.ingress_annotations.is_none() {
+            spec.ingress_annotations = source.ingress_annotations.clone();
+        }
+        if payload.strategy.is_none() {
+            spec.strategy = source.strategy.clone();
+            spec.drain_period_seconds = source.drain_period_seconds;
+        }
+        if payload.volume.is_none() {
+            spec.volume = source.volume.clone();
+        }
+        if payload.scheduling.is_none() {
+            spec.scheduling = source.scheduling.clone();
+        }
+        if payload.image_pull_policy.is_none() {
+            spec.image_pull_policy = source.image_pull_policy.clone();
+        }
+        if payload.ratelimit.is_none() {
+            spec.rate_limit = source.rate_limit.clone();
+        }
+        if payload.ports.is_none() {
+            spec.extra_ports = source.extra_ports.clone();
+        }
+        if payload.service_account.is_none() {
+            spec.service_account_name = source.service_account_name.clone();
+        }
+        spec.source = Some(source);
+    }
+
+    validate_volume_replicas(spec.volume.as_ref(), spec.replicas)?;
+    validate_volume_strategy(spec.volume.as_ref(), &spec.strategy)?;
+    validate_extra_ports_http_port(spec.extra_ports.as_ref(), spec.http_port)?;
+    #[cfg(feature = "backend")]
+    validate_resource_constraints(
+        state,
+        resolved_environment,
+        spec.replicas,
+        &spec.cpu,
+        &spec.memory,
+        spec.cpu_limit.as_deref(),
+        spec.memory_limit.as_deref(),
+        spec.source.is_some(),
+    )?;
+    #[cfg(not(feature = "backend"))]
+    let _ = resolved_environment;
+
+    Ok(spec)
+}
+
+/// POST /deployments - Create a new deployment
+pub async fn create_deployment(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(payload): Json<CreateDeploymentRequest>,
+) -> Result<Json<CreateDeploymentResponse>, ServerError> {
+    info!("Creating deployment for project '{}'", payload.project);
+
+    // Validate deployment group name if explicitly provided
+    validate_requested_group(payload.group.as_deref())?;
+
+    // Validate and normalize URL fields if provided
+    let job_url = match payload.job_url {
+        Some(ref url) => Some(
+            crate::server::project::handlers::validate_http_url(url)
+                .map_err(|e| ServerError::bad_request(format!("job_url: {e}")))?,
+        ),
+        None => None,
+    };
+    let pull_request_url = match payload.pull_request_url {
+        Some(ref url) => Some(
+            crate::server::project::handlers::validate_http_url(url)
+                .map_err(|e| ServerError::bad_request(format!("pull_request_url: {e}")))?,
+        ),
+        None => None,
+    };
+
+    let message = normalize_deployment_message(payload.message.as_deref())?;
+
+    validate_env_overrides(&payload.env_overrides)?;
+
+    // Parse expiration duration if provided, capped to the platform's maximum
+    let max_expiration = max_expiration(&state)?;
+    let expires_at = match payload.expires_in {
+        Some(ref expires_in) => Some(capped_expiration(Utc::now(), expires_in, max_expiration)?),
+        None => None,
+    };
+
+    // Query project by name
+    let project = projects::find_by_name(&state.db_pool, &payload.project)
+        .await
+        .internal_err("Failed to query project")?
+        .ok_or_else(|| {
+            ServerError::not_found(format!("Project '{}' not found", payload.project))
+        })?;
+
+    // Prevent deployments on projects in deletion lifecycle
+    ensure_project_accepts_deployments(&project)?;
+
+    // Resolve deployment target (group + environment) from request parameters
+    let (resolved_group, resolved_environment) = resolve_deployment_target(
+        &state.db_pool,
+        project.id,
+        payload.environment.as_deref(),
+        payload.group.as_deref(),
+    )
+    .await?;
+
+    let user = authorize_deployment(&state, auth, &project, resolved_environment.as_ref()).await?;
+
+    // Without an explicit expiration, fall back to the project's group TTL policies
+    let expires_at = match expires_at {
+        Some(expires_at) => Some(expires_at),
+        None => group_ttl_expiration(
+            Utc::now(),
+            &crate::server::project::handlers::group_ttl_policies(project.group_ttls.clone()),
+            &resolved_group,
+        )?
+        .map(|expires_at| match max_expiration {
+            Some(max) => expires_at.min(Utc::now() + max),
+            None => expires_at,
+        }),
+    };
+
+    // Generate deployment ID
+    let deployment_id = generate_deployment_id();
+    debug!("Generated deployment ID: {}", deployment_id);
+
+    let spec = resolve_deployment_spec(&state, &payload, &project, &resolved_environment).await?;
+    info!(
+        "Using http_port {} for deployment {}",
+        spec.http_port, deployment_id
+    );
+
+    // Handle deployment creation from an existing deployment (redeploy/rollback)
+    if let Some(ref source_deployment) = spec.source {
+        let from_deployment_id = &source_deployment.deployment_id;
+        info!(
+            "Creating deployment from existing deployment '{}'",
+            from_deployment_id
+        );
+
+        // Create new deployment with Pushed status and invoke extension hooks
+        // Copy image and image_digest from source - the helper function will determine the tag
+        // For pre-built images: image_digest is copied, helper returns it
+        // For build-from-source: rolled_back_from_deployment_id is used to find the original source deployment's image
+        let new_deployment = create_deployment_with_hooks(
+            &state,
+            db_deployments::CreateDeploymentParams {
+                deployment_id: &deployment_id,
+                project_id: project.id,
+                created_by_id: user.id,
+                status: DbDeploymentStatus::Pushed, // Start in Pushed state so controller picks it up
+                image: source_deployment.image.as_deref(), // Copy image from source if present
+                image_digest: source_deployment.image_digest.as_deref(), // Copy digest from source if present
+                rolled_back_from_deployment_id: spec.original_source_id(), // Track original source for image tag calculation
+                deployment_group: &resolved_group, // Use requested group (may be different from source)
+                environment_id: resolved_environment.as_ref().map(|e| e.id),
+                expires_at,                       // expires_at
+                http_port: spec.http_port as i32, // Use determined http_port
+                is_active: false,                 // Deployments start as inactive
+                job_url: job_url.as_deref(),
+                pull_request_url: pull_request_url.as_deref(),
+                message: message.as_deref(),
+                replicas: spec.replicas as i32,
+                cpu: &spec.cpu,
+                memory: &spec.memory,
+                cpu_limit: spec.cpu_limit.as_deref(),
+                memory_limit: spec.memory_limit.as_deref(),
+                health_check: spec.health_check.as_ref(),
+                init_container: spec.init_container.as_ref(),
+                protocol: &spec.protocol,
+                labels: spec.labels.as_ref(),
+                annotations: spec.annotations.as_ref(),
+                command: spec.command.as_deref(),
+                args: spec.args.as_deref(),
+                ingress_annotations: spec.ingress_annotations.as_ref(),
+                strategy: &spec.strategy,
+                drain_period_seconds: spec.drain_period_seconds,
+                volume: spec.volume.as_ref(),
+                scheduling: spec.scheduling.as_ref(),
+                image_pull_policy: spec.image_pull_policy.as_deref(),
+                rate_limit: spec.rate_limit.as_ref(),
+                extra_ports: spec.extra_ports.as_ref(),
+                service_account_name: spec.service_account_name.as_deref(),
+            },
+            &project,
+        )
+        .await?;
+
+        // Handle environment variables based on use_source_env_vars flag
+        if payload.use_source_env_vars {
+            // Copy environment variables from source deployment
+            info!(
+                "Copying environment variables from source deployment '{}'",
+                from_deployment_id
+            );
+            crate::db::env_vars::copy_deployment_env_vars_to_deployment(
+                &state.db_pool,
+                source_deployment.id,
+                new_deployment.id,
+            )
+            .await
+            .internal_err("Failed to copy environment variables")?;
+        } else {
+            // Copy current project environment variables to deployment
+            info!("Using current project environment variables");
+            crate::db::env_vars::copy_project_env_vars_to_deployment(
+                &state.db_pool,
+                project.id,
+                new_deployment.id,
+                new_deployment.environment_id,
+                resolved_environment.as_ref().map(|e| e.name.as_str()),
+            )
+            .await
+            .internal_err("Failed to copy environment variables")?;
+        }
+
+        // Apply env overrides from the request
+        apply_env_overrides(
+            &state,
+            new_deployment.id,
+            &payload.env_overrides,
+            resolved_environment.as_ref().map(|e| e.name.as_str()),
+        )
+        .await?;
+
+        // Upsert PORT env var with the final http_port value
+        crate::db::env_vars::upsert_deployment_env_var(
+            &state.db_pool,
+            new_deployment.id,
+            "PORT",
+            &spec.http_port.to_string(),
+            false, // not a secret
+            false, // is_protected
+            "system",
+        )
+        .await
+        .internal_err("Failed to insert PORT env var")?;
+
+        // Insert Rise-provided environment variables
+        insert_rise_env_vars(&state, &new_deployment, &project).await?;
+        reject_circular_env_references(&state, &new_deployment, &project).await?;
+
+        // Use helper to determine image tag (for logging/response only)
+        let image_tag = crate::server::deployment::utils::get_deployment_image_tag(
+            &state,
+            &new_deployment,
+            &project,
+        )
+        .await;
+
+        info!(
+            "Created deployment {} from {} (image: {}, env vars: {})",
+            deployment_id,
+            from_deployment_id,
+            image_tag,
+            if payload.use_source_env_vars {
+                "from source"
+            } else {
+                "from project"
+            }
+        );
+
+        // Trigger Metacontroller resync so the webhook picks up the new Pushed deployment
+        if let Some(ref kube_client) = state.kube_client {
+            if let Err(e) =
+                crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
+            {
+                tracing::warn!(
+                    project = %project.name,
+                    "Failed to trigger CRD resync after rollback deployment: {:?}", e
+                );
+            }
+        }
+
+        // Return response with image tag and empty credentials (no push needed)
+        return Ok(Json(CreateDeploymentResponse {
+            deployment_id,
+            image_tag,
+            credentials: crate::server::registry::models::RegistryCredentials {
+                registry_url: String::new(),
+                username: String::new(),
+                password: String::new(),
+                expires_in: None,
+                auth_method: Default::default(),
+            },
+        }));
+    }
+
+    // Branch based on whether user provided a pre-built image
+    if let Some(ref user_image) = payload.image {
+        if payload.push_image {
+            // Path 1a: Push-image deployment — CLI will pull and push to Rise registry
+            // Skip digest resolution: the image will be pushed to the internal registry by tag,
+            // and the controller uses get_image_tag() (the no-digest path) to pull from there.
+            info!("Creating push-image deployment with image: {}", user_image);
+
+            let credentials = state
+                .registry_provider
+                .get_credentials(&payload.project)
+                .await
+                .internal_err("Failed to get credentials")?;
+            let image_tag = state.registry_provider.get_image_tag(
+                &payload.project,
+                &deployment_id,
+                ImageTagType::ClientFacing,
+            );
+
+            let deployment = create_deployment_with_hooks(
+                &state,
+                db_deployments::CreateDeploymentParams {
+                    deployment_id: &deployment_id,
+                    project_id: project.id,
+                    created_by_id: user.id,
+                    status: DbDeploymentStatus::Pending,
+                    image: Some(user_image), // Store original user input for display
+                    image_digest: None, // No digest — controller will use internal registry tag
+                    rolled_back_from_deployment_id: None,
+                    deployment_group: &resolved_group,
+                    environment_id: resolved_environment.as_ref().map(|e| e.id),
+                    expires_at,
+                    http_port: spec.http_port as i32,
+                    is_active: false,
+                    job_url: job_url.as_deref(),
+                    pull_request_url: pull_request_url.as_deref(),
+                    message: message.as_deref(),
+                    replicas: spec.replicas as i32,
+                    cpu: &spec.cpu,
+                    memory: &spec.memory,
+                    cpu_limit: spec.cpu_limit.as_deref(),
+                    memory_limit: spec.memory_limit.as_deref(),
+                    health_check: spec.health_check.as_ref(),
+                    init_container: spec.init_container.as_ref(),
+                    protocol: &spec.protocol,
+                    labels: spec.labels.as_ref(),
+                    annotations: spec.annotations.as_ref(),
+                    command: spec.command.as_deref(),
+                    args: spec.args.as_deref(),
+                    ingress_annotations: spec.ingress_annotations.as_ref(),
+                    strategy: &spec.strategy,
+                    drain_period_seconds: spec.drain_period_seconds,
+                    volume: spec.volume.as_ref(),
+                    scheduling: spec.scheduling.as_ref(),
+                    image_pull_policy: spec.image_pull_policy.as_deref(),
+                    rate_limit: spec.rate_limit.as_ref(),
+                    extra_ports: spec.extra_ports.as_ref(),
+                    service_account_name: spec.service_account_name.as_deref(),
+                },
+                &project,
+            )
+            .await?;
+
+            info!(
+                "Created push-image deployment {} for project {}",
+                deployment_id, payload.project
+            );
+
+            // Copy project environment variables to deployment
+            crate::db::env_vars::copy_project_env_vars_to_deployment(
+                &state.db_pool,
+                project.id,
+                deployment.id,
+                deployment.environment_id,
+                resolved_environment.as_ref().map(|e| e.name.as_str()),
+            )
+            .await
+            .internal_err("Failed to copy environment variables")?;
+
+            // Apply env overrides from the request
+            apply_env_overrides(
+                &state,
+                deployment.id,
+                &payload.env_overrides,
+                resolved_environment.as_ref().map(|e| e.name.as_str()),
+            )
+            .await?;
+
+            crate::db::env_vars::upsert_deployment_env_var(
+                &state.db_pool,
+                deployment.id,
+                "PORT",
+                &spec.http_port.to_string(),
+                false,
+                false,
+                "system",
+            )
+            .await
+            .internal_err("Failed to insert PORT env var")?;
+
+            insert_rise_env_vars(&state, &deployment, &project).await?;
+            reject_circular_env_references(&state, &deployment, &project).await?;
+
+            return Ok(Json(CreateDeploymentResponse {
+                deployment_id,
+                image_tag,
+                credentials,
+            }));
+        }
+
+        // Path 1b: Direct pre-built image deployment (no push)
+        info!("Creating deployment with pre-built image: {}", user_image);
+
+        // Normalize image reference (add registry and namespace if missing)
+        let normalized_image = normalize_image_reference(user_image);
+        info!(
+            "Normalized image reference: {} -> {}",
+            user_image, normalized_image
+        );
+
+        // Redeploying the tag the group already runs is how users pick up a moved tag,
+        // so don't hand back the digest cached from the previous deployment
+        let last_deployment = db_deployments::find_last_for_project_and_group(
+            &state.db_pool,
+            project.id,
+            &resolved_group,
+        )
+        .await
+        .internal_err("Failed to query last deployment of group")?;
+        if last_deployment.and_then(|d| d.image).as_deref() == Some(user_image.as_str()) {
+            debug!(
+                "Redeploying '{}' to group '{}', refreshing its digest",
+                normalized_image, resolved_group
+            );
+            state.oci_client.invalidate_digest(&normalized_image);
+        }
+
+        // Resolve image to digest
+        info!("Resolving image '{}' to digest...", normalized_image);
+        let image_digest = resolve_image_digest(
+            &state.oci_client,
+            &state.registry_provider,
+            &normalized_image,
+        )
+        .await
+        .map_err(|e| {
+            ServerError::bad_request(format!("Failed to resolve image '{}': {}", user_image, e))
+        })?;
+
+        info!("Successfully resolved image to digest: {}", image_digest);
+
+        // Create deployment record with image fields set and invoke extension hooks
+        let deployment = create_deployment_with_hooks(
+            &state,
+            db_deployments::CreateDeploymentParams {
+                deployment_id: &deployment_id,
+                project_id: project.id,
+                created_by_id: user.id,
+                status: DbDeploymentStatus::Pushed,
+                image: Some(user_image),
+                image_digest: Some(&image_digest),
+                rolled_back_from_deployment_id: None,
+                deployment_group: &resolved_group,
+                environment_id: resolved_environment.as_ref().map(|e| e.id),
+                expires_at,
+                http_port: spec.http_port as i32,
+                is_active: false,
+                job_url: job_url.as_deref(),
+                pull_request_url: pull_request_url.as_deref(),
+                message: message.as_deref(),
+                replicas: spec.replicas as i32,
+                cpu: &spec.cpu,
+                memory: &spec.memory,
+                cpu_limit: spec.cpu_limit.as_deref(),
+                memory_limit: spec.memory_limit.as_deref(),
+                health_check: spec.health_check.as_ref(),
+                init_container: spec.init_container.as_ref(),
+                protocol: &spec.protocol,
+                labels: spec.labels.as_ref(),
+                annotations: spec.annotations.as_ref(),
+                command: spec.command.as_deref(),
+                args: spec.args.as_deref(),
+                ingress_annotations: spec.ingress_annotations.as_ref(),
+                strategy: &spec.strategy,
+                drain_period_seconds: spec.drain_period_seconds,
+                volume: spec.volume.as_ref(),
+                scheduling: spec.scheduling.as_ref(),
+                image_pull_policy: spec.image_pull_policy.as_deref(),
+                rate_limit: spec.rate_limit.as_ref(),
+                extra_ports: spec.extra_ports.as_ref(),
+                service_account_name: spec.service_account_name.as_deref(),
+            },
+            &project,
+        )
+        .await?;
+
+        info!(
+            "Created pre-built image deployment {} for project {}",
+            deployment_id, payload.project
+        );
+
+        // Copy project environment variables to deployment
+        crate::db::env_vars::copy_project_env_vars_to_deployment(
+            &state.db_pool,
+            project.id,
+            deployment.id,
+            deployment.environment_id,
+            resolved_environment.as_ref().map(|e| e.name.as_str()),
+        )
+        .await
+        .internal_err("Failed to copy environment variables")?;
+
+        // Apply env overrides from the request
+        apply_env_overrides(
+            &state,
+            deployment.id,
+            &payload.env_overrides,
+            resolved_environment.as_ref().map(|e| e.name.as_str()),
+        )
+        .await?;
+
+        // Upsert PORT env var with the resolved effective value
+        // This overwrites any user-set PORT with the resolved value (which may be the same)
+        crate::db::env_vars::upsert_deployment_env_var(
+            &state.db_pool,
+            deployment.id,
+            "PORT",
+            &spec.http_port.to_string(),
+            false, // not a secret
+            false, // is_protected
+            "system",
+        )
+        .await
+        .internal_err("Failed to insert PORT env var")?;
+
+        // Insert Rise-provided environment variables
+        insert_rise_env_vars(&state, &deployment, &project).await?;
+        reject_circular_env_references(&state, &deployment, &project).await?;
+
+        // Trigger Metacontroller resync so the webhook picks up the new Pushed deployment
+        if let Some(ref kube_client) = state.kube_client {
+            if let Err(e) =
+                crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
+            {
+                tracing::warn!(
+                    project = %project.name,
+                    "Failed to trigger CRD resync after pre-built image deployment: {:?}", e
+                );
+            }
+        }
+
+        // Return response with digest as image_tag and empty credentials
+        Ok(Json(CreateDeploymentResponse {
+            deployment_id,
+            image_tag: image_digest,
+            credentials: crate::server::registry::models::RegistryCredentials {
+                registry_url: String::new(),
+                username: String::new(),
+                password: String::new(),
+                expires_in: None,
+                auth_method: Default::default(),
+            },
+        }))
+    } else {
+        // Path 2: Build from source (current behavior)
+        // Get registry credentials
+        let credentials = state
+            .registry_provider
+            .get_credentials(&payload.project)
+            .await
+            .internal_err("Failed to get credentials")?;
+
+        // Get full image tag from provider for CLI client (uses client_registry_url if configured)
+        let image_tag = state.registry_provider.get_image_tag(
+            &payload.project,
+            &deployment_id,
+            ImageTagType::ClientFacing,
+        );
+
+        debug!("Image tag: {}", image_tag);
+
+        // Create deployment record in database and invoke extension hooks
+        let deployment = create_deployment_with_hooks(
+            &state,
+            db_deployments::CreateDeploymentParams {
+                deployment_id: &deployment_id,
+                project_id: project.id,
+                created_by_id: user.id,
+                status: DbDeploymentStatus::Pending,
+                image: None,        // image - NULL for build-from-source
+                image_digest: None, // image_digest - NULL for build-from-source
+                rolled_back_from_deployment_id: None, // Not a rollback
+                deployment_group: &resolved_group, // deployment_group
+                environment_id: resolved_environment.as_ref().map(|e| e.id),
+                expires_at,                       // expires_at
+                http_port: spec.http_port as i32, // http_port
+                is_active: false,                 // Deployments start as inactive
+                job_url: job_url.as_deref(),
+                pull_request_url: pull_request_url.as_deref(),
+                message: message.as_deref(),
+                replicas: spec.replicas as i32,
+                cpu: &spec.cpu,
+                memory: &spec.memory,
+                cpu_limit: spec.cpu_limit.as_deref(),
+                memory_limit: spec.memory_limit.as_deref(),
+                health_check: spec.health_check.as_ref(),
+                init_container: spec.init_container.as_ref(),
+                protocol: &spec.protocol,
+                labels: spec.labels.as_ref(),
+                annotations: spec.annotations.as_ref(),
+                command: spec.command.as_deref(),
+                args: spec.args.as_deref(),
+                ingress_annotations: spec.ingress_annotations.as_ref(),
+                strategy: &spec.strategy,
+                drain_period_seconds: spec.drain_period_seconds,
+                volume: spec.volume.as_ref(),
+                scheduling: spec.scheduling.as_ref(),
+                image_pull_policy: spec.image_pull_policy.as_deref(),
+                rate_limit: spec.rate_limit.as_ref(),
+                extra_ports: spec.extra_ports.as_ref(),
+                service_account_name: spec.service_account_name.as_deref(),
+            },
+            &project,
+        )
+        .await?;
+
+        info!(
+            "Created build-from-source deployment {} for project {}",
+            deployment_id, payload.project
+        );
+
+        // Copy project environment variables to deployment
+        crate::db::env_vars::copy_project_env_vars_to_deployment(
+            &state.db_pool,
+            project.id,
+            deployment.id,
+            deployment.environment_id,
+            resolved_environment.as_ref().map(|e| e.name.as_str()),
+        )
+        .await
+        .internal_err("Failed to copy environment variables")?;
+
+        // Apply env overrides from the request
+        apply_env_overrides(
+            &state,
+            deployment.id,
+            &payload.env_overrides,
+            resolved_environment.as_ref().map(|e| e.name.as_str()),
+        )
+        .await?;
+
+        // Upsert PORT env var with the resolved effective value
+        // This overwrites any user-set PORT with the resolved value (which may be the same)
+        crate::db::env_vars::upsert_deployment_env_var(
+            &state.db_pool,
+            deployment.id,
+            "PORT",
+            &spec.http_port.to_string(),
+            false, // not a secret
+            false, // is_protected
+            "system",
+        )
+        .await
+        .internal_err("Failed to insert PORT env var")?;
+
+        // Insert Rise-provided environment variables
+        insert_rise_env_vars(&state, &deployment, &project).await?;
+        reject_circular_env_references(&state, &deployment, &project).await?;
+
+        // Return response
+        Ok(Json(CreateDeploymentResponse {
+            deployment_id,
+            image_tag,
+            credentials,
+        }))
+    }
+}
+
+/// POST /deployments/dry-run - Render the Kubernetes manifests for a deployment request
+///
+/// Resolves the request like `create_deployment` (target, port, resources, probes,
+/// env vars, redeploy inheritance) into an in-memory deployment and renders the
+/// resources the controller would apply for it. Nothing is persisted, no image is
+/// resolved, and secret env var values are redacted.
+#[cfg(feature = "backend")]
+pub async fn dry_run_deployment(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(payload): Json<CreateDeploymentRequest>,
+) -> Result<Json<DryRunDeploymentResponse>, ServerError> {
+    use std::collections::BTreeMap;
+
+    let resource_builder = state.resource_builder.clone().ok_or_else(|| {
+        ServerError::bad_request(
+            "Dry-run is only supported with the Kubernetes deployment controller",
+        )
+    })?;
+
+    validate_requested_group(payload.group.as_deref())?;
+    validate_env_overrides(&payload.env_overrides)?;
+
+    let project = projects::find_by_name(&state.db_pool, &payload.project)
+        .await
+        .internal_err("Failed to query project")?
+        .ok_or_else(|| {
+            ServerError::not_found(format!("Project '{}' not found", payload.project))
+        })?;
+    ensure_project_accepts_deployments(&project)?;
+
+    let (resolved_group, resolved_environment) = resolve_deployment_target(
+        &state.db_pool,
+        project.id,
         payload.environment.as_deref(),
         payload.group.as_deref(),
     )
-    .await?;
+    .await?;
+    let environment_name = resolved_environment.as_ref().map(|e| e.name.as_str());
+
+    let user = authorize_deployment(&state, auth, &project, resolved_environment.as_ref()).await?;
+    let spec = resolve_deployment_spec(&state, &payload, &project, &resolved_environment).await?;
+
+    // Pre-built images are rendered as given; digests are only resolved on deploy
+    let (image, image_digest) = match spec.source {
+        Some(ref source) => (source.image.clone(), source.image_digest.clone()),
+        None => {
+            let image = payload.image.clone().filter(|_| !payload.push_image);
+            (image.clone(), image)
+        }
+    };
+    let rolled_back_from_deployment_id = spec.original_source_id();
+    let source_image_deployment_id = match rolled_back_from_deployment_id {
+        Some(id) => db_deployments::find_by_id(&state.db_pool, id)
+            .await
+            .internal_err("Failed to find original source deployment")?
+            .map(|d| d.deployment_id),
+        None => None,
+    };
+
+    // Values of the deployment's env vars: (value, is_secret)
+    let mut env_vars: BTreeMap<String, (String, bool)> = BTreeMap::new();
+
+    match spec.source {
+        Some(ref source) if payload.use_source_env_vars => {
+            for var in crate::db::env_vars::list_deployment_env_vars(&state.db_pool, source.id)
+                .await
+                .internal_err("Failed to list source environment variables")?
+            {
+                env_vars.insert(var.key, (var.value, var.is_secret));
+            }
+        }
+        _ => {
+            // Environment-scoped vars are listed before global ones with the same key
+            for var in crate::db::env_vars::list_project_env_vars(
+                &state.db_pool,
+                project.id,
+                resolved_environment.as_ref().map(|e| e.id),
+            )
+            .await
+            .internal_err("Failed to list environment variables")?
+            {
+                env_vars
+                    .entry(var.key)
+                    .or_insert((var.value, var.is_secret));
+            }
+        }
+    }
+    for env_override in
+        filter_env_overrides_by_environment(&payload.env_overrides, environment_name)
+    {
+        env_vars.insert(
+            env_override.key.clone(),
+            (env_override.value.clone(), env_override.is_secret),
+        );
+    }
+    env_vars.insert("PORT".to_string(), (spec.http_port.to_string(), false));
+
+    let now = Utc::now();
+    let deployment = crate::db::models::Deployment {
+        id: uuid::Uuid::nil(),
+        deployment_id: generate_deployment_id(),
+        project_id: project.id,
+        created_by_id: user.id,
+        status: DbDeploymentStatus::Pushed,
+        deployment_group: resolved_group,
+        environment_id: resolved_environment.as_ref().map(|e| e.id),
+        expires_at: None,
+        termination_reason: None,
+        completed_at: None,
+        error_message: None,
+        build_logs: None,
+        controller_metadata: serde_json::json!({}),
+        image,
+        image_digest,
+        rolled_back_from_deployment_id,
+        http_port: spec.http_port as i32,
+        needs_reconcile: false,
+        is_active: true,
+        deploying_started_at: None,
+        first_healthy_at: None,
+        job_url: None,
+        pull_request_url: None,
+        message: None,
+        replicas: spec.replicas as i32,
+        cpu: spec.cpu,
+        memory: spec.memory,
+        cpu_limit: spec.cpu_limit,
+        memory_limit: spec.memory_limit,
+        health_check: spec.health_check,
+        init_container: spec.init_container,
+        protocol: spec.protocol,
+        labels: spec.labels,
+        annotations: spec.annotations,
+        command: spec.command,
+        args: spec.args,
+        ingress_annotations: spec.ingress_annotations,
+        strategy: spec.strategy,
+        drain_period_seconds: spec.drain_period_seconds,
+        volume: spec.volume,
+        scheduling: spec.scheduling,
+        image_pull_policy: spec.image_pull_policy,
+        rate_limit: spec.rate_limit,
+        extra_ports: spec.extra_ports,
+        service_account_name: spec.service_account_name,
+        restarted_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let deployment_urls = state
+        .deployment_backend
+        .get_deployment_urls(&deployment, &project)
+        .await
+        .internal_err("Failed to get deployment URLs")?;
+    for (key, value) in models::rise_system_env_vars(
+        &state.public_url,
+        &deployment.deployment_group,
+        &deployment_urls,
+        environment_name,
+    ) {
+        env_vars.insert(key, (value, false));
+    }
+
+    let mut plain_env_vars = Vec::new();
+    let mut secret_env_keys = Vec::new();
+    for (key, (value, is_secret)) in env_vars {
+        if is_secret {
+            secret_env_keys.push(key);
+        } else {
+            plain_env_vars.push(k8s_openapi::api::core::v1::EnvVar {
+                name: key,
+                value: Some(value),
+                ..Default::default()
+            });
+        }
+    }
+
+    let custom_domains =
+        crate::db::custom_domains::list_project_custom_domains(&state.db_pool, project.id)
+            .await
+            .internal_err("Failed to list custom domains")?;
+
+    let manifests = crate::server::deployment::webhook::render_deployment_manifests(
+        &resource_builder,
+        &project,
+        &deployment,
+        resolved_environment.as_ref(),
+        source_image_deployment_id.as_deref(),
+        plain_env_vars,
+        &secret_env_keys,
+        &custom_domains,
+    )
+    .internal_err("Failed to render manifests")?;
+
+    let mut yaml = String::new();
+    for manifest in &manifests {
+        yaml.push_str("---\n");
+        yaml.push_str(
+            &serde_yaml::to_string(manifest).internal_err("Failed to serialize manifest")?,
+        );
+    }
+
+    info!(
+        "Rendered {} manifest(s) for dry-run deployment of project '{}'",
+        manifests.len(),
+        project.name
+    );
+
+    Ok(Json(DryRunDeploymentResponse {
+        deployment_id: deployment.deployment_id,
+        manifests: yaml,
+    }))
+}
+
+/// Shared logic for updating a deployment's status.
+///
+/// Given a resolved deployment and project, validates permissions and applies the status update.
+/// When `is_sa` is true, permission checks are skipped (already validated by `resolve_for_project`).
+async fn perform_status_update(
+    state: &AppState,
+    user: &crate::db::models::User,
+    is_sa: bool,
+    deployment: crate::db::models::Deployment,
+    project: &crate::db::models::Project,
+    deployment_id: &str,
+    payload: UpdateDeploymentStatusRequest,
+) -> Result<Json<Deployment>, ServerError> {
+    // Check if user has permission (owns the project)
+    // SA access was already validated by resolve_for_project
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(state, user, project)
+            .await
+            .map_err(|_| {
+                ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
+            })?;
+    }
+
+    // Update status in database
+    let status_copy = payload.status.clone();
+    let updated_deployment = match payload.status {
+        DeploymentStatus::Failed => {
+            let error_msg = payload.error_message.as_deref().unwrap_or("Unknown error");
+            let deployment = db_deployments::mark_failed(&state.db_pool, deployment.id, error_msg)
+                .await
+                .internal_err("Failed to update deployment")?;
+
+            // Update project status to Failed
+            projects::update_calculated_status(&state.db_pool, project.id)
+                .await
+                .internal_err("Failed to update project status")?;
+
+            deployment
+        }
+        _ => {
+            // update_status will validate the state transition
+            let db_status = convert_status_to_db(payload.status);
+            let deployment =
+                db_deployments::update_status(&state.db_pool, deployment.id, db_status)
+                    .await
+                    .map_err(|e| {
+                        // State transition validation errors are returned as anyhow errors
+                        // Return BAD_REQUEST for validation errors, INTERNAL_SERVER_ERROR otherwise
+                        let error_msg = e.to_string();
+                        if error_msg.contains("Invalid deployment state transition") {
+                            ServerError::bad_request(error_msg)
+                        } else {
+                            ServerError::internal_anyhow(e, "Failed to update deployment")
+                        }
+                    })?;
+
+            // Update project status (e.g., to Deploying)
+            projects::update_calculated_status(&state.db_pool, project.id)
+                .await
+                .internal_err("Failed to update project status")?;
+
+            deployment
+        }
+    };
+
+    info!(
+        "Updated deployment {} to status {:?}",
+        deployment_id, status_copy
+    );
+
+    // Trigger Metacontroller resync so the webhook picks up the status change
+    if let Some(ref kube_client) = state.kube_client {
+        if let Err(e) =
+            crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
+        {
+            tracing::warn!(
+                project = %project.name,
+                "Failed to trigger CRD resync: {:?}", e
+            );
+        }
+    }
+
+    // Only calculate URLs for non-terminal deployments that could receive traffic
+    let (primary_url, custom_domain_urls) =
+        if state_machine::is_terminal(&updated_deployment.status) {
+            (None, vec![])
+        } else {
+            match state
+                .deployment_backend
+                .get_deployment_urls(&updated_deployment, project)
+                .await
+            {
+                Ok(urls) => (Some(urls.primary_url), urls.custom_domain_urls),
+                Err(e) => {
+                    error!(
+                        "Failed to calculate URLs for deployment {}: {}",
+                        deployment_id, e
+                    );
+                    (None, vec![])
+                }
+            }
+        };
+
+    let created_by_email =
+        get_creator_email(&state.db_pool, updated_deployment.created_by_id).await;
+    Ok(Json(
+        convert_deployment(
+            state,
+            updated_deployment,
+            project,
+            created_by_email,
+            primary_url,
+            custom_domain_urls,
+        )
+        .await,
+    ))
+}
+
+/// PATCH /projects/{project_name}/deployments/{deployment_id}/status - Update deployment status (project-scoped)
+pub async fn update_deployment_status_by_project(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((project_name, deployment_id)): Path<(String, String)>,
+    Json(payload): Json<UpdateDeploymentStatusRequest>,
+) -> Result<Json<Deployment>, ServerError> {
+    info!(
+        "Updating deployment {} status to {:?} for project {}",
+        deployment_id, payload.status, project_name
+    );
+
+    // Find project by name
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+
+    // Resolve auth for project scope
+    let (user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
+
+    // Find deployment by deployment_id + project_id
+    let deployment =
+        db_deployments::find_by_deployment_id(&state.db_pool, &deployment_id, project.id)
+            .await
+            .internal_err("Failed to find deployment")?
+            .ok_or_else(|| {
+                ServerError::not_found(format!(
+                    "Deployment '{}' not found for project '{}'",
+                    deployment_id, project_name
+                ))
+            })?;
+
+    perform_status_update(
+        &state,
+        &user,
+        is_sa,
+        deployment,
+        &project,
+        &deployment_id,
+        payload,
+    )
+    .await
+}
+
+/// PATCH /deployments/{deployment_id}/status - Update deployment status (deprecated, unscoped)
+///
+/// This endpoint is deprecated. Use `PATCH /projects/{project_name}/deployments/{deployment_id}/status` instead.
+/// Kept for backward compatibility with older CLI versions.
+///
+/// Returns 409 Conflict when multiple projects have deployments with the same deployment_id,
+/// since we cannot determine which one the caller intended.
+pub async fn update_deployment_status(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(deployment_id): Path<String>,
+    Json(payload): Json<UpdateDeploymentStatusRequest>,
+) -> Result<Json<Deployment>, ServerError> {
+    warn!(
+        "Deprecated endpoint called: PATCH /deployments/{}/status — use PATCH /projects/{{project_name}}/deployments/{}/status instead",
+        deployment_id, deployment_id
+    );
+
+    // Single query to find matching deployments across all projects (LIMIT 2 to detect collisions)
+    let matching_deployments =
+        db_deployments::find_by_deployment_id_unscoped(&state.db_pool, &deployment_id, 2)
+            .await
+            .internal_err(format!("Failed to query deployment '{}'", deployment_id))?;
+
+    if matching_deployments.len() > 1 {
+        let project_ids: Vec<String> = matching_deployments
+            .iter()
+            .map(|d| d.project_id.to_string())
+            .collect();
+        warn!(
+            "Deployment ID collision on deprecated endpoint: deployment_id={} matches {} projects: {:?}. \
+             Refusing to proceed. Clients should migrate to the project-scoped endpoint.",
+            deployment_id,
+            matching_deployments.len(),
+            project_ids,
+        );
+        return Err(ServerError::conflict(format!(
+            "Ambiguous deployment_id '{}': matches multiple projects. \
+             Use PATCH /api/v1/projects/{{project_name}}/deployments/{}/status instead.",
+            deployment_id, deployment_id
+        )));
+    }
+
+    let deployment = matching_deployments.into_iter().next().ok_or_else(|| {
+        ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
+    })?;
+
+    let project = projects::find_by_id(&state.db_pool, deployment.project_id)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| {
+            ServerError::not_found(format!(
+                "Project for deployment '{}' not found",
+                deployment_id
+            ))
+        })?;
+
+    // Resolve auth for project scope
+    let (user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
+
+    perform_status_update(
+        &state,
+        &user,
+        is_sa,
+        deployment,
+        &project,
+        &deployment_id,
+        payload,
+    )
+    .await
+}
 
-    // Resolve auth for project scope (validates SA claims if external token)
-    // Only mask auth failures (401/403) as 404 to prevent project existence leakage;
-    // preserve 409 (SA collision) and 5xx (misconfiguration) for diagnosability.
+/// Query parameters for listing deployments
+#[derive(Debug, serde::Deserialize)]
+pub struct ListDeploymentsQuery {
+    #[serde(rename = "group")]
+    pub deployment_group: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// List deployments for a project
+pub async fn list_deployments(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(project_name): Path<String>,
+    Query(query): Query<ListDeploymentsQuery>,
+) -> Result<Json<Vec<Deployment>>, ServerError> {
+    debug!(
+        "Listing deployments for project: {} (group: {:?})",
+        project_name, query.deployment_group
+    );
+
+    // Find the project by name
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+
+    // Resolve auth for project scope
     let (user, is_sa) = auth
         .resolve_for_project(&state.db_pool, &project)
         .await
@@ -811,678 +2873,888 @@ pub async fn create_deployment(
             }
         })?;
 
-    // Check deployment permissions (SA access already validated above)
+    // Check if user has permission to view deployments (SA access already validated)
     if !is_sa {
         crate::server::project::handlers::ensure_project_access_or_admin(&state, &user, &project)
             .await
-            .map_err(|_| {
-                ServerError::not_found(format!("Project '{}' not found", payload.project))
-            })?;
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
     }
 
-    // Enforce service account environment restrictions
-    if is_sa {
-        let sa = service_accounts::find_active_by_user_id(&state.db_pool, user.id)
-            .await
-            .internal_err("Failed to look up service account")?;
-        if let Some(ref allowed_env_ids) = sa.and_then(|sa| sa.allowed_environment_ids) {
-            let target_env_id = resolved_environment.as_ref().map(|e| e.id);
-            match target_env_id {
-                Some(env_id) if !allowed_env_ids.contains(&env_id) => {
-                    return Err(ServerError::forbidden(
-                        "This service account is not allowed to deploy to the requested environment",
-                    ));
-                }
-                None => {
-                    // SA has environment restrictions but no environment was specified;
-                    // block the deployment since we can't verify the target is allowed.
-                    return Err(ServerError::forbidden(
-                        "This service account requires an explicit environment target",
-                    ));
+    // Get deployments from database (optionally filtered by group, with pagination)
+    let db_deployments = db_deployments::list_for_project_and_group(
+        &state.db_pool,
+        project.id,
+        query.deployment_group.as_deref(),
+        query.limit,
+        query.offset,
+    )
+    .await
+    .internal_err("Failed to list deployments")?;
+
+    // Convert to API models (fetch creator emails and calculate URLs)
+    let mut deployments = Vec::with_capacity(db_deployments.len());
+    for db_deployment in db_deployments {
+        let created_by_email = get_creator_email(&state.db_pool, db_deployment.created_by_id).await;
+
+        // Only calculate URLs for non-terminal deployments that could receive traffic
+        let (primary_url, custom_domain_urls) = if state_machine::is_terminal(&db_deployment.status)
+        {
+            // Terminal deployments (Failed, Stopped, Cancelled, Superseded, Expired) cannot receive traffic
+            (None, vec![])
+        } else {
+            // Calculate deployment URLs dynamically for active deployments
+            match state
+                .deployment_backend
+                .get_deployment_urls(&db_deployment, &project)
+                .await
+            {
+                Ok(urls) => (Some(urls.primary_url), urls.custom_domain_urls),
+                Err(e) => {
+                    error!(
+                        "Failed to calculate URLs for deployment {}: {}",
+                        db_deployment.deployment_id, e
+                    );
+                    (None, vec![])
                 }
-                _ => {} // target environment is in the allowed list
             }
-        }
-    }
+        };
 
-    // Generate deployment ID
-    let deployment_id = generate_deployment_id();
-    debug!("Generated deployment ID: {}", deployment_id);
+        deployments.push(
+            convert_deployment(
+                &state,
+                db_deployment,
+                &project,
+                created_by_email,
+                primary_url,
+                custom_domain_urls,
+            )
+            .await,
+        );
+    }
 
-    // Resolve effective http_port:
-    // 1. Explicit http_port from request (if provided)
-    // 2. Source deployment's http_port (if --from is used, handled below)
-    // 3. PORT env var from project (if set and valid)
-    // 4. Default: 8080
-    let effective_http_port =
-        resolve_effective_http_port(&state, project.id, payload.http_port).await?;
-    info!(
-        "Using http_port {} for deployment {}",
-        effective_http_port, deployment_id
-    );
+    Ok(Json(deployments))
+}
 
-    // Resolve effective deployment resources (replicas, cpu, memory)
-    // Priority: request payload > platform defaults
-    // Validation against constraints happens after rollback inheritance (below)
-    let (mut effective_replicas, mut effective_cpu, mut effective_memory) = {
-        #[cfg(feature = "backend")]
-        {
-            let defaults = state
-                .deployment_defaults
-                .as_ref()
-                .cloned()
-                .unwrap_or_default();
+/// Query parameters for stopping deployments (exactly one of `group` / `all`)
+#[derive(Debug, serde::Deserialize)]
+pub struct StopDeploymentsQuery {
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Stop the deployments of every group
+    #[serde(default)]
+    pub all: bool,
+}
 
-            // Resolve: request > platform defaults
-            let replicas = payload.replicas.unwrap_or(defaults.replicas);
-            let cpu = payload.cpu.clone().unwrap_or_else(|| defaults.cpu.clone());
-            let memory = payload
-                .memory
-                .clone()
-                .unwrap_or_else(|| defaults.memory.clone());
+/// Response for stopping deployments
+#[derive(Debug, serde::Serialize)]
+pub struct StopDeploymentsResponse {
+    pub stopped_count: usize,
+    pub deployment_ids: Vec<String>,
+    /// Groups that had deployments stopped
+    pub groups: Vec<String>,
+}
 
-            (replicas, cpu, memory)
+/// POST /projects/{project_name}/deployments/stop - Stop all deployments in a group,
+/// or in every group with `all=true`
+pub async fn stop_deployments_by_group(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(project_name): Path<String>,
+    Query(query): Query<StopDeploymentsQuery>,
+) -> Result<Json<StopDeploymentsResponse>, ServerError> {
+    let scope = match (&query.group, query.all) {
+        (Some(group), false) => {
+            // Validate group name
+            if !is_valid_group_name(group) {
+                return Err(ServerError::bad_request(format!(
+                    "Invalid group name '{}'. Must be 'default' or match pattern [a-z0-9][a-z0-9/-]*[a-z0-9] (no consecutive hyphens, normalized length max 63 chars)",
+                    group
+                )));
+            }
+            format!("group '{}'", group)
         }
-
-        #[cfg(not(feature = "backend"))]
-        {
-            let replicas = payload.replicas.unwrap_or(1);
-            let cpu = payload.cpu.clone().unwrap_or_else(|| "500m".to_string());
-            let memory = payload
-                .memory
-                .clone()
-                .unwrap_or_else(|| "256Mi".to_string());
-            (replicas, cpu, memory)
+        (None, true) => "all groups".to_string(),
+        _ => {
+            return Err(ServerError::bad_request(
+                "Specify exactly one of 'group' or 'all=true'",
+            ))
         }
     };
 
-    // Handle deployment creation from an existing deployment (redeploy/rollback)
-    if let Some(ref from_deployment_id) = payload.from_deployment {
-        info!(
-            "Creating deployment from existing deployment '{}'",
-            from_deployment_id
-        );
+    info!(
+        "Stopping all deployments in {} for project '{}'",
+        scope, project_name
+    );
 
-        // Find the source deployment
-        let source_deployment = db_deployments::find_by_project_and_deployment_id(
-            &state.db_pool,
-            project.id,
-            from_deployment_id,
-        )
+    // Find the project by name
+    let project = projects::find_by_name(&state.db_pool, &project_name)
         .await
-        .internal_err("Failed to find source deployment")?
-        .ok_or_else(|| {
-            ServerError::not_found(format!(
-                "Source deployment '{}' not found for project '{}'",
-                from_deployment_id, payload.project
-            ))
-        })?;
-
-        // Verify the source deployment already has a reusable image.
-        if !state_machine::can_create_from(&source_deployment) {
-            return Err(ServerError::bad_request(format!(
-                "Cannot create deployment from '{}' because its image is not available yet (status '{}').",
-                from_deployment_id, source_deployment.status
-            )));
-        }
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
 
-        // For chained redeployments, follow the chain to find the original source
-        // This ensures we use the correct image tag from the original deployment that built the image
-        let original_source_id =
-            if let Some(chained_source) = source_deployment.rolled_back_from_deployment_id {
-                // Source is itself a redeploy - use its source instead
-                debug!(
-                    "Source deployment {} is a redeploy, following chain to original source {}",
-                    from_deployment_id, chained_source
-                );
-                chained_source
+    // Resolve auth for project scope
+    let (_user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
             } else {
-                // Source is the original - use it directly
-                source_deployment.id
-            };
-
-        // Determine http_port for the new deployment:
-        // - If explicit http_port was provided in request, use it (already in effective_http_port)
-        // - If no explicit port, inherit from source deployment
-        let final_http_port = if payload.http_port.is_some() {
-            effective_http_port
-        } else {
-            source_deployment.http_port as u16
-        };
-
-        // Inherit resources from source deployment unless explicitly overridden
-        if payload.replicas.is_none() {
-            effective_replicas = source_deployment.replicas as u32;
-        }
-        if payload.cpu.is_none() {
-            effective_cpu = source_deployment.cpu.clone();
-        }
-        if payload.memory.is_none() {
-            effective_memory = source_deployment.memory.clone();
-        }
-
-        // Validate resources against constraints (after rollback inheritance)
-        #[cfg(feature = "backend")]
-        validate_resource_constraints(
-            &state,
-            &resolved_environment,
-            effective_replicas,
-            &effective_cpu,
-            &effective_memory,
-            payload.from_deployment.is_some(),
-        )?;
+                e
+            }
+        })?;
 
-        // Create new deployment with Pushed status and invoke extension hooks
-        // Copy image and image_digest from source - the helper function will determine the tag
-        // For pre-built images: image_digest is copied, helper returns it
-        // For build-from-source: rolled_back_from_deployment_id is used to find the original source deployment's image
-        let new_deployment = create_deployment_with_hooks(
-            &state,
-            db_deployments::CreateDeploymentParams {
-                deployment_id: &deployment_id,
-                project_id: project.id,
-                created_by_id: user.id,
-                status: DbDeploymentStatus::Pushed, // Start in Pushed state so controller picks it up
-                image: source_deployment.image.as_deref(), // Copy image from source if present
-                image_digest: source_deployment.image_digest.as_deref(), // Copy digest from source if present
-                rolled_back_from_deployment_id: Some(original_source_id), // Track original source for image tag calculation
-                deployment_group: &resolved_group, // Use requested group (may be different from source)
-                environment_id: resolved_environment.as_ref().map(|e| e.id),
-                expires_at,                        // expires_at
-                http_port: final_http_port as i32, // Use determined http_port
-                is_active: false,                  // Deployments start as inactive
-                job_url: job_url.as_deref(),
-                pull_request_url: pull_request_url.as_deref(),
-                replicas: effective_replicas as i32,
-                cpu: &effective_cpu,
-                memory: &effective_memory,
-            },
-            &project,
-        )
-        .await?;
+    // Check if user has permission to stop deployments (SA access already validated)
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
+            .await
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+    }
 
-        // Handle environment variables based on use_source_env_vars flag
-        if payload.use_source_env_vars {
-            // Copy environment variables from source deployment
-            info!(
-                "Copying environment variables from source deployment '{}'",
-                from_deployment_id
-            );
-            crate::db::env_vars::copy_deployment_env_vars_to_deployment(
+    // Find all non-terminal deployments in this group (or project)
+    let deployments = match &query.group {
+        Some(group) => {
+            db_deployments::find_non_terminal_for_project_and_group(
                 &state.db_pool,
-                source_deployment.id,
-                new_deployment.id,
+                project.id,
+                group,
             )
             .await
-            .internal_err("Failed to copy environment variables")?;
+        }
+        None => db_deployments::list_non_terminal_for_project(&state.db_pool, project.id).await,
+    }
+    .internal_err("Failed to find deployments")?;
+
+    let mut stopped_ids = Vec::new();
+    let mut stopped_groups = std::collections::BTreeSet::new();
+
+    // Mark each deployment using appropriate state transition
+    for deployment in deployments {
+        let result = if state_machine::is_cancellable(&deployment.status) {
+            db_deployments::mark_cancelling(&state.db_pool, deployment.id, Some(_user.id))
+                .await
+                .map(|_| "Cancelling")
         } else {
-            // Copy current project environment variables to deployment
-            info!("Using current project environment variables");
-            crate::db::env_vars::copy_project_env_vars_to_deployment(
+            db_deployments::mark_terminating(
                 &state.db_pool,
-                project.id,
-                new_deployment.id,
-                new_deployment.environment_id,
-                resolved_environment.as_ref().map(|e| e.name.as_str()),
+                deployment.id,
+                crate::db::models::TerminationReason::UserStopped,
+                Some(_user.id),
             )
             .await
-            .internal_err("Failed to copy environment variables")?;
+            .map(|_| "Terminating")
+        };
+        match result {
+            Ok(new_status) => {
+                info!(
+                    "Marked deployment {} as {}",
+                    deployment.deployment_id, new_status
+                );
+                stopped_ids.push(deployment.deployment_id);
+                stopped_groups.insert(deployment.deployment_group);
+            }
+            Err(e) => {
+                error!(
+                    "Failed to stop deployment {}: {}",
+                    deployment.deployment_id, e
+                );
+            }
         }
+    }
 
-        // Apply env overrides from the request
-        apply_env_overrides(
-            &state,
-            new_deployment.id,
-            &payload.env_overrides,
-            resolved_environment.as_ref().map(|e| e.name.as_str()),
-        )
-        .await?;
-
-        // Upsert PORT env var with the final http_port value
-        crate::db::env_vars::upsert_deployment_env_var(
-            &state.db_pool,
-            new_deployment.id,
-            "PORT",
-            &final_http_port.to_string(),
-            false, // not a secret
-            false, // is_protected
-            "system",
-        )
+    // Update project status
+    projects::update_calculated_status(&state.db_pool, project.id)
         .await
-        .internal_err("Failed to insert PORT env var")?;
-
-        // Insert Rise-provided environment variables
-        insert_rise_env_vars(&state, &new_deployment, &project).await?;
-
-        // Use helper to determine image tag (for logging/response only)
-        let image_tag = crate::server::deployment::utils::get_deployment_image_tag(
-            &state,
-            &new_deployment,
-            &project,
-        )
-        .await;
-
-        info!(
-            "Created deployment {} from {} (image: {}, env vars: {})",
-            deployment_id,
-            from_deployment_id,
-            image_tag,
-            if payload.use_source_env_vars {
-                "from source"
-            } else {
-                "from project"
-            }
-        );
+        .internal_err("Failed to update project status")?;
 
-        // Trigger Metacontroller resync so the webhook picks up the new Pushed deployment
+    // Trigger Metacontroller resync
+    if !stopped_ids.is_empty() {
         if let Some(ref kube_client) = state.kube_client {
             if let Err(e) =
                 crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
             {
                 tracing::warn!(
                     project = %project.name,
-                    "Failed to trigger CRD resync after rollback deployment: {:?}", e
+                    "Failed to trigger CRD resync: {:?}", e
                 );
             }
         }
+    }
 
-        // Return response with image tag and empty credentials (no push needed)
-        return Ok(Json(CreateDeploymentResponse {
-            deployment_id,
-            image_tag,
-            credentials: crate::server::registry::models::RegistryCredentials {
-                registry_url: String::new(),
-                username: String::new(),
-                password: String::new(),
-                expires_in: None,
-                auth_method: Default::default(),
-            },
-        }));
+    info!(
+        "Stopped {} deployments in {} for project '{}'",
+        stopped_ids.len(),
+        scope,
+        project_name
+    );
+
+    Ok(Json(StopDeploymentsResponse {
+        stopped_count: stopped_ids.len(),
+        deployment_ids: stopped_ids,
+        groups: stopped_groups.into_iter().collect(),
+    }))
+}
+
+/// Query parameters for previewing a rollback
+#[derive(Debug, serde::Deserialize)]
+pub struct RollbackPreviewQuery {
+    /// Deployment to roll back to. If omitted, the deployment that preceded the
+    /// group's current deployment is used.
+    pub deployment_id: Option<String>,
+    /// Deployment group; defaults to the target's group, or 'default'
+    pub group: Option<String>,
+}
+
+/// Environment variable keys that change when rolling back
+#[derive(Debug, Default, PartialEq, serde::Serialize)]
+pub struct EnvKeyDiff {
+    /// Keys present in the target but not in the current deployment
+    pub added: Vec<String>,
+    /// Keys present in the current deployment but not in the target
+    pub removed: Vec<String>,
+}
+
+/// Response for previewing a rollback
+#[derive(Debug, serde::Serialize)]
+pub struct RollbackPreviewResponse {
+    pub deployment_group: String,
+    pub target_deployment_id: String,
+    pub target_status: DeploymentStatus,
+    /// Image the rollback deploys (digest-pinned when available)
+    pub image: String,
+    pub current_deployment_id: Option<String>,
+    /// URL serving the group once the rollback is healthy
+    pub url: Option<String>,
+    pub env_diff: EnvKeyDiff,
+}
+
+/// Compute the key-level diff between current and target env vars
+fn env_key_diff(current_keys: &[String], target_keys: &[String]) -> EnvKeyDiff {
+    let current: std::collections::BTreeSet<&String> = current_keys.iter().collect();
+    let target: std::collections::BTreeSet<&String> = target_keys.iter().collect();
+    EnvKeyDiff {
+        added: target.difference(&current).map(|k| k.to_string()).collect(),
+        removed: current.difference(&target).map(|k| k.to_string()).collect(),
     }
+}
 
-    // Validate resources against constraints (normal deployment path)
-    #[cfg(feature = "backend")]
-    validate_resource_constraints(
-        &state,
-        &resolved_environment,
-        effective_replicas,
-        &effective_cpu,
-        &effective_memory,
-        false,
-    )?;
+/// Pick the most recent deployment created before `current` that can be redeployed.
+///
+/// `deployments` must be ordered newest first.
+fn select_previous_deployment<'a>(
+    deployments: &'a [crate::db::models::Deployment],
+    current: &crate::db::models::Deployment,
+) -> Option<&'a crate::db::models::Deployment> {
+    deployments.iter().find(|d| {
+        d.id != current.id
+            && d.created_at < current.created_at
+            && !matches!(
+                d.status,
+                DbDeploymentStatus::Failed | DbDeploymentStatus::Cancelled
+            )
+            && state_machine::can_create_from(d)
+    })
+}
 
-    // Branch based on whether user provided a pre-built image
-    if let Some(ref user_image) = payload.image {
-        if payload.push_image {
-            // Path 1a: Push-image deployment — CLI will pull and push to Rise registry
-            // Skip digest resolution: the image will be pushed to the internal registry by tag,
-            // and the controller uses get_image_tag() (the no-digest path) to pull from there.
-            info!("Creating push-image deployment with image: {}", user_image);
+/// Preview a rollback without changing any state
+///
+/// Resolves the rollback target (explicit or previous), and reports its image, the URL
+/// it will serve, and how its env var keys differ from the current deployment's.
+pub async fn preview_rollback(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(project_name): Path<String>,
+    Query(query): Query<RollbackPreviewQuery>,
+) -> Result<Json<RollbackPreviewResponse>, ServerError> {
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
 
-            let credentials = state
-                .registry_provider
-                .get_credentials(&payload.project)
+    // Resolve auth for project scope
+    let (_user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
+
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
+            .await
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+    }
+
+    let explicit_target = match query.deployment_id {
+        Some(ref deployment_id) => Some(
+            db_deployments::find_by_project_and_deployment_id(
+                &state.db_pool,
+                project.id,
+                deployment_id,
+            )
+            .await
+            .internal_err("Failed to find deployment")?
+            .ok_or_else(|| {
+                ServerError::not_found(format!(
+                    "Deployment '{}' not found for project '{}'",
+                    deployment_id, project_name
+                ))
+            })?,
+        ),
+        None => None,
+    };
+
+    let group = query
+        .group
+        .clone()
+        .or_else(|| explicit_target.as_ref().map(|d| d.deployment_group.clone()))
+        .unwrap_or_else(|| models::DEFAULT_DEPLOYMENT_GROUP.to_string());
+
+    let current =
+        db_deployments::find_active_for_project_and_group(&state.db_pool, project.id, &group)
+            .await
+            .internal_err("Failed to find current deployment")?;
+
+    let target = match explicit_target {
+        Some(target) => target,
+        None => {
+            let current = current.as_ref().ok_or_else(|| {
+                ServerError::not_found(format!(
+                    "No current deployment in group '{}' to roll back from",
+                    group
+                ))
+            })?;
+            let recent = db_deployments::list_for_project_and_group(
+                &state.db_pool,
+                project.id,
+                Some(&group),
+                Some(50),
+                None,
+            )
+            .await
+            .internal_err("Failed to list deployments")?;
+            select_previous_deployment(&recent, current)
+                .cloned()
+                .ok_or_else(|| {
+                    ServerError::not_found(format!(
+                        "No previous deployment in group '{}' to roll back to",
+                        group
+                    ))
+                })?
+        }
+    };
+
+    if !state_machine::can_create_from(&target) {
+        return Err(ServerError::bad_request(format!(
+            "Cannot roll back to '{}' because its image is not available (status '{}').",
+            target.deployment_id, target.status
+        )));
+    }
+
+    let env_keys = |deployment_id| {
+        let pool = state.db_pool.clone();
+        async move {
+            crate::db::env_vars::list_deployment_env_vars(&pool, deployment_id)
                 .await
-                .internal_err("Failed to get credentials")?;
-            let image_tag = state.registry_provider.get_image_tag(
-                &payload.project,
-                &deployment_id,
-                ImageTagType::ClientFacing,
+                .map(|vars| vars.into_iter().map(|v| v.key).collect::<Vec<_>>())
+        }
+    };
+    let target_keys = env_keys(target.id)
+        .await
+        .internal_err("Failed to load target environment variables")?;
+    let current_keys = match current {
+        Some(ref current) => env_keys(current.id)
+            .await
+            .internal_err("Failed to load current environment variables")?,
+        None => Vec::new(),
+    };
+
+    let image = super::utils::get_deployment_image_tag(&state, &target, &project).await;
+    let url = match state
+        .deployment_backend
+        .get_project_urls(&project, &group)
+        .await
+    {
+        Ok(urls) => Some(urls.primary_url),
+        Err(e) => {
+            warn!(
+                "Failed to compute URLs for project '{}': {:?}",
+                project.name, e
             );
+            None
+        }
+    };
 
-            let deployment = create_deployment_with_hooks(
-                &state,
-                db_deployments::CreateDeploymentParams {
-                    deployment_id: &deployment_id,
-                    project_id: project.id,
-                    created_by_id: user.id,
-                    status: DbDeploymentStatus::Pending,
-                    image: Some(user_image), // Store original user input for display
-                    image_digest: None, // No digest — controller will use internal registry tag
-                    rolled_back_from_deployment_id: None,
-                    deployment_group: &resolved_group,
-                    environment_id: resolved_environment.as_ref().map(|e| e.id),
-                    expires_at,
-                    http_port: effective_http_port as i32,
-                    is_active: false,
-                    job_url: job_url.as_deref(),
-                    pull_request_url: pull_request_url.as_deref(),
-                    replicas: effective_replicas as i32,
-                    cpu: &effective_cpu,
-                    memory: &effective_memory,
-                },
-                &project,
-            )
-            .await?;
+    Ok(Json(RollbackPreviewResponse {
+        deployment_group: group,
+        target_deployment_id: target.deployment_id.clone(),
+        target_status: convert_status_from_db(target.status.clone()),
+        image,
+        current_deployment_id: current.map(|d| d.deployment_id),
+        url,
+        env_diff: env_key_diff(&current_keys, &target_keys),
+    }))
+}
+
+/// Query parameters for diffing two deployments
+#[derive(Debug, serde::Deserialize)]
+pub struct DeploymentDiffQuery {
+    /// Deployment to compare from
+    pub from: String,
+    /// Deployment to compare to
+    pub to: String,
+}
 
-            info!(
-                "Created push-image deployment {} for project {}",
-                deployment_id, payload.project
-            );
+/// Fields of one deployment that are compared by a diff
+#[derive(Debug, serde::Serialize)]
+pub struct DeploymentDiffSide {
+    pub deployment_id: String,
+    pub status: DeploymentStatus,
+    pub image: Option<String>,
+    pub image_digest: Option<String>,
+    pub http_port: u16,
+    pub replicas: u32,
+    pub cpu: String,
+    pub memory: String,
+    pub cpu_limit: Option<String>,
+    pub memory_limit: Option<String>,
+}
 
-            // Copy project environment variables to deployment
-            crate::db::env_vars::copy_project_env_vars_to_deployment(
-                &state.db_pool,
-                project.id,
-                deployment.id,
-                deployment.environment_id,
-                resolved_environment.as_ref().map(|e| e.name.as_str()),
-            )
-            .await
-            .internal_err("Failed to copy environment variables")?;
+impl From<&crate::db::models::Deployment> for DeploymentDiffSide {
+    fn from(deployment: &crate::db::models::Deployment) -> Self {
+        Self {
+            deployment_id: deployment.deployment_id.clone(),
+            status: convert_status_from_db(deployment.status.clone()),
+            image: deployment.image.clone(),
+            image_digest: deployment.image_digest.clone(),
+            http_port: deployment.http_port as u16,
+            replicas: deployment.replicas as u32,
+            cpu: deployment.cpu.clone(),
+            memory: deployment.memory.clone(),
+            cpu_limit: deployment.cpu_limit.clone(),
+            memory_limit: deployment.memory_limit.clone(),
+        }
+    }
+}
 
-            // Apply env overrides from the request
-            apply_env_overrides(
-                &state,
-                deployment.id,
-                &payload.env_overrides,
-                resolved_environment.as_ref().map(|e| e.name.as_str()),
-            )
-            .await?;
+/// How an environment variable differs between two deployments
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvVarChange {
+    Added,
+    Removed,
+    Changed,
+    Unchanged,
+}
 
-            crate::db::env_vars::upsert_deployment_env_var(
-                &state.db_pool,
-                deployment.id,
-                "PORT",
-                &effective_http_port.to_string(),
-                false,
-                false,
-                "system",
-            )
-            .await
-            .internal_err("Failed to insert PORT env var")?;
+/// One environment variable in a deployment diff
+///
+/// Values are only included for plain variables; for secrets the diff only tells
+/// whether the value changed.
+#[derive(Debug, PartialEq, serde::Serialize)]
+pub struct EnvVarDiffEntry {
+    pub key: String,
+    pub change: EnvVarChange,
+    pub is_secret: bool,
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+}
 
-            insert_rise_env_vars(&state, &deployment, &project).await?;
+/// Response for diffing two deployments
+#[derive(Debug, serde::Serialize)]
+pub struct DeploymentDiffResponse {
+    pub from: DeploymentDiffSide,
+    pub to: DeploymentDiffSide,
+    pub env: Vec<EnvVarDiffEntry>,
+}
 
-            return Ok(Json(CreateDeploymentResponse {
-                deployment_id,
-                image_tag,
-                credentials,
-            }));
-        }
+/// An environment variable reduced to what a diff compares: `value` is the
+/// plaintext, decrypted for secrets where possible.
+struct ComparableEnvVar {
+    key: String,
+    is_secret: bool,
+    value: String,
+}
 
-        // Path 1b: Direct pre-built image deployment (no push)
-        info!("Creating deployment with pre-built image: {}", user_image);
+/// Compute the per-key diff between two deployments' env vars, sorted by key
+fn env_var_diff(from: Vec<ComparableEnvVar>, to: Vec<ComparableEnvVar>) -> Vec<EnvVarDiffEntry> {
+    let mut from: std::collections::BTreeMap<String, ComparableEnvVar> =
+        from.into_iter().map(|v| (v.key.clone(), v)).collect();
+    let mut entries = Vec::new();
+
+    for to_var in to {
+        let from_var = from.remove(&to_var.key);
+        let is_secret = to_var.is_secret || from_var.as_ref().is_some_and(|v| v.is_secret);
+        let change = match &from_var {
+            None => EnvVarChange::Added,
+            Some(f) if f.is_secret == to_var.is_secret && f.value == to_var.value => {
+                EnvVarChange::Unchanged
+            }
+            Some(_) => EnvVarChange::Changed,
+        };
+        let plain_value = |v: ComparableEnvVar| (!v.is_secret).then_some(v.value);
+        entries.push(EnvVarDiffEntry {
+            key: to_var.key.clone(),
+            change,
+            is_secret,
+            from_value: from_var.and_then(plain_value),
+            to_value: plain_value(to_var),
+        });
+    }
+    for (key, from_var) in from {
+        entries.push(EnvVarDiffEntry {
+            key,
+            change: EnvVarChange::Removed,
+            is_secret: from_var.is_secret,
+            from_value: (!from_var.is_secret).then_some(from_var.value),
+            to_value: None,
+        });
+    }
 
-        // Normalize image reference (add registry and namespace if missing)
-        let normalized_image = normalize_image_reference(user_image);
-        info!(
-            "Normalized image reference: {} -> {}",
-            user_image, normalized_image
-        );
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+}
 
-        // Resolve image to digest
-        info!("Resolving image '{}' to digest...", normalized_image);
-        let image_digest = resolve_image_digest(
-            &state.oci_client,
-            &state.registry_provider,
-            &normalized_image,
-        )
+/// Load a deployment's env vars for diffing, decrypting secrets so that changed
+/// values can be detected even though ciphertexts differ
+async fn comparable_env_vars(
+    state: &AppState,
+    deployment_id: uuid::Uuid,
+) -> Result<Vec<ComparableEnvVar>, ServerError> {
+    let vars = crate::db::env_vars::list_deployment_env_vars(&state.db_pool, deployment_id)
         .await
-        .map_err(|e| {
-            ServerError::bad_request(format!("Failed to resolve image '{}': {}", user_image, e))
-        })?;
+        .internal_err("Failed to load deployment environment variables")?;
 
-        info!("Successfully resolved image to digest: {}", image_digest);
+    let mut comparable = Vec::with_capacity(vars.len());
+    for var in vars {
+        comparable.push(comparable_env_var(state, var.key, var.value, var.is_secret).await?);
+    }
+    Ok(comparable)
+}
 
-        // Create deployment record with image fields set and invoke extension hooks
-        let deployment = create_deployment_with_hooks(
-            &state,
-            db_deployments::CreateDeploymentParams {
-                deployment_id: &deployment_id,
-                project_id: project.id,
-                created_by_id: user.id,
-                status: DbDeploymentStatus::Pushed,
-                image: Some(user_image),
-                image_digest: Some(&image_digest),
-                rolled_back_from_deployment_id: None,
-                deployment_group: &resolved_group,
-                environment_id: resolved_environment.as_ref().map(|e| e.id),
-                expires_at,
-                http_port: effective_http_port as i32,
-                is_active: false,
-                job_url: job_url.as_deref(),
-                pull_request_url: pull_request_url.as_deref(),
-                replicas: effective_replicas as i32,
-                cpu: &effective_cpu,
-                memory: &effective_memory,
-            },
-            &project,
-        )
-        .await?;
+/// Build a [`ComparableEnvVar`], decrypting the value if it is a secret
+async fn comparable_env_var(
+    state: &AppState,
+    key: String,
+    value: String,
+    is_secret: bool,
+) -> Result<ComparableEnvVar, ServerError> {
+    let value = match (&state.encryption_provider, is_secret) {
+        (Some(provider), true) => provider
+            .decrypt(&value)
+            .await
+            .internal_err("Failed to decrypt secret")?,
+        // Without an encryption provider, identical ciphertexts (secrets copied from
+        // the same project variable) still compare equal
+        _ => value,
+    };
+    Ok(ComparableEnvVar {
+        key,
+        is_secret,
+        value,
+    })
+}
 
-        info!(
-            "Created pre-built image deployment {} for project {}",
-            deployment_id, payload.project
-        );
+/// GET /projects/{project_name}/deployments/diff - Compare two deployments
+///
+/// Reports image, port, replica and resource settings of both deployments, and how
+/// their env vars differ. Secret values are never returned.
+pub async fn diff_deployments(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(project_name): Path<String>,
+    Query(query): Query<DeploymentDiffQuery>,
+) -> Result<Json<DeploymentDiffResponse>, ServerError> {
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
 
-        // Copy project environment variables to deployment
-        crate::db::env_vars::copy_project_env_vars_to_deployment(
-            &state.db_pool,
-            project.id,
-            deployment.id,
-            deployment.environment_id,
-            resolved_environment.as_ref().map(|e| e.name.as_str()),
-        )
+    // Resolve auth for project scope
+    let (_user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
         .await
-        .internal_err("Failed to copy environment variables")?;
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
 
-        // Apply env overrides from the request
-        apply_env_overrides(
-            &state,
-            deployment.id,
-            &payload.env_overrides,
-            resolved_environment.as_ref().map(|e| e.name.as_str()),
-        )
-        .await?;
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
+            .await
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+    }
 
-        // Upsert PORT env var with the resolved effective value
-        // This overwrites any user-set PORT with the resolved value (which may be the same)
-        crate::db::env_vars::upsert_deployment_env_var(
+    let mut sides = Vec::with_capacity(2);
+    for deployment_id in [&query.from, &query.to] {
+        let deployment = db_deployments::find_by_project_and_deployment_id(
             &state.db_pool,
-            deployment.id,
-            "PORT",
-            &effective_http_port.to_string(),
-            false, // not a secret
-            false, // is_protected
-            "system",
+            project.id,
+            deployment_id,
         )
         .await
-        .internal_err("Failed to insert PORT env var")?;
+        .internal_err("Failed to find deployment")?
+        .ok_or_else(|| {
+            ServerError::not_found(format!(
+                "Deployment '{}' not found for project '{}'",
+                deployment_id, project_name
+            ))
+        })?;
+        let env = comparable_env_vars(&state, deployment.id).await?;
+        sides.push((DeploymentDiffSide::from(&deployment), env));
+    }
+    let (to, to_env) = sides.pop().expect("two deployments");
+    let (from, from_env) = sides.pop().expect("two deployments");
 
-        // Insert Rise-provided environment variables
-        insert_rise_env_vars(&state, &deployment, &project).await?;
+    Ok(Json(DeploymentDiffResponse {
+        from,
+        to,
+        env: env_var_diff(from_env, to_env),
+    }))
+}
 
-        // Trigger Metacontroller resync so the webhook picks up the new Pushed deployment
-        if let Some(ref kube_client) = state.kube_client {
-            if let Err(e) =
-                crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
-            {
-                tracing::warn!(
-                    project = %project.name,
-                    "Failed to trigger CRD resync after pre-built image deployment: {:?}", e
-                );
+/// Response for diffing a deployment's env vars against the current project env vars
+#[derive(Debug, serde::Serialize)]
+pub struct DeploymentEnvDiffResponse {
+    pub deployment_id: String,
+    /// Whether any project env var was added, removed or changed since the deployment
+    pub has_changes: bool,
+    pub env: Vec<EnvVarDiffEntry>,
+}
+
+/// Resolve the project env vars a new deployment in `environment_id` would receive:
+/// environment-scoped values win over global ones, mirroring
+/// `copy_project_env_vars_to_deployment`.
+fn effective_project_env_vars(
+    vars: Vec<crate::db::models::ProjectEnvVar>,
+    environment_id: Option<uuid::Uuid>,
+) -> Vec<crate::db::models::ProjectEnvVar> {
+    let mut resolved: std::collections::BTreeMap<String, crate::db::models::ProjectEnvVar> =
+        std::collections::BTreeMap::new();
+    for var in vars {
+        if var.environment_id.is_some() && var.environment_id != environment_id {
+            continue;
+        }
+        match resolved.get(&var.key) {
+            Some(existing) if existing.environment_id.is_some() => {}
+            _ => {
+                resolved.insert(var.key.clone(), var);
             }
         }
+    }
+    resolved.into_values().collect()
+}
 
-        // Return response with digest as image_tag and empty credentials
-        Ok(Json(CreateDeploymentResponse {
-            deployment_id,
-            image_tag: image_digest,
-            credentials: crate::server::registry::models::RegistryCredentials {
-                registry_url: String::new(),
-                username: String::new(),
-                password: String::new(),
-                expires_in: None,
-                auth_method: Default::default(),
-            },
-        }))
-    } else {
-        // Path 2: Build from source (current behavior)
-        // Get registry credentials
-        let credentials = state
-            .registry_provider
-            .get_credentials(&payload.project)
-            .await
-            .internal_err("Failed to get credentials")?;
-
-        // Get full image tag from provider for CLI client (uses client_registry_url if configured)
-        let image_tag = state.registry_provider.get_image_tag(
-            &payload.project,
-            &deployment_id,
-            ImageTagType::ClientFacing,
-        );
-
-        debug!("Image tag: {}", image_tag);
-
-        // Create deployment record in database and invoke extension hooks
-        let deployment = create_deployment_with_hooks(
-            &state,
-            db_deployments::CreateDeploymentParams {
-                deployment_id: &deployment_id,
-                project_id: project.id,
-                created_by_id: user.id,
-                status: DbDeploymentStatus::Pending,
-                image: None,        // image - NULL for build-from-source
-                image_digest: None, // image_digest - NULL for build-from-source
-                rolled_back_from_deployment_id: None, // Not a rollback
-                deployment_group: &resolved_group, // deployment_group
-                environment_id: resolved_environment.as_ref().map(|e| e.id),
-                expires_at,                            // expires_at
-                http_port: effective_http_port as i32, // http_port
-                is_active: false,                      // Deployments start as inactive
-                job_url: job_url.as_deref(),
-                pull_request_url: pull_request_url.as_deref(),
-                replicas: effective_replicas as i32,
-                cpu: &effective_cpu,
-                memory: &effective_memory,
-            },
-            &project,
-        )
-        .await?;
-
-        info!(
-            "Created build-from-source deployment {} for project {}",
-            deployment_id, payload.project
-        );
+/// GET /projects/{project_name}/deployments/{deployment_id}/env-diff - Compare a
+/// deployment's env vars with the current project env vars
+///
+/// Only variables that were copied from the project (`global` and `env:*` sources)
+/// are compared; keys the deployment sets from rise.toml, the CLI, extensions or the
+/// system shadow project values and are skipped. Secret values are never returned.
+pub async fn diff_deployment_env(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((project_name, deployment_id)): Path<(String, String)>,
+) -> Result<Json<DeploymentEnvDiffResponse>, ServerError> {
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
 
-        // Copy project environment variables to deployment
-        crate::db::env_vars::copy_project_env_vars_to_deployment(
-            &state.db_pool,
-            project.id,
-            deployment.id,
-            deployment.environment_id,
-            resolved_environment.as_ref().map(|e| e.name.as_str()),
-        )
+    // Resolve auth for project scope
+    let (_user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
         .await
-        .internal_err("Failed to copy environment variables")?;
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
 
-        // Apply env overrides from the request
-        apply_env_overrides(
-            &state,
-            deployment.id,
-            &payload.env_overrides,
-            resolved_environment.as_ref().map(|e| e.name.as_str()),
-        )
-        .await?;
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
+            .await
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+    }
 
-        // Upsert PORT env var with the resolved effective value
-        // This overwrites any user-set PORT with the resolved value (which may be the same)
-        crate::db::env_vars::upsert_deployment_env_var(
-            &state.db_pool,
-            deployment.id,
-            "PORT",
-            &effective_http_port.to_string(),
-            false, // not a secret
-            false, // is_protected
-            "system",
-        )
-        .await
-        .internal_err("Failed to insert PORT env var")?;
+    let deployment = db_deployments::find_by_project_and_deployment_id(
+        &state.db_pool,
+        project.id,
+        &deployment_id,
+    )
+    .await
+    .internal_err("Failed to find deployment")?
+    .ok_or_else(|| {
+        ServerError::not_found(format!(
+            "Deployment '{}' not found for project '{}'",
+            deployment_id, project_name
+        ))
+    })?;
 
-        // Insert Rise-provided environment variables
-        insert_rise_env_vars(&state, &deployment, &project).await?;
+    let deployment_vars =
+        crate::db::env_vars::list_deployment_env_vars(&state.db_pool, deployment.id)
+            .await
+            .internal_err("Failed to load deployment environment variables")?;
+    let mut shadowed = std::collections::HashSet::new();
+    let mut from_env = Vec::new();
+    for var in deployment_vars {
+        match crate::db::models::EnvVarSource::parse(&var.source) {
+            Some(crate::db::models::EnvVarSource::Global)
+            | Some(crate::db::models::EnvVarSource::Env(_)) => {
+                from_env.push(comparable_env_var(&state, var.key, var.value, var.is_secret).await?);
+            }
+            _ => {
+                shadowed.insert(var.key);
+            }
+        }
+    }
 
-        // Return response
-        Ok(Json(CreateDeploymentResponse {
-            deployment_id,
-            image_tag,
-            credentials,
-        }))
+    let project_vars = crate::db::env_vars::list_project_env_vars(
+        &state.db_pool,
+        project.id,
+        deployment.environment_id,
+    )
+    .await
+    .internal_err("Failed to list project environment variables")?;
+    let mut to_env = Vec::new();
+    for var in effective_project_env_vars(project_vars, deployment.environment_id) {
+        if shadowed.contains(&var.key) {
+            continue;
+        }
+        to_env.push(comparable_env_var(&state, var.key, var.value, var.is_secret).await?);
     }
+
+    let env = env_var_diff(from_env, to_env);
+    Ok(Json(DeploymentEnvDiffResponse {
+        deployment_id: deployment.deployment_id,
+        has_changes: env.iter().any(|e| e.change != EnvVarChange::Unchanged),
+        env,
+    }))
 }
 
-/// Shared logic for updating a deployment's status.
+/// POST /projects/{project_name}/deployments/{deployment_id}/stop - Stop a specific deployment
+pub async fn stop_deployment(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((project_name, deployment_id)): Path<(String, String)>,
+) -> Result<Json<Deployment>, ServerError> {
+    info!(
+        "Stopping deployment '{}' for project '{}'",
+        deployment_id, project_name
+    );
+    stop_single_deployment(state, auth, project_name, deployment_id, false).await
+}
+
+/// POST /projects/{project_name}/deployments/{deployment_id}/cancel - Cancel a deployment
+/// that is still in progress, leaving the group's active deployment untouched
+pub async fn cancel_deployment(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((project_name, deployment_id)): Path<(String, String)>,
+) -> Result<Json<Deployment>, ServerError> {
+    info!(
+        "Cancelling deployment '{}' for project '{}'",
+        deployment_id, project_name
+    );
+    stop_single_deployment(state, auth, project_name, deployment_id, true).await
+}
+
+/// Move a non-terminal deployment to Cancelling or Terminating.
 ///
-/// Given a resolved deployment and project, validates permissions and applies the status update.
-/// When `is_sa` is true, permission checks are skipped (already validated by `resolve_for_project`).
-async fn perform_status_update(
-    state: &AppState,
-    user: &crate::db::models::User,
-    is_sa: bool,
-    deployment: crate::db::models::Deployment,
-    project: &crate::db::models::Project,
-    deployment_id: &str,
-    payload: UpdateDeploymentStatusRequest,
+/// With `keep_active`, the active deployment of its group is refused.
+async fn stop_single_deployment(
+    state: AppState,
+    auth: AuthContext,
+    project_name: String,
+    deployment_id: String,
+    keep_active: bool,
 ) -> Result<Json<Deployment>, ServerError> {
-    // Check if user has permission (owns the project)
-    // SA access was already validated by resolve_for_project
+    // Find the project by name
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+
+    // Resolve auth for project scope
+    let (_user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
+
+    // Check if user has permission to stop deployments (SA access already validated)
     if !is_sa {
-        crate::server::project::handlers::ensure_project_access_or_admin(state, user, project)
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
             .await
-            .map_err(|_| {
-                ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
-            })?;
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
     }
 
-    // Update status in database
-    let status_copy = payload.status.clone();
-    let updated_deployment = match payload.status {
-        DeploymentStatus::Failed => {
-            let error_msg = payload.error_message.as_deref().unwrap_or("Unknown error");
-            let deployment = db_deployments::mark_failed(&state.db_pool, deployment.id, error_msg)
-                .await
-                .internal_err("Failed to update deployment")?;
-
-            // Update project status to Failed
-            projects::update_calculated_status(&state.db_pool, project.id)
-                .await
-                .internal_err("Failed to update project status")?;
+    // Find the specific deployment
+    let deployment =
+        db_deployments::find_by_deployment_id(&state.db_pool, &deployment_id, project.id)
+            .await
+            .internal_err("Failed to find deployment")?
+            .ok_or_else(|| {
+                ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
+            })?;
 
-            deployment
-        }
-        _ => {
-            // update_status will validate the state transition
-            let db_status = convert_status_to_db(payload.status);
-            let deployment =
-                db_deployments::update_status(&state.db_pool, deployment.id, db_status)
-                    .await
-                    .map_err(|e| {
-                        // State transition validation errors are returned as anyhow errors
-                        // Return BAD_REQUEST for validation errors, INTERNAL_SERVER_ERROR otherwise
-                        let error_msg = e.to_string();
-                        if error_msg.contains("Invalid deployment state transition") {
-                            ServerError::bad_request(error_msg)
-                        } else {
-                            ServerError::internal_anyhow(e, "Failed to update deployment")
-                        }
-                    })?;
+    // Check if deployment is already in a terminal state
+    if state_machine::is_terminal(&deployment.status) {
+        return Err(ServerError::bad_request(format!(
+            "Deployment '{}' is already in terminal state: {}",
+            deployment_id, deployment.status
+        )));
+    }
 
-            // Update project status (e.g., to Deploying)
-            projects::update_calculated_status(&state.db_pool, project.id)
-                .await
-                .internal_err("Failed to update project status")?;
+    if keep_active && deployment.is_active {
+        return Err(ServerError::bad_request(format!(
+            "Deployment '{}' is the active deployment of group '{}'; stop the group instead",
+            deployment_id, deployment.deployment_group
+        )));
+    }
 
-            deployment
-        }
+    // Use the appropriate state transition based on current status:
+    // Pre-infrastructure states (Pending, Building, Pushing, Pushed, Deploying) → Cancelling
+    // Infrastructure states (Healthy, Unhealthy) → Terminating
+    let updated_deployment = if state_machine::is_cancellable(&deployment.status) {
+        let d = db_deployments::mark_cancelling(&state.db_pool, deployment.id, Some(_user.id))
+            .await
+            .internal_err("Failed to cancel deployment")?;
+        info!("Marked deployment {} as Cancelling", deployment_id);
+        d
+    } else {
+        let d = db_deployments::mark_terminating(
+            &state.db_pool,
+            deployment.id,
+            crate::db::models::TerminationReason::UserStopped,
+            Some(_user.id),
+        )
+        .await
+        .internal_err("Failed to stop deployment")?;
+        info!("Marked deployment {} as Terminating", deployment_id);
+        d
     };
 
-    info!(
-        "Updated deployment {} to status {:?}",
-        deployment_id, status_copy
-    );
+    // Update project status
+    projects::update_calculated_status(&state.db_pool, project.id)
+        .await
+        .internal_err("Failed to update project status")?;
 
-    // Trigger Metacontroller resync so the webhook picks up the status change
+    // Trigger Metacontroller resync
     if let Some(ref kube_client) = state.kube_client {
         if let Err(e) =
             crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
@@ -1494,34 +3766,30 @@ async fn perform_status_update(
         }
     }
 
-    // Only calculate URLs for non-terminal deployments that could receive traffic
-    let (primary_url, custom_domain_urls) =
-        if state_machine::is_terminal(&updated_deployment.status) {
+    // Calculate deployment URLs dynamically
+    let (primary_url, custom_domain_urls) = match state
+        .deployment_backend
+        .get_deployment_urls(&updated_deployment, &project)
+        .await
+    {
+        Ok(urls) => (Some(urls.primary_url), urls.custom_domain_urls),
+        Err(e) => {
+            error!(
+                "Failed to calculate URLs for deployment {}: {}",
+                deployment_id, e
+            );
             (None, vec![])
-        } else {
-            match state
-                .deployment_backend
-                .get_deployment_urls(&updated_deployment, project)
-                .await
-            {
-                Ok(urls) => (Some(urls.primary_url), urls.custom_domain_urls),
-                Err(e) => {
-                    error!(
-                        "Failed to calculate URLs for deployment {}: {}",
-                        deployment_id, e
-                    );
-                    (None, vec![])
-                }
-            }
-        };
+        }
+    };
 
+    // Get creator email and convert to API model
     let created_by_email =
         get_creator_email(&state.db_pool, updated_deployment.created_by_id).await;
     Ok(Json(
         convert_deployment(
-            state,
+            &state,
             updated_deployment,
-            project,
+            &project,
             created_by_email,
             primary_url,
             custom_domain_urls,
@@ -1530,25 +3798,36 @@ async fn perform_status_update(
     ))
 }
 
-/// PATCH /projects/{project_name}/deployments/{deployment_id}/status - Update deployment status (project-scoped)
-pub async fn update_deployment_status_by_project(
+/// Request body for promoting a deployment to another group
+#[derive(Debug, serde::Deserialize)]
+pub struct PromoteDeploymentRequest {
+    /// Target deployment group
+    pub to: String,
+    /// Target environment (defaults to the environment resolved from the group)
+    #[serde(default)]
+    pub environment: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// POST /projects/{project_name}/deployments/{deployment_id}/promote - Deploy the image
+/// and env var snapshot of a deployment to another group
+pub async fn promote_deployment(
     State(state): State<AppState>,
     auth: AuthContext,
     Path((project_name, deployment_id)): Path<(String, String)>,
-    Json(payload): Json<UpdateDeploymentStatusRequest>,
-) -> Result<Json<Deployment>, ServerError> {
+    Json(payload): Json<PromoteDeploymentRequest>,
+) -> Result<Json<CreateDeploymentResponse>, ServerError> {
     info!(
-        "Updating deployment {} status to {:?} for project {}",
-        deployment_id, payload.status, project_name
+        "Promoting deployment '{}' of project '{}' to group '{}'",
+        deployment_id, project_name, payload.to
     );
 
-    // Find project by name
     let project = projects::find_by_name(&state.db_pool, &project_name)
         .await
         .internal_err("Failed to find project")?
         .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
 
-    // Resolve auth for project scope
     let (user, is_sa) = auth
         .resolve_for_project(&state.db_pool, &project)
         .await
@@ -1560,130 +3839,67 @@ pub async fn update_deployment_status_by_project(
             }
         })?;
 
-    // Find deployment by deployment_id + project_id
-    let deployment =
-        db_deployments::find_by_deployment_id(&state.db_pool, &deployment_id, project.id)
-            .await
-            .internal_err("Failed to find deployment")?
-            .ok_or_else(|| {
-                ServerError::not_found(format!(
-                    "Deployment '{}' not found for project '{}'",
-                    deployment_id, project_name
-                ))
-            })?;
-
-    perform_status_update(
-        &state,
-        &user,
-        is_sa,
-        deployment,
-        &project,
-        &deployment_id,
-        payload,
-    )
-    .await
-}
-
-/// PATCH /deployments/{deployment_id}/status - Update deployment status (deprecated, unscoped)
-///
-/// This endpoint is deprecated. Use `PATCH /projects/{project_name}/deployments/{deployment_id}/status` instead.
-/// Kept for backward compatibility with older CLI versions.
-///
-/// Returns 409 Conflict when multiple projects have deployments with the same deployment_id,
-/// since we cannot determine which one the caller intended.
-pub async fn update_deployment_status(
-    State(state): State<AppState>,
-    auth: AuthContext,
-    Path(deployment_id): Path<String>,
-    Json(payload): Json<UpdateDeploymentStatusRequest>,
-) -> Result<Json<Deployment>, ServerError> {
-    warn!(
-        "Deprecated endpoint called: PATCH /deployments/{}/status — use PATCH /projects/{{project_name}}/deployments/{}/status instead",
-        deployment_id, deployment_id
-    );
-
-    // Single query to find matching deployments across all projects (LIMIT 2 to detect collisions)
-    let matching_deployments =
-        db_deployments::find_by_deployment_id_unscoped(&state.db_pool, &deployment_id, 2)
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &user, &project)
             .await
-            .internal_err(format!("Failed to query deployment '{}'", deployment_id))?;
-
-    if matching_deployments.len() > 1 {
-        let project_ids: Vec<String> = matching_deployments
-            .iter()
-            .map(|d| d.project_id.to_string())
-            .collect();
-        warn!(
-            "Deployment ID collision on deprecated endpoint: deployment_id={} matches {} projects: {:?}. \
-             Refusing to proceed. Clients should migrate to the project-scoped endpoint.",
-            deployment_id,
-            matching_deployments.len(),
-            project_ids,
-        );
-        return Err(ServerError::conflict(format!(
-            "Ambiguous deployment_id '{}': matches multiple projects. \
-             Use PATCH /api/v1/projects/{{project_name}}/deployments/{}/status instead.",
-            deployment_id, deployment_id
-        )));
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
     }
 
-    let deployment = matching_deployments.into_iter().next().ok_or_else(|| {
-        ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
-    })?;
-
-    let project = projects::find_by_id(&state.db_pool, deployment.project_id)
+    let source = db_deployments::find_by_deployment_id(&state.db_pool, &deployment_id, project.id)
         .await
-        .internal_err("Failed to find project")?
+        .internal_err("Failed to find deployment")?
         .ok_or_else(|| {
-            ServerError::not_found(format!(
-                "Project for deployment '{}' not found",
-                deployment_id
-            ))
+            ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
         })?;
 
-    // Resolve auth for project scope
-    let (user, is_sa) = auth
-        .resolve_for_project(&state.db_pool, &project)
-        .await
-        .map_err(|e| {
-            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
-                ServerError::not_found(format!("Project '{}' not found", project.name))
-            } else {
-                e
-            }
-        })?;
+    if source.deployment_group == payload.to {
+        return Err(ServerError::bad_request(format!(
+            "Deployment '{}' is already in group '{}'. Use 'rise deployment rollback' to redeploy it.",
+            deployment_id, payload.to
+        )));
+    }
 
-    perform_status_update(
-        &state,
-        &user,
-        is_sa,
-        deployment,
-        &project,
-        &deployment_id,
-        payload,
+    let message = payload.message.unwrap_or_else(|| {
+        format!(
+            "Promote {} from group '{}'",
+            deployment_id, source.deployment_group
+        )
+    });
+
+    // The rest (group name, environment restrictions, quota, image availability) is
+    // validated like for any other deployment created from an existing one
+    create_deployment(
+        State(state),
+        auth,
+        Json(CreateDeploymentRequest {
+            project: project.name,
+            group: Some(payload.to),
+            environment: payload.environment,
+            from_deployment: Some(deployment_id),
+            use_source_env_vars: true,
+            message: Some(message),
+            ..Default::default()
+        }),
     )
     .await
 }
 
-/// Query parameters for listing deployments
+/// Request body for scaling a deployment
 #[derive(Debug, serde::Deserialize)]
-pub struct ListDeploymentsQuery {
-    #[serde(rename = "group")]
-    pub deployment_group: Option<String>,
-    pub limit: Option<i64>,
-    pub offset: Option<i64>,
+pub struct ScaleDeploymentRequest {
+    pub replicas: u32,
 }
 
-/// List deployments for a project
-pub async fn list_deployments(
+/// POST /projects/{project_name}/deployments/{deployment_id}/scale - Change the replica count
+pub async fn scale_deployment(
     State(state): State<AppState>,
     auth: AuthContext,
-    Path(project_name): Path<String>,
-    Query(query): Query<ListDeploymentsQuery>,
-) -> Result<Json<Vec<Deployment>>, ServerError> {
-    debug!(
-        "Listing deployments for project: {} (group: {:?})",
-        project_name, query.deployment_group
+    Path((project_name, deployment_id)): Path<(String, String)>,
+    Json(payload): Json<ScaleDeploymentRequest>,
+) -> Result<Json<Deployment>, ServerError> {
+    info!(
+        "Scaling deployment '{}' for project '{}' to {} replicas",
+        deployment_id, project_name, payload.replicas
     );
 
     // Find the project by name
@@ -1693,7 +3909,7 @@ pub async fn list_deployments(
         .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
 
     // Resolve auth for project scope
-    let (user, is_sa) = auth
+    let (_user, is_sa) = auth
         .resolve_for_project(&state.db_pool, &project)
         .await
         .map_err(|e| {
@@ -1704,101 +3920,105 @@ pub async fn list_deployments(
             }
         })?;
 
-    // Check if user has permission to view deployments (SA access already validated)
+    // Check if user has permission to scale deployments (SA access already validated)
     if !is_sa {
-        crate::server::project::handlers::ensure_project_access_or_admin(&state, &user, &project)
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
             .await
             .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
     }
 
-    // Get deployments from database (optionally filtered by group, with pagination)
-    let db_deployments = db_deployments::list_for_project_and_group(
-        &state.db_pool,
-        project.id,
-        query.deployment_group.as_deref(),
-        query.limit,
-        query.offset,
-    )
-    .await
-    .internal_err("Failed to list deployments")?;
+    let deployment =
+        db_deployments::find_by_deployment_id(&state.db_pool, &deployment_id, project.id)
+            .await
+            .internal_err("Failed to find deployment")?
+            .ok_or_else(|| {
+                ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
+            })?;
 
-    // Convert to API models (fetch creator emails and calculate URLs)
-    let mut deployments = Vec::with_capacity(db_deployments.len());
-    for db_deployment in db_deployments {
-        let created_by_email = get_creator_email(&state.db_pool, db_deployment.created_by_id).await;
+    if state_machine::is_terminal(&deployment.status) {
+        return Err(ServerError::bad_request(format!(
+            "Deployment '{}' is already in terminal state: {}",
+            deployment_id, deployment.status
+        )));
+    }
 
-        // Only calculate URLs for non-terminal deployments that could receive traffic
-        let (primary_url, custom_domain_urls) = if state_machine::is_terminal(&db_deployment.status)
-        {
-            // Terminal deployments (Failed, Stopped, Cancelled, Superseded, Expired) cannot receive traffic
-            (None, vec![])
-        } else {
-            // Calculate deployment URLs dynamically for active deployments
-            match state
-                .deployment_backend
-                .get_deployment_urls(&db_deployment, &project)
-                .await
-            {
-                Ok(urls) => (Some(urls.primary_url), urls.custom_domain_urls),
-                Err(e) => {
-                    error!(
-                        "Failed to calculate URLs for deployment {}: {}",
-                        db_deployment.deployment_id, e
-                    );
-                    (None, vec![])
-                }
-            }
-        };
+    // Enforce the same replica constraints as at deploy time
+    validate_volume_replicas(deployment.volume.as_ref(), payload.replicas)?;
+    let environment = match deployment.environment_id {
+        Some(env_id) => crate::db::environments::find_by_id(&state.db_pool, env_id)
+            .await
+            .internal_err("Failed to find environment")?,
+        None => None,
+    };
+    validate_resource_constraints(
+        &state,
+        &environment,
+        payload.replicas,
+        &deployment.cpu,
+        &deployment.memory,
+        deployment.cpu_limit.as_deref(),
+        deployment.memory_limit.as_deref(),
+        false,
+    )?;
 
-        deployments.push(
-            convert_deployment(
-                &state,
-                db_deployment,
-                &project,
-                created_by_email,
-                primary_url,
-                custom_domain_urls,
-            )
-            .await,
-        );
-    }
+    let updated_deployment =
+        db_deployments::update_replicas(&state.db_pool, deployment.id, payload.replicas as i32)
+            .await
+            .internal_err("Failed to scale deployment")?;
 
-    Ok(Json(deployments))
-}
+    // Trigger Metacontroller resync
+    if let Some(ref kube_client) = state.kube_client {
+        if let Err(e) =
+            crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
+        {
+            tracing::warn!(
+                project = %project.name,
+                "Failed to trigger CRD resync: {:?}", e
+            );
+        }
+    }
 
-/// Query parameters for stopping deployments
-#[derive(Debug, serde::Deserialize)]
-pub struct StopDeploymentsQuery {
-    pub group: String,
-}
+    let (primary_url, custom_domain_urls) = match state
+        .deployment_backend
+        .get_deployment_urls(&updated_deployment, &project)
+        .await
+    {
+        Ok(urls) => (Some(urls.primary_url), urls.custom_domain_urls),
+        Err(e) => {
+            error!(
+                "Failed to calculate URLs for deployment {}: {}",
+                deployment_id, e
+            );
+            (None, vec![])
+        }
+    };
 
-/// Response for stopping deployments
-#[derive(Debug, serde::Serialize)]
-pub struct StopDeploymentsResponse {
-    pub stopped_count: usize,
-    pub deployment_ids: Vec<String>,
+    let created_by_email =
+        get_creator_email(&state.db_pool, updated_deployment.created_by_id).await;
+    Ok(Json(
+        convert_deployment(
+            &state,
+            updated_deployment,
+            &project,
+            created_by_email,
+            primary_url,
+            custom_domain_urls,
+        )
+        .await,
+    ))
 }
 
-/// POST /projects/{project_name}/deployments/stop - Stop all deployments in a group
-pub async fn stop_deployments_by_group(
+/// POST /projects/{project_name}/deployments/{deployment_id}/restart - Recreate pods without a rebuild
+pub async fn restart_deployment(
     State(state): State<AppState>,
     auth: AuthContext,
-    Path(project_name): Path<String>,
-    Query(query): Query<StopDeploymentsQuery>,
-) -> Result<Json<StopDeploymentsResponse>, ServerError> {
+    Path((project_name, deployment_id)): Path<(String, String)>,
+) -> Result<Json<Deployment>, ServerError> {
     info!(
-        "Stopping all deployments in group '{}' for project '{}'",
-        query.group, project_name
+        "Restarting deployment '{}' for project '{}'",
+        deployment_id, project_name
     );
 
-    // Validate group name
-    if !is_valid_group_name(&query.group) {
-        return Err(ServerError::bad_request(format!(
-            "Invalid group name '{}'. Must be 'default' or match pattern [a-z0-9][a-z0-9/-]*[a-z0-9] (no consecutive hyphens, normalized length max 63 chars)",
-            query.group
-        )));
-    }
-
     // Find the project by name
     let project = projects::find_by_name(&state.db_pool, &project_name)
         .await
@@ -1817,97 +4037,96 @@ pub async fn stop_deployments_by_group(
             }
         })?;
 
-    // Check if user has permission to stop deployments (SA access already validated)
+    // Check if user has permission to restart deployments (SA access already validated)
     if !is_sa {
         crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
             .await
             .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
     }
 
-    // Find all non-terminal deployments in this group
-    let deployments = db_deployments::find_non_terminal_for_project_and_group(
-        &state.db_pool,
-        project.id,
-        &query.group,
-    )
-    .await
-    .internal_err("Failed to find deployments")?;
-
-    let mut stopped_ids = Vec::new();
-
-    // Mark each deployment using appropriate state transition
-    for deployment in deployments {
-        let result = if state_machine::is_cancellable(&deployment.status) {
-            db_deployments::mark_cancelling(&state.db_pool, deployment.id)
-                .await
-                .map(|_| "Cancelling")
-        } else {
-            db_deployments::mark_terminating(
-                &state.db_pool,
-                deployment.id,
-                crate::db::models::TerminationReason::UserStopped,
-            )
+    let deployment =
+        db_deployments::find_by_deployment_id(&state.db_pool, &deployment_id, project.id)
             .await
-            .map(|_| "Terminating")
-        };
-        match result {
-            Ok(new_status) => {
-                info!(
-                    "Marked deployment {} as {}",
-                    deployment.deployment_id, new_status
-                );
-                stopped_ids.push(deployment.deployment_id);
-            }
-            Err(e) => {
-                error!(
-                    "Failed to stop deployment {}: {}",
-                    deployment.deployment_id, e
-                );
-            }
-        }
+            .internal_err("Failed to find deployment")?
+            .ok_or_else(|| {
+                ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
+            })?;
+
+    // Only deployments with running infrastructure can be restarted
+    if !matches!(
+        deployment.status,
+        crate::db::models::DeploymentStatus::Healthy
+            | crate::db::models::DeploymentStatus::Unhealthy
+    ) {
+        return Err(ServerError::bad_request(format!(
+            "Deployment '{}' cannot be restarted in state {}",
+            deployment_id, deployment.status
+        )));
     }
 
-    // Update project status
-    projects::update_calculated_status(&state.db_pool, project.id)
+    let updated_deployment = db_deployments::mark_restarted(&state.db_pool, deployment.id)
         .await
-        .internal_err("Failed to update project status")?;
+        .internal_err("Failed to restart deployment")?;
 
     // Trigger Metacontroller resync
-    if !stopped_ids.is_empty() {
-        if let Some(ref kube_client) = state.kube_client {
-            if let Err(e) =
-                crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
-            {
-                tracing::warn!(
-                    project = %project.name,
-                    "Failed to trigger CRD resync: {:?}", e
-                );
-            }
+    if let Some(ref kube_client) = state.kube_client {
+        if let Err(e) =
+            crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
+        {
+            tracing::warn!(
+                project = %project.name,
+                "Failed to trigger CRD resync: {:?}", e
+            );
         }
     }
 
-    info!(
-        "Stopped {} deployments in group '{}' for project '{}'",
-        stopped_ids.len(),
-        query.group,
-        project_name
-    );
+    let (primary_url, custom_domain_urls) = match state
+        .deployment_backend
+        .get_deployment_urls(&updated_deployment, &project)
+        .await
+    {
+        Ok(urls) => (Some(urls.primary_url), urls.custom_domain_urls),
+        Err(e) => {
+            error!(
+                "Failed to calculate URLs for deployment {}: {}",
+                deployment_id, e
+            );
+            (None, vec![])
+        }
+    };
 
-    Ok(Json(StopDeploymentsResponse {
-        stopped_count: stopped_ids.len(),
-        deployment_ids: stopped_ids,
-    }))
+    let created_by_email =
+        get_creator_email(&state.db_pool, updated_deployment.created_by_id).await;
+    Ok(Json(
+        convert_deployment(
+            &state,
+            updated_deployment,
+            &project,
+            created_by_email,
+            primary_url,
+            custom_domain_urls,
+        )
+        .await,
+    ))
 }
 
-/// POST /projects/{project_name}/deployments/{deployment_id}/stop - Stop a specific deployment
-pub async fn stop_deployment(
+/// Request body for renewing a deployment's expiration
+#[derive(Debug, serde::Deserialize)]
+pub struct RenewDeploymentRequest {
+    /// New expiration duration from now (e.g., '7d', '2h', '30m')
+    pub expires_in: String,
+}
+
+/// POST /projects/{project_name}/deployments/{deployment_id}/renew - Reset the expiration
+pub async fn renew_deployment(
     State(state): State<AppState>,
     auth: AuthContext,
     Path((project_name, deployment_id)): Path<(String, String)>,
+    Json(payload): Json<RenewDeploymentRequest>,
 ) -> Result<Json<Deployment>, ServerError> {
     info!(
-        "Stopping deployment '{}' for project '{}'",
-        deployment_id, project_name
+        "Renewing deployment '{}' for project '{}' to expire in {}",
+        deployment_id, project_name, payload.expires_in
     );
 
     // Find the project by name
@@ -1928,14 +4147,13 @@ pub async fn stop_deployment(
             }
         })?;
 
-    // Check if user has permission to stop deployments (SA access already validated)
+    // Check if user has permission to renew deployments (SA access already validated)
     if !is_sa {
         crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
             .await
             .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
     }
 
-    // Find the specific deployment
     let deployment =
         db_deployments::find_by_deployment_id(&state.db_pool, &deployment_id, project.id)
             .await
@@ -1944,41 +4162,36 @@ pub async fn stop_deployment(
                 ServerError::not_found(format!("Deployment '{}' not found", deployment_id))
             })?;
 
-    // Check if deployment is already in a terminal state
-    if state_machine::is_terminal(&deployment.status) {
+    // Deployments that are finished or being torn down cannot be renewed
+    if state_machine::is_terminal(&deployment.status)
+        || matches!(
+            deployment.status,
+            crate::db::models::DeploymentStatus::Terminating
+                | crate::db::models::DeploymentStatus::Cancelling
+        )
+    {
         return Err(ServerError::bad_request(format!(
-            "Deployment '{}' is already in terminal state: {}",
+            "Deployment '{}' cannot be renewed in state {}",
             deployment_id, deployment.status
         )));
     }
 
-    // Use the appropriate state transition based on current status:
-    // Pre-infrastructure states (Pending, Building, Pushing, Pushed, Deploying) → Cancelling
-    // Infrastructure states (Healthy, Unhealthy) → Terminating
-    let updated_deployment = if state_machine::is_cancellable(&deployment.status) {
-        let d = db_deployments::mark_cancelling(&state.db_pool, deployment.id)
-            .await
-            .internal_err("Failed to cancel deployment")?;
-        info!("Marked deployment {} as Cancelling", deployment_id);
-        d
-    } else {
-        let d = db_deployments::mark_terminating(
-            &state.db_pool,
-            deployment.id,
-            crate::db::models::TerminationReason::UserStopped,
-        )
-        .await
-        .internal_err("Failed to stop deployment")?;
-        info!("Marked deployment {} as Terminating", deployment_id);
-        d
-    };
+    // Renewing extends an expiration; it doesn't give a permanent deployment one
+    if deployment.expires_at.is_none() {
+        return Err(ServerError::bad_request(format!(
+            "Deployment '{}' does not expire and cannot be renewed",
+            deployment_id
+        )));
+    }
 
-    // Update project status
-    projects::update_calculated_status(&state.db_pool, project.id)
-        .await
-        .internal_err("Failed to update project status")?;
+    let expires_at = capped_expiration(Utc::now(), &payload.expires_in, max_expiration(&state)?)?;
 
-    // Trigger Metacontroller resync
+    let updated_deployment =
+        db_deployments::update_expires_at(&state.db_pool, deployment.id, expires_at)
+            .await
+            .internal_err("Failed to renew deployment")?;
+
+    // Trigger Metacontroller resync so the requeue delay follows the new expiration
     if let Some(ref kube_client) = state.kube_client {
         if let Err(e) =
             crate::server::deployment::crd::trigger_resync(kube_client, &project.name).await
@@ -1990,7 +4203,6 @@ pub async fn stop_deployment(
         }
     }
 
-    // Calculate deployment URLs dynamically
     let (primary_url, custom_domain_urls) = match state
         .deployment_backend
         .get_deployment_urls(&updated_deployment, &project)
@@ -2006,7 +4218,6 @@ pub async fn stop_deployment(
         }
     };
 
-    // Get creator email and convert to API model
     let created_by_email =
         get_creator_email(&state.db_pool, updated_deployment.created_by_id).await;
     Ok(Json(
@@ -2095,32 +4306,142 @@ pub async fn get_deployment_by_project(
         }
     };
 
-    let created_by_email = get_creator_email(&state.db_pool, deployment.created_by_id).await;
-    Ok(Json(
-        convert_deployment(
-            &state,
-            deployment,
-            &project,
-            created_by_email,
-            primary_url,
-            custom_domain_urls,
-        )
-        .await,
-    ))
+    let created_by_email = get_creator_email(&state.db_pool, deployment.created_by_id).await;
+    Ok(Json(
+        convert_deployment(
+            &state,
+            deployment,
+            &project,
+            created_by_email,
+            primary_url,
+            custom_domain_urls,
+        )
+        .await,
+    ))
+}
+
+/// GET /projects/{project_name}/deployment-groups - List all deployment groups for a project
+pub async fn list_deployment_groups(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(project_name): Path<String>,
+) -> Result<Json<Vec<String>>, ServerError> {
+    debug!("Listing deployment groups for project: {}", project_name);
+
+    // Find the project by name
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+
+    // Resolve auth for project scope
+    let (_user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
+
+    // Check if user has permission to view deployment groups (SA access already validated)
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
+            .await
+            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+    }
+
+    // Get deployment groups from database
+    let groups = db_deployments::get_all_deployment_groups(&state.db_pool, project.id)
+        .await
+        .internal_err("Failed to list deployment groups")?;
+
+    Ok(Json(groups))
+}
+
+/// Response for listing a deployment's runtime events
+#[derive(Debug, serde::Serialize)]
+pub struct DeploymentEventsResponse {
+    /// Events involving the deployment's workload, oldest first
+    pub events: Vec<super::controller::DeploymentEvent>,
+}
+
+/// List runtime events of a deployment's workload, e.g. Kubernetes `FailedScheduling`
+/// or image pull errors
+///
+/// GET /projects/{project_name}/deployments/{deployment_id}/events
+pub async fn list_deployment_events(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((project_name, deployment_id)): Path<(String, String)>,
+) -> Result<Json<DeploymentEventsResponse>, ServerError> {
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to fetch project")?
+        .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+
+    // Resolve auth for project scope
+    let (_user, is_sa) = auth
+        .resolve_for_project(&state.db_pool, &project)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNAUTHORIZED || e.status == StatusCode::FORBIDDEN {
+                ServerError::not_found(format!("Project '{}' not found", project.name))
+            } else {
+                e
+            }
+        })?;
+
+    // Check permission (SA access already validated)
+    if !is_sa {
+        crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
+            .await?;
+    }
+
+    let deployment = db_deployments::find_by_project_and_deployment_id(
+        &state.db_pool,
+        project.id,
+        &deployment_id,
+    )
+    .await
+    .internal_err("Failed to fetch deployment")?
+    .ok_or_else(|| {
+        ServerError::not_found(format!(
+            "Deployment '{}' not found for project '{}'",
+            deployment_id, project_name
+        ))
+    })?;
+
+    let events = state
+        .deployment_backend
+        .list_events(&deployment, &project)
+        .await
+        .map_err(|e| ServerError::internal_anyhow(e, "Failed to list deployment events"))?;
+
+    Ok(Json(DeploymentEventsResponse { events }))
 }
 
-/// GET /projects/{project_name}/deployment-groups - List all deployment groups for a project
-pub async fn list_deployment_groups(
+/// Response for listing a deployment's lifecycle timeline
+#[derive(Debug, serde::Serialize)]
+pub struct DeploymentHistoryResponse {
+    /// Creation and status changes of the deployment, oldest first
+    pub events: Vec<crate::db::models::DeploymentAuditEvent>,
+}
+
+/// List the lifecycle events (audit trail) of a deployment: who created it and
+/// every status transition, including who stopped it
+///
+/// GET /projects/{project_name}/deployments/{deployment_id}/history
+pub async fn list_deployment_history(
     State(state): State<AppState>,
     auth: AuthContext,
-    Path(project_name): Path<String>,
-) -> Result<Json<Vec<String>>, ServerError> {
-    debug!("Listing deployment groups for project: {}", project_name);
-
-    // Find the project by name
+    Path((project_name, deployment_id)): Path<(String, String)>,
+) -> Result<Json<DeploymentHistoryResponse>, ServerError> {
     let project = projects::find_by_name(&state.db_pool, &project_name)
         .await
-        .internal_err("Failed to find project")?
+        .internal_err("Failed to fetch project")?
         .ok_or_else(|| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
 
     // Resolve auth for project scope
@@ -2135,19 +4456,31 @@ pub async fn list_deployment_groups(
             }
         })?;
 
-    // Check if user has permission to view deployment groups (SA access already validated)
+    // Check permission (SA access already validated)
     if !is_sa {
         crate::server::project::handlers::ensure_project_access_or_admin(&state, &_user, &project)
-            .await
-            .map_err(|_| ServerError::not_found(format!("Project '{}' not found", project_name)))?;
+            .await?;
     }
 
-    // Get deployment groups from database
-    let groups = db_deployments::get_all_deployment_groups(&state.db_pool, project.id)
+    let deployment = db_deployments::find_by_project_and_deployment_id(
+        &state.db_pool,
+        project.id,
+        &deployment_id,
+    )
+    .await
+    .internal_err("Failed to fetch deployment")?
+    .ok_or_else(|| {
+        ServerError::not_found(format!(
+            "Deployment '{}' not found for project '{}'",
+            deployment_id, project_name
+        ))
+    })?;
+
+    let events = crate::db::deployment_events::list_for_deployment(&state.db_pool, deployment.id)
         .await
-        .internal_err("Failed to list deployment groups")?;
+        .internal_err("Failed to list deployment history")?;
 
-    Ok(Json(groups))
+    Ok(Json(DeploymentHistoryResponse { events }))
 }
 
 /// Query parameters for log streaming
@@ -2163,6 +4496,9 @@ pub struct LogStreamParams {
     pub timestamps: bool,
     /// Show logs since this many seconds ago
     pub since: Option<i64>,
+    /// Merge logs of all pods of the deployment instead of a single one
+    #[serde(default)]
+    pub all_pods: bool,
 }
 
 /// Stream logs from a deployment via Server-Sent Events
@@ -2247,6 +4583,7 @@ pub async fn stream_deployment_logs(
             tail,
             params.timestamps,
             params.since,
+            params.all_pods,
         )
         .await
         .map_err(|e| {
@@ -2294,11 +4631,484 @@ pub async fn stream_deployment_logs(
 #[cfg(test)]
 mod tests {
     use super::{
-        normalize_env_override_is_protected, validate_env_override, validate_env_override_key,
+        capped_expiration, effective_project_env_vars, env_key_diff, env_var_diff,
+        group_ttl_expiration, normalize_container_override, normalize_deployment_message,
+        normalize_env_override_is_protected, normalize_extra_ports, normalize_health_check,
+        normalize_image_pull_policy, normalize_ingress_annotations, normalize_init_container,
+        normalize_protocol, normalize_rate_limit, normalize_scheduling, normalize_service_account,
+        normalize_strategy, normalize_volume, normalize_workload_metadata,
+        select_previous_deployment, validate_env_override, validate_env_override_key,
+        validate_extra_ports_http_port, validate_volume_replicas, validate_volume_strategy,
+        ComparableEnvVar, EnvKeyDiff, EnvVarChange, EnvVarDiffEntry, MAX_DEPLOYMENT_MESSAGE_LEN,
     };
     use crate::server::deployment::models::EnvOverride;
     use axum::http::StatusCode;
 
+    #[test]
+    fn deployment_message_is_trimmed_and_blank_is_none() {
+        assert_eq!(
+            normalize_deployment_message(Some("  fix login bug \n")).unwrap(),
+            Some("fix login bug".to_string())
+        );
+        assert_eq!(normalize_deployment_message(Some("   ")).unwrap(), None);
+        assert_eq!(normalize_deployment_message(None).unwrap(), None);
+    }
+
+    #[test]
+    fn health_check_validation_rejects_invalid_values() {
+        use crate::rise_toml::HealthCheckConfig;
+
+        let valid = HealthCheckConfig {
+            readiness_path: Some("/ready".to_string()),
+            period_seconds: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(
+            normalize_health_check(Some(&valid)).unwrap(),
+            Some(serde_json::json!({
+                "readiness_path": "/ready",
+                "liveness_path": null,
+                "initial_delay_seconds": null,
+                "period_seconds": 5,
+                "startup_path": null,
+                "startup_failure_threshold": null,
+                "startup_period_seconds": null,
+                "path": null,
+                "expected_status": null,
+                "timeout": null,
+            }))
+        );
+        assert_eq!(normalize_health_check(None).unwrap(), None);
+
+        for invalid in [
+            HealthCheckConfig {
+                liveness_path: Some("healthz".to_string()),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                initial_delay_seconds: Some(-1),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                period_seconds: Some(0),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                path: Some("healthz".to_string()),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                startup_path: Some("started".to_string()),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                startup_failure_threshold: Some(0),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                startup_period_seconds: Some(0),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                expected_status: Some(vec![]),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                expected_status: Some(vec![1000]),
+                ..Default::default()
+            },
+            HealthCheckConfig {
+                timeout: Some(0),
+                ..Default::default()
+            },
+        ] {
+            let err = normalize_health_check(Some(&invalid)).unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn ingress_annotations_require_kubernetes_controller() {
+        use std::collections::BTreeMap;
+
+        assert_eq!(normalize_ingress_annotations(None, None).unwrap(), None);
+        assert_eq!(
+            normalize_ingress_annotations(None, Some(&BTreeMap::new())).unwrap(),
+            None
+        );
+
+        let entries = BTreeMap::from([(
+            "nginx.ingress.kubernetes.io/proxy-body-size".to_string(),
+            "50m".to_string(),
+        )]);
+        let err = normalize_ingress_annotations(None, Some(&entries)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn strategy_defaults_to_rolling_and_drain_period_requires_blue_green() {
+        use crate::rise_toml::DeploymentStrategy;
+
+        assert_eq!(
+            normalize_strategy(None, None, None).unwrap(),
+            ("rolling".to_string(), None)
+        );
+
+        let err =
+            normalize_strategy(None, Some(DeploymentStrategy::Rolling), Some("10m")).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // Only the Kubernetes controller implements blue-green
+        let err = normalize_strategy(None, Some(DeploymentStrategy::BlueGreen), None).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn tcp_protocol_requires_kubernetes() {
+        use crate::rise_toml::DeploymentProtocol;
+
+        assert_eq!(normalize_protocol(None, None).unwrap(), "http");
+        assert_eq!(
+            normalize_protocol(None, Some(DeploymentProtocol::Http)).unwrap(),
+            "http"
+        );
+
+        // The ECS controller only serves apps through its HTTP load balancer
+        let err = normalize_protocol(None, Some(DeploymentProtocol::Tcp)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert!(err.message.contains("Kubernetes deployment controller"));
+    }
+
+    #[test]
+    fn volume_requires_kubernetes_and_a_single_replica() {
+        use crate::rise_toml::VolumeConfig;
+
+        assert_eq!(normalize_volume(None, None).unwrap(), None);
+
+        // Only the Kubernetes controller creates PersistentVolumeClaims
+        let volume = VolumeConfig {
+            size: "1Gi".to_string(),
+            storage_class: None,
+            mount_path: "/data".to_string(),
+        };
+        let err = normalize_volume(None, Some(&volume)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let stored = serde_json::to_value(&volume).unwrap();
+        assert!(validate_volume_replicas(Some(&stored), 1).is_ok());
+        assert!(validate_volume_replicas(None, 3).is_ok());
+        let err = validate_volume_replicas(Some(&stored), 2).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // The previous deployment can't keep the claim through a drain period
+        assert!(validate_volume_strategy(Some(&stored), "rolling").is_ok());
+        assert!(validate_volume_strategy(None, "blue-green").is_ok());
+        let err = validate_volume_strategy(Some(&stored), "blue-green").unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn image_pull_policy_requires_kubernetes() {
+        use crate::rise_toml::ImagePullPolicy;
+
+        assert_eq!(normalize_image_pull_policy(None, None).unwrap(), None);
+        let err =
+            normalize_image_pull_policy(None, Some(ImagePullPolicy::IfNotPresent)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            ImagePullPolicy::from_db(ImagePullPolicy::IfNotPresent.as_str()),
+            Some(ImagePullPolicy::IfNotPresent)
+        );
+    }
+
+    #[test]
+    fn service_account_is_validated() {
+        use crate::rise_toml::ServiceAccountConfig;
+
+        assert_eq!(normalize_service_account(None, None).unwrap(), None);
+        let message = |name: &str| {
+            normalize_service_account(
+                None,
+                Some(&ServiceAccountConfig {
+                    name: name.to_string(),
+                }),
+            )
+            .unwrap_err()
+            .message
+        };
+        assert!(message("").contains("invalid name"));
+        assert!(message("App").contains("invalid name 'App'"));
+        assert!(message("-app").contains("invalid name"));
+        assert!(message("app..irsa").contains("invalid name"));
+        assert!(message("app_irsa").contains("invalid name"));
+        assert!(message(&"a".repeat(254)).contains("invalid name"));
+        assert!(message("default").contains("'default' is reserved"));
+        assert!(message("env-production").contains("'env-production' is reserved"));
+        assert!(message("env-staging").contains("reserved"));
+        assert!(message("environment").contains("Kubernetes deployment controller"));
+        assert!(message("app.irsa-1").contains("Kubernetes deployment controller"));
+    }
+
+    #[test]
+    fn extra_ports_are_validated() {
+        use crate::rise_toml::PortConfig;
+        use std::collections::BTreeMap;
+
+        assert_eq!(normalize_extra_ports(None, None).unwrap(), None);
+        assert_eq!(
+            normalize_extra_ports(None, Some(&BTreeMap::new())).unwrap(),
+            None
+        );
+        let message = |ports: &[(&str, u16)]| {
+            let ports: BTreeMap<String, PortConfig> = ports
+                .iter()
+                .map(|(name, port)| {
+                    (
+                        name.to_string(),
+                        PortConfig {
+                            port: *port,
+                            service: false,
+                        },
+                    )
+                })
+                .collect();
+            normalize_extra_ports(None, Some(&ports))
+                .unwrap_err()
+                .message
+        };
+        assert!(message(&[("Metrics", 9090)]).contains("invalid port name 'Metrics'"));
+        assert!(message(&[("prometheus-metrics", 9090)]).contains("invalid port name"));
+        assert!(message(&[("9090", 9090)]).contains("invalid port name"));
+        assert!(message(&[("http", 9090)]).contains("reserved"));
+        assert!(message(&[("metrics", 0)]).contains("between 1 and 65535"));
+        assert!(message(&[("admin", 9090), ("metrics", 9090)]).contains("more than once"));
+        assert!(message(&[("metrics", 9090)]).contains("Kubernetes deployment controller"));
+
+        let stored = serde_json::json!({ "metrics": { "port": 9090, "service": true } });
+        assert!(validate_extra_ports_http_port(Some(&stored), 8080).is_ok());
+        assert!(validate_extra_ports_http_port(None, 9090).is_ok());
+        let err = validate_extra_ports_http_port(Some(&stored), 9090).unwrap_err();
+        assert!(err.message.contains("already the deployment's http_port"));
+    }
+
+    #[test]
+    fn rate_limit_is_validated() {
+        use crate::rise_toml::RateLimitConfig;
+
+        assert_eq!(normalize_rate_limit(None, None).unwrap(), None);
+        let message = |rate_limit: RateLimitConfig| {
+            normalize_rate_limit(None, Some(&rate_limit))
+                .unwrap_err()
+                .message
+        };
+        assert!(message(RateLimitConfig::default()).contains("at least one"));
+        assert!(message(RateLimitConfig {
+            rps: Some(0),
+            ..Default::default()
+        })
+        .contains("'rps' must be between 1 and 100000"));
+        assert!(message(RateLimitConfig {
+            rps: Some(10),
+            burst: Some(101),
+            ..Default::default()
+        })
+        .contains("'burst' must be between 1 and 100"));
+        assert!(message(RateLimitConfig {
+            burst: Some(2),
+            connections: Some(20),
+            ..Default::default()
+        })
+        .contains("requires it"));
+        assert!(message(RateLimitConfig {
+            rps: Some(10),
+            burst: Some(3),
+            connections: Some(20),
+        })
+        .contains("Kubernetes deployment controller"));
+    }
+
+    #[test]
+    fn scheduling_requires_kubernetes() {
+        use crate::rise_toml::{AntiAffinity, SchedulingConfig, SpreadAcross};
+
+        assert_eq!(normalize_scheduling(None, None).unwrap(), None);
+
+        let scheduling = SchedulingConfig {
+            spread_across: Some(SpreadAcross::Zone),
+            anti_affinity: Some(AntiAffinity::Required),
+        };
+        let err = normalize_scheduling(None, Some(&scheduling)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            serde_json::to_value(&scheduling).unwrap(),
+            serde_json::json!({ "spread_across": "zone", "anti_affinity": "required" })
+        );
+    }
+
+    #[test]
+    fn workload_metadata_validation_rejects_reserved_and_invalid_keys() {
+        use std::collections::BTreeMap;
+
+        let entries = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        let valid = entries(&[("team", "payments"), ("example.com/cost-center", "1234")]);
+        assert_eq!(
+            normalize_workload_metadata("labels", Some(&valid)).unwrap(),
+            Some(serde_json::json!({"team": "payments", "example.com/cost-center": "1234"}))
+        );
+        assert_eq!(normalize_workload_metadata("labels", None).unwrap(), None);
+        assert_eq!(
+            normalize_workload_metadata("labels", Some(&BTreeMap::new())).unwrap(),
+            None
+        );
+        // Annotation values are free-form
+        assert!(normalize_workload_metadata(
+            "annotations",
+            Some(&entries(&[("example.com/owner", "payments team <a@b.c>")]))
+        )
+        .is_ok());
+
+        for (field, invalid) in [
+            ("labels", entries(&[("rise.dev/project", "other")])),
+            ("annotations", entries(&[("rise.dev/restarted-at", "now")])),
+            ("labels", entries(&[("-team", "payments")])),
+            ("labels", entries(&[("Example.com/team", "payments")])),
+            ("labels", entries(&[("team", "payments team")])),
+            ("annotations", entries(&[("", "x")])),
+        ] {
+            let err = normalize_workload_metadata(field, Some(&invalid)).unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn container_override_validation_omits_empty_lists() {
+        let command = vec!["docker-entrypoint.sh".to_string()];
+        assert_eq!(
+            normalize_container_override("command", Some(&command[..])).unwrap(),
+            Some(command)
+        );
+        assert_eq!(
+            normalize_container_override("command", Some(&[])).unwrap(),
+            None
+        );
+        assert_eq!(normalize_container_override("args", None).unwrap(), None);
+        // Only the executable must be non-empty; empty arguments are legitimate
+        let args = vec!["--name".to_string(), String::new()];
+        assert_eq!(
+            normalize_container_override("args", Some(&args[..])).unwrap(),
+            Some(args)
+        );
+
+        let err = normalize_container_override("command", Some(&[" ".to_string()])).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn init_container_validation_rejects_empty_command() {
+        use crate::rise_toml::InitContainerConfig;
+
+        let valid = InitContainerConfig {
+            command: vec!["./manage.py".to_string(), "migrate".to_string()],
+            image: None,
+        };
+        assert_eq!(
+            normalize_init_container(Some(&valid)).unwrap(),
+            Some(serde_json::json!({
+                "command": ["./manage.py", "migrate"],
+                "image": null,
+            }))
+        );
+
+        for invalid in [
+            InitContainerConfig::default(),
+            InitContainerConfig {
+                command: vec![" ".to_string()],
+                image: None,
+            },
+            InitContainerConfig {
+                command: vec!["migrate".to_string()],
+                image: Some(String::new()),
+            },
+        ] {
+            let err = normalize_init_container(Some(&invalid)).unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn group_ttl_expiration_uses_first_matching_policy() {
+        use crate::server::project::models::GroupTtlPolicy;
+
+        let policy = |pattern: &str, expire: &str| GroupTtlPolicy {
+            pattern: pattern.to_string(),
+            expire: expire.to_string(),
+        };
+        let policies = vec![
+            policy("mr/1??", "1d"),
+            policy("mr/*", "7d"),
+            policy("preview-*", "12h"),
+        ];
+        let now = chrono::Utc::now();
+
+        assert_eq!(
+            group_ttl_expiration(now, &policies, "mr/123").unwrap(),
+            Some(now + chrono::Duration::days(1))
+        );
+        assert_eq!(
+            group_ttl_expiration(now, &policies, "mr/42").unwrap(),
+            Some(now + chrono::Duration::days(7))
+        );
+        assert_eq!(
+            group_ttl_expiration(now, &policies, "preview-login").unwrap(),
+            Some(now + chrono::Duration::hours(12))
+        );
+        assert_eq!(
+            group_ttl_expiration(now, &policies, "default").unwrap(),
+            None
+        );
+        assert_eq!(group_ttl_expiration(now, &policies, "xmr/1").unwrap(), None);
+        assert_eq!(group_ttl_expiration(now, &[], "mr/1").unwrap(), None);
+    }
+
+    #[test]
+    fn capped_expiration_is_clamped_to_max_expiration() {
+        let now = chrono::Utc::now();
+        let week = Some(chrono::Duration::days(7));
+        assert_eq!(
+            capped_expiration(now, "2d", None).unwrap(),
+            now + chrono::Duration::days(2)
+        );
+        assert_eq!(
+            capped_expiration(now, "30d", week).unwrap(),
+            now + chrono::Duration::days(7)
+        );
+        assert_eq!(
+            capped_expiration(now, "12h", week).unwrap(),
+            now + chrono::Duration::hours(12)
+        );
+        assert_eq!(
+            capped_expiration(now, "7x", None).unwrap_err().status,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            capped_expiration(now, "0d", None).unwrap_err().status,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[test]
+    fn deployment_message_rejects_overlong_messages() {
+        let message = "x".repeat(MAX_DEPLOYMENT_MESSAGE_LEN + 1);
+        let err = normalize_deployment_message(Some(&message)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn env_override_key_validation_rejects_empty_keys() {
         assert!(!validate_env_override_key(""));
@@ -2749,4 +5559,192 @@ mod tests {
         assert_eq!(group, "mr/123");
         assert_eq!(env.unwrap().name, "staging");
     }
+
+    fn deployment(
+        deployment_id: &str,
+        status: crate::db::models::DeploymentStatus,
+        age_minutes: i64,
+    ) -> crate::db::models::Deployment {
+        let created_at = chrono::Utc::now() - chrono::Duration::minutes(age_minutes);
+        crate::db::models::Deployment {
+            id: uuid::Uuid::new_v4(),
+            deployment_id: deployment_id.to_string(),
+            project_id: uuid::Uuid::nil(),
+            created_by_id: uuid::Uuid::nil(),
+            status,
+            deployment_group: "default".to_string(),
+            environment_id: None,
+            expires_at: None,
+            termination_reason: None,
+            completed_at: None,
+            error_message: None,
+            build_logs: None,
+            controller_metadata: serde_json::Value::Null,
+            image: None,
+            image_digest: Some("sha256:abc".to_string()),
+            rolled_back_from_deployment_id: None,
+            http_port: 8080,
+            needs_reconcile: false,
+            is_active: false,
+            deploying_started_at: None,
+            first_healthy_at: None,
+            job_url: None,
+            pull_request_url: None,
+            message: None,
+            replicas: 1,
+            cpu: "500m".to_string(),
+            memory: "256Mi".to_string(),
+            cpu_limit: None,
+            memory_limit: None,
+            health_check: None,
+            init_container: None,
+            protocol: "http".to_string(),
+            labels: None,
+            annotations: None,
+            command: None,
+            args: None,
+            ingress_annotations: None,
+            strategy: "rolling".to_string(),
+            drain_period_seconds: None,
+            volume: None,
+            scheduling: None,
+            image_pull_policy: None,
+            rate_limit: None,
+            extra_ports: None,
+            service_account_name: None,
+            restarted_at: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn previous_deployment_skips_failed_and_newer_deployments() {
+        use crate::db::models::DeploymentStatus::*;
+
+        let current = deployment("20260101-0300", Healthy, 30);
+        let deployments = vec![
+            deployment("20260101-0400", Failed, 10),
+            current.clone(),
+            deployment("20260101-0200", Failed, 60),
+            deployment("20260101-0100", Superseded, 90),
+            deployment("20260101-0000", Superseded, 120),
+        ];
+
+        let previous = select_previous_deployment(&deployments, &current).unwrap();
+        assert_eq!(previous.deployment_id, "20260101-0100");
+        assert!(select_previous_deployment(&deployments[..3], &current).is_none());
+    }
+
+    #[test]
+    fn env_key_diff_reports_added_and_removed_keys() {
+        let keys = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            env_key_diff(
+                &keys(&["DATABASE_URL", "FEATURE_X", "PORT"]),
+                &keys(&["PORT", "DATABASE_URL", "LEGACY_MODE"])
+            ),
+            EnvKeyDiff {
+                added: keys(&["LEGACY_MODE"]),
+                removed: keys(&["FEATURE_X"]),
+            }
+        );
+        assert_eq!(
+            env_key_diff(&keys(&["A"]), &keys(&["A"])),
+            EnvKeyDiff::default()
+        );
+    }
+
+    #[test]
+    fn env_var_diff_shows_plain_values_and_hides_secrets() {
+        let var = |key: &str, is_secret: bool, value: &str| ComparableEnvVar {
+            key: key.to_string(),
+            is_secret,
+            value: value.to_string(),
+        };
+        let diff = env_var_diff(
+            vec![
+                var("API_KEY", true, "old-secret"),
+                var("LOG_LEVEL", false, "info"),
+                var("REMOVED", false, "x"),
+                var("TOKEN", true, "same"),
+            ],
+            vec![
+                var("ADDED", true, "new"),
+                var("API_KEY", true, "new-secret"),
+                var("LOG_LEVEL", false, "debug"),
+                var("TOKEN", true, "same"),
+            ],
+        );
+
+        let entry =
+            |key: &str, change, is_secret, from: Option<&str>, to: Option<&str>| EnvVarDiffEntry {
+                key: key.to_string(),
+                change,
+                is_secret,
+                from_value: from.map(String::from),
+                to_value: to.map(String::from),
+            };
+        assert_eq!(
+            diff,
+            vec![
+                entry("ADDED", EnvVarChange::Added, true, None, None),
+                entry("API_KEY", EnvVarChange::Changed, true, None, None),
+                entry(
+                    "LOG_LEVEL",
+                    EnvVarChange::Changed,
+                    false,
+                    Some("info"),
+                    Some("debug")
+                ),
+                entry("REMOVED", EnvVarChange::Removed, false, Some("x"), None),
+                entry("TOKEN", EnvVarChange::Unchanged, true, None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn effective_project_env_vars_prefers_environment_scoped_values() {
+        let staging = uuid::Uuid::new_v4();
+        let production = uuid::Uuid::new_v4();
+        let var = |key: &str, value: &str, environment_id| crate::db::models::ProjectEnvVar {
+            id: uuid::Uuid::new_v4(),
+            project_id: uuid::Uuid::nil(),
+            key: key.to_string(),
+            value: value.to_string(),
+            is_secret: false,
+            is_protected: false,
+            environment_id,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let vars = || {
+            vec![
+                var("DATABASE_URL", "staging-db", Some(staging)),
+                var("DATABASE_URL", "global-db", None),
+                var("LOG_LEVEL", "info", None),
+                var("SENTRY_DSN", "prod-dsn", Some(production)),
+            ]
+        };
+        let values = |vars: Vec<crate::db::models::ProjectEnvVar>| {
+            vars.into_iter()
+                .map(|v| (v.key, v.value))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            values(effective_project_env_vars(vars(), Some(staging))),
+            vec![
+                ("DATABASE_URL".to_string(), "staging-db".to_string()),
+                ("LOG_LEVEL".to_string(), "info".to_string()),
+            ]
+        );
+        assert_eq!(
+            values(effective_project_env_vars(vars(), None)),
+            vec![
+                ("DATABASE_URL".to_string(), "global-db".to_string()),
+                ("LOG_LEVEL".to_string(), "info".to_string()),
+            ]
+        );
+    }
 }
@@ -1,10 +1,12 @@
 pub mod controller;
 #[cfg(feature = "backend")]
 pub mod crd;
+pub mod env_interpolation;
 pub mod handlers;
 #[cfg(feature = "backend")]
 pub mod ip_validator;
 pub mod models;
+pub mod notifier;
 #[cfg(feature = "backend")]
 pub mod quantity;
 #[cfg(feature = "backend")]
@@ -96,10 +96,16 @@ pub struct Deployment {
     pub cpu: String,
     #[serde(default = "default_memory")]
     pub memory: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub job_url: Option<String>, // URL to the CI pipeline/job that created this deployment
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pull_request_url: Option<String>, // URL to the PR/MR associated with this deployment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>, // Human-readable reason for the deployment
     #[serde(default)]
     pub created: String,
     #[serde(default)]
@@ -158,6 +164,46 @@ pub fn normalize_deployment_group(deployment_group: &str) -> String {
         .to_string()
 }
 
+/// Prefix of the labels and annotations Rise manages on workloads. Custom
+/// `[deploy.labels]` / `[deploy.annotations]` entries may not use it.
+pub const RESERVED_METADATA_PREFIX: &str = "rise.dev/";
+
+/// Whether `name` matches the Kubernetes qualified name part:
+/// `([A-Za-z0-9][-A-Za-z0-9_.]*)?[A-Za-z0-9]`, at most 63 characters
+fn is_qualified_name_part(name: &str) -> bool {
+    name.len() <= 63
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Whether `key` is a valid Kubernetes label or annotation key: an optional
+/// DNS subdomain prefix and `/`, followed by a qualified name
+pub fn is_valid_metadata_key(key: &str) -> bool {
+    let (prefix, name) = match key.split_once('/') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, key),
+    };
+    let valid_prefix = prefix.is_none_or(|prefix| {
+        prefix.len() <= 253
+            && prefix.split('.').all(|segment| {
+                segment.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    && segment.ends_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    && segment
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+            })
+    });
+    valid_prefix && is_qualified_name_part(name)
+}
+
+/// Whether `value` is a valid Kubernetes label value (empty or a qualified name part)
+pub fn is_valid_label_value(value: &str) -> bool {
+    value.is_empty() || is_qualified_name_part(value)
+}
+
 /// Generate the Rise system environment variables for a deployment.
 ///
 /// Returns `(key, value)` pairs for:
@@ -219,7 +265,7 @@ pub struct EnvOverride {
 }
 
 // Request to create a deployment
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct CreateDeploymentRequest {
     pub project: String, // Project name
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -251,6 +297,9 @@ pub struct CreateDeploymentRequest {
     /// URL to the pull request/merge request associated with this deployment. Auto-detected from CI environment if not provided.
     #[serde(default)]
     pub pull_request_url: Option<String>,
+    /// Human-readable reason for the deployment (e.g., "fix login bug"). Auto-detected from the CI commit title if not provided.
+    #[serde(default)]
+    pub message: Option<String>,
     /// Number of replicas (overrides rise.toml and platform defaults)
     #[serde(default)]
     pub replicas: Option<u32>,
@@ -260,6 +309,60 @@ pub struct CreateDeploymentRequest {
     /// Memory allocation (e.g., "256Mi", "1Gi") — overrides rise.toml and platform defaults
     #[serde(default)]
     pub memory: Option<String>,
+    /// CPU limit (defaults to `cpu`)
+    #[serde(default)]
+    pub cpu_limit: Option<String>,
+    /// Memory limit (defaults to `memory`)
+    #[serde(default)]
+    pub memory_limit: Option<String>,
+    /// Health probe overrides from rise.toml (unset fields use controller defaults)
+    #[serde(default)]
+    pub health_check: Option<crate::rise_toml::HealthCheckConfig>,
+    /// Init container from rise.toml, run before the app container starts
+    #[serde(default)]
+    pub init_container: Option<crate::rise_toml::InitContainerConfig>,
+    /// Protocol the app speaks (default: http, or the source deployment's on redeploy)
+    #[serde(default)]
+    pub protocol: Option<crate::rise_toml::DeploymentProtocol>,
+    /// Custom labels for the app's pods, from rise.toml `[deploy.labels]`
+    #[serde(default)]
+    pub labels: Option<std::collections::BTreeMap<String, String>>,
+    /// Custom annotations for the app's pods, from rise.toml `[deploy.annotations]`
+    #[serde(default)]
+    pub annotations: Option<std::collections::BTreeMap<String, String>>,
+    /// Override for the app container's command (image entrypoint)
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+    /// Override for the app container's args (image cmd)
+    #[serde(default)]
+    pub args: Option<Vec<String>>,
+    /// Extra annotations for the app's ingresses, from rise.toml `[deploy.ingress.annotations]`
+    #[serde(default)]
+    pub ingress_annotations: Option<std::collections::BTreeMap<String, String>>,
+    /// Deployment strategy (default: rolling, or the source deployment's on redeploy)
+    #[serde(default)]
+    pub strategy: Option<crate::rise_toml::DeploymentStrategy>,
+    /// How long the previous deployment keeps running after a blue-green switch (e.g., '10m')
+    #[serde(default)]
+    pub drain_period: Option<String>,
+    /// Persistent volume for the deployment group, from rise.toml `[deploy.volume]`
+    #[serde(default)]
+    pub volume: Option<crate::rise_toml::VolumeConfig>,
+    /// Replica spreading across nodes or zones, from rise.toml `[deploy.scheduling]`
+    #[serde(default)]
+    pub scheduling: Option<crate::rise_toml::SchedulingConfig>,
+    /// When nodes pull the image, from rise.toml `[deploy] image_pull_policy`
+    #[serde(default)]
+    pub image_pull_policy: Option<crate::rise_toml::ImagePullPolicy>,
+    /// Ingress rate limits, from rise.toml `[deploy.ratelimit]`
+    #[serde(default)]
+    pub ratelimit: Option<crate::rise_toml::RateLimitConfig>,
+    /// Additional named container ports, from rise.toml `[deploy.ports]`
+    #[serde(default)]
+    pub ports: Option<std::collections::BTreeMap<String, crate::rise_toml::PortConfig>>,
+    /// Kubernetes ServiceAccount of the pods, from rise.toml `[deploy.service_account]`
+    #[serde(default)]
+    pub service_account: Option<crate::rise_toml::ServiceAccountConfig>,
 }
 
 // Response from creating a deployment
@@ -273,6 +376,15 @@ pub struct CreateDeploymentResponse {
     pub credentials: crate::server::registry::models::RegistryCredentials,
 }
 
+/// Response from rendering a deployment request without creating it
+#[derive(Debug, Serialize)]
+pub struct DryRunDeploymentResponse {
+    /// Placeholder deployment ID used in resource names
+    pub deployment_id: String,
+    /// Multi-document YAML of the rendered resources (secret values redacted)
+    pub manifests: String,
+}
+
 // Request to update deployment status
 #[derive(Debug, Deserialize)]
 pub struct UpdateDeploymentStatusRequest {
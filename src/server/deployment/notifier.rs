@@ -0,0 +1,293 @@
+//! Deployment status webhooks
+//!
+//! A database trigger enqueues a row in `deployment_notifications` whenever a
+//! deployment of a project with a webhook becomes Healthy, Unhealthy or Failed.
+//! The notifier delivers these rows in the background, so slow or failing
+//! endpoints never block reconciliation. Failed deliveries are retried with
+//! exponential backoff.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::db::leader_leases::LeaderElection;
+use crate::db::models::DeploymentNotification;
+use crate::db::{
+    deployment_notifications as db_notifications, deployments as db_deployments, project_webhooks,
+    projects as db_projects,
+};
+use crate::server::ssrf;
+use crate::server::state::AppState;
+
+/// Event name sent in the `X-Rise-Event` header and the payload
+const EVENT_STATUS_CHANGED: &str = "deployment.status_changed";
+
+/// Deliveries are given up on after this many failed attempts
+const MAX_ATTEMPTS: i32 = 8;
+
+/// Delay before the first retry; doubled on every further attempt
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(15);
+
+/// Upper bound of the retry delay
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+/// Number of notifications delivered per tick
+const BATCH_SIZE: i64 = 20;
+
+/// JSON body POSTed to the project webhook
+#[derive(Debug, Serialize)]
+struct WebhookPayload {
+    event: &'static str,
+    deployment_id: String,
+    project: String,
+    deployment_group: String,
+    status: String,
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_message: Option<String>,
+    /// When the status transition happened
+    timestamp: DateTime<Utc>,
+}
+
+/// Delivers queued deployment status notifications to project webhooks
+pub struct DeploymentNotifier {
+    state: AppState,
+    http_client: reqwest::Client,
+    poll_interval: Duration,
+    election: LeaderElection,
+}
+
+impl DeploymentNotifier {
+    pub fn new(state: AppState) -> Self {
+        let election = LeaderElection::spawn(
+            state.db_pool.clone(),
+            "rise-deployment-notifier",
+            Uuid::new_v4(),
+            Duration::from_secs(60),
+        );
+        let http_client = ssrf::safe_client(&state.server_settings.ssrf);
+        Self {
+            state,
+            http_client,
+            poll_interval: Duration::from_secs(5),
+            election,
+        }
+    }
+
+    /// Start the delivery loop
+    pub fn start(self: Arc<Self>) {
+        tokio::spawn(async move {
+            self.delivery_loop().await;
+        });
+    }
+
+    async fn delivery_loop(&self) {
+        info!("Deployment notifier loop started");
+        let mut ticker = interval(self.poll_interval);
+
+        loop {
+            ticker.tick().await;
+
+            if !self.election.is_leader() {
+                continue;
+            }
+
+            if let Err(e) = self.deliver_due().await {
+                error!("Error in deployment notifier loop: {:#}", e);
+            }
+        }
+    }
+
+    /// Deliver all notifications that are due, concurrently
+    async fn deliver_due(&self) -> Result<()> {
+        let due = db_notifications::list_due(&self.state.db_pool, BATCH_SIZE).await?;
+        if due.is_empty() {
+            return Ok(());
+        }
+        debug!("Delivering {} deployment notifications", due.len());
+
+        futures::future::join_all(due.iter().map(|n| self.process(n))).await;
+        Ok(())
+    }
+
+    async fn process(&self, notification: &DeploymentNotification) {
+        let pool = &self.state.db_pool;
+        let result = match self.deliver(notification).await {
+            Ok(()) => db_notifications::delete(pool, notification.id).await,
+            Err(e) => {
+                let attempts = notification.attempts + 1;
+                if attempts >= MAX_ATTEMPTS {
+                    warn!(
+                        notification_id = notification.id,
+                        "Giving up on deployment notification after {} attempts: {:#}", attempts, e
+                    );
+                    db_notifications::delete(pool, notification.id).await
+                } else {
+                    let delay = retry_delay(notification.attempts);
+                    debug!(
+                        notification_id = notification.id,
+                        "Deployment notification failed, retrying in {:?}: {:#}", delay, e
+                    );
+                    db_notifications::reschedule(
+                        pool,
+                        notification.id,
+                        delay.as_secs_f64(),
+                        &format!("{:#}", e),
+                    )
+                    .await
+                }
+            }
+        };
+
+        if let Err(e) = result {
+            warn!(
+                notification_id = notification.id,
+                "Failed to update deployment notification: {:#}", e
+            );
+        }
+    }
+
+    /// POST the notification to the project webhook.
+    ///
+    /// Returns Ok when the notification was delivered or no longer applies
+    /// (deployment or webhook removed in the meantime).
+    async fn deliver(&self, notification: &DeploymentNotification) -> Result<()> {
+        let pool = &self.state.db_pool;
+        let Some(deployment) = db_deployments::find_by_id(pool, notification.deployment_id).await?
+        else {
+            return Ok(());
+        };
+        let Some(project) = db_projects::find_by_id(pool, deployment.project_id).await? else {
+            return Ok(());
+        };
+        let Some(webhook) = project_webhooks::get(pool, project.id).await? else {
+            return Ok(());
+        };
+
+        let url = match self
+            .state
+            .deployment_backend
+            .get_deployment_urls(&deployment, &project)
+            .await
+        {
+            Ok(urls) => Some(urls.primary_url),
+            Err(e) => {
+                debug!(
+                    deployment_id = %deployment.deployment_id,
+                    "Failed to compute deployment URL for notification: {:#}", e
+                );
+                None
+            }
+        };
+
+        let payload = WebhookPayload {
+            event: EVENT_STATUS_CHANGED,
+            deployment_id: deployment.deployment_id,
+            project: project.name.clone(),
+            deployment_group: deployment.deployment_group,
+            status: notification.status.clone(),
+            url,
+            error_message: deployment.error_message,
+            timestamp: notification.created_at,
+        };
+        let body = serde_json::to_vec(&payload).context("Failed to serialize payload")?;
+
+        // Re-validate on every delivery; DNS may have changed since the URL was set
+        ssrf::validate_url(&webhook.url, &self.state.server_settings.ssrf)
+            .await
+            .map_err(|e| anyhow!("Webhook URL rejected: {}", e))?;
+
+        let mut request = self
+            .http_client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-Rise-Event", EVENT_STATUS_CHANGED);
+
+        if let Some(encrypted_secret) = &webhook.secret {
+            let provider = self.state.encryption_provider.as_ref().ok_or_else(|| {
+                anyhow!("Webhook secret is set but no encryption provider is configured")
+            })?;
+            let secret = provider
+                .decrypt(encrypted_secret)
+                .await
+                .context("Failed to decrypt webhook secret")?;
+            request = request.header(
+                "X-Rise-Signature",
+                format!("sha256={}", hmac_sha256_hex(secret.as_bytes(), &body)),
+            );
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .context("Failed to send webhook request")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            anyhow::bail!("Webhook responded with status {}", status);
+        }
+
+        debug!(
+            project = %project.name,
+            "Delivered '{}' notification", notification.status
+        );
+        Ok(())
+    }
+}
+
+/// Delay before retrying a delivery that has failed `attempts + 1` times
+fn retry_delay(attempts: i32) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.max(0) as u32);
+    INITIAL_RETRY_DELAY
+        .saturating_mul(factor)
+        .min(MAX_RETRY_DELAY)
+}
+
+/// HMAC-SHA256 of `message`, hex-encoded
+fn hmac_sha256_hex(key: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_sha256_matches_rfc4231() {
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Keys longer than the block size are hashed first
+        assert_eq!(
+            hmac_sha256_hex(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn retry_delay_backs_off_exponentially_up_to_the_cap() {
+        assert_eq!(retry_delay(0), Duration::from_secs(15));
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(3), Duration::from_secs(120));
+        assert_eq!(retry_delay(20), MAX_RETRY_DELAY);
+    }
+}
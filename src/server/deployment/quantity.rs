@@ -132,6 +132,7 @@ mod tests {
         assert!(parse_memory_bytes("").is_err());
         assert!(parse_memory_bytes("abc").is_err());
         assert!(parse_memory_bytes("Mi").is_err());
+        assert!(parse_memory_bytes("500mb").is_err());
         // Overflow: u64::MAX Ti would overflow
         assert!(parse_memory_bytes("18446744073709551615Ti").is_err());
     }
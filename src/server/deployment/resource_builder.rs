@@ -9,16 +9,20 @@
 
 use k8s_openapi::api::apps::v1::{Deployment as K8sDeployment, DeploymentSpec};
 use k8s_openapi::api::core::v1::{
-    Capabilities, Container, ContainerPort, EnvFromSource, EnvVar, HTTPGetAction, HostAlias,
-    LocalObjectReference, Namespace, PodSecurityContext, PodSpec, PodTemplateSpec, Probe,
-    ProjectedVolumeSource, ResourceRequirements, SeccompProfile, Secret, SecretEnvSource,
-    SecurityContext, Service, ServiceAccount, ServiceAccountTokenProjection, ServicePort,
-    ServiceSpec, Volume, VolumeMount, VolumeProjection,
+    Affinity, Capabilities, Container, ContainerPort, EnvFromSource, EnvVar, HTTPGetAction,
+    HostAlias, LocalObjectReference, Namespace, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+    PersistentVolumeClaimVolumeSource, PodAffinityTerm, PodAntiAffinity, PodSecurityContext,
+    PodSpec, PodTemplateSpec, Probe, ProjectedVolumeSource, ResourceRequirements, SeccompProfile,
+    Secret, SecretEnvSource, SecurityContext, Service, ServiceAccount,
+    ServiceAccountTokenProjection, ServicePort, ServiceSpec, TCPSocketAction,
+    TopologySpreadConstraint, Volume, VolumeMount, VolumeProjection, VolumeResourceRequirements,
+    WeightedPodAffinityTerm,
 };
 use k8s_openapi::api::networking::v1::{
     HTTPIngressPath, HTTPIngressRuleValue, Ingress, IngressBackend, IngressRule,
     IngressServiceBackend, IngressSpec, NetworkPolicy, NetworkPolicySpec, ServiceBackendPort,
 };
+use k8s_openapi::api::policy::v1::{PodDisruptionBudget, PodDisruptionBudgetSpec};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
 use k8s_openapi::ByteString;
 use std::collections::BTreeMap;
@@ -26,6 +30,11 @@ use std::sync::Arc;
 use tracing::warn;
 
 use crate::db::models::{CustomDomain, Deployment, Project};
+use crate::rise_toml::{
+    AntiAffinity, DeploymentProtocol, HealthCheckConfig, ImagePullPolicy, InitContainerConfig,
+    PortConfig, RateLimitConfig, SchedulingConfig, SpreadAcross, VolumeConfig,
+};
+use crate::server::custom_domains::validation;
 use crate::server::registry::{
     models::{RegistryAuthMethod, RegistryCredentials},
     RegistryProvider,
@@ -41,10 +50,42 @@ pub const LABEL_DEPLOYMENT_UUID: &str = "rise.dev/deployment-uuid";
 pub const LABEL_ENVIRONMENT: &str = "rise.dev/environment";
 pub const ANNOTATION_LAST_REFRESH: &str = "rise.dev/last-refresh";
 pub const ANNOTATION_ENV_SECRET_HASH: &str = "rise.dev/env-secret-hash";
-pub const IMAGE_PULL_SECRET_NAME: &str = "rise-registry-creds";
+/// Pod template annotation bumped by `rise deployment restart` to roll the pods
+pub const ANNOTATION_RESTARTED_AT: &str = "rise.dev/restarted-at";
+/// cert-manager annotation selecting the ClusterIssuer for an ingress's certificates
+const ANNOTATION_CLUSTER_ISSUER: &str = "cert-manager.io/cluster-issuer";
+/// ingress-nginx rate limit annotations, set from `[deploy.ratelimit]`
+const ANNOTATION_LIMIT_RPS: &str = "nginx.ingress.kubernetes.io/limit-rps";
+const ANNOTATION_LIMIT_BURST_MULTIPLIER: &str =
+    "nginx.ingress.kubernetes.io/limit-burst-multiplier";
+const ANNOTATION_LIMIT_CONNECTIONS: &str = "nginx.ingress.kubernetes.io/limit-connections";
+/// Ingress annotations Rise manages for auth, path rewriting, HTTPS and rate limits.
+/// Projects can never set them via `[deploy.ingress.annotations]`, even if they are
+/// allowlisted.
+pub const RESERVED_INGRESS_ANNOTATIONS: &[&str] = &[
+    "nginx.ingress.kubernetes.io/auth-url",
+    "nginx.ingress.kubernetes.io/auth-signin",
+    "nginx.ingress.kubernetes.io/auth-response-headers",
+    "nginx.ingress.kubernetes.io/rewrite-target",
+    "nginx.ingress.kubernetes.io/x-forwarded-prefix",
+    "nginx.ingress.kubernetes.io/ssl-redirect",
+    "nginx.ingress.kubernetes.io/force-ssl-redirect",
+    "nginx.ingress.kubernetes.io/hsts",
+    "nginx.ingress.kubernetes.io/hsts-max-age",
+    ANNOTATION_CLUSTER_ISSUER,
+    ANNOTATION_LIMIT_RPS,
+    ANNOTATION_LIMIT_BURST_MULTIPLIER,
+    ANNOTATION_LIMIT_CONNECTIONS,
+];
 
 const EXTRA_SERVICE_TOKENS_VOLUME_NAME: &str = "rise-extra-service-tokens";
 const EXTRA_SERVICE_TOKENS_MOUNT_PATH: &str = "/var/run/secrets/rise/tokens";
+const DATA_VOLUME_NAME: &str = "data";
+const TOPOLOGY_KEY_HOSTNAME: &str = "kubernetes.io/hostname";
+const TOPOLOGY_KEY_ZONE: &str = "topology.kubernetes.io/zone";
+/// Set by the Deployment controller per ReplicaSet. Used as `matchLabelKeys` so
+/// replica spreading ignores the old pods a rollout is replacing.
+const POD_TEMPLATE_HASH_LABEL: &str = "pod-template-hash";
 
 /// Container waiting state reasons that indicate irrecoverable errors
 pub const IRRECOVERABLE_CONTAINER_REASONS: &[&str] = &[
@@ -63,6 +104,7 @@ pub const IRRECOVERABLE_CONTAINER_REASONS: &[&str] = &[
 enum ProbeType {
     Liveness,
     Readiness,
+    Startup,
 }
 
 /// Parsed ingress URL components
@@ -87,34 +129,165 @@ pub struct ResourceBuilder {
     pub namespace_labels: std::collections::HashMap<String, String>,
     pub namespace_annotations: std::collections::HashMap<String, String>,
     pub ingress_annotations: std::collections::HashMap<String, String>,
+    pub allowed_ingress_annotations: Vec<String>,
     pub ingress_tls_secret_name: Option<String>,
     pub custom_domain_tls_mode: crate::server::settings::CustomDomainTlsMode,
+    pub acme_cluster_issuer: Option<String>,
     pub custom_domain_ingress_annotations: std::collections::HashMap<String, String>,
     pub node_selector: std::collections::HashMap<String, String>,
     pub image_pull_secret_name: Option<String>,
+    /// Name of the controller-managed pull secret, used when `image_pull_secret_name` is unset
+    pub managed_image_pull_secret_name: String,
     pub access_classes: std::collections::HashMap<String, crate::server::settings::AccessClass>,
     pub host_aliases: std::collections::HashMap<String, String>,
     pub extra_service_token_audiences: std::collections::HashMap<String, String>,
     pub use_default_service_account_for_production: bool,
+    /// Create the ServiceAccounts named in `[deploy.service_account]`
+    pub create_deployment_service_accounts: bool,
     pub network_policy: crate::server::settings::NetworkPolicyConfig,
     pub pod_security_enabled: bool,
     pub health_probes: Option<crate::server::settings::HealthProbeConfig>,
     pub namespace_format: String,
+    pub revision_history_limit: i32,
+    pub termination_grace_period_secs: Option<u32>,
+    pub pod_disruption_budget_min_available: Option<u32>,
+    pub cleanup_terminal_replica_sets: bool,
+    pub in_flight_resync_secs: f64,
+    pub tcp_service_type: crate::server::settings::TcpServiceType,
+}
+
+/// Format a namespace name using the given format string, project name and owning team.
+///
+/// `{team}` is replaced with the team name, lowercased and with characters not
+/// allowed in namespace names replaced by `-`, or with `user` for projects owned
+/// by a user.
+pub fn format_namespace_name(format: &str, project_name: &str, team_name: Option<&str>) -> String {
+    let team = match team_name {
+        Some(name) => name
+            .to_lowercase()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect(),
+        None => "user".to_string(),
+    };
+    format
+        .replace("{project_name}", project_name)
+        .replace("{team}", &team)
 }
 
-/// Format a namespace name using the given format string and project name.
+/// Whether `name` is a valid Kubernetes namespace name (RFC 1123 label)
+pub fn is_valid_namespace_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 63
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+}
+
+/// Resolve the ingress URL (host and optional path, without scheme) of a deployment group.
 ///
-/// Standalone function for use in contexts where a `ResourceBuilder` instance
-/// is not available (e.g., CRD backfill at startup).
-pub fn format_namespace_name(format: &str, project_name: &str) -> String {
-    format.replace("{project_name}", project_name)
+/// Standalone function so backends without a `ResourceBuilder` (e.g., ECS) resolve
+/// URLs exactly like the Kubernetes ingresses.
+pub fn format_group_ingress_url(
+    production_template: &str,
+    staging_template: Option<&str>,
+    project_name: &str,
+    deployment_group: &str,
+) -> String {
+    if deployment_group == crate::server::deployment::models::DEFAULT_DEPLOYMENT_GROUP {
+        production_template.replace("{project_name}", project_name)
+    } else if let Some(staging_template) = staging_template {
+        staging_template
+            .replace("{project_name}", project_name)
+            .replace(
+                "{deployment_group}",
+                &ResourceBuilder::escaped_group_name(deployment_group),
+            )
+    } else {
+        let base_url = production_template.replace("{project_name}", project_name);
+        if let Some(dot_pos) = base_url.find('.') {
+            format!(
+                "{}-{}{}",
+                &base_url[..dot_pos],
+                ResourceBuilder::escaped_group_name(deployment_group),
+                &base_url[dot_pos..]
+            )
+        } else {
+            format!(
+                "{}-{}",
+                base_url,
+                ResourceBuilder::escaped_group_name(deployment_group)
+            )
+        }
+    }
 }
 
 impl ResourceBuilder {
     // ── Naming helpers ─────────────────────────────────────────────────
 
+    /// Namespace of a project: the one stored at creation, or the configured format for
+    /// projects whose namespace has not been backfilled yet
     pub fn namespace_name(&self, project: &Project) -> String {
-        format_namespace_name(&self.namespace_format, &project.name)
+        match &project.namespace {
+            Some(namespace) => namespace.clone(),
+            None => format_namespace_name(&self.namespace_format, &project.name, None),
+        }
+    }
+
+    /// Resolve the namespace of a new project from the configured format
+    pub fn resolve_namespace_name(
+        &self,
+        project_name: &str,
+        team_name: Option<&str>,
+    ) -> Result<String, String> {
+        let namespace = format_namespace_name(&self.namespace_format, project_name, team_name);
+        if !is_valid_namespace_name(&namespace) {
+            return Err(format!(
+                "Namespace '{}' resolved from namespace_format is not a valid Kubernetes namespace name \
+                 (at most 63 lowercase alphanumeric characters or '-', starting and ending with an alphanumeric character)",
+                namespace
+            ));
+        }
+        Ok(namespace)
+    }
+
+    /// Store the namespace of projects created before namespaces were stored, using
+    /// the configured format
+    pub async fn backfill_project_namespaces(&self, pool: &sqlx::PgPool) -> anyhow::Result<()> {
+        let projects = crate::db::projects::list(pool, None).await?;
+        let mut backfilled = 0u32;
+        for project in projects.iter().filter(|p| p.namespace.is_none()) {
+            let team_name = match project.owner_team_id {
+                Some(team_id) => crate::db::teams::find_by_id(pool, team_id)
+                    .await?
+                    .map(|team| team.name),
+                None => None,
+            };
+            let namespace = match self.resolve_namespace_name(&project.name, team_name.as_deref()) {
+                Ok(namespace) => namespace,
+                Err(e) => {
+                    warn!(project = %project.name, "Skipping namespace backfill: {}", e);
+                    continue;
+                }
+            };
+            if let Err(e) = crate::db::projects::set_namespace(pool, project.id, &namespace).await {
+                warn!(project = %project.name, "Failed to backfill namespace: {:#}", e);
+                continue;
+            }
+            backfilled += 1;
+        }
+        if backfilled > 0 {
+            tracing::info!("Backfilled the namespace of {} project(s)", backfilled);
+        }
+        Ok(())
     }
 
     pub fn sanitize_label_value(value: &str) -> String {
@@ -156,6 +329,77 @@ impl ResourceBuilder {
         Self::escaped_group_name(&deployment.deployment_group)
     }
 
+    pub fn pod_disruption_budget_name(project: &Project, deployment: &Deployment) -> String {
+        Self::deployment_name(project, deployment)
+    }
+
+    /// PersistentVolumeClaim name; shared by all deployments of a group
+    pub fn persistent_volume_claim_name(_project: &Project, deployment: &Deployment) -> String {
+        format!(
+            "{}-data",
+            Self::escaped_group_name(&deployment.deployment_group)
+        )
+    }
+
+    /// The deployment's persistent volume (`[deploy.volume]`), if it declares one
+    pub fn volume(deployment: &Deployment) -> Option<VolumeConfig> {
+        deployment
+            .volume
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// The deployment's replica spreading (`[deploy.scheduling]`), if it declares one
+    pub fn scheduling(deployment: &Deployment) -> Option<SchedulingConfig> {
+        deployment
+            .scheduling
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// Image pull policy of the app container: the configured one, otherwise
+    /// `IfNotPresent` for digest-pinned images (their content can't change) and
+    /// `Always` for tags, which may be pushed again
+    pub fn image_pull_policy(deployment: &Deployment) -> ImagePullPolicy {
+        match Self::configured_image_pull_policy(deployment) {
+            Some(policy) => policy,
+            None if deployment.image_digest.is_some() => ImagePullPolicy::IfNotPresent,
+            None => ImagePullPolicy::Always,
+        }
+    }
+
+    /// The deployment's `image_pull_policy` from rise.toml, if it sets one
+    fn configured_image_pull_policy(deployment: &Deployment) -> Option<ImagePullPolicy> {
+        deployment
+            .image_pull_policy
+            .as_deref()
+            .and_then(ImagePullPolicy::from_db)
+    }
+
+    /// Additional named ports from `[deploy.ports]`, sorted by name
+    fn extra_ports(deployment: &Deployment) -> BTreeMap<String, PortConfig> {
+        deployment
+            .extra_ports
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether the deployment serves plain TCP (no Ingress, TCP probes)
+    pub fn is_tcp(deployment: &Deployment) -> bool {
+        DeploymentProtocol::from_db(&deployment.protocol) == DeploymentProtocol::Tcp
+    }
+
+    /// In-cluster address of a TCP deployment's Service
+    pub fn tcp_service_url(&self, project: &Project, deployment: &Deployment) -> String {
+        format!(
+            "tcp://{}.{}.svc.cluster.local:{}",
+            Self::service_name(project, deployment),
+            self.namespace_name(project),
+            deployment.http_port
+        )
+    }
+
     pub fn environment_service_account_name(environment_name: &str) -> String {
         format!("env-{}", environment_name)
     }
@@ -184,35 +428,12 @@ impl ResourceBuilder {
         project: &Project,
         deployment_group: &str,
     ) -> String {
-        if deployment_group == crate::server::deployment::models::DEFAULT_DEPLOYMENT_GROUP {
-            self.production_ingress_url_template
-                .replace("{project_name}", &project.name)
-        } else if let Some(ref staging_template) = self.staging_ingress_url_template {
-            staging_template
-                .replace("{project_name}", &project.name)
-                .replace(
-                    "{deployment_group}",
-                    &Self::escaped_group_name(deployment_group),
-                )
-        } else {
-            let base_url = self
-                .production_ingress_url_template
-                .replace("{project_name}", &project.name);
-            if let Some(dot_pos) = base_url.find('.') {
-                format!(
-                    "{}-{}{}",
-                    &base_url[..dot_pos],
-                    Self::escaped_group_name(deployment_group),
-                    &base_url[dot_pos..]
-                )
-            } else {
-                format!(
-                    "{}-{}",
-                    base_url,
-                    Self::escaped_group_name(deployment_group)
-                )
-            }
-        }
+        format_group_ingress_url(
+            &self.production_ingress_url_template,
+            self.staging_ingress_url_template.as_deref(),
+            &project.name,
+            deployment_group,
+        )
     }
 
     pub fn resolved_environment_url(
@@ -263,8 +484,6 @@ impl ResourceBuilder {
         &self,
         custom_domains: &[CustomDomain],
     ) -> Vec<CustomDomain> {
-        use crate::server::custom_domains::validation;
-
         custom_domains
             .iter()
             .filter(|domain| {
@@ -300,6 +519,16 @@ impl ResourceBuilder {
         environment: Option<&crate::db::models::Environment>,
         custom_domains: &[CustomDomain],
     ) -> super::controller::DeploymentUrls {
+        // TCP deployments have no Ingress; they are only reachable through their Service
+        if Self::is_tcp(deployment) {
+            let url = self.tcp_service_url(project, deployment);
+            return super::controller::DeploymentUrls {
+                default_url: url.clone(),
+                primary_url: url,
+                custom_domain_urls: Vec::new(),
+            };
+        }
+
         let default_url_host = self.full_ingress_url(project, deployment);
         let default_url = format!("{}://{}", self.ingress_schema, default_url_host);
 
@@ -343,6 +572,7 @@ impl ResourceBuilder {
 
             let urls: Vec<String> = custom_domains
                 .iter()
+                .filter(|domain| !validation::is_wildcard(&domain.domain))
                 .map(|domain| {
                     let url_host = if let Some(port) = self.ingress_port {
                         format!("{}:{}", domain.domain, port)
@@ -395,6 +625,7 @@ impl ResourceBuilder {
 
                 let urls: Vec<String> = custom_domains
                     .iter()
+                    .filter(|domain| !validation::is_wildcard(&domain.domain))
                     .map(|domain| {
                         let url_host = if let Some(port) = self.ingress_port {
                             format!("{}:{}", domain.domain, port)
@@ -434,6 +665,44 @@ impl ResourceBuilder {
         labels
     }
 
+    /// Merge the deployment's custom labels or annotations (`[deploy.labels]` /
+    /// `[deploy.annotations]`) under `managed`; Rise's own keys always win.
+    fn with_custom_metadata(
+        mut managed: BTreeMap<String, String>,
+        custom: Option<&serde_json::Value>,
+    ) -> BTreeMap<String, String> {
+        let custom: BTreeMap<String, String> = custom
+            .cloned()
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        for (key, value) in custom {
+            if !key.starts_with(super::models::RESERVED_METADATA_PREFIX) {
+                managed.entry(key).or_insert(value);
+            }
+        }
+        managed
+    }
+
+    /// Pod template annotations; a change in either Rise value rolls the pods
+    fn pod_template_annotations(
+        secret_env_hash: Option<String>,
+        restarted_at: Option<chrono::DateTime<chrono::Utc>>,
+        custom_annotations: Option<&serde_json::Value>,
+    ) -> Option<BTreeMap<String, String>> {
+        let mut annotations = BTreeMap::new();
+        if let Some(hash) = secret_env_hash {
+            annotations.insert(ANNOTATION_ENV_SECRET_HASH.to_string(), hash);
+        }
+        if let Some(restarted_at) = restarted_at {
+            annotations.insert(
+                ANNOTATION_RESTARTED_AT.to_string(),
+                restarted_at.to_rfc3339(),
+            );
+        }
+        let annotations = Self::with_custom_metadata(annotations, custom_annotations);
+        (!annotations.is_empty()).then_some(annotations)
+    }
+
     pub fn group_labels(
         project: &Project,
         deployment: &Deployment,
@@ -512,6 +781,26 @@ impl ResourceBuilder {
         }
     }
 
+    /// ServiceAccount named in a deployment's `[deploy.service_account]`, created when
+    /// `create_deployment_service_accounts` is enabled
+    pub fn create_deployment_service_account(
+        &self,
+        project: &Project,
+        name: &str,
+        namespace: &str,
+        environment_name: Option<&str>,
+    ) -> ServiceAccount {
+        ServiceAccount {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                namespace: Some(namespace.to_string()),
+                labels: Some(Self::common_labels(project, environment_name)),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
     pub fn create_dockerconfigjson_secret(
         &self,
         name: &str,
@@ -605,6 +894,14 @@ impl ResourceBuilder {
         http_port: u16,
         environment_name: Option<&str>,
     ) -> Service {
+        // HTTP apps are reached through the Ingress on port 80; TCP apps are reached
+        // directly on their own port
+        let (service_type, port_name, port) = if Self::is_tcp(deployment) {
+            (self.tcp_service_type.as_str(), "tcp", http_port as i32)
+        } else {
+            ("ClusterIP", "http", 80)
+        };
+
         Service {
             metadata: ObjectMeta {
                 name: Some(Self::service_name(project, deployment)),
@@ -613,23 +910,42 @@ impl ResourceBuilder {
                 ..Default::default()
             },
             spec: Some(ServiceSpec {
-                type_: Some("ClusterIP".to_string()),
+                type_: Some(service_type.to_string()),
                 selector: Some(Self::deployment_labels(
                     project,
                     deployment,
                     environment_name,
                 )),
-                ports: Some(vec![ServicePort {
-                    name: Some("http".to_string()),
-                    port: 80,
-                    target_port: Some(
-                        k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(
-                            http_port as i32,
+                ports: Some(
+                    std::iter::once(ServicePort {
+                        name: Some(port_name.to_string()),
+                        port,
+                        target_port: Some(
+                            k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(
+                                http_port as i32,
+                            ),
                         ),
-                    ),
-                    protocol: Some("TCP".to_string()),
-                    ..Default::default()
-                }]),
+                        protocol: Some("TCP".to_string()),
+                        ..Default::default()
+                    })
+                    .chain(
+                        Self::extra_ports(deployment)
+                            .into_iter()
+                            .filter(|(_, config)| config.service)
+                            .map(|(name, config)| ServicePort {
+                                port: config.port as i32,
+                                target_port: Some(
+                                    k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::String(
+                                        name.clone(),
+                                    ),
+                                ),
+                                name: Some(name),
+                                protocol: Some("TCP".to_string()),
+                                ..Default::default()
+                            }),
+                    )
+                    .collect(),
+                ),
                 ..Default::default()
             }),
             ..Default::default()
@@ -760,6 +1076,92 @@ impl ResourceBuilder {
         }
     }
 
+    /// PodDisruptionBudget keeping `minAvailable` pods of a deployment up during
+    /// voluntary disruptions (node drains). Returns `None` for single-replica
+    /// deployments, where any budget would either block drains or be a no-op.
+    pub fn create_pod_disruption_budget(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        namespace: &str,
+        environment_name: Option<&str>,
+    ) -> Option<PodDisruptionBudget> {
+        if deployment.replicas <= 1 {
+            return None;
+        }
+
+        let max_min_available = deployment.replicas - 1;
+        let min_available = self
+            .pod_disruption_budget_min_available
+            .map(|n| i32::try_from(n).unwrap_or(i32::MAX).min(max_min_available))
+            .unwrap_or(max_min_available);
+
+        Some(PodDisruptionBudget {
+            metadata: ObjectMeta {
+                name: Some(Self::pod_disruption_budget_name(project, deployment)),
+                namespace: Some(namespace.to_string()),
+                labels: Some(Self::deployment_labels(
+                    project,
+                    deployment,
+                    environment_name,
+                )),
+                ..Default::default()
+            },
+            spec: Some(PodDisruptionBudgetSpec {
+                min_available: Some(
+                    k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(min_available),
+                ),
+                selector: Some(LabelSelector {
+                    match_labels: Some(Self::deployment_labels(
+                        project,
+                        deployment,
+                        environment_name,
+                    )),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    /// `ReadWriteOnce` PersistentVolumeClaim backing the deployment group's volume.
+    ///
+    /// The claim carries group labels only, so it is shared across redeploys of
+    /// the group. Returns `None` if the deployment declares no volume.
+    pub fn create_persistent_volume_claim(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+        namespace: &str,
+        environment_name: Option<&str>,
+    ) -> Option<PersistentVolumeClaim> {
+        use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+
+        let volume = Self::volume(deployment)?;
+        Some(PersistentVolumeClaim {
+            metadata: ObjectMeta {
+                name: Some(Self::persistent_volume_claim_name(project, deployment)),
+                namespace: Some(namespace.to_string()),
+                labels: Some(Self::group_labels(project, deployment, environment_name)),
+                ..Default::default()
+            },
+            spec: Some(PersistentVolumeClaimSpec {
+                access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+                storage_class_name: volume.storage_class,
+                resources: Some(VolumeResourceRequirements {
+                    requests: Some(BTreeMap::from([(
+                        "storage".to_string(),
+                        Quantity(volume.size),
+                    )])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
     // ── Pod security & resources ───────────────────────────────────────
 
     fn create_pod_security_context(&self) -> Option<PodSecurityContext> {
@@ -794,35 +1196,53 @@ impl ResourceBuilder {
 
     fn create_resource_requirements(
         &self,
-        cpu: &str,
-        memory: &str,
+        deployment: &Deployment,
     ) -> Option<ResourceRequirements> {
         use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 
-        // Single-knob: same value for both request and limit
+        // Limits default to the requested values
+        let cpu_limit = deployment.cpu_limit.as_ref().unwrap_or(&deployment.cpu);
+        let memory_limit = deployment
+            .memory_limit
+            .as_ref()
+            .unwrap_or(&deployment.memory);
         Some(ResourceRequirements {
             requests: Some({
                 let mut map = BTreeMap::new();
-                map.insert("cpu".to_string(), Quantity(cpu.to_string()));
-                map.insert("memory".to_string(), Quantity(memory.to_string()));
+                map.insert("cpu".to_string(), Quantity(deployment.cpu.clone()));
+                map.insert("memory".to_string(), Quantity(deployment.memory.clone()));
                 map
             }),
             limits: Some({
                 let mut map = BTreeMap::new();
-                map.insert("cpu".to_string(), Quantity(cpu.to_string()));
-                map.insert("memory".to_string(), Quantity(memory.to_string()));
+                map.insert("cpu".to_string(), Quantity(cpu_limit.clone()));
+                map.insert("memory".to_string(), Quantity(memory_limit.clone()));
                 map
             }),
             ..Default::default()
         })
     }
 
-    fn create_http_probe(&self, port: i32, probe_type: ProbeType) -> Option<Probe> {
+    /// Build a probe, applying the deployment's `health_check` overrides on top
+    /// of the controller's `health_probes` configuration. TCP deployments get a TCP
+    /// socket probe instead of an HTTP GET, ignoring the configured paths.
+    ///
+    /// The startup probe is only created when the deployment sets a `startup_*`
+    /// field; its path defaults to the liveness path.
+    fn create_probe(
+        &self,
+        port: i32,
+        probe_type: ProbeType,
+        overrides: &HealthCheckConfig,
+        tcp: bool,
+    ) -> Option<Probe> {
         let config = self.health_probes.as_ref().cloned().unwrap_or_else(|| {
             crate::server::settings::HealthProbeConfig {
                 liveness_enabled: true,
                 readiness_enabled: true,
                 path: "/".to_string(),
+                readiness_path: None,
+                liveness_path: None,
                 initial_delay_seconds: 10,
                 period_seconds: 10,
                 timeout_seconds: 5,
@@ -830,34 +1250,72 @@ impl ResourceBuilder {
             }
         });
 
-        let enabled = match probe_type {
-            ProbeType::Liveness => config.liveness_enabled,
-            ProbeType::Readiness => config.readiness_enabled,
+        let (enabled, path) = match probe_type {
+            ProbeType::Liveness => (
+                config.liveness_enabled,
+                overrides
+                    .liveness_path
+                    .as_ref()
+                    .or(config.liveness_path.as_ref()),
+            ),
+            ProbeType::Readiness => (
+                config.readiness_enabled,
+                overrides
+                    .readiness_path
+                    .as_ref()
+                    .or(config.readiness_path.as_ref()),
+            ),
+            ProbeType::Startup => (
+                overrides.has_startup_probe(),
+                overrides
+                    .startup_path
+                    .as_ref()
+                    .or(overrides.liveness_path.as_ref())
+                    .or(config.liveness_path.as_ref()),
+            ),
         };
 
         if !enabled {
             return None;
         }
 
-        let path = if config.path.is_empty() || !config.path.starts_with('/') {
-            warn!(
-                "Invalid health probe path '{}', using default '/'",
-                config.path
-            );
-            "/".to_string()
+        let port = k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(port);
+        let (http_get, tcp_socket) = if tcp {
+            (
+                None,
+                Some(TCPSocketAction {
+                    port,
+                    ..Default::default()
+                }),
+            )
         } else {
-            config.path.clone()
+            (
+                Some(Self::http_get_action(path.unwrap_or(&config.path), port)),
+                None,
+            )
         };
 
-        Some(Probe {
-            http_get: Some(HTTPGetAction {
-                path: Some(path),
-                port: k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(port),
-                scheme: Some("HTTP".to_string()),
+        if let ProbeType::Startup = probe_type {
+            return Some(Probe {
+                http_get,
+                tcp_socket,
+                period_seconds: Some(overrides.startup_period_seconds.unwrap_or(10)),
+                timeout_seconds: Some(config.timeout_seconds),
+                failure_threshold: Some(overrides.startup_failure_threshold.unwrap_or(30)),
+                success_threshold: Some(1),
                 ..Default::default()
-            }),
-            initial_delay_seconds: Some(config.initial_delay_seconds),
-            period_seconds: Some(config.period_seconds),
+            });
+        }
+
+        Some(Probe {
+            http_get,
+            tcp_socket,
+            initial_delay_seconds: Some(
+                overrides
+                    .initial_delay_seconds
+                    .unwrap_or(config.initial_delay_seconds),
+            ),
+            period_seconds: Some(overrides.period_seconds.unwrap_or(config.period_seconds)),
             timeout_seconds: Some(config.timeout_seconds),
             failure_threshold: Some(config.failure_threshold),
             success_threshold: Some(1),
@@ -865,6 +1323,25 @@ impl ResourceBuilder {
         })
     }
 
+    fn http_get_action(
+        path: &str,
+        port: k8s_openapi::apimachinery::pkg::util::intstr::IntOrString,
+    ) -> HTTPGetAction {
+        let path = if path.is_empty() || !path.starts_with('/') {
+            warn!("Invalid health probe path '{}', using default '/'", path);
+            "/".to_string()
+        } else {
+            path.to_string()
+        };
+
+        HTTPGetAction {
+            path: Some(path),
+            port,
+            scheme: Some("HTTP".to_string()),
+            ..Default::default()
+        }
+    }
+
     fn create_extra_service_token_volume(&self) -> Option<Volume> {
         if self.extra_service_token_audiences.is_empty() {
             return None;
@@ -928,27 +1405,103 @@ impl ResourceBuilder {
         service_account_name: Option<String>,
         environment_name: Option<&str>,
     ) -> K8sDeployment {
-        let volumes = self
+        let data_volume = Self::volume(deployment);
+        // A single replica has nothing to spread
+        let scheduling = Self::scheduling(deployment).filter(|_| deployment.replicas > 1);
+        let affinity = scheduling
+            .as_ref()
+            .and_then(|scheduling| {
+                Self::replica_pod_anti_affinity(project, deployment, environment_name, scheduling)
+            })
+            .map(|anti_affinity| Affinity {
+                pod_anti_affinity: Some(anti_affinity),
+                ..Default::default()
+            });
+        let volumes: Vec<Volume> = self
             .create_extra_service_token_volume()
-            .map(|volume| vec![volume]);
-        let volume_mounts = self
+            .into_iter()
+            .chain(data_volume.as_ref().map(|_| Volume {
+                name: DATA_VOLUME_NAME.to_string(),
+                persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+                    claim_name: Self::persistent_volume_claim_name(project, deployment),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }))
+            .collect();
+        let volumes = (!volumes.is_empty()).then_some(volumes);
+        let volume_mounts: Vec<VolumeMount> = self
             .create_extra_service_token_volume_mount()
-            .map(|mount| vec![mount]);
+            .into_iter()
+            .chain(data_volume.as_ref().map(|volume| VolumeMount {
+                name: DATA_VOLUME_NAME.to_string(),
+                mount_path: volume.mount_path.clone(),
+                ..Default::default()
+            }))
+            .collect();
+        let volume_mounts = (!volume_mounts.is_empty()).then_some(volume_mounts);
+        let health_check: HealthCheckConfig = deployment
+            .health_check
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        let init_container: Option<InitContainerConfig> = deployment
+            .init_container
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok());
+        let env_from = secret_env_name.map(|name| {
+            vec![EnvFromSource {
+                secret_ref: Some(SecretEnvSource {
+                    name,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }]
+        });
+        let env = (!env_vars.is_empty()).then_some(env_vars);
+        // Runs with the app's environment so migrations see the same configuration
+        let image_pull_policy = Self::image_pull_policy(deployment);
+        let init_containers = init_container.map(|init| {
+            // A separate init image is referenced by tag, so it isn't covered by the digest pin
+            let init_pull_policy = match init.image {
+                Some(_) => Self::configured_image_pull_policy(deployment)
+                    .unwrap_or(ImagePullPolicy::Always),
+                None => image_pull_policy,
+            };
+            vec![Container {
+                name: "init".to_string(),
+                image: Some(init.image.unwrap_or_else(|| image.to_string())),
+                command: Some(init.command),
+                image_pull_policy: Some(init_pull_policy.kubernetes_value().to_string()),
+                env: env.clone(),
+                env_from: env_from.clone(),
+                security_context: self.create_container_security_context(),
+                resources: self.create_resource_requirements(deployment),
+                volume_mounts: volume_mounts.clone(),
+                ..Default::default()
+            }]
+        });
+
+        // Custom labels never enter the selector, which is immutable
+        let labels = Self::with_custom_metadata(
+            Self::deployment_labels(project, deployment, environment_name),
+            deployment.labels.as_ref(),
+        );
+        let annotations =
+            Self::with_custom_metadata(BTreeMap::new(), deployment.annotations.as_ref());
 
         K8sDeployment {
             metadata: ObjectMeta {
                 name: Some(Self::deployment_name(project, deployment)),
                 namespace: Some(namespace.to_string()),
-                labels: Some(Self::deployment_labels(
-                    project,
-                    deployment,
-                    environment_name,
-                )),
+                labels: Some(labels.clone()),
+                annotations: (!annotations.is_empty()).then_some(annotations),
                 ..Default::default()
             },
             spec: Some(DeploymentSpec {
                 replicas: Some(deployment.replicas),
                 min_ready_seconds: None,
+                revision_history_limit: Some(self.revision_history_limit.max(0)),
                 selector: LabelSelector {
                     match_labels: Some(Self::deployment_labels(
                         project,
@@ -957,73 +1510,81 @@ impl ResourceBuilder {
                     )),
                     ..Default::default()
                 },
-                strategy: Some(k8s_openapi::api::apps::v1::DeploymentStrategy {
-                    type_: Some("RollingUpdate".to_string()),
-                    rolling_update: Some(k8s_openapi::api::apps::v1::RollingUpdateDeployment {
-                        max_surge: Some(
-                            k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(1),
-                        ),
-                        max_unavailable: Some(
-                            k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(0),
-                        ),
-                    }),
-                }),
+                strategy: Some(Self::k8s_deployment_strategy(data_volume.is_some())),
                 template: PodTemplateSpec {
                     metadata: Some(ObjectMeta {
-                        labels: Some(Self::deployment_labels(
-                            project,
-                            deployment,
-                            environment_name,
-                        )),
-                        annotations: secret_env_hash.map(|hash| {
-                            let mut annotations = BTreeMap::new();
-                            annotations.insert(ANNOTATION_ENV_SECRET_HASH.to_string(), hash);
-                            annotations
-                        }),
+                        labels: Some(labels),
+                        annotations: Self::pod_template_annotations(
+                            secret_env_hash,
+                            deployment.restarted_at,
+                            deployment.annotations.as_ref(),
+                        ),
                         ..Default::default()
                     }),
                     spec: Some(PodSpec {
+                        termination_grace_period_seconds: self
+                            .termination_grace_period_secs
+                            .map(i64::from),
                         security_context: self.create_pod_security_context(),
                         image_pull_secrets: {
                             if self.registry_provider.requires_pull_secret() {
                                 let secret_name = self
                                     .image_pull_secret_name
                                     .as_deref()
-                                    .or(Some(IMAGE_PULL_SECRET_NAME));
-                                secret_name.map(|name| {
-                                    vec![LocalObjectReference {
-                                        name: name.to_string(),
-                                    }]
-                                })
+                                    .unwrap_or(&self.managed_image_pull_secret_name);
+                                Some(vec![LocalObjectReference {
+                                    name: secret_name.to_string(),
+                                }])
                             } else {
                                 None
                             }
                         },
+                        init_containers,
                         containers: vec![Container {
                             name: "app".to_string(),
                             image: Some(image.to_string()),
-                            ports: Some(vec![ContainerPort {
-                                container_port: http_port as i32,
-                                ..Default::default()
-                            }]),
-                            image_pull_policy: Some("Always".to_string()),
-                            env: (!env_vars.is_empty()).then_some(env_vars),
-                            env_from: secret_env_name.map(|name| {
-                                vec![EnvFromSource {
-                                    secret_ref: Some(SecretEnvSource {
-                                        name,
-                                        ..Default::default()
-                                    }),
+                            command: deployment.command.clone(),
+                            args: deployment.args.clone(),
+                            ports: Some(
+                                std::iter::once(ContainerPort {
+                                    container_port: http_port as i32,
                                     ..Default::default()
-                                }]
-                            }),
+                                })
+                                .chain(Self::extra_ports(deployment).into_iter().map(
+                                    |(name, config)| ContainerPort {
+                                        name: Some(name),
+                                        container_port: config.port as i32,
+                                        protocol: Some("TCP".to_string()),
+                                        ..Default::default()
+                                    },
+                                ))
+                                .collect(),
+                            ),
+                            image_pull_policy: Some(
+                                image_pull_policy.kubernetes_value().to_string(),
+                            ),
+                            env,
+                            env_from,
                             security_context: self.create_container_security_context(),
-                            resources: self
-                                .create_resource_requirements(&deployment.cpu, &deployment.memory),
-                            liveness_probe: self
-                                .create_http_probe(http_port as i32, ProbeType::Liveness),
-                            readiness_probe: self
-                                .create_http_probe(http_port as i32, ProbeType::Readiness),
+                            resources: self.create_resource_requirements(deployment),
+                            liveness_probe: self.create_probe(
+                                http_port as i32,
+                                ProbeType::Liveness,
+                                &health_check,
+                                Self::is_tcp(deployment),
+                            ),
+                            readiness_probe: self.create_probe(
+                                http_port as i32,
+                                ProbeType::Readiness,
+                                &health_check,
+                                Self::is_tcp(deployment),
+                            ),
+                            startup_probe: self.create_probe(
+                                http_port as i32,
+                                ProbeType::Startup,
+                                &health_check,
+                                Self::is_tcp(deployment),
+                            ),
                             volume_mounts,
                             ..Default::default()
                         }],
@@ -1046,7 +1607,20 @@ impl ResourceBuilder {
                                     .collect(),
                             )
                         },
-                        service_account_name,
+                        // `[deploy.service_account]` wins over the environment's account
+                        service_account_name: deployment
+                            .service_account_name
+                            .clone()
+                            .or(service_account_name),
+                        affinity,
+                        topology_spread_constraints: scheduling.as_ref().and_then(|scheduling| {
+                            Self::replica_topology_spread_constraints(
+                                project,
+                                deployment,
+                                environment_name,
+                                scheduling,
+                            )
+                        }),
                         ..Default::default()
                     }),
                 },
@@ -1056,21 +1630,175 @@ impl ResourceBuilder {
         }
     }
 
-    // ── Ingress ────────────────────────────────────────────────────────
+    /// Rollout strategy of a K8s Deployment. Pods with a `ReadWriteOnce` volume are
+    /// recreated, so a restart never runs two pods on the claim. Handing the claim
+    /// to the group's next deployment is sequenced by the sync webhook.
+    fn k8s_deployment_strategy(has_volume: bool) -> k8s_openapi::api::apps::v1::DeploymentStrategy {
+        if has_volume {
+            return k8s_openapi::api::apps::v1::DeploymentStrategy {
+                type_: Some("Recreate".to_string()),
+                rolling_update: None,
+            };
+        }
+        k8s_openapi::api::apps::v1::DeploymentStrategy {
+            type_: Some("RollingUpdate".to_string()),
+            rolling_update: Some(k8s_openapi::api::apps::v1::RollingUpdateDeployment {
+                max_surge: Some(k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(1)),
+                max_unavailable: Some(
+                    k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(0),
+                ),
+            }),
+        }
+    }
 
-    fn build_ingress_annotations(
-        &self,
+    fn spread_topology_key(scheduling: &SchedulingConfig) -> &'static str {
+        match scheduling.spread_across.unwrap_or_default() {
+            SpreadAcross::Node => TOPOLOGY_KEY_HOSTNAME,
+            SpreadAcross::Zone => TOPOLOGY_KEY_ZONE,
+        }
+    }
+
+    fn replica_label_selector(
         project: &Project,
-    ) -> anyhow::Result<BTreeMap<String, String>> {
-        let mut annotations: BTreeMap<String, String> = self
-            .ingress_annotations
-            .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect();
+        deployment: &Deployment,
+        environment_name: Option<&str>,
+    ) -> LabelSelector {
+        LabelSelector {
+            match_labels: Some(Self::deployment_labels(
+                project,
+                deployment,
+                environment_name,
+            )),
+            ..Default::default()
+        }
+    }
 
-        let access_class = self
-            .access_classes
-            .get(&project.access_class)
+    /// Pod anti-affinity between the deployment's replicas, if `anti_affinity` is set
+    fn replica_pod_anti_affinity(
+        project: &Project,
+        deployment: &Deployment,
+        environment_name: Option<&str>,
+        scheduling: &SchedulingConfig,
+    ) -> Option<PodAntiAffinity> {
+        let term = PodAffinityTerm {
+            label_selector: Some(Self::replica_label_selector(
+                project,
+                deployment,
+                environment_name,
+            )),
+            match_label_keys: Some(vec![POD_TEMPLATE_HASH_LABEL.to_string()]),
+            topology_key: Self::spread_topology_key(scheduling).to_string(),
+            ..Default::default()
+        };
+
+        match scheduling.anti_affinity? {
+            AntiAffinity::Preferred => Some(PodAntiAffinity {
+                preferred_during_scheduling_ignored_during_execution: Some(vec![
+                    WeightedPodAffinityTerm {
+                        weight: 100,
+                        pod_affinity_term: term,
+                    },
+                ]),
+                ..Default::default()
+            }),
+            AntiAffinity::Required => Some(PodAntiAffinity {
+                required_during_scheduling_ignored_during_execution: Some(vec![term]),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Best-effort even spread of the deployment's replicas, unless `anti_affinity`
+    /// already keeps them apart
+    fn replica_topology_spread_constraints(
+        project: &Project,
+        deployment: &Deployment,
+        environment_name: Option<&str>,
+        scheduling: &SchedulingConfig,
+    ) -> Option<Vec<TopologySpreadConstraint>> {
+        if scheduling.anti_affinity.is_some() {
+            return None;
+        }
+        Some(vec![TopologySpreadConstraint {
+            max_skew: 1,
+            topology_key: Self::spread_topology_key(scheduling).to_string(),
+            when_unsatisfiable: "ScheduleAnyway".to_string(),
+            label_selector: Some(Self::replica_label_selector(
+                project,
+                deployment,
+                environment_name,
+            )),
+            match_label_keys: Some(vec![POD_TEMPLATE_HASH_LABEL.to_string()]),
+            ..Default::default()
+        }])
+    }
+
+    // ── Ingress ────────────────────────────────────────────────────────
+
+    /// Whether a project may set the ingress annotation `key` via
+    /// `[deploy.ingress.annotations]`: it must match an `allowed_ingress_annotations`
+    /// entry (exact, or a prefix ending in `*`) and not be reserved by Rise.
+    pub fn is_ingress_annotation_allowed(&self, key: &str) -> bool {
+        !RESERVED_INGRESS_ANNOTATIONS.contains(&key)
+            && self.allowed_ingress_annotations.iter().any(|pattern| {
+                match pattern.strip_suffix('*') {
+                    Some(prefix) => key.starts_with(prefix),
+                    None => key == pattern,
+                }
+            })
+    }
+
+    fn build_ingress_annotations(
+        &self,
+        project: &Project,
+        deployment: &Deployment,
+    ) -> anyhow::Result<BTreeMap<String, String>> {
+        let mut annotations: BTreeMap<String, String> = self
+            .ingress_annotations
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        // Project annotations go first so the auth and access class annotations
+        // below always win. Keys are re-checked in case the allowlist was narrowed
+        // since the deployment was created.
+        let custom: BTreeMap<String, String> = deployment
+            .ingress_annotations
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default();
+        for (key, value) in custom {
+            if self.is_ingress_annotation_allowed(&key) {
+                annotations.insert(key, value);
+            } else {
+                warn!(
+                    "Skipping ingress annotation '{}' of deployment {}: not allowed",
+                    key, deployment.deployment_id
+                );
+            }
+        }
+
+        // The ingress is rebuilt from scratch on every sync, so clearing
+        // `[deploy.ratelimit]` drops these annotations again
+        let rate_limit: Option<RateLimitConfig> = deployment
+            .rate_limit
+            .clone()
+            .and_then(|value| serde_json::from_value(value).ok());
+        if let Some(rate_limit) = rate_limit {
+            for (key, value) in [
+                (ANNOTATION_LIMIT_RPS, rate_limit.rps),
+                (ANNOTATION_LIMIT_BURST_MULTIPLIER, rate_limit.burst),
+                (ANNOTATION_LIMIT_CONNECTIONS, rate_limit.connections),
+            ] {
+                if let Some(value) = value {
+                    annotations.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+
+        let access_class = self
+            .access_classes
+            .get(&project.access_class)
             .ok_or_else(|| {
                 anyhow::anyhow!(
                     "Access class '{}' not configured. Available: {}",
@@ -1192,7 +1920,7 @@ impl ResourceBuilder {
     ) -> anyhow::Result<Ingress> {
         let url_components = self.ingress_url_components(project, deployment);
 
-        let mut annotations = self.build_ingress_annotations(project)?;
+        let mut annotations = self.build_ingress_annotations(project, deployment)?;
 
         if let Some(ref path) = url_components.path_prefix {
             annotations.insert(
@@ -1223,6 +1951,7 @@ impl ResourceBuilder {
         }];
 
         let tls = self.build_primary_tls_config(&url_components.host);
+        Self::apply_https_annotations(project, tls.is_some(), &mut annotations);
 
         Ok(Ingress {
             metadata: ObjectMeta {
@@ -1250,16 +1979,26 @@ impl ResourceBuilder {
         custom_domains: &[CustomDomain],
         environment_name: Option<&str>,
     ) -> anyhow::Result<Ingress> {
-        let mut annotations = self.build_ingress_annotations(project)?;
+        let mut annotations = self.build_ingress_annotations(project, deployment)?;
 
         for (k, v) in &self.custom_domain_ingress_annotations {
             annotations.insert(k.clone(), v.clone());
         }
+        if self.custom_domain_tls_mode == crate::server::settings::CustomDomainTlsMode::Acme {
+            if let Some(ref issuer) = self.acme_cluster_issuer {
+                annotations.insert(ANNOTATION_CLUSTER_ISSUER.to_string(), issuer.clone());
+            }
+        }
+
+        // Sort so rules and TLS entries don't reorder between reconciles
+        let mut custom_domains = custom_domains.to_vec();
+        custom_domains.sort_by(|a, b| a.domain.cmp(&b.domain));
+        custom_domains.dedup_by(|a, b| a.domain == b.domain);
 
         let service_name = Self::service_name(project, deployment);
 
         let mut rules = Vec::new();
-        for domain in custom_domains {
+        for domain in &custom_domains {
             let paths = self.build_ingress_paths(&service_name, "/", "Prefix");
             rules.push(IngressRule {
                 host: Some(domain.domain.clone()),
@@ -1267,7 +2006,8 @@ impl ResourceBuilder {
             });
         }
 
-        let tls = self.build_custom_domain_tls_config(custom_domains);
+        let tls = self.build_custom_domain_tls_config(&custom_domains);
+        Self::apply_https_annotations(project, tls.is_some(), &mut annotations);
 
         Ok(Ingress {
             metadata: ObjectMeta {
@@ -1287,6 +2027,54 @@ impl ResourceBuilder {
         })
     }
 
+    /// Render the project's HTTP→HTTPS redirect and HSTS settings as ingress annotations.
+    /// These take precedence over globally configured ingress annotations.
+    fn apply_https_annotations(
+        project: &Project,
+        has_tls: bool,
+        annotations: &mut BTreeMap<String, String>,
+    ) {
+        match project.force_https {
+            Some(true) => {
+                annotations.insert(
+                    "nginx.ingress.kubernetes.io/ssl-redirect".to_string(),
+                    "true".to_string(),
+                );
+                if !has_tls {
+                    // TLS is terminated in front of the ingress controller
+                    annotations.insert(
+                        "nginx.ingress.kubernetes.io/force-ssl-redirect".to_string(),
+                        "true".to_string(),
+                    );
+                }
+            }
+            Some(false) => {
+                annotations.insert(
+                    "nginx.ingress.kubernetes.io/ssl-redirect".to_string(),
+                    "false".to_string(),
+                );
+            }
+            None if has_tls => {
+                annotations.insert(
+                    "nginx.ingress.kubernetes.io/ssl-redirect".to_string(),
+                    "true".to_string(),
+                );
+            }
+            None => {}
+        }
+
+        if let Some(max_age) = project.hsts_max_age {
+            annotations.insert(
+                "nginx.ingress.kubernetes.io/hsts".to_string(),
+                "true".to_string(),
+            );
+            annotations.insert(
+                "nginx.ingress.kubernetes.io/hsts-max-age".to_string(),
+                max_age.to_string(),
+            );
+        }
+    }
+
     fn build_primary_tls_config(
         &self,
         primary_host: &str,
@@ -1298,6 +2086,14 @@ impl ResourceBuilder {
         }])
     }
 
+    /// Per-domain TLS secret name (`*` is not valid in Kubernetes object names)
+    fn custom_domain_tls_secret_name(domain: &str) -> String {
+        match domain.strip_prefix("*.") {
+            Some(base) => format!("tls-wildcard.{}", base),
+            None => format!("tls-{}", domain),
+        }
+    }
+
     fn build_custom_domain_tls_config(
         &self,
         custom_domains: &[CustomDomain],
@@ -1318,11 +2114,12 @@ impl ResourceBuilder {
                     secret_name: Some(shared_secret.clone()),
                 });
             }
-            crate::server::settings::CustomDomainTlsMode::PerDomain => {
+            crate::server::settings::CustomDomainTlsMode::PerDomain
+            | crate::server::settings::CustomDomainTlsMode::Acme => {
                 for domain in custom_domains {
                     tls_configs.push(k8s_openapi::api::networking::v1::IngressTLS {
                         hosts: Some(vec![domain.domain.clone()]),
-                        secret_name: Some(format!("tls-{}", domain.domain)),
+                        secret_name: Some(Self::custom_domain_tls_secret_name(&domain.domain)),
                     });
                 }
             }
@@ -1366,6 +2163,7 @@ mod tests {
     use crate::server::registry::{ImageTagType, RegistryProvider};
     use anyhow::Result;
     use async_trait::async_trait;
+    use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
     use std::sync::Arc;
 
     struct TestRegistryProvider;
@@ -1380,6 +2178,10 @@ mod tests {
             unreachable!("not used in these tests")
         }
 
+        fn registry_type(&self) -> &'static str {
+            "test"
+        }
+
         fn registry_host(&self) -> &str {
             "registry.example.test"
         }
@@ -1407,15 +2209,19 @@ mod tests {
             namespace_labels: std::collections::HashMap::new(),
             namespace_annotations: std::collections::HashMap::new(),
             ingress_annotations: std::collections::HashMap::new(),
+            allowed_ingress_annotations: vec!["nginx.ingress.kubernetes.io/proxy-*".to_string()],
             ingress_tls_secret_name: None,
             custom_domain_tls_mode: crate::server::settings::CustomDomainTlsMode::PerDomain,
+            acme_cluster_issuer: None,
             custom_domain_ingress_annotations: std::collections::HashMap::new(),
             node_selector: std::collections::HashMap::new(),
             image_pull_secret_name: None,
+            managed_image_pull_secret_name: "rise-registry-creds".to_string(),
             access_classes: std::collections::HashMap::new(),
             host_aliases: std::collections::HashMap::new(),
             extra_service_token_audiences: std::collections::HashMap::new(),
             use_default_service_account_for_production: true,
+            create_deployment_service_accounts: false,
             network_policy: crate::server::settings::NetworkPolicyConfig {
                 ingress: vec![],
                 egress: None,
@@ -1423,6 +2229,12 @@ mod tests {
             pod_security_enabled: true,
             health_probes: None,
             namespace_format: "{project_name}".to_string(),
+            revision_history_limit: 2,
+            termination_grace_period_secs: None,
+            pod_disruption_budget_min_available: None,
+            cleanup_terminal_replica_sets: true,
+            in_flight_resync_secs: 2.0,
+            tcp_service_type: crate::server::settings::TcpServiceType::ClusterIP,
         }
     }
 
@@ -1436,6 +2248,11 @@ mod tests {
             owner_team_id: None,
             finalizers: vec![],
             source_url: None,
+            force_https: None,
+            hsts_max_age: None,
+            max_active_deployments: None,
+            group_ttls: None,
+            namespace: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -1466,9 +2283,29 @@ mod tests {
             first_healthy_at: None,
             job_url: None,
             pull_request_url: None,
+            message: None,
             replicas: 1,
             cpu: "500m".to_string(),
             memory: "256Mi".to_string(),
+            cpu_limit: None,
+            memory_limit: None,
+            health_check: None,
+            init_container: None,
+            protocol: "http".to_string(),
+            labels: None,
+            annotations: None,
+            command: None,
+            args: None,
+            ingress_annotations: None,
+            strategy: "rolling".to_string(),
+            drain_period_seconds: None,
+            volume: None,
+            scheduling: None,
+            image_pull_policy: None,
+            rate_limit: None,
+            extra_ports: None,
+            service_account_name: None,
+            restarted_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -1520,10 +2357,65 @@ mod tests {
     }
 
     #[test]
-    fn create_k8s_deployment_uses_env_from_for_secret_env() {
+    fn resource_limits_default_to_requests() {
         let builder = test_resource_builder();
+        let mut deployment = test_deployment();
+
+        let resources = builder.create_resource_requirements(&deployment).unwrap();
+        assert_eq!(resources.requests, resources.limits);
+
+        deployment.cpu_limit = Some("1".to_string());
+        deployment.memory_limit = Some("512Mi".to_string());
+        let resources = builder.create_resource_requirements(&deployment).unwrap();
+        let requests = resources.requests.unwrap();
+        let limits = resources.limits.unwrap();
+        assert_eq!(requests["cpu"].0, "500m");
+        assert_eq!(limits["cpu"].0, "1");
+        assert_eq!(requests["memory"].0, "256Mi");
+        assert_eq!(limits["memory"].0, "512Mi");
+    }
+
+    #[test]
+    fn format_namespace_name_substitutes_team() {
+        assert_eq!(
+            format_namespace_name("rise-{project_name}", "my-app", None),
+            "rise-my-app"
+        );
+        assert_eq!(
+            format_namespace_name("{team}-{project_name}", "my-app", Some("Data_Eng")),
+            "data-eng-my-app"
+        );
+        assert_eq!(
+            format_namespace_name("{team}-{project_name}", "my-app", None),
+            "user-my-app"
+        );
+        assert!(is_valid_namespace_name("data-eng-my-app"));
+        assert!(!is_valid_namespace_name("-my-app"));
+        assert!(!is_valid_namespace_name(&"a".repeat(64)));
+    }
+
+    #[test]
+    fn create_k8s_deployment_applies_health_check_overrides() {
+        let builder = ResourceBuilder {
+            health_probes: Some(crate::server::settings::HealthProbeConfig {
+                liveness_enabled: true,
+                readiness_enabled: true,
+                path: "/healthz".to_string(),
+                readiness_path: None,
+                liveness_path: None,
+                initial_delay_seconds: 10,
+                period_seconds: 10,
+                timeout_seconds: 5,
+                failure_threshold: 3,
+            }),
+            ..test_resource_builder()
+        };
         let project = test_project();
-        let deployment = test_deployment();
+        let mut deployment = test_deployment();
+        deployment.health_check = Some(serde_json::json!({
+            "readiness_path": "/ready",
+            "period_seconds": 3,
+        }));
 
         let k8s_deployment = builder.create_k8s_deployment(
             &project,
@@ -1531,17 +2423,12 @@ mod tests {
             "demo",
             "registry.example.test/rise/demo:20260502-000000",
             8080,
-            vec![EnvVar {
-                name: "PORT".to_string(),
-                value: Some("8080".to_string()),
-                ..Default::default()
-            }],
-            Some("demo-20260502-000000-env".to_string()),
-            Some("abc123".to_string()),
+            vec![],
+            None,
+            None,
             None,
             None,
         );
-
         let container = &k8s_deployment
             .spec
             .as_ref()
@@ -1552,36 +2439,26 @@ mod tests {
             .unwrap()
             .containers[0];
 
-        assert_eq!(container.env.as_ref().unwrap().len(), 1);
-        assert_eq!(
-            container.env_from.as_ref().unwrap()[0]
-                .secret_ref
-                .as_ref()
-                .unwrap()
-                .name
-                .as_str(),
-            "demo-20260502-000000-env"
-        );
-        assert_eq!(
-            k8s_deployment
-                .spec
-                .as_ref()
-                .unwrap()
-                .template
-                .metadata
-                .as_ref()
-                .and_then(|metadata| metadata.annotations.as_ref())
-                .and_then(|annotations| annotations.get(ANNOTATION_ENV_SECRET_HASH))
-                .map(String::as_str),
-            Some("abc123")
-        );
+        let readiness = container.readiness_probe.as_ref().unwrap();
+        let liveness = container.liveness_probe.as_ref().unwrap();
+        let path = |probe: &Probe| probe.http_get.as_ref().unwrap().path.clone().unwrap();
+        assert_eq!(path(readiness), "/ready");
+        assert_eq!(readiness.period_seconds, Some(3));
+        assert_eq!(readiness.initial_delay_seconds, Some(10));
+        assert_eq!(path(liveness), "/healthz");
+        assert_eq!(liveness.period_seconds, Some(3));
+        assert!(container.startup_probe.is_none());
     }
 
     #[test]
-    fn create_k8s_deployment_omits_empty_env_and_env_from() {
+    fn create_k8s_deployment_adds_startup_probe_when_configured() {
         let builder = test_resource_builder();
         let project = test_project();
-        let deployment = test_deployment();
+        let mut deployment = test_deployment();
+        deployment.health_check = Some(serde_json::json!({
+            "liveness_path": "/alive",
+            "startup_failure_threshold": 60,
+        }));
 
         let k8s_deployment = builder.create_k8s_deployment(
             &project,
@@ -1595,7 +2472,6 @@ mod tests {
             None,
             None,
         );
-
         let container = &k8s_deployment
             .spec
             .as_ref()
@@ -1606,8 +2482,1078 @@ mod tests {
             .unwrap()
             .containers[0];
 
-        assert!(container.env.is_none());
-        assert!(container.env_from.is_none());
+        let startup = container.startup_probe.as_ref().unwrap();
+        let path = startup.http_get.as_ref().unwrap().path.clone().unwrap();
+        assert_eq!(path, "/alive");
+        assert_eq!(startup.failure_threshold, Some(60));
+        assert_eq!(startup.period_seconds, Some(10));
+        assert_eq!(startup.initial_delay_seconds, None);
+    }
+
+    #[test]
+    fn create_k8s_deployment_merges_custom_labels_and_annotations() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.labels = Some(serde_json::json!({
+            "team": "payments",
+            "rise.dev/project": "spoofed",
+        }));
+        deployment.annotations = Some(serde_json::json!({
+            "example.com/cost-center": "1234",
+        }));
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+        let spec = k8s_deployment.spec.as_ref().unwrap();
+        let pod_metadata = spec.template.metadata.as_ref().unwrap();
+
+        for labels in [
+            k8s_deployment.metadata.labels.as_ref().unwrap(),
+            pod_metadata.labels.as_ref().unwrap(),
+        ] {
+            assert_eq!(labels["team"], "payments");
+            assert_eq!(labels[LABEL_PROJECT], project.name);
+        }
+        assert!(!spec
+            .selector
+            .match_labels
+            .as_ref()
+            .unwrap()
+            .contains_key("team"));
+        assert_eq!(
+            pod_metadata.annotations.as_ref().unwrap()["example.com/cost-center"],
+            "1234"
+        );
+        assert_eq!(
+            k8s_deployment.metadata.annotations.as_ref().unwrap()["example.com/cost-center"],
+            "1234"
+        );
+
+        // Unchanged metadata renders identically, so reconciles don't roll the pods
+        let again = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            again.spec.unwrap().template.metadata,
+            spec.template.metadata
+        );
+    }
+
+    #[test]
+    fn create_k8s_deployment_applies_command_and_args_overrides() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.init_container = Some(serde_json::json!({"command": ["./migrate"]}));
+
+        let render = |deployment: &Deployment| {
+            builder.create_k8s_deployment(
+                &project,
+                deployment,
+                "demo",
+                "postgres:16",
+                5432,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+
+        // Without overrides the image's entrypoint and cmd are kept
+        let pod_spec = render(&deployment).spec.unwrap().template.spec.unwrap();
+        assert_eq!(pod_spec.containers[0].command, None);
+        assert_eq!(pod_spec.containers[0].args, None);
+
+        deployment.command = Some(vec!["docker-entrypoint.sh".to_string()]);
+        deployment.args = Some(vec![
+            "postgres".to_string(),
+            "-c".to_string(),
+            "max_connections=200".to_string(),
+        ]);
+        let pod_spec = render(&deployment).spec.unwrap().template.spec.unwrap();
+        let app = &pod_spec.containers[0];
+        assert_eq!(app.command, deployment.command);
+        assert_eq!(app.args, deployment.args);
+        // The init container keeps its own command
+        let init = &pod_spec.init_containers.unwrap()[0];
+        assert_eq!(init.command, Some(vec!["./migrate".to_string()]));
+        assert_eq!(init.args, None);
+    }
+
+    #[test]
+    fn image_pull_policy_depends_on_digest_pinning() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.init_container =
+            Some(serde_json::json!({"command": ["./migrate"], "image": "migrate:latest"}));
+
+        let pull_policies = |deployment: &Deployment| {
+            let pod_spec = builder
+                .create_k8s_deployment(
+                    &project,
+                    deployment,
+                    "demo",
+                    "registry.example/app:tag",
+                    8080,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .spec
+                .unwrap()
+                .template
+                .spec
+                .unwrap();
+            (
+                pod_spec.containers[0].image_pull_policy.clone().unwrap(),
+                pod_spec.init_containers.unwrap()[0]
+                    .image_pull_policy
+                    .clone()
+                    .unwrap(),
+            )
+        };
+
+        // Tags may be pushed again, so they are always pulled
+        assert_eq!(
+            pull_policies(&deployment),
+            ("Always".to_string(), "Always".to_string())
+        );
+
+        // A pinned digest can't change; the separately tagged init image still can
+        deployment.image_digest = Some("registry.example/app@sha256:abc".to_string());
+        assert_eq!(
+            pull_policies(&deployment),
+            ("IfNotPresent".to_string(), "Always".to_string())
+        );
+
+        // An explicit policy wins in both directions
+        deployment.image_pull_policy = Some("always".to_string());
+        assert_eq!(
+            pull_policies(&deployment),
+            ("Always".to_string(), "Always".to_string())
+        );
+        deployment.image_digest = None;
+        deployment.image_pull_policy = Some("if-not-present".to_string());
+        assert_eq!(
+            pull_policies(&deployment),
+            ("IfNotPresent".to_string(), "IfNotPresent".to_string())
+        );
+    }
+
+    #[test]
+    fn volumes_get_a_group_claim_mounted_into_the_app() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+
+        let render = |deployment: &Deployment| {
+            builder.create_k8s_deployment(
+                &project,
+                deployment,
+                "demo",
+                "postgres:16",
+                5432,
+                vec![],
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+
+        // Without a volume there is no claim and the rollout surges
+        assert!(builder
+            .create_persistent_volume_claim(&project, &deployment, "demo", None)
+            .is_none());
+        let spec = render(&deployment).spec.unwrap();
+        assert_eq!(
+            spec.strategy.unwrap().type_.as_deref(),
+            Some("RollingUpdate")
+        );
+        assert!(spec.template.spec.unwrap().volumes.is_none());
+
+        deployment.volume = Some(serde_json::json!({
+            "size": "5Gi",
+            "storage_class": "ssd",
+            "mount_path": "/var/lib/postgresql/data",
+        }));
+        let pvc = builder
+            .create_persistent_volume_claim(&project, &deployment, "demo", None)
+            .unwrap();
+        assert_eq!(pvc.metadata.name.as_deref(), Some("default-data"));
+        // Group labels only, so the claim is shared by every deployment of the group
+        assert!(!pvc
+            .metadata
+            .labels
+            .as_ref()
+            .unwrap()
+            .contains_key(LABEL_DEPLOYMENT_ID));
+        let pvc_spec = pvc.spec.unwrap();
+        assert_eq!(
+            pvc_spec.access_modes,
+            Some(vec!["ReadWriteOnce".to_string()])
+        );
+        assert_eq!(pvc_spec.storage_class_name.as_deref(), Some("ssd"));
+        assert_eq!(
+            pvc_spec.resources.unwrap().requests.unwrap()["storage"].0,
+            "5Gi"
+        );
+
+        let spec = render(&deployment).spec.unwrap();
+        assert_eq!(spec.strategy.unwrap().type_.as_deref(), Some("Recreate"));
+        let pod_spec = spec.template.spec.unwrap();
+        let volume = &pod_spec.volumes.unwrap()[0];
+        assert_eq!(
+            volume.persistent_volume_claim.as_ref().unwrap().claim_name,
+            "default-data"
+        );
+        let mount = &pod_spec.containers[0].volume_mounts.as_ref().unwrap()[0];
+        assert_eq!(mount.name, volume.name);
+        assert_eq!(mount.mount_path, "/var/lib/postgresql/data");
+        assert!(pod_spec.affinity.is_none());
+    }
+
+    #[test]
+    fn tcp_deployments_get_tcp_service_probes_and_url() {
+        let builder = ResourceBuilder {
+            tcp_service_type: crate::server::settings::TcpServiceType::LoadBalancer,
+            ..test_resource_builder()
+        };
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.protocol = "tcp".to_string();
+        deployment.http_port = 5432;
+
+        let service = builder.create_service(&project, &deployment, "demo", 5432, None);
+        let spec = service.spec.unwrap();
+        assert_eq!(spec.type_.as_deref(), Some("LoadBalancer"));
+        let port = &spec.ports.unwrap()[0];
+        assert_eq!(port.name.as_deref(), Some("tcp"));
+        assert_eq!(port.port, 5432);
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            5432,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+        let pod_spec = k8s_deployment.spec.unwrap().template.spec.unwrap();
+        let container = &pod_spec.containers[0];
+        for probe in [&container.readiness_probe, &container.liveness_probe] {
+            let probe = probe.as_ref().unwrap();
+            assert!(probe.http_get.is_none());
+            assert!(probe.tcp_socket.is_some());
+        }
+
+        let urls = builder.compute_deployment_urls(&project, &deployment, None, &[]);
+        assert_eq!(
+            urls.primary_url,
+            "tcp://default.demo.svc.cluster.local:5432"
+        );
+        assert_eq!(urls.default_url, urls.primary_url);
+        assert!(urls.custom_domain_urls.is_empty());
+    }
+
+    #[test]
+    fn extra_ports_are_added_to_container_and_optionally_service() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.extra_ports = Some(serde_json::json!({
+            "metrics": { "port": 9090, "service": true },
+            "admin": { "port": 8081, "service": false },
+        }));
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+        let pod_spec = k8s_deployment.spec.unwrap().template.spec.unwrap();
+        let container = &pod_spec.containers[0];
+        let container_ports: Vec<_> = container
+            .ports
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|p| (p.name.as_deref(), p.container_port))
+            .collect();
+        assert_eq!(
+            container_ports,
+            vec![(None, 8080), (Some("admin"), 8081), (Some("metrics"), 9090)]
+        );
+        // Probes keep targeting the primary port
+        let probe = container.readiness_probe.as_ref().unwrap();
+        assert_eq!(
+            probe.http_get.as_ref().unwrap().port,
+            IntOrString::Int(8080)
+        );
+
+        let service = builder.create_service(&project, &deployment, "demo", 8080, None);
+        let service_ports: Vec<_> = service
+            .spec
+            .unwrap()
+            .ports
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.name.unwrap(), p.port, p.target_port.unwrap()))
+            .collect();
+        assert_eq!(
+            service_ports,
+            vec![
+                ("http".to_string(), 80, IntOrString::Int(8080)),
+                (
+                    "metrics".to_string(),
+                    9090,
+                    IntOrString::String("metrics".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn create_k8s_deployment_uses_env_from_for_secret_env() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let deployment = test_deployment();
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![EnvVar {
+                name: "PORT".to_string(),
+                value: Some("8080".to_string()),
+                ..Default::default()
+            }],
+            Some("demo-20260502-000000-env".to_string()),
+            Some("abc123".to_string()),
+            None,
+            None,
+        );
+
+        let container = &k8s_deployment
+            .spec
+            .as_ref()
+            .unwrap()
+            .template
+            .spec
+            .as_ref()
+            .unwrap()
+            .containers[0];
+
+        assert_eq!(container.env.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            container.env_from.as_ref().unwrap()[0]
+                .secret_ref
+                .as_ref()
+                .unwrap()
+                .name
+                .as_str(),
+            "demo-20260502-000000-env"
+        );
+        assert_eq!(
+            k8s_deployment
+                .spec
+                .as_ref()
+                .unwrap()
+                .template
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.annotations.as_ref())
+                .and_then(|annotations| annotations.get(ANNOTATION_ENV_SECRET_HASH))
+                .map(String::as_str),
+            Some("abc123")
+        );
+    }
+
+    #[test]
+    fn create_k8s_deployment_adds_init_container_with_app_env() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.init_container = Some(serde_json::json!({
+            "command": ["./manage.py", "migrate"],
+        }));
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![EnvVar {
+                name: "PORT".to_string(),
+                value: Some("8080".to_string()),
+                ..Default::default()
+            }],
+            Some("demo-20260502-000000-env".to_string()),
+            None,
+            None,
+            None,
+        );
+
+        let pod_spec = k8s_deployment
+            .spec
+            .as_ref()
+            .unwrap()
+            .template
+            .spec
+            .as_ref()
+            .unwrap();
+        let init = &pod_spec.init_containers.as_ref().unwrap()[0];
+
+        assert_eq!(
+            init.image.as_deref(),
+            Some("registry.example.test/rise/demo:20260502-000000")
+        );
+        assert_eq!(
+            init.command.as_deref(),
+            Some(&["./manage.py".to_string(), "migrate".to_string()][..])
+        );
+        assert_eq!(init.env, pod_spec.containers[0].env);
+        assert_eq!(init.env_from, pod_spec.containers[0].env_from);
+    }
+
+    #[test]
+    fn create_k8s_deployment_omits_empty_env_and_env_from() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let deployment = test_deployment();
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+
+        let container = &k8s_deployment
+            .spec
+            .as_ref()
+            .unwrap()
+            .template
+            .spec
+            .as_ref()
+            .unwrap()
+            .containers[0];
+
+        assert!(container.env.is_none());
+        assert!(container.env_from.is_none());
+        assert!(k8s_deployment
+            .spec
+            .as_ref()
+            .unwrap()
+            .template
+            .metadata
+            .as_ref()
+            .unwrap()
+            .annotations
+            .is_none());
+    }
+
+    #[test]
+    fn create_k8s_deployment_sets_restarted_at_annotation() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.restarted_at = Some(
+            chrono::DateTime::parse_from_rfc3339("2026-05-10T12:00:00Z")
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+        );
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert_eq!(
+            k8s_deployment
+                .spec
+                .as_ref()
+                .unwrap()
+                .template
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.annotations.as_ref())
+                .and_then(|annotations| annotations.get(ANNOTATION_RESTARTED_AT))
+                .map(String::as_str),
+            Some("2026-05-10T12:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn https_annotations_default_to_redirect_only_with_tls() {
+        let project = test_project();
+
+        let mut annotations = BTreeMap::new();
+        ResourceBuilder::apply_https_annotations(&project, true, &mut annotations);
+        assert_eq!(
+            annotations
+                .get("nginx.ingress.kubernetes.io/ssl-redirect")
+                .map(String::as_str),
+            Some("true")
+        );
+        assert!(!annotations.contains_key("nginx.ingress.kubernetes.io/hsts"));
+
+        let mut annotations = BTreeMap::new();
+        ResourceBuilder::apply_https_annotations(&project, false, &mut annotations);
+        assert!(annotations.is_empty());
+    }
+
+    #[test]
+    fn https_annotations_render_project_settings() {
+        let mut project = test_project();
+        project.force_https = Some(true);
+        project.hsts_max_age = Some(31536000);
+
+        let mut annotations = BTreeMap::new();
+        ResourceBuilder::apply_https_annotations(&project, false, &mut annotations);
+        assert_eq!(
+            annotations
+                .get("nginx.ingress.kubernetes.io/force-ssl-redirect")
+                .map(String::as_str),
+            Some("true")
+        );
+        assert_eq!(
+            annotations
+                .get("nginx.ingress.kubernetes.io/hsts-max-age")
+                .map(String::as_str),
+            Some("31536000")
+        );
+
+        project.force_https = Some(false);
+        let mut annotations = BTreeMap::from([(
+            "nginx.ingress.kubernetes.io/ssl-redirect".to_string(),
+            "true".to_string(),
+        )]);
+        ResourceBuilder::apply_https_annotations(&project, true, &mut annotations);
+        assert_eq!(
+            annotations
+                .get("nginx.ingress.kubernetes.io/ssl-redirect")
+                .map(String::as_str),
+            Some("false")
+        );
+    }
+
+    #[test]
+    fn create_k8s_deployment_sets_revision_history_limit() {
+        let mut builder = test_resource_builder();
+        builder.revision_history_limit = 0;
+        let project = test_project();
+        let deployment = test_deployment();
+
+        let k8s_deployment = builder.create_k8s_deployment(
+            &project,
+            &deployment,
+            "demo",
+            "registry.example.test/rise/demo:20260502-000000",
+            8080,
+            vec![],
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert_eq!(
+            k8s_deployment.spec.as_ref().unwrap().revision_history_limit,
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn wildcard_custom_domains_get_tls_but_no_url() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let domain = |name: &str| CustomDomain {
+            id: uuid::Uuid::new_v4(),
+            project_id: project.id,
+            domain: name.to_string(),
+            is_primary: false,
+            verified: false,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let domains = vec![domain("*.example.com"), domain("example.com")];
+
+        let tls = builder.build_custom_domain_tls_config(&domains).unwrap();
+        let secrets: Vec<_> = tls
+            .iter()
+            .filter_map(|t| t.secret_name.as_deref())
+            .collect();
+        assert_eq!(secrets, vec!["tls-wildcard.example.com", "tls-example.com"]);
+        assert_eq!(
+            tls[0].hosts.as_deref(),
+            Some(&["*.example.com".to_string()][..])
+        );
+
+        let urls = builder.compute_project_urls(&project, "default", &domains);
+        assert_eq!(urls.custom_domain_urls, vec!["https://example.com"]);
+    }
+
+    #[test]
+    fn acme_custom_domain_ingress_is_stable() {
+        let mut builder = test_resource_builder();
+        builder.custom_domain_tls_mode = crate::server::settings::CustomDomainTlsMode::Acme;
+        builder.acme_cluster_issuer = Some("letsencrypt-prod".to_string());
+        builder.access_classes.insert(
+            "default".to_string(),
+            crate::server::settings::AccessClass {
+                display_name: "Public".to_string(),
+                description: String::new(),
+                ingress_class: "nginx".to_string(),
+                access_requirement: AccessRequirement::None,
+                custom_annotations: std::collections::HashMap::new(),
+            },
+        );
+        let project = test_project();
+        let deployment = test_deployment();
+        let domain = |name: &str| CustomDomain {
+            id: uuid::Uuid::new_v4(),
+            project_id: project.id,
+            domain: name.to_string(),
+            is_primary: false,
+            verified: false,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let domains = vec![domain("b.example.com"), domain("a.example.com")];
+        let reversed: Vec<_> = domains.iter().rev().cloned().collect();
+
+        let ingress = builder
+            .create_custom_domain_ingress(&project, &deployment, "demo", &domains, None)
+            .unwrap();
+        let ingress_reversed = builder
+            .create_custom_domain_ingress(&project, &deployment, "demo", &reversed, None)
+            .unwrap();
+        assert_eq!(ingress, ingress_reversed);
+
+        assert_eq!(
+            ingress
+                .metadata
+                .annotations
+                .as_ref()
+                .unwrap()
+                .get("cert-manager.io/cluster-issuer")
+                .map(String::as_str),
+            Some("letsencrypt-prod")
+        );
+        let tls = ingress.spec.unwrap().tls.unwrap();
+        let secrets: Vec<_> = tls
+            .iter()
+            .filter_map(|t| t.secret_name.as_deref())
+            .collect();
+        assert_eq!(secrets, vec!["tls-a.example.com", "tls-b.example.com"]);
+    }
+
+    #[test]
+    fn primary_ingress_merges_allowed_project_annotations() {
+        let mut builder = test_resource_builder();
+        builder.access_classes.insert(
+            "default".to_string(),
+            crate::server::settings::AccessClass {
+                display_name: "Private".to_string(),
+                description: String::new(),
+                ingress_class: "nginx".to_string(),
+                access_requirement: AccessRequirement::Authenticated,
+                custom_annotations: std::collections::HashMap::new(),
+            },
+        );
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.ingress_annotations = Some(serde_json::json!({
+            "nginx.ingress.kubernetes.io/proxy-body-size": "50m",
+            "nginx.ingress.kubernetes.io/auth-url": "https://evil.example.test",
+            "nginx.ingress.kubernetes.io/configuration-snippet": "return 200;",
+        }));
+
+        assert!(
+            builder.is_ingress_annotation_allowed("nginx.ingress.kubernetes.io/proxy-body-size")
+        );
+        assert!(!builder.is_ingress_annotation_allowed("nginx.ingress.kubernetes.io/auth-url"));
+        assert!(!builder
+            .is_ingress_annotation_allowed("nginx.ingress.kubernetes.io/configuration-snippet"));
+
+        let ingress = builder
+            .create_primary_ingress(&project, &deployment, "demo", None)
+            .unwrap();
+        let annotations = ingress.metadata.annotations.unwrap();
+        assert_eq!(
+            annotations["nginx.ingress.kubernetes.io/proxy-body-size"],
+            "50m"
+        );
+        assert!(annotations["nginx.ingress.kubernetes.io/auth-url"]
+            .starts_with("https://auth.example.test/"));
+        assert!(!annotations.contains_key("nginx.ingress.kubernetes.io/configuration-snippet"));
+    }
+
+    #[test]
+    fn primary_ingress_sets_rate_limit_annotations() {
+        let mut builder = test_resource_builder();
+        builder.access_classes.insert(
+            "default".to_string(),
+            crate::server::settings::AccessClass {
+                display_name: "Public".to_string(),
+                description: String::new(),
+                ingress_class: "nginx".to_string(),
+                access_requirement: AccessRequirement::None,
+                custom_annotations: std::collections::HashMap::new(),
+            },
+        );
+        builder.allowed_ingress_annotations = vec!["nginx.ingress.kubernetes.io/*".to_string()];
+        let project = test_project();
+        let mut deployment = test_deployment();
+        deployment.ingress_annotations = Some(serde_json::json!({
+            "nginx.ingress.kubernetes.io/limit-rps": "1000",
+        }));
+        deployment.rate_limit = Some(serde_json::json!({ "rps": 10, "burst": 3 }));
+
+        assert!(!builder.is_ingress_annotation_allowed(ANNOTATION_LIMIT_RPS));
+        let annotations = builder
+            .create_primary_ingress(&project, &deployment, "demo", None)
+            .unwrap()
+            .metadata
+            .annotations
+            .unwrap();
+        assert_eq!(annotations[ANNOTATION_LIMIT_RPS], "10");
+        assert_eq!(annotations[ANNOTATION_LIMIT_BURST_MULTIPLIER], "3");
+        assert!(!annotations.contains_key(ANNOTATION_LIMIT_CONNECTIONS));
+
+        // Clearing the config removes the annotations on the next sync
+        deployment.rate_limit = None;
+        let annotations = builder
+            .create_primary_ingress(&project, &deployment, "demo", None)
+            .unwrap()
+            .metadata
+            .annotations
+            .unwrap();
+        assert!(!annotations.contains_key(ANNOTATION_LIMIT_RPS));
+        assert!(!annotations.contains_key(ANNOTATION_LIMIT_BURST_MULTIPLIER));
+    }
+
+    #[test]
+    fn create_k8s_deployment_sets_termination_grace_period() {
+        let mut builder = test_resource_builder();
+        let project = test_project();
+        let deployment = test_deployment();
+        let grace_period = |builder: &ResourceBuilder| {
+            builder
+                .create_k8s_deployment(
+                    &project,
+                    &deployment,
+                    "demo",
+                    "registry.example.test/rise/demo:20260502-000000",
+                    8080,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .spec
+                .unwrap()
+                .template
+                .spec
+                .unwrap()
+                .termination_grace_period_seconds
+        };
+
+        assert_eq!(grace_period(&builder), None);
+
+        builder.termination_grace_period_secs = Some(90);
+        assert_eq!(grace_period(&builder), Some(90));
+    }
+
+    #[test]
+    fn create_k8s_deployment_uses_configured_image_pull_secret_name() {
+        let mut builder = test_resource_builder();
+        let project = test_project();
+        let deployment = test_deployment();
+        let pull_secrets = |builder: &ResourceBuilder| {
+            builder
+                .create_k8s_deployment(
+                    &project,
+                    &deployment,
+                    "demo",
+                    "registry.example.test/rise/demo:20260502-000000",
+                    8080,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .spec
+                .unwrap()
+                .template
+                .spec
+                .unwrap()
+                .image_pull_secrets
+                .unwrap()
+                .into_iter()
+                .map(|secret| secret.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(pull_secrets(&builder), vec!["rise-registry-creds"]);
+
+        builder.managed_image_pull_secret_name = "custom-registry-creds".to_string();
+        assert_eq!(pull_secrets(&builder), vec!["custom-registry-creds"]);
+
+        // An externally managed secret takes precedence
+        builder.image_pull_secret_name = Some("external-creds".to_string());
+        assert_eq!(pull_secrets(&builder), vec!["external-creds"]);
+    }
+
+    #[test]
+    fn create_k8s_deployment_prefers_deployment_service_account() {
+        let builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        let service_account = |deployment: &Deployment| {
+            builder
+                .create_k8s_deployment(
+                    &project,
+                    deployment,
+                    "demo",
+                    "registry.example.test/rise/demo:20260502-000000",
+                    8080,
+                    vec![],
+                    None,
+                    None,
+                    Some("env-staging".to_string()),
+                    Some("staging"),
+                )
+                .spec
+                .unwrap()
+                .template
+                .spec
+                .unwrap()
+                .service_account_name
+        };
+
+        assert_eq!(service_account(&deployment).as_deref(), Some("env-staging"));
+
+        deployment.service_account_name = Some("app-irsa".to_string());
+        assert_eq!(service_account(&deployment).as_deref(), Some("app-irsa"));
+    }
+
+    #[test]
+    fn create_k8s_deployment_spreads_multi_replica_deployments() {
+        let mut builder = test_resource_builder();
+        builder
+            .node_selector
+            .insert("pool".to_string(), "apps".to_string());
+        let project = test_project();
+        let mut deployment = test_deployment();
+        let pod_spec = |deployment: &Deployment| {
+            builder
+                .create_k8s_deployment(
+                    &project,
+                    deployment,
+                    "demo",
+                    "registry.example.test/rise/demo:20260502-000000",
+                    8080,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .spec
+                .unwrap()
+                .template
+                .spec
+                .unwrap()
+        };
+        let replica_selector = Some(LabelSelector {
+            match_labels: Some(ResourceBuilder::deployment_labels(
+                &project,
+                &deployment,
+                None,
+            )),
+            ..Default::default()
+        });
+
+        // Single replica: nothing to spread
+        deployment.scheduling = Some(serde_json::json!({ "spread_across": "zone" }));
+        let spec = pod_spec(&deployment);
+        assert!(spec.affinity.is_none());
+        assert!(spec.topology_spread_constraints.is_none());
+
+        // Without anti_affinity: best-effort topology spread, node by default
+        deployment.replicas = 3;
+        deployment.scheduling = Some(serde_json::json!({}));
+        let spec = pod_spec(&deployment);
+        assert!(spec.affinity.is_none());
+        let constraints = spec.topology_spread_constraints.unwrap();
+        assert_eq!(constraints.len(), 1);
+        assert_eq!(constraints[0].topology_key, "kubernetes.io/hostname");
+        assert_eq!(constraints[0].max_skew, 1);
+        assert_eq!(constraints[0].when_unsatisfiable, "ScheduleAnyway");
+        assert_eq!(constraints[0].label_selector, replica_selector);
+        assert_eq!(
+            constraints[0].match_label_keys,
+            Some(vec!["pod-template-hash".to_string()])
+        );
+        assert_eq!(
+            spec.node_selector,
+            Some(BTreeMap::from([("pool".to_string(), "apps".to_string())]))
+        );
+
+        deployment.scheduling = Some(serde_json::json!({ "spread_across": "zone" }));
+        let constraints = pod_spec(&deployment).topology_spread_constraints.unwrap();
+        assert_eq!(constraints[0].topology_key, "topology.kubernetes.io/zone");
+
+        // Preferred anti-affinity replaces the spread constraint
+        deployment.scheduling = Some(serde_json::json!({
+            "spread_across": "zone",
+            "anti_affinity": "preferred",
+        }));
+        let spec = pod_spec(&deployment);
+        assert!(spec.topology_spread_constraints.is_none());
+        let anti_affinity = spec.affinity.unwrap().pod_anti_affinity.unwrap();
+        assert!(anti_affinity
+            .required_during_scheduling_ignored_during_execution
+            .is_none());
+        let preferred = anti_affinity
+            .preferred_during_scheduling_ignored_during_execution
+            .unwrap();
+        assert_eq!(preferred.len(), 1);
+        assert_eq!(preferred[0].weight, 100);
+        assert_eq!(
+            preferred[0].pod_affinity_term.topology_key,
+            "topology.kubernetes.io/zone"
+        );
+        assert_eq!(
+            preferred[0].pod_affinity_term.label_selector,
+            replica_selector
+        );
+
+        // Required anti-affinity: at most one replica per node
+        deployment.scheduling = Some(serde_json::json!({ "anti_affinity": "required" }));
+        let spec = pod_spec(&deployment);
+        assert!(spec.topology_spread_constraints.is_none());
+        let affinity = spec.affinity.unwrap();
+        assert!(affinity.pod_affinity.is_none());
+        let anti_affinity = affinity.pod_anti_affinity.unwrap();
+        assert!(anti_affinity
+            .preferred_during_scheduling_ignored_during_execution
+            .is_none());
+        let required = anti_affinity
+            .required_during_scheduling_ignored_during_execution
+            .unwrap();
+        assert_eq!(required.len(), 1);
+        assert_eq!(required[0].topology_key, "kubernetes.io/hostname");
+        assert_eq!(
+            required[0].match_label_keys,
+            Some(vec!["pod-template-hash".to_string()])
+        );
+        assert_eq!(
+            spec.node_selector,
+            Some(BTreeMap::from([("pool".to_string(), "apps".to_string())]))
+        );
+    }
+
+    #[test]
+    fn pod_disruption_budget_only_for_multi_replica_deployments() {
+        let mut builder = test_resource_builder();
+        let project = test_project();
+        let mut deployment = test_deployment();
+        let min_available = |builder: &ResourceBuilder, deployment: &Deployment| {
+            builder
+                .create_pod_disruption_budget(&project, deployment, "demo", None)
+                .map(|pdb| pdb.spec.unwrap().min_available.unwrap())
+        };
+
+        assert_eq!(min_available(&builder, &deployment), None);
+
+        deployment.replicas = 3;
+        let pdb = builder
+            .create_pod_disruption_budget(&project, &deployment, "demo", None)
+            .unwrap();
+        assert_eq!(pdb.metadata.name.as_deref(), Some("demo-20260502-000000"));
+        assert_eq!(
+            pdb.spec.unwrap().selector.unwrap().match_labels,
+            Some(ResourceBuilder::deployment_labels(
+                &project,
+                &deployment,
+                None
+            ))
+        );
+        assert_eq!(
+            min_available(&builder, &deployment),
+            Some(IntOrString::Int(2))
+        );
+
+        builder.pod_disruption_budget_min_available = Some(1);
+        assert_eq!(
+            min_available(&builder, &deployment),
+            Some(IntOrString::Int(1))
+        );
+
+        // Never require all replicas, which would block node drains
+        builder.pod_disruption_budget_min_available = Some(5);
+        assert_eq!(
+            min_available(&builder, &deployment),
+            Some(IntOrString::Int(2))
+        );
     }
 }
 
@@ -7,6 +7,10 @@ use axum::{
 pub fn deployment_routes() -> Router<AppState> {
     Router::new()
         .route("/deployments", post(super::handlers::create_deployment))
+        .route(
+            "/deployments/dry-run",
+            post(super::handlers::dry_run_deployment),
+        )
         .route(
             "/deployments/{deployment_id}/status",
             patch(super::handlers::update_deployment_status),
@@ -23,10 +27,22 @@ pub fn deployment_routes() -> Router<AppState> {
             "/projects/{project_name}/deployments/stop",
             post(super::handlers::stop_deployments_by_group),
         )
+        .route(
+            "/projects/{project_name}/deployments/rollback-preview",
+            get(super::handlers::preview_rollback),
+        )
+        .route(
+            "/projects/{project_name}/deployments/diff",
+            get(super::handlers::diff_deployments),
+        )
         .route(
             "/projects/{project_name}/deployments/{deployment_id}",
             get(super::handlers::get_deployment_by_project),
         )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/env-diff",
+            get(super::handlers::diff_deployment_env),
+        )
         .route(
             "/projects/{project_name}/deployments/{deployment_id}/status",
             patch(super::handlers::update_deployment_status_by_project),
@@ -35,6 +51,34 @@ pub fn deployment_routes() -> Router<AppState> {
             "/projects/{project_name}/deployments/{deployment_id}/stop",
             post(super::handlers::stop_deployment),
         )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/cancel",
+            post(super::handlers::cancel_deployment),
+        )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/promote",
+            post(super::handlers::promote_deployment),
+        )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/scale",
+            post(super::handlers::scale_deployment),
+        )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/restart",
+            post(super::handlers::restart_deployment),
+        )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/renew",
+            post(super::handlers::renew_deployment),
+        )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/events",
+            get(super::handlers::list_deployment_events),
+        )
+        .route(
+            "/projects/{project_name}/deployments/{deployment_id}/history",
+            get(super::handlers::list_deployment_history),
+        )
         .route(
             "/projects/{project_name}/deployments/{deployment_id}/logs",
             get(super::handlers::stream_deployment_logs),
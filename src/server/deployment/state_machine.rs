@@ -176,9 +176,29 @@ mod tests {
             first_healthy_at: None,
             job_url: None,
             pull_request_url: None,
+            message: None,
             replicas: 1,
             cpu: "500m".to_string(),
             memory: "256Mi".to_string(),
+            cpu_limit: None,
+            memory_limit: None,
+            health_check: None,
+            init_container: None,
+            protocol: "http".to_string(),
+            labels: None,
+            annotations: None,
+            command: None,
+            args: None,
+            ingress_annotations: None,
+            strategy: "rolling".to_string(),
+            drain_period_seconds: None,
+            volume: None,
+            scheduling: None,
+            image_pull_policy: None,
+            rate_limit: None,
+            extra_ports: None,
+            service_account_name: None,
+            restarted_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -1,10 +1,13 @@
 use chrono::Utc;
+use sqlx::PgPool;
 use tracing::error;
+use uuid::Uuid;
 
 use crate::db::deployments as db_deployments;
 use crate::db::env_vars as db_env_vars;
 use crate::db::models::{Deployment, Project};
 use crate::server::error::{ServerError, ServerErrorExt};
+use crate::server::extensions::registry::ExtensionRegistry;
 use crate::server::extensions::InjectedEnvVarValue;
 use crate::server::state::AppState;
 
@@ -84,9 +87,11 @@ pub async fn get_deployment_image_tag(
 /// Create a deployment and invoke extension hooks
 ///
 /// This is the single code path for creating deployments. It:
-/// 1. Creates the deployment record in the database
+/// 1. Creates the deployment record in the database, rejecting it with
+///    `429 Too Many Requests` if the project is at its active deployment quota
 /// 2. Invokes before_deployment hooks for all registered extensions
-/// 3. Marks the deployment as failed if any extension hook fails
+/// 3. Marks the deployment as failed if any extension hook fails, with the
+///    extension's error as the deployment's `error_message`
 ///
 /// # Arguments
 /// * `state` - AppState containing database pool and extension registry
@@ -100,29 +105,69 @@ pub async fn create_deployment_with_hooks(
     params: db_deployments::CreateDeploymentParams<'_>,
     project: &Project,
 ) -> Result<Deployment, ServerError> {
-    // Extract deployment_group before moving params (needed for extension hooks)
-    let deployment_group = params.deployment_group.to_string();
+    // Create the deployment record; the quota check and insert happen in one transaction
+    let quota = active_deployment_quota(
+        project,
+        state.server_settings.max_active_deployments_per_project,
+    );
+    let deployment = match quota {
+        None => db_deployments::create(&state.db_pool, params)
+            .await
+            .internal_err("Failed to create deployment")?,
+        Some(quota) => db_deployments::create_within_quota(&state.db_pool, params, quota)
+            .await
+            .internal_err("Failed to create deployment")?
+            .ok_or_else(|| {
+                ServerError::too_many_requests(format!(
+                    "Project '{}' has reached its quota of {} active deployment(s). \
+                     Stop old deployments (e.g. `rise deployment stop --group <group>`) before creating new ones.",
+                    project.name, quota
+                ))
+            })?,
+    };
 
-    // Create the deployment record
-    let deployment = db_deployments::create(&state.db_pool, params)
-        .await
-        .internal_err("Failed to create deployment")?;
+    run_before_deployment_hooks(
+        &state.db_pool,
+        &state.extension_registry,
+        project.id,
+        &deployment,
+    )
+    .await?;
+
+    Ok(deployment)
+}
+
+/// The project's quota of active deployments: its own `max_active_deployments`, or
+/// the server-wide default. `None` means unlimited.
+fn active_deployment_quota(project: &Project, default_quota: Option<u32>) -> Option<i64> {
+    project
+        .max_active_deployments
+        .map(i64::from)
+        .or(default_quota.map(i64::from))
+}
 
-    // Call before_deployment hooks for all registered extensions
-    for (_, extension) in state.extension_registry.iter() {
+/// Invoke `before_deployment` hooks for all registered extensions
+///
+/// Writes the returned env vars to the deployment. If a hook fails, the deployment is
+/// marked as failed with the extension's error prefixed by its type (e.g.,
+/// `"[aws-rds-postgres] subnet group not found"`), and the same message is returned
+/// to the client.
+async fn run_before_deployment_hooks(
+    pool: &PgPool,
+    extension_registry: &ExtensionRegistry,
+    project_id: Uuid,
+    deployment: &Deployment,
+) -> Result<(), ServerError> {
+    for (_, extension) in extension_registry.iter() {
         let vars = match extension
-            .before_deployment(project.id, &deployment_group)
+            .before_deployment(project_id, &deployment.deployment_group)
             .await
         {
             Ok(vars) => vars,
             Err(e) => {
-                let error_msg = format!(
-                    "Extension type '{}' failed: {}",
-                    extension.extension_type(),
-                    e
-                );
+                let error_msg = format!("[{}] {:#}", extension.extension_type(), e);
                 if let Err(mark_err) =
-                    db_deployments::mark_failed(&state.db_pool, deployment.id, &error_msg).await
+                    db_deployments::mark_failed(pool, deployment.id, &error_msg).await
                 {
                     error!(
                         "Failed to mark deployment as failed after extension error: {:?}",
@@ -130,13 +175,8 @@ pub async fn create_deployment_with_hooks(
                     );
                 }
 
-                return Err(ServerError::internal_anyhow(
-                    e,
-                    format!(
-                        "Extension type '{}' before_deployment hook failed",
-                        extension.extension_type()
-                    ),
-                ));
+                return Err(ServerError::internal_anyhow(e, error_msg)
+                    .with_context("deployment_id", &deployment.deployment_id));
             }
         };
 
@@ -149,7 +189,7 @@ pub async fn create_deployment_with_hooks(
             };
 
             if let Err(e) = db_env_vars::upsert_deployment_env_var(
-                &state.db_pool,
+                pool,
                 deployment.id,
                 &var.key,
                 &value,
@@ -161,7 +201,7 @@ pub async fn create_deployment_with_hooks(
             {
                 let error_msg = format!("Failed to write env var '{}'", var.key);
                 if let Err(mark_err) =
-                    db_deployments::mark_failed(&state.db_pool, deployment.id, &error_msg).await
+                    db_deployments::mark_failed(pool, deployment.id, &error_msg).await
                 {
                     error!(
                         "Failed to mark deployment as failed after env var write error: {:?}",
@@ -174,5 +214,166 @@ pub async fn create_deployment_with_hooks(
         }
     }
 
-    Ok(deployment)
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{DeploymentStatus, ProjectStatus};
+    use crate::db::{projects, users};
+    use crate::server::extensions::{Extension, InjectedEnvVar};
+    use anyhow::Context;
+    use async_trait::async_trait;
+    use axum::http::StatusCode;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_active_deployment_quota_prefers_project_override() {
+        let mut project = Project {
+            id: Uuid::new_v4(),
+            name: "quota".to_string(),
+            status: ProjectStatus::Running,
+            access_class: "public".to_string(),
+            owner_user_id: None,
+            owner_team_id: None,
+            finalizers: vec![],
+            source_url: None,
+            force_https: None,
+            hsts_max_age: None,
+            max_active_deployments: None,
+            group_ttls: None,
+            namespace: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(active_deployment_quota(&project, None), None);
+        assert_eq!(active_deployment_quota(&project, Some(2)), Some(2));
+
+        project.max_active_deployments = Some(3);
+        assert_eq!(active_deployment_quota(&project, Some(2)), Some(3));
+    }
+
+    /// Extension whose `before_deployment` hook always fails
+    struct FailingExtension;
+
+    #[async_trait]
+    impl Extension for FailingExtension {
+        fn extension_type(&self) -> &str {
+            "aws-rds-postgres"
+        }
+
+        fn display_name(&self) -> &str {
+            "Failing extension"
+        }
+
+        async fn validate_spec(&self, _spec: &Value) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn start(&self) {}
+
+        async fn before_deployment(
+            &self,
+            _project_id: Uuid,
+            _deployment_group: &str,
+        ) -> anyhow::Result<Vec<InjectedEnvVar>> {
+            Err(anyhow::anyhow!("subnet group not found")).context("Failed to provision database")
+        }
+
+        fn format_status(&self, _status: &Value) -> String {
+            String::new()
+        }
+
+        fn description(&self) -> &str {
+            ""
+        }
+
+        fn documentation(&self) -> &str {
+            ""
+        }
+
+        fn spec_schema(&self) -> Value {
+            Value::Null
+        }
+    }
+
+    #[sqlx::test]
+    async fn failing_extension_hook_marks_deployment_failed(pool: PgPool) {
+        let user = users::create(&pool, "hooks-test@example.com")
+            .await
+            .unwrap();
+        let project = projects::create(
+            &pool,
+            "hooks-project",
+            ProjectStatus::Stopped,
+            "public".to_string(),
+            Some(user.id),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let deployment = db_deployments::create(
+            &pool,
+            db_deployments::CreateDeploymentParams {
+                deployment_id: "20260101-000000",
+                project_id: project.id,
+                created_by_id: user.id,
+                status: DeploymentStatus::Pending,
+                image: None,
+                image_digest: None,
+                rolled_back_from_deployment_id: None,
+                deployment_group: "default",
+                environment_id: None,
+                expires_at: None,
+                http_port: 8080,
+                is_active: false,
+                job_url: None,
+                pull_request_url: None,
+                message: None,
+                replicas: 1,
+                cpu: "500m",
+                memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let mut registry = ExtensionRegistry::new();
+        registry.register_type(Arc::new(FailingExtension));
+
+        let err = run_before_deployment_hooks(&pool, &registry, project.id, &deployment)
+            .await
+            .unwrap_err();
+        let expected = "[aws-rds-postgres] Failed to provision database: subnet group not found";
+        assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.message, expected);
+
+        let deployment = db_deployments::find_by_id(&pool, deployment.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(deployment.status, DeploymentStatus::Failed);
+        assert_eq!(deployment.error_message.as_deref(), Some(expected));
+    }
 }
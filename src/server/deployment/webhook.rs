@@ -16,7 +16,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use chrono::Utc;
-use k8s_openapi::api::apps::v1::Deployment as K8sDeployment;
+use k8s_openapi::api::apps::v1::{Deployment as K8sDeployment, ReplicaSet};
 use k8s_openapi::api::core::v1::{EnvVar, Secret};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use k8s_openapi::ByteString;
@@ -31,12 +31,18 @@ use crate::db::{
     deployments as db_deployments, env_vars as db_env_vars, environments as db_environments,
     projects as db_projects,
 };
+use crate::rise_toml::DeploymentStrategy;
+use crate::server::deployment::controller::metadata::{
+    ContainerInfo, ContainerState, ControllerMetadata, PodCondition, PodInfo, PodStatus,
+};
 use crate::server::deployment::crd;
+use crate::server::deployment::env_interpolation::{self, CircularReference, EnvValue};
 use crate::server::deployment::resource_builder::{
-    ResourceBuilder, ANNOTATION_ENV_SECRET_HASH, ANNOTATION_LAST_REFRESH, IMAGE_PULL_SECRET_NAME,
-    IRRECOVERABLE_CONTAINER_REASONS, LABEL_DEPLOYMENT_ID,
+    ResourceBuilder, ANNOTATION_ENV_SECRET_HASH, ANNOTATION_LAST_REFRESH,
+    IRRECOVERABLE_CONTAINER_REASONS, LABEL_DEPLOYMENT_ID, LABEL_MANAGED_BY, LABEL_PROJECT,
 };
 use crate::server::deployment::state_machine;
+use crate::server::metrics;
 use crate::server::state::AppState;
 
 // ── Metacontroller webhook protocol types ──────────────────────────────
@@ -67,6 +73,10 @@ pub struct ObservedChildren {
     pub ingresses: HashMap<String, serde_json::Value>,
     #[serde(rename = "NetworkPolicy.networking.k8s.io/v1", default)]
     pub network_policies: HashMap<String, serde_json::Value>,
+    #[serde(rename = "PodDisruptionBudget.policy/v1", default)]
+    pub pod_disruption_budgets: HashMap<String, serde_json::Value>,
+    #[serde(rename = "PersistentVolumeClaim.v1", default)]
+    pub persistent_volume_claims: HashMap<String, serde_json::Value>,
 }
 
 /// Metacontroller sync response
@@ -101,15 +111,20 @@ pub struct FinalizeResponse {
 const DEPLOYING_TIMEOUT_MINUTES: i64 = 5;
 /// Duration a deployment can be in pre-Pushed states before timing out
 const PRE_PUSHED_TIMEOUT_MINUTES: i64 = 10;
-/// Duration after which image pull secret is refreshed (6 hours)
-const SECRET_REFRESH_HOURS: i64 = 6;
 /// Maximum number of terminating/terminated pods to carry forward in controller_metadata
 const MAX_INACTIVE_PODS: usize = 5;
+/// Seconds to wait for a pod's response to the HTTP health check by default
+const DEFAULT_HTTP_HEALTH_CHECK_TIMEOUT_SECS: u64 = 5;
+/// Upper bound for probing all pods of a deployment, so the sync webhook answers
+/// well within Metacontroller's hook timeout
+const HTTP_HEALTH_CHECK_DEADLINE: std::time::Duration = std::time::Duration::from_secs(5);
+/// Seconds a superseded deployment keeps running after a blue-green switch by default
+const DEFAULT_DRAIN_PERIOD_SECS: i64 = 300;
 
 #[derive(Debug, Default)]
-struct ResolvedDeploymentEnvVars {
-    plain_env_vars: Vec<EnvVar>,
-    secret_env_vars: BTreeMap<String, ByteString>,
+pub(crate) struct ResolvedDeploymentEnvVars {
+    pub(crate) plain_env_vars: Vec<EnvVar>,
+    pub(crate) secret_env_vars: BTreeMap<String, ByteString>,
 }
 
 #[derive(Debug)]
@@ -163,7 +178,11 @@ pub async fn handle_sync(
         }
     };
 
-    match process_sync(&state, &project_name, &request.children).await {
+    let started = std::time::Instant::now();
+    let result = process_sync(&state, &project_name, &request.children).await;
+    metrics::record_reconcile("kubernetes", started.elapsed(), result.is_err());
+
+    match result {
         Ok(response) => (StatusCode::OK, Json(response)).into_response(),
         Err(e) => {
             error!(project = %project_name, "Sync webhook error: {:?}", e);
@@ -254,15 +273,55 @@ async fn process_sync(
     )
     .await?;
 
+    // 7. Sweep ReplicaSets left behind by deployments in terminal states
+    if resource_builder.cleanup_terminal_replica_sets {
+        sweep_terminal_replica_sets(state, resource_builder, &project, &all_deployments).await;
+    }
+
     Ok(SyncResponse {
         status: serde_json::json!({
             "lastSyncTime": Utc::now().to_rfc3339(),
         }),
+        resync_after_seconds: resync_after_seconds(
+            &all_deployments,
+            resource_builder.in_flight_resync_secs,
+            Utc::now(),
+        ),
         children,
-        resync_after_seconds: None,
     })
 }
 
+/// Resync hint for a project: soon while a deployment is in flight or about to
+/// expire, otherwise `None` so Metacontroller's regular resync period applies.
+fn resync_after_seconds(
+    deployments: &[Deployment],
+    in_flight_resync_secs: f64,
+    now: chrono::DateTime<Utc>,
+) -> Option<f64> {
+    let in_flight = deployments.iter().any(|d| {
+        matches!(
+            d.status,
+            DeploymentStatus::Pushed
+                | DeploymentStatus::Deploying
+                | DeploymentStatus::Cancelling
+                | DeploymentStatus::Terminating
+        ) && !is_draining(d, now)
+    });
+    // Wake up when a deployment expires or a blue-green drain period ends
+    let next_expiry = deployments
+        .iter()
+        .flat_map(|d| [d.expires_at, drain_deadline(d)])
+        .flatten()
+        .map(|expires_at| ((expires_at - now).num_milliseconds() as f64 / 1000.0).max(0.0))
+        .min_by(f64::total_cmp);
+
+    let in_flight = in_flight.then_some(in_flight_resync_secs.max(0.0));
+    match (in_flight, next_expiry) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
 /// Inspect the observed Kubernetes state for each non-terminal deployment and
 /// advance its status: Pushed → Deploying, Deploying → Healthy/Failed, timeouts,
 /// expiration, and cancellation.
@@ -297,15 +356,23 @@ async fn perform_status_transitions(
         }
 
         // Handle Terminating — mark as terminal based on reason
-        // (Metacontroller will delete the K8s Deployment since we won't return it)
+        // (Metacontroller will delete the K8s Deployment since we won't return it).
+        // Superseded blue-green deployments stay Terminating until their drain period ends.
         if deployment.status == DeploymentStatus::Terminating {
-            complete_termination(state, deployment, project).await?;
+            if !is_draining(deployment, Utc::now()) {
+                complete_termination(state, deployment, project).await?;
+            }
             continue;
         }
 
         // For Pushed/Deploying/Healthy/Unhealthy — check observed K8s Deployment
         match deployment.status {
             DeploymentStatus::Pushed => {
+                // Stay in Pushed while the concurrency limits are reached
+                if !may_start_deploying(state, deployment).await? {
+                    continue;
+                }
+
                 // Transition Pushed → Deploying
                 info!(
                     deployment_id = %deployment.deployment_id,
@@ -321,7 +388,11 @@ async fn perform_status_transitions(
             }
 
             DeploymentStatus::Deploying => {
-                check_deploying_timeout(state, deployment, project).await?;
+                // A timed-out deployment must not be promoted in the same pass,
+                // otherwise traffic would switch to a deployment just marked Failed
+                if check_deploying_timeout(state, deployment, project).await? {
+                    continue;
+                }
                 check_deployment_health_from_observed(state, deployment, project, observed).await?;
             }
 
@@ -343,7 +414,10 @@ async fn perform_status_transitions(
 }
 
 /// Check if a pre-pushed deployment has timed out
-async fn check_pre_pushed_timeout(state: &AppState, deployment: &Deployment) -> anyhow::Result<()> {
+pub(crate) async fn check_pre_pushed_timeout(
+    state: &AppState,
+    deployment: &Deployment,
+) -> anyhow::Result<()> {
     let elapsed = Utc::now().signed_duration_since(deployment.created_at);
     if elapsed > chrono::Duration::minutes(PRE_PUSHED_TIMEOUT_MINUTES) {
         warn!(
@@ -363,32 +437,89 @@ async fn check_pre_pushed_timeout(state: &AppState, deployment: &Deployment) ->
     Ok(())
 }
 
-/// Check if a deploying deployment has timed out
-async fn check_deploying_timeout(
+/// Check whether a Pushed deployment may move to Deploying under the configured
+/// concurrency limits. Deferred deployments stay in Pushed and are retried on the
+/// next sync, which comes quickly because Pushed counts as in flight.
+pub(crate) async fn may_start_deploying(
     state: &AppState,
     deployment: &Deployment,
-    project: &Project,
-) -> anyhow::Result<()> {
-    if let Some(deploying_started_at) = deployment.deploying_started_at {
-        let elapsed = Utc::now().signed_duration_since(deploying_started_at);
-        if elapsed > chrono::Duration::minutes(DEPLOYING_TIMEOUT_MINUTES) {
-            let error_msg = format!(
-                "Deployment timed out after {} seconds in Deploying state",
-                elapsed.num_seconds()
+) -> anyhow::Result<bool> {
+    within_concurrency_limits(&state.db_pool, state.deployment_concurrency, deployment).await
+}
+
+/// Whether `deployment` may start deploying given the deployments already in Deploying
+async fn within_concurrency_limits(
+    pool: &sqlx::PgPool,
+    limits: crate::server::settings::DeploymentConcurrencyLimits,
+    deployment: &Deployment,
+) -> anyhow::Result<bool> {
+    if let Some(limit) = limits.per_project {
+        let deploying = db_deployments::count_deploying(pool, Some(deployment.project_id)).await?;
+        if deploying >= i64::from(limit) {
+            debug!(
+                deployment_id = %deployment.deployment_id,
+                "Deferring deployment: {} deployments of the project already deploying (limit {})",
+                deploying,
+                limit
             );
-            warn!(
+            return Ok(false);
+        }
+    }
+
+    if let Some(limit) = limits.global {
+        let deploying = db_deployments::count_deploying(pool, None).await?;
+        if deploying >= i64::from(limit) {
+            debug!(
                 deployment_id = %deployment.deployment_id,
-                "{}", error_msg
+                "Deferring deployment: {} deployments already deploying (limit {})",
+                deploying,
+                limit
             );
-            db_deployments::mark_failed(&state.db_pool, deployment.id, &error_msg).await?;
-            db_projects::update_calculated_status(&state.db_pool, project.id).await?;
+            return Ok(false);
         }
     }
-    Ok(())
+
+    Ok(true)
+}
+
+/// Check if a deploying deployment has timed out, marking it as Failed if so.
+///
+/// Returns `true` if the deployment timed out. Traffic is only switched once a
+/// deployment is fully ready, so a deployment that times out never received
+/// traffic and the previously active deployment keeps serving.
+pub(crate) async fn check_deploying_timeout(
+    state: &AppState,
+    deployment: &Deployment,
+    project: &Project,
+) -> anyhow::Result<bool> {
+    let Some(elapsed) = deploying_timeout_elapsed(deployment, Utc::now()) else {
+        return Ok(false);
+    };
+    let error_msg = format!(
+        "Deployment did not become ready within {} seconds in Deploying state; \
+         traffic was not switched",
+        elapsed.num_seconds()
+    );
+    warn!(
+        deployment_id = %deployment.deployment_id,
+        "{}", error_msg
+    );
+    db_deployments::mark_failed(&state.db_pool, deployment.id, &error_msg).await?;
+    db_projects::update_calculated_status(&state.db_pool, project.id).await?;
+    Ok(true)
+}
+
+/// Time spent in Deploying if it exceeds the deploying timeout
+fn deploying_timeout_elapsed(
+    deployment: &Deployment,
+    now: chrono::DateTime<Utc>,
+) -> Option<chrono::Duration> {
+    let elapsed = now.signed_duration_since(deployment.deploying_started_at?);
+    (elapsed > chrono::Duration::minutes(DEPLOYING_TIMEOUT_MINUTES)).then_some(elapsed)
 }
 
 /// Complete termination: move from Terminating to the appropriate terminal state.
-async fn complete_termination(
+pub(crate) async fn complete_termination(
     state: &AppState,
     deployment: &Deployment,
     project: &Project,
@@ -470,6 +601,13 @@ async fn check_deployment_health_from_observed(
         .as_ref()
         .and_then(|s| s.ready_replicas)
         .unwrap_or(0);
+    let updated_replicas = observed_k8s
+        .status
+        .as_ref()
+        .and_then(|s| s.updated_replicas)
+        .unwrap_or(0);
+
+    let mut metadata = ControllerMetadata::from_value(&deployment.controller_metadata);
 
     // Check for pod-level errors and collect full pod status via kube-rs
     // (Metacontroller only gives us the Deployment, not individual pods)
@@ -479,28 +617,26 @@ async fn check_deployment_health_from_observed(
         deployment,
         desired_replicas,
         ready_replicas,
-        &deployment.controller_metadata,
+        metadata.pod_status.as_ref(),
     )
     .await;
 
     // Update controller_metadata with pod status
-    if let Some(ref pod_status) = pod_check.pod_status {
+    if let Some(pod_status) = pod_check.pod_status {
         let is_healthy = deployment.status == DeploymentStatus::Healthy
             || (deployment.status == DeploymentStatus::Deploying
                 && !pod_check.has_error
                 && ready_replicas >= desired_replicas
                 && desired_replicas > 0);
 
-        let metadata = serde_json::json!({
-            "pod_status": pod_status,
-            "health": {
-                "last_check": Utc::now().to_rfc3339(),
-                "healthy": is_healthy,
-            },
-        });
-        if let Err(e) =
-            db_deployments::update_controller_metadata(&state.db_pool, deployment.id, &metadata)
-                .await
+        metadata.pod_status = Some(pod_status);
+        metadata.set_health(is_healthy);
+        if let Err(e) = db_deployments::update_controller_metadata(
+            &state.db_pool,
+            deployment.id,
+            &metadata.to_value(),
+        )
+        .await
         {
             warn!(
                 deployment_id = %deployment.deployment_id,
@@ -510,6 +646,23 @@ async fn check_deployment_health_from_observed(
     }
 
     let is_ready = ready_replicas >= desired_replicas && desired_replicas > 0;
+    // A rollout (e.g. after `rise deployment restart`) is only complete once all
+    // pods run the current template; old pods keep serving until then
+    let rollout_complete = is_ready && updated_replicas >= desired_replicas;
+
+    // Optional HTTP check on top of pod readiness (`[deploy.health_check] path`)
+    let http_check_error = match http_health_check(deployment) {
+        Some((health_check, path)) if is_ready && !pod_check.has_error => {
+            check_pods_http_health(
+                &health_check,
+                &path,
+                deployment.http_port,
+                &pod_check.ready_pods,
+            )
+            .await
+        }
+        _ => None,
+    };
 
     match deployment.status {
         DeploymentStatus::Deploying => {
@@ -521,9 +674,16 @@ async fn check_deployment_health_from_observed(
                     deployment_id = %deployment.deployment_id,
                     "Deployment has irrecoverable pod error: {}", error_msg
                 );
+                metrics::record_health_check_failure("kubernetes");
                 db_deployments::mark_failed(&state.db_pool, deployment.id, &error_msg).await?;
                 db_projects::update_calculated_status(&state.db_pool, project.id).await?;
-            } else if is_ready {
+            } else if let Some(ref msg) = http_check_error {
+                // Keep waiting: the deploying timeout fails deployments that never pass
+                debug!(
+                    deployment_id = %deployment.deployment_id,
+                    "Pods are ready but the HTTP health check fails: {}", msg
+                );
+            } else if rollout_complete {
                 info!(
                     deployment_id = %deployment.deployment_id,
                     "Deployment is ready ({}/{} replicas), marking as Healthy",
@@ -534,22 +694,30 @@ async fn check_deployment_health_from_observed(
             }
         }
 
-        DeploymentStatus::Healthy if pod_check.has_error || !is_ready => {
-            let msg = pod_check.error_message.unwrap_or_else(|| {
-                format!(
-                    "Deployment unhealthy: {}/{} replicas ready",
-                    ready_replicas, desired_replicas
-                )
-            });
+        DeploymentStatus::Healthy
+            if pod_check.has_error || !is_ready || http_check_error.is_some() =>
+        {
+            let msg = pod_check
+                .error_message
+                .or(http_check_error)
+                .unwrap_or_else(|| {
+                    format!(
+                        "Deployment unhealthy: {}/{} replicas ready",
+                        ready_replicas, desired_replicas
+                    )
+                });
             warn!(
                 deployment_id = %deployment.deployment_id,
                 "Healthy deployment is now unhealthy: {}", msg
             );
+            metrics::record_health_check_failure("kubernetes");
             db_deployments::mark_unhealthy(&state.db_pool, deployment.id, msg).await?;
             db_projects::update_calculated_status(&state.db_pool, project.id).await?;
         }
 
-        DeploymentStatus::Unhealthy if !pod_check.has_error && is_ready => {
+        DeploymentStatus::Unhealthy
+            if !pod_check.has_error && rollout_complete && http_check_error.is_none() =>
+        {
             info!(
                 deployment_id = %deployment.deployment_id,
                 "Unhealthy deployment has recovered, marking as Healthy"
@@ -564,6 +732,20 @@ async fn check_deployment_health_from_observed(
     Ok(())
 }
 
+/// The deployment's HTTP health check configuration and path, if it has one.
+/// TCP deployments never get an HTTP check.
+fn http_health_check(
+    deployment: &Deployment,
+) -> Option<(crate::rise_toml::HealthCheckConfig, String)> {
+    if ResourceBuilder::is_tcp(deployment) {
+        return None;
+    }
+    let health_check: crate::rise_toml::HealthCheckConfig =
+        serde_json::from_value(deployment.health_check.clone()?).ok()?;
+    let path = health_check.path.clone()?;
+    Some((health_check, path))
+}
+
 /// Result of checking pod status via kube-rs API.
 struct PodCheckResult {
     /// Whether any pod has an irrecoverable error
@@ -571,7 +753,48 @@ struct PodCheckResult {
     /// Error message if has_error is true
     error_message: Option<String>,
     /// Full pod status for storing in controller_metadata
-    pod_status: Option<serde_json::Value>,
+    pod_status: Option<PodStatus>,
+    /// Name and IP of each ready, non-terminating pod (targets of the HTTP health check)
+    ready_pods: Vec<(String, String)>,
+}
+
+/// Describe the first init container that is failing irrecoverably, if any.
+///
+/// Init containers that exit non-zero are restarted with back-off, so a crash-looping
+/// init container shows up as `CrashLoopBackOff` (or as terminated after repeated
+/// restarts). The message includes the last exit code so migration failures are easy
+/// to spot.
+fn init_container_error(status: &k8s_openapi::api::core::v1::PodStatus) -> Option<String> {
+    for cs in status.init_container_statuses.as_ref()? {
+        let last_exit = cs
+            .last_state
+            .as_ref()
+            .and_then(|s| s.terminated.as_ref())
+            .map(|t| format!(" (last exit code: {})", t.exit_code))
+            .unwrap_or_default();
+        let Some(state) = cs.state.as_ref() else {
+            continue;
+        };
+        if let Some(waiting) = &state.waiting {
+            let reason = waiting.reason.as_deref().unwrap_or("");
+            if IRRECOVERABLE_CONTAINER_REASONS.contains(&reason) {
+                let message = waiting.message.as_deref().unwrap_or(reason);
+                return Some(format!(
+                    "Init container '{}' failed: {}: {}{}",
+                    cs.name, reason, message, last_exit
+                ));
+            }
+        } else if let Some(terminated) = &state.terminated {
+            if terminated.exit_code != 0 && cs.restart_count >= 3 {
+                let reason = terminated.reason.as_deref().unwrap_or("ContainerFailed");
+                return Some(format!(
+                    "Init container '{}' failed: {}: exit code {} (restarts: {})",
+                    cs.name, reason, terminated.exit_code, cs.restart_count
+                ));
+            }
+        }
+    }
+    None
 }
 
 /// Check pods for errors via direct kube-rs API call and collect full pod status.
@@ -586,7 +809,7 @@ async fn check_pod_errors_via_kube(
     deployment: &Deployment,
     desired_replicas: i32,
     ready_replicas: i32,
-    prev_controller_metadata: &serde_json::Value,
+    prev_pod_status: Option<&PodStatus>,
 ) -> PodCheckResult {
     let kube_client = match &state.kube_client {
         Some(client) => client,
@@ -595,6 +818,7 @@ async fn check_pod_errors_via_kube(
                 has_error: false,
                 error_message: None,
                 pod_status: None,
+                ready_pods: Vec::new(),
             }
         }
     };
@@ -606,6 +830,7 @@ async fn check_pod_errors_via_kube(
                 has_error: false,
                 error_message: None,
                 pod_status: None,
+                ready_pods: Vec::new(),
             }
         }
     };
@@ -629,13 +854,15 @@ async fn check_pod_errors_via_kube(
                 has_error: false,
                 error_message: None,
                 pod_status: None,
+                ready_pods: Vec::new(),
             };
         }
     };
 
     let mut has_error = false;
     let mut error_message: Option<String> = None;
-    let mut pod_infos: Vec<serde_json::Value> = Vec::new();
+    let mut pod_infos: Vec<PodInfo> = Vec::new();
+    let mut ready_pods: Vec<(String, String)> = Vec::new();
     let mut current_replicas: i32 = 0;
 
     for pod in &pods.items {
@@ -657,27 +884,41 @@ async fn check_pod_errors_via_kube(
             .to_string();
 
         // Collect pod conditions
-        let conditions: Vec<serde_json::Value> = pod
+        let conditions: Vec<PodCondition> = pod
             .status
             .as_ref()
             .and_then(|s| s.conditions.as_ref())
             .map(|conds| {
                 conds
                     .iter()
-                    .map(|c| {
-                        serde_json::json!({
-                            "type": c.type_,
-                            "status": c.status,
-                            "reason": c.reason,
-                            "message": c.message,
-                        })
+                    .map(|c| PodCondition {
+                        type_: c.type_.clone(),
+                        status: c.status.clone(),
+                        reason: c.reason.clone(),
+                        message: c.message.clone(),
                     })
                     .collect()
             })
             .unwrap_or_default();
 
+        let pod_ip = pod.status.as_ref().and_then(|s| s.pod_ip.clone());
+        let is_ready = conditions
+            .iter()
+            .any(|c| c.type_ == "Ready" && c.status == "True");
+        if let (false, true, Some(ip)) = (is_terminating, is_ready, pod_ip) {
+            ready_pods.push((pod_name.clone(), ip));
+        }
+
+        // A failing init container keeps the app container from ever starting
+        if !is_terminating && !has_error {
+            if let Some(message) = pod.status.as_ref().and_then(init_container_error) {
+                has_error = true;
+                error_message = Some(message);
+            }
+        }
+
         // Collect container statuses
-        let mut container_infos: Vec<serde_json::Value> = Vec::new();
+        let mut container_infos: Vec<ContainerInfo> = Vec::new();
         if let Some(container_statuses) = pod
             .status
             .as_ref()
@@ -696,16 +937,19 @@ async fn check_pod_errors_via_kube(
                             let message = waiting.message.as_deref().unwrap_or(reason);
                             error_message = Some(format!("{}: {}", reason, message));
                         }
-                        Some(serde_json::json!({
-                            "state_type": "waiting",
-                            "reason": waiting.reason,
-                            "message": waiting.message,
-                        }))
+                        Some(ContainerState {
+                            state_type: "waiting".to_string(),
+                            reason: waiting.reason.clone(),
+                            message: waiting.message.clone(),
+                            exit_code: None,
+                        })
                     } else if let Some(running) = &state.running {
-                        Some(serde_json::json!({
-                            "state_type": "running",
-                            "reason": running.started_at.as_ref().map(|t| t.0.to_string()),
-                        }))
+                        Some(ContainerState {
+                            state_type: "running".to_string(),
+                            reason: running.started_at.as_ref().map(|t| t.0.to_string()),
+                            message: None,
+                            exit_code: None,
+                        })
                     } else if let Some(terminated) = &state.terminated {
                         // Check terminated with too many restarts (skip for terminating pods)
                         if !is_terminating
@@ -722,12 +966,12 @@ async fn check_pod_errors_via_kube(
                                 reason, message, cs.restart_count
                             ));
                         }
-                        Some(serde_json::json!({
-                            "state_type": "terminated",
-                            "reason": terminated.reason,
-                            "message": terminated.message,
-                            "exit_code": terminated.exit_code,
-                        }))
+                        Some(ContainerState {
+                            state_type: "terminated".to_string(),
+                            reason: terminated.reason.clone(),
+                            message: terminated.message.clone(),
+                            exit_code: Some(terminated.exit_code),
+                        })
                     } else {
                         None
                     }
@@ -735,94 +979,118 @@ async fn check_pod_errors_via_kube(
                     None
                 };
 
-                container_infos.push(serde_json::json!({
-                    "name": cs.name,
-                    "ready": cs.ready,
-                    "restart_count": cs.restart_count,
-                    "state": state_info,
-                }));
+                container_infos.push(ContainerInfo {
+                    name: cs.name.clone(),
+                    ready: cs.ready,
+                    restart_count: cs.restart_count,
+                    state: state_info,
+                });
             }
         }
 
-        pod_infos.push(serde_json::json!({
-            "name": pod_name,
-            "phase": pod_phase,
-            "terminating": is_terminating,
-            "conditions": conditions,
-            "containers": container_infos,
-        }));
+        pod_infos.push(PodInfo {
+            name: pod_name,
+            phase: pod_phase,
+            terminating: is_terminating,
+            terminated: false,
+            conditions,
+            containers: container_infos,
+        });
     }
 
+    let mut pod_status = PodStatus {
+        desired_replicas,
+        ready_replicas,
+        current_replicas,
+        pods: pod_infos,
+        last_checked: Utc::now(),
+    };
+
     // Carry forward pods that were terminating/terminated in the previous snapshot
     // but are no longer in the K8s pod list — mark them as fully terminated.
-    // Keep at most MAX_INACTIVE_PODS inactive (terminating + terminated) pods total.
-    if let Some(prev_pods) = prev_controller_metadata
-        .get("pod_status")
-        .and_then(|ps| ps.get("pods"))
-        .and_then(|p| p.as_array())
-    {
-        let live_pod_names: std::collections::HashSet<String> = pod_infos
-            .iter()
-            .filter_map(|p| p.get("name").and_then(|n| n.as_str()).map(String::from))
-            .collect();
-
-        // Count live terminating pods already in the list
-        let mut inactive_count = pod_infos
-            .iter()
-            .filter(|p| {
-                p.get("terminating")
-                    .and_then(|v| v.as_bool())
-                    .unwrap_or(false)
-            })
-            .count();
-
-        for prev_pod in prev_pods {
-            if inactive_count >= MAX_INACTIVE_PODS {
-                break;
-            }
-            let was_terminating = prev_pod
-                .get("terminating")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            let was_terminated = prev_pod
-                .get("terminated")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-            let name = prev_pod.get("name").and_then(|n| n.as_str()).unwrap_or("");
-
-            if (was_terminating || was_terminated)
-                && !name.is_empty()
-                && !live_pod_names.contains(name)
-            {
-                // Pod is gone from K8s — carry forward with terminated: true
-                let mut carried = prev_pod.clone();
-                if let Some(obj) = carried.as_object_mut() {
-                    obj.insert("terminating".to_string(), serde_json::Value::Bool(false));
-                    obj.insert("terminated".to_string(), serde_json::Value::Bool(true));
-                }
-                pod_infos.push(carried);
-                inactive_count += 1;
-            }
-        }
+    if let Some(prev_pod_status) = prev_pod_status {
+        pod_status.carry_forward_terminated(prev_pod_status, MAX_INACTIVE_PODS);
     }
 
-    let pod_status = serde_json::json!({
-        "desired_replicas": desired_replicas,
-        "ready_replicas": ready_replicas,
-        "current_replicas": current_replicas,
-        "pods": pod_infos,
-        "last_checked": Utc::now().to_rfc3339(),
-    });
-
     PodCheckResult {
         has_error,
         error_message,
         pod_status: Some(pod_status),
+        ready_pods,
+    }
+}
+
+/// Run the project's HTTP health check (`health_check.path`) against each ready pod.
+///
+/// Returns an error message naming the pod and the status code it returned when a
+/// pod answers with an unexpected status or cannot be reached. Redirects are not
+/// followed, so a 3xx can be an expected status. Pods are probed concurrently, and
+/// all of them within [`HTTP_HEALTH_CHECK_DEADLINE`]. Pods are requested directly by
+/// IP, so this only works when the controller runs inside the cluster and may reach
+/// the pods.
+async fn check_pods_http_health(
+    health_check: &crate::rise_toml::HealthCheckConfig,
+    path: &str,
+    http_port: i32,
+    ready_pods: &[(String, String)],
+) -> Option<String> {
+    let timeout = std::time::Duration::from_secs(
+        health_check
+            .timeout
+            .unwrap_or(DEFAULT_HTTP_HEALTH_CHECK_TIMEOUT_SECS),
+    )
+    .min(HTTP_HEALTH_CHECK_DEADLINE);
+    let client = match reqwest::Client::builder()
+        .timeout(timeout)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return Some(format!("Failed to create health check client: {}", e)),
+    };
+
+    let probes = ready_pods.iter().map(|(pod_name, pod_ip)| {
+        let client = &client;
+        async move {
+            let url = format!("http://{}:{}{}", pod_ip, http_port, path);
+            match client.get(&url).send().await {
+                Ok(response) if health_check.is_expected_status(response.status().as_u16()) => None,
+                Ok(response) => Some(format!(
+                    "Health check GET {} on pod {} returned status {}",
+                    path,
+                    pod_name,
+                    response.status().as_u16()
+                )),
+                Err(e) => Some(format!(
+                    "Health check GET {} on pod {} failed: {}",
+                    path, pod_name, e
+                )),
+            }
+        }
+    });
+
+    match tokio::time::timeout(
+        HTTP_HEALTH_CHECK_DEADLINE,
+        futures::future::join_all(probes),
+    )
+    .await
+    {
+        // Report the first failing pod in pod order
+        Ok(results) => results.into_iter().flatten().next(),
+        Err(_) => Some(format!(
+            "Health check GET {} did not finish on all pods within {} seconds",
+            path,
+            HTTP_HEALTH_CHECK_DEADLINE.as_secs()
+        )),
     }
 }
 
 /// Handle a deployment becoming Healthy: mark active, supersede old deployments.
-async fn handle_deployment_became_healthy(
+///
+/// This is the only place a deployment becomes active, and the Service/Ingress of a
+/// group only ever point at the active deployment. Callers must therefore only call
+/// this once all replicas of the new deployment are ready.
+pub(crate) async fn handle_deployment_became_healthy(
     state: &AppState,
     deployment: &Deployment,
     project: &Project,
@@ -845,12 +1113,7 @@ async fn handle_deployment_became_healthy(
                 "Deployment {} replacing {} in group '{}', marking old as Terminating",
                 deployment.deployment_id, old_active.deployment_id, deployment.deployment_group
             );
-            db_deployments::mark_terminating(
-                &state.db_pool,
-                old_active.id,
-                TerminationReason::Superseded,
-            )
-            .await?;
+            supersede(state, deployment, &old_active).await?;
         }
     }
 
@@ -871,12 +1134,7 @@ async fn handle_deployment_became_healthy(
                 "Cleaning up non-active deployment {} in group '{}', marking as Terminating",
                 other.deployment_id, deployment.deployment_group
             );
-            db_deployments::mark_terminating(
-                &state.db_pool,
-                other.id,
-                TerminationReason::Superseded,
-            )
-            .await?;
+            supersede(state, deployment, &other).await?;
         }
     }
 
@@ -899,8 +1157,59 @@ async fn handle_deployment_became_healthy(
     Ok(())
 }
 
+/// Mark `old` as Terminating because `new` replaced it.
+///
+/// Under the blue-green strategy `old` keeps its pods for `new`'s drain period:
+/// traffic already goes to `new`, but in-flight requests can finish and the
+/// previous version stays warm. See [`is_draining`].
+async fn supersede(state: &AppState, new: &Deployment, old: &Deployment) -> anyhow::Result<()> {
+    db_deployments::mark_terminating(&state.db_pool, old.id, TerminationReason::Superseded, None)
+        .await?;
+
+    if DeploymentStrategy::from_db(&new.strategy) != DeploymentStrategy::BlueGreen {
+        return Ok(());
+    }
+
+    let drain_period = chrono::Duration::seconds(
+        new.drain_period_seconds
+            .map(i64::from)
+            .unwrap_or(DEFAULT_DRAIN_PERIOD_SECS),
+    );
+    let mut metadata = ControllerMetadata::from_value(&old.controller_metadata);
+    metadata.drain_until = Some(Utc::now() + drain_period);
+    db_deployments::update_controller_metadata(&state.db_pool, old.id, &metadata.to_value())
+        .await?;
+
+    info!(
+        deployment_id = %old.deployment_id,
+        "Blue-green switch to {}: keeping previous deployment running for {}s",
+        new.deployment_id,
+        drain_period.num_seconds()
+    );
+    Ok(())
+}
+
+/// End of the drain period of a deployment superseded by a blue-green deployment
+fn drain_deadline(deployment: &Deployment) -> Option<chrono::DateTime<Utc>> {
+    if deployment.status != DeploymentStatus::Terminating
+        || deployment.termination_reason != Some(TerminationReason::Superseded)
+    {
+        return None;
+    }
+    ControllerMetadata::from_value(&deployment.controller_metadata).drain_until
+}
+
+/// Whether a superseded deployment is still draining after a blue-green switch.
+///
+/// Draining deployments keep their K8s Deployment (see [`should_have_infrastructure`])
+/// but never receive traffic, because Services and Ingresses only select the active
+/// deployment of a group.
+fn is_draining(deployment: &Deployment, now: chrono::DateTime<Utc>) -> bool {
+    drain_deadline(deployment).is_some_and(|until| now < until)
+}
+
 /// Check for expired deployments
-async fn check_expirations(
+pub(crate) async fn check_expirations(
     state: &AppState,
     non_terminal: &[&Deployment],
     project: &Project,
@@ -922,6 +1231,7 @@ async fn check_expirations(
                     &state.db_pool,
                     deployment.id,
                     TerminationReason::Expired,
+                    None,
                 )
                 .await?;
                 db_projects::update_calculated_status(&state.db_pool, project.id).await?;
@@ -991,27 +1301,38 @@ async fn compute_desired_children(
     // 4. Per-environment ServiceAccounts
     let mut seen_environments: std::collections::HashSet<uuid::Uuid> =
         std::collections::HashSet::new();
+    let mut service_account_names: std::collections::HashSet<String> =
+        std::collections::HashSet::new();
     for deployment in &infra_deployments {
         if let Some(env_id) = deployment.environment_id {
             if seen_environments.insert(env_id) {
                 if let Some(environment) = environments.get(&env_id) {
-                    // Check if we should skip SA creation for production
-                    if resource_builder.use_default_service_account_for_production
-                        && environment.is_production
+                    if let Some(name) =
+                        pod_service_account_name(resource_builder, Some(environment))
                     {
-                        continue;
+                        let sa = resource_builder.create_service_account(
+                            project,
+                            &environment.name,
+                            &namespace,
+                        );
+                        service_account_names.insert(name);
+                        children.push(serde_json::to_value(&sa)?);
                     }
-                    let sa = resource_builder.create_service_account(
-                        project,
-                        &environment.name,
-                        &namespace,
-                    );
-                    children.push(serde_json::to_value(&sa)?);
                 }
             }
         }
     }
 
+    // 4b. ServiceAccounts from `[deploy.service_account]`, if Rise manages them
+    if resource_builder.create_deployment_service_accounts {
+        for name in deployment_service_account_names(&infra_deployments, &mut service_account_names)
+        {
+            let sa = resource_builder
+                .create_deployment_service_account(project, &name, &namespace, None);
+            children.push(serde_json::to_value(&sa)?);
+        }
+    }
+
     // 5. K8s Deployments, Services, Ingresses, NetworkPolicies per deployment/group
     // Track which deployment groups have active deployments that are ready for phase 2.
     let mut active_by_group: HashMap<String, &Deployment> = HashMap::new();
@@ -1024,10 +1345,72 @@ async fn compute_desired_children(
             .map(|env| env.name.clone())
     };
 
+    // PersistentVolumeClaims — one per group whose deployments declare a volume
+    let mut claim_names: std::collections::HashSet<String> = std::collections::HashSet::new();
+    for deployment in &infra_deployments {
+        let env_name = env_name_for(deployment);
+        if let Some(pvc) = resource_builder.create_persistent_volume_claim(
+            project,
+            deployment,
+            &namespace,
+            env_name.as_deref(),
+        ) {
+            if claim_names.insert(ResourceBuilder::persistent_volume_claim_name(
+                project, deployment,
+            )) {
+                children.push(serde_json::to_value(&pvc)?);
+            }
+        }
+    }
+    // Claims outlive their deployments: keep returning every observed claim so
+    // Metacontroller only deletes them with the project (finalize returns no children)
+    let mut retained_claims: Vec<&serde_json::Value> = observed
+        .persistent_volume_claims
+        .values()
+        .filter(|pvc| {
+            pvc["metadata"]["name"]
+                .as_str()
+                .is_some_and(|name| !claim_names.contains(name))
+        })
+        .collect();
+    retained_claims.sort_by(|a, b| {
+        a["metadata"]["name"]
+            .as_str()
+            .cmp(&b["metadata"]["name"].as_str())
+    });
+    for pvc in retained_claims {
+        children.push(retained_persistent_volume_claim(pvc));
+    }
+
+    // Hand each group's `ReadWriteOnce` claim over: the previous deployments are
+    // scaled to zero and the incoming one only gets pods once theirs are gone
+    let mut replica_overrides = HashMap::new();
+    for handover in volume_handovers(&infra_deployments) {
+        let mut previous_has_pods = false;
+        for previous in &handover.previous {
+            previous_has_pods |= deployment_has_pods(state, &namespace, previous).await;
+        }
+        replica_overrides.extend(handover_replicas(&handover, previous_has_pods));
+    }
+
     // K8s Deployments — one per non-terminal, infrastructure-bearing deployment
     for deployment in &infra_deployments {
         let env_name = env_name_for(deployment);
-        let env_vars = load_env_vars(state, project, deployment).await?;
+        let env_vars = match load_env_vars(state, project, deployment).await {
+            Ok(env_vars) => env_vars,
+            // A reference loop only breaks this deployment; keep reconciling the others
+            Err(e) if e.is::<CircularReference>() => {
+                let error_msg = e.to_string();
+                warn!(
+                    deployment_id = %deployment.deployment_id,
+                    "{}, marking deployment as Failed", error_msg
+                );
+                db_deployments::mark_failed(&state.db_pool, deployment.id, &error_msg).await?;
+                db_projects::update_calculated_status(&state.db_pool, project.id).await?;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
 
         let secret_env = if env_vars.secret_env_vars.is_empty() {
             None
@@ -1074,12 +1457,10 @@ async fn compute_desired_children(
             }
         }
 
-        let (secret_env_name, secret_env_hash) = match secret_env {
-            Some(secret_env) => (Some(secret_env.secret_name), Some(secret_env.secret_hash)),
-            None => (None, None),
-        };
+        let secret_env =
+            secret_env.map(|secret_env| (secret_env.secret_name, secret_env.secret_hash));
 
-        // Resolve image
+        // Image of redeploys is the one built for their source deployment
         let source_deployment_id =
             if let Some(source_id) = deployment.rolled_back_from_deployment_id {
                 db_deployments::find_by_id(&state.db_pool, source_id)
@@ -1088,150 +1469,522 @@ async fn compute_desired_children(
             } else {
                 None
             };
-        let image =
-            resource_builder.resolve_image(project, deployment, source_deployment_id.as_deref());
-
-        // Resolve service account name from preloaded environments
-        let sa_name = {
-            let env = deployment
-                .environment_id
-                .and_then(|id| environments.get(&id));
-            match env {
-                Some(environment)
-                    if resource_builder.use_default_service_account_for_production
-                        && environment.is_production =>
-                {
-                    None
-                }
-                Some(environment) => Some(ResourceBuilder::environment_service_account_name(
-                    &environment.name,
-                )),
-                None => None,
-            }
-        };
-
-        let k8s_deploy = resource_builder.create_k8s_deployment(
+        let environment = deployment
+            .environment_id
+            .and_then(|id| environments.get(&id));
+        children.extend(deployment_workload_children(
+            resource_builder,
             project,
             deployment,
             &namespace,
-            &image,
-            deployment.http_port as u16,
+            environment,
+            source_deployment_id.as_deref(),
+            replica_overrides.get(&deployment.id).copied(),
             env_vars.plain_env_vars,
-            secret_env_name,
-            secret_env_hash,
-            sa_name,
-            env_name.as_deref(),
-        );
-        children.push(serde_json::to_value(&k8s_deploy)?);
+            secret_env,
+        )?);
 
         if deployment.is_active {
             active_by_group.insert(deployment.deployment_group.clone(), deployment);
         }
     }
 
-    // Services, Ingresses, NetworkPolicies — one per group with an active deployment
+    // Services, NetworkPolicies, Ingresses — one per group with an active deployment
+    // (no Ingresses for TCP deployments)
     let custom_domains =
         crate::db::custom_domains::list_project_custom_domains(&state.db_pool, project.id).await?;
     let valid_custom_domains = resource_builder.filter_valid_custom_domains(&custom_domains);
 
-    for (group, active_deployment) in &active_by_group {
-        let env_name = env_name_for(active_deployment);
-
-        // Service (selector points to the active deployment)
-        let service = resource_builder.create_service(
-            project,
-            active_deployment,
-            &namespace,
-            active_deployment.http_port as u16,
-            env_name.as_deref(),
-        );
-        children.push(serde_json::to_value(&service)?);
-
-        // Primary Ingress
-        let ingress = resource_builder.create_primary_ingress(
-            project,
-            active_deployment,
-            &namespace,
-            env_name.as_deref(),
-        )?;
-        children.push(serde_json::to_value(&ingress)?);
-
-        // Custom domain Ingress (only for production primary group)
+    for active_deployment in active_by_group.values() {
         let environment = active_deployment
             .environment_id
             .and_then(|id| environments.get(&id));
-
-        let is_production_primary = environment
-            .map(|env| {
-                env.is_production && env.primary_deployment_group.as_deref() == Some(group.as_str())
-            })
-            .unwrap_or(*group == crate::server::deployment::models::DEFAULT_DEPLOYMENT_GROUP);
-
-        if is_production_primary && !valid_custom_domains.is_empty() {
-            let custom_ingress = resource_builder.create_custom_domain_ingress(
-                project,
-                active_deployment,
-                &namespace,
-                &valid_custom_domains,
-                env_name.as_deref(),
-            )?;
-            children.push(serde_json::to_value(&custom_ingress)?);
-        }
-
-        // NetworkPolicy
-        let np = resource_builder.create_network_policy(
+        children.extend(group_routing_children(
+            resource_builder,
             project,
             active_deployment,
             &namespace,
-            env_name.as_deref(),
-        );
-        children.push(serde_json::to_value(&np)?);
+            environment,
+            &valid_custom_domains,
+        )?);
     }
 
     Ok(children)
 }
 
-/// Returns true if this deployment should have K8s infrastructure (K8s Deployment resource).
-fn should_have_infrastructure(deployment: &Deployment) -> bool {
-    matches!(
-        deployment.status,
-        DeploymentStatus::Pushed
-            | DeploymentStatus::Deploying
-            | DeploymentStatus::Healthy
-            | DeploymentStatus::Unhealthy
-    )
+/// Desired state for an observed PersistentVolumeClaim that no current deployment
+/// declares. Only identity, labels and the spec are echoed back; PVCs use the
+/// OnDelete update strategy, so Metacontroller never rewrites them.
+fn retained_persistent_volume_claim(observed: &serde_json::Value) -> serde_json::Value {
+    let metadata = &observed["metadata"];
+    serde_json::json!({
+        "apiVersion": "v1",
+        "kind": "PersistentVolumeClaim",
+        "metadata": {
+            "name": metadata["name"],
+            "namespace": metadata["namespace"],
+            "labels": metadata["labels"],
+        },
+        "spec": observed["spec"],
+    })
 }
 
-/// Build image pull secret, refreshing if stale.
-async fn build_image_pull_secret(
+/// Placeholder for secret values in rendered dry-run manifests
+pub(crate) const REDACTED_SECRET_VALUE: &str = "<redacted>";
+
+/// Render the resources a single deployment would get once it is active, for
+/// `rise deploy --dry-run`.
+///
+/// Builds the same Namespace, PersistentVolumeClaim, ServiceAccount, env Secret,
+/// Deployment, Service, NetworkPolicy and Ingresses as [`compute_desired_children`], but for an
+/// in-memory deployment and without reading observed state. Secret values are
+/// never loaded: the env Secret lists its keys with a redacted value.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_deployment_manifests(
     resource_builder: &ResourceBuilder,
     project: &Project,
-    namespace: &str,
-    observed: &ObservedChildren,
-) -> anyhow::Result<Option<Secret>> {
-    // Metacontroller keys namespaced children of cluster-scoped parents as "namespace/name"
-    let secret_key = format!("{}/{}", namespace, IMAGE_PULL_SECRET_NAME);
+    deployment: &Deployment,
+    environment: Option<&crate::db::models::Environment>,
+    source_deployment_id: Option<&str>,
+    plain_env_vars: Vec<EnvVar>,
+    secret_env_keys: &[String],
+    custom_domains: &[crate::db::models::CustomDomain],
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let mut manifests = Vec::new();
+    let namespace = resource_builder.namespace_name(project);
+    let env_name = environment.map(|env| env.name.as_str());
 
-    // Check if existing secret is fresh enough
-    let needs_refresh = match observed.secrets.get(&secret_key) {
-        Some(secret_json) => {
-            let last_refresh = secret_json
-                .get("metadata")
-                .and_then(|m| m.get("annotations"))
-                .and_then(|a| a.get(ANNOTATION_LAST_REFRESH))
-                .and_then(|v| v.as_str())
-                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+    manifests.push(serde_json::to_value(
+        resource_builder.create_namespace(project),
+    )?);
 
-            match last_refresh {
-                Some(ts) => {
-                    let age = Utc::now().signed_duration_since(ts.with_timezone(&Utc));
-                    age > chrono::Duration::hours(SECRET_REFRESH_HOURS)
-                }
-                None => true, // No annotation → refresh
-            }
-        }
-        None => true, // Not observed → create
-    };
+    if let Some(pvc) =
+        resource_builder.create_persistent_volume_claim(project, deployment, &namespace, env_name)
+    {
+        manifests.push(serde_json::to_value(&pvc)?);
+    }
+
+    let sa_name = pod_service_account_name(resource_builder, environment);
+    if let (Some(env), Some(_)) = (environment, &sa_name) {
+        manifests.push(serde_json::to_value(
+            resource_builder.create_service_account(project, &env.name, &namespace),
+        )?);
+    }
+    if resource_builder.create_deployment_service_accounts {
+        let mut seen = sa_name.iter().cloned().collect();
+        for name in deployment_service_account_names(&[deployment], &mut seen) {
+            manifests.push(serde_json::to_value(
+                resource_builder
+                    .create_deployment_service_account(project, &name, &namespace, None),
+            )?);
+        }
+    }
+
+    let secret_env = if secret_env_keys.is_empty() {
+        None
+    } else {
+        let secret = resource_builder.create_deployment_env_secret(
+            project,
+            deployment,
+            &namespace,
+            env_name,
+            REDACTED_SECRET_VALUE,
+            BTreeMap::new(),
+        );
+        let mut secret = serde_json::to_value(&secret)?;
+        // stringData keeps the placeholder readable instead of base64-encoding it
+        secret["stringData"] = secret_env_keys
+            .iter()
+            .map(|key| (key.clone(), REDACTED_SECRET_VALUE.into()))
+            .collect::<serde_json::Map<_, _>>()
+            .into();
+        if let Some(obj) = secret.as_object_mut() {
+            obj.remove("data");
+        }
+        manifests.push(secret);
+        Some((
+            ResourceBuilder::deployment_env_secret_name(project, deployment),
+            REDACTED_SECRET_VALUE.to_string(),
+        ))
+    };
+
+    manifests.extend(deployment_workload_children(
+        resource_builder,
+        project,
+        deployment,
+        &namespace,
+        environment,
+        source_deployment_id,
+        None,
+        plain_env_vars,
+        secret_env,
+    )?);
+    manifests.extend(group_routing_children(
+        resource_builder,
+        project,
+        deployment,
+        &namespace,
+        environment,
+        &resource_builder.filter_valid_custom_domains(custom_domains),
+    )?);
+
+    Ok(manifests)
+}
+
+/// ServiceAccount the pods of a deployment in `environment` run as, `None` for the
+/// namespace's default one
+fn pod_service_account_name(
+    resource_builder: &ResourceBuilder,
+    environment: Option<&crate::db::models::Environment>,
+) -> Option<String> {
+    match environment {
+        Some(env)
+            if resource_builder.use_default_service_account_for_production && env.is_production =>
+        {
+            None
+        }
+        Some(env) => Some(ResourceBuilder::environment_service_account_name(&env.name)),
+        None => None,
+    }
+}
+
+/// The K8s Deployment of a deployment and its PodDisruptionBudget
+///
+/// `replicas` overrides the deployment's replica count on the K8s Deployment (volume
+/// handovers); `secret_env` is the name and content hash of its env Secret.
+#[allow(clippy::too_many_arguments)]
+fn deployment_workload_children(
+    resource_builder: &ResourceBuilder,
+    project: &Project,
+    deployment: &Deployment,
+    namespace: &str,
+    environment: Option<&crate::db::models::Environment>,
+    source_deployment_id: Option<&str>,
+    replicas: Option<i32>,
+    plain_env_vars: Vec<EnvVar>,
+    secret_env: Option<(String, String)>,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let mut children = Vec::new();
+    let env_name = environment.map(|env| env.name.as_str());
+    let (secret_env_name, secret_env_hash) = secret_env.unzip();
+
+    let image = resource_builder.resolve_image(project, deployment, source_deployment_id);
+    let scaled;
+    let spec_deployment = match replicas {
+        Some(replicas) => {
+            scaled = Deployment {
+                replicas,
+                ..deployment.clone()
+            };
+            &scaled
+        }
+        None => deployment,
+    };
+    let k8s_deploy = resource_builder.create_k8s_deployment(
+        project,
+        spec_deployment,
+        namespace,
+        &image,
+        deployment.http_port as u16,
+        plain_env_vars,
+        secret_env_name,
+        secret_env_hash,
+        pod_service_account_name(resource_builder, environment),
+        env_name,
+    );
+    children.push(serde_json::to_value(&k8s_deploy)?);
+
+    // PodDisruptionBudget (multi-replica only; garbage-collected with the Deployment)
+    if let Some(pdb) =
+        resource_builder.create_pod_disruption_budget(project, deployment, namespace, env_name)
+    {
+        children.push(serde_json::to_value(&pdb)?);
+    }
+
+    Ok(children)
+}
+
+/// Service, NetworkPolicy and Ingresses routing a deployment group to its active deployment
+///
+/// TCP deployments get no Ingresses; the custom domain Ingress is only created for
+/// the primary group of the production environment.
+fn group_routing_children(
+    resource_builder: &ResourceBuilder,
+    project: &Project,
+    active_deployment: &Deployment,
+    namespace: &str,
+    environment: Option<&crate::db::models::Environment>,
+    valid_custom_domains: &[crate::db::models::CustomDomain],
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let mut children = Vec::new();
+    let env_name = environment.map(|env| env.name.as_str());
+    let group = active_deployment.deployment_group.as_str();
+
+    // Service (selector points to the active deployment)
+    let service = resource_builder.create_service(
+        project,
+        active_deployment,
+        namespace,
+        active_deployment.http_port as u16,
+        env_name,
+    );
+    children.push(serde_json::to_value(&service)?);
+
+    // NetworkPolicy
+    let np =
+        resource_builder.create_network_policy(project, active_deployment, namespace, env_name);
+    children.push(serde_json::to_value(&np)?);
+
+    // TCP deployments are only reachable through their Service
+    if ResourceBuilder::is_tcp(active_deployment) {
+        return Ok(children);
+    }
+
+    // Primary Ingress
+    let ingress =
+        resource_builder.create_primary_ingress(project, active_deployment, namespace, env_name)?;
+    children.push(serde_json::to_value(&ingress)?);
+
+    // Custom domain Ingress (only for production primary group)
+    let is_production_primary = environment
+        .map(|env| env.is_production && env.primary_deployment_group.as_deref() == Some(group))
+        .unwrap_or(group == crate::server::deployment::models::DEFAULT_DEPLOYMENT_GROUP);
+
+    if is_production_primary && !valid_custom_domains.is_empty() {
+        let custom_ingress = resource_builder.create_custom_domain_ingress(
+            project,
+            active_deployment,
+            namespace,
+            valid_custom_domains,
+            env_name,
+        )?;
+        children.push(serde_json::to_value(&custom_ingress)?);
+    }
+
+    Ok(children)
+}
+
+/// Returns true if this deployment should have K8s infrastructure (K8s Deployment resource).
+///
+/// Pushed deployments get none: status transitions move every Pushed deployment that
+/// [`may_start_deploying`] admits to Deploying before children are computed, so one
+/// still in Pushed is held back by the concurrency limits and must not start pods or
+/// take over its group's volume yet.
+fn should_have_infrastructure(deployment: &Deployment) -> bool {
+    matches!(
+        deployment.status,
+        DeploymentStatus::Deploying | DeploymentStatus::Healthy | DeploymentStatus::Unhealthy
+    ) || is_draining(deployment, Utc::now())
+}
+
+/// A deployment group taking its volume over from its previous deployments
+#[derive(Debug)]
+struct VolumeHandover<'a> {
+    incoming: &'a Deployment,
+    previous: Vec<&'a Deployment>,
+}
+
+/// Volume handovers in progress.
+///
+/// Every deployment of a group is a separate K8s Deployment, so the `Recreate`
+/// strategy can't keep two of them off the group's `ReadWriteOnce` claim. In each
+/// group with a volume, the newest deployment still rolling out (Deploying) takes the
+/// claim over from all other deployments of the group.
+fn volume_handovers<'a>(deployments: &[&'a Deployment]) -> Vec<VolumeHandover<'a>> {
+    let mut groups: BTreeMap<&str, Vec<&'a Deployment>> = BTreeMap::new();
+    for deployment in deployments.iter().filter(|d| d.volume.is_some()) {
+        groups
+            .entry(deployment.deployment_group.as_str())
+            .or_default()
+            .push(deployment);
+    }
+
+    groups
+        .into_values()
+        .filter_map(|group| {
+            let incoming = group
+                .iter()
+                .filter(|d| d.status == DeploymentStatus::Deploying)
+                .max_by_key(|d| d.created_at)
+                .copied()?;
+            let previous: Vec<&Deployment> =
+                group.into_iter().filter(|d| d.id != incoming.id).collect();
+            (!previous.is_empty()).then_some(VolumeHandover { incoming, previous })
+        })
+        .collect()
+}
+
+/// Replica overrides of a handover: the previous deployments run no pods, and the
+/// incoming one waits with zero replicas while pods of the previous ones remain.
+fn handover_replicas(
+    handover: &VolumeHandover,
+    previous_has_pods: bool,
+) -> HashMap<uuid::Uuid, i32> {
+    let mut replicas: HashMap<uuid::Uuid, i32> =
+        handover.previous.iter().map(|d| (d.id, 0)).collect();
+    if previous_has_pods {
+        replicas.insert(handover.incoming.id, 0);
+    }
+    replicas
+}
+
+/// Whether any pod of a deployment still exists, including terminating pods that may
+/// still hold its volume. Errors count as pods left, so a claim is never shared.
+async fn deployment_has_pods(state: &AppState, namespace: &str, deployment: &Deployment) -> bool {
+    let Some(ref kube_client) = state.kube_client else {
+        return false;
+    };
+    let pod_api: kube::Api<k8s_openapi::api::core::v1::Pod> =
+        kube::Api::namespaced(kube_client.clone(), namespace);
+    match pod_api
+        .list_metadata(&kube::api::ListParams::default().labels(&format!(
+            "{}={}",
+            LABEL_DEPLOYMENT_ID, deployment.deployment_id
+        )))
+        .await
+    {
+        Ok(pods) => !pods.items.is_empty(),
+        Err(e) => {
+            warn!(
+                deployment_id = %deployment.deployment_id,
+                "Failed to list pods for volume handover: {:?}", e
+            );
+            true
+        }
+    }
+}
+
+/// ServiceAccounts named in `[deploy.service_account]` that still need to be returned
+/// as children. Names in `seen` (e.g. environment accounts) and the namespace's
+/// built-in `default` account are skipped; returned names are added to `seen`.
+fn deployment_service_account_names(
+    deployments: &[&Deployment],
+    seen: &mut std::collections::HashSet<String>,
+) -> Vec<String> {
+    deployments
+        .iter()
+        .filter_map(|d| d.service_account_name.as_deref())
+        .filter(|name| *name != "default")
+        .filter(|name| seen.insert(name.to_string()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Delete ReplicaSets of deployments that no longer have infrastructure.
+///
+/// Metacontroller garbage-collects the K8s Deployment of a terminal deployment,
+/// which normally cascades to its ReplicaSets. ReplicaSets that lost their owner
+/// (orphaning deletes, manual edits, older controllers) are swept here so they
+/// don't accumulate in busy namespaces. Failures are logged and never fail the sync.
+async fn sweep_terminal_replica_sets(
+    state: &AppState,
+    resource_builder: &ResourceBuilder,
+    project: &Project,
+    deployments: &[Deployment],
+) {
+    let Some(ref kube_client) = state.kube_client else {
+        return;
+    };
+    let namespace = resource_builder.namespace_name(project);
+    let api: kube::Api<ReplicaSet> = kube::Api::namespaced(kube_client.clone(), &namespace);
+
+    let selector = format!(
+        "{}=rise,{}={},{}",
+        LABEL_MANAGED_BY, LABEL_PROJECT, project.name, LABEL_DEPLOYMENT_ID
+    );
+    let replica_sets = match api
+        .list(&kube::api::ListParams::default().labels(&selector))
+        .await
+    {
+        Ok(list) => list.items,
+        Err(e) => {
+            debug!(
+                project = %project.name,
+                "Failed to list ReplicaSets for cleanup: {:?}", e
+            );
+            return;
+        }
+    };
+
+    for name in stale_replica_set_names(&replica_sets, deployments) {
+        match api
+            .delete(&name, &kube::api::DeleteParams::background())
+            .await
+        {
+            Ok(_) => info!(
+                project = %project.name,
+                replica_set = %name,
+                "Deleted ReplicaSet of terminal deployment"
+            ),
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => warn!(
+                project = %project.name,
+                replica_set = %name,
+                "Failed to delete ReplicaSet of terminal deployment: {:?}", e
+            ),
+        }
+    }
+}
+
+/// Names of ReplicaSets whose deployment ID does not belong to a deployment that
+/// should currently have infrastructure.
+fn stale_replica_set_names(replica_sets: &[ReplicaSet], deployments: &[Deployment]) -> Vec<String> {
+    let active: std::collections::HashSet<&str> = deployments
+        .iter()
+        .filter(|d| should_have_infrastructure(d))
+        .map(|d| d.deployment_id.as_str())
+        .collect();
+
+    replica_sets
+        .iter()
+        .filter(|rs| rs.metadata.deletion_timestamp.is_none())
+        .filter(|rs| {
+            rs.metadata
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.get(LABEL_DEPLOYMENT_ID))
+                .is_some_and(|id| !active.contains(id.as_str()))
+        })
+        .filter_map(|rs| rs.metadata.name.clone())
+        .collect()
+}
+
+/// Build image pull secret, refreshing once older than the provider's refresh interval.
+async fn build_image_pull_secret(
+    resource_builder: &ResourceBuilder,
+    project: &Project,
+    namespace: &str,
+    observed: &ObservedChildren,
+) -> anyhow::Result<Option<Secret>> {
+    // Metacontroller keys namespaced children of cluster-scoped parents as "namespace/name"
+    let secret_name = &resource_builder.managed_image_pull_secret_name;
+    let secret_key = format!("{}/{}", namespace, secret_name);
+
+    // Check if existing secret is fresh enough
+    let refresh_interval = chrono::Duration::from_std(
+        resource_builder
+            .registry_provider
+            .pull_secret_refresh_interval(),
+    )?;
+    let needs_refresh = match observed.secrets.get(&secret_key) {
+        Some(secret_json) => {
+            let last_refresh = secret_json
+                .get("metadata")
+                .and_then(|m| m.get("annotations"))
+                .and_then(|a| a.get(ANNOTATION_LAST_REFRESH))
+                .and_then(|v| v.as_str())
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+
+            match last_refresh {
+                Some(ts) => {
+                    let age = Utc::now().signed_duration_since(ts.with_timezone(&Utc));
+                    age > refresh_interval
+                }
+                None => true, // No annotation → refresh
+            }
+        }
+        None => true, // Not observed → create
+    };
 
     if !needs_refresh {
         // Return a clean secret with only the fields we control, using the existing
@@ -1252,7 +2005,7 @@ async fn build_image_pull_secret(
             annotations.insert(ANNOTATION_LAST_REFRESH.to_string(), last_refresh);
             return Ok(Some(Secret {
                 metadata: ObjectMeta {
-                    name: Some(IMAGE_PULL_SECRET_NAME.to_string()),
+                    name: Some(secret_name.clone()),
                     namespace: Some(namespace.to_string()),
                     annotations: Some(annotations),
                     ..Default::default()
@@ -1272,7 +2025,7 @@ async fn build_image_pull_secret(
     let registry_host = resource_builder.registry_provider.registry_host();
 
     let secret = resource_builder.create_dockerconfigjson_secret(
-        IMAGE_PULL_SECRET_NAME,
+        secret_name,
         namespace,
         registry_host,
         &credentials,
@@ -1421,51 +2174,83 @@ async fn apply_backend_endpoints(
 }
 
 /// Load and decrypt environment variables for a deployment
-async fn load_env_vars(
+pub(crate) async fn load_env_vars(
     state: &AppState,
     _project: &Project,
     deployment: &Deployment,
 ) -> anyhow::Result<ResolvedDeploymentEnvVars> {
     let env_vars = db_env_vars::list_deployment_env_vars(&state.db_pool, deployment.id).await?;
-    resolve_deployment_env_vars(env_vars, state.encryption_provider.as_deref()).await
+    resolve_deployment_env_vars(
+        env_vars,
+        state.encryption_provider.as_deref(),
+        state.env_decrypt_concurrency,
+    )
+    .await
 }
 
+/// Decrypt secret values, resolve `${VAR}` references and split env vars into
+/// plain and secret values.
+///
+/// Secrets are decrypted with up to `concurrency` in-flight `decrypt` calls;
+/// the output keeps the input order. Values composed from a secret are secret.
 async fn resolve_deployment_env_vars(
     env_vars: Vec<DeploymentEnvVar>,
     encryption_provider: Option<&dyn crate::server::encryption::EncryptionProvider>,
+    concurrency: usize,
 ) -> anyhow::Result<ResolvedDeploymentEnvVars> {
-    let mut resolved = ResolvedDeploymentEnvVars::default();
+    use futures::stream::{self, StreamExt, TryStreamExt};
 
-    for var in env_vars {
-        let value = if var.is_secret {
+    let decrypted: Vec<(DeploymentEnvVar, String)> = stream::iter(env_vars)
+        .map(|var| async move {
+            if !var.is_secret {
+                let value = var.value.clone();
+                return Ok((var, value));
+            }
             match encryption_provider {
-                Some(provider) => provider
-                    .decrypt(&var.value)
-                    .await
-                    .with_context(|| format!("Failed to decrypt secret variable '{}'", var.key))?,
+                Some(provider) => {
+                    let value = provider.decrypt(&var.value).await.with_context(|| {
+                        format!("Failed to decrypt secret variable '{}'", var.key)
+                    })?;
+                    Ok((var, value))
+                }
                 None => {
                     tracing::error!(
                         "Encountered secret variable '{}' but no encryption provider configured",
                         var.key
                     );
-                    return Err(anyhow::anyhow!(
+                    Err(anyhow::anyhow!(
                         "Cannot decrypt secret variable '{}': no encryption provider",
                         var.key
-                    ));
+                    ))
                 }
             }
-        } else {
-            var.value
-        };
+        })
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await?;
+
+    let interpolated = env_interpolation::interpolate(
+        decrypted
+            .into_iter()
+            .map(|(var, value)| EnvValue {
+                key: var.key,
+                value,
+                is_secret: var.is_secret,
+            })
+            .collect(),
+    )?;
 
+    let mut resolved = ResolvedDeploymentEnvVars::default();
+
+    for var in interpolated {
         if var.is_secret {
             resolved
                 .secret_env_vars
-                .insert(var.key, ByteString(value.into_bytes()));
+                .insert(var.key, ByteString(var.value.into_bytes()));
         } else {
             resolved.plain_env_vars.push(EnvVar {
                 name: var.key,
-                value: Some(value),
+                value: Some(var.value),
                 ..Default::default()
             });
         }
@@ -1549,13 +2334,14 @@ async fn process_finalize(
                     &deployment.status,
                     &DeploymentStatus::Cancelling,
                 ) {
-                    db_deployments::mark_cancelling(&state.db_pool, deployment.id).await?;
+                    db_deployments::mark_cancelling(&state.db_pool, deployment.id, None).await?;
                     db_deployments::mark_cancelled(&state.db_pool, deployment.id).await?;
                 } else {
                     db_deployments::mark_terminating(
                         &state.db_pool,
                         deployment.id,
                         TerminationReason::UserStopped,
+                        None,
                     )
                     .await?;
                     db_deployments::mark_stopped(&state.db_pool, deployment.id).await?;
@@ -1596,6 +2382,10 @@ mod tests {
             unreachable!("not used in these tests")
         }
 
+        fn registry_type(&self) -> &'static str {
+            "test"
+        }
+
         fn registry_host(&self) -> &str {
             "registry.example.test"
         }
@@ -1614,7 +2404,6 @@ mod tests {
     #[test]
     fn test_should_have_infrastructure_for_active_states() {
         let statuses_with_infra = [
-            DeploymentStatus::Pushed,
             DeploymentStatus::Deploying,
             DeploymentStatus::Healthy,
             DeploymentStatus::Unhealthy,
@@ -1635,6 +2424,8 @@ mod tests {
             DeploymentStatus::Pending,
             DeploymentStatus::Building,
             DeploymentStatus::Pushing,
+            // Still Pushed after status transitions: deferred by the concurrency limits
+            DeploymentStatus::Pushed,
             DeploymentStatus::Cancelling,
             DeploymentStatus::Cancelled,
             DeploymentStatus::Terminating,
@@ -1653,6 +2444,196 @@ mod tests {
         }
     }
 
+    fn draining_deployment(drain_until: chrono::DateTime<Utc>) -> Deployment {
+        let mut d = test_deployment(DeploymentStatus::Terminating);
+        d.termination_reason = Some(TerminationReason::Superseded);
+        d.controller_metadata = serde_json::json!({ "drain_until": drain_until });
+        d
+    }
+
+    #[test]
+    fn test_draining_deployment_keeps_infrastructure_until_drain_ends() {
+        let now = Utc::now();
+
+        let draining = draining_deployment(now + chrono::Duration::minutes(5));
+        assert!(is_draining(&draining, now));
+        assert!(should_have_infrastructure(&draining));
+
+        let drained = draining_deployment(now - chrono::Duration::seconds(1));
+        assert!(!is_draining(&drained, now));
+        assert!(!should_have_infrastructure(&drained));
+
+        // Only superseded deployments drain; stopping one tears it down right away
+        let mut stopped = draining_deployment(now + chrono::Duration::minutes(5));
+        stopped.termination_reason = Some(TerminationReason::UserStopped);
+        assert!(!is_draining(&stopped, now));
+        assert!(!should_have_infrastructure(&stopped));
+    }
+
+    // ── ReplicaSet cleanup ─────────────────────────────────────────────
+
+    fn test_replica_set(name: &str, deployment_id: Option<&str>) -> ReplicaSet {
+        ReplicaSet {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                labels: deployment_id
+                    .map(|id| BTreeMap::from([(LABEL_DEPLOYMENT_ID.to_string(), id.to_string())])),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_retained_persistent_volume_claim_drops_server_fields() {
+        let observed = serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "PersistentVolumeClaim",
+            "metadata": {
+                "name": "default-data",
+                "namespace": "demo",
+                "labels": {"rise.dev/project": "demo"},
+                "uid": "1234",
+                "resourceVersion": "42",
+            },
+            "spec": {"accessModes": ["ReadWriteOnce"], "volumeName": "pvc-1234"},
+            "status": {"phase": "Bound"},
+        });
+
+        let retained = retained_persistent_volume_claim(&observed);
+        assert_eq!(retained["metadata"]["name"], "default-data");
+        assert_eq!(retained["metadata"]["namespace"], "demo");
+        assert!(retained["metadata"].get("uid").is_none());
+        assert!(retained.get("status").is_none());
+        assert_eq!(retained["spec"], observed["spec"]);
+    }
+
+    #[test]
+    fn test_init_container_error_reports_crash_loop_with_exit_code() {
+        let status: k8s_openapi::api::core::v1::PodStatus =
+            serde_json::from_value(serde_json::json!({
+                "initContainerStatuses": [{
+                    "name": "init",
+                    "image": "app",
+                    "imageID": "",
+                    "ready": false,
+                    "restartCount": 4,
+                    "state": {"waiting": {"reason": "CrashLoopBackOff", "message": "back-off 40s"}},
+                    "lastState": {"terminated": {"exitCode": 1, "reason": "Error"}}
+                }]
+            }))
+            .unwrap();
+
+        assert_eq!(
+            init_container_error(&status).as_deref(),
+            Some(
+                "Init container 'init' failed: CrashLoopBackOff: back-off 40s (last exit code: 1)"
+            )
+        );
+
+        let running: k8s_openapi::api::core::v1::PodStatus =
+            serde_json::from_value(serde_json::json!({
+                "initContainerStatuses": [{
+                    "name": "init",
+                    "image": "app",
+                    "imageID": "",
+                    "ready": false,
+                    "restartCount": 0,
+                    "state": {"running": {}}
+                }]
+            }))
+            .unwrap();
+        assert_eq!(init_container_error(&running), None);
+    }
+
+    #[test]
+    fn test_deployment_service_account_names_skip_seen_and_default() {
+        let mut app = test_deployment(DeploymentStatus::Healthy);
+        app.service_account_name = Some("app-irsa".to_string());
+        let mut same = test_deployment(DeploymentStatus::Deploying);
+        same.service_account_name = Some("app-irsa".to_string());
+        let mut env = test_deployment(DeploymentStatus::Healthy);
+        env.service_account_name = Some("env-staging".to_string());
+        let mut default = test_deployment(DeploymentStatus::Healthy);
+        default.service_account_name = Some("default".to_string());
+        let none = test_deployment(DeploymentStatus::Healthy);
+
+        let mut seen = std::collections::HashSet::from(["env-staging".to_string()]);
+        assert_eq!(
+            deployment_service_account_names(&[&app, &same, &env, &default, &none], &mut seen),
+            vec!["app-irsa".to_string()]
+        );
+        assert!(seen.contains("app-irsa"));
+    }
+
+    #[test]
+    fn test_volume_handover_stops_previous_deployment_before_incoming_starts() {
+        let volume = Some(serde_json::json!({"size": "1Gi", "mount_path": "/data"}));
+        let mut active = test_deployment(DeploymentStatus::Healthy);
+        active.id = uuid::Uuid::new_v4();
+        active.is_active = true;
+        active.volume = volume.clone();
+        let mut incoming = test_deployment(DeploymentStatus::Deploying);
+        incoming.id = uuid::Uuid::new_v4();
+        incoming.deployment_id = "20260429-000001".to_string();
+        incoming.created_at = active.created_at + chrono::Duration::minutes(5);
+        incoming.volume = volume.clone();
+        let mut other_group = test_deployment(DeploymentStatus::Deploying);
+        other_group.id = uuid::Uuid::new_v4();
+        other_group.deployment_group = "staging".to_string();
+
+        // A newer deployment deferred in Pushed does not take the claim over
+        let mut deferred = test_deployment(DeploymentStatus::Pushed);
+        deferred.id = uuid::Uuid::new_v4();
+        deferred.created_at = incoming.created_at + chrono::Duration::minutes(5);
+        deferred.volume = volume.clone();
+        assert!(volume_handovers(&[&active, &deferred]).is_empty());
+
+        let handovers = volume_handovers(&[&active, &incoming, &other_group]);
+        assert_eq!(handovers.len(), 1);
+        assert_eq!(handovers[0].incoming.id, incoming.id);
+
+        // 1. The previous deployment still has pods: nothing may mount the claim anew
+        assert_eq!(
+            handover_replicas(&handovers[0], true),
+            HashMap::from([(active.id, 0), (incoming.id, 0)])
+        );
+        // 2. Its pods are gone: the incoming deployment gets its replicas
+        assert_eq!(
+            handover_replicas(&handovers[0], false),
+            HashMap::from([(active.id, 0)])
+        );
+        // 3. Once the incoming deployment is active, the previous one has no
+        //    infrastructure and the handover is over
+        incoming.status = DeploymentStatus::Healthy;
+        incoming.is_active = true;
+        assert!(volume_handovers(&[&incoming]).is_empty());
+
+        // Groups without a volume roll out side by side as before
+        let mut previous = test_deployment(DeploymentStatus::Healthy);
+        previous.id = uuid::Uuid::new_v4();
+        previous.deployment_group = "staging".to_string();
+        assert!(volume_handovers(&[&previous, &other_group]).is_empty());
+    }
+
+    #[test]
+    fn test_stale_replica_sets_include_terminal_deployments_in_occupied_group() {
+        let mut healthy = test_deployment(DeploymentStatus::Healthy);
+        healthy.deployment_id = "20260429-000001".to_string();
+        let stopped = test_deployment(DeploymentStatus::Stopped);
+
+        let replica_sets = [
+            test_replica_set("demo-20260429-000000-abc", Some("20260429-000000")),
+            test_replica_set("demo-20260429-000001-def", Some("20260429-000001")),
+            test_replica_set("unlabeled", None),
+        ];
+
+        assert_eq!(
+            stale_replica_set_names(&replica_sets, &[healthy, stopped]),
+            vec!["demo-20260429-000000-abc".to_string()]
+        );
+    }
+
     // ── FinalizeResponse serialization ─────────────────────────────────
 
     #[test]
@@ -1678,8 +2659,231 @@ mod tests {
         assert_eq!(json["finalized"], false);
     }
 
+    // ── Resync hint ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_resync_after_seconds_backs_off_for_settled_projects() {
+        let now = Utc::now();
+        let healthy = test_deployment(DeploymentStatus::Healthy);
+        let unhealthy = test_deployment(DeploymentStatus::Unhealthy);
+
+        assert_eq!(resync_after_seconds(&[], 2.0, now), None);
+        assert_eq!(resync_after_seconds(&[healthy, unhealthy], 2.0, now), None);
+    }
+
+    #[test]
+    fn test_resync_after_seconds_polls_in_flight_deployments() {
+        let now = Utc::now();
+        for status in [
+            DeploymentStatus::Pushed,
+            DeploymentStatus::Deploying,
+            DeploymentStatus::Cancelling,
+            DeploymentStatus::Terminating,
+        ] {
+            let deployments = [
+                test_deployment(DeploymentStatus::Healthy),
+                test_deployment(status),
+            ];
+            assert_eq!(resync_after_seconds(&deployments, 2.0, now), Some(2.0));
+        }
+    }
+
+    #[test]
+    fn test_resync_after_seconds_wakes_up_for_next_expiry() {
+        let now = Utc::now();
+        let mut expiring = test_deployment(DeploymentStatus::Healthy);
+        expiring.expires_at = Some(now + chrono::Duration::seconds(90));
+        let mut later = test_deployment(DeploymentStatus::Healthy);
+        later.expires_at = Some(now + chrono::Duration::hours(1));
+
+        assert_eq!(
+            resync_after_seconds(&[later, expiring.clone()], 2.0, now),
+            Some(90.0)
+        );
+        // An in-flight rollout polls sooner than the expiry
+        let deploying = test_deployment(DeploymentStatus::Deploying);
+        assert_eq!(
+            resync_after_seconds(&[expiring.clone(), deploying], 2.0, now),
+            Some(2.0)
+        );
+        // Overdue expiries resync right away
+        expiring.expires_at = Some(now - chrono::Duration::seconds(5));
+        assert_eq!(resync_after_seconds(&[expiring], 2.0, now), Some(0.0));
+    }
+
+    #[test]
+    fn test_resync_after_seconds_wakes_up_when_drain_ends() {
+        let now = Utc::now();
+        let draining = draining_deployment(now + chrono::Duration::seconds(120));
+        let healthy = test_deployment(DeploymentStatus::Healthy);
+
+        // A draining deployment is not in flight, so polling backs off until it ends
+        assert_eq!(
+            resync_after_seconds(&[healthy, draining], 2.0, now),
+            Some(120.0)
+        );
+    }
+
     // ── SyncResponse serialization ─────────────────────────────────────
 
+    #[test]
+    fn test_deploying_timeout_only_after_limit() {
+        let now = Utc::now();
+        let mut deployment = test_deployment(DeploymentStatus::Deploying);
+        assert!(deploying_timeout_elapsed(&deployment, now).is_none());
+
+        deployment.deploying_started_at =
+            Some(now - chrono::Duration::minutes(DEPLOYING_TIMEOUT_MINUTES - 1));
+        assert!(deploying_timeout_elapsed(&deployment, now).is_none());
+
+        deployment.deploying_started_at =
+            Some(now - chrono::Duration::minutes(DEPLOYING_TIMEOUT_MINUTES + 1));
+        assert_eq!(
+            deploying_timeout_elapsed(&deployment, now),
+            Some(chrono::Duration::minutes(DEPLOYING_TIMEOUT_MINUTES + 1))
+        );
+    }
+
+    async fn create_test_deployment(
+        pool: &sqlx::PgPool,
+        project_id: uuid::Uuid,
+        user_id: uuid::Uuid,
+        deployment_id: &str,
+        group: &str,
+        status: DeploymentStatus,
+    ) -> Deployment {
+        db_deployments::create(
+            pool,
+            db_deployments::CreateDeploymentParams {
+                deployment_id,
+                project_id,
+                created_by_id: user_id,
+                status,
+                image: Some("test:v1"),
+                image_digest: None,
+                rolled_back_from_deployment_id: None,
+                deployment_group: group,
+                environment_id: None,
+                expires_at: None,
+                http_port: 8080,
+                is_active: false,
+                job_url: None,
+                pull_request_url: None,
+                message: None,
+                replicas: 1,
+                cpu: "500m",
+                memory: "256Mi",
+                cpu_limit: None,
+                memory_limit: None,
+                health_check: None,
+                init_container: None,
+                protocol: "http",
+                labels: None,
+                annotations: None,
+                command: None,
+                args: None,
+                ingress_annotations: None,
+                strategy: "rolling",
+                drain_period_seconds: None,
+                volume: None,
+                scheduling: None,
+                image_pull_policy: None,
+                rate_limit: None,
+                extra_ports: None,
+                service_account_name: None,
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_concurrency_limits_defer_pushed_deployments_at_the_limit(pool: sqlx::PgPool) {
+        use crate::db::{projects, users};
+        use crate::server::settings::DeploymentConcurrencyLimits;
+
+        let user = users::create(&pool, "deploy-test@example.com")
+            .await
+            .unwrap();
+        let mut project_ids = Vec::new();
+        for name in ["limits-a", "limits-b"] {
+            let project = projects::create(
+                &pool,
+                name,
+                ProjectStatus::Running,
+                "public".to_string(),
+                Some(user.id),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            project_ids.push(project.id);
+        }
+        let (project_a, project_b) = (project_ids[0], project_ids[1]);
+
+        create_test_deployment(
+            &pool,
+            project_a,
+            user.id,
+            "20260101-100000",
+            "default",
+            DeploymentStatus::Deploying,
+        )
+        .await;
+        let pushed = create_test_deployment(
+            &pool,
+            project_a,
+            user.id,
+            "20260101-110000",
+            "mr/1",
+            DeploymentStatus::Pushed,
+        )
+        .await;
+
+        let per_project = |limit| DeploymentConcurrencyLimits {
+            per_project: Some(limit),
+            global: None,
+        };
+        let global = |limit| DeploymentConcurrencyLimits {
+            per_project: None,
+            global: Some(limit),
+        };
+
+        // One deployment of the project is deploying: accepted below the limit only
+        assert!(within_concurrency_limits(&pool, per_project(2), &pushed)
+            .await
+            .unwrap());
+        assert!(!within_concurrency_limits(&pool, per_project(1), &pushed)
+            .await
+            .unwrap());
+        assert!(
+            within_concurrency_limits(&pool, DeploymentConcurrencyLimits::default(), &pushed)
+                .await
+                .unwrap()
+        );
+
+        // Another project's deployment counts toward the global limit only
+        create_test_deployment(
+            &pool,
+            project_b,
+            user.id,
+            "20260101-120000",
+            "default",
+            DeploymentStatus::Deploying,
+        )
+        .await;
+        assert!(within_concurrency_limits(&pool, per_project(2), &pushed)
+            .await
+            .unwrap());
+        assert!(within_concurrency_limits(&pool, global(3), &pushed)
+            .await
+            .unwrap());
+        assert!(!within_concurrency_limits(&pool, global(2), &pushed)
+            .await
+            .unwrap());
+    }
+
     #[test]
     fn test_sync_response_omits_resync_when_none() {
         let response = SyncResponse {
@@ -1723,7 +2927,7 @@ mod tests {
             encrypted_env_vars.push(var);
         }
 
-        let resolved = resolve_deployment_env_vars(encrypted_env_vars, Some(&provider))
+        let resolved = resolve_deployment_env_vars(encrypted_env_vars, Some(&provider), 4)
             .await
             .unwrap();
 
@@ -1741,6 +2945,115 @@ mod tests {
         );
     }
 
+    /// Encryption provider whose `decrypt` sleeps and records peak concurrency.
+    /// Ciphertexts are `enc:<delay_ms>:<plaintext>`.
+    #[derive(Default)]
+    struct SleepingEncryptionProvider {
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EncryptionProvider for SleepingEncryptionProvider {
+        async fn encrypt(&self, _plaintext: &str) -> Result<String> {
+            unreachable!("not used in these tests")
+        }
+
+        async fn decrypt(&self, ciphertext: &str) -> Result<String> {
+            use std::sync::atomic::Ordering;
+
+            let (delay_ms, plaintext) = ciphertext
+                .strip_prefix("enc:")
+                .and_then(|rest| rest.split_once(':'))
+                .unwrap();
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms.parse().unwrap())).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(plaintext.to_string())
+        }
+    }
+
+    fn sleeping_secret_env_vars(count: usize) -> Vec<DeploymentEnvVar> {
+        // Later variables finish first, so completion order differs from input order
+        (0..count)
+            .map(|i| {
+                let ciphertext = format!("enc:{}:value-{}", 10 + (count - i) * 5, i);
+                test_env_var(&format!("SECRET_{:02}", i), &ciphertext, true, true)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn resolve_deployment_env_vars_decrypts_with_bounded_concurrency() {
+        let mut env_vars = sleeping_secret_env_vars(12);
+        env_vars.insert(6, test_env_var("PORT", "8080", false, false));
+
+        let provider = SleepingEncryptionProvider::default();
+        let resolved = resolve_deployment_env_vars(env_vars, Some(&provider), 4)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            provider
+                .max_in_flight
+                .load(std::sync::atomic::Ordering::SeqCst),
+            4
+        );
+        assert_eq!(resolved.plain_env_vars.len(), 1);
+        assert_eq!(resolved.plain_env_vars[0].name, "PORT");
+        assert_eq!(resolved.secret_env_vars.len(), 12);
+        for i in 0..12 {
+            assert_eq!(
+                resolved.secret_env_vars[&format!("SECRET_{:02}", i)].0,
+                format!("value-{}", i).into_bytes()
+            );
+        }
+    }
+
+    /// Runs on a paused clock: sleeps complete by auto-advancing time, so the measured
+    /// durations are exact regardless of machine load.
+    #[tokio::test(start_paused = true)]
+    async fn resolve_deployment_env_vars_concurrent_decrypt_is_faster_than_serial() {
+        let serial_provider = SleepingEncryptionProvider::default();
+        let started = tokio::time::Instant::now();
+        resolve_deployment_env_vars(sleeping_secret_env_vars(12), Some(&serial_provider), 1)
+            .await
+            .unwrap();
+        let serial = started.elapsed();
+
+        let concurrent_provider = SleepingEncryptionProvider::default();
+        let started = tokio::time::Instant::now();
+        resolve_deployment_env_vars(sleeping_secret_env_vars(12), Some(&concurrent_provider), 8)
+            .await
+            .unwrap();
+        let concurrent = started.elapsed();
+
+        assert_eq!(
+            serial_provider
+                .max_in_flight
+                .load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+        // Serial decryption takes the sum of all delays (10ms + 5ms * (12 - i) each)
+        assert_eq!(serial, std::time::Duration::from_millis(510));
+        assert!(
+            concurrent * 2 < serial,
+            "expected concurrent decryption ({:?}) to be much faster than serial ({:?})",
+            concurrent,
+            serial
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_deployment_env_vars_fails_without_encryption_provider() {
+        let env_vars = vec![test_env_var("API_KEY", "enc:10:secret", true, true)];
+        let err = resolve_deployment_env_vars(env_vars, None, 4)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no encryption provider"));
+    }
+
     #[test]
     fn deployment_env_secret_hash_is_stable_for_identical_data() {
         let mut data_a = BTreeMap::new();
@@ -1808,6 +3121,94 @@ mod tests {
         assert!(prepared_ready.is_ready);
     }
 
+    #[test]
+    fn render_deployment_manifests_redacts_secrets() {
+        let mut builder = test_resource_builder();
+        builder.access_classes.insert(
+            "default".to_string(),
+            crate::server::settings::AccessClass {
+                display_name: "Public".to_string(),
+                description: String::new(),
+                ingress_class: "nginx".to_string(),
+                access_requirement: crate::server::settings::AccessRequirement::None,
+                custom_annotations: HashMap::new(),
+            },
+        );
+        let project = test_project();
+        let deployment = test_deployment(DeploymentStatus::Pushed);
+
+        let manifests = render_deployment_manifests(
+            &builder,
+            &project,
+            &deployment,
+            None,
+            None,
+            vec![EnvVar {
+                name: "PORT".to_string(),
+                value: Some("8080".to_string()),
+                ..Default::default()
+            }],
+            &["API_KEY".to_string()],
+            &[],
+        )
+        .unwrap();
+
+        let kinds: Vec<_> = manifests
+            .iter()
+            .map(|m| m["kind"].as_str().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "Namespace",
+                "Secret",
+                "Deployment",
+                "Service",
+                "NetworkPolicy",
+                "Ingress"
+            ]
+        );
+
+        let secret = &manifests[1];
+        assert!(secret.get("data").is_none());
+        assert_eq!(secret["stringData"]["API_KEY"], REDACTED_SECRET_VALUE);
+
+        let mut tcp_deployment = test_deployment(DeploymentStatus::Pushed);
+        tcp_deployment.protocol = "tcp".to_string();
+        let tcp_manifests = render_deployment_manifests(
+            &builder,
+            &project,
+            &tcp_deployment,
+            None,
+            None,
+            Vec::new(),
+            &[],
+            &[],
+        )
+        .unwrap();
+        assert!(tcp_manifests.iter().all(|m| m["kind"] != "Ingress"));
+    }
+
+    #[test]
+    fn http_health_check_requires_path_and_http_protocol() {
+        let mut deployment = test_deployment(DeploymentStatus::Healthy);
+        assert!(http_health_check(&deployment).is_none());
+
+        deployment.health_check = Some(serde_json::json!({ "readiness_path": "/ready" }));
+        assert!(http_health_check(&deployment).is_none());
+
+        deployment.health_check = Some(serde_json::json!({
+            "path": "/health",
+            "expected_status": [200],
+        }));
+        let (health_check, path) = http_health_check(&deployment).unwrap();
+        assert_eq!(path, "/health");
+        assert!(!health_check.is_expected_status(500));
+
+        deployment.protocol = "tcp".to_string();
+        assert!(http_health_check(&deployment).is_none());
+    }
+
     // ── Helper ─────────────────────────────────────────────────────────
 
     fn test_resource_builder() -> ResourceBuilder {
@@ -1824,15 +3225,19 @@ mod tests {
             namespace_labels: HashMap::new(),
             namespace_annotations: HashMap::new(),
             ingress_annotations: HashMap::new(),
+            allowed_ingress_annotations: Vec::new(),
             ingress_tls_secret_name: None,
             custom_domain_tls_mode: crate::server::settings::CustomDomainTlsMode::PerDomain,
+            acme_cluster_issuer: None,
             custom_domain_ingress_annotations: HashMap::new(),
             node_selector: HashMap::new(),
             image_pull_secret_name: None,
+            managed_image_pull_secret_name: "rise-registry-creds".to_string(),
             access_classes: HashMap::new(),
             host_aliases: HashMap::new(),
             extra_service_token_audiences: HashMap::new(),
             use_default_service_account_for_production: true,
+            create_deployment_service_accounts: false,
             network_policy: crate::server::settings::NetworkPolicyConfig {
                 ingress: vec![],
                 egress: None,
@@ -1840,6 +3245,12 @@ mod tests {
             pod_security_enabled: true,
             health_probes: None,
             namespace_format: "{project_name}".to_string(),
+            revision_history_limit: 2,
+            termination_grace_period_secs: None,
+            pod_disruption_budget_min_available: None,
+            cleanup_terminal_replica_sets: true,
+            in_flight_resync_secs: 2.0,
+            tcp_service_type: crate::server::settings::TcpServiceType::ClusterIP,
         }
     }
 
@@ -1853,6 +3264,11 @@ mod tests {
             owner_team_id: None,
             finalizers: vec![],
             source_url: None,
+            force_https: None,
+            hsts_max_age: None,
+            max_active_deployments: None,
+            group_ttls: None,
+            namespace: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
@@ -1883,9 +3299,29 @@ mod tests {
             first_healthy_at: None,
             job_url: None,
             pull_request_url: None,
+            message: None,
             replicas: 1,
             cpu: "500m".to_string(),
             memory: "256Mi".to_string(),
+            cpu_limit: None,
+            memory_limit: None,
+            health_check: None,
+            init_container: None,
+            protocol: "http".to_string(),
+            labels: None,
+            annotations: None,
+            command: None,
+            args: None,
+            ingress_annotations: None,
+            strategy: "rolling".to_string(),
+            drain_period_seconds: None,
+            volume: None,
+            scheduling: None,
+            image_pull_policy: None,
+            rate_limit: None,
+            extra_ports: None,
+            service_account_name: None,
+            restarted_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         }
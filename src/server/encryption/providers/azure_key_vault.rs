@@ -0,0 +1,263 @@
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Nonce,
+};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use base64::{
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL},
+    Engine,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use crate::server::azure_auth::{AzureCredential, AzureTokenSource};
+use crate::server::encryption::EncryptionProvider;
+
+/// Name used for this provider in logs and errors
+const PROVIDER_NAME: &str = "azure-key-vault";
+
+/// Key Vault REST API version
+const API_VERSION: &str = "7.4";
+
+/// OAuth2 scope / resource for Key Vault access tokens
+const KEY_VAULT_RESOURCE: &str = "https://vault.azure.net";
+
+/// Key wrapping algorithm; works with RSA keys of any size
+const WRAP_ALGORITHM: &str = "RSA-OAEP-256";
+
+/// Prefix identifying ciphertexts produced by this provider
+const CIPHERTEXT_PREFIX: &str = "azkv";
+
+/// Azure Key Vault encryption provider
+///
+/// Uses envelope encryption: each secret is encrypted locally with a fresh AES-256-GCM
+/// data key, which is wrapped with the Key Vault key (`wrapKey`/`unwrapKey`). Ciphertexts
+/// are stored as `azkv:<key version>:<wrapped key>:<nonce + ciphertext>` so secrets stay
+/// decryptable after the Key Vault key is rotated.
+pub struct AzureKeyVaultEncryptionProvider {
+    http_client: reqwest::Client,
+    vault_url: String,
+    key_name: String,
+    token_source: AzureTokenSource,
+}
+
+#[derive(Deserialize)]
+struct KeyOperationResult {
+    /// Full key identifier including the version, e.g. `https://<vault>/keys/<name>/<version>`
+    kid: String,
+    /// Base64url-encoded result
+    value: String,
+}
+
+#[derive(Deserialize)]
+struct KeyVaultErrorResponse {
+    error: KeyVaultErrorBody,
+}
+
+#[derive(Deserialize)]
+struct KeyVaultErrorBody {
+    code: String,
+    message: String,
+}
+
+/// Turn a failed Key Vault response into an error
+fn key_vault_error(status: StatusCode, body: &str) -> anyhow::Error {
+    match serde_json::from_str::<KeyVaultErrorResponse>(body) {
+        Ok(response) => anyhow::anyhow!(
+            "Key Vault returned {} ({}): {}",
+            status,
+            response.error.code,
+            response.error.message
+        ),
+        Err(_) => anyhow::anyhow!("Key Vault returned {}: {}", status, body),
+    }
+}
+
+/// Split a stored ciphertext into (key version, wrapped key, encrypted payload)
+fn parse_ciphertext(ciphertext: &str) -> Result<(&str, &str, &str)> {
+    let mut parts = ciphertext.splitn(4, ':');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(CIPHERTEXT_PREFIX), Some(version), Some(wrapped_key), Some(payload))
+            if !version.is_empty() =>
+        {
+            Ok((version, wrapped_key, payload))
+        }
+        _ => bail!("Ciphertext was not produced by Azure Key Vault"),
+    }
+}
+
+impl AzureKeyVaultEncryptionProvider {
+    /// Create a new Azure Key Vault encryption provider
+    ///
+    /// With `client_secret` set, authenticates as the service principal `client_id` in
+    /// `tenant_id`. Otherwise a managed identity is used (`client_id` optionally selecting
+    /// a user-assigned identity).
+    pub fn new(
+        vault_url: String,
+        key_name: String,
+        tenant_id: Option<String>,
+        client_id: Option<String>,
+        client_secret: Option<String>,
+    ) -> Result<Self> {
+        let credential = match client_secret {
+            Some(client_secret) => AzureCredential::ServicePrincipal {
+                tenant_id: tenant_id.context(
+                    "Azure Key Vault 'tenant_id' is required when 'client_secret' is set",
+                )?,
+                client_id: client_id.context(
+                    "Azure Key Vault 'client_id' is required when 'client_secret' is set",
+                )?,
+                client_secret,
+            },
+            None => AzureCredential::ManagedIdentity { client_id },
+        };
+
+        Ok(Self {
+            http_client: reqwest::Client::new(),
+            vault_url: vault_url.trim_end_matches('/').to_string(),
+            key_name,
+            token_source: AzureTokenSource::new(credential, KEY_VAULT_RESOURCE),
+        })
+    }
+
+    /// Run a key operation (`wrapkey`/`unwrapkey`) against the given key version
+    async fn key_operation(
+        &self,
+        version: Option<&str>,
+        operation: &str,
+        value: &[u8],
+    ) -> Result<KeyOperationResult> {
+        let key_path = match version {
+            Some(version) => format!("{}/{}", self.key_name, version),
+            None => self.key_name.clone(),
+        };
+        let url = format!(
+            "{}/keys/{}/{}?api-version={}",
+            self.vault_url, key_path, operation, API_VERSION
+        );
+
+        let response = self
+            .http_client
+            .post(&url)
+            .bearer_auth(self.token_source.access_token().await?)
+            .json(&serde_json::json!({
+                "alg": WRAP_ALGORITHM,
+                "value": BASE64_URL.encode(value),
+            }))
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach Key Vault at {}", self.vault_url))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(key_vault_error(status, &body));
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse Key Vault {} response", operation))
+    }
+}
+
+#[async_trait]
+impl EncryptionProvider for AzureKeyVaultEncryptionProvider {
+    async fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let data_key = Aes256Gcm::generate_key(&mut OsRng);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new(&data_key)
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Encryption failed: {}", e))?;
+
+        let wrapped = self
+            .key_operation(None, "wrapkey", data_key.as_slice())
+            .await
+            .with_context(|| {
+                format!(
+                    "Key Vault wrapKey failed for key '{}' ({})",
+                    self.key_name, PROVIDER_NAME
+                )
+            })?;
+        let version = wrapped
+            .kid
+            .rsplit('/')
+            .next()
+            .filter(|v| !v.is_empty())
+            .context("Key Vault response did not include a key version")?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(format!(
+            "{}:{}:{}:{}",
+            CIPHERTEXT_PREFIX,
+            version,
+            wrapped.value,
+            BASE64.encode(&payload)
+        ))
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> Result<String> {
+        let (version, wrapped_key, payload) = parse_ciphertext(ciphertext)?;
+
+        let wrapped_key = BASE64_URL
+            .decode(wrapped_key)
+            .context("Failed to decode wrapped data key")?;
+        let unwrapped = self
+            .key_operation(Some(version), "unwrapkey", &wrapped_key)
+            .await
+            .with_context(|| {
+                format!(
+                    "Key Vault unwrapKey failed for key '{}' version '{}' ({})",
+                    self.key_name, version, PROVIDER_NAME
+                )
+            })?;
+        let data_key = BASE64_URL
+            .decode(unwrapped.value)
+            .context("Failed to decode data key from Key Vault")?;
+        let cipher = Aes256Gcm::new_from_slice(&data_key)
+            .context("Key Vault returned a data key of the wrong size")?;
+
+        let payload = BASE64
+            .decode(payload)
+            .context("Failed to decode ciphertext from base64")?;
+        if payload.len() < 12 {
+            bail!("Ciphertext too short");
+        }
+        let (nonce, ciphertext) = payload.split_at(12);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|e| anyhow::anyhow!("Decryption failed: {}", e))?;
+
+        String::from_utf8(plaintext).context("Decrypted data is not valid UTF-8")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ciphertext() {
+        assert_eq!(
+            parse_ciphertext("azkv:0123abcd:d3JhcHBlZA:cGF5bG9hZA==").unwrap(),
+            ("0123abcd", "d3JhcHBlZA", "cGF5bG9hZA==")
+        );
+        assert!(parse_ciphertext("vault:v1:abc").is_err());
+        assert!(parse_ciphertext("azkv::d3JhcHBlZA:cGF5bG9hZA==").is_err());
+    }
+
+    #[test]
+    fn test_key_vault_error_includes_code() {
+        let err = key_vault_error(
+            StatusCode::FORBIDDEN,
+            r#"{"error":{"code":"Forbidden","message":"Caller is not authorized"}}"#,
+        );
+        assert_eq!(
+            err.to_string(),
+            "Key Vault returned 403 Forbidden (Forbidden): Caller is not authorized"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use crate::server::encryption::EncryptionProvider;
+use crate::server::settings::EncryptionSettings;
+
+/// Prefix of ciphertexts tagged with the provider that produced them.
+/// Full format: `rise:v1:{tag}:{ciphertext}`
+const TAGGED_PREFIX: &str = "rise:v1:";
+
+/// Encryption provider that encrypts with a primary provider and falls back to
+/// legacy providers on decrypt, for migrating between providers without
+/// re-encrypting every secret at once.
+///
+/// While legacy providers are configured, ciphertexts produced by this provider
+/// are tagged with the primary provider's tag so they are routed straight to the
+/// right provider on decrypt. Untagged ciphertexts (written before the fallback
+/// was configured) are tried against the primary provider first and then against
+/// each legacy provider in order. Without legacy providers it behaves exactly like
+/// the primary provider, but still understands tagged ciphertexts.
+pub struct FallbackEncryptionProvider {
+    primary: TaggedProvider,
+    legacy: Vec<TaggedProvider>,
+}
+
+struct TaggedProvider {
+    tag: String,
+    provider: Arc<dyn EncryptionProvider>,
+}
+
+impl FallbackEncryptionProvider {
+    /// Create a fallback provider from `(tag, provider)` pairs
+    pub fn new(
+        primary: (String, Arc<dyn EncryptionProvider>),
+        legacy: Vec<(String, Arc<dyn EncryptionProvider>)>,
+    ) -> Result<Self> {
+        let primary = TaggedProvider {
+            tag: primary.0,
+            provider: primary.1,
+        };
+        let legacy: Vec<_> = legacy
+            .into_iter()
+            .map(|(tag, provider)| TaggedProvider { tag, provider })
+            .collect();
+
+        let mut seen = std::collections::HashSet::new();
+        for tag in std::iter::once(&primary.tag).chain(legacy.iter().map(|p| &p.tag)) {
+            if tag.is_empty() || tag.contains(':') {
+                bail!("Invalid encryption provider tag '{}'", tag);
+            }
+            if !seen.insert(tag) {
+                bail!("Encryption provider '{}' is configured more than once", tag);
+            }
+        }
+
+        Ok(Self { primary, legacy })
+    }
+
+    /// Stable tag identifying the provider (and key) described by `settings`.
+    ///
+    /// Derived from a hash of the key material, KMS key ID or Vault key, so the key
+    /// itself never ends up in stored ciphertexts.
+    pub fn tag_for(settings: &EncryptionSettings) -> String {
+        let (kind, identity) = match settings {
+            EncryptionSettings::Local { key } => ("aes-gcm-256", key.clone()),
+            EncryptionSettings::AwsKms { key_id, .. } => ("aws-kms", key_id.clone()),
+            EncryptionSettings::Vault {
+                address, key_name, ..
+            } => ("vault", format!("{}/{}", address, key_name)),
+            EncryptionSettings::AzureKeyVault {
+                vault_url,
+                key_name,
+                ..
+            } => ("azure-key-vault", format!("{}/{}", vault_url, key_name)),
+            EncryptionSettings::GcpKms { key_name, .. } => ("gcp-kms", key_name.clone()),
+            EncryptionSettings::InsecurePlaintext => ("insecure-plaintext", String::new()),
+        };
+        let digest = Sha256::digest(format!("{}:{}", kind, identity).as_bytes());
+        let fingerprint: String = digest[..6].iter().map(|b| format!("{:02x}", b)).collect();
+        format!("{}-{}", kind, fingerprint)
+    }
+
+    fn providers(&self) -> impl Iterator<Item = &TaggedProvider> {
+        std::iter::once(&self.primary).chain(self.legacy.iter())
+    }
+}
+
+#[async_trait]
+impl EncryptionProvider for FallbackEncryptionProvider {
+    async fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let ciphertext = self.primary.provider.encrypt(plaintext).await?;
+        if self.legacy.is_empty() {
+            return Ok(ciphertext);
+        }
+        Ok(format!(
+            "{}{}:{}",
+            TAGGED_PREFIX, self.primary.tag, ciphertext
+        ))
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> Result<String> {
+        if let Some(tagged) = ciphertext.strip_prefix(TAGGED_PREFIX) {
+            let (tag, inner) = tagged
+                .split_once(':')
+                .context("Invalid tagged ciphertext: missing provider tag")?;
+            let Some(entry) = self.providers().find(|p| p.tag == tag) else {
+                bail!(
+                    "Ciphertext was encrypted with provider '{}', which is not configured",
+                    tag
+                );
+            };
+            return entry
+                .provider
+                .decrypt(inner)
+                .await
+                .with_context(|| format!("Decryption with provider '{}' failed", tag));
+        }
+
+        // Untagged ciphertext: try providers in order of preference
+        let mut last_error = None;
+        for entry in self.providers() {
+            match entry.provider.decrypt(ciphertext).await {
+                Ok(plaintext) => {
+                    if entry.tag != self.primary.tag {
+                        tracing::debug!(
+                            "Decrypted untagged ciphertext with legacy provider '{}'",
+                            entry.tag
+                        );
+                    }
+                    return Ok(plaintext);
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| anyhow::anyhow!("No encryption provider configured"))
+            .context("Decryption failed with all configured encryption providers"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::encryption::providers::local::LocalEncryptionProvider;
+
+    const KEY_A: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+    const KEY_B: &str = "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=";
+
+    fn local(key: &str) -> (String, Arc<dyn EncryptionProvider>) {
+        (
+            FallbackEncryptionProvider::tag_for(&EncryptionSettings::Local {
+                key: key.to_string(),
+            }),
+            Arc::new(LocalEncryptionProvider::new(key).unwrap()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_tags_ciphertext_with_primary() {
+        let provider = FallbackEncryptionProvider::new(local(KEY_B), vec![local(KEY_A)]).unwrap();
+
+        let ciphertext = provider.encrypt("hello").await.unwrap();
+        assert!(ciphertext.starts_with(&format!("{}{}:", TAGGED_PREFIX, local(KEY_B).0)));
+        assert_eq!(provider.decrypt(&ciphertext).await.unwrap(), "hello");
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_untagged_legacy_ciphertext() {
+        let legacy = LocalEncryptionProvider::new(KEY_A).unwrap();
+        let old_ciphertext = legacy.encrypt("old secret").await.unwrap();
+
+        let provider = FallbackEncryptionProvider::new(local(KEY_B), vec![local(KEY_A)]).unwrap();
+        assert_eq!(
+            provider.decrypt(&old_ciphertext).await.unwrap(),
+            "old secret"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_tagged_ciphertext_from_legacy_provider() {
+        // Written while migrating to KEY_A, read after switching again to KEY_B
+        let old = FallbackEncryptionProvider::new(local(KEY_A), vec![local(KEY_B)]).unwrap();
+        let ciphertext = old.encrypt("tagged secret").await.unwrap();
+
+        let provider = FallbackEncryptionProvider::new(local(KEY_B), vec![local(KEY_A)]).unwrap();
+        assert_eq!(
+            provider.decrypt(&ciphertext).await.unwrap(),
+            "tagged secret"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_tag_without_legacy_providers() {
+        let provider = FallbackEncryptionProvider::new(local(KEY_A), vec![]).unwrap();
+        let ciphertext = provider.encrypt("secret").await.unwrap();
+
+        let plain = LocalEncryptionProvider::new(KEY_A).unwrap();
+        assert_eq!(plain.decrypt(&ciphertext).await.unwrap(), "secret");
+    }
+
+    #[tokio::test]
+    async fn test_tagged_ciphertext_readable_after_legacy_providers_removed() {
+        let migrating = FallbackEncryptionProvider::new(local(KEY_B), vec![local(KEY_A)]).unwrap();
+        let ciphertext = migrating.encrypt("secret").await.unwrap();
+
+        let migrated = FallbackEncryptionProvider::new(local(KEY_B), vec![]).unwrap();
+        assert_eq!(migrated.decrypt(&ciphertext).await.unwrap(), "secret");
+    }
+
+    #[tokio::test]
+    async fn test_decrypt_fails_for_unknown_tag() {
+        let old = FallbackEncryptionProvider::new(local(KEY_A), vec![local(KEY_B)]).unwrap();
+        let ciphertext = old.encrypt("secret").await.unwrap();
+
+        let provider = FallbackEncryptionProvider::new(local(KEY_B), vec![]).unwrap();
+        let err = provider.decrypt(&ciphertext).await.unwrap_err();
+        assert!(err.to_string().contains("not configured"));
+    }
+
+    #[test]
+    fn test_duplicate_providers_are_rejected() {
+        assert!(FallbackEncryptionProvider::new(local(KEY_A), vec![local(KEY_A)]).is_err());
+    }
+
+    #[test]
+    fn test_tag_is_stable_and_key_specific() {
+        let a = EncryptionSettings::Local {
+            key: KEY_A.to_string(),
+        };
+        let b = EncryptionSettings::Local {
+            key: KEY_B.to_string(),
+        };
+        assert_eq!(
+            FallbackEncryptionProvider::tag_for(&a),
+            FallbackEncryptionProvider::tag_for(&a)
+        );
+        assert_ne!(
+            FallbackEncryptionProvider::tag_for(&a),
+            FallbackEncryptionProvider::tag_for(&b)
+        );
+        assert!(FallbackEncryptionProvider::tag_for(&a).starts_with("aes-gcm-256-"));
+    }
+}
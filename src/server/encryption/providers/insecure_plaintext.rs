@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use crate::server::encryption::EncryptionProvider;
+
+/// Prefix of values stored by `InsecurePlaintextEncryptionProvider`, so they are
+/// never mistaken for real ciphertext
+pub const INSECURE_PLAINTEXT_PREFIX: &str = "insecure-plaintext:";
+
+/// Passthrough "encryption" provider for local development
+///
+/// Stores secrets unencrypted, prefixed with `insecure-plaintext:`. It lets the
+/// secret env var flows work without a key or KMS, and must never be used in production.
+pub struct InsecurePlaintextEncryptionProvider;
+
+#[async_trait]
+impl EncryptionProvider for InsecurePlaintextEncryptionProvider {
+    async fn encrypt(&self, plaintext: &str) -> Result<String> {
+        Ok(format!("{}{}", INSECURE_PLAINTEXT_PREFIX, plaintext))
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> Result<String> {
+        ciphertext
+            .strip_prefix(INSECURE_PLAINTEXT_PREFIX)
+            .map(str::to_string)
+            .context("Value was not stored by the insecure-plaintext provider")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_roundtrip_is_prefixed_plaintext() {
+        let provider = InsecurePlaintextEncryptionProvider;
+
+        let stored = provider.encrypt("my secret").await.unwrap();
+        assert_eq!(stored, "insecure-plaintext:my secret");
+        assert_eq!(provider.decrypt(&stored).await.unwrap(), "my secret");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_real_ciphertext() {
+        let provider = InsecurePlaintextEncryptionProvider;
+        assert!(provider
+            .decrypt("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+            .await
+            .is_err());
+    }
+}
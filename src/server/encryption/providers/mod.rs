@@ -1,3 +1,11 @@
 #[cfg(feature = "backend")]
 pub mod aws_kms;
+#[cfg(feature = "backend")]
+pub mod azure_key_vault;
+pub mod fallback;
+#[cfg(feature = "backend")]
+pub mod gcp_kms;
+pub mod insecure_plaintext;
 pub mod local;
+#[cfg(feature = "backend")]
+pub mod vault;
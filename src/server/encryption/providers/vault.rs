@@ -0,0 +1,270 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::server::encryption::EncryptionProvider;
+
+/// Delay before retrying a failed token renewal
+const RENEW_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// HashiCorp Vault encryption provider
+/// Uses the transit secrets engine; ciphertexts are stored in Vault's `vault:v1:...` format
+pub struct VaultEncryptionProvider {
+    client: VaultClient,
+    key_name: String,
+}
+
+/// Minimal authenticated client for the Vault HTTP API
+#[derive(Clone)]
+struct VaultClient {
+    http_client: reqwest::Client,
+    address: String,
+    token: String,
+    namespace: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct VaultResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct EncryptData {
+    ciphertext: String,
+}
+
+#[derive(Deserialize)]
+struct DecryptData {
+    plaintext: String,
+}
+
+#[derive(Deserialize)]
+struct TokenLookupData {
+    ttl: u64,
+    #[serde(default)]
+    renewable: bool,
+}
+
+#[derive(Deserialize)]
+struct TokenRenewResponse {
+    auth: TokenRenewAuth,
+}
+
+#[derive(Deserialize)]
+struct TokenRenewAuth {
+    lease_duration: u64,
+}
+
+#[derive(Deserialize)]
+struct VaultErrors {
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
+impl VaultClient {
+    async fn request<T: serde::de::DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let url = format!("{}/v1/{}", self.address.trim_end_matches('/'), path);
+        let mut request = self
+            .http_client
+            .request(method, &url)
+            .header("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach Vault at {}", self.address))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(vault_error(&self.address, status, &body));
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse Vault response for {}", path))
+    }
+
+    /// Renew the token at half its TTL for as long as the process runs
+    async fn renew_token_loop(self, initial_ttl: u64) {
+        let mut delay = Duration::from_secs((initial_ttl / 2).max(1));
+        loop {
+            tokio::time::sleep(delay).await;
+            match self
+                .request::<TokenRenewResponse>(
+                    reqwest::Method::POST,
+                    "auth/token/renew-self",
+                    Some(serde_json::json!({})),
+                )
+                .await
+            {
+                Ok(response) => {
+                    let ttl = response.auth.lease_duration;
+                    if ttl == 0 {
+                        return;
+                    }
+                    tracing::debug!("Renewed Vault token (ttl: {}s)", ttl);
+                    delay = Duration::from_secs((ttl / 2).max(1));
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to renew Vault token: {:#}", e);
+                    delay = RENEW_RETRY_DELAY;
+                }
+            }
+        }
+    }
+}
+
+/// Turn a failed Vault response into an error, calling out a sealed Vault explicitly
+fn vault_error(address: &str, status: StatusCode, body: &str) -> anyhow::Error {
+    let errors = serde_json::from_str::<VaultErrors>(body)
+        .map(|e| e.errors.join("; "))
+        .unwrap_or_else(|_| body.to_string());
+
+    if status == StatusCode::SERVICE_UNAVAILABLE && errors.to_lowercase().contains("sealed") {
+        return anyhow::anyhow!(
+            "Vault at {} is sealed; secrets cannot be encrypted or decrypted until it is unsealed",
+            address
+        );
+    }
+    anyhow::anyhow!("Vault returned {}: {}", status, errors)
+}
+
+impl VaultEncryptionProvider {
+    /// Create a new Vault encryption provider
+    ///
+    /// If the token has a TTL and is renewable, a background task keeps renewing it.
+    pub async fn new(
+        address: String,
+        token: String,
+        key_name: String,
+        namespace: Option<String>,
+    ) -> Result<Self> {
+        let client = VaultClient {
+            http_client: reqwest::Client::new(),
+            address,
+            token,
+            namespace,
+        };
+
+        match client
+            .request::<VaultResponse<TokenLookupData>>(
+                reqwest::Method::GET,
+                "auth/token/lookup-self",
+                None,
+            )
+            .await
+        {
+            Ok(lookup) if lookup.data.ttl > 0 && lookup.data.renewable => {
+                tracing::info!(
+                    "Vault token expires in {}s, renewing in the background",
+                    lookup.data.ttl
+                );
+                tokio::spawn(client.clone().renew_token_loop(lookup.data.ttl));
+            }
+            Ok(lookup) if lookup.data.ttl > 0 => {
+                tracing::warn!(
+                    "Vault token is not renewable and expires in {}s",
+                    lookup.data.ttl
+                );
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to look up Vault token, it will not be renewed: {:#}",
+                    e
+                );
+            }
+        }
+
+        Ok(Self { client, key_name })
+    }
+}
+
+#[async_trait]
+impl EncryptionProvider for VaultEncryptionProvider {
+    async fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let response: VaultResponse<EncryptData> = self
+            .client
+            .request(
+                reqwest::Method::POST,
+                &format!("transit/encrypt/{}", self.key_name),
+                Some(serde_json::json!({ "plaintext": BASE64.encode(plaintext) })),
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Vault transit encryption failed for key '{}'",
+                    self.key_name
+                )
+            })?;
+
+        Ok(response.data.ciphertext)
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> Result<String> {
+        if !ciphertext.starts_with("vault:") {
+            bail!("Ciphertext was not produced by Vault transit");
+        }
+
+        let response: VaultResponse<DecryptData> = self
+            .client
+            .request(
+                reqwest::Method::POST,
+                &format!("transit/decrypt/{}", self.key_name),
+                Some(serde_json::json!({ "ciphertext": ciphertext })),
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Vault transit decryption failed for key '{}'",
+                    self.key_name
+                )
+            })?;
+
+        let plaintext = BASE64
+            .decode(response.data.plaintext)
+            .context("Failed to decode plaintext from Vault")?;
+        String::from_utf8(plaintext).context("Decrypted data is not valid UTF-8")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_error_reports_sealed_vault() {
+        let err = vault_error(
+            "https://vault.example.com",
+            StatusCode::SERVICE_UNAVAILABLE,
+            r#"{"errors":["Vault is sealed"]}"#,
+        );
+        assert!(err.to_string().contains("is sealed"));
+
+        let err = vault_error(
+            "https://vault.example.com",
+            StatusCode::FORBIDDEN,
+            r#"{"errors":["permission denied"]}"#,
+        );
+        assert_eq!(
+            err.to_string(),
+            "Vault returned 403 Forbidden: permission denied"
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use super::models::{
     EnvVarResponse, EnvVarValueResponse, EnvVarsResponse, MoveEnvVarRequest, SetEnvVarRequest,
+    SetEnvVarsRequest,
 };
 use crate::db::{env_vars as db_env_vars, environments as db_environments, projects};
 use crate::server::auth::context::AuthContext;
+use crate::server::deployment::env_interpolation::{self, EnvValue};
 use crate::server::deployment::models as deployment_models;
 use crate::server::error::{ServerError, ServerErrorExt};
 use crate::server::extensions::InjectedEnvVarValue;
@@ -34,6 +36,92 @@ async fn resolve_environment_id(
     }
 }
 
+/// Encrypt a secret value with the configured encryption provider.
+///
+/// Fails the same way as `decrypt_secret` when the server has no provider, so
+/// setting and reading secrets report a missing provider consistently.
+async fn encrypt_secret(state: &AppState, value: &str) -> Result<String, ServerError> {
+    let provider = state
+        .encryption_provider
+        .as_ref()
+        .ok_or_else(no_encryption_provider)?;
+    provider
+        .encrypt(value)
+        .await
+        .internal_err("Failed to encrypt secret")
+}
+
+/// Decrypt a stored secret value with the configured encryption provider
+async fn decrypt_secret(state: &AppState, value: &str) -> Result<String, ServerError> {
+    let provider = state
+        .encryption_provider
+        .as_ref()
+        .ok_or_else(no_encryption_provider)?;
+    provider
+        .decrypt(value)
+        .await
+        .internal_err("Failed to decrypt secret")
+}
+
+/// Reject new values whose `${VAR}` references would form a loop
+///
+/// Checks the variables a deployment in the written scope sees: the global ones,
+/// plus the environment's own when `environment_id` is set. Stored secrets are only
+/// decrypted when one of the new values contains a reference.
+async fn reject_circular_references(
+    state: &AppState,
+    project_id: uuid::Uuid,
+    environment_id: Option<uuid::Uuid>,
+    updates: &[(&str, &str)],
+) -> Result<(), ServerError> {
+    if !updates.iter().any(|(_, value)| value.contains("${")) {
+        return Ok(());
+    }
+
+    let stored = db_env_vars::list_project_env_vars(&state.db_pool, project_id, environment_id)
+        .await
+        .internal_err("Failed to list environment variables")?;
+
+    // Environment-scoped values are listed before the global value of the same key
+    let mut values: HashMap<String, String> = HashMap::new();
+    for var in stored {
+        if values.contains_key(&var.key)
+            || (environment_id.is_none() && var.environment_id.is_some())
+        {
+            continue;
+        }
+        let value = if var.is_secret {
+            decrypt_secret(state, &var.value).await?
+        } else {
+            var.value
+        };
+        values.insert(var.key, value);
+    }
+    for (key, value) in updates {
+        values.insert(key.to_string(), value.to_string());
+    }
+
+    env_interpolation::interpolate(
+        values
+            .into_iter()
+            .map(|(key, value)| EnvValue {
+                key,
+                value,
+                is_secret: false,
+            })
+            .collect(),
+    )
+    .map_err(|e| ServerError::bad_request(e.to_string()))?;
+    Ok(())
+}
+
+/// Error for secret operations on a server without an encryption provider
+pub(crate) fn no_encryption_provider() -> ServerError {
+    ServerError::bad_request(
+        "Secret variables are not available: no encryption provider configured on the server",
+    )
+}
+
 /// Set or update a project environment variable
 pub async fn set_project_env_var(
     State(state): State<AppState>,
@@ -68,31 +156,24 @@ pub async fn set_project_env_var(
         ));
     }
 
-    // IMPORTANT: If this is a secret, we must have an encryption provider
-    if payload.is_secret && state.encryption_provider.is_none() {
-        return Err(ServerError::bad_request(
-            "Cannot store secret variables: no encryption provider configured",
-        ));
-    }
+    // Resolve environment from query parameter
+    let environment_id = resolve_environment_id(&state.db_pool, project.id, &params).await?;
+
+    reject_circular_references(
+        &state,
+        project.id,
+        environment_id,
+        &[(key.as_str(), payload.value.as_str())],
+    )
+    .await?;
 
     // Encrypt the value if it's a secret
     let value_to_store = if payload.is_secret {
-        let provider = state
-            .encryption_provider
-            .as_ref()
-            .expect("Encryption provider checked above");
-
-        provider
-            .encrypt(&payload.value)
-            .await
-            .internal_err("Failed to encrypt secret")?
+        encrypt_secret(&state, &payload.value).await?
     } else {
         payload.value.clone()
     };
 
-    // Resolve environment from query parameter
-    let environment_id = resolve_environment_id(&state.db_pool, project.id, &params).await?;
-
     let env_var = db_env_vars::upsert_project_env_var(
         &state.db_pool,
         project.id,
@@ -129,6 +210,116 @@ pub async fn set_project_env_var(
     Ok(Json(response))
 }
 
+/// Set several environment variables for a project in one transaction
+pub async fn set_project_env_vars(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(project_id_or_name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    Json(payload): Json<SetEnvVarsRequest>,
+) -> Result<Json<EnvVarsResponse>, ServerError> {
+    // Find project by ID or name
+    let project = if let Ok(uuid) = project_id_or_name.parse() {
+        projects::find_by_id(&state.db_pool, uuid)
+            .await
+            .internal_err("Failed to get project")?
+    } else {
+        projects::find_by_name(&state.db_pool, &project_id_or_name)
+            .await
+            .internal_err("Failed to get project")?
+    }
+    .ok_or_else(|| ServerError::not_found("Project not found"))?;
+
+    let user = auth.user()?;
+    ensure_project_access_or_admin(&state, user, &project).await?;
+
+    if payload.env_vars.is_empty() {
+        return Err(ServerError::bad_request(
+            "At least one environment variable is required",
+        ));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for item in &payload.env_vars {
+        if item.key.is_empty() {
+            return Err(ServerError::bad_request(
+                "Environment variable keys cannot be empty",
+            ));
+        }
+        if !seen.insert(item.key.as_str()) {
+            return Err(ServerError::bad_request(format!(
+                "Environment variable '{}' is specified more than once",
+                item.key
+            )));
+        }
+        if item.is_protected.unwrap_or(item.is_secret) && !item.is_secret {
+            return Err(ServerError::bad_request(
+                "Non-secret variables cannot be protected. Protection only applies to secrets.",
+            ));
+        }
+    }
+
+    let environment_id = resolve_environment_id(&state.db_pool, project.id, &params).await?;
+
+    let updates: Vec<(&str, &str)> = payload
+        .env_vars
+        .iter()
+        .map(|item| (item.key.as_str(), item.value.as_str()))
+        .collect();
+    reject_circular_references(&state, project.id, environment_id, &updates).await?;
+
+    // Encrypt all secrets before touching the database so a provider failure
+    // cannot leave a partially applied set behind
+    let mut values = Vec::with_capacity(payload.env_vars.len());
+    for item in &payload.env_vars {
+        values.push(if item.is_secret {
+            encrypt_secret(&state, &item.value).await?
+        } else {
+            item.value.clone()
+        });
+    }
+
+    let upserts: Vec<_> = payload
+        .env_vars
+        .iter()
+        .zip(&values)
+        .map(|(item, value)| db_env_vars::ProjectEnvVarUpsert {
+            key: &item.key,
+            value,
+            is_secret: item.is_secret,
+            is_protected: item.is_protected.unwrap_or(item.is_secret),
+        })
+        .collect();
+
+    let stored =
+        db_env_vars::upsert_project_env_vars(&state.db_pool, project.id, &upserts, environment_id)
+            .await
+            .internal_err("Failed to store environment variables")?;
+
+    tracing::info!(
+        "Set {} environment variables for project '{}'. This will apply to new deployments only.",
+        stored.len(),
+        project.name
+    );
+
+    let env_vars = stored
+        .into_iter()
+        .map(|env_var| {
+            let mut response = EnvVarResponse::from_db_model(
+                env_var.key,
+                env_var.value,
+                env_var.is_secret,
+                env_var.is_protected,
+                None,
+            );
+            response.environment = params.get("environment").cloned();
+            response
+        })
+        .collect();
+
+    Ok(Json(EnvVarsResponse { env_vars }))
+}
+
 /// List all environment variables for a project
 pub async fn list_project_env_vars(
     State(state): State<AppState>,
@@ -177,17 +368,7 @@ pub async fn list_project_env_vars(
     for var in db_env_vars {
         let value = if include_unprotected && var.is_secret && !var.is_protected {
             // Decrypt unprotected secret
-            match &state.encryption_provider {
-                Some(provider) => provider
-                    .decrypt(&var.value)
-                    .await
-                    .internal_err("Failed to decrypt secret")?,
-                None => {
-                    return Err(ServerError::internal(
-                        "Cannot decrypt secrets: no encryption provider configured",
-                    ))
-                }
-            }
+            decrypt_secret(&state, &var.value).await?
         } else {
             var.value.clone()
         };
@@ -414,17 +595,7 @@ pub async fn list_deployment_env_vars(
     for var in db_env_vars {
         let value = if include_unprotected && var.is_secret && !var.is_protected {
             // Decrypt unprotected secret
-            match &state.encryption_provider {
-                Some(provider) => provider
-                    .decrypt(&var.value)
-                    .await
-                    .internal_err("Failed to decrypt secret")?,
-                None => {
-                    return Err(ServerError::internal(
-                        "Cannot decrypt secrets: no encryption provider configured",
-                    ))
-                }
-            }
+            decrypt_secret(&state, &var.value).await?
         } else {
             var.value.clone()
         };
@@ -499,17 +670,7 @@ pub async fn get_project_env_var_value(
     }
 
     // Decrypt the value
-    let decrypted_value = match &state.encryption_provider {
-        Some(provider) => provider
-            .decrypt(&env_var.value)
-            .await
-            .internal_err("Failed to decrypt secret")?,
-        None => {
-            return Err(ServerError::internal(
-                "Cannot decrypt secrets: no encryption provider configured",
-            ))
-        }
-    };
+    let decrypted_value = decrypt_secret(&state, &env_var.value).await?;
 
     tracing::info!(
         "Retrieved decrypted value for secret '{}' in project '{}' by user '{}'",
@@ -569,17 +730,7 @@ pub async fn get_deployment_env_var_value(
     }
 
     // Decrypt the value
-    let decrypted_value = match &state.encryption_provider {
-        Some(provider) => provider
-            .decrypt(&env_var.value)
-            .await
-            .internal_err("Failed to decrypt secret")?,
-        None => {
-            return Err(ServerError::internal(
-                "Cannot decrypt secrets: no encryption provider configured",
-            ))
-        }
-    };
+    let decrypted_value = decrypt_secret(&state, &env_var.value).await?;
 
     tracing::info!(
         "Retrieved decrypted value for secret '{}' in deployment '{}' by user '{}'",
@@ -651,17 +802,7 @@ pub async fn preview_deployment_env_vars(
 
         if var.is_secret && !var.is_protected {
             // Unprotected secret — decrypt for preview
-            let decrypted = match &state.encryption_provider {
-                Some(provider) => provider
-                    .decrypt(&var.value)
-                    .await
-                    .internal_err("Failed to decrypt secret")?,
-                None => {
-                    return Err(ServerError::internal(
-                        "Cannot decrypt secrets: no encryption provider configured",
-                    ))
-                }
-            };
+            let decrypted = decrypt_secret(&state, &var.value).await?;
             env_map.insert(
                 var.key.clone(),
                 EnvVarResponse {
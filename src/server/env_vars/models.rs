@@ -10,6 +10,23 @@ pub struct SetEnvVarRequest {
     pub is_protected: Option<bool>,
 }
 
+/// A single variable in a `SetEnvVarsRequest`
+#[derive(Debug, Deserialize)]
+pub struct SetEnvVarsItem {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub is_secret: bool,
+    /// When omitted, defaults to the same value as is_secret
+    pub is_protected: Option<bool>,
+}
+
+/// Request to set or update several environment variables at once
+#[derive(Debug, Deserialize)]
+pub struct SetEnvVarsRequest {
+    pub env_vars: Vec<SetEnvVarsItem>,
+}
+
 /// Request to move an environment variable to a different environment
 #[derive(Debug, Deserialize)]
 pub struct MoveEnvVarRequest {
@@ -23,7 +23,7 @@ pub fn routes() -> Router<AppState> {
         )
         .route(
             "/projects/{project_id_or_name}/env",
-            get(handlers::list_project_env_vars),
+            get(handlers::list_project_env_vars).put(handlers::set_project_env_vars),
         )
         // Preview all env vars a deployment would receive
         .route(
@@ -143,6 +143,11 @@ impl ServerError {
         Self::new(StatusCode::GONE, message)
     }
 
+    /// Create a 429 Too Many Requests error
+    pub fn too_many_requests(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::TOO_MANY_REQUESTS, message)
+    }
+
     /// Create a 503 Service Unavailable error
     pub fn service_unavailable(message: impl Into<String>) -> Self {
         Self::new(StatusCode::SERVICE_UNAVAILABLE, message)
@@ -33,6 +33,35 @@ pub async fn list_extension_types(
     Ok(Json(ListExtensionTypesResponse { extension_types }))
 }
 
+/// Validate a spec against an extension type without creating anything
+pub async fn validate_extension_spec(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(extension_type): Path<String>,
+    Json(payload): Json<ValidateExtensionSpecRequest>,
+) -> Result<Json<ValidateExtensionSpecResponse>, ServerError> {
+    let _user = auth.user()?;
+    // Like listing extension types, this doesn't touch any project
+
+    let extension = state
+        .extension_registry
+        .get(&extension_type)
+        .ok_or_else(|| {
+            ServerError::not_found(format!("Unknown extension type: {}", extension_type))
+        })?;
+
+    let error = extension
+        .validate_spec(&payload.spec)
+        .await
+        .err()
+        .map(|e| e.to_string());
+
+    Ok(Json(ValidateExtensionSpecResponse {
+        valid: error.is_none(),
+        error,
+    }))
+}
+
 /// Create or upsert extension for project
 pub async fn create_extension(
     State(state): State<AppState>,
@@ -390,6 +419,37 @@ pub async fn get_extension(
     }))
 }
 
+/// Get the recent reconcile events of an extension
+pub async fn get_extension_events(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path((project_name, extension_name)): Path<(String, String)>,
+) -> Result<Json<ExtensionEventsResponse>, ServerError> {
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to look up project")?
+        .ok_or_else(|| ServerError::not_found("Project not found"))?;
+
+    let user = auth.user()?;
+    let has_access = check_project_access(&state, user, project.id).await?;
+    if !has_access {
+        return Err(ServerError::forbidden("Access denied"));
+    }
+
+    let ext = db_extensions::find_by_project_and_name(&state.db_pool, project.id, &extension_name)
+        .await
+        .internal_err("Failed to look up extension")?
+        .ok_or_else(|| ServerError::not_found("Extension not found"))?;
+
+    let events = state
+        .extension_registry
+        .get(&ext.extension_type)
+        .map(|ext_provider| ext_provider.recent_events(&ext.status))
+        .unwrap_or_default();
+
+    Ok(Json(ExtensionEventsResponse { events }))
+}
+
 /// Delete extension (mark for deletion)
 pub async fn delete_extension(
     State(state): State<AppState>,
@@ -1,5 +1,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
@@ -42,6 +44,55 @@ pub struct InjectedEnvVar {
     pub value: InjectedEnvVarValue,
 }
 
+/// Maximum number of events an extension keeps in its status
+pub const MAX_EXTENSION_EVENTS: usize = 50;
+
+/// Severity of an extension event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtensionEventLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A reconcile event recorded by an extension, shown by `rise extension logs`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionEvent {
+    pub timestamp: DateTime<Utc>,
+    pub level: ExtensionEventLevel,
+    pub message: String,
+}
+
+/// Append an event to an extension's event list, keeping the newest
+/// `MAX_EXTENSION_EVENTS`.
+///
+/// Reconcile loops retry the same step repeatedly, so an event identical to the
+/// latest one only refreshes its timestamp instead of flooding the list.
+pub fn record_event(
+    events: &mut Vec<ExtensionEvent>,
+    level: ExtensionEventLevel,
+    message: impl Into<String>,
+) {
+    let message = message.into();
+    let now = Utc::now();
+    if let Some(last) = events.last_mut() {
+        if last.level == level && last.message == message {
+            last.timestamp = now;
+            return;
+        }
+    }
+    events.push(ExtensionEvent {
+        timestamp: now,
+        level,
+        message,
+    });
+    if events.len() > MAX_EXTENSION_EVENTS {
+        let excess = events.len() - MAX_EXTENSION_EVENTS;
+        events.drain(..excess);
+    }
+}
+
 /// Extension trait for project resource provisioning
 #[async_trait]
 pub trait Extension: Send + Sync {
@@ -165,6 +216,22 @@ pub trait Extension: Send + Sync {
     /// - "Failed: Invalid subnet group"
     fn format_status(&self, status: &Value) -> String;
 
+    /// Recent reconcile events, oldest first
+    ///
+    /// Lets users see why provisioning is stuck (`rise extension logs`). The default
+    /// implementation reads the `events` array that extensions maintain in their
+    /// status with [`record_event`].
+    ///
+    /// # Arguments
+    /// * `status` - The status JSONB value from the database
+    fn recent_events(&self, status: &Value) -> Vec<ExtensionEvent> {
+        status
+            .get("events")
+            .cloned()
+            .and_then(|events| serde_json::from_value(events).ok())
+            .unwrap_or_default()
+    }
+
     /// Get a human-readable description of the extension
     ///
     /// This should be a concise one-line summary of what the extension does.
@@ -196,3 +263,29 @@ pub trait Extension: Send + Sync {
     /// JSON value representing the schema or example spec
     fn spec_schema(&self) -> Value;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_event_collapses_repeats_and_caps_length() {
+        let mut events = Vec::new();
+        record_event(&mut events, ExtensionEventLevel::Warning, "still creating");
+        record_event(&mut events, ExtensionEventLevel::Warning, "still creating");
+        assert_eq!(events.len(), 1);
+
+        for i in 0..MAX_EXTENSION_EVENTS + 5 {
+            record_event(
+                &mut events,
+                ExtensionEventLevel::Info,
+                format!("step {}", i),
+            );
+        }
+        assert_eq!(events.len(), MAX_EXTENSION_EVENTS);
+        assert_eq!(
+            events.last().unwrap().message,
+            format!("step {}", MAX_EXTENSION_EVENTS + 4)
+        );
+    }
+}
@@ -16,6 +16,13 @@ pub struct Extension {
     pub updated: String,
 }
 
+/// Response listing an extension's recent reconcile events
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExtensionEventsResponse {
+    /// Events, oldest first
+    pub events: Vec<super::ExtensionEvent>,
+}
+
 /// Request to create an extension
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateExtensionRequest {
@@ -24,6 +31,21 @@ pub struct CreateExtensionRequest {
     pub spec: Value,
 }
 
+/// Request to validate a spec against an extension type
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidateExtensionSpecRequest {
+    pub spec: Value,
+}
+
+/// Result of validating a spec (an invalid spec is not an error response)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidateExtensionSpecResponse {
+    pub valid: bool,
+    /// Why the spec was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 /// Response after creating an extension
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateExtensionResponse {
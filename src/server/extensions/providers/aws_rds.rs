@@ -3,7 +3,10 @@ use crate::db::{
     leader_leases::LeaderElection, postgres_admin, projects as db_projects,
 };
 use crate::server::encryption::EncryptionProvider;
-use crate::server::extensions::{Extension, InjectedEnvVar, InjectedEnvVarValue};
+use crate::server::extensions::{
+    record_event, Extension, ExtensionEvent, ExtensionEventLevel, InjectedEnvVar,
+    InjectedEnvVarValue,
+};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use aws_sdk_rds::Client as RdsClient;
@@ -119,6 +122,9 @@ pub struct AwsRdsStatus {
     /// Last error message
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Recent reconcile events, shown by `rise extension logs`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<ExtensionEvent>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -238,6 +244,7 @@ impl AwsRdsProvisioner {
                 master_password_encrypted: None,
                 databases: HashMap::new(),
                 error: None,
+                events: Vec::new(),
             });
 
         // Migrate old finalizer format to new format
@@ -370,6 +377,11 @@ impl AwsRdsProvisioner {
                     "RDS instance for project {} is in failed state, retrying immediately",
                     project.name
                 );
+                record_event(
+                    &mut status.events,
+                    ExtensionEventLevel::Info,
+                    "Retrying after failure",
+                );
                 status.state = RdsState::Pending;
                 status.error = None;
             }
@@ -443,6 +455,7 @@ impl AwsRdsProvisioner {
         if self.vpc_security_group_ids.is_some() && self.db_subnet_group_name.is_none() {
             let error_msg = "vpc_security_group_ids requires db_subnet_group_name to be set";
             error!("{}", error_msg);
+            record_event(&mut status.events, ExtensionEventLevel::Error, error_msg);
             status.state = RdsState::Failed;
             status.error = Some(error_msg.to_string());
             return Ok(());
@@ -506,6 +519,14 @@ impl AwsRdsProvisioner {
         match create_request.send().await {
             Ok(_) => {
                 info!("RDS create request sent for instance {}", instance_id);
+                record_event(
+                    &mut status.events,
+                    ExtensionEventLevel::Info,
+                    format!(
+                        "Requested RDS instance {} ({})",
+                        instance_id, self.instance_size
+                    ),
+                );
                 status.state = RdsState::Creating;
                 status.instance_id = Some(instance_id);
                 status.instance_size = Some(self.instance_size.clone());
@@ -531,6 +552,11 @@ impl AwsRdsProvisioner {
             }
             Err(e) => {
                 error!("Failed to create RDS instance {}: {:?}", instance_id, e);
+                record_event(
+                    &mut status.events,
+                    ExtensionEventLevel::Error,
+                    format!("Failed to create RDS instance {}: {}", instance_id, e),
+                );
                 status.state = RdsState::Failed;
                 status.error = Some(format!("Failed to create instance: {:?}", e));
             }
@@ -565,6 +591,11 @@ impl AwsRdsProvisioner {
                         match instance_status {
                             "available" => {
                                 info!("RDS instance {} is now available", instance_id);
+                                record_event(
+                                    &mut status.events,
+                                    ExtensionEventLevel::Info,
+                                    format!("RDS instance {} is available", instance_id),
+                                );
                                 status.state = RdsState::Available;
 
                                 // Extract endpoint
@@ -627,9 +658,22 @@ impl AwsRdsProvisioner {
                                     "RDS instance {} is still creating (status: {})",
                                     instance_id, instance_status
                                 );
+                                record_event(
+                                    &mut status.events,
+                                    ExtensionEventLevel::Info,
+                                    format!(
+                                        "Waiting for RDS instance {} (status: {})",
+                                        instance_id, instance_status
+                                    ),
+                                );
                             }
                             "failed" => {
                                 error!("RDS instance {} failed to create", instance_id);
+                                record_event(
+                                    &mut status.events,
+                                    ExtensionEventLevel::Error,
+                                    format!("RDS instance {} failed to create", instance_id),
+                                );
                                 status.state = RdsState::Failed;
                                 status.error = Some("Instance creation failed".to_string());
                             }
@@ -638,6 +682,14 @@ impl AwsRdsProvisioner {
                                     "RDS instance {} has unexpected status: {}",
                                     instance_id, instance_status
                                 );
+                                record_event(
+                                    &mut status.events,
+                                    ExtensionEventLevel::Warning,
+                                    format!(
+                                        "RDS instance {} has unexpected status: {}",
+                                        instance_id, instance_status
+                                    ),
+                                );
                             }
                         }
                     }
@@ -645,6 +697,11 @@ impl AwsRdsProvisioner {
             }
             Err(e) => {
                 error!("Failed to describe RDS instance {}: {:?}", instance_id, e);
+                record_event(
+                    &mut status.events,
+                    ExtensionEventLevel::Warning,
+                    format!("Failed to describe RDS instance {}: {}", instance_id, e),
+                );
                 // Don't fail immediately, will retry on next reconcile
             }
         }
@@ -681,6 +738,14 @@ impl AwsRdsProvisioner {
                                 "RDS instance {} status changed from available to {}",
                                 instance_id, instance_status
                             );
+                            record_event(
+                                &mut status.events,
+                                ExtensionEventLevel::Warning,
+                                format!(
+                                    "RDS instance {} is no longer available (status: {})",
+                                    instance_id, instance_status
+                                ),
+                            );
                             status.state = RdsState::Creating; // Will check again on next reconcile
                         } else {
                             // Instance is available, process any pending databases
@@ -707,6 +772,11 @@ impl AwsRdsProvisioner {
                     }
                 } else {
                     error!("RDS instance {} no longer exists", instance_id);
+                    record_event(
+                        &mut status.events,
+                        ExtensionEventLevel::Error,
+                        format!("RDS instance {} no longer exists", instance_id),
+                    );
                     status.state = RdsState::Failed;
                     status.error = Some("Instance no longer exists".to_string());
                 }
@@ -733,6 +803,11 @@ impl AwsRdsProvisioner {
             match db_status.status {
                 DatabaseState::Pending => {
                     info!("Starting provisioning for database '{}'", db_name);
+                    record_event(
+                        &mut status.events,
+                        ExtensionEventLevel::Info,
+                        format!("Provisioning database '{}'", db_name),
+                    );
                     db_status.status = DatabaseState::CreatingDatabase;
                     // Will continue in next reconciliation
                     return Ok(());
@@ -782,6 +857,11 @@ impl AwsRdsProvisioner {
                         }
                         Err(e) => {
                             error!("Failed to create database '{}': {:?}", db_name, e);
+                            record_event(
+                                &mut status.events,
+                                ExtensionEventLevel::Error,
+                                format!("Failed to create database '{}': {}", db_name, e),
+                            );
                             status.state = RdsState::Creating;
                             status.error = Some(format!("Failed to create database: {}", e));
                             return Ok(());
@@ -879,6 +959,11 @@ impl AwsRdsProvisioner {
                             {
                                 Ok(_) => {
                                     info!("Changed owner of '{}' to '{}'", db_name, db_status.user);
+                                    record_event(
+                                        &mut status.events,
+                                        ExtensionEventLevel::Info,
+                                        format!("Database '{}' is available", db_name),
+                                    );
                                     db_status.status = DatabaseState::Available;
                                 }
                                 Err(e) => {
@@ -889,6 +974,11 @@ impl AwsRdsProvisioner {
                         }
                         Err(e) => {
                             error!("Failed to connect to RDS instance: {:?}", e);
+                            record_event(
+                                &mut status.events,
+                                ExtensionEventLevel::Error,
+                                format!("Failed to connect to RDS instance: {}", e),
+                            );
                             status.error = Some(format!("Failed to connect: {}", e));
                             return Ok(());
                         }
@@ -1086,6 +1176,11 @@ impl AwsRdsProvisioner {
                 {
                     Ok(_) => {
                         info!("RDS delete request sent for instance {}", instance_id);
+                        record_event(
+                            &mut status.events,
+                            ExtensionEventLevel::Info,
+                            format!("Deleting RDS instance {}", instance_id),
+                        );
                         status.state = RdsState::Deleting;
                     }
                     Err(e) => {
@@ -1105,6 +1200,11 @@ impl AwsRdsProvisioner {
                             // Keep current state, will retry on next loop
                         } else {
                             error!("Failed to delete RDS instance {}: {:?}", instance_id, e);
+                            record_event(
+                                &mut status.events,
+                                ExtensionEventLevel::Error,
+                                format!("Failed to delete RDS instance {}: {}", instance_id, e),
+                            );
                             status.error = Some(format!("Failed to delete instance: {:?}", e));
                         }
                     }
@@ -5,5 +5,11 @@ pub mod aws_rds;
 
 pub mod oauth;
 
+#[cfg(feature = "backend")]
+pub mod snowflake_connection;
+
 #[cfg(feature = "backend")]
 pub mod snowflake_oauth;
+
+#[cfg(feature = "backend")]
+pub mod snowflake_secrets;
@@ -0,0 +1,233 @@
+// Snowflake connection shared by the Snowflake extension providers
+
+use crate::server::settings::{PrivateKeySource, SnowflakeAuth};
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use snowflake_connector_rs::{
+    QueryRequest, SnowflakeAuthMethod, SnowflakeClient, SnowflakeClientConfig,
+};
+use tracing::debug;
+
+/// Escape a Snowflake identifier (table name, integration name, etc.)
+/// Validates allowed characters, normalizes hyphens to underscores, and wraps in double quotes
+pub fn escape_identifier(identifier: &str) -> Result<String> {
+    // Snowflake identifiers: alphanumeric, underscore, dollar sign
+    // We're more restrictive for security and normalize hyphens to underscores
+    let normalized = identifier.replace('-', "_");
+    if !normalized.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(anyhow!(
+            "Invalid identifier '{}': only alphanumeric and underscore characters are allowed",
+            identifier
+        ));
+    }
+
+    // Escape internal double quotes and wrap in double quotes
+    let escaped = normalized.replace('"', "\"\"");
+    Ok(format!("\"{}\"", escaped))
+}
+
+/// Escape a string literal for use in Snowflake SQL
+/// Doubles backslashes and single quotes, since Snowflake treats `\` as an escape character
+pub fn escape_string_literal(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "''")
+}
+
+/// Backend Snowflake credentials (from config/{RISE_CONFIG_RUN_MODE}.yaml)
+#[derive(Debug, Clone)]
+pub struct SnowflakeConnection {
+    pub account: String,
+    pub user: String,
+    pub role: Option<String>,
+    pub warehouse: Option<String>,
+    pub auth: SnowflakeAuth,
+}
+
+impl SnowflakeConnection {
+    /// Create Snowflake client using configured credentials
+    fn create_client(&self) -> Result<SnowflakeClient> {
+        let auth_method = match &self.auth {
+            SnowflakeAuth::Password { password } => SnowflakeAuthMethod::Password(password.clone()),
+            SnowflakeAuth::PrivateKey {
+                key_source,
+                private_key_password,
+            } => {
+                let private_key_pem = match key_source {
+                    PrivateKeySource::Path { private_key_path } => {
+                        std::fs::read_to_string(private_key_path)
+                            .context("Failed to read private key file")?
+                    }
+                    PrivateKeySource::Inline { private_key } => private_key.clone(),
+                };
+
+                // Detect if key is encrypted or unencrypted based on PEM header
+                let is_encrypted = private_key_pem.contains("BEGIN ENCRYPTED PRIVATE KEY");
+                let is_unencrypted_pkcs8 = private_key_pem.contains("BEGIN PRIVATE KEY");
+                let is_rsa_key = private_key_pem.contains("BEGIN RSA PRIVATE KEY");
+
+                // For unencrypted keys, we need to convert to encrypted PKCS#8 format
+                // because the Snowflake connector library only supports encrypted keys
+                let password_bytes = if is_encrypted {
+                    // Key is already encrypted, use provided password
+                    private_key_password
+                        .as_ref()
+                        .map(|p| p.as_bytes().to_vec())
+                        .unwrap_or_default()
+                } else if is_unencrypted_pkcs8 || is_rsa_key {
+                    // Key is unencrypted - the library doesn't support this
+                    // We need to return a clear error
+                    return Err(anyhow!(
+                        "Unencrypted private keys are not supported by the Snowflake connector. \n\
+                         \n\
+                         Please encrypt your private key using:\n\
+                         openssl pkcs8 -topk8 -v2 aes256 -in unencrypted_key.pem -out encrypted_key.p8\n\
+                         \n\
+                         Then update your config/{{RISE_CONFIG_RUN_MODE}}.yaml:\n\
+                         auth_type: private_key\n\
+                         private_key: \"$${{SNOWFLAKE_PRIVATE_KEY}}\"  # encrypted key\n\
+                         private_key_password: \"$${{SNOWFLAKE_PRIVATE_KEY_PASSWORD}}\"\n\
+                         \n\
+                         Alternatively, use password authentication instead of private key."
+                    ));
+                } else {
+                    // Unknown key format
+                    return Err(anyhow!(
+                        "Unsupported private key format. Expected PEM format with one of:\n\
+                         - BEGIN ENCRYPTED PRIVATE KEY (PKCS#8 encrypted)\n\
+                         - BEGIN PRIVATE KEY (PKCS#8 unencrypted - not supported, must be encrypted)\n\
+                         - BEGIN RSA PRIVATE KEY (PKCS#1 - not supported, must be PKCS#8 encrypted)"
+                    ));
+                };
+
+                SnowflakeAuthMethod::KeyPair {
+                    encrypted_pem: private_key_pem,
+                    password: password_bytes,
+                }
+            }
+        };
+
+        // Parse account to extract account locator and cloud region
+        // Account format: "account_locator.region" or just "account_locator"
+        let account_parts: Vec<&str> = self.account.split('.').collect();
+        let account_identifier = account_parts
+            .first()
+            .ok_or_else(|| anyhow!("Invalid account format"))?
+            .to_string();
+
+        let mut config = SnowflakeClientConfig {
+            account: account_identifier,
+            ..Default::default()
+        };
+
+        // Set role if configured
+        if let Some(ref role) = self.role {
+            config.role = Some(role.clone());
+        }
+
+        // Set warehouse if configured
+        if let Some(ref warehouse) = self.warehouse {
+            config.warehouse = Some(warehouse.clone());
+        }
+
+        let client = SnowflakeClient::new(&self.user, auth_method, config).map_err(|e| {
+            // Provide helpful error messages for common issues
+            let error_str = format!("{:?}", e);
+            if error_str.contains("ENCRYPTED PRIVATE KEY") {
+                anyhow!(
+                    "Failed to create Snowflake client: {}. \n\
+                     \n\
+                     The snowflake-connector-rs library expects private keys in PKCS#8 encrypted format.\n\
+                     \n\
+                     If you have an unencrypted private key, you can encrypt it with:\n\
+                     openssl pkcs8 -topk8 -v2 aes256 -in rsa_key.p8 -out rsa_key_encrypted.p8\n\
+                     \n\
+                     Or generate a new encrypted key pair:\n\
+                     openssl genrsa 2048 | openssl pkcs8 -topk8 -v2 aes256 -out rsa_key.p8\n\
+                     \n\
+                     Then configure the encrypted key and password in config/{{RISE_CONFIG_RUN_MODE}}.yaml:\n\
+                     auth_type: private_key\n\
+                     private_key: \"$${{SNOWFLAKE_PRIVATE_KEY}}\"\n\
+                     private_key_password: \"$${{SNOWFLAKE_PRIVATE_KEY_PASSWORD}}\"\n\
+                     \n\
+                     Alternatively, use password authentication instead.",
+                    e
+                )
+            } else {
+                anyhow!("Failed to create Snowflake client: {}", e)
+            }
+        })?;
+
+        Ok(client)
+    }
+
+    /// Execute SQL statement on Snowflake
+    ///
+    /// Rows are returned as JSON objects holding the requested `columns` (keyed in
+    /// lowercase); the connector only allows reading values by column name.
+    pub async fn execute_sql(&self, sql: &str, columns: &[&str]) -> Result<Vec<Value>> {
+        self.execute_query(QueryRequest::from(sql), columns).await
+    }
+
+    /// Execute a query with bind parameters (`?` placeholders) on Snowflake
+    ///
+    /// Rows are returned like for [`Self::execute_sql`].
+    pub async fn execute_query(
+        &self,
+        request: QueryRequest,
+        columns: &[&str],
+    ) -> Result<Vec<Value>> {
+        let client = self.create_client()?;
+        let session = client
+            .create_session()
+            .await
+            .context("Failed to create Snowflake session")?;
+
+        // Explicitly set warehouse if configured
+        // The warehouse field in SnowflakeClientConfig might not automatically apply to sessions
+        if let Some(ref warehouse) = self.warehouse {
+            debug!("Setting warehouse for session: {}", warehouse);
+            let use_warehouse_sql = format!("USE WAREHOUSE {}", escape_identifier(warehouse)?);
+            debug!("Executing: {}", use_warehouse_sql);
+            session
+                .query(use_warehouse_sql.as_str())
+                .await
+                .context("Failed to set warehouse for session")?;
+            debug!("Warehouse set successfully");
+        } else {
+            debug!("No warehouse configured - session will have no active warehouse");
+        }
+
+        let rows = session
+            .query(request)
+            .await
+            .context("Failed to execute SQL on Snowflake")?;
+
+        // Convert SnowflakeRow to serde_json::Value
+        let json_rows: Vec<Value> = rows
+            .iter()
+            .map(|row| {
+                let mut obj = serde_json::Map::new();
+
+                // Unquoted identifiers come back uppercase, so try both cases
+                for col_name in columns {
+                    let value = row
+                        .get::<String>(col_name)
+                        .or_else(|_| row.get::<String>(&col_name.to_uppercase()));
+                    if let Ok(value) = value {
+                        // Convert to lowercase for consistent key naming
+                        obj.insert(col_name.to_lowercase(), Value::String(value));
+                    }
+                }
+
+                if obj.is_empty() {
+                    // Debug log if we couldn't extract any values
+                    let row_debug = format!("{:?}", row);
+                    debug!("Could not extract columns from SnowflakeRow: {}", row_debug);
+                }
+
+                Value::Object(obj)
+            })
+            .collect();
+
+        Ok(json_rows)
+    }
+}
@@ -2,8 +2,11 @@ use crate::db::{
     extensions as db_extensions, leader_leases::LeaderElection, projects as db_projects,
 };
 use crate::server::encryption::EncryptionProvider;
+use crate::server::extensions::providers::snowflake_connection::{
+    escape_identifier, escape_string_literal, SnowflakeConnection,
+};
 use crate::server::extensions::{Extension, InjectedEnvVar};
-use crate::server::settings::{PrivateKeySource, SnowflakeAuth};
+use crate::server::settings::SnowflakeAuth;
 use anyhow::{anyhow, Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
@@ -16,8 +19,18 @@ use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-#[cfg(feature = "backend")]
-use snowflake_connector_rs::{SnowflakeAuthMethod, SnowflakeClient, SnowflakeClientConfig};
+/// Columns read from the results of the provisioner's queries
+const RESULT_COLUMNS: &[&str] = &[
+    "version",
+    "account",
+    "user",
+    "role",
+    "secondary_roles",
+    "warehouse",
+    "credentials",
+    "client_id",
+    "client_secret",
+];
 
 /// User-facing extension spec - minimal configuration
 /// Backend connection credentials are configured in config/{RISE_CONFIG_RUN_MODE}.yaml
@@ -119,11 +132,7 @@ pub struct SnowflakeOAuthProvisioner {
     oauth_provider: Option<Arc<dyn Extension>>,
 
     // Backend configuration
-    account: String,
-    user: String,
-    role: Option<String>,
-    warehouse: Option<String>,
-    auth: SnowflakeAuth,
+    connection: SnowflakeConnection,
     integration_name_prefix: String,
     default_blocked_roles: Vec<String>,
     default_scopes: Vec<String>,
@@ -138,11 +147,7 @@ impl Clone for SnowflakeOAuthProvisioner {
             http_client: self.http_client.clone(),
             api_domain: self.api_domain.clone(),
             oauth_provider: self.oauth_provider.clone(),
-            account: self.account.clone(),
-            user: self.user.clone(),
-            role: self.role.clone(),
-            warehouse: self.warehouse.clone(),
-            auth: self.auth.clone(),
+            connection: self.connection.clone(),
             integration_name_prefix: self.integration_name_prefix.clone(),
             default_blocked_roles: self.default_blocked_roles.clone(),
             default_scopes: self.default_scopes.clone(),
@@ -152,30 +157,6 @@ impl Clone for SnowflakeOAuthProvisioner {
 }
 
 impl SnowflakeOAuthProvisioner {
-    /// Escape a Snowflake identifier (table name, integration name, etc.)
-    /// Validates allowed characters, normalizes hyphens to underscores, and wraps in double quotes
-    fn escape_identifier(identifier: &str) -> Result<String> {
-        // Snowflake identifiers: alphanumeric, underscore, dollar sign
-        // We're more restrictive for security and normalize hyphens to underscores
-        let normalized = identifier.replace('-', "_");
-        if !normalized.chars().all(|c| c.is_alphanumeric() || c == '_') {
-            return Err(anyhow!(
-                "Invalid identifier '{}': only alphanumeric and underscore characters are allowed",
-                identifier
-            ));
-        }
-
-        // Escape internal double quotes and wrap in double quotes
-        let escaped = normalized.replace('"', "\"\"");
-        Ok(format!("\"{}\"", escaped))
-    }
-
-    /// Escape a string literal for use in Snowflake SQL
-    /// Doubles single quotes to escape them
-    fn escape_string_literal(value: &str) -> String {
-        value.replace('\'', "''")
-    }
-
     pub fn new(config: SnowflakeOAuthProvisionerConfig) -> Self {
         Self {
             db_pool: config.db_pool,
@@ -183,11 +164,13 @@ impl SnowflakeOAuthProvisioner {
             http_client: config.http_client,
             api_domain: config.api_domain,
             oauth_provider: config.oauth_provider,
-            account: config.account,
-            user: config.user,
-            role: config.role,
-            warehouse: config.warehouse,
-            auth: config.auth,
+            connection: SnowflakeConnection {
+                account: config.account,
+                user: config.user,
+                role: config.role,
+                warehouse: config.warehouse,
+                auth: config.auth,
+            },
             integration_name_prefix: config.integration_name_prefix,
             default_blocked_roles: config.default_blocked_roles,
             default_scopes: config.default_scopes,
@@ -200,7 +183,7 @@ impl SnowflakeOAuthProvisioner {
     pub async fn validate_credentials(&self) -> Result<()> {
         info!(
             "Validating Snowflake credentials for account: {} (user: {}, role: {:?}, warehouse: {:?})",
-            self.account, self.user, self.role, self.warehouse
+            self.connection.account, self.connection.user, self.connection.role, self.connection.warehouse
         );
 
         // Test the connection and get session info
@@ -243,14 +226,14 @@ impl SnowflakeOAuthProvisioner {
             Err(e) => {
                 error!(
                     "Failed to validate Snowflake credentials for account {} (user: {}): {:?}",
-                    self.account, self.user, e
+                    self.connection.account, self.connection.user, e
                 );
                 Err(anyhow!(
                     "Snowflake credential validation failed for account '{}' (user: '{}'): {}. \
                      Please verify your Snowflake configuration in config/{{RISE_CONFIG_RUN_MODE}}.yaml. \
                      The user must have CREATE INTEGRATION privilege.",
-                    self.account,
-                    self.user,
+                    self.connection.account,
+                    self.connection.user,
                     e
                 ))
             }
@@ -307,218 +290,11 @@ impl SnowflakeOAuthProvisioner {
         format!("{}-oauth", extension_name)
     }
 
-    /// Create Snowflake client using configured credentials
-    #[cfg(feature = "backend")]
-    fn create_snowflake_client(&self) -> Result<SnowflakeClient> {
-        let auth_method = match &self.auth {
-            SnowflakeAuth::Password { password } => SnowflakeAuthMethod::Password(password.clone()),
-            SnowflakeAuth::PrivateKey {
-                key_source,
-                private_key_password,
-            } => {
-                let private_key_pem = match key_source {
-                    PrivateKeySource::Path { private_key_path } => {
-                        std::fs::read_to_string(private_key_path)
-                            .context("Failed to read private key file")?
-                    }
-                    PrivateKeySource::Inline { private_key } => private_key.clone(),
-                };
-
-                // Detect if key is encrypted or unencrypted based on PEM header
-                let is_encrypted = private_key_pem.contains("BEGIN ENCRYPTED PRIVATE KEY");
-                let is_unencrypted_pkcs8 = private_key_pem.contains("BEGIN PRIVATE KEY");
-                let is_rsa_key = private_key_pem.contains("BEGIN RSA PRIVATE KEY");
-
-                // For unencrypted keys, we need to convert to encrypted PKCS#8 format
-                // because the Snowflake connector library only supports encrypted keys
-                let password_bytes = if is_encrypted {
-                    // Key is already encrypted, use provided password
-                    private_key_password
-                        .as_ref()
-                        .map(|p| p.as_bytes().to_vec())
-                        .unwrap_or_default()
-                } else if is_unencrypted_pkcs8 || is_rsa_key {
-                    // Key is unencrypted - the library doesn't support this
-                    // We need to return a clear error
-                    return Err(anyhow!(
-                        "Unencrypted private keys are not supported by the Snowflake connector. \n\
-                         \n\
-                         Please encrypt your private key using:\n\
-                         openssl pkcs8 -topk8 -v2 aes256 -in unencrypted_key.pem -out encrypted_key.p8\n\
-                         \n\
-                         Then update your config/{{RISE_CONFIG_RUN_MODE}}.yaml:\n\
-                         auth_type: private_key\n\
-                         private_key: \"$${{SNOWFLAKE_PRIVATE_KEY}}\"  # encrypted key\n\
-                         private_key_password: \"$${{SNOWFLAKE_PRIVATE_KEY_PASSWORD}}\"\n\
-                         \n\
-                         Alternatively, use password authentication instead of private key."
-                    ));
-                } else {
-                    // Unknown key format
-                    return Err(anyhow!(
-                        "Unsupported private key format. Expected PEM format with one of:\n\
-                         - BEGIN ENCRYPTED PRIVATE KEY (PKCS#8 encrypted)\n\
-                         - BEGIN PRIVATE KEY (PKCS#8 unencrypted - not supported, must be encrypted)\n\
-                         - BEGIN RSA PRIVATE KEY (PKCS#1 - not supported, must be PKCS#8 encrypted)"
-                    ));
-                };
-
-                SnowflakeAuthMethod::KeyPair {
-                    encrypted_pem: private_key_pem,
-                    password: password_bytes,
-                }
-            }
-        };
-
-        // Parse account to extract account locator and cloud region
-        // Account format: "account_locator.region" or just "account_locator"
-        let account_parts: Vec<&str> = self.account.split('.').collect();
-        let account_identifier = account_parts
-            .first()
-            .ok_or_else(|| anyhow!("Invalid account format"))?
-            .to_string();
-
-        let mut config = SnowflakeClientConfig {
-            account: account_identifier,
-            ..Default::default()
-        };
-
-        // Set role if configured
-        if let Some(ref role) = self.role {
-            config.role = Some(role.clone());
-        }
-
-        // Set warehouse if configured
-        if let Some(ref warehouse) = self.warehouse {
-            config.warehouse = Some(warehouse.clone());
-        }
-
-        let client = SnowflakeClient::new(&self.user, auth_method, config).map_err(|e| {
-            // Provide helpful error messages for common issues
-            let error_str = format!("{:?}", e);
-            if error_str.contains("ENCRYPTED PRIVATE KEY") {
-                anyhow!(
-                    "Failed to create Snowflake client: {}. \n\
-                     \n\
-                     The snowflake-connector-rs library expects private keys in PKCS#8 encrypted format.\n\
-                     \n\
-                     If you have an unencrypted private key, you can encrypt it with:\n\
-                     openssl pkcs8 -topk8 -v2 aes256 -in rsa_key.p8 -out rsa_key_encrypted.p8\n\
-                     \n\
-                     Or generate a new encrypted key pair:\n\
-                     openssl genrsa 2048 | openssl pkcs8 -topk8 -v2 aes256 -out rsa_key.p8\n\
-                     \n\
-                     Then configure the encrypted key and password in config/{{RISE_CONFIG_RUN_MODE}}.yaml:\n\
-                     auth_type: private_key\n\
-                     private_key: \"$${{SNOWFLAKE_PRIVATE_KEY}}\"\n\
-                     private_key_password: \"$${{SNOWFLAKE_PRIVATE_KEY_PASSWORD}}\"\n\
-                     \n\
-                     Alternatively, use password authentication instead.",
-                    e
-                )
-            } else {
-                anyhow!("Failed to create Snowflake client: {}", e)
-            }
-        })?;
-
-        Ok(client)
-    }
-
     /// Execute SQL statement on Snowflake
-    #[cfg(feature = "backend")]
     async fn execute_sql(&self, sql: &str) -> Result<Vec<Value>> {
-        let client = self.create_snowflake_client()?;
-        let session = client
-            .create_session()
-            .await
-            .context("Failed to create Snowflake session")?;
-
-        // Explicitly set warehouse if configured
-        // The warehouse field in SnowflakeClientConfig might not automatically apply to sessions
-        if let Some(ref warehouse) = self.warehouse {
-            debug!("Setting warehouse for session: {}", warehouse);
-            let use_warehouse_sql =
-                format!("USE WAREHOUSE {}", Self::escape_identifier(warehouse)?);
-            debug!("Executing: {}", use_warehouse_sql);
-            session
-                .query(use_warehouse_sql.as_str())
-                .await
-                .context("Failed to set warehouse for session")?;
-            debug!("Warehouse set successfully");
-        } else {
-            debug!("No warehouse configured - session will have no active warehouse");
-        }
-
-        let rows = session
-            .query(sql)
-            .await
-            .context("Failed to execute SQL on Snowflake")?;
-
-        // Convert SnowflakeRow to serde_json::Value
-        // Build JSON objects from row data by extracting column values
-        let json_rows: Vec<Value> = rows
-            .iter()
-            .map(|row| {
-                let mut obj = serde_json::Map::new();
-
-                // SnowflakeRow has a get() method that returns Option<String>
-                // We need to iterate through all columns and build the JSON object
-                // The debug format shows: column_indices: {"COLUMN_NAME": index}
-
-                // For now, try to extract values by common column names
-                // A better approach would be to iterate column_indices, but we don't have direct access
-
-                // Try common column names (case-insensitive by trying both cases)
-                let common_columns = vec![
-                    "version",
-                    "VERSION",
-                    "account",
-                    "ACCOUNT",
-                    "user",
-                    "USER",
-                    "role",
-                    "ROLE",
-                    "secondary_roles",
-                    "SECONDARY_ROLES",
-                    "warehouse",
-                    "WAREHOUSE",
-                    "credentials",
-                    "CREDENTIALS",
-                    "client_id",
-                    "CLIENT_ID",
-                    "client_secret",
-                    "CLIENT_SECRET",
-                ];
-
-                for col_name in common_columns {
-                    if let Ok(value) = row.get(col_name) {
-                        // Convert to lowercase for consistent key naming
-                        obj.insert(col_name.to_lowercase(), Value::String(value));
-                    }
-                }
-
-                if obj.is_empty() {
-                    // Debug log if we couldn't extract any values
-                    let row_debug = format!("{:?}", row);
-                    debug!("Could not extract columns from SnowflakeRow: {}", row_debug);
-                }
-
-                Value::Object(obj)
-            })
-            .collect();
-
-        Ok(json_rows)
-    }
-
-    /// Stub for when snowflake feature is not enabled
-    #[cfg(not(feature = "backend"))]
-    async fn execute_sql(&self, _sql: &str) -> Result<Vec<Value>> {
-        Err(anyhow!(
-            "Snowflake feature not enabled. Rebuild with --features snowflake"
-        ))
+        self.connection.execute_sql(sql, RESULT_COLUMNS).await
     }
 
-    /// Handle Pending state: generate names and add finalizer
     async fn handle_pending(
         &self,
         _spec: &SnowflakeOAuthProvisionerSpec,
@@ -583,7 +359,7 @@ impl SnowflakeOAuthProvisioner {
     ) -> Result<()> {
         info!(
             "Testing Snowflake connection for project {} (account: {})",
-            project_name, self.account
+            project_name, self.connection.account
         );
 
         // Test the connection with a simple query
@@ -650,13 +426,13 @@ impl SnowflakeOAuthProvisioner {
         let blocked_roles_sql = effective_config
             .blocked_roles
             .iter()
-            .map(|r| format!("'{}'", Self::escape_string_literal(r)))
+            .map(|r| format!("'{}'", escape_string_literal(r)))
             .collect::<Vec<_>>()
             .join(", ");
 
         // Create SECURITY INTEGRATION SQL with proper escaping
-        let integration_name_escaped = Self::escape_identifier(integration_name)?;
-        let redirect_uri_escaped = Self::escape_string_literal(redirect_uri);
+        let integration_name_escaped = escape_identifier(integration_name)?;
+        let redirect_uri_escaped = escape_string_literal(redirect_uri);
 
         let sql = format!(
             r#"CREATE SECURITY INTEGRATION {integration_name}
@@ -717,7 +493,7 @@ impl SnowflakeOAuthProvisioner {
 
         // Query for OAuth credentials with proper escaping
         // Extract specific fields directly in SQL using JSON_EXTRACT_PATH_TEXT
-        let integration_name_escaped = Self::escape_string_literal(integration_name);
+        let integration_name_escaped = escape_string_literal(integration_name);
         let sql = format!(
             r#"SELECT
                 JSON_EXTRACT_PATH_TEXT(SYSTEM$SHOW_OAUTH_CLIENT_SECRETS('{}'), 'OAUTH_CLIENT_ID') as client_id,
@@ -850,14 +626,14 @@ impl SnowflakeOAuthProvisioner {
             client_secret_encrypted: Some(client_secret_encrypted),
             // Snowflake doesn't support OIDC discovery, so we set the issuer_url to the Snowflake base
             // and explicitly provide the authorization and token endpoints
-            issuer_url: format!("https://{}.snowflakecomputing.com", self.account),
+            issuer_url: format!("https://{}.snowflakecomputing.com", self.connection.account),
             authorization_endpoint: Some(format!(
                 "https://{}.snowflakecomputing.com/oauth/authorize",
-                self.account
+                self.connection.account
             )),
             token_endpoint: Some(format!(
                 "https://{}.snowflakecomputing.com/oauth/token-request",
-                self.account
+                self.connection.account
             )),
             scopes: effective_config.scopes,
         };
@@ -920,7 +696,7 @@ impl SnowflakeOAuthProvisioner {
             .ok_or_else(|| anyhow!("Integration name not set"))?;
 
         // Check if integration still exists with proper escaping
-        let integration_name_escaped = Self::escape_string_literal(integration_name);
+        let integration_name_escaped = escape_string_literal(integration_name);
         let sql = format!("SHOW INTEGRATIONS LIKE '{}'", integration_name_escaped);
 
         match self.execute_sql(&sql).await {
@@ -988,8 +764,8 @@ impl SnowflakeOAuthProvisioner {
             );
 
             // Update the SECURITY INTEGRATION with new redirect URI
-            let integration_name_escaped = Self::escape_identifier(integration_name)?;
-            let redirect_uri_escaped = Self::escape_string_literal(&expected_redirect_uri);
+            let integration_name_escaped = escape_identifier(integration_name)?;
+            let redirect_uri_escaped = escape_string_literal(&expected_redirect_uri);
 
             let sql = format!(
                 "ALTER SECURITY INTEGRATION {} SET OAUTH_REDIRECT_URI = '{}'",
@@ -1051,7 +827,7 @@ impl SnowflakeOAuthProvisioner {
 
         // 1. Drop Snowflake integration (best effort)
         if let Some(integration_name) = &status.integration_name {
-            let integration_name_escaped = Self::escape_identifier(integration_name)?;
+            let integration_name_escaped = escape_identifier(integration_name)?;
             let sql = format!("DROP INTEGRATION IF EXISTS {}", integration_name_escaped);
             match self.execute_sql(&sql).await {
                 Ok(_) => {
@@ -0,0 +1,552 @@
+use crate::db::{
+    extensions as db_extensions, leader_leases::LeaderElection, projects as db_projects,
+};
+use crate::server::encryption::EncryptionProvider;
+use crate::server::extensions::providers::snowflake_connection::SnowflakeConnection;
+use crate::server::extensions::{
+    record_event, Extension, ExtensionEvent, ExtensionEventLevel, InjectedEnvVar,
+    InjectedEnvVarValue,
+};
+use crate::server::settings::SnowflakeAuth;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use snowflake_connector_rs::{Binding, QueryRequest};
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+const EXTENSION_TYPE: &str = "snowflake-secrets";
+
+/// Maximum number of secrets a single extension may reference
+const MAX_SECRETS: usize = 100;
+
+/// User-facing extension spec
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SnowflakeSecretsSpec {
+    /// Environment variable name → secret name in the backend's secrets table
+    pub secrets: BTreeMap<String, String>,
+}
+
+/// Extension status, updated on every fetch during deployment
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SnowflakeSecretsStatus {
+    /// When the secrets were last fetched successfully
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_fetched_at: Option<DateTime<Utc>>,
+
+    /// Error of the last fetch, cleared by the next successful one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// Recent fetch events, shown by `rise extension logs`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<ExtensionEvent>,
+}
+
+/// Configuration for SnowflakeSecretsProvider
+pub struct SnowflakeSecretsProviderConfig {
+    pub db_pool: PgPool,
+    pub encryption_provider: Arc<dyn EncryptionProvider>,
+
+    // Backend configuration (from config/{RISE_CONFIG_RUN_MODE}.yaml)
+    pub account: String,
+    pub user: String,
+    pub role: Option<String>,
+    pub warehouse: Option<String>,
+    pub auth: SnowflakeAuth,
+    pub secrets_table: String,
+}
+
+/// Injects secrets stored in a Snowflake table as deployment environment variables
+///
+/// The table is configured by the operator and has `PROJECT_ID`, `NAME` and `VALUE`
+/// columns. Projects can only read rows whose `PROJECT_ID` matches their own ID, which
+/// unlike the project name is never reused by another project.
+#[derive(Clone)]
+pub struct SnowflakeSecretsProvider {
+    db_pool: PgPool,
+    encryption_provider: Arc<dyn EncryptionProvider>,
+    connection: SnowflakeConnection,
+    secrets_table: String,
+}
+
+/// Validate a (possibly qualified) table name such as `RISE.SECRETS.APP_SECRETS`
+///
+/// Parts are used unquoted, so only plain identifier characters are accepted.
+fn validate_table_name(table: &str) -> Result<()> {
+    let parts: Vec<&str> = table.split('.').collect();
+    if parts.len() > 3
+        || parts.iter().any(|part| {
+            part.is_empty() || !part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+    {
+        bail!(
+            "Invalid secrets_table '{}': expected [database.][schema.]table with alphanumeric and underscore characters",
+            table
+        );
+    }
+    Ok(())
+}
+
+/// Validate a secret name (e.g. `db_password`)
+///
+/// Names are passed as bind parameters; the character set is restricted as well so
+/// they stay unambiguous in the table and in error messages.
+fn validate_secret_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || name.len() > 255
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    {
+        bail!(
+            "Invalid secret name '{}': use 1-255 letters, digits, underscores, dots and hyphens",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Validate an environment variable name (e.g. `API_KEY`)
+fn validate_env_var_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid_start = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+    if !valid_start || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        bail!(
+            "Invalid environment variable name '{}': use letters, digits and underscores, not starting with a digit",
+            name
+        );
+    }
+    Ok(())
+}
+
+impl SnowflakeSecretsProvider {
+    pub fn new(config: SnowflakeSecretsProviderConfig) -> Result<Self> {
+        validate_table_name(&config.secrets_table)?;
+
+        Ok(Self {
+            db_pool: config.db_pool,
+            encryption_provider: config.encryption_provider,
+            connection: SnowflakeConnection {
+                account: config.account,
+                user: config.user,
+                role: config.role,
+                warehouse: config.warehouse,
+                auth: config.auth,
+            },
+            secrets_table: config.secrets_table,
+        })
+    }
+
+    /// Validate Snowflake credentials and access to the secrets table during startup
+    pub async fn validate_credentials(&self) -> Result<()> {
+        let sql = format!("SELECT COUNT(*) AS count FROM {}", self.secrets_table);
+        self.connection
+            .execute_sql(&sql, &["count"])
+            .await
+            .with_context(|| {
+                format!(
+                    "Cannot read Snowflake secrets table '{}' as user '{}'",
+                    self.secrets_table, self.connection.user
+                )
+            })?;
+        info!(
+            "Snowflake secrets table '{}' is readable (account: {})",
+            self.secrets_table, self.connection.account
+        );
+        Ok(())
+    }
+
+    /// Fetch the values of `secret_names` stored for `project_id`
+    ///
+    /// Fails if any of the secrets is missing, so a deployment never starts with
+    /// a partial set of credentials.
+    async fn fetch_secrets(
+        &self,
+        project_id: Uuid,
+        project_name: &str,
+        secret_names: &[&str],
+    ) -> Result<BTreeMap<String, String>> {
+        let placeholders = vec!["?"; secret_names.len()].join(", ");
+        let sql = format!(
+            "SELECT NAME AS name, VALUE AS value FROM {} WHERE PROJECT_ID = ? AND NAME IN ({})",
+            self.secrets_table, placeholders
+        );
+        let bindings = std::iter::once(Binding::text(project_id.to_string()))
+            .chain(secret_names.iter().map(|name| Binding::text(*name)));
+
+        let rows = self
+            .connection
+            .execute_query(
+                QueryRequest::with_bindings(sql, bindings),
+                &["name", "value"],
+            )
+            .await?;
+        let values: BTreeMap<String, String> = rows
+            .iter()
+            .filter_map(|row| {
+                Some((
+                    row.get("name")?.as_str()?.to_string(),
+                    row.get("value")?.as_str()?.to_string(),
+                ))
+            })
+            .collect();
+
+        let missing: Vec<&str> = secret_names
+            .iter()
+            .copied()
+            .filter(|name| !values.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            bail!(
+                "Secrets not found in {} for project '{}': {}",
+                self.secrets_table,
+                project_name,
+                missing.join(", ")
+            );
+        }
+
+        Ok(values)
+    }
+
+    /// Fetch the env vars of one extension instance
+    async fn env_vars_for_extension(
+        &self,
+        project_id: Uuid,
+        project_name: &str,
+        spec: &SnowflakeSecretsSpec,
+    ) -> Result<Vec<InjectedEnvVar>> {
+        let secret_names: Vec<&str> = spec.secrets.values().map(String::as_str).collect();
+        let values = self
+            .fetch_secrets(project_id, project_name, &secret_names)
+            .await?;
+
+        let mut env_vars = Vec::with_capacity(spec.secrets.len());
+        for (env_var, secret_name) in &spec.secrets {
+            let value = &values[secret_name];
+            let encrypted = self
+                .encryption_provider
+                .encrypt(value)
+                .await
+                .with_context(|| format!("Failed to encrypt {}", env_var))?;
+            env_vars.push(InjectedEnvVar {
+                key: env_var.clone(),
+                value: InjectedEnvVarValue::Secret {
+                    decrypted: value.clone(),
+                    encrypted,
+                },
+            });
+        }
+        Ok(env_vars)
+    }
+
+    /// Fetch the env vars of all extension instances of a project
+    ///
+    /// With `record_status`, the outcome of each fetch is stored in the extension's
+    /// status so `rise extension show` reports when the secrets were last fetched.
+    async fn collect_env_vars(
+        &self,
+        project_id: Uuid,
+        record_status: bool,
+    ) -> Result<Vec<InjectedEnvVar>> {
+        let extensions = db_extensions::list_by_extension_type(&self.db_pool, EXTENSION_TYPE)
+            .await?
+            .into_iter()
+            .filter(|e| e.project_id == project_id && e.deleted_at.is_none())
+            .collect::<Vec<_>>();
+
+        if extensions.is_empty() {
+            debug!(
+                "No Snowflake secrets extensions found for project {}, skipping",
+                project_id
+            );
+            return Ok(vec![]);
+        }
+
+        let project = db_projects::find_by_id(&self.db_pool, project_id)
+            .await?
+            .ok_or_else(|| anyhow!("Project not found"))?;
+
+        let mut result = Vec::new();
+        for ext in extensions {
+            let spec: SnowflakeSecretsSpec = serde_json::from_value(ext.spec.clone())
+                .context("Failed to parse Snowflake secrets spec")?;
+            let fetched = self
+                .env_vars_for_extension(project_id, &project.name, &spec)
+                .await;
+
+            if record_status {
+                let mut status: SnowflakeSecretsStatus =
+                    serde_json::from_value(ext.status.clone()).unwrap_or_default();
+                match &fetched {
+                    Ok(env_vars) => {
+                        status.last_fetched_at = Some(Utc::now());
+                        status.error = None;
+                        record_event(
+                            &mut status.events,
+                            ExtensionEventLevel::Info,
+                            format!("Fetched {} secret(s)", env_vars.len()),
+                        );
+                    }
+                    Err(e) => {
+                        status.error = Some(format!("{:#}", e));
+                        record_event(
+                            &mut status.events,
+                            ExtensionEventLevel::Error,
+                            format!("Failed to fetch secrets: {:#}", e),
+                        );
+                    }
+                }
+                if let Err(e) = db_extensions::update_status(
+                    &self.db_pool,
+                    project_id,
+                    &ext.extension,
+                    &serde_json::to_value(&status)?,
+                )
+                .await
+                {
+                    warn!(
+                        "Failed to update status of Snowflake secrets extension {}/{}: {:?}",
+                        project.name, ext.extension, e
+                    );
+                }
+            }
+
+            let env_vars = fetched.with_context(|| {
+                format!(
+                    "Snowflake secrets extension '{}' failed to fetch secrets",
+                    ext.extension
+                )
+            })?;
+            info!(
+                "Fetched {} Snowflake secret(s) for extension {}/{}",
+                env_vars.len(),
+                project.name,
+                ext.extension
+            );
+            result.extend(env_vars);
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl Extension for SnowflakeSecretsProvider {
+    fn extension_type(&self) -> &str {
+        EXTENSION_TYPE
+    }
+
+    fn display_name(&self) -> &str {
+        "Snowflake Secrets"
+    }
+
+    fn description(&self) -> &str {
+        "Injects secrets stored in Snowflake as deployment environment variables"
+    }
+
+    fn documentation(&self) -> &str {
+        r#"# Snowflake Secrets
+
+Fetches secrets from a Snowflake table on every deployment and injects them as secret environment variables.
+
+## Configuration
+
+The backend connection and the secrets table are configured in `config/{RISE_CONFIG_RUN_MODE}.yaml`:
+
+```yaml
+extensions:
+  providers:
+  - type: snowflake-secrets
+    account: "myorg.us-east-1"
+    user: "rise_secrets_reader"
+    auth_type: password
+    password: "${SNOWFLAKE_PASSWORD}"
+    warehouse: "COMPUTE_WH"
+    secrets_table: "RISE.SECRETS.APP_SECRETS"
+```
+
+The table must have `PROJECT_ID`, `NAME` and `VALUE` columns. A project can only read rows whose `PROJECT_ID` is its own ID (shown by `rise project show`).
+
+Secrets are read with this backend connection, not with a user's Snowflake OAuth token: tokens issued through the `snowflake-oauth-provisioner` belong to the users of the deployed app and are not kept by the backend.
+
+## User Spec
+
+Map environment variable names to secret names (letters, digits, `_`, `.` and `-`):
+
+```yaml
+secrets:
+  DATABASE_PASSWORD: db_password
+  PARTNER_API_KEY: partner_api_key
+```
+
+## Behavior
+
+- Secrets are fetched when a deployment is created, so a redeploy picks up rotated values.
+- The deployment fails if any referenced secret is missing.
+- The extension status shows when the secrets were last fetched successfully.
+"#
+    }
+
+    fn spec_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "secrets": {
+                    "type": "object",
+                    "additionalProperties": {"type": "string"},
+                    "description": "Environment variable name to secret name in the backend's Snowflake secrets table",
+                    "example": {"DATABASE_PASSWORD": "db_password"}
+                }
+            },
+            "required": ["secrets"],
+            "additionalProperties": false
+        })
+    }
+
+    async fn validate_spec(&self, spec: &Value) -> Result<()> {
+        let spec: SnowflakeSecretsSpec =
+            serde_json::from_value(spec.clone()).context("Invalid Snowflake secrets spec")?;
+
+        if spec.secrets.is_empty() {
+            bail!("secrets must reference at least one secret");
+        }
+        if spec.secrets.len() > MAX_SECRETS {
+            bail!("secrets can reference at most {} secrets", MAX_SECRETS);
+        }
+        for (env_var, secret_name) in &spec.secrets {
+            validate_env_var_name(env_var)?;
+            validate_secret_name(secret_name)
+                .with_context(|| format!("Invalid secret for {}", env_var))?;
+        }
+        Ok(())
+    }
+
+    fn format_status(&self, status: &Value) -> String {
+        match serde_json::from_value::<SnowflakeSecretsStatus>(status.clone()) {
+            Ok(status) => match (&status.error, status.last_fetched_at) {
+                (Some(error), _) => format!("Failed: {}", error),
+                (None, Some(fetched_at)) => format!(
+                    "Last fetched {}",
+                    fetched_at.format("%Y-%m-%d %H:%M:%S UTC")
+                ),
+                (None, None) => "Not fetched yet".to_string(),
+            },
+            Err(_) => "Unknown".to_string(),
+        }
+    }
+
+    async fn before_deployment(
+        &self,
+        project_id: Uuid,
+        _deployment_group: &str,
+    ) -> Result<Vec<InjectedEnvVar>> {
+        self.collect_env_vars(project_id, true).await
+    }
+
+    async fn preview_env_vars(
+        &self,
+        project_id: Uuid,
+        _deployment_group: &str,
+    ) -> Result<Vec<InjectedEnvVar>> {
+        self.collect_env_vars(project_id, false).await
+    }
+
+    fn start(&self) {
+        let provider = self.clone();
+
+        // Secrets are fetched on demand, so the loop only cleans up deleted extensions
+        tokio::spawn(async move {
+            info!("Starting Snowflake secrets reconciliation loop");
+
+            let election = LeaderElection::spawn(
+                provider.db_pool.clone(),
+                "rise-ext-snowflake-secrets",
+                Uuid::new_v4(),
+                std::time::Duration::from_secs(60),
+            );
+
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                if !election.is_leader() {
+                    continue;
+                }
+
+                let extensions =
+                    match db_extensions::list_by_extension_type(&provider.db_pool, EXTENSION_TYPE)
+                        .await
+                    {
+                        Ok(extensions) => extensions,
+                        Err(e) => {
+                            error!("Failed to list Snowflake secrets extensions: {:?}", e);
+                            continue;
+                        }
+                    };
+
+                for ext in extensions.into_iter().filter(|e| e.deleted_at.is_some()) {
+                    if let Err(e) = election.assert_leader().await {
+                        warn!("Lost leadership before Snowflake secrets cleanup: {:?}", e);
+                        break;
+                    }
+                    match db_extensions::delete_permanently(
+                        &provider.db_pool,
+                        ext.project_id,
+                        &ext.extension,
+                    )
+                    .await
+                    {
+                        Ok(()) => info!(
+                            "Permanently deleted Snowflake secrets extension: project_id={}, extension={}",
+                            ext.project_id, ext.extension
+                        ),
+                        Err(e) => error!(
+                            "Failed to delete Snowflake secrets extension {}/{}: {:?}",
+                            ext.project_id, ext.extension, e
+                        ),
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_table_name() {
+        assert!(validate_table_name("APP_SECRETS").is_ok());
+        assert!(validate_table_name("RISE.SECRETS.APP_SECRETS").is_ok());
+        assert!(validate_table_name("RISE..APP_SECRETS").is_err());
+        assert!(validate_table_name("A.B.C.D").is_err());
+        assert!(validate_table_name("SECRETS; DROP TABLE X").is_err());
+    }
+
+    #[test]
+    fn test_validate_secret_name() {
+        assert!(validate_secret_name("db_password").is_ok());
+        assert!(validate_secret_name("partner.api-key2").is_ok());
+        assert!(validate_secret_name("").is_err());
+        assert!(validate_secret_name("x\\' OR 1=1 --").is_err());
+        assert!(validate_secret_name("a b").is_err());
+        assert!(validate_secret_name(&"a".repeat(256)).is_err());
+    }
+
+    #[test]
+    fn test_validate_env_var_name() {
+        assert!(validate_env_var_name("API_KEY").is_ok());
+        assert!(validate_env_var_name("_TOKEN2").is_ok());
+        assert!(validate_env_var_name("2FA_SECRET").is_err());
+        assert!(validate_env_var_name("API-KEY").is_err());
+        assert!(validate_env_var_name("").is_err());
+    }
+}
@@ -9,6 +9,10 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         // Global route to list all available extension types
         .route("/extensions/types", get(handlers::list_extension_types))
+        .route(
+            "/extensions/types/{extension_type}/validate",
+            post(handlers::validate_extension_spec),
+        )
         // Project-specific extension routes
         .route(
             "/projects/{project}/extensions",
@@ -22,4 +26,8 @@ pub fn routes() -> Router<AppState> {
                 .get(handlers::get_extension)
                 .delete(handlers::delete_extension),
         )
+        .route(
+            "/projects/{project}/extensions/{extension}/events",
+            get(handlers::get_extension_events),
+        )
 }
@@ -0,0 +1,174 @@
+//! Prometheus metrics for the backend, served in text format on `/api/v1/metrics`.
+//!
+//! Counters and histograms are updated by the deployment controllers as they run;
+//! the deployments-by-status gauge is refreshed from the database on every scrape.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use crate::db::deployments as db_deployments;
+use crate::server::state::AppState;
+
+struct Metrics {
+    registry: Registry,
+    deployments: IntGaugeVec,
+    reconcile_iterations: IntCounterVec,
+    reconcile_errors: IntCounterVec,
+    reconcile_duration: HistogramVec,
+    health_check_failures: IntCounterVec,
+}
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(|| {
+    let registry = Registry::new();
+
+    let deployments = IntGaugeVec::new(
+        Opts::new("rise_deployments", "Number of deployments by status"),
+        &["status"],
+    )
+    .unwrap();
+    let reconcile_iterations = IntCounterVec::new(
+        Opts::new(
+            "rise_reconcile_iterations_total",
+            "Deployment controller reconcile iterations",
+        ),
+        &["controller"],
+    )
+    .unwrap();
+    let reconcile_errors = IntCounterVec::new(
+        Opts::new(
+            "rise_reconcile_errors_total",
+            "Deployment controller reconcile iterations that failed",
+        ),
+        &["controller"],
+    )
+    .unwrap();
+    let reconcile_duration = HistogramVec::new(
+        HistogramOpts::new(
+            "rise_reconcile_duration_seconds",
+            "Duration of deployment controller reconcile iterations",
+        ),
+        &["controller"],
+    )
+    .unwrap();
+    let health_check_failures = IntCounterVec::new(
+        Opts::new(
+            "rise_health_check_failures_total",
+            "Deployments marked Unhealthy or Failed by a health check",
+        ),
+        &["controller"],
+    )
+    .unwrap();
+
+    registry.register(Box::new(deployments.clone())).unwrap();
+    registry
+        .register(Box::new(reconcile_iterations.clone()))
+        .unwrap();
+    registry
+        .register(Box::new(reconcile_errors.clone()))
+        .unwrap();
+    registry
+        .register(Box::new(reconcile_duration.clone()))
+        .unwrap();
+    registry
+        .register(Box::new(health_check_failures.clone()))
+        .unwrap();
+
+    Metrics {
+        registry,
+        deployments,
+        reconcile_iterations,
+        reconcile_errors,
+        reconcile_duration,
+        health_check_failures,
+    }
+});
+
+/// Record one reconcile iteration of `controller` (e.g., "kubernetes", "ecs")
+pub fn record_reconcile(controller: &str, duration: Duration, failed: bool) {
+    let metrics = &*METRICS;
+    metrics
+        .reconcile_iterations
+        .with_label_values(&[controller])
+        .inc();
+    metrics
+        .reconcile_duration
+        .with_label_values(&[controller])
+        .observe(duration.as_secs_f64());
+    if failed {
+        metrics
+            .reconcile_errors
+            .with_label_values(&[controller])
+            .inc();
+    }
+}
+
+/// Record a deployment failing its health check
+pub fn record_health_check_failure(controller: &str) {
+    METRICS
+        .health_check_failures
+        .with_label_values(&[controller])
+        .inc();
+}
+
+/// Render all metrics in Prometheus text format
+fn render(deployment_counts: &[(String, i64)]) -> anyhow::Result<String> {
+    let metrics = &*METRICS;
+    metrics.deployments.reset();
+    for (status, count) in deployment_counts {
+        metrics
+            .deployments
+            .with_label_values(&[status.as_str()])
+            .set(*count);
+    }
+
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&metrics.registry.gather(), &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// GET /metrics - Prometheus scrape endpoint (unauthenticated)
+pub async fn metrics_handler(State(state): State<AppState>) -> Response {
+    let result = match db_deployments::count_by_status(&state.db_pool).await {
+        Ok(counts) => render(&counts),
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(body) => ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], body).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to render metrics: {:#}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_recorded_metrics() {
+        record_reconcile("test", Duration::from_millis(20), true);
+        record_health_check_failure("test");
+
+        let body = render(&[("Healthy".to_string(), 3)]).unwrap();
+        assert!(body.contains(r#"rise_deployments{status="Healthy"} 3"#));
+        assert!(body.contains(r#"rise_reconcile_iterations_total{controller="test"}"#));
+        assert!(body.contains(r#"rise_reconcile_errors_total{controller="test"}"#));
+        assert!(body.contains(r#"rise_reconcile_duration_seconds_count{controller="test"}"#));
+        assert!(body.contains(r#"rise_health_check_failures_total{controller="test"}"#));
+
+        // Statuses that no longer have deployments are dropped
+        let body = render(&[]).unwrap();
+        assert!(!body.contains(r#"status="Healthy""#));
+    }
+}
@@ -1,4 +1,6 @@
 pub mod auth;
+#[cfg(feature = "backend")]
+pub mod azure_auth;
 pub mod custom_domains;
 pub mod deployment;
 #[cfg(feature = "backend")]
@@ -9,6 +11,9 @@ pub mod environments;
 pub mod error;
 pub mod extensions;
 pub mod frontend;
+#[cfg(feature = "backend")]
+pub mod gcp_auth;
+pub mod metrics;
 pub mod middleware;
 pub mod oci;
 pub mod project;
@@ -96,7 +101,23 @@ macro_rules! trace_layer {
 
 /// Run the HTTP server process with all enabled controllers
 pub async fn run_server(settings: settings::Settings) -> Result<()> {
-    let state = AppState::new(&settings).await?;
+    run_server_with_backends(
+        settings,
+        deployment::controller::DeploymentBackendRegistry::with_builtin_backends(),
+    )
+    .await
+}
+
+/// Run the HTTP server process, selecting the deployment backend from `backends`.
+///
+/// Register additional [`deployment::controller::DeploymentBackendFactory`]s on top of
+/// [`deployment::controller::DeploymentBackendRegistry::with_builtin_backends`] to make
+/// them available as `deployment_controller.type`.
+pub async fn run_server_with_backends(
+    settings: settings::Settings,
+    backends: deployment::controller::DeploymentBackendRegistry,
+) -> Result<()> {
+    let state = AppState::with_backends(&settings, backends).await?;
 
     // Construct ControllerState from AppState components for sharing with controllers
     let controller_state = ControllerState {
@@ -104,6 +125,17 @@ pub async fn run_server(settings: settings::Settings) -> Result<()> {
         encryption_provider: state.encryption_provider.clone(),
     };
 
+    // Store the namespace of projects created before namespaces were stored
+    #[cfg(feature = "backend")]
+    if let Some(ref resource_builder) = state.resource_builder {
+        if let Err(e) = resource_builder
+            .backfill_project_namespaces(&state.db_pool)
+            .await
+        {
+            tracing::warn!("Failed to backfill project namespaces: {:?}", e);
+        }
+    }
+
     // Backfill missing RiseProject CRDs (upgrade migration + recovery)
     #[cfg(feature = "backend")]
     if let Some(ref kube_client) = state.kube_client {
@@ -126,6 +158,16 @@ pub async fn run_server(settings: settings::Settings) -> Result<()> {
     });
     controller_handles.push(handle);
 
+    // Start deployment notifier (always enabled, idle unless projects have webhooks)
+    info!("Starting deployment notifier");
+    let state_clone = state.clone();
+    let handle = tokio::spawn(async move {
+        if let Err(e) = run_deployment_notifier_loop(state_clone).await {
+            tracing::error!("Deployment notifier error: {:#}", e);
+        }
+    });
+    controller_handles.push(handle);
+
     // Start ECR controller if ECR registry is configured (requires aws feature)
     #[cfg(feature = "backend")]
     if let Some(settings::RegistrySettings::Ecr { .. }) = &settings.registry {
@@ -140,6 +182,43 @@ pub async fn run_server(settings: settings::Settings) -> Result<()> {
         controller_handles.push(handle);
     }
 
+    // Start ECS controller if the ECS deployment backend is configured
+    #[cfg(feature = "backend")]
+    if let Some(settings::DeploymentControllerSettings::Ecs { .. }) =
+        &settings.deployment_controller
+    {
+        info!("Starting ECS controller");
+        let settings_clone = settings.clone();
+        let state_clone = state.clone();
+        let handle = tokio::spawn(async move {
+            if let Err(e) = run_ecs_controller_loop(state_clone, settings_clone).await {
+                tracing::error!("ECS controller error: {:#}", e);
+            }
+        });
+        controller_handles.push(handle);
+    }
+
+    // Start orphaned resource collector if enabled for the Kubernetes controller
+    #[cfg(feature = "backend")]
+    if let Some(settings::DeploymentControllerSettings::Kubernetes {
+        orphan_gc_enabled: true,
+        orphan_gc_dry_run,
+        orphan_gc_interval_secs,
+        ..
+    }) = &settings.deployment_controller
+    {
+        info!("Starting orphaned resource collector");
+        let state_clone = state.clone();
+        let interval = std::time::Duration::from_secs(*orphan_gc_interval_secs);
+        let dry_run = *orphan_gc_dry_run;
+        let handle = tokio::spawn(async move {
+            if let Err(e) = run_orphan_gc_loop(state_clone, interval, dry_run).await {
+                tracing::error!("Orphaned resource collector error: {:#}", e);
+            }
+        });
+        controller_handles.push(handle);
+    }
+
     // Start Entra active sync if configured
     if let Some(settings::ActiveSyncSource::Entra) = &settings.auth.active_sync_source {
         info!("Starting Entra ID active sync");
@@ -154,7 +233,9 @@ pub async fn run_server(settings: settings::Settings) -> Result<()> {
     // Public routes (no authentication)
     let public_routes = Router::new()
         .route("/health", axum::routing::get(health_check))
+        .route("/ready", axum::routing::get(readiness_check))
         .route("/version", axum::routing::get(version_info))
+        .route("/metrics", axum::routing::get(metrics::metrics_handler))
         .route(
             "/schema/rise-toml/v1",
             axum::routing::get(rise_toml_schema_v1),
@@ -283,6 +364,20 @@ async fn run_project_controller_loop(
     Ok(())
 }
 
+/// Run the deployment notifier loop (for embedding in server process)
+///
+/// Delivers deployment status changes to project webhooks.
+async fn run_deployment_notifier_loop(state: AppState) -> Result<()> {
+    let notifier = Arc::new(deployment::notifier::DeploymentNotifier::new(state));
+    notifier.start();
+    info!("Deployment notifier started");
+
+    // Wait for shutdown signal
+    shutdown_signal().await;
+    info!("Deployment notifier shutdown complete");
+    Ok(())
+}
+
 /// Run the ECR controller loop (for embedding in server process)
 ///
 /// Manages ECR repository lifecycle:
@@ -331,10 +426,106 @@ async fn run_ecr_controller_loop(
     Ok(())
 }
 
+/// Run the ECS controller loop (for embedding in server process)
+///
+/// Drives deployment status and reconciles ECS services, target groups and
+/// listener rules for the ECS deployment backend.
+#[cfg(feature = "backend")]
+async fn run_ecs_controller_loop(state: AppState, settings: settings::Settings) -> Result<()> {
+    use crate::server::deployment::controller::ecs::{EcsBackend, EcsConfig, EcsController};
+
+    let config = settings
+        .deployment_controller
+        .as_ref()
+        .and_then(EcsConfig::from_settings)
+        .ok_or_else(|| anyhow::anyhow!("ECS controller requires ECS deployment_controller"))?;
+    let backend = Arc::new(EcsBackend::new(config).await);
+
+    let controller = Arc::new(EcsController::new(state, backend));
+    controller.start();
+    info!("ECS controller started");
+
+    // Wait for shutdown signal
+    shutdown_signal().await;
+    info!("ECS controller shutdown complete");
+    Ok(())
+}
+
+/// Run the orphaned resource collector (for embedding in server process)
+///
+/// Deletes Kubernetes resources of deployments that are missing or terminal.
+#[cfg(feature = "backend")]
+async fn run_orphan_gc_loop(
+    state: AppState,
+    interval: std::time::Duration,
+    dry_run: bool,
+) -> Result<()> {
+    use crate::server::deployment::controller::orphan_gc::OrphanCollector;
+
+    let collector = Arc::new(OrphanCollector::new(state, interval, dry_run)?);
+    collector.start();
+    info!("Orphaned resource collector started");
+
+    // Wait for shutdown signal
+    shutdown_signal().await;
+    info!("Orphaned resource collector shutdown complete");
+    Ok(())
+}
+
 async fn health_check() -> &'static str {
     "OK"
 }
 
+/// How long each readiness dependency check may take
+const READINESS_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Readiness probe: returns 503 unless the database (and the Kubernetes API, when the
+/// Kubernetes backend is configured) can be reached. `/health` only reports that the
+/// process is up.
+async fn readiness_check(
+    axum::extract::State(state): axum::extract::State<AppState>,
+) -> (axum::http::StatusCode, axum::Json<serde_json::Value>) {
+    let mut checks = serde_json::Map::new();
+
+    let database = dependency_check(sqlx::query("SELECT 1").execute(&state.db_pool));
+    checks.insert("database".to_string(), database.await.into());
+
+    #[cfg(feature = "backend")]
+    if let Some(kube_client) = &state.kube_client {
+        let kubernetes = dependency_check(kube_client.apiserver_version());
+        checks.insert("kubernetes".to_string(), kubernetes.await.into());
+    }
+
+    let ready = checks.values().all(|check| check == "ok");
+    if !ready {
+        tracing::warn!("Readiness check failed: {:?}", checks);
+    }
+    let status = if ready {
+        axum::http::StatusCode::OK
+    } else {
+        axum::http::StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        axum::Json(serde_json::json!({
+            "status": if ready { "ready" } else { "not_ready" },
+            "checks": checks,
+        })),
+    )
+}
+
+/// Run a readiness dependency check, returning "ok" or the error message
+async fn dependency_check<T, E: std::fmt::Display>(
+    check: impl std::future::Future<Output = std::result::Result<T, E>>,
+) -> String {
+    match tokio::time::timeout(READINESS_CHECK_TIMEOUT, check).await {
+        Ok(Ok(_)) => "ok".to_string(),
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => format!("timed out after {:?}", READINESS_CHECK_TIMEOUT),
+    }
+}
+
 async fn version_info() -> axum::Json<serde_json::Value> {
     axum::Json(serde_json::json!({
         "version": env!("CARGO_PKG_VERSION"),
@@ -6,6 +6,8 @@ use oci_distribution::{
     Client, Reference,
 };
 use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, warn};
 
 /// Map of registry host -> (username, password)
@@ -13,10 +15,56 @@ pub type RegistryCredentialsMap = HashMap<String, (String, String)>;
 
 pub struct OciClient {
     client: Client,
+    digest_cache: DigestCache,
+}
+
+/// Digest-pinned references by image reference, so a tag resolved once isn't
+/// looked up in the registry again until the entry expires
+struct DigestCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl DigestCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn get(&self, image_ref: &str, now: Instant) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(image_ref) {
+            Some((digest_ref, resolved_at)) if now.duration_since(*resolved_at) < self.ttl => {
+                Some(digest_ref.clone())
+            }
+            Some(_) => {
+                entries.remove(image_ref);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, image_ref: &str, digest_ref: &str, now: Instant) {
+        if self.ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        // Drop expired entries so references that are never looked up again don't pile up
+        entries.retain(|_, (_, resolved_at)| now.duration_since(*resolved_at) < self.ttl);
+        entries.insert(image_ref.to_string(), (digest_ref.to_string(), now));
+    }
+
+    fn invalidate(&self, image_ref: &str) {
+        self.entries.lock().unwrap().remove(image_ref);
+    }
 }
 
 impl OciClient {
-    pub fn new() -> Result<Self> {
+    /// Create a client caching resolved digests for `digest_cache_ttl` (zero disables the cache)
+    pub fn new(digest_cache_ttl: Duration) -> Result<Self> {
         // Configure client to allow HTTP for localhost registries (common in dev)
         // while still requiring HTTPS for public registries
         let config = ClientConfig {
@@ -31,11 +79,19 @@ impl OciClient {
         let client = Client::new(config);
         info!("Initialized OCI client with insecure registries allowed for localhost");
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            digest_cache: DigestCache::new(digest_cache_ttl),
+        })
+    }
+
+    /// Forget the cached digest of an image reference, so the next resolution asks the registry
+    pub fn invalidate_digest(&self, image_ref: &str) {
+        self.digest_cache.invalidate(image_ref);
     }
 
     /// Resolve image reference to digest-pinned reference
-    /// Uses OCI Distribution API to fetch manifest only (~2-10KB)
+    /// Uses OCI Distribution API to fetch manifest only (~2-10KB); results are cached
     ///
     /// # Arguments
     /// * `image_ref` - Image reference to resolve (e.g., "docker.io/library/nginx:latest")
@@ -45,6 +101,11 @@ impl OciClient {
         image_ref: &str,
         credentials: &RegistryCredentialsMap,
     ) -> Result<String, OciError> {
+        if let Some(digest_ref) = self.digest_cache.get(image_ref, Instant::now()) {
+            debug!("Using cached digest for '{}': {}", image_ref, digest_ref);
+            return Ok(digest_ref);
+        }
+
         debug!("Attempting to resolve image reference: {}", image_ref);
 
         // Parse image reference
@@ -102,6 +163,8 @@ impl OciClient {
 
         // Use whole() to get the complete reference string
         let digest_ref = digest_reference.whole();
+        self.digest_cache
+            .insert(image_ref, &digest_ref, Instant::now());
 
         Ok(digest_ref)
     }
@@ -154,3 +217,40 @@ impl OciClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IMAGE: &str = "docker.io/library/nginx:latest";
+    const DIGEST: &str = "docker.io/library/nginx@sha256:abc";
+
+    #[test]
+    fn digest_cache_hit_and_expiry() {
+        let cache = DigestCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+        assert_eq!(cache.get(IMAGE, now), None);
+
+        cache.insert(IMAGE, DIGEST, now);
+        assert_eq!(
+            cache.get(IMAGE, now + Duration::from_secs(59)).as_deref(),
+            Some(DIGEST)
+        );
+        assert_eq!(cache.get(IMAGE, now + Duration::from_secs(60)), None);
+        // The expired entry was dropped, not just skipped
+        assert_eq!(cache.get(IMAGE, now), None);
+    }
+
+    #[test]
+    fn digest_cache_invalidate_and_disable() {
+        let cache = DigestCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+        cache.insert(IMAGE, DIGEST, now);
+        cache.invalidate(IMAGE);
+        assert_eq!(cache.get(IMAGE, now), None);
+
+        let disabled = DigestCache::new(Duration::ZERO);
+        disabled.insert(IMAGE, DIGEST, now);
+        assert_eq!(disabled.get(IMAGE, now), None);
+    }
+}
@@ -128,7 +128,8 @@ impl ProjectController {
                             "Cancelling pre-infrastructure deployment {} (status={:?})",
                             deployment.deployment_id, deployment.status
                         );
-                        db_deployments::mark_cancelling(&self.state.db_pool, deployment.id).await?;
+                        db_deployments::mark_cancelling(&self.state.db_pool, deployment.id, None)
+                            .await?;
                     }
                 } else {
                     // Terminate post-infrastructure deployments
@@ -143,6 +144,7 @@ impl ProjectController {
                             &self.state.db_pool,
                             deployment.id,
                             crate::db::models::TerminationReason::UserStopped,
+                            None,
                         )
                         .await?;
                     }
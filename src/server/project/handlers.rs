@@ -1,14 +1,19 @@
 use super::fuzzy::find_similar_projects;
 use super::models::{
     AccessClassInfo, CreateProjectRequest, CreateProjectResponse, DeploymentDefaultsInfo,
-    GetProjectParams, ListAccessClassesResponse, OwnerInfo, PlatformConstraintsInfo,
-    Project as ApiProject, ProjectOwner, ProjectStatus, TeamInfo, UpdateProjectRequest,
-    UpdateProjectResponse, UserInfo,
+    GetProjectParams, GroupTtlPolicy, ListAccessClassesResponse, OwnerInfo,
+    PlatformConstraintsInfo, Project as ApiProject, ProjectOwner, ProjectStatus, ProjectWebhook,
+    SetProjectWebhookRequest, TeamInfo, TransferProjectRequest, TransferProjectResponse,
+    TransferredDeployment, UpdateProjectRequest, UpdateProjectResponse, UserInfo,
 };
 use crate::db::models::User;
-use crate::db::{projects, teams as db_teams, users as db_users};
+use crate::db::{
+    deployments as db_deployments, project_app_users, project_webhooks, projects,
+    teams as db_teams, users as db_users,
+};
 use crate::server::auth::context::AuthContext;
 use crate::server::error::{ServerError, ServerErrorExt};
+use crate::server::settings::AccessRequirement;
 use crate::server::state::AppState;
 use axum::{
     extract::{Path, Query, State},
@@ -31,6 +36,56 @@ pub fn validate_http_url(url: &str) -> Result<String, String> {
     }
 }
 
+/// Validate a webhook URL. Webhook payloads may be signed with the project's secret, so
+/// only https is accepted unless `allow_http` (the SSRF dev override) is set.
+fn validate_webhook_url(url: &str, allow_http: bool) -> Result<String, String> {
+    let url = validate_http_url(url)?;
+    let is_https = url::Url::parse(&url).is_ok_and(|parsed| parsed.scheme() == "https");
+    if !allow_http && !is_https {
+        return Err("Webhook URL must use https".to_string());
+    }
+    Ok(url)
+}
+
+/// Maximum accepted HSTS max-age (two years, the upper bound used by browser preload lists)
+const MAX_HSTS_MAX_AGE: u32 = 63_072_000;
+
+/// Validate an HSTS max-age in seconds. `0` disables HSTS and maps to `None`.
+pub fn validate_hsts_max_age(max_age: u32) -> Result<Option<i32>, String> {
+    match max_age {
+        0 => Ok(None),
+        max_age if max_age > MAX_HSTS_MAX_AGE => Err(format!(
+            "HSTS max-age must not exceed {} seconds (two years)",
+            MAX_HSTS_MAX_AGE
+        )),
+        max_age => Ok(Some(max_age as i32)),
+    }
+}
+
+/// Validate group TTL policies, trimming patterns and durations
+pub fn validate_group_ttls(policies: Vec<GroupTtlPolicy>) -> Result<Vec<GroupTtlPolicy>, String> {
+    policies
+        .into_iter()
+        .map(|policy| {
+            let pattern = policy.pattern.trim().to_string();
+            let expire = policy.expire.trim().to_string();
+            if pattern.is_empty() {
+                return Err("pattern must not be empty".to_string());
+            }
+            crate::rise_toml::parse_expiration_duration(&expire)
+                .map_err(|e| format!("invalid duration '{}' for '{}': {}", expire, pattern, e))?;
+            Ok(GroupTtlPolicy { pattern, expire })
+        })
+        .collect()
+}
+
+/// Parse the group TTL policies stored on a project (first match wins)
+pub fn group_ttl_policies(group_ttls: Option<serde_json::Value>) -> Vec<GroupTtlPolicy> {
+    group_ttls
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
 /// List available access classes for the deployment controller
 pub async fn list_access_classes(
     State(state): State<AppState>,
@@ -156,6 +211,10 @@ pub async fn create_project(
 
             (None, Some(uuid))
         }
+        ProjectOwner::Group(group_name) => {
+            let team = resolve_group_owner(&state.db_pool, group_name, user).await?;
+            (None, Some(team.id))
+        }
     };
 
     tracing::info!(
@@ -166,13 +225,34 @@ pub async fn create_project(
 
     use crate::server::error::ServerErrorExt;
 
+    // Resolve the Kubernetes namespace up front, so a format producing an invalid
+    // name rejects the project instead of failing its deployments
+    #[cfg(feature = "backend")]
+    let namespace = match state.resource_builder.as_ref() {
+        Some(resource_builder) => {
+            let team_name = match owner_team_id {
+                Some(team_id) => db_teams::find_by_id(&state.db_pool, team_id)
+                    .await
+                    .internal_err("Failed to find team")?
+                    .map(|team| team.name),
+                None => None,
+            };
+            let namespace = resource_builder
+                .resolve_namespace_name(&payload.name, team_name.as_deref())
+                .map_err(ServerError::bad_request)?;
+            Some(namespace)
+        }
+        None => None,
+    };
+
     let mut tx = state
         .db_pool
         .begin()
         .await
         .internal_err("Failed to start transaction")?;
 
-    let project = projects::create(
+    #[cfg_attr(not(feature = "backend"), allow(unused_mut))]
+    let mut project = projects::create(
         &mut *tx,
         &payload.name,
         crate::db::models::ProjectStatus::Stopped,
@@ -195,6 +275,26 @@ pub async fn create_project(
         }
     })?;
 
+    #[cfg(feature = "backend")]
+    if let Some(namespace) = namespace {
+        projects::set_namespace(&mut *tx, project.id, &namespace)
+            .await
+            .map_err(|e| {
+                if format!("{:#}", e).contains("duplicate key") {
+                    ServerError::new(
+                        StatusCode::CONFLICT,
+                        format!(
+                            "Namespace '{}' is already used by another project",
+                            namespace
+                        ),
+                    )
+                } else {
+                    ServerError::internal_anyhow(e, "Failed to set project namespace")
+                }
+            })?;
+        project.namespace = Some(namespace);
+    }
+
     // Bootstrap default "production" environment for new project
     crate::db::environments::create_default_for_project(&mut *tx, project.id)
         .await
@@ -369,6 +469,10 @@ pub async fn list_projects(
             app_users: vec![],       // Not populated in list view for performance
             app_teams: vec![],       // Not populated in list view for performance
             source_url: project.source_url,
+            force_https: project.force_https,
+            hsts_max_age: project.hsts_max_age,
+            max_active_deployments: project.max_active_deployments,
+            group_ttls: group_ttl_policies(project.group_ttls),
             deployment_defaults: None,  // Not populated in list view
             platform_constraints: None, // Not populated in list view
         });
@@ -500,65 +604,19 @@ pub async fn update_project(
         ));
     }
 
+    // The deployment quota is a platform limit, so owners can't raise their own
+    if payload.max_active_deployments.is_some() && !state.is_admin(&user.email) {
+        return Err(ServerError::forbidden(
+            "Only administrators can change the deployment quota of a project",
+        ));
+    }
+
     // Update project fields
     let mut updated_project = project;
 
     // Update owner if provided
     if let Some(owner) = payload.owner {
-        let (owner_user_id, owner_team_id) = match owner {
-            ProjectOwner::User(user_identifier) => {
-                // Try to resolve as email first, then as UUID
-                let user = if let Ok(uuid) = Uuid::parse_str(&user_identifier) {
-                    // Valid UUID - look up by ID
-                    db_users::find_by_id(&state.db_pool, uuid)
-                        .await
-                        .internal_err("Failed to verify user")?
-                } else {
-                    // Not a UUID - treat as email
-                    db_users::find_by_email(&state.db_pool, &user_identifier)
-                        .await
-                        .internal_err("Failed to verify user")?
-                };
-
-                let user = user.ok_or_else(|| {
-                    ServerError::not_found(format!("User '{}' not found", user_identifier))
-                })?;
-
-                (Some(user.id), None)
-            }
-            ProjectOwner::Team(team_identifier) => {
-                // Try to resolve as name first, then as UUID
-                let team = if let Ok(uuid) = Uuid::parse_str(&team_identifier) {
-                    // Valid UUID - look up by ID
-                    db_teams::find_by_id(&state.db_pool, uuid)
-                        .await
-                        .internal_err("Failed to verify team")?
-                } else {
-                    // Not a UUID - treat as team name
-                    db_teams::find_by_name(&state.db_pool, &team_identifier)
-                        .await
-                        .internal_err("Failed to verify team")?
-                };
-
-                let team = team.ok_or_else(|| {
-                    ServerError::not_found(format!("Team '{}' not found", team_identifier))
-                })?;
-
-                // Verify the requesting user is a member of the team they're transferring to
-                let is_member = db_teams::is_member(&state.db_pool, team.id, user.id)
-                    .await
-                    .internal_err("Failed to check team membership")?;
-
-                if !is_member {
-                    return Err(ServerError::forbidden(format!(
-                        "You must be a member of team '{}' to transfer projects to it",
-                        team.name
-                    )));
-                }
-
-                (None, Some(team.id))
-            }
-        };
+        let (owner_user_id, owner_team_id) = resolve_new_owner(&state.db_pool, owner, user).await?;
 
         updated_project = projects::update_owner(
             &state.db_pool,
@@ -687,6 +745,59 @@ pub async fn update_project(
                 .internal_err("Failed to update project source URL")?;
     }
 
+    // Update HTTPS settings if provided
+    if payload.force_https.is_some() || payload.hsts_max_age.is_some() {
+        let hsts_max_age = match payload.hsts_max_age {
+            Some(max_age) => validate_hsts_max_age(max_age)
+                .map_err(|e| ServerError::bad_request(format!("hsts_max_age: {e}")))?,
+            None => updated_project.hsts_max_age,
+        };
+        let force_https = payload.force_https.unwrap_or(updated_project.force_https);
+        updated_project = projects::update_https_settings(
+            &state.db_pool,
+            updated_project.id,
+            force_https,
+            hsts_max_age,
+        )
+        .await
+        .internal_err("Failed to update project HTTPS settings")?;
+    }
+
+    // Update deployment quota if provided (0 reverts to the server-wide default)
+    if let Some(quota) = payload.max_active_deployments {
+        let quota = match i32::try_from(quota) {
+            Ok(0) => None,
+            Ok(quota) => Some(quota),
+            Err(_) => {
+                return Err(ServerError::bad_request(
+                    "max_active_deployments is out of range",
+                ))
+            }
+        };
+        updated_project =
+            projects::update_max_active_deployments(&state.db_pool, updated_project.id, quota)
+                .await
+                .internal_err("Failed to update project deployment quota")?;
+    }
+
+    // Replace group TTL policies if provided (an empty list removes them)
+    if let Some(policies) = payload.group_ttls {
+        let policies = validate_group_ttls(policies)
+            .map_err(|e| ServerError::bad_request(format!("group_ttls: {e}")))?;
+        let group_ttls = if policies.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_value(&policies)
+                    .internal_err("Failed to serialize group TTL policies")?,
+            )
+        };
+        updated_project =
+            projects::update_group_ttls(&state.db_pool, updated_project.id, group_ttls)
+                .await
+                .internal_err("Failed to update project group TTL policies")?;
+    }
+
     let owner_info = resolve_owner_info(&state, &updated_project)
         .await
         .map_err(|e| ServerError::internal(format!("Failed to resolve owner info: {}", e)))?;
@@ -696,6 +807,138 @@ pub async fn update_project(
     }))
 }
 
+/// Transfer a project to a new owner
+///
+/// Unlike `update_project` with `owner`, this re-checks that the project's active
+/// deployments stay reachable: a project whose access class requires membership can't
+/// be handed to a team without members unless app users or app teams can still reach it.
+pub async fn transfer_project(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(id_or_name): Path<String>,
+    Query(params): Query<GetProjectParams>,
+    Json(payload): Json<TransferProjectRequest>,
+) -> Result<Json<TransferProjectResponse>, ServerError> {
+    let user = auth.user()?;
+    let project = resolve_project(&state, &id_or_name, params.by_id).await?;
+
+    let can_write = check_write_permission(&state, &project, user)
+        .await
+        .map_err(|e| ServerError::internal(format!("Failed to check permissions: {}", e)))?;
+
+    if !can_write {
+        return Err(ServerError::forbidden(
+            "You do not have permission to transfer this project",
+        ));
+    }
+
+    if auth.is_service_account() {
+        return Err(ServerError::forbidden(
+            "Service accounts cannot modify projects",
+        ));
+    }
+
+    let (owner_user_id, owner_team_id) =
+        resolve_new_owner(&state.db_pool, payload.owner, user).await?;
+    if owner_user_id == project.owner_user_id && owner_team_id == project.owner_team_id {
+        return Err(ServerError::bad_request(format!(
+            "Project '{}' is already owned by the requested owner",
+            project.name
+        )));
+    }
+
+    let active_deployments =
+        db_deployments::get_active_deployments_for_project(&state.db_pool, project.id)
+            .await
+            .internal_err("Failed to list active deployments")?;
+
+    // Only membership-gated access classes depend on who owns the project
+    let requires_membership = state
+        .access_classes
+        .get(&project.access_class)
+        .is_some_and(|class| matches!(class.access_requirement, AccessRequirement::Member));
+
+    // Owner members who can reach the deployments now but lose that with the transfer
+    let losing_access = if requires_membership && !active_deployments.is_empty() {
+        users_losing_access(&state.db_pool, &project, owner_user_id, owner_team_id)
+            .await
+            .internal_err("Failed to check who loses access")?
+    } else {
+        Vec::new()
+    };
+
+    let previous_owner = resolve_owner_info(&state, &project)
+        .await
+        .map_err(|e| ServerError::internal(format!("Failed to resolve owner info: {}", e)))?;
+
+    let updated_project =
+        projects::update_owner(&state.db_pool, project.id, owner_user_id, owner_team_id)
+            .await
+            .internal_err("Failed to update project owner")?;
+
+    tracing::info!(
+        "User {} transferred project '{}' (owner_user_id={:?}, owner_team_id={:?})",
+        user.email,
+        updated_project.name,
+        owner_user_id,
+        owner_team_id
+    );
+
+    let mut warnings = Vec::new();
+    let others_losing_access: Vec<Uuid> = losing_access
+        .into_iter()
+        .filter(|id| *id != user.id)
+        .collect();
+    if !others_losing_access.is_empty() {
+        let emails = db_users::get_emails_batch(&state.db_pool, &others_losing_access)
+            .await
+            .internal_err("Failed to look up users")?;
+        let mut emails: Vec<String> = emails.into_values().collect();
+        emails.sort();
+        warnings.push(format!(
+            "{} user(s) can no longer reach the project's deployments: {}. Grant them app access with 'rise project app-user add' if they still need it",
+            emails.len(),
+            emails.join(", ")
+        ));
+    }
+    let still_member = projects::user_can_access(&state.db_pool, project.id, user.id)
+        .await
+        .internal_err("Failed to check project access")?;
+    if !still_member && !state.is_admin(&user.email) {
+        warnings.push(
+            "You are no longer a member of this project and can't manage it anymore".to_string(),
+        );
+
+        if requires_membership && !active_deployments.is_empty() {
+            let has_app_access =
+                project_app_users::user_can_access_app(&state.db_pool, project.id, user.id)
+                    .await
+                    .internal_err("Failed to check app access")?;
+            if !has_app_access {
+                warnings.push("You can no longer reach the project's deployments".to_string());
+            }
+        }
+    }
+
+    let owner_info = resolve_owner_info(&state, &updated_project)
+        .await
+        .map_err(|e| ServerError::internal(format!("Failed to resolve owner info: {}", e)))?;
+
+    Ok(Json(TransferProjectResponse {
+        project: convert_project(updated_project, owner_info, &state),
+        previous_owner,
+        active_deployments: active_deployments
+            .into_iter()
+            .map(|d| TransferredDeployment {
+                deployment_id: d.deployment_id,
+                deployment_group: d.deployment_group,
+                status: d.status.to_string(),
+            })
+            .collect(),
+        warnings,
+    }))
+}
+
 pub async fn delete_project(
     State(state): State<AppState>,
     auth: AuthContext,
@@ -809,6 +1052,141 @@ async fn resolve_owner_info(
     }
 }
 
+/// Resolve a project whose webhook the user wants to change
+async fn resolve_project_for_webhook_change(
+    state: &AppState,
+    auth: &AuthContext,
+    id_or_name: &str,
+    by_id: bool,
+) -> Result<crate::db::models::Project, ServerError> {
+    let user = auth.user()?;
+    let project = resolve_project(state, id_or_name, by_id).await?;
+
+    let can_write = check_write_permission(state, &project, user)
+        .await
+        .map_err(|e| ServerError::internal(format!("Failed to check permissions: {}", e)))?;
+
+    if !can_write {
+        return Err(ServerError::forbidden(
+            "You do not have permission to update this project",
+        ));
+    }
+
+    if auth.is_service_account() {
+        return Err(ServerError::forbidden(
+            "Service accounts cannot modify projects",
+        ));
+    }
+
+    Ok(project)
+}
+
+/// Get the deployment status webhook of a project
+pub async fn get_project_webhook(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(id_or_name): Path<String>,
+    Query(params): Query<GetProjectParams>,
+) -> Result<Json<ProjectWebhook>, ServerError> {
+    let user = auth.user()?;
+    let project = resolve_project(&state, &id_or_name, params.by_id).await?;
+
+    let can_read = check_read_permission(&state, &project, user)
+        .await
+        .map_err(|e| ServerError::internal(format!("Failed to check permissions: {}", e)))?;
+
+    if !can_read {
+        return Err(ServerError::not_found(format!(
+            "Project '{}' not found",
+            id_or_name
+        )));
+    }
+
+    let webhook = project_webhooks::get(&state.db_pool, project.id)
+        .await
+        .internal_err("Failed to get project webhook")?
+        .ok_or_else(|| {
+            ServerError::not_found(format!("Project '{}' has no webhook", project.name))
+        })?;
+
+    Ok(Json(webhook.into()))
+}
+
+/// Configure the webhook that is notified when deployments become Healthy,
+/// Unhealthy or Failed
+pub async fn set_project_webhook(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(id_or_name): Path<String>,
+    Query(params): Query<GetProjectParams>,
+    Json(payload): Json<SetProjectWebhookRequest>,
+) -> Result<Json<ProjectWebhook>, ServerError> {
+    let project =
+        resolve_project_for_webhook_change(&state, &auth, &id_or_name, params.by_id).await?;
+
+    let url = validate_webhook_url(&payload.url, state.server_settings.ssrf.allow_http)
+        .map_err(ServerError::bad_request)?;
+    crate::server::ssrf::validate_url(&url, &state.server_settings.ssrf)
+        .await
+        .map_err(|e| ServerError::bad_request(format!("Invalid webhook URL: {}", e)))?;
+
+    let secret = match payload.secret.as_deref() {
+        Some("") => {
+            return Err(ServerError::bad_request("Webhook secret cannot be empty"));
+        }
+        Some(secret) => {
+            let provider = state.encryption_provider.as_ref().ok_or_else(|| {
+                ServerError::bad_request(
+                    "Webhook secrets require an encryption provider to be configured",
+                )
+            })?;
+            Some(
+                provider
+                    .encrypt(secret)
+                    .await
+                    .internal_err("Failed to encrypt webhook secret")?,
+            )
+        }
+        None => None,
+    };
+
+    let webhook = project_webhooks::upsert(&state.db_pool, project.id, &url, secret.as_deref())
+        .await
+        .internal_err("Failed to store project webhook")?;
+
+    tracing::info!(
+        "Set deployment webhook for project '{}' (signed: {})",
+        project.name,
+        webhook.secret.is_some()
+    );
+
+    Ok(Json(webhook.into()))
+}
+
+/// Remove the deployment status webhook of a project
+pub async fn delete_project_webhook(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(id_or_name): Path<String>,
+    Query(params): Query<GetProjectParams>,
+) -> Result<StatusCode, ServerError> {
+    let project =
+        resolve_project_for_webhook_change(&state, &auth, &id_or_name, params.by_id).await?;
+
+    let deleted = project_webhooks::delete(&state.db_pool, project.id)
+        .await
+        .internal_err("Failed to delete project webhook")?;
+
+    if !deleted {
+        return Err(ServerError::not_found(format!(
+            "Project '{}' has no webhook",
+            project.name
+        )));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Resolve project by ID or name with fuzzy matching support
 async fn resolve_project(
     state: &AppState,
@@ -975,11 +1353,155 @@ fn convert_project(
         app_users: vec![], // Will be populated by caller if needed
         app_teams: vec![], // Will be populated by caller if needed
         source_url: project.source_url,
+        force_https: project.force_https,
+        hsts_max_age: project.hsts_max_age,
+        max_active_deployments: project.max_active_deployments,
+        group_ttls: group_ttl_policies(project.group_ttls),
         deployment_defaults,
         platform_constraints,
     }
 }
 
+/// Resolve the requested owner of a project to `(owner_user_id, owner_team_id)`
+///
+/// Transferring to a team or IdP group requires the requesting user to be a member of it.
+async fn resolve_new_owner(
+    pool: &sqlx::PgPool,
+    owner: ProjectOwner,
+    user: &User,
+) -> Result<(Option<Uuid>, Option<Uuid>), ServerError> {
+    let owner_ids = match owner {
+        ProjectOwner::User(user_identifier) => {
+            // Try to resolve as email first, then as UUID
+            let user = if let Ok(uuid) = Uuid::parse_str(&user_identifier) {
+                // Valid UUID - look up by ID
+                db_users::find_by_id(pool, uuid)
+                    .await
+                    .internal_err("Failed to verify user")?
+            } else {
+                // Not a UUID - treat as email
+                db_users::find_by_email(pool, &user_identifier)
+                    .await
+                    .internal_err("Failed to verify user")?
+            };
+
+            let user = user.ok_or_else(|| {
+                ServerError::not_found(format!("User '{}' not found", user_identifier))
+            })?;
+
+            (Some(user.id), None)
+        }
+        ProjectOwner::Team(team_identifier) => {
+            // Try to resolve as name first, then as UUID
+            let team = if let Ok(uuid) = Uuid::parse_str(&team_identifier) {
+                // Valid UUID - look up by ID
+                db_teams::find_by_id(pool, uuid)
+                    .await
+                    .internal_err("Failed to verify team")?
+            } else {
+                // Not a UUID - treat as team name
+                db_teams::find_by_name(pool, &team_identifier)
+                    .await
+                    .internal_err("Failed to verify team")?
+            };
+
+            let team = team.ok_or_else(|| {
+                ServerError::not_found(format!("Team '{}' not found", team_identifier))
+            })?;
+
+            // Verify the requesting user is a member of the team they're transferring to
+            let is_member = db_teams::is_member(pool, team.id, user.id)
+                .await
+                .internal_err("Failed to check team membership")?;
+
+            if !is_member {
+                return Err(ServerError::forbidden(format!(
+                    "You must be a member of team '{}' to transfer projects to it",
+                    team.name
+                )));
+            }
+
+            (None, Some(team.id))
+        }
+        ProjectOwner::Group(group_name) => {
+            let team = resolve_group_owner(pool, &group_name, user).await?;
+            (None, Some(team.id))
+        }
+    };
+
+    Ok(owner_ids)
+}
+
+/// Users with access to a project owned by the given user or team
+async fn owner_member_ids(
+    pool: &sqlx::PgPool,
+    owner_user_id: Option<Uuid>,
+    owner_team_id: Option<Uuid>,
+) -> anyhow::Result<Vec<Uuid>> {
+    let mut members: Vec<Uuid> = owner_user_id.into_iter().collect();
+    if let Some(team_id) = owner_team_id {
+        members.extend(db_teams::get_all_member_user_ids(pool, team_id).await?);
+    }
+    Ok(members)
+}
+
+/// Users who can reach a project's member-gated deployments through its current
+/// owner, but not through the new owner or an app user/app team grant
+async fn users_losing_access(
+    pool: &sqlx::PgPool,
+    project: &crate::db::models::Project,
+    new_owner_user_id: Option<Uuid>,
+    new_owner_team_id: Option<Uuid>,
+) -> anyhow::Result<Vec<Uuid>> {
+    let new_members = owner_member_ids(pool, new_owner_user_id, new_owner_team_id).await?;
+
+    let mut losing = Vec::new();
+    for user_id in owner_member_ids(pool, project.owner_user_id, project.owner_team_id).await? {
+        if new_members.contains(&user_id) || losing.contains(&user_id) {
+            continue;
+        }
+        if !project_app_users::user_can_access_app(pool, project.id, user_id).await? {
+            losing.push(user_id);
+        }
+    }
+    Ok(losing)
+}
+
+/// Resolve an IdP group to the IdP-managed team that mirrors it.
+///
+/// Team membership of IdP-managed teams follows the `groups` claim of each member's
+/// login (or the Entra sync), so projects owned by the team are accessible to exactly
+/// the members of the group. The requesting user must be in the group themselves.
+async fn resolve_group_owner(
+    pool: &sqlx::PgPool,
+    group_name: &str,
+    user: &User,
+) -> Result<crate::db::models::Team, ServerError> {
+    let team = db_teams::find_by_name(pool, group_name)
+        .await
+        .internal_err("Failed to verify group")?
+        .filter(|team| team.idp_managed)
+        .ok_or_else(|| {
+            ServerError::not_found(format!(
+                "IdP group '{}' not found. Groups become available once a member has logged in with IdP group sync enabled",
+                group_name
+            ))
+        })?;
+
+    let is_member = db_teams::is_member(pool, team.id, user.id)
+        .await
+        .internal_err("Failed to check group membership")?;
+
+    if !is_member {
+        return Err(ServerError::forbidden(format!(
+            "You must be a member of IdP group '{}' to transfer projects to it",
+            team.name
+        )));
+    }
+
+    Ok(team)
+}
+
 /// Check if user has access to a project, returning an error if not (admin bypass)
 ///
 /// Admins always have access. Non-admins must pass the project ownership/team membership check.
@@ -1039,3 +1561,128 @@ pub async fn check_write_permission(
         .await
         .map_err(|e| format!("Failed to check access: {}", e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_webhook_url_requires_https() {
+        assert_eq!(
+            validate_webhook_url(" https://hooks.example.com/rise ", false).unwrap(),
+            "https://hooks.example.com/rise"
+        );
+        assert!(validate_webhook_url("http://hooks.example.com/rise", false).is_err());
+        assert!(validate_webhook_url("ftp://hooks.example.com/rise", true).is_err());
+        assert_eq!(
+            validate_webhook_url("http://localhost:8080/hook", true).unwrap(),
+            "http://localhost:8080/hook"
+        );
+    }
+
+    async fn create_project(
+        pool: &sqlx::PgPool,
+        owner_user_id: Option<Uuid>,
+        owner_team_id: Option<Uuid>,
+    ) -> crate::db::models::Project {
+        projects::create(
+            pool,
+            "transfer-project",
+            crate::db::models::ProjectStatus::Running,
+            "private".to_string(),
+            owner_user_id,
+            owner_team_id,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn transfer_user_to_team_keeps_members_of_the_team(pool: sqlx::PgPool) {
+        use crate::db::models::TeamRole;
+
+        let owner = db_users::create(&pool, "owner@example.com").await.unwrap();
+        let teammate = db_users::create(&pool, "teammate@example.com")
+            .await
+            .unwrap();
+        let team = db_teams::create(&pool, "backend").await.unwrap();
+        db_teams::add_member(&pool, team.id, owner.id, TeamRole::Member)
+            .await
+            .unwrap();
+        db_teams::add_member(&pool, team.id, teammate.id, TeamRole::Member)
+            .await
+            .unwrap();
+        let project = create_project(&pool, Some(owner.id), None).await;
+
+        let new_owner = resolve_new_owner(&pool, ProjectOwner::Team("backend".to_string()), &owner)
+            .await
+            .unwrap();
+        assert_eq!(new_owner, (None, Some(team.id)));
+        assert!(
+            users_losing_access(&pool, &project, new_owner.0, new_owner.1)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[sqlx::test]
+    async fn transfer_team_to_user_reports_other_members(pool: sqlx::PgPool) {
+        use crate::db::models::TeamRole;
+
+        let owner = db_users::create(&pool, "owner@example.com").await.unwrap();
+        let teammate = db_users::create(&pool, "teammate@example.com")
+            .await
+            .unwrap();
+        let app_user = db_users::create(&pool, "app-user@example.com")
+            .await
+            .unwrap();
+        let team = db_teams::create(&pool, "backend").await.unwrap();
+        for member in [&owner, &teammate, &app_user] {
+            db_teams::add_member(&pool, team.id, member.id, TeamRole::Member)
+                .await
+                .unwrap();
+        }
+        let project = create_project(&pool, None, Some(team.id)).await;
+        project_app_users::add_user(&pool, project.id, app_user.id)
+            .await
+            .unwrap();
+
+        let new_owner = resolve_new_owner(
+            &pool,
+            ProjectOwner::User("owner@example.com".to_string()),
+            &owner,
+        )
+        .await
+        .unwrap();
+        assert_eq!(new_owner, (Some(owner.id), None));
+        // The app user keeps access through their grant, the teammate doesn't
+        assert_eq!(
+            users_losing_access(&pool, &project, new_owner.0, new_owner.1)
+                .await
+                .unwrap(),
+            vec![teammate.id]
+        );
+    }
+
+    #[sqlx::test]
+    async fn transfer_to_team_requires_membership(pool: sqlx::PgPool) {
+        let owner = db_users::create(&pool, "owner@example.com").await.unwrap();
+        db_teams::create(&pool, "backend").await.unwrap();
+
+        let err = resolve_new_owner(&pool, ProjectOwner::Team("backend".to_string()), &owner)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let err = resolve_new_owner(
+            &pool,
+            ProjectOwner::User("nobody@example.com".to_string()),
+            &owner,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
@@ -1,4 +1,4 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Access class information for API responses
 #[derive(Debug, Serialize, Clone)]
@@ -54,8 +54,9 @@ impl From<ProjectStatus> for crate::db::models::ProjectStatus {
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ProjectOwner {
-    User(String), // User ID
-    Team(String), // Team ID
+    User(String),  // User ID
+    Team(String),  // Team ID
+    Group(String), // IdP group name (owned by the IdP-managed team of that name)
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Default)]
@@ -88,6 +89,18 @@ pub struct Project {
     pub app_teams: Vec<TeamInfo>, // Teams whose members can access the deployed app
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_url: Option<String>, // URL to where the project code lives
+    /// Redirect HTTP to HTTPS (unset = only when TLS is configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_https: Option<bool>,
+    /// HSTS max-age in seconds (unset = HSTS disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hsts_max_age: Option<i32>,
+    /// Quota of concurrently active deployments (unset = server-wide default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_active_deployments: Option<i32>,
+    /// Deployment group TTL policies, first match wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group_ttls: Vec<GroupTtlPolicy>,
     /// Effective deployment defaults (platform defaults, shown to users)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deployment_defaults: Option<DeploymentDefaultsInfo>,
@@ -128,6 +141,53 @@ pub struct UpdateProjectRequest {
     pub app_users: Option<Vec<String>>,     // User emails or IDs
     pub app_teams: Option<Vec<String>>,     // Team names or IDs
     pub source_url: Option<Option<String>>, // URL to where the project code lives (None = don't update, Some(None) = clear)
+    /// Redirect HTTP to HTTPS on the project's ingresses (None = don't update,
+    /// Some(None) = automatic, i.e. on when TLS is configured)
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub force_https: Option<Option<bool>>,
+    pub hsts_max_age: Option<u32>, // HSTS max-age in seconds (0 = disable HSTS)
+    /// Active deployment quota, admins only (0 = server default)
+    pub max_active_deployments: Option<u32>,
+    /// Replaces the group TTL policies (empty list = remove all policies)
+    pub group_ttls: Option<Vec<GroupTtlPolicy>>,
+}
+
+/// Deserialize a present field into `Some`, so an explicit `null` becomes `Some(None)`
+/// while an absent field stays `None` (via `#[serde(default)]`)
+fn deserialize_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Expiration applied to new deployments of the groups matching `pattern`
+/// when the deployment doesn't request one itself
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct GroupTtlPolicy {
+    /// Group name glob (`*` matches any characters, `?` a single character)
+    pub pattern: String,
+    /// Time until expiration (e.g. "7d", "12h", "30m")
+    pub expire: String,
+}
+
+impl GroupTtlPolicy {
+    /// Whether the policy's pattern matches the given deployment group
+    pub fn matches(&self, group: &str) -> bool {
+        let mut regex = String::from("^");
+        for c in self.pattern.chars() {
+            match c {
+                '*' => regex.push_str(".*"),
+                '?' => regex.push('.'),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        regex.push('$');
+        regex::Regex::new(&regex)
+            .map(|re| re.is_match(group))
+            .unwrap_or(false)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -135,6 +195,56 @@ pub struct UpdateProjectResponse {
     pub project: Project,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct TransferProjectRequest {
+    pub owner: ProjectOwner,
+}
+
+/// Request to configure the deployment status webhook of a project
+#[derive(Debug, Deserialize, Clone)]
+pub struct SetProjectWebhookRequest {
+    pub url: String,
+    /// Secret for the `X-Rise-Signature` HMAC-SHA256 header (stored encrypted)
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// Deployment status webhook of a project (the secret is never returned)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProjectWebhook {
+    pub url: String,
+    pub has_secret: bool,
+    pub updated_at: String,
+}
+
+impl From<crate::db::models::ProjectWebhook> for ProjectWebhook {
+    fn from(webhook: crate::db::models::ProjectWebhook) -> Self {
+        Self {
+            url: webhook.url,
+            has_secret: webhook.secret.is_some(),
+            updated_at: webhook.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// An active deployment that moved to the new owner with its project
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TransferredDeployment {
+    pub deployment_id: String,
+    pub deployment_group: String,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TransferProjectResponse {
+    pub project: Project,
+    pub previous_owner: Option<OwnerInfo>,
+    /// Active deployments, which keep running and are now governed by the new owner
+    pub active_deployments: Vec<TransferredDeployment>,
+    /// Effects of the transfer the requesting user should know about (e.g. losing access)
+    pub warnings: Vec<String>,
+}
+
 // User information for expanded responses
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct UserInfo {
@@ -182,3 +292,22 @@ pub struct GetProjectParams {
     #[serde(default)]
     pub by_id: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_request_distinguishes_null_from_absent() {
+        let request: UpdateProjectRequest =
+            serde_json::from_str(r#"{"force_https": null}"#).unwrap();
+        assert_eq!(request.force_https, Some(None));
+        assert_eq!(request.source_url, None);
+
+        // Only force_https gives `null` a meaning of its own
+        let request: UpdateProjectRequest =
+            serde_json::from_str(r#"{"force_https": false, "source_url": null}"#).unwrap();
+        assert_eq!(request.force_https, Some(Some(false)));
+        assert_eq!(request.source_url, None);
+    }
+}
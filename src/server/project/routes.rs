@@ -16,4 +16,14 @@ pub fn routes() -> Router<AppState> {
         .route("/projects/{id_or_name}", get(handlers::get_project))
         .route("/projects/{id_or_name}", put(handlers::update_project))
         .route("/projects/{id_or_name}", delete(handlers::delete_project))
+        .route(
+            "/projects/{id_or_name}/transfer",
+            post(handlers::transfer_project),
+        )
+        .route(
+            "/projects/{id_or_name}/webhook",
+            get(handlers::get_project_webhook)
+                .put(handlers::set_project_webhook)
+                .delete(handlers::delete_project_webhook),
+        )
 }
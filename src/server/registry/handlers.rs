@@ -84,6 +84,7 @@ pub async fn get_deployment_registry_credentials(
         .map_err(|e| {
             e.with_context("project_name", &project_name)
                 .with_context("repository", &repository)
+                .with_context("registry_type", state.registry_provider.registry_type())
         })?;
 
     Ok(Json(GetRegistryCredsResponse {
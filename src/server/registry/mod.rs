@@ -54,6 +54,9 @@ pub trait RegistryProvider: Send + Sync {
         })
     }
 
+    /// Provider type, matching the `type` of the registry settings (e.g., "ecr", "harbor")
+    fn registry_type(&self) -> &'static str;
+
     /// Get the registry host (for credentials map key)
     ///
     /// Returns the registry hostname without protocol or path
@@ -82,4 +85,12 @@ pub trait RegistryProvider: Send + Sync {
     fn requires_pull_secret(&self) -> bool {
         true
     }
+
+    /// How long a Kubernetes image pull secret is used before the controller re-mints it.
+    ///
+    /// Providers issuing credentials that expire sooner than the 6 hour default
+    /// (e.g., GAR access tokens) must return a shorter interval.
+    fn pull_secret_refresh_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(6 * 60 * 60)
+    }
 }
@@ -88,6 +88,57 @@ fn default_namespace() -> String {
     String::new()
 }
 
+/// Configuration for Google Artifact Registry
+///
+/// Access tokens are minted from `credentials_json` (a service-account key) when set,
+/// otherwise from the GKE metadata server (workload identity).
+#[cfg(feature = "backend")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct GarConfig {
+    /// Artifact Registry location (e.g., "europe", "us-central1")
+    pub location: String,
+    /// GCP project ID hosting the repository
+    pub project_id: String,
+    /// Artifact Registry repository name; images are stored at
+    /// `<location>-docker.pkg.dev/<project_id>/<repository>/<app>:<tag>`
+    pub repository: String,
+    /// Optional service-account JSON key (contents, not a path)
+    #[serde(default)]
+    pub credentials_json: Option<String>,
+}
+
+/// Configuration for GitHub Container Registry
+///
+/// Authenticates with either a personal access token (`token`) or a GitHub App
+/// (`app_id`, `installation_id`, `private_key`) whose installation tokens are minted
+/// on demand.
+#[cfg(feature = "backend")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct GhcrConfig {
+    /// GitHub user or organization owning the packages; images are stored at
+    /// `ghcr.io/<owner>/<app>:<tag>`
+    pub owner: String,
+    /// Personal access token with `write:packages` scope
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Username paired with `token` (defaults to `owner`)
+    #[serde(default)]
+    pub username: Option<String>,
+    /// GitHub App ID
+    #[serde(default)]
+    pub app_id: Option<String>,
+    /// GitHub App installation ID for the owner
+    #[serde(default)]
+    pub installation_id: Option<String>,
+    /// GitHub App private key (PEM contents, not a path)
+    #[serde(default)]
+    pub private_key: Option<String>,
+    /// GitHub REST API URL used to mint installation tokens
+    /// (defaults to "https://api.github.com")
+    #[serde(default)]
+    pub github_api_url: Option<String>,
+}
+
 /// Configuration for GitLab container registry
 ///
 /// Credentials are minted as short-lived scoped JWTs from GitLab's JWT auth endpoint,
@@ -116,3 +167,48 @@ pub struct GitLabRegistryConfig {
     #[serde(default)]
     pub client_registry_url: Option<String>,
 }
+
+/// Configuration for Azure Container Registry
+///
+/// Authenticates with either a service principal (`tenant_id`, `client_id`,
+/// `client_secret`), whose Entra ID tokens are exchanged for ACR refresh tokens, or the
+/// registry's admin user (`username`, `password`).
+#[cfg(feature = "backend")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct AcrConfig {
+    /// Registry name or login server; images are stored at
+    /// `<registry>.azurecr.io/<app>:<tag>`
+    pub registry: String,
+    /// Microsoft Entra tenant ID of the service principal
+    #[serde(default)]
+    pub tenant_id: Option<String>,
+    /// Service principal application (client) ID
+    #[serde(default)]
+    pub client_id: Option<String>,
+    /// Service principal client secret
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// Admin username
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Admin password
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// Configuration for a Harbor registry
+///
+/// A robot account with push and pull permissions on `project` is used for both
+/// CLI pushes and image pull secrets. Images are stored at `<host>/<project>/<app>:<tag>`.
+#[cfg(feature = "backend")]
+#[derive(Debug, Clone, Deserialize)]
+pub struct HarborConfig {
+    /// Harbor URL or host (e.g., "https://harbor.example.com")
+    pub url: String,
+    /// Robot account name (e.g., "robot$rise")
+    pub robot_username: String,
+    /// Robot account secret
+    pub robot_secret: String,
+    /// Harbor project holding the app repositories
+    pub project: String,
+}
@@ -0,0 +1,257 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::server::azure_auth::{AzureCredential, AzureTokenSource};
+use crate::server::registry::{
+    models::{AcrConfig, RegistryCredentials},
+    ImageTagType, RegistryProvider,
+};
+
+/// Resource of the Entra ID token exchanged for an ACR refresh token
+const MANAGEMENT_RESOURCE: &str = "https://management.azure.com";
+
+/// Username ACR expects when authenticating with a refresh token
+const REFRESH_TOKEN_USERNAME: &str = "00000000-0000-0000-0000-000000000000";
+
+/// Lifetime of ACR refresh tokens
+const REFRESH_TOKEN_LIFETIME: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// How often the Kubernetes controller re-mints pull secrets when using a service
+/// principal (refresh tokens live 3 hours)
+const SP_PULL_SECRET_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Refresh cached tokens this long before they expire, so every token handed out
+/// outlives the pull secret it ends up in
+const TOKEN_EXPIRY_BUFFER: Duration = Duration::from_secs(65 * 60);
+
+/// Azure Container Registry provider
+///
+/// Authenticates with either the registry's admin user or a service principal. For
+/// service principals, an Entra ID access token is exchanged for an ACR refresh token
+/// (valid for 3 hours), which is registry-wide and used for pushes and pulls.
+pub struct AcrProvider {
+    http_client: reqwest::Client,
+    auth: AcrAuth,
+    /// Registry login server (e.g., "myregistry.azurecr.io")
+    registry_host: String,
+}
+
+enum AcrAuth {
+    Admin {
+        username: String,
+        password: String,
+    },
+    ServicePrincipal {
+        tenant_id: String,
+        client_id: String,
+        /// Entra ID tokens for the service principal
+        entra_token: Box<AzureTokenSource>,
+        /// Cached refresh token and the instant it expires
+        cached_token: RwLock<Option<(String, Instant)>>,
+    },
+}
+
+#[derive(Deserialize)]
+struct ExchangeResponse {
+    refresh_token: String,
+}
+
+impl AcrProvider {
+    pub fn new(config: AcrConfig) -> Result<Self> {
+        let registry = config.registry.trim().to_lowercase();
+        if registry.is_empty() || registry.contains('/') {
+            anyhow::bail!(
+                "Invalid ACR registry '{}': expected a name such as \"myregistry\" or \"myregistry.azurecr.io\"",
+                config.registry
+            );
+        }
+        // Accept both the registry name and its login server
+        let registry_host = if registry.contains('.') {
+            registry
+        } else {
+            format!("{}.azurecr.io", registry)
+        };
+
+        let auth = match (config.client_id, config.username) {
+            (Some(client_id), None) => {
+                let tenant_id = config
+                    .tenant_id
+                    .context("ACR 'tenant_id' is required when 'client_id' is set")?;
+                let client_secret = config
+                    .client_secret
+                    .context("ACR 'client_secret' is required when 'client_id' is set")?;
+                AcrAuth::ServicePrincipal {
+                    entra_token: Box::new(AzureTokenSource::new(
+                        AzureCredential::ServicePrincipal {
+                            tenant_id: tenant_id.clone(),
+                            client_id: client_id.clone(),
+                            client_secret,
+                        },
+                        MANAGEMENT_RESOURCE,
+                    )),
+                    tenant_id,
+                    client_id,
+                    cached_token: RwLock::new(None),
+                }
+            }
+            (None, Some(username)) => AcrAuth::Admin {
+                username,
+                password: config
+                    .password
+                    .context("ACR 'password' is required when 'username' is set")?,
+            },
+            (Some(_), Some(_)) => {
+                anyhow::bail!("ACR registry accepts either 'client_id' or 'username', not both")
+            }
+            (None, None) => {
+                anyhow::bail!("ACR registry requires either 'client_id' or 'username'")
+            }
+        };
+
+        Ok(Self {
+            http_client: reqwest::Client::new(),
+            auth,
+            registry_host,
+        })
+    }
+
+    /// Get a (username, password) pair and, for refresh tokens, the remaining lifetime
+    async fn access_token(&self) -> Result<(String, String, Option<Duration>)> {
+        match &self.auth {
+            AcrAuth::Admin { username, password } => Ok((username.clone(), password.clone(), None)),
+            AcrAuth::ServicePrincipal {
+                tenant_id,
+                client_id,
+                entra_token,
+                cached_token,
+            } => {
+                {
+                    let cache = cached_token.read().unwrap();
+                    if let Some((token, expires_at)) = cache.as_ref() {
+                        let remaining = expires_at.saturating_duration_since(Instant::now());
+                        if remaining > TOKEN_EXPIRY_BUFFER {
+                            tracing::debug!("Using cached ACR refresh token");
+                            return Ok((
+                                REFRESH_TOKEN_USERNAME.to_string(),
+                                token.clone(),
+                                Some(remaining),
+                            ));
+                        }
+                    }
+                }
+
+                let token = self
+                    .fetch_refresh_token(tenant_id, client_id, entra_token)
+                    .await?;
+                {
+                    let mut cache = cached_token.write().unwrap();
+                    *cache = Some((token.clone(), Instant::now() + REFRESH_TOKEN_LIFETIME));
+                }
+
+                Ok((
+                    REFRESH_TOKEN_USERNAME.to_string(),
+                    token,
+                    Some(REFRESH_TOKEN_LIFETIME),
+                ))
+            }
+        }
+    }
+
+    /// Get an Entra ID token for the service principal and exchange it for an ACR
+    /// refresh token
+    async fn fetch_refresh_token(
+        &self,
+        tenant_id: &str,
+        client_id: &str,
+        entra_token: &AzureTokenSource,
+    ) -> Result<String> {
+        tracing::info!(
+            "Fetching ACR refresh token for service principal {}",
+            client_id
+        );
+
+        let access_token = entra_token.access_token().await?;
+
+        let response = self
+            .http_client
+            .post(format!("https://{}/oauth2/exchange", self.registry_host))
+            .form(&[
+                ("grant_type", "access_token"),
+                ("service", self.registry_host.as_str()),
+                ("tenant", tenant_id),
+                ("access_token", access_token.as_str()),
+            ])
+            .send()
+            .await
+            .context("Failed to reach ACR token exchange endpoint")?;
+        let exchange: ExchangeResponse =
+            Self::parse_response(response, "ACR token exchange").await?;
+
+        Ok(exchange.refresh_token)
+    }
+
+    async fn parse_response<T: serde::de::DeserializeOwned>(
+        response: reqwest::Response,
+        what: &str,
+    ) -> Result<T> {
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("{} request returned {}: {}", what, status, body);
+        }
+
+        response
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} response", what))
+    }
+}
+
+#[async_trait]
+impl RegistryProvider for AcrProvider {
+    async fn get_credentials(&self, repository: &str) -> Result<RegistryCredentials> {
+        tracing::info!(repository = repository, "Getting ACR credentials");
+
+        let (username, password, remaining) = self.access_token().await?;
+
+        Ok(RegistryCredentials {
+            registry_url: format!("{}/{}", self.registry_host, repository),
+            username,
+            password,
+            expires_in: remaining.map(|d| d.as_secs()),
+            auth_method: Default::default(),
+        })
+    }
+
+    async fn get_pull_credentials(&self) -> Result<(String, String)> {
+        let (username, password, _) = self.access_token().await?;
+        Ok((username, password))
+    }
+
+    fn registry_type(&self) -> &'static str {
+        "acr"
+    }
+
+    fn registry_host(&self) -> &str {
+        &self.registry_host
+    }
+
+    fn registry_url(&self) -> &str {
+        &self.registry_host
+    }
+
+    fn get_image_tag(&self, repository: &str, tag: &str, _tag_type: ImageTagType) -> String {
+        // ACR doesn't differentiate between client and internal - always use same path
+        format!("{}/{}:{}", self.registry_host, repository, tag)
+    }
+
+    fn pull_secret_refresh_interval(&self) -> Duration {
+        match self.auth {
+            AcrAuth::Admin { .. } => Duration::from_secs(6 * 60 * 60),
+            AcrAuth::ServicePrincipal { .. } => SP_PULL_SECRET_REFRESH_INTERVAL,
+        }
+    }
+}
@@ -88,6 +88,10 @@ impl RegistryProvider for OciClientAuthProvider {
         Ok((String::new(), String::new()))
     }
 
+    fn registry_type(&self) -> &'static str {
+        "oci-client-auth"
+    }
+
     fn registry_host(&self) -> &str {
         &self.registry_host
     }
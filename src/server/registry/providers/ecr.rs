@@ -283,6 +283,10 @@ impl RegistryProvider for EcrProvider {
         Ok((username, password))
     }
 
+    fn registry_type(&self) -> &'static str {
+        "ecr"
+    }
+
     fn registry_host(&self) -> &str {
         &self.registry_host
     }
@@ -0,0 +1,103 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::time::Duration;
+
+use crate::server::gcp_auth::GcpTokenSource;
+use crate::server::registry::{
+    models::{GarConfig, RegistryCredentials},
+    ImageTagType, RegistryProvider,
+};
+
+/// OAuth2 scope required for pushing to and pulling from Artifact Registry
+const CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// How often the Kubernetes controller re-mints pull secrets (tokens live ~1 hour)
+const PULL_SECRET_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Refresh cached tokens this long before they expire, so every token handed out
+/// outlives the pull secret it ends up in
+const TOKEN_EXPIRY_BUFFER: Duration = Duration::from_secs(35 * 60);
+
+/// Username Artifact Registry expects when authenticating with an access token
+const ACCESS_TOKEN_USERNAME: &str = "oauth2accesstoken";
+
+/// Google Artifact Registry provider
+///
+/// Mints short-lived (~1 hour) OAuth2 access tokens, either from a service-account
+/// JSON key or from the GKE metadata server (workload identity). Access tokens are
+/// project-wide, so the same token is used for pushes and pulls.
+pub struct GarProvider {
+    token_source: GcpTokenSource,
+    /// Registry host (e.g., "europe-docker.pkg.dev")
+    registry_host: String,
+    /// Repository path (e.g., "europe-docker.pkg.dev/my-project/rise")
+    registry_url: String,
+}
+
+impl GarProvider {
+    pub fn new(config: GarConfig) -> Result<Self> {
+        let token_source = GcpTokenSource::new(
+            config.credentials_json.as_deref(),
+            CLOUD_PLATFORM_SCOPE,
+            TOKEN_EXPIRY_BUFFER,
+        )
+        .context("Failed to configure GAR credentials")?;
+
+        let registry_host = format!("{}-docker.pkg.dev", config.location);
+        let registry_url = format!(
+            "{}/{}/{}",
+            registry_host,
+            config.project_id,
+            config.repository.trim_matches('/')
+        );
+
+        Ok(Self {
+            token_source,
+            registry_host,
+            registry_url,
+        })
+    }
+}
+
+#[async_trait]
+impl RegistryProvider for GarProvider {
+    async fn get_credentials(&self, repository: &str) -> Result<RegistryCredentials> {
+        tracing::info!(repository = repository, "Getting GAR credentials");
+
+        let (token, remaining) = self.token_source.access_token().await?;
+
+        Ok(RegistryCredentials {
+            registry_url: format!("{}/{}", self.registry_url, repository),
+            username: ACCESS_TOKEN_USERNAME.to_string(),
+            password: token,
+            expires_in: Some(remaining.as_secs()),
+            auth_method: Default::default(),
+        })
+    }
+
+    async fn get_pull_credentials(&self) -> Result<(String, String)> {
+        let (token, _) = self.token_source.access_token().await?;
+        Ok((ACCESS_TOKEN_USERNAME.to_string(), token))
+    }
+
+    fn registry_type(&self) -> &'static str {
+        "gar"
+    }
+
+    fn registry_host(&self) -> &str {
+        &self.registry_host
+    }
+
+    fn registry_url(&self) -> &str {
+        &self.registry_url
+    }
+
+    fn get_image_tag(&self, repository: &str, tag: &str, _tag_type: ImageTagType) -> String {
+        // GAR doesn't differentiate between client and internal - always use same path
+        format!("{}/{}:{}", self.registry_url, repository, tag)
+    }
+
+    fn pull_secret_refresh_interval(&self) -> Duration {
+        PULL_SECRET_REFRESH_INTERVAL
+    }
+}
@@ -0,0 +1,268 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use crate::server::registry::{
+    models::{GhcrConfig, RegistryCredentials},
+    ImageTagType, RegistryProvider,
+};
+
+/// GitHub Container Registry host
+const GHCR_HOST: &str = "ghcr.io";
+
+/// Default GitHub REST API base URL (GitHub Enterprise Server uses `<host>/api/v3`)
+const DEFAULT_GITHUB_API_URL: &str = "https://api.github.com";
+
+/// How often the Kubernetes controller re-mints pull secrets when using a GitHub App
+/// (installation tokens live 1 hour)
+const APP_PULL_SECRET_REFRESH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Refresh cached installation tokens this long before they expire, so every token
+/// handed out outlives the pull secret it ends up in
+const TOKEN_EXPIRY_BUFFER: Duration = Duration::from_secs(35 * 60);
+
+/// Username GHCR expects when authenticating with a GitHub App installation token
+const INSTALLATION_TOKEN_USERNAME: &str = "x-access-token";
+
+/// GitHub Container Registry provider
+///
+/// Authenticates with either a static personal access token or short-lived (1 hour)
+/// GitHub App installation tokens. GHCR tokens are not scoped to a single image, so
+/// the same token is used for pushes and pulls.
+pub struct GhcrProvider {
+    http_client: reqwest::Client,
+    auth: GhcrAuth,
+    /// Repository path (e.g., "ghcr.io/my-org")
+    registry_url: String,
+}
+
+enum GhcrAuth {
+    PersonalAccessToken {
+        username: String,
+        token: String,
+    },
+    App {
+        api_url: String,
+        app_id: String,
+        installation_id: String,
+        encoding_key: EncodingKey,
+        /// Cached installation token and the time it expires
+        cached_token: RwLock<Option<(String, chrono::DateTime<chrono::Utc>)>>,
+    },
+}
+
+#[derive(Serialize)]
+struct AppJwtClaims<'a> {
+    iss: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct InstallationTokenResponse {
+    token: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl GhcrProvider {
+    pub fn new(config: GhcrConfig) -> Result<Self> {
+        let auth = match (config.token, config.app_id) {
+            (Some(token), None) => GhcrAuth::PersonalAccessToken {
+                username: config.username.unwrap_or_else(|| config.owner.clone()),
+                token,
+            },
+            (None, Some(app_id)) => {
+                let installation_id = config
+                    .installation_id
+                    .context("GHCR 'installation_id' is required when 'app_id' is set")?;
+                let private_key = config
+                    .private_key
+                    .context("GHCR 'private_key' is required when 'app_id' is set")?;
+                let encoding_key = EncodingKey::from_rsa_pem(private_key.as_bytes())
+                    .context("Invalid GitHub App private key for GHCR")?;
+                GhcrAuth::App {
+                    api_url: config
+                        .github_api_url
+                        .unwrap_or_else(|| DEFAULT_GITHUB_API_URL.to_string())
+                        .trim_end_matches('/')
+                        .to_string(),
+                    app_id,
+                    installation_id,
+                    encoding_key,
+                    cached_token: RwLock::new(None),
+                }
+            }
+            (Some(_), Some(_)) => {
+                anyhow::bail!("GHCR registry accepts either 'token' or 'app_id', not both")
+            }
+            (None, None) => {
+                anyhow::bail!("GHCR registry requires either 'token' or 'app_id'")
+            }
+        };
+
+        // GHCR rejects image names with uppercase characters
+        let registry_url = format!(
+            "{}/{}",
+            GHCR_HOST,
+            config.owner.trim_matches('/').to_lowercase()
+        );
+
+        Ok(Self {
+            http_client: reqwest::Client::new(),
+            auth,
+            registry_url,
+        })
+    }
+
+    /// Get a (username, token) pair and, for installation tokens, the remaining lifetime
+    async fn access_token(&self) -> Result<(String, String, Option<Duration>)> {
+        match &self.auth {
+            GhcrAuth::PersonalAccessToken { username, token } => {
+                Ok((username.clone(), token.clone(), None))
+            }
+            GhcrAuth::App {
+                api_url,
+                app_id,
+                installation_id,
+                encoding_key,
+                cached_token,
+            } => {
+                {
+                    let cache = cached_token.read().unwrap();
+                    if let Some((token, expires_at)) = cache.as_ref() {
+                        let remaining = (*expires_at - chrono::Utc::now())
+                            .to_std()
+                            .unwrap_or_default();
+                        if remaining > TOKEN_EXPIRY_BUFFER {
+                            tracing::debug!("Using cached GHCR installation token");
+                            return Ok((
+                                INSTALLATION_TOKEN_USERNAME.to_string(),
+                                token.clone(),
+                                Some(remaining),
+                            ));
+                        }
+                    }
+                }
+
+                let response = self
+                    .fetch_installation_token(api_url, app_id, installation_id, encoding_key)
+                    .await?;
+                let remaining = (response.expires_at - chrono::Utc::now())
+                    .to_std()
+                    .unwrap_or_default();
+                {
+                    let mut cache = cached_token.write().unwrap();
+                    *cache = Some((response.token.clone(), response.expires_at));
+                }
+
+                Ok((
+                    INSTALLATION_TOKEN_USERNAME.to_string(),
+                    response.token,
+                    Some(remaining),
+                ))
+            }
+        }
+    }
+
+    /// Exchange a signed app JWT for an installation access token
+    async fn fetch_installation_token(
+        &self,
+        api_url: &str,
+        app_id: &str,
+        installation_id: &str,
+        encoding_key: &EncodingKey,
+    ) -> Result<InstallationTokenResponse> {
+        tracing::info!(
+            "Fetching GHCR installation token for GitHub App {} (installation {})",
+            app_id,
+            installation_id
+        );
+
+        // Backdate iat to allow for clock drift, as recommended by GitHub
+        let now = chrono::Utc::now().timestamp();
+        let claims = AppJwtClaims {
+            iss: app_id,
+            iat: now - 60,
+            exp: now + 9 * 60,
+        };
+        let jwt = encode(&Header::new(Algorithm::RS256), &claims, encoding_key)
+            .context("Failed to sign GitHub App JWT")?;
+
+        let response = self
+            .http_client
+            .post(format!(
+                "{}/app/installations/{}/access_tokens",
+                api_url, installation_id
+            ))
+            .bearer_auth(jwt)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "rise")
+            .send()
+            .await
+            .context("Failed to reach GitHub API for installation token")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "GitHub installation token request returned {}: {}",
+                status,
+                body
+            );
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse GitHub installation token response")
+    }
+}
+
+#[async_trait]
+impl RegistryProvider for GhcrProvider {
+    async fn get_credentials(&self, repository: &str) -> Result<RegistryCredentials> {
+        tracing::info!(repository = repository, "Getting GHCR credentials");
+
+        let (username, password, remaining) = self.access_token().await?;
+
+        Ok(RegistryCredentials {
+            registry_url: format!("{}/{}", self.registry_url, repository),
+            username,
+            password,
+            expires_in: remaining.map(|d| d.as_secs()),
+            auth_method: Default::default(),
+        })
+    }
+
+    async fn get_pull_credentials(&self) -> Result<(String, String)> {
+        let (username, password, _) = self.access_token().await?;
+        Ok((username, password))
+    }
+
+    fn registry_type(&self) -> &'static str {
+        "ghcr"
+    }
+
+    fn registry_host(&self) -> &str {
+        GHCR_HOST
+    }
+
+    fn registry_url(&self) -> &str {
+        &self.registry_url
+    }
+
+    fn get_image_tag(&self, repository: &str, tag: &str, _tag_type: ImageTagType) -> String {
+        // GHCR doesn't differentiate between client and internal - always use same path
+        format!("{}/{}:{}", self.registry_url, repository, tag)
+    }
+
+    fn pull_secret_refresh_interval(&self) -> Duration {
+        match self.auth {
+            GhcrAuth::PersonalAccessToken { .. } => Duration::from_secs(6 * 60 * 60),
+            GhcrAuth::App { .. } => APP_PULL_SECRET_REFRESH_INTERVAL,
+        }
+    }
+}
@@ -129,6 +129,10 @@ impl RegistryProvider for GitLabRegistryProvider {
         Ok((self.config.username.clone(), self.config.token.clone()))
     }
 
+    fn registry_type(&self) -> &'static str {
+        "gitlab"
+    }
+
     fn registry_host(&self) -> &str {
         &self.registry_host
     }
@@ -0,0 +1,96 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::time::Duration;
+
+use crate::server::registry::{
+    models::{HarborConfig, RegistryCredentials},
+    ImageTagType, RegistryProvider,
+};
+
+/// How often the Kubernetes controller rewrites pull secrets. Robot secrets don't
+/// expire, so this only bounds how long a rotated secret takes to reach namespaces.
+const PULL_SECRET_REFRESH_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Harbor registry provider
+///
+/// Authenticates pushes and pulls with a long-lived Harbor robot account, which needs
+/// push and pull permissions on the configured Harbor project.
+pub struct HarborProvider {
+    config: HarborConfig,
+    /// Registry host (e.g., "harbor.example.com")
+    registry_host: String,
+    /// Harbor project path (e.g., "harbor.example.com/rise")
+    registry_url: String,
+}
+
+impl HarborProvider {
+    pub fn new(config: HarborConfig) -> Result<Self> {
+        let registry_host = config
+            .url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_end_matches('/')
+            .to_string();
+        if registry_host.is_empty() || registry_host.contains('/') {
+            anyhow::bail!(
+                "Invalid Harbor URL '{}': expected a host such as \"harbor.example.com\"",
+                config.url
+            );
+        }
+
+        let project = config.project.trim_matches('/');
+        if project.is_empty() {
+            anyhow::bail!("Harbor project must not be empty");
+        }
+        let registry_url = format!("{}/{}", registry_host, project);
+
+        Ok(Self {
+            config,
+            registry_host,
+            registry_url,
+        })
+    }
+}
+
+#[async_trait]
+impl RegistryProvider for HarborProvider {
+    async fn get_credentials(&self, repository: &str) -> Result<RegistryCredentials> {
+        tracing::info!(repository = repository, "Getting Harbor credentials");
+
+        Ok(RegistryCredentials {
+            registry_url: format!("{}/{}", self.registry_url, repository),
+            username: self.config.robot_username.clone(),
+            password: self.config.robot_secret.clone(),
+            expires_in: None,
+            auth_method: Default::default(),
+        })
+    }
+
+    async fn get_pull_credentials(&self) -> Result<(String, String)> {
+        Ok((
+            self.config.robot_username.clone(),
+            self.config.robot_secret.clone(),
+        ))
+    }
+
+    fn registry_type(&self) -> &'static str {
+        "harbor"
+    }
+
+    fn registry_host(&self) -> &str {
+        &self.registry_host
+    }
+
+    fn registry_url(&self) -> &str {
+        &self.registry_url
+    }
+
+    fn get_image_tag(&self, repository: &str, tag: &str, _tag_type: ImageTagType) -> String {
+        // Harbor doesn't differentiate between client and internal - always use same path
+        format!("{}/{}:{}", self.registry_url, repository, tag)
+    }
+
+    fn pull_secret_refresh_interval(&self) -> Duration {
+        PULL_SECRET_REFRESH_INTERVAL
+    }
+}
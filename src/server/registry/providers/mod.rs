@@ -1,15 +1,39 @@
+#[cfg(feature = "backend")]
+pub mod acr;
+
 pub mod docker; // Note: File still named docker.rs but contains OciClientAuthProvider
 
 #[cfg(feature = "backend")]
 pub mod ecr;
 
+#[cfg(feature = "backend")]
+pub mod gar;
+
+#[cfg(feature = "backend")]
+pub mod ghcr;
+
 #[cfg(feature = "backend")]
 pub mod gitlab;
 
+#[cfg(feature = "backend")]
+pub mod harbor;
+
+#[cfg(feature = "backend")]
+pub use acr::AcrProvider;
+
 pub use docker::OciClientAuthProvider;
 
 #[cfg(feature = "backend")]
 pub use ecr::EcrProvider;
 
+#[cfg(feature = "backend")]
+pub use gar::GarProvider;
+
+#[cfg(feature = "backend")]
+pub use ghcr::GhcrProvider;
+
 #[cfg(feature = "backend")]
 pub use gitlab::GitLabRegistryProvider;
+
+#[cfg(feature = "backend")]
+pub use harbor::HarborProvider;
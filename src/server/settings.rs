@@ -10,10 +10,14 @@ pub struct Settings {
     pub database: DatabaseSettings,
     #[serde(default)]
     pub registry: Option<RegistrySettings>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_deployment_controller")]
     pub deployment_controller: Option<DeploymentControllerSettings>,
     #[serde(default)]
     pub encryption: Option<EncryptionSettings>,
+    /// Previously used encryption providers, tried when decrypting secrets that were
+    /// not encrypted by the current `encryption` provider (for provider migrations)
+    #[serde(default)]
+    pub legacy_encryption: Vec<EncryptionSettings>,
     #[serde(default)]
     pub extensions: Option<ExtensionsSettings>,
 }
@@ -80,6 +84,21 @@ pub struct ServerSettings {
     /// OAuth endpoint rate limiting configuration.
     #[serde(default)]
     pub oauth_rate_limit: OAuthRateLimitSettings,
+
+    /// Default quota of concurrently active (Pending through Unhealthy) deployments
+    /// per project, enforced when a deployment is created. Admins can override it per
+    /// project. Unlimited if not set.
+    #[serde(default)]
+    pub max_active_deployments_per_project: Option<u32>,
+
+    /// How long a resolved image digest is reused for pre-built image deployments
+    /// before the registry is asked again, in seconds (default: 300, 0 disables caching)
+    #[serde(default = "default_image_digest_cache_ttl_secs")]
+    pub image_digest_cache_ttl_secs: u64,
+}
+
+fn default_image_digest_cache_ttl_secs() -> u64 {
+    300 // 5 minutes
 }
 
 /// Rate limiting configuration for OAuth endpoints (authorize, callback, token).
@@ -256,6 +275,40 @@ pub struct AuthSettings {
     /// Interval in seconds for active sync polling (default: 300 = 5 minutes)
     #[serde(default = "default_active_sync_interval_secs")]
     pub active_sync_interval_secs: u64,
+    /// Additional OIDC issuers users can log in with, keyed by name
+    /// (`rise login --issuer <name>`). The issuer configured above remains the
+    /// default and is the one used for browser sign-in.
+    #[serde(default)]
+    pub issuers: std::collections::HashMap<String, OidcIssuerSettings>,
+}
+
+/// An additional OIDC issuer for login
+#[derive(Debug, Deserialize, Clone, JsonSchema)]
+pub struct OidcIssuerSettings {
+    /// Issuer URL (e.g., "https://my-org.okta.com")
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Human-readable name shown when listing issuers (e.g., "Okta (staff)")
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Optional custom authorize endpoint URL
+    /// If not set, will be discovered from issuer's .well-known/openid-configuration
+    #[serde(default)]
+    pub authorize_url: Option<String>,
+    /// Optional custom token endpoint URL
+    /// If not set, will be discovered from issuer's .well-known/openid-configuration
+    #[serde(default)]
+    pub token_url: Option<String>,
+    /// Email domains this issuer may log users in with (e.g., ["contractors.example.com"]).
+    /// Users are matched by email across issuers, so this is required to keep the
+    /// issuer from claiming accounts it does not own.
+    #[serde(default)]
+    pub allowed_email_domains: Vec<String>,
+    /// Sync team memberships from this issuer's `groups` claim when
+    /// `idp_group_sync_enabled` is set. Defaults to false.
+    #[serde(default)]
+    pub sync_groups: bool,
 }
 
 #[derive(Debug, Deserialize, Clone, JsonSchema)]
@@ -272,10 +325,36 @@ fn default_ingress_schema() -> String {
     "https".to_string()
 }
 
+fn default_ecs_health_check_path() -> String {
+    "/".to_string()
+}
+
+fn default_ecs_reconcile_interval_secs() -> u64 {
+    10
+}
+
 fn default_namespace_format() -> String {
     "rise-{project_name}".to_string()
 }
 
+fn default_managed_image_pull_secret_name() -> String {
+    "rise-registry-creds".to_string()
+}
+
+fn default_allowed_ingress_annotations() -> Vec<String> {
+    [
+        "nginx.ingress.kubernetes.io/proxy-body-size",
+        "nginx.ingress.kubernetes.io/proxy-connect-timeout",
+        "nginx.ingress.kubernetes.io/proxy-read-timeout",
+        "nginx.ingress.kubernetes.io/proxy-send-timeout",
+        "nginx.ingress.kubernetes.io/proxy-buffer-size",
+        "nginx.ingress.kubernetes.io/proxy-buffering",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
 fn default_node_selector() -> std::collections::HashMap<String, String> {
     let mut selector = std::collections::HashMap::new();
     selector.insert("kubernetes.io/arch".to_string(), "amd64".to_string());
@@ -330,12 +409,52 @@ pub enum CustomDomainTlsMode {
     Shared,
     /// Each custom domain gets its own tls-{domain} secret (cert-manager integration)
     PerDomain,
+    /// Like per-domain, with certificates issued by cert-manager through `acme_cluster_issuer`
+    Acme,
+}
+
+/// Kubernetes Service type for deployments with `protocol = "tcp"`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub enum TcpServiceType {
+    /// Only reachable from inside the cluster
+    #[default]
+    ClusterIP,
+    /// Additionally exposed on a port of every node
+    NodePort,
+    /// Exposed through a cloud load balancer
+    LoadBalancer,
+}
+
+impl TcpServiceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TcpServiceType::ClusterIP => "ClusterIP",
+            TcpServiceType::NodePort => "NodePort",
+            TcpServiceType::LoadBalancer => "LoadBalancer",
+        }
+    }
 }
 
 fn default_metacontroller_webhook_port() -> u16 {
     3001
 }
 
+fn default_env_decrypt_concurrency() -> usize {
+    8
+}
+
+fn default_revision_history_limit() -> i32 {
+    2
+}
+
+fn default_in_flight_resync_secs() -> f64 {
+    2.0
+}
+
+fn default_orphan_gc_interval_secs() -> u64 {
+    600
+}
+
 fn default_custom_domain_tls_mode() -> CustomDomainTlsMode {
     CustomDomainTlsMode::PerDomain
 }
@@ -379,13 +498,23 @@ pub struct DeploymentDefaults {
     #[serde(default = "default_replicas")]
     pub replicas: u32,
 
-    /// Default CPU allocation (default: "500m") — sets both K8s request and limit
+    /// Default CPU allocation (default: "500m") — the K8s request, and the limit unless
+    /// `cpu_limit` is set
     #[serde(default = "default_cpu")]
     pub cpu: String,
 
-    /// Default memory allocation (default: "256Mi") — sets both K8s request and limit
+    /// Default memory allocation (default: "256Mi") — the K8s request, and the limit unless
+    /// `memory_limit` is set
     #[serde(default = "default_memory")]
     pub memory: String,
+
+    /// Default CPU limit, used when the deployment does not set its own `cpu`
+    #[serde(default)]
+    pub cpu_limit: Option<String>,
+
+    /// Default memory limit, used when the deployment does not set its own `memory`
+    #[serde(default)]
+    pub memory_limit: Option<String>,
 }
 
 impl Default for DeploymentDefaults {
@@ -394,6 +523,8 @@ impl Default for DeploymentDefaults {
             replicas: default_replicas(),
             cpu: default_cpu(),
             memory: default_memory(),
+            cpu_limit: None,
+            memory_limit: None,
         }
     }
 }
@@ -425,6 +556,21 @@ pub struct DeploymentConstraints {
     /// Maximum memory allowed (default: "2Gi")
     #[serde(default = "default_max_memory")]
     pub max_memory: String,
+
+    /// Maximum expiration of a deployment, from the time it is created or renewed
+    /// (e.g., "30d"; unlimited if not set)
+    #[serde(default)]
+    pub max_expiration: Option<String>,
+}
+
+impl DeploymentConstraints {
+    /// Parsed `max_expiration`; validated when the settings are loaded
+    pub fn max_expiration(&self) -> Result<Option<chrono::Duration>, String> {
+        self.max_expiration
+            .as_deref()
+            .map(crate::rise_toml::parse_expiration_duration)
+            .transpose()
+    }
 }
 
 impl Default for DeploymentConstraints {
@@ -436,6 +582,7 @@ impl Default for DeploymentConstraints {
             max_cpu: default_max_cpu(),
             min_memory: default_min_memory(),
             max_memory: default_max_memory(),
+            max_expiration: None,
         }
     }
 }
@@ -455,6 +602,14 @@ pub struct HealthProbeConfig {
     #[serde(default = "default_probe_path")]
     pub path: String,
 
+    /// Path for the readiness probe (default: `path`)
+    #[serde(default)]
+    pub readiness_path: Option<String>,
+
+    /// Path for the liveness probe (default: `path`)
+    #[serde(default)]
+    pub liveness_path: Option<String>,
+
     /// Initial delay in seconds (default: 10)
     #[serde(default = "default_initial_delay")]
     pub initial_delay_seconds: i32,
@@ -558,6 +713,7 @@ fn default_failure_threshold() -> i32 {
 /// Deployment controller configuration
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(tag = "type", rename_all = "kebab-case")]
+#[allow(clippy::large_enum_variant)]
 pub enum DeploymentControllerSettings {
     /// Kubernetes deployment controller
     #[cfg(feature = "backend")]
@@ -620,8 +776,9 @@ pub enum DeploymentControllerSettings {
         auth_signin_url: String,
 
         /// Namespace format template for deployed applications
-        /// Template variables: {project_name}
+        /// Template variables: {project_name}, {team} (owning team, or "user")
         /// Example: "rise-{project_name}" → namespace "rise-myapp" for project "myapp"
+        /// Resolved once when a project is created; changing it keeps existing namespaces.
         /// Defaults to "rise-{project_name}"
         #[serde(default = "default_namespace_format")]
         namespace_format: String,
@@ -643,6 +800,15 @@ pub enum DeploymentControllerSettings {
         #[serde(default)]
         ingress_annotations: std::collections::HashMap<String, String>,
 
+        /// Ingress annotations projects may set via `[deploy.ingress.annotations]` in rise.toml
+        /// Entries match exactly, or by prefix when they end in `*`
+        /// Annotations Rise manages (auth, rewrite, HTTPS redirects) are always rejected
+        /// Allowing snippet annotations lets projects bypass ingress authentication
+        /// Defaults to the nginx proxy body size, timeout and buffering annotations
+        /// Example: ["nginx.ingress.kubernetes.io/proxy-*", "nginx.ingress.kubernetes.io/cors-*"]
+        #[serde(default = "default_allowed_ingress_annotations")]
+        allowed_ingress_annotations: Vec<String>,
+
         /// TLS secret name for primary ingress certificates
         /// If set, enables TLS on primary ingresses with this secret
         /// For custom domain TLS, see custom_domain_tls_mode and custom_domain_ingress_annotations
@@ -654,11 +820,18 @@ pub enum DeploymentControllerSettings {
         /// - "shared": All custom domains share ingress_tls_secret_name (requires it to be set)
         /// - "per-domain": Each custom domain gets its own tls-{domain} secret
         ///   (works with cert-manager when custom_domain_ingress_annotations are configured)
+        /// - "acme": Like "per-domain", and custom domain ingresses request certificates
+        ///   from the cert-manager ClusterIssuer set in acme_cluster_issuer
         ///
         /// Defaults to "per-domain"
         #[serde(default = "default_custom_domain_tls_mode")]
         custom_domain_tls_mode: CustomDomainTlsMode,
 
+        /// cert-manager ClusterIssuer for custom domain certificates (e.g. "letsencrypt-prod").
+        /// Required when custom_domain_tls_mode is "acme".
+        #[serde(default)]
+        acme_cluster_issuer: Option<String>,
+
         /// Annotations to apply ONLY to custom domain ingresses (not primary ingresses)
         /// Use this for cert-manager integration or other custom domain-specific configuration
         /// Example: {"cert-manager.io/cluster-issuer": "letsencrypt-prod"}
@@ -686,6 +859,15 @@ pub enum DeploymentControllerSettings {
         #[serde(default)]
         image_pull_secret_name: Option<String>,
 
+        /// Name of the imagePullSecret the controller creates and refreshes in each
+        /// project namespace when a registry provider requires pull credentials
+        ///
+        /// Ignored when `image_pull_secret_name` is set. Change it if the default name
+        /// is already taken or disallowed by cluster policy.
+        /// Defaults to "rise-registry-creds"
+        #[serde(default = "default_managed_image_pull_secret_name")]
+        managed_image_pull_secret_name: String,
+
         /// Access classes defining ingress authentication levels
         /// Key: access class identifier (e.g., "public", "private")
         /// Value: access class configuration (display info, ingress settings)
@@ -713,6 +895,13 @@ pub enum DeploymentControllerSettings {
         #[serde(default = "default_use_default_service_account_for_production")]
         use_default_service_account_for_production: bool,
 
+        /// Create the ServiceAccounts named in `[deploy.service_account]` in the
+        /// project namespace. When false, they must already exist (e.g. with the
+        /// IRSA or Workload Identity annotations set by the cluster operator).
+        /// Defaults to false.
+        #[serde(default)]
+        create_deployment_service_accounts: bool,
+
         /// NetworkPolicy configuration for deployed apps
         network_policy: NetworkPolicyConfig,
 
@@ -750,9 +939,290 @@ pub enum DeploymentControllerSettings {
         /// Defaults to "app.kubernetes.io/name=metacontroller-operator".
         #[serde(default)]
         metacontroller_pod_label_selector: Option<String>,
+
+        /// Maximum number of secret environment variables decrypted concurrently
+        /// while reconciling a deployment. Raising this speeds up reconciles for
+        /// secret-heavy apps with a remote encryption provider (e.g. AWS KMS).
+        /// Defaults to 8.
+        #[serde(default = "default_env_decrypt_concurrency")]
+        env_decrypt_concurrency: usize,
+
+        /// Number of old ReplicaSets kept per K8s Deployment (`revisionHistoryLimit`).
+        /// Rise rolls back by creating new deployments, so a small value avoids
+        /// piling up dead ReplicaSets on every pod template change. Defaults to 2.
+        #[serde(default = "default_revision_history_limit")]
+        revision_history_limit: i32,
+
+        /// Seconds pods get to finish in-flight requests after SIGTERM before they
+        /// are killed (`terminationGracePeriodSeconds`). Applies when a superseded or
+        /// stopped deployment is torn down. Defaults to the Kubernetes default (30s).
+        #[serde(default)]
+        termination_grace_period_secs: Option<u32>,
+
+        /// `minAvailable` of the PodDisruptionBudget created for deployments with
+        /// more than one replica, so node drains cannot evict all pods at once.
+        /// Capped at `replicas - 1` so drains are never blocked entirely.
+        /// Defaults to `replicas - 1`.
+        #[serde(default)]
+        pod_disruption_budget_min_available: Option<u32>,
+
+        /// Delete leftover ReplicaSets of deployments in terminal states
+        /// (Cancelled, Stopped, ...) during reconcile. Defaults to true.
+        #[serde(default = "default_true")]
+        cleanup_terminal_replica_sets: bool,
+
+        /// Periodically delete Deployments, ReplicaSets, PodDisruptionBudgets and env
+        /// Secrets labelled with a deployment ID that has no active deployment in the
+        /// database (e.g. left behind by a crash or an out-of-band database change).
+        /// Needs list/delete access to these kinds cluster-wide. Defaults to false.
+        #[serde(default)]
+        orphan_gc_enabled: bool,
+
+        /// Only log the resources the orphan GC would delete. Defaults to false.
+        #[serde(default)]
+        orphan_gc_dry_run: bool,
+
+        /// Seconds between orphan GC passes. Defaults to 600.
+        #[serde(default = "default_orphan_gc_interval_secs")]
+        orphan_gc_interval_secs: u64,
+
+        /// Seconds after which Metacontroller re-syncs a project while one of its
+        /// deployments is in flight (Pushed, Deploying, Cancelling, Terminating).
+        /// Settled projects only re-sync on the CompositeController's
+        /// `resyncPeriodSeconds`, which can then be raised to reduce API server
+        /// load. Defaults to 2.
+        #[serde(default = "default_in_flight_resync_secs")]
+        in_flight_resync_secs: f64,
+
+        /// Service type for deployments with `protocol = "tcp"`, which get no
+        /// Ingress. Defaults to ClusterIP (in-cluster access only).
+        #[serde(default)]
+        tcp_service_type: TcpServiceType,
+
+        /// Maximum number of deployments of a single project in Deploying at once.
+        /// Further deployments wait in Pushed until one finishes. Unlimited if not set.
+        #[serde(default)]
+        max_concurrent_per_project: Option<u32>,
+
+        /// Maximum number of deployments across all projects in Deploying at once.
+        /// Unlimited if not set.
+        #[serde(default)]
+        max_concurrent: Option<u32>,
+    },
+
+    /// AWS ECS (Fargate) deployment controller
+    ///
+    /// Each deployment runs as an ECS service with its own target group behind a
+    /// shared Application Load Balancer listener. Rise polls ECS to drive deployment
+    /// status, so no Metacontroller is needed.
+    #[cfg(feature = "backend")]
+    Ecs {
+        /// AWS region of the cluster, e.g. "eu-west-1"
+        region: String,
+
+        /// ECS cluster name or ARN
+        cluster: String,
+
+        /// Subnets the Fargate tasks are placed in
+        subnets: Vec<String>,
+
+        /// Security groups attached to the Fargate tasks
+        /// (must allow traffic from the load balancer on the application port)
+        security_groups: Vec<String>,
+
+        /// Assign public IPs to tasks (needed in public subnets without a NAT gateway)
+        #[serde(default)]
+        assign_public_ip: bool,
+
+        /// VPC in which target groups are created
+        vpc_id: String,
+
+        /// ARN of the ALB listener that receives a rule per deployment group
+        listener_arn: String,
+
+        /// Task execution role (pulls images from ECR and writes CloudWatch logs)
+        execution_role_arn: String,
+
+        /// Optional IAM role assumed by the application containers
+        #[serde(default)]
+        task_role_arn: Option<String>,
+
+        /// CloudWatch Logs group receiving container logs (must exist)
+        log_group: String,
+
+        /// Ingress URL template for the production (default) deployment group.
+        /// Same format as for the Kubernetes controller, e.g. "{project_name}.apps.rise.dev"
+        production_ingress_url_template: String,
+
+        /// Ingress URL template for staging (non-default) deployment groups
+        #[serde(default)]
+        staging_ingress_url_template: Option<String>,
+
+        /// URL scheme for generated URLs. Defaults to "https"
+        #[serde(default = "default_ingress_schema")]
+        ingress_schema: String,
+
+        /// Path used by target group health checks. Defaults to "/"
+        #[serde(default = "default_ecs_health_check_path")]
+        health_check_path: String,
+
+        /// Seconds between reconcile passes. Defaults to 10
+        #[serde(default = "default_ecs_reconcile_interval_secs")]
+        reconcile_interval_secs: u64,
+
+        /// Maximum number of secret environment variables decrypted concurrently
+        /// when registering a task definition. Defaults to 8.
+        #[serde(default = "default_env_decrypt_concurrency")]
+        env_decrypt_concurrency: usize,
+
+        /// Optional AWS access key ID (uses default credential chain if not set)
+        #[serde(default)]
+        access_key_id: Option<String>,
+
+        /// Optional AWS secret access key
+        #[serde(default)]
+        secret_access_key: Option<String>,
+
+        /// Maximum number of deployments of a single project in Deploying at once.
+        /// Further deployments wait in Pushed until one finishes. Unlimited if not set.
+        #[serde(default)]
+        max_concurrent_per_project: Option<u32>,
+
+        /// Maximum number of deployments across all projects in Deploying at once.
+        /// Unlimited if not set.
+        #[serde(default)]
+        max_concurrent: Option<u32>,
+    },
+
+    /// Deployment controller of a backend registered with
+    /// `DeploymentBackendRegistry::register` under its `type`.
+    /// All other fields are passed to the backend's factory as `config`.
+    #[cfg(feature = "backend")]
+    #[serde(skip)]
+    Custom {
+        backend_type: String,
+        config: serde_json::Map<String, serde_json::Value>,
     },
 }
 
+/// `type`s of the deployment controllers shipped with Rise
+const BUILTIN_DEPLOYMENT_CONTROLLER_TYPES: &[&str] = &["kubernetes", "ecs"];
+
+/// Deserialize `deployment_controller`, mapping types not shipped with Rise to
+/// [`DeploymentControllerSettings::Custom`] so that errors in the configuration of a
+/// built-in controller are still reported instead of falling back to a custom backend
+fn deserialize_deployment_controller<'de, D>(
+    deserializer: D,
+) -> Result<Option<DeploymentControllerSettings>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let Some(value) = Option::<serde_json::Value>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    let custom_type = value
+        .get("type")
+        .and_then(serde_json::Value::as_str)
+        .filter(|backend_type| !BUILTIN_DEPLOYMENT_CONTROLLER_TYPES.contains(backend_type))
+        .map(str::to_string);
+
+    match (custom_type, value) {
+        (Some(backend_type), serde_json::Value::Object(mut config)) => {
+            config.remove("type");
+            Ok(Some(DeploymentControllerSettings::Custom {
+                backend_type,
+                config,
+            }))
+        }
+        (_, value) => serde_ignored::deserialize(value, |path| {
+            tracing::warn!(
+                "Unknown configuration field in backend config: deployment_controller.{}",
+                path
+            );
+        })
+        .map(Some)
+        .map_err(serde::de::Error::custom),
+    }
+}
+
+/// Limits on how many deployments may be in Deploying at the same time
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DeploymentConcurrencyLimits {
+    pub per_project: Option<u32>,
+    pub global: Option<u32>,
+}
+
+impl DeploymentControllerSettings {
+    /// Deploying concurrency limits configured for this controller
+    pub fn concurrency_limits(&self) -> DeploymentConcurrencyLimits {
+        match self {
+            #[cfg(feature = "backend")]
+            DeploymentControllerSettings::Kubernetes {
+                max_concurrent_per_project,
+                max_concurrent,
+                ..
+            }
+            | DeploymentControllerSettings::Ecs {
+                max_concurrent_per_project,
+                max_concurrent,
+                ..
+            } => DeploymentConcurrencyLimits {
+                per_project: *max_concurrent_per_project,
+                global: *max_concurrent,
+            },
+            #[cfg(feature = "backend")]
+            DeploymentControllerSettings::Custom { config, .. } => {
+                let limit = |key: &str| {
+                    config
+                        .get(key)
+                        .and_then(serde_json::Value::as_u64)
+                        .and_then(|limit| u32::try_from(limit).ok())
+                };
+                DeploymentConcurrencyLimits {
+                    per_project: limit("max_concurrent_per_project"),
+                    global: limit("max_concurrent"),
+                }
+            }
+        }
+    }
+
+    /// Maximum number of secret environment variables decrypted concurrently
+    /// when rendering a deployment's workload (at least 1)
+    pub fn env_decrypt_concurrency(&self) -> usize {
+        let concurrency = match self {
+            #[cfg(feature = "backend")]
+            DeploymentControllerSettings::Kubernetes {
+                env_decrypt_concurrency,
+                ..
+            }
+            | DeploymentControllerSettings::Ecs {
+                env_decrypt_concurrency,
+                ..
+            } => *env_decrypt_concurrency,
+            #[cfg(feature = "backend")]
+            DeploymentControllerSettings::Custom { config, .. } => config
+                .get("env_decrypt_concurrency")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|concurrency| usize::try_from(concurrency).ok())
+                .unwrap_or_else(default_env_decrypt_concurrency),
+        };
+        concurrency.max(1)
+    }
+
+    /// The `type` tag of this controller, used to look up its backend factory
+    pub fn backend_type(&self) -> &str {
+        match self {
+            #[cfg(feature = "backend")]
+            DeploymentControllerSettings::Kubernetes { .. } => "kubernetes",
+            #[cfg(feature = "backend")]
+            DeploymentControllerSettings::Ecs { .. } => "ecs",
+            #[cfg(feature = "backend")]
+            DeploymentControllerSettings::Custom { backend_type, .. } => backend_type,
+        }
+    }
+}
+
 /// Registry provider configuration
 #[derive(Debug, Clone, Deserialize, JsonSchema)]
 #[serde(tag = "type", rename_all = "kebab-case")]
@@ -789,6 +1259,44 @@ pub enum RegistrySettings {
         #[serde(default)]
         client_registry_url: Option<String>,
     },
+    /// Google Artifact Registry — mints short-lived OAuth2 access tokens
+    Gar {
+        /// Artifact Registry location (e.g., "europe", "us-central1")
+        location: String,
+        /// GCP project ID hosting the repository
+        project_id: String,
+        /// Artifact Registry repository name (e.g., "rise")
+        repository: String,
+        /// Service-account JSON key contents. If unset, tokens are fetched from the
+        /// GKE metadata server (workload identity).
+        #[serde(default)]
+        credentials_json: Option<String>,
+    },
+    /// GitHub Container Registry — authenticates with a personal access token or
+    /// GitHub App installation tokens
+    Ghcr {
+        /// GitHub user or organization owning the packages (e.g., "my-org")
+        owner: String,
+        /// Personal access token with `write:packages` scope
+        #[serde(default)]
+        token: Option<String>,
+        /// Username paired with `token` (defaults to `owner`)
+        #[serde(default)]
+        username: Option<String>,
+        /// GitHub App ID. Set together with `installation_id` and `private_key`
+        /// instead of `token` to mint hourly installation tokens.
+        #[serde(default)]
+        app_id: Option<String>,
+        /// GitHub App installation ID for the owner
+        #[serde(default)]
+        installation_id: Option<String>,
+        /// GitHub App private key (PEM contents)
+        #[serde(default)]
+        private_key: Option<String>,
+        /// GitHub REST API URL (defaults to "https://api.github.com")
+        #[serde(default)]
+        github_api_url: Option<String>,
+    },
     /// GitLab container registry — mints scoped JWTs per deployment
     #[serde(rename = "gitlab")]
     GitLab {
@@ -811,6 +1319,38 @@ pub enum RegistrySettings {
         #[serde(default)]
         client_registry_url: Option<String>,
     },
+    /// Harbor registry — authenticates with a robot account
+    Harbor {
+        /// Harbor URL or host (e.g., "https://harbor.example.com")
+        url: String,
+        /// Robot account name (e.g., "robot$rise")
+        robot_username: String,
+        /// Robot account secret
+        robot_secret: String,
+        /// Harbor project holding the app repositories (e.g., "rise")
+        project: String,
+    },
+    /// Azure Container Registry — exchanges service principal tokens for ACR refresh
+    /// tokens, or authenticates with the admin user
+    Acr {
+        /// Registry name (e.g., "myregistry") or login server ("myregistry.azurecr.io")
+        registry: String,
+        /// Microsoft Entra tenant ID. Set together with `client_id` and `client_secret`.
+        #[serde(default)]
+        tenant_id: Option<String>,
+        /// Service principal application (client) ID, needs the AcrPush role
+        #[serde(default)]
+        client_id: Option<String>,
+        /// Service principal client secret
+        #[serde(default)]
+        client_secret: Option<String>,
+        /// Admin username, instead of a service principal
+        #[serde(default)]
+        username: Option<String>,
+        /// Admin password
+        #[serde(default)]
+        password: Option<String>,
+    },
 }
 
 /// Encryption provider configuration
@@ -840,6 +1380,47 @@ pub enum EncryptionSettings {
         #[allow(dead_code)]
         secret_access_key: Option<String>,
     },
+    /// HashiCorp Vault transit secrets engine
+    Vault {
+        /// Vault address (e.g., "https://vault.example.com:8200")
+        address: String,
+        /// Vault token; renewed in the background if it has a TTL
+        token: String,
+        /// Transit key name
+        key_name: String,
+        /// Optional Vault Enterprise namespace
+        #[serde(default)]
+        namespace: Option<String>,
+    },
+    /// Azure Key Vault key (envelope encryption via wrapKey/unwrapKey)
+    AzureKeyVault {
+        /// Vault URL (e.g., "https://my-vault.vault.azure.net")
+        vault_url: String,
+        /// Name of an RSA key in the vault
+        key_name: String,
+        /// Azure AD tenant ID (required for service principal authentication)
+        #[serde(default)]
+        tenant_id: Option<String>,
+        /// Service principal client ID, or the client ID of a user-assigned managed identity
+        #[serde(default)]
+        client_id: Option<String>,
+        /// Service principal client secret; if unset, a managed identity is used
+        #[serde(default)]
+        client_secret: Option<String>,
+    },
+    /// GCP Cloud KMS symmetric key (encrypt/decrypt)
+    GcpKms {
+        /// Crypto key resource name
+        /// (e.g., "projects/my-project/locations/global/keyRings/rise/cryptoKeys/secrets")
+        key_name: String,
+        /// Contents of a service account JSON key; if unset, the metadata server is used
+        /// (GKE workload identity or the VM's service account)
+        #[serde(default)]
+        credentials_json: Option<String>,
+    },
+    /// DEVELOPMENT ONLY: stores secrets unencrypted, prefixed with "insecure-plaintext:"
+    /// Lets secret env vars work locally without a key or KMS. Never use in production.
+    InsecurePlaintext,
 }
 
 impl Settings {
@@ -969,6 +1550,49 @@ impl Settings {
         Ok(())
     }
 
+    fn validate_auth_issuers(auth: &AuthSettings) -> Result<(), ConfigError> {
+        let name_re = regex::Regex::new(r"^[a-z0-9][a-z0-9_-]*$").map_err(|e| {
+            ConfigError::Message(format!("Failed to compile issuer name regex: {}", e))
+        })?;
+
+        for (name, issuer) in &auth.issuers {
+            if name == crate::server::auth::issuers::DEFAULT_ISSUER_NAME {
+                return Err(ConfigError::Message(format!(
+                    "auth.issuers cannot contain '{}'; it refers to the top-level auth issuer",
+                    name
+                )));
+            }
+
+            if !name_re.is_match(name) {
+                return Err(ConfigError::Message(format!(
+                    "auth.issuers name '{}' is invalid; use lowercase letters, numbers, '_' or '-'",
+                    name
+                )));
+            }
+
+            if issuer.issuer.trim().is_empty() || issuer.client_id.trim().is_empty() {
+                return Err(ConfigError::Message(format!(
+                    "auth.issuers '{}' requires issuer and client_id",
+                    name
+                )));
+            }
+
+            if issuer.allowed_email_domains.is_empty()
+                || issuer
+                    .allowed_email_domains
+                    .iter()
+                    .any(|domain| domain.trim().is_empty() || domain.contains('@'))
+            {
+                return Err(ConfigError::Message(format!(
+                    "auth.issuers '{}' requires allowed_email_domains (e.g. [\"example.com\"])",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn new() -> Result<Self, ConfigError> {
         let run_mode = env::var("RISE_CONFIG_RUN_MODE").unwrap_or_else(|_| "development".into());
         let config_dir = env::var("RISE_CONFIG_DIR").unwrap_or_else(|_| "config".into());
@@ -1042,18 +1666,47 @@ impl Settings {
             ));
         }
 
+        Self::validate_auth_issuers(&settings.auth)?;
+
         // Validate deployment controller settings if configured
         if let Some(DeploymentControllerSettings::Kubernetes {
             ref namespace_format,
+            ref managed_image_pull_secret_name,
             ref production_ingress_url_template,
             ref staging_ingress_url_template,
             ref environment_ingress_url_template,
             ref access_classes,
             ref extra_service_token_audiences,
+            ref deployment_constraints,
+            orphan_gc_interval_secs,
             ..
         }) = settings.deployment_controller
         {
+            deployment_constraints.max_expiration().map_err(|e| {
+                ConfigError::Message(format!(
+                    "Invalid deployment_constraints.max_expiration '{}': {}",
+                    deployment_constraints
+                        .max_expiration
+                        .as_deref()
+                        .unwrap_or_default(),
+                    e
+                ))
+            })?;
             Self::validate_format_string(namespace_format, "namespace_format", "{project_name}")?;
+            if orphan_gc_interval_secs == 0 {
+                return Err(ConfigError::Message(
+                    "orphan_gc_interval_secs must be greater than 0".to_string(),
+                ));
+            }
+            if !crate::server::deployment::resource_builder::is_valid_namespace_name(
+                managed_image_pull_secret_name,
+            ) {
+                return Err(ConfigError::Message(format!(
+                    "Invalid managed_image_pull_secret_name '{}': must be a valid Kubernetes \
+                     name (lowercase alphanumerics and '-', at most 63 characters)",
+                    managed_image_pull_secret_name
+                )));
+            }
             Self::validate_format_string(
                 production_ingress_url_template,
                 "production_ingress_url_template",
@@ -1263,6 +1916,85 @@ unknown_top_level: "also unknown"
         );
     }
 
+    #[test]
+    fn test_deployment_constraints_max_expiration() {
+        let constraints = |max: Option<&str>| DeploymentConstraints {
+            max_expiration: max.map(str::to_string),
+            ..Default::default()
+        };
+        assert_eq!(constraints(None).max_expiration(), Ok(None));
+        assert_eq!(
+            constraints(Some("30d")).max_expiration(),
+            Ok(Some(chrono::Duration::days(30)))
+        );
+        assert!(constraints(Some("a month")).max_expiration().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_custom_deployment_controller() {
+        let controller = deserialize_deployment_controller(serde_json::json!({
+            "type": "nomad",
+            "address": "http://nomad:4646",
+            "max_concurrent": 5,
+        }))
+        .unwrap()
+        .unwrap();
+        assert_eq!(controller.backend_type(), "nomad");
+        assert_eq!(controller.concurrency_limits().global, Some(5));
+        let DeploymentControllerSettings::Custom { config, .. } = controller else {
+            panic!("expected custom deployment_controller");
+        };
+        assert_eq!(config["address"], "http://nomad:4646");
+        assert!(!config.contains_key("type"));
+    }
+
+    #[test]
+    fn test_deserialize_invalid_builtin_deployment_controller() {
+        let err = deserialize_deployment_controller(serde_json::json!({
+            "type": "kubernetes",
+            "production_ingress_url_template": "{project_name}.apps.example.com",
+        }))
+        .unwrap_err();
+        assert!(err.to_string().contains("missing field"), "{}", err);
+    }
+
+    #[test]
+    fn test_ecs_env_decrypt_concurrency() {
+        let controller = |extra: serde_json::Value| {
+            let mut value = serde_json::json!({
+                "type": "ecs",
+                "region": "eu-west-1",
+                "cluster": "rise-apps",
+                "subnets": ["subnet-0123"],
+                "security_groups": ["sg-0123"],
+                "vpc_id": "vpc-0123",
+                "listener_arn": "arn:aws:elasticloadbalancing:eu-west-1:123456789012:listener/app/rise/1/2",
+                "execution_role_arn": "arn:aws:iam::123456789012:role/rise-task-execution",
+                "log_group": "/rise/apps",
+                "production_ingress_url_template": "{project_name}.apps.example.com",
+            });
+            value
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            deserialize_deployment_controller(value).unwrap().unwrap()
+        };
+        assert_eq!(
+            controller(serde_json::json!({})).env_decrypt_concurrency(),
+            8
+        );
+        assert_eq!(
+            controller(serde_json::json!({ "env_decrypt_concurrency": 16 }))
+                .env_decrypt_concurrency(),
+            16
+        );
+        assert_eq!(
+            controller(serde_json::json!({ "env_decrypt_concurrency": 0 }))
+                .env_decrypt_concurrency(),
+            1
+        );
+    }
+
     #[test]
     fn test_validate_extra_service_token_audiences_accepts_empty_map() {
         let audiences = std::collections::HashMap::new();
@@ -1285,6 +2017,52 @@ unknown_top_level: "also unknown"
             .contains("extra_service_token_audiences token name 'vault/token' is invalid"));
     }
 
+    #[test]
+    fn test_validate_auth_issuers() {
+        let auth = |issuers: serde_json::Value| -> AuthSettings {
+            serde_json::from_value(serde_json::json!({
+                "issuer": "https://login.example.com",
+                "client_id": "rise",
+                "client_secret": "secret",
+                "issuers": issuers,
+            }))
+            .unwrap()
+        };
+        let issuer = serde_json::json!({
+            "issuer": "https://github.example.com",
+            "client_id": "rise-contractors",
+            "client_secret": "secret",
+            "allowed_email_domains": ["contractors.example.com"],
+        });
+
+        assert!(Settings::validate_auth_issuers(&auth(serde_json::json!({}))).is_ok());
+        assert!(
+            Settings::validate_auth_issuers(&auth(serde_json::json!({ "github": issuer }))).is_ok()
+        );
+        assert!(
+            Settings::validate_auth_issuers(&auth(serde_json::json!({ "default": issuer })))
+                .is_err()
+        );
+        assert!(
+            Settings::validate_auth_issuers(&auth(serde_json::json!({ "Git Hub": issuer })))
+                .is_err()
+        );
+
+        // Secondary issuers must be limited to the email domains they own
+        for domains in [
+            serde_json::json!([]),
+            serde_json::json!([""]),
+            serde_json::json!(["@example.com"]),
+        ] {
+            let mut unrestricted = issuer.clone();
+            unrestricted["allowed_email_domains"] = domains;
+            assert!(Settings::validate_auth_issuers(&auth(
+                serde_json::json!({ "github": unrestricted })
+            ))
+            .is_err());
+        }
+    }
+
     #[test]
     fn test_settings_load_with_extra_service_token_audiences() {
         use std::fs;
@@ -1498,6 +2276,27 @@ pub enum ExtensionProviderConfig {
         #[serde(default = "default_refresh_token_validity_seconds")]
         refresh_token_validity_seconds: i64,
     },
+
+    #[cfg(feature = "backend")]
+    #[serde(rename = "snowflake-secrets")]
+    SnowflakeSecrets {
+        /// Snowflake account identifier (e.g., "myorg.us-east-1")
+        account: String,
+        /// Snowflake user with SELECT privilege on the secrets table
+        user: String,
+        /// Snowflake role to use
+        #[serde(default)]
+        role: Option<String>,
+        /// Snowflake warehouse to use for queries
+        #[serde(default)]
+        warehouse: Option<String>,
+        /// Authentication configuration (password, private key, or JWT)
+        #[serde(flatten)]
+        auth: SnowflakeAuth,
+        /// Table holding the secrets, with PROJECT_ID, NAME and VALUE columns
+        /// (e.g., "RISE.SECRETS.APP_SECRETS")
+        secrets_table: String,
+    },
 }
 
 #[allow(dead_code)]
@@ -1,5 +1,6 @@
 use crate::server::auth::{
     cookie_helpers::CookieSettings,
+    issuers::LoginIssuers,
     jwt::JwtValidator,
     jwt_signer::JwtSigner,
     oauth::OAuthClient,
@@ -12,8 +13,10 @@ use crate::server::registry::{
 
 #[cfg(feature = "backend")]
 use crate::server::registry::{
-    models::{EcrConfig, GitLabRegistryConfig},
-    providers::{EcrProvider, GitLabRegistryProvider},
+    models::{AcrConfig, EcrConfig, GarConfig, GhcrConfig, GitLabRegistryConfig, HarborConfig},
+    providers::{
+        AcrProvider, EcrProvider, GarProvider, GhcrProvider, GitLabRegistryProvider, HarborProvider,
+    },
 };
 use crate::server::settings::{
     AuthSettings, EncryptionSettings, RegistrySettings, ServerSettings, Settings,
@@ -24,9 +27,6 @@ use sqlx::PgPool;
 use std::sync::Arc;
 use std::time::Duration;
 
-#[cfg(feature = "backend")]
-use crate::server::deployment::controller::DeploymentBackend;
-
 /// Minimal state for controllers - database access and encryption
 #[derive(Clone)]
 pub struct ControllerState {
@@ -42,6 +42,8 @@ pub struct AppState {
     pub jwt_validator: Arc<JwtValidator>,
     pub jwt_signer: Arc<JwtSigner>,
     pub oauth_client: Arc<OAuthClient>,
+    /// Issuers users can log in with from the CLI (default issuer first)
+    pub login_issuers: Arc<LoginIssuers>,
     pub registry_provider: Arc<dyn RegistryProvider>,
     pub oci_client: Arc<crate::server::oci::OciClient>,
     pub admin_users: Arc<Vec<String>>,
@@ -82,43 +84,100 @@ pub struct AppState {
     /// Platform-level constraints for deployment resources
     #[cfg(feature = "backend")]
     pub deployment_constraints: Option<crate::server::settings::DeploymentConstraints>,
+    /// Maximum number of secret env vars decrypted concurrently during reconcile
+    #[cfg(feature = "backend")]
+    pub env_decrypt_concurrency: usize,
+    /// Limits on deployments in Deploying at the same time
+    #[cfg(feature = "backend")]
+    pub deployment_concurrency: crate::server::settings::DeploymentConcurrencyLimits,
 }
 
 /// Initialize encryption provider from settings
+///
+/// The primary provider is wrapped in a `FallbackEncryptionProvider` so secrets written
+/// by legacy providers (and tagged ciphertexts from a past migration) stay readable.
 async fn init_encryption_provider(
     encryption_settings: Option<&EncryptionSettings>,
+    legacy_encryption_settings: &[EncryptionSettings],
 ) -> Result<Option<Arc<dyn EncryptionProvider>>> {
-    if let Some(encryption_config) = encryption_settings {
-        match encryption_config {
-            EncryptionSettings::Local { key } => {
-                use crate::server::encryption::providers::local::LocalEncryptionProvider;
-                let provider = LocalEncryptionProvider::new(key)
-                    .context("Failed to initialize local encryption provider")?;
+    use crate::server::encryption::providers::fallback::FallbackEncryptionProvider;
+
+    let Some(encryption_config) = encryption_settings else {
+        if !legacy_encryption_settings.is_empty() {
+            anyhow::bail!(
+                "legacy_encryption is configured but no primary encryption provider is set"
+            );
+        }
+        tracing::info!("No encryption provider configured - secret environment variables will not be available");
+        return Ok(None);
+    };
+
+    let primary = build_encryption_provider(encryption_config, true).await?;
+
+    let mut legacy = Vec::with_capacity(legacy_encryption_settings.len());
+    for legacy_config in legacy_encryption_settings {
+        // Legacy keys may be restricted to decryption, so skip the round-trip test
+        let provider = build_encryption_provider(legacy_config, false)
+            .await
+            .context("Failed to initialize legacy encryption provider")?;
+        legacy.push((FallbackEncryptionProvider::tag_for(legacy_config), provider));
+    }
+
+    let provider = FallbackEncryptionProvider::new(
+        (
+            FallbackEncryptionProvider::tag_for(encryption_config),
+            primary,
+        ),
+        legacy,
+    )?;
+    if !legacy_encryption_settings.is_empty() {
+        tracing::info!(
+            "✓ Encryption provider fallback enabled with {} legacy provider(s)",
+            legacy_encryption_settings.len()
+        );
+    }
+
+    Ok(Some(Arc::new(provider)))
+}
 
+/// Build a single encryption provider, optionally validating it with a round-trip test
+async fn build_encryption_provider(
+    encryption_config: &EncryptionSettings,
+    validate: bool,
+) -> Result<Arc<dyn EncryptionProvider>> {
+    match encryption_config {
+        EncryptionSettings::Local { key } => {
+            use crate::server::encryption::providers::local::LocalEncryptionProvider;
+            let provider = LocalEncryptionProvider::new(key)
+                .context("Failed to initialize local encryption provider")?;
+
+            if validate {
                 // Test encryption/decryption at startup
                 tracing::info!("Testing local encryption provider...");
                 test_encryption_provider(&provider).await?;
                 tracing::info!("✓ Local AES-256-GCM encryption provider initialized and validated");
-
-                Ok(Some(Arc::new(provider)))
             }
-            #[cfg(feature = "backend")]
-            EncryptionSettings::AwsKms {
+
+            Ok(Arc::new(provider))
+        }
+        #[cfg(feature = "backend")]
+        EncryptionSettings::AwsKms {
+            region,
+            key_id,
+            access_key_id,
+            secret_access_key,
+        } => {
+            use crate::server::encryption::providers::aws_kms::AwsKmsEncryptionProvider;
+            let provider = AwsKmsEncryptionProvider::new(
                 region,
-                key_id,
-                access_key_id,
-                secret_access_key,
-            } => {
-                use crate::server::encryption::providers::aws_kms::AwsKmsEncryptionProvider;
-                let provider = AwsKmsEncryptionProvider::new(
-                    region,
-                    key_id.clone(),
-                    access_key_id.clone(),
-                    secret_access_key.clone(),
-                )
-                .await
-                .context("Failed to initialize AWS KMS encryption provider")?;
+                key_id.clone(),
+                access_key_id.clone(),
+                secret_access_key.clone(),
+            )
+            .await
+            .context("Failed to initialize AWS KMS encryption provider")?;
 
+            if validate {
                 // Test encryption/decryption at startup
                 tracing::info!("Testing AWS KMS encryption provider with key {}...", key_id);
                 test_encryption_provider(&provider).await.with_context(|| {
@@ -132,21 +191,153 @@ async fn init_encryption_provider(
                     )
                 })?;
                 tracing::info!("✓ AWS KMS encryption provider initialized and validated");
+            }
 
-                Ok(Some(Arc::new(provider)))
+            Ok(Arc::new(provider))
+        }
+        #[cfg(not(feature = "backend"))]
+        EncryptionSettings::AwsKms { key_id, .. } => {
+            anyhow::bail!(
+                "AWS KMS encryption is configured (key: {}) but the 'aws' feature is not enabled. \
+                 Please rebuild with --features aws or use a pre-built binary with AWS support.",
+                key_id
+            )
+        }
+        #[cfg(feature = "backend")]
+        EncryptionSettings::Vault {
+            address,
+            token,
+            key_name,
+            namespace,
+        } => {
+            use crate::server::encryption::providers::vault::VaultEncryptionProvider;
+            let provider = VaultEncryptionProvider::new(
+                address.clone(),
+                token.clone(),
+                key_name.clone(),
+                namespace.clone(),
+            )
+            .await
+            .context("Failed to initialize Vault encryption provider")?;
+
+            if validate {
+                // Test encryption/decryption at startup
+                tracing::info!("Testing Vault encryption provider with key {}...", key_name);
+                test_encryption_provider(&provider).await.with_context(|| {
+                    format!(
+                        "Vault provider initialized but encryption test failed. \
+                         Please verify: 1) Vault at '{}' is reachable and unsealed, \
+                         2) the transit key '{}' exists, \
+                         3) the token's policy allows transit/encrypt and transit/decrypt",
+                        address, key_name
+                    )
+                })?;
+                tracing::info!("✓ Vault encryption provider initialized and validated");
             }
-            #[cfg(not(feature = "backend"))]
-            EncryptionSettings::AwsKms { key_id, .. } => {
-                anyhow::bail!(
-                    "AWS KMS encryption is configured (key: {}) but the 'aws' feature is not enabled. \
-                     Please rebuild with --features aws or use a pre-built binary with AWS support.",
-                    key_id
-                )
+
+            Ok(Arc::new(provider))
+        }
+        #[cfg(not(feature = "backend"))]
+        EncryptionSettings::Vault { address, .. } => {
+            anyhow::bail!(
+                "Vault encryption is configured ({}) but the 'backend' feature is not enabled.",
+                address
+            )
+        }
+        #[cfg(feature = "backend")]
+        EncryptionSettings::AzureKeyVault {
+            vault_url,
+            key_name,
+            tenant_id,
+            client_id,
+            client_secret,
+        } => {
+            use crate::server::encryption::providers::azure_key_vault::AzureKeyVaultEncryptionProvider;
+            let provider = AzureKeyVaultEncryptionProvider::new(
+                vault_url.clone(),
+                key_name.clone(),
+                tenant_id.clone(),
+                client_id.clone(),
+                client_secret.clone(),
+            )
+            .context("Failed to initialize Azure Key Vault encryption provider")?;
+
+            if validate {
+                // Test encryption/decryption at startup
+                tracing::info!(
+                    "Testing Azure Key Vault encryption provider with key {}...",
+                    key_name
+                );
+                test_encryption_provider(&provider).await.with_context(|| {
+                    format!(
+                        "Azure Key Vault provider initialized but encryption test failed. \
+                         Please verify: 1) Vault '{}' is reachable, \
+                         2) the RSA key '{}' exists and is enabled, \
+                         3) the identity has the wrapKey and unwrapKey permissions \
+                         (e.g. the 'Key Vault Crypto User' role)",
+                        vault_url, key_name
+                    )
+                })?;
+                tracing::info!("✓ Azure Key Vault encryption provider initialized and validated");
             }
+
+            Ok(Arc::new(provider))
+        }
+        #[cfg(not(feature = "backend"))]
+        EncryptionSettings::AzureKeyVault { vault_url, .. } => {
+            anyhow::bail!(
+                "Azure Key Vault encryption is configured ({}) but the 'backend' feature is not enabled.",
+                vault_url
+            )
+        }
+        #[cfg(feature = "backend")]
+        EncryptionSettings::GcpKms {
+            key_name,
+            credentials_json,
+        } => {
+            use crate::server::encryption::providers::gcp_kms::GcpKmsEncryptionProvider;
+            let provider =
+                GcpKmsEncryptionProvider::new(key_name.clone(), credentials_json.clone())
+                    .context("Failed to initialize GCP KMS encryption provider")?;
+
+            if validate {
+                // Test encryption/decryption at startup
+                tracing::info!(
+                    "Testing GCP KMS encryption provider with key {}...",
+                    key_name
+                );
+                test_encryption_provider(&provider).await.with_context(|| {
+                    format!(
+                        "GCP KMS provider initialized but encryption test failed. \
+                         Please verify: 1) the key '{}' exists and is a symmetric \
+                         ENCRYPT_DECRYPT key with an enabled primary version, \
+                         2) GCP credentials are available (credentials_json or workload identity), \
+                         3) the service account has the 'Cloud KMS CryptoKey Encrypter/Decrypter' role",
+                        key_name
+                    )
+                })?;
+                tracing::info!("✓ GCP KMS encryption provider initialized and validated");
+            }
+
+            Ok(Arc::new(provider))
+        }
+        #[cfg(not(feature = "backend"))]
+        EncryptionSettings::GcpKms { key_name, .. } => {
+            anyhow::bail!(
+                "GCP KMS encryption is configured (key: {}) but the 'backend' feature is not enabled.",
+                key_name
+            )
+        }
+        EncryptionSettings::InsecurePlaintext => {
+            use crate::server::encryption::providers::insecure_plaintext::InsecurePlaintextEncryptionProvider;
+            tracing::warn!(
+                "⚠ INSECURE: the insecure-plaintext encryption provider is enabled. Secret \
+                 environment variables are stored UNENCRYPTED. Use it for local development \
+                 only, never in production!"
+            );
+
+            Ok(Arc::new(InsecurePlaintextEncryptionProvider))
         }
-    } else {
-        tracing::info!("No encryption provider configured - secret environment variables will not be available");
-        Ok(None)
     }
 }
 
@@ -171,28 +362,6 @@ async fn test_encryption_provider(provider: &dyn EncryptionProvider) -> Result<(
     Ok(())
 }
 
-/// Initialize Kubernetes deployment backend from settings
-///
-/// Creates a slim `KubernetesBackend` that wraps `ResourceBuilder` and a kube client.
-/// Provides URL computation, log streaming, and environment cleanup.
-/// All reconciliation is handled by the Metacontroller sync webhook.
-#[cfg(feature = "backend")]
-async fn init_kubernetes_backend(
-    resource_builder: Arc<crate::server::deployment::resource_builder::ResourceBuilder>,
-    kube_client: kube::Client,
-    db_pool: PgPool,
-) -> Result<Arc<dyn DeploymentBackend>> {
-    use crate::server::deployment::controller::KubernetesBackend;
-
-    let backend = KubernetesBackend::new(kube_client, resource_builder, db_pool);
-
-    // Test Kubernetes API connection
-    backend.test_connection().await?;
-    tracing::info!("Kubernetes deployment backend initialized and connection tested");
-
-    Ok(Arc::new(backend) as Arc<dyn DeploymentBackend>)
-}
-
 impl AppState {
     /// Check if a user is an admin (case-insensitive email match)
     pub fn is_admin(&self, user_email: &str) -> bool {
@@ -210,8 +379,12 @@ impl AppState {
         Ok(())
     }
 
-    /// Initialize full state for HTTP server
-    pub async fn new(settings: &Settings) -> Result<Self> {
+    /// Initialize full state for HTTP server, creating the deployment backend
+    /// configured in `deployment_controller.type` from `backends`
+    pub async fn with_backends(
+        settings: &Settings,
+        backends: crate::server::deployment::controller::DeploymentBackendRegistry,
+    ) -> Result<Self> {
         tracing::info!("Initializing AppState for HTTP server");
 
         // Connect to PostgreSQL with server-optimized pool size
@@ -258,6 +431,10 @@ impl AppState {
             .await?,
         );
 
+        // Initialize additional login issuers (`auth.issuers`)
+        let login_issuers =
+            Arc::new(LoginIssuers::new(&settings.auth, Arc::clone(&oauth_client)).await?);
+
         // Initialize registry provider (required for server operation)
         let registry_provider: Arc<dyn RegistryProvider> = match &settings.registry {
             Some(registry_config) => match registry_config {
@@ -313,6 +490,68 @@ impl AppState {
                     Arc::new(provider)
                 }
                 #[cfg(feature = "backend")]
+                RegistrySettings::Gar {
+                    location,
+                    project_id,
+                    repository,
+                    credentials_json,
+                } => {
+                    let gar_config = GarConfig {
+                        location: location.clone(),
+                        project_id: project_id.clone(),
+                        repository: repository.clone(),
+                        credentials_json: credentials_json.clone(),
+                    };
+                    let provider = GarProvider::new(gar_config)
+                        .context("Failed to initialize GAR registry provider")?;
+                    tracing::info!(
+                        "Initialized GAR registry provider at {}",
+                        provider.registry_url()
+                    );
+                    Arc::new(provider)
+                }
+                #[cfg(not(feature = "backend"))]
+                RegistrySettings::Gar { project_id, .. } => {
+                    anyhow::bail!(
+                        "GAR registry is configured (project: {}) but the 'backend' feature is not enabled.",
+                        project_id
+                    )
+                }
+                #[cfg(feature = "backend")]
+                RegistrySettings::Ghcr {
+                    owner,
+                    token,
+                    username,
+                    app_id,
+                    installation_id,
+                    private_key,
+                    github_api_url,
+                } => {
+                    let ghcr_config = GhcrConfig {
+                        owner: owner.clone(),
+                        token: token.clone(),
+                        username: username.clone(),
+                        app_id: app_id.clone(),
+                        installation_id: installation_id.clone(),
+                        private_key: private_key.clone(),
+                        github_api_url: github_api_url.clone(),
+                    };
+                    let provider = GhcrProvider::new(ghcr_config)
+                        .context("Failed to initialize GHCR registry provider")?;
+                    tracing::info!(
+                        "Initialized GHCR registry provider at {}",
+                        provider.registry_url()
+                    );
+                    Arc::new(provider)
+                }
+                #[cfg(not(feature = "backend"))]
+                RegistrySettings::Ghcr { owner, .. } => {
+                    anyhow::bail!(
+                        "GHCR registry is configured (owner: {}) but the 'backend' feature is not enabled.",
+                        owner
+                    )
+                }
+                #[cfg(feature = "backend")]
                 RegistrySettings::GitLab {
                     gitlab_url,
                     registry_url,
@@ -343,18 +582,81 @@ impl AppState {
                         registry_url
                     )
                 }
+                #[cfg(feature = "backend")]
+                RegistrySettings::Harbor {
+                    url,
+                    robot_username,
+                    robot_secret,
+                    project,
+                } => {
+                    let harbor_config = HarborConfig {
+                        url: url.clone(),
+                        robot_username: robot_username.clone(),
+                        robot_secret: robot_secret.clone(),
+                        project: project.clone(),
+                    };
+                    let provider = HarborProvider::new(harbor_config)
+                        .context("Failed to initialize Harbor registry provider")?;
+                    tracing::info!(
+                        "Initialized Harbor registry provider at {}",
+                        provider.registry_url()
+                    );
+                    Arc::new(provider)
+                }
+                #[cfg(not(feature = "backend"))]
+                RegistrySettings::Harbor { url, .. } => {
+                    anyhow::bail!(
+                        "Harbor registry is configured ({}) but the 'backend' feature is not enabled.",
+                        url
+                    )
+                }
+                #[cfg(feature = "backend")]
+                RegistrySettings::Acr {
+                    registry,
+                    tenant_id,
+                    client_id,
+                    client_secret,
+                    username,
+                    password,
+                } => {
+                    let acr_config = AcrConfig {
+                        registry: registry.clone(),
+                        tenant_id: tenant_id.clone(),
+                        client_id: client_id.clone(),
+                        client_secret: client_secret.clone(),
+                        username: username.clone(),
+                        password: password.clone(),
+                    };
+                    let provider = AcrProvider::new(acr_config)
+                        .context("Failed to initialize ACR registry provider")?;
+                    tracing::info!(
+                        "Initialized ACR registry provider at {}",
+                        provider.registry_url()
+                    );
+                    Arc::new(provider)
+                }
+                #[cfg(not(feature = "backend"))]
+                RegistrySettings::Acr { registry, .. } => {
+                    anyhow::bail!(
+                        "ACR registry is configured ({}) but the 'backend' feature is not enabled.",
+                        registry
+                    )
+                }
             },
             None => {
                 anyhow::bail!(
                     "Registry provider is required for server operation. \
-                     Please configure a registry in settings (ECR, OCI client-auth, or GitLab)"
+                     Please configure a registry in settings (ECR, GAR, GHCR, OCI client-auth, GitLab, Harbor, or ACR)"
                 )
             }
         };
 
         // Initialize OCI client for direct registry interaction
         let oci_client = Arc::new(
-            crate::server::oci::OciClient::new().context("Failed to initialize OCI client")?,
+            crate::server::oci::OciClient::new(std::time::Duration::from_secs(
+                settings.server.image_digest_cache_ttl_secs,
+            ))
+            .context("Failed to initialize OCI client")?,
         );
         tracing::info!("Initialized OCI client for registry digest resolution");
 
@@ -443,7 +745,9 @@ impl AppState {
         }
 
         // Initialize encryption provider
-        let encryption_provider = init_encryption_provider(settings.encryption.as_ref()).await?;
+        let encryption_provider =
+            init_encryption_provider(settings.encryption.as_ref(), &settings.legacy_encryption)
+                .await?;
 
         // Initialize deployment backend
         #[cfg(not(feature = "backend"))]
@@ -476,15 +780,19 @@ impl AppState {
                 namespace_labels,
                 namespace_annotations,
                 ingress_annotations,
+                allowed_ingress_annotations,
                 ingress_tls_secret_name,
                 custom_domain_tls_mode,
+                acme_cluster_issuer,
                 custom_domain_ingress_annotations,
                 node_selector,
                 image_pull_secret_name,
+                managed_image_pull_secret_name,
                 access_classes,
                 host_aliases,
                 extra_service_token_audiences,
                 use_default_service_account_for_production,
+                create_deployment_service_accounts,
                 network_policy,
                 pod_security_enabled,
                 deployment_defaults,
@@ -494,6 +802,12 @@ impl AppState {
                 metacontroller_pod_namespace,
                 metacontroller_pod_label_selector,
                 namespace_format,
+                revision_history_limit,
+                termination_grace_period_secs,
+                pod_disruption_budget_min_available,
+                cleanup_terminal_replica_sets,
+                in_flight_resync_secs,
+                tcp_service_type,
                 ..
             }) = &settings.deployment_controller
             {
@@ -523,6 +837,17 @@ impl AppState {
                     .filter_map(|(k, v)| v.as_ref().map(|ac| (k.clone(), ac.clone())))
                     .collect();
 
+                if *custom_domain_tls_mode == crate::server::settings::CustomDomainTlsMode::Acme
+                    && acme_cluster_issuer
+                        .as_deref()
+                        .is_none_or(|issuer| issuer.trim().is_empty())
+                {
+                    anyhow::bail!(
+                        "kubernetes.custom_domain_tls_mode 'acme' requires \
+                         kubernetes.acme_cluster_issuer to be set"
+                    );
+                }
+
                 let rb = ResourceBuilder {
                     production_ingress_url_template: production_ingress_url_template.clone(),
                     staging_ingress_url_template: staging_ingress_url_template.clone(),
@@ -536,20 +861,30 @@ impl AppState {
                     namespace_labels: namespace_labels.clone(),
                     namespace_annotations: namespace_annotations.clone(),
                     ingress_annotations: ingress_annotations.clone(),
+                    allowed_ingress_annotations: allowed_ingress_annotations.clone(),
                     ingress_tls_secret_name: ingress_tls_secret_name.clone(),
                     custom_domain_tls_mode: custom_domain_tls_mode.clone(),
+                    acme_cluster_issuer: acme_cluster_issuer.clone(),
                     custom_domain_ingress_annotations: custom_domain_ingress_annotations.clone(),
                     node_selector: node_selector.clone(),
                     image_pull_secret_name: image_pull_secret_name.clone(),
+                    managed_image_pull_secret_name: managed_image_pull_secret_name.clone(),
                     access_classes: filtered_access_classes,
                     host_aliases: host_aliases.clone(),
                     extra_service_token_audiences: extra_service_token_audiences.clone(),
                     use_default_service_account_for_production:
                         *use_default_service_account_for_production,
+                    create_deployment_service_accounts: *create_deployment_service_accounts,
                     network_policy: network_policy.clone(),
                     pod_security_enabled: *pod_security_enabled,
                     health_probes: health_probes.clone(),
                     namespace_format: namespace_format.clone(),
+                    revision_history_limit: *revision_history_limit,
+                    termination_grace_period_secs: *termination_grace_period_secs,
+                    pod_disruption_budget_min_available: *pod_disruption_budget_min_available,
+                    cleanup_terminal_replica_sets: *cleanup_terminal_replica_sets,
+                    in_flight_resync_secs: *in_flight_resync_secs,
+                    tcp_service_type: *tcp_service_type,
                 };
 
                 let run_mode =
@@ -591,16 +926,21 @@ impl AppState {
             }
         };
 
-        // Initialize deployment backend (slim wrapper around ResourceBuilder + kube client)
+        // Initialize deployment backend by looking up the configured controller type
         #[cfg(feature = "backend")]
         let deployment_backend = {
-            let rb = resource_builder.clone().ok_or_else(|| {
-                anyhow::anyhow!("Deployment controller not configured. Please add deployment_controller configuration with type: kubernetes")
+            let controller_settings = settings.deployment_controller.as_ref().ok_or_else(|| {
+                anyhow::anyhow!("Deployment controller not configured. Please add deployment_controller configuration with type: kubernetes or ecs")
             })?;
-            let kc = webhook_kube_client
-                .clone()
-                .ok_or_else(|| anyhow::anyhow!("Kubernetes client not initialized"))?;
-            init_kubernetes_backend(rb, kc, db_pool.clone()).await?
+            let ctx = crate::server::deployment::controller::BackendContext {
+                settings: controller_settings,
+                db_pool: db_pool.clone(),
+                resource_builder: resource_builder.clone(),
+                kube_client: webhook_kube_client.clone(),
+            };
+            backends
+                .create(controller_settings.backend_type(), &ctx)
+                .await?
         };
 
         // Initialize extension registry
@@ -774,6 +1114,53 @@ impl AppState {
             }
         }
 
+        // Register Snowflake secrets provider (if configured)
+        #[cfg(feature = "backend")]
+        if let Some(ref extensions_config) = settings.extensions {
+            for provider_config in &extensions_config.providers {
+                if let crate::server::settings::ExtensionProviderConfig::SnowflakeSecrets {
+                    account,
+                    user,
+                    role,
+                    warehouse,
+                    auth,
+                    secrets_table,
+                } = provider_config
+                {
+                    tracing::info!("Initializing Snowflake secrets provider");
+
+                    let snowflake_secrets_provider =
+                        crate::server::extensions::providers::snowflake_secrets::SnowflakeSecretsProvider::new(
+                            crate::server::extensions::providers::snowflake_secrets::SnowflakeSecretsProviderConfig {
+                                db_pool: db_pool.clone(),
+                                encryption_provider: encryption_provider.clone()
+                                    .ok_or_else(|| anyhow::anyhow!("Encryption provider required for Snowflake secrets provider"))?,
+                                account: account.clone(),
+                                user: user.clone(),
+                                role: role.clone(),
+                                warehouse: warehouse.clone(),
+                                auth: auth.clone(),
+                                secrets_table: secrets_table.clone(),
+                            },
+                        )?;
+
+                    // Validate access to the secrets table during startup - fail fast if invalid
+                    snowflake_secrets_provider
+                        .validate_credentials()
+                        .await
+                        .context(
+                            "Failed to validate Snowflake secrets configuration during startup",
+                        )?;
+
+                    let snowflake_secrets_arc: Arc<dyn crate::server::extensions::Extension> =
+                        Arc::new(snowflake_secrets_provider);
+                    extension_registry.register_type(snowflake_secrets_arc.clone());
+                    snowflake_secrets_arc.start();
+                    tracing::info!("Snowflake secrets provider initialized and started");
+                }
+            }
+        }
+
         let extension_registry = Arc::new(extension_registry);
 
         // Initialize encrypt endpoint rate limiter (100 requests/hour per user)
@@ -799,24 +1186,35 @@ impl AppState {
         // Extract access_classes from deployment controller settings
         // Filter out null values (used to remove inherited access classes)
         let (access_classes, production_ingress_url_template, staging_ingress_url_template) =
-            if let Some(crate::server::settings::DeploymentControllerSettings::Kubernetes {
-                access_classes,
-                production_ingress_url_template,
-                staging_ingress_url_template,
-                ..
-            }) = &settings.deployment_controller
-            {
-                let filtered: std::collections::HashMap<_, _> = access_classes
-                    .iter()
-                    .filter_map(|(k, v)| v.as_ref().map(|ac| (k.clone(), ac.clone())))
-                    .collect();
-                (
-                    Arc::new(filtered),
+            match &settings.deployment_controller {
+                #[cfg(feature = "backend")]
+                Some(crate::server::settings::DeploymentControllerSettings::Kubernetes {
+                    access_classes,
+                    production_ingress_url_template,
+                    staging_ingress_url_template,
+                    ..
+                }) => {
+                    let filtered: std::collections::HashMap<_, _> = access_classes
+                        .iter()
+                        .filter_map(|(k, v)| v.as_ref().map(|ac| (k.clone(), ac.clone())))
+                        .collect();
+                    (
+                        Arc::new(filtered),
+                        Some(production_ingress_url_template.clone()),
+                        staging_ingress_url_template.clone(),
+                    )
+                }
+                #[cfg(feature = "backend")]
+                Some(crate::server::settings::DeploymentControllerSettings::Ecs {
+                    production_ingress_url_template,
+                    staging_ingress_url_template,
+                    ..
+                }) => (
+                    Arc::new(std::collections::HashMap::new()),
                     Some(production_ingress_url_template.clone()),
                     staging_ingress_url_template.clone(),
-                )
-            } else {
-                (Arc::new(std::collections::HashMap::new()), None, None)
+                ),
+                _ => (Arc::new(std::collections::HashMap::new()), None, None),
             };
 
         Ok(Self {
@@ -824,6 +1222,7 @@ impl AppState {
             jwt_validator,
             jwt_signer,
             oauth_client,
+            login_issuers,
             registry_provider,
             oci_client,
             admin_users,
@@ -852,6 +1251,17 @@ impl AppState {
             deployment_defaults: deployment_defaults_opt,
             #[cfg(feature = "backend")]
             deployment_constraints: deployment_constraints_opt,
+            #[cfg(feature = "backend")]
+            env_decrypt_concurrency: settings
+                .deployment_controller
+                .as_ref()
+                .map_or(1, |c| c.env_decrypt_concurrency()),
+            #[cfg(feature = "backend")]
+            deployment_concurrency: settings
+                .deployment_controller
+                .as_ref()
+                .map(|c| c.concurrency_limits())
+                .unwrap_or_default(),
         })
     }
 }
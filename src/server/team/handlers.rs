@@ -1,7 +1,7 @@
 use super::fuzzy::find_similar_teams;
 use super::models::{
-    CreateTeamRequest, CreateTeamResponse, GetTeamParams, Team as ApiTeam, UpdateTeamRequest,
-    UpdateTeamResponse, UserInfo,
+    CreateTeamRequest, CreateTeamResponse, GetTeamParams, Team as ApiTeam, TeamProject,
+    UpdateTeamRequest, UpdateTeamResponse, UserInfo,
 };
 use crate::db::models::TeamRole;
 use crate::db::{projects as db_projects, service_accounts, teams as db_teams};
@@ -393,6 +393,50 @@ pub async fn list_teams(
     Ok(Json(api_teams))
 }
 
+pub async fn list_team_projects(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(id_or_name): Path<String>,
+    Query(params): Query<GetTeamParams>,
+) -> Result<Json<Vec<TeamProject>>, ServerError> {
+    let user = auth.user()?;
+    // Resolve team by ID or name
+    let team = resolve_team(&state, &id_or_name, params.by_id).await?;
+
+    // Only team members/owners and admins may see the team's projects
+    let is_admin = state.is_admin(&user.email);
+    let is_member = if !is_admin {
+        db_teams::is_member(&state.db_pool, team.id, user.id)
+            .await
+            .internal_err("Failed to check team membership")?
+    } else {
+        true // Admins bypass membership check
+    };
+
+    if !is_member {
+        return Err(ServerError::forbidden(format!(
+            "You must be a member of team '{}' to list its projects",
+            team.name
+        )));
+    }
+
+    let projects = db_projects::list_owned_by_team(&state.db_pool, team.id)
+        .await
+        .internal_err("Failed to list projects owned by team")?;
+
+    Ok(Json(
+        projects
+            .into_iter()
+            .map(|p| TeamProject {
+                id: p.id.to_string(),
+                name: p.name,
+                status: p.status.to_string(),
+                access_class: p.access_class,
+            })
+            .collect(),
+    ))
+}
+
 /// Query team by ID
 async fn query_team_by_id(
     state: &AppState,
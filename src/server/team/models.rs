@@ -57,3 +57,12 @@ pub struct GetTeamParams {
     #[serde(default)]
     pub by_id: bool,
 }
+
+// Project owned by a team, as returned by GET /teams/{id}/projects
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TeamProject {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub access_class: String,
+}
@@ -12,4 +12,5 @@ pub fn team_routes() -> Router<AppState> {
         .route("/teams/{id}", get(handlers::get_team))
         .route("/teams/{id}", put(handlers::update_team))
         .route("/teams/{id}", delete(handlers::delete_team))
+        .route("/teams/{id}/projects", get(handlers::list_team_projects))
 }
@@ -3,16 +3,18 @@ use axum::{
     http::StatusCode,
     Json,
 };
+use base64::{engine::general_purpose, Engine as _};
 use uuid::Uuid;
 
 use crate::db::{environments as db_environments, projects, service_accounts, users};
-use crate::server::auth::context::AuthContext;
+use crate::server::auth::context::{match_service_accounts, AuthContext};
 use crate::server::error::{ServerError, ServerErrorExt};
 use crate::server::project::handlers::{check_read_permission, check_write_permission};
 use crate::server::ssrf;
 use crate::server::state::AppState;
 use crate::server::workload_identity::models::{
-    CreateWorkloadIdentityRequest, ListWorkloadIdentitiesResponse, UpdateWorkloadIdentityRequest,
+    CreateWorkloadIdentityRequest, ListWorkloadIdentitiesResponse, TestWorkloadIdentityRequest,
+    TestWorkloadIdentityResponse, UpdateWorkloadIdentityRequest, WorkloadIdentityMatch,
     WorkloadIdentityResponse,
 };
 use std::collections::HashMap;
@@ -46,6 +48,23 @@ async fn resolve_env_names_to_ids(
     Ok(ids)
 }
 
+/// Decode a JWT's payload without verifying it, for reporting its claims.
+fn decode_unverified_claims(token: &str) -> Result<serde_json::Value, ServerError> {
+    let parts: Vec<&str> = token.split('.').collect();
+    let &[_, payload, _] = parts.as_slice() else {
+        return Err(ServerError::bad_request("Invalid JWT format"));
+    };
+    let decoded = general_purpose::URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| ServerError::bad_request("Invalid token encoding"))?;
+    let claims: serde_json::Value = serde_json::from_slice(&decoded)
+        .map_err(|_| ServerError::bad_request("Invalid token claims"))?;
+    if !claims.is_object() {
+        return Err(ServerError::bad_request("Invalid token claims"));
+    }
+    Ok(claims)
+}
+
 /// Verify that an OIDC issuer is reachable and has valid configuration.
 ///
 /// Includes SSRF protections: requires HTTPS, blocks private/internal IPs,
@@ -573,3 +592,100 @@ pub async fn delete_workload_identity(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// Check which service account of a project an OIDC token would authenticate as.
+///
+/// Runs the same signature verification and claim matching as service account
+/// authentication, but reports the outcome for every service account instead of
+/// rejecting the request, to help debug `--claim` configuration.
+pub async fn test_workload_identity(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(project_name): Path<String>,
+    Json(req): Json<TestWorkloadIdentityRequest>,
+) -> Result<Json<TestWorkloadIdentityResponse>, ServerError> {
+    let user = auth.user()?;
+
+    // Get project
+    let project = projects::find_by_name(&state.db_pool, &project_name)
+        .await
+        .internal_err("Failed to find project")?
+        .ok_or_else(|| ServerError::not_found("Project not found"))?;
+
+    // Check read permission
+    if !check_read_permission(&state, &project, user)
+        .await
+        .map_err(ServerError::internal)?
+    {
+        return Err(ServerError::not_found("Project not found"));
+    }
+
+    let token = req.token.trim();
+    let claims = decode_unverified_claims(token)?;
+    let issuer = claims
+        .get("iss")
+        .and_then(|iss| iss.as_str())
+        .ok_or_else(|| ServerError::bad_request("Token has no 'iss' claim"))?
+        .to_string();
+
+    let sas = service_accounts::find_by_project_and_issuer(&state.db_pool, project.id, &issuer)
+        .await
+        .internal_err("Failed to look up service accounts")?;
+
+    // Like the auth middleware, only fetch the issuer's JWKS if a service account
+    // uses it, so arbitrary tokens can't make the backend call arbitrary URLs
+    let token_error = if sas.is_empty() {
+        None
+    } else {
+        state
+            .jwt_validator
+            .validate_token(token, &issuer)
+            .await
+            .err()
+            .map(|e| format!("{:#}", e))
+    };
+
+    let mut results = Vec::with_capacity(sas.len());
+    for (sa, outcome) in match_service_accounts(&claims, &sas)? {
+        let sa_user = users::find_by_id(&state.db_pool, sa.user_id)
+            .await
+            .internal_err("Failed to find service account user")?
+            .ok_or_else(|| ServerError::internal("Service account user not found"))?;
+        let expected_claims: HashMap<String, String> = serde_json::from_value(sa.claims.clone())
+            .internal_err("Failed to deserialize claims")?;
+
+        results.push(WorkloadIdentityMatch {
+            id: sa.id.to_string(),
+            email: sa_user.email,
+            claims: expected_claims,
+            matched: outcome.is_ok(),
+            reason: outcome.err().map(|e| e.to_string()),
+        });
+    }
+
+    Ok(Json(TestWorkloadIdentityResponse {
+        issuer,
+        claims,
+        token_error,
+        service_accounts: results,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_unverified_claims() {
+        let payload = general_purpose::URL_SAFE_NO_PAD
+            .encode(r#"{"iss":"https://gitlab.com","project_path":"myorg/app"}"#);
+        let claims = decode_unverified_claims(&format!("e30.{}.sig", payload)).unwrap();
+        assert_eq!(claims["iss"], "https://gitlab.com");
+        assert_eq!(claims["project_path"], "myorg/app");
+
+        assert!(decode_unverified_claims("not-a-jwt").is_err());
+        assert!(decode_unverified_claims("e30.!!!.sig").is_err());
+        let array = general_purpose::URL_SAFE_NO_PAD.encode("[1]");
+        assert!(decode_unverified_claims(&format!("e30.{}.sig", array)).is_err());
+    }
+}
@@ -40,3 +40,36 @@ pub struct WorkloadIdentityResponse {
 pub struct ListWorkloadIdentitiesResponse {
     pub workload_identities: Vec<WorkloadIdentityResponse>,
 }
+
+/// Request to check which service account an OIDC token would authenticate as
+#[derive(Debug, Deserialize)]
+pub struct TestWorkloadIdentityRequest {
+    pub token: String,
+}
+
+/// Claim-matching outcome for a single service account
+#[derive(Debug, Serialize)]
+pub struct WorkloadIdentityMatch {
+    pub id: String,
+    pub email: String,
+    pub claims: HashMap<String, String>,
+    pub matched: bool,
+    /// Why the token's claims don't match (absent when matched)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Response for testing an OIDC token against a project's service accounts
+#[derive(Debug, Serialize)]
+pub struct TestWorkloadIdentityResponse {
+    /// The token's `iss` claim
+    pub issuer: String,
+    /// The token's claims, as decoded from its payload
+    pub claims: serde_json::Value,
+    /// Why the token's signature or expiry failed verification. Only checked when
+    /// a service account uses the token's issuer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_error: Option<String>,
+    /// Outcome for each service account configured for the token's issuer
+    pub service_accounts: Vec<WorkloadIdentityMatch>,
+}
@@ -12,6 +12,10 @@ pub fn routes() -> Router<AppState> {
             "/projects/{project_name}/workload-identities",
             post(handlers::create_workload_identity).get(handlers::list_workload_identities),
         )
+        .route(
+            "/projects/{project_name}/workload-identities/test",
+            post(handlers::test_workload_identity),
+        )
         .route(
             "/projects/{project_name}/workload-identities/{sa_id}",
             get(handlers::get_workload_identity)